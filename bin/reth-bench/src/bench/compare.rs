@@ -0,0 +1,557 @@
+//! Command for comparing the results of two benchmark runs.
+//!
+//! Reads the `combined_latency.csv` files written by `new-payload-fcu` and `replay-payloads`,
+//! aligns the runs by block number and reports per-block and aggregate deltas with confidence
+//! intervals.
+
+use crate::bench::output::COMBINED_OUTPUT_SUFFIX;
+use clap::Parser;
+use eyre::Context;
+use reth_cli_runner::CliContext;
+use reth_primitives_traits::constants::GIGAGAS;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+use tracing::{info, warn};
+
+/// File name of the Markdown comparison report.
+const MARKDOWN_REPORT: &str = "comparison.md";
+
+/// File name of the JSON comparison report.
+const JSON_REPORT: &str = "comparison.json";
+
+/// z-score of a two-sided 95% confidence interval.
+const Z_95: f64 = 1.96;
+
+/// Number of blocks with the largest latency regressions listed in the Markdown report.
+const TOP_BLOCKS: usize = 10;
+
+/// `reth-bench compare` command
+///
+/// Compares the output of a baseline and a candidate benchmark run, and writes a Markdown and a
+/// JSON report.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// Output directory of the baseline run.
+    #[arg(value_name = "BASELINE_DIR")]
+    baseline: PathBuf,
+
+    /// Output directory of the candidate run.
+    #[arg(value_name = "CANDIDATE_DIR")]
+    candidate: PathBuf,
+
+    /// Relative change in percent above which a metric is flagged as a regression.
+    #[arg(long, value_name = "PERCENT", default_value_t = 5.0)]
+    threshold: f64,
+
+    /// Directory to write the reports to. Defaults to the candidate directory.
+    #[arg(long, value_name = "OUTPUT_DIR")]
+    output: Option<PathBuf>,
+
+    /// Exit with an error if any metric regressed above the threshold.
+    #[arg(long, default_value_t = false)]
+    fail_on_regression: bool,
+}
+
+impl Command {
+    /// Execute the `compare` command.
+    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+        let baseline = read_run(&self.baseline)?;
+        let candidate = read_run(&self.candidate)?;
+
+        let report = ComparisonReport::new(
+            self.baseline.clone(),
+            self.candidate.clone(),
+            &baseline,
+            &candidate,
+            self.threshold,
+        )?;
+
+        let output = self.output.as_ref().unwrap_or(&self.candidate);
+        std::fs::create_dir_all(output)?;
+        let markdown_path = output.join(MARKDOWN_REPORT);
+        reth_fs_util::write(&markdown_path, report.to_markdown())?;
+        let json_path = output.join(JSON_REPORT);
+        reth_fs_util::write(&json_path, serde_json::to_string_pretty(&report)?)?;
+
+        info!(
+            target: "reth-bench",
+            blocks = report.blocks,
+            regressions = report.regressions.len(),
+            markdown = %markdown_path.display(),
+            json = %json_path.display(),
+            "Wrote comparison report"
+        );
+        for regression in &report.regressions {
+            warn!(
+                target: "reth-bench",
+                metric = %regression,
+                threshold = self.threshold,
+                "Metric regressed above the threshold"
+            );
+        }
+
+        if self.fail_on_regression && !report.regressions.is_empty() {
+            eyre::bail!("{} metrics regressed above {}%", report.regressions.len(), self.threshold)
+        }
+
+        Ok(())
+    }
+}
+
+/// A row of the `combined_latency.csv` output. Durations are in microseconds.
+#[derive(Debug, Clone, Deserialize)]
+struct CombinedRow {
+    block_number: u64,
+    gas_used: u64,
+    new_payload_latency: u64,
+    persistence_wait: u64,
+}
+
+impl CombinedRow {
+    fn gas_per_second(&self) -> f64 {
+        self.gas_used as f64 / (self.new_payload_latency as f64 / 1_000_000.0)
+    }
+}
+
+/// Reads the combined latency output of a run, keyed by block number.
+fn read_run(dir: &Path) -> eyre::Result<BTreeMap<u64, CombinedRow>> {
+    let path = dir.join(COMBINED_OUTPUT_SUFFIX);
+    let mut reader = csv::Reader::from_path(&path)
+        .wrap_err_with(|| format!("failed to open {}", path.display()))?;
+
+    let mut rows = BTreeMap::new();
+    for row in reader.deserialize() {
+        let row: CombinedRow =
+            row.wrap_err_with(|| format!("failed to parse {}", path.display()))?;
+        if rows.insert(row.block_number, row).is_some() {
+            warn!(
+                target: "reth-bench",
+                path = %path.display(),
+                "Duplicate block, using the last row"
+            );
+        }
+    }
+    Ok(rows)
+}
+
+/// Whether higher or lower values of a metric are better.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Better {
+    Higher,
+    Lower,
+}
+
+/// Comparison of an aggregate metric between the two runs.
+#[derive(Debug, Clone, Serialize)]
+struct MetricComparison {
+    /// Name of the metric.
+    name: String,
+    /// Unit of the baseline and candidate values.
+    unit: &'static str,
+    /// Value of the baseline run.
+    baseline: f64,
+    /// Value of the candidate run.
+    candidate: f64,
+    /// Relative change in percent, `None` if the baseline is zero.
+    change_percent: Option<f64>,
+    /// 95% confidence interval of the relative change in percent, for metrics that are averages
+    /// of paired per-block values.
+    confidence_interval_percent: Option<(f64, f64)>,
+    /// Whether the change is a regression above the threshold.
+    regression: bool,
+}
+
+impl MetricComparison {
+    /// Compares the mean of a per-block metric, with a confidence interval of the mean of the
+    /// paired per-block differences.
+    fn mean(
+        name: &str,
+        unit: &'static str,
+        better: Better,
+        threshold: f64,
+        pairs: &[(f64, f64)],
+    ) -> Self {
+        let n = pairs.len() as f64;
+        let baseline = pairs.iter().map(|(b, _)| b).sum::<f64>() / n;
+        let candidate = pairs.iter().map(|(_, c)| c).sum::<f64>() / n;
+
+        let mean_diff = candidate - baseline;
+        let confidence_interval_percent = (pairs.len() > 1 && baseline != 0.0).then(|| {
+            let variance =
+                pairs.iter().map(|(b, c)| (c - b - mean_diff).powi(2)).sum::<f64>() / (n - 1.0);
+            let half_width = Z_95 * (variance / n).sqrt();
+            (
+                (mean_diff - half_width) / baseline * 100.0,
+                (mean_diff + half_width) / baseline * 100.0,
+            )
+        });
+
+        Self::new(name, unit, better, threshold, baseline, candidate, confidence_interval_percent)
+    }
+
+    /// Compares the `p`-th percentile of a per-block metric.
+    fn percentile(
+        name: &str,
+        unit: &'static str,
+        better: Better,
+        threshold: f64,
+        pairs: &[(f64, f64)],
+        p: f64,
+    ) -> Self {
+        let baseline = percentile(pairs.iter().map(|(b, _)| *b).collect(), p);
+        let candidate = percentile(pairs.iter().map(|(_, c)| *c).collect(), p);
+        Self::new(name, unit, better, threshold, baseline, candidate, None)
+    }
+
+    /// Creates the comparison, flagging it as a regression if the metric got worse by more than
+    /// `threshold` percent and the confidence interval, if any, does not include zero.
+    fn new(
+        name: &str,
+        unit: &'static str,
+        better: Better,
+        threshold: f64,
+        baseline: f64,
+        candidate: f64,
+        confidence_interval_percent: Option<(f64, f64)>,
+    ) -> Self {
+        let change_percent = relative_change(baseline, candidate);
+        let regression = change_percent.is_some_and(|change| {
+            let ci = confidence_interval_percent;
+            let (worse, significant) = match better {
+                Better::Higher => (-change, ci.is_none_or(|(_, high)| high < 0.0)),
+                Better::Lower => (change, ci.is_none_or(|(low, _)| low > 0.0)),
+            };
+            worse > threshold && significant
+        });
+
+        Self {
+            name: name.to_string(),
+            unit,
+            baseline,
+            candidate,
+            change_percent,
+            confidence_interval_percent,
+            regression,
+        }
+    }
+}
+
+/// Per-block comparison of the two runs.
+#[derive(Debug, Clone, Serialize)]
+struct BlockDelta {
+    block_number: u64,
+    gas_used: u64,
+    baseline_latency_us: u64,
+    candidate_latency_us: u64,
+    latency_change_percent: Option<f64>,
+    gas_per_second_change_percent: Option<f64>,
+    baseline_persistence_wait_us: u64,
+    candidate_persistence_wait_us: u64,
+}
+
+/// Comparison report of a baseline and a candidate run.
+#[derive(Debug, Clone, Serialize)]
+struct ComparisonReport {
+    baseline: PathBuf,
+    candidate: PathBuf,
+    /// Number of blocks present in both runs.
+    blocks: usize,
+    /// Number of blocks only present in the baseline run.
+    baseline_only_blocks: usize,
+    /// Number of blocks only present in the candidate run.
+    candidate_only_blocks: usize,
+    threshold_percent: f64,
+    metrics: Vec<MetricComparison>,
+    /// Names of the metrics that regressed above the threshold.
+    regressions: Vec<String>,
+    block_deltas: Vec<BlockDelta>,
+}
+
+impl ComparisonReport {
+    fn new(
+        baseline_dir: PathBuf,
+        candidate_dir: PathBuf,
+        baseline: &BTreeMap<u64, CombinedRow>,
+        candidate: &BTreeMap<u64, CombinedRow>,
+        threshold: f64,
+    ) -> eyre::Result<Self> {
+        let aligned = baseline
+            .iter()
+            .filter_map(|(number, b)| candidate.get(number).map(|c| (b, c)))
+            .collect::<Vec<_>>();
+        eyre::ensure!(!aligned.is_empty(), "the runs have no blocks in common");
+
+        let gas_per_second = aligned
+            .iter()
+            .map(|(b, c)| {
+                (b.gas_per_second() / GIGAGAS as f64, c.gas_per_second() / GIGAGAS as f64)
+            })
+            .collect::<Vec<_>>();
+        let latency = aligned
+            .iter()
+            .map(|(b, c)| {
+                (b.new_payload_latency as f64 / 1000.0, c.new_payload_latency as f64 / 1000.0)
+            })
+            .collect::<Vec<_>>();
+        let persistence_wait = aligned
+            .iter()
+            .map(|(b, c)| (b.persistence_wait as f64 / 1000.0, c.persistence_wait as f64 / 1000.0))
+            .collect::<Vec<_>>();
+
+        let metrics = vec![
+            MetricComparison::mean("gas/s", "Ggas/s", Better::Higher, threshold, &gas_per_second),
+            MetricComparison::mean("newPayload latency", "ms", Better::Lower, threshold, &latency),
+            MetricComparison::percentile(
+                "newPayload latency p50",
+                "ms",
+                Better::Lower,
+                threshold,
+                &latency,
+                50.0,
+            ),
+            MetricComparison::percentile(
+                "newPayload latency p90",
+                "ms",
+                Better::Lower,
+                threshold,
+                &latency,
+                90.0,
+            ),
+            MetricComparison::percentile(
+                "newPayload latency p99",
+                "ms",
+                Better::Lower,
+                threshold,
+                &latency,
+                99.0,
+            ),
+            MetricComparison::mean(
+                "persistence wait",
+                "ms",
+                Better::Lower,
+                threshold,
+                &persistence_wait,
+            ),
+            MetricComparison::percentile(
+                "persistence wait p99",
+                "ms",
+                Better::Lower,
+                threshold,
+                &persistence_wait,
+                99.0,
+            ),
+        ];
+        let regressions =
+            metrics.iter().filter(|metric| metric.regression).map(|m| m.name.clone()).collect();
+
+        let block_deltas = aligned
+            .iter()
+            .map(|(b, c)| BlockDelta {
+                block_number: b.block_number,
+                gas_used: b.gas_used,
+                baseline_latency_us: b.new_payload_latency,
+                candidate_latency_us: c.new_payload_latency,
+                latency_change_percent: relative_change(
+                    b.new_payload_latency as f64,
+                    c.new_payload_latency as f64,
+                ),
+                gas_per_second_change_percent: relative_change(
+                    b.gas_per_second(),
+                    c.gas_per_second(),
+                ),
+                baseline_persistence_wait_us: b.persistence_wait,
+                candidate_persistence_wait_us: c.persistence_wait,
+            })
+            .collect();
+
+        Ok(Self {
+            baseline: baseline_dir,
+            candidate: candidate_dir,
+            blocks: aligned.len(),
+            baseline_only_blocks: baseline.len() - aligned.len(),
+            candidate_only_blocks: candidate.len() - aligned.len(),
+            threshold_percent: threshold,
+            metrics,
+            regressions,
+            block_deltas,
+        })
+    }
+
+    /// Renders the report as Markdown.
+    fn to_markdown(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "## reth-bench comparison\n");
+        let _ = writeln!(
+            out,
+            "Baseline `{}`, candidate `{}`: {} blocks compared ({} only in baseline, {} only in \
+             candidate).\n",
+            self.baseline.display(),
+            self.candidate.display(),
+            self.blocks,
+            self.baseline_only_blocks,
+            self.candidate_only_blocks,
+        );
+
+        let _ = writeln!(out, "| Metric | Baseline | Candidate | Change | 95% CI |");
+        let _ = writeln!(out, "| --- | ---: | ---: | ---: | --- |");
+        for metric in &self.metrics {
+            let ci = metric.confidence_interval_percent.map_or_else(
+                || "-".to_string(),
+                |(low, high)| {
+                    format!("[{}, {}]", format_percent(Some(low)), format_percent(Some(high)))
+                },
+            );
+            let _ = writeln!(
+                out,
+                "| {}{} | {:.4} {} | {:.4} {} | {} | {} |",
+                metric.name,
+                if metric.regression { " :warning:" } else { "" },
+                metric.baseline,
+                metric.unit,
+                metric.candidate,
+                metric.unit,
+                format_percent(metric.change_percent),
+                ci,
+            );
+        }
+        let _ = writeln!(out);
+
+        if self.regressions.is_empty() {
+            let _ = writeln!(out, "No regressions above {}%.", self.threshold_percent);
+        } else {
+            let _ = writeln!(
+                out,
+                "**Regressions above {}%:** {}",
+                self.threshold_percent,
+                self.regressions.join(", ")
+            );
+        }
+
+        let mut worst = self
+            .block_deltas
+            .iter()
+            .filter(|delta| delta.latency_change_percent.is_some_and(|change| change > 0.0))
+            .collect::<Vec<_>>();
+        worst.sort_by(|a, b| {
+            let change = |delta: &BlockDelta| delta.latency_change_percent.unwrap_or_default();
+            change(b).total_cmp(&change(a))
+        });
+        if !worst.is_empty() {
+            let _ = writeln!(out, "\n### Largest per-block latency regressions\n");
+            let _ = writeln!(out, "| Block | Gas used | Baseline | Candidate | Change |");
+            let _ = writeln!(out, "| ---: | ---: | ---: | ---: | ---: |");
+            for delta in worst.into_iter().take(TOP_BLOCKS) {
+                let _ = writeln!(
+                    out,
+                    "| {} | {} | {:.3} ms | {:.3} ms | {} |",
+                    delta.block_number,
+                    delta.gas_used,
+                    delta.baseline_latency_us as f64 / 1000.0,
+                    delta.candidate_latency_us as f64 / 1000.0,
+                    format_percent(delta.latency_change_percent),
+                );
+            }
+        }
+
+        out
+    }
+}
+
+/// Returns the relative change from `baseline` to `candidate` in percent, or `None` if the
+/// baseline is zero.
+fn relative_change(baseline: f64, candidate: f64) -> Option<f64> {
+    (baseline != 0.0).then(|| (candidate - baseline) / baseline * 100.0)
+}
+
+/// Returns the `p`-th percentile of the values using the nearest-rank method.
+fn percentile(mut values: Vec<f64>, p: f64) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let rank = ((p / 100.0) * values.len() as f64).ceil() as usize;
+    values[rank.clamp(1, values.len()) - 1]
+}
+
+fn format_percent(percent: Option<f64>) -> String {
+    percent.map_or_else(|| "n/a".to_string(), |percent| format!("{percent:+.2}%"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(latencies: &[u64]) -> BTreeMap<u64, CombinedRow> {
+        latencies
+            .iter()
+            .enumerate()
+            .map(|(number, latency)| {
+                let row = CombinedRow {
+                    block_number: number as u64,
+                    gas_used: 30_000_000,
+                    new_payload_latency: *latency,
+                    persistence_wait: 0,
+                };
+                (number as u64, row)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn percentile_nearest_rank() {
+        let values = (1..=100).map(f64::from).collect::<Vec<_>>();
+        assert_eq!(percentile(values.clone(), 50.0), 50.0);
+        assert_eq!(percentile(values.clone(), 99.0), 99.0);
+        assert_eq!(percentile(values, 100.0), 100.0);
+        assert_eq!(percentile(vec![7.0], 1.0), 7.0);
+    }
+
+    #[test]
+    fn flags_latency_regression() {
+        let baseline = run(&[10_000, 11_000, 9_000, 10_500, 9_500, 10_000]);
+        // 20% slower on every block, and one block only present in the candidate run
+        let candidate = run(&[12_000, 13_200, 10_800, 12_600, 11_400, 12_000, 5_000]);
+
+        let report = ComparisonReport::new(
+            PathBuf::from("baseline"),
+            PathBuf::from("candidate"),
+            &baseline,
+            &candidate,
+            5.0,
+        )
+        .unwrap();
+
+        assert_eq!(report.blocks, 6);
+        assert_eq!(report.candidate_only_blocks, 1);
+        assert!(report.regressions.contains(&"gas/s".to_string()));
+        assert!(report.regressions.contains(&"newPayload latency".to_string()));
+        // persistence wait is zero in both runs
+        assert!(!report.regressions.contains(&"persistence wait".to_string()));
+
+        let latency = &report.metrics[1];
+        assert!((latency.change_percent.unwrap() - 20.0).abs() < 1e-9);
+        let (low, high) = latency.confidence_interval_percent.unwrap();
+        assert!(low <= 20.0 && 20.0 <= high);
+
+        let markdown = report.to_markdown();
+        assert!(markdown.contains("**Regressions above 5%:**"));
+        assert!(markdown.contains("### Largest per-block latency regressions"));
+    }
+
+    #[test]
+    fn no_regression_within_threshold() {
+        let baseline = run(&[10_000, 11_000, 9_000]);
+        let candidate = run(&[10_100, 11_000, 9_050]);
+        let report = ComparisonReport::new(
+            PathBuf::from("baseline"),
+            PathBuf::from("candidate"),
+            &baseline,
+            &candidate,
+            5.0,
+        )
+        .unwrap();
+        assert!(report.regressions.is_empty());
+        assert!(report.to_markdown().contains("No regressions above 5%."));
+    }
+}
@@ -1,9 +1,10 @@
 //! Command for generating large blocks by merging transactions from consecutive real blocks.
 //!
-//! This command fetches consecutive blocks from an RPC until a target gas usage is reached,
-//! takes block 0 as the "base" payload, concatenates transactions from subsequent blocks,
-//! and saves the result to disk as a [`BigBlockPayload`] JSON file containing the merged
-//! [`ExecutionData`] and environment switches at each block boundary.
+//! This command fetches consecutive blocks from an RPC, or reads them from a local reth datadir
+//! or a directory of era1 files, until a target gas usage is reached, takes block 0 as the "base"
+//! payload, concatenates transactions from subsequent blocks, and saves the result to disk as a
+//! [`BigBlockPayload`] JSON file containing the merged [`ExecutionData`] and environment switches
+//! at each block boundary.
 
 use alloy_consensus::{TxEnvelope, TxReceipt};
 use alloy_eips::{
@@ -24,14 +25,22 @@ use eyre::Context;
 use reth_chainspec::EthChainSpec;
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_runner::CliContext;
+use reth_db::DatabaseEnv;
 use reth_engine_primitives::BigBlockData;
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
-use reth_ethereum_primitives::Receipt;
+use reth_ethereum_primitives::{Block, EthPrimitives, Receipt};
+use reth_node_api::NodeTypesWithDBAdapter;
+use reth_node_ethereum::EthereumNode;
 use reth_primitives_traits::proofs;
+use reth_provider::{
+    providers::{Era1History, ProviderNodeTypes, ReadOnlyConfig},
+    BlockReader, ProviderFactory, ReceiptProvider, TransactionVariant,
+};
 use serde::{Deserialize, Serialize};
 use std::{
     collections::{HashMap, HashSet},
     future::Future,
+    path::PathBuf,
 };
 use tracing::{info, warn};
 
@@ -230,15 +239,172 @@ pub struct BigBlockPayload {
     pub block_access_list: Option<BlockAccessList>,
 }
 
+/// Source of the real blocks that are merged into big blocks.
+#[derive(Debug)]
+enum BlockSource {
+    /// Blocks and receipts are fetched from an RPC.
+    Rpc(RootProvider<AnyNetwork>),
+    /// Blocks and receipts are read from a local reth datadir, falling back to era1 files for
+    /// blocks that are not in the datadir.
+    Local {
+        factory: Option<ProviderFactory<NodeTypesWithDBAdapter<EthereumNode, DatabaseEnv>>>,
+        era1_history: Option<Era1History>,
+    },
+}
+
+impl BlockSource {
+    /// Returns the block with the given number as [`ExecutionData`] together with its receipts.
+    ///
+    /// Returns `Ok(None)` if the block is not available, e.g. because it is past the chain tip.
+    async fn fetch_block(
+        &self,
+        block_number: u64,
+    ) -> eyre::Result<Option<(ExecutionData, Vec<Receipt>)>> {
+        match self {
+            Self::Rpc(provider) => fetch_rpc_block(provider, block_number).await,
+            Self::Local { factory, era1_history } => {
+                read_local_block(factory.as_ref(), era1_history.as_ref(), block_number)
+            }
+        }
+    }
+
+    /// Returns the block access list of the block with the given number.
+    async fn fetch_block_access_list(&self, block_number: u64) -> eyre::Result<BlockAccessList> {
+        match self {
+            Self::Rpc(provider) => fetch_block_access_list(provider, block_number).await,
+            Self::Local { .. } => {
+                Err(eyre::eyre!("block access lists can only be fetched with --rpc-url"))
+            }
+        }
+    }
+}
+
+/// Fetches a block and its receipts from an RPC.
+async fn fetch_rpc_block(
+    provider: &RootProvider<AnyNetwork>,
+    block_number: u64,
+) -> eyre::Result<Option<(ExecutionData, Vec<Receipt>)>> {
+    let (rpc_block, receipts) = match tokio::try_join!(
+        provider.get_block_by_number(block_number.into()).full(),
+        provider.get_block_receipts(block_number.into()),
+    )? {
+        (Some(block), Some(receipts)) => (block, receipts),
+        (None, _) | (_, None) => return Ok(None),
+    };
+
+    // Convert RPC receipts to consensus receipts
+    let consensus_receipts: Vec<Receipt> = receipts
+        .iter()
+        .map(|r| {
+            let inner = &r.inner.inner.inner;
+            let tx_type = r.inner.inner.r#type.try_into().unwrap_or_default();
+            Receipt {
+                tx_type,
+                success: inner.receipt.status.coerce_status(),
+                cumulative_gas_used: inner.receipt.cumulative_gas_used,
+                logs: inner
+                    .receipt
+                    .logs
+                    .iter()
+                    .map(|log| alloy_primitives::Log {
+                        address: log.inner.address,
+                        data: log.inner.data.clone(),
+                    })
+                    .collect(),
+            }
+        })
+        .collect();
+
+    // Convert to consensus block
+    let block = rpc_block
+        .into_inner()
+        .map_header(|header| header.map(|h| h.into_header_with_defaults()))
+        .try_map_transactions(|tx| -> eyre::Result<TxEnvelope> {
+            tx.try_into().map_err(|_| eyre::eyre!("unsupported tx type"))
+        })?
+        .into_consensus();
+
+    // Convert to ExecutionData
+    let (payload, sidecar) = ExecutionPayload::from_block_slow(&block);
+    Ok(Some((ExecutionData { payload, sidecar }, consensus_receipts)))
+}
+
+/// Reads a block and its receipts from a local datadir, or from era1 files if the block or its
+/// receipts are not in the datadir.
+fn read_local_block<N>(
+    factory: Option<&ProviderFactory<N>>,
+    era1_history: Option<&Era1History>,
+    block_number: u64,
+) -> eyre::Result<Option<(ExecutionData, Vec<Receipt>)>>
+where
+    N: ProviderNodeTypes<Primitives = EthPrimitives>,
+{
+    if let Some(factory) = factory &&
+        let Some(block) =
+            factory.recovered_block(block_number.into(), TransactionVariant::NoHash)?
+    {
+        // Pruned receipts are missing from the datadir, but may still be in the era1 files
+        let receipts = match factory
+            .receipts_by_block(block_number.into())?
+            .filter(|receipts| receipts.len() == block.body().transactions.len())
+        {
+            Some(receipts) => receipts,
+            None => era1_history
+                .map(|era1_history| era1_history.receipts::<Receipt>(block_number))
+                .transpose()?
+                .flatten()
+                .ok_or_else(|| eyre::eyre!("Receipts not found for block {block_number}"))?,
+        };
+
+        // Use the block hash stored in the datadir instead of rehashing the header
+        let (payload, sidecar) =
+            ExecutionPayload::from_block_unchecked(block.hash(), &block.into_block());
+        return Ok(Some((ExecutionData { payload, sidecar }, receipts)))
+    }
+
+    if let Some(era1_history) = era1_history &&
+        let Some(block) = era1_history.block::<Block>(block_number)?
+    {
+        let receipts = era1_history
+            .receipts::<Receipt>(block_number)?
+            .ok_or_else(|| eyre::eyre!("Receipts not found for block {block_number}"))?;
+
+        let (payload, sidecar) = ExecutionPayload::from_block_slow(&block);
+        return Ok(Some((ExecutionData { payload, sidecar }, receipts)))
+    }
+
+    Ok(None)
+}
+
 /// `reth bench generate-big-block` command
 ///
-/// Generates a large block by fetching consecutive blocks from an RPC, merging their
-/// transactions into a single payload, and saving the result to disk.
+/// Generates a large block by fetching consecutive blocks from an RPC, or reading them from a
+/// local datadir or era1 files, merging their transactions into a single payload, and saving the
+/// result to disk.
 #[derive(Debug, Parser)]
 pub struct Command {
     /// The RPC URL to use for fetching blocks.
-    #[arg(long, value_name = "RPC_URL")]
-    rpc_url: String,
+    #[arg(
+        long,
+        value_name = "RPC_URL",
+        required_unless_present_any = ["datadir", "era1_dir"],
+        conflicts_with_all = ["datadir", "era1_dir"]
+    )]
+    rpc_url: Option<String>,
+
+    /// Read blocks, senders and receipts from a local reth datadir instead of an RPC.
+    ///
+    /// This is the chain-specific directory that contains the `db` and `static_files`
+    /// directories, e.g. `~/.local/share/reth/mainnet`.
+    #[arg(long, value_name = "DATA_DIR")]
+    datadir: Option<PathBuf>,
+
+    /// Read blocks and receipts from a directory of era1 files instead of an RPC.
+    ///
+    /// If used together with `--datadir`, the era1 files are only used for blocks that are not
+    /// in the datadir, or whose receipts were pruned from it.
+    #[arg(long = "era1-dir", value_name = "ERA1_DIR")]
+    era1_dir: Option<PathBuf>,
 
     /// The chain name or path to a chain spec JSON file.
     #[arg(long, value_name = "CHAIN", default_value = "mainnet")]
@@ -268,13 +434,13 @@ pub struct Command {
 
     /// Query `eth_getBlockAccessListByBlockNumber` for each fetched block and persist
     /// the flattened BAL on the stored payload.
-    #[arg(long, default_value_t = false)]
+    #[arg(long, default_value_t = false, requires = "rpc_url")]
     bal: bool,
 }
 
 impl Command {
     /// Execute the `generate-big-block` command.
-    pub async fn execute(self, _ctx: CliContext) -> eyre::Result<()> {
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
         if self.target_gas == 0 {
             return Err(eyre::eyre!("--target-gas must be greater than 0"));
         }
@@ -302,11 +468,29 @@ impl Command {
             format!("Failed to create output directory: {:?}", self.output_dir)
         })?;
 
-        // Set up RPC provider
-        let client = ClientBuilder::default()
-            .layer(alloy_transport::layers::RetryBackoffLayer::new(10, 800, u64::MAX))
-            .http(self.rpc_url.parse()?);
-        let provider = RootProvider::<AnyNetwork>::new(client);
+        let source = if let Some(rpc_url) = &self.rpc_url {
+            // Set up RPC provider
+            let client = ClientBuilder::default()
+                .layer(alloy_transport::layers::RetryBackoffLayer::new(10, 800, u64::MAX))
+                .http(rpc_url.parse()?);
+            BlockSource::Rpc(RootProvider::<AnyNetwork>::new(client))
+        } else {
+            let factory = self
+                .datadir
+                .as_ref()
+                .map(|datadir| {
+                    EthereumNode::provider_factory_builder()
+                        .open_read_only(
+                            chain_spec.clone(),
+                            ReadOnlyConfig::from_datadir(datadir),
+                            ctx.task_executor.clone(),
+                        )
+                        .wrap_err_with(|| format!("Failed to open datadir: {}", datadir.display()))
+                })
+                .transpose()?;
+            let era1_history = self.era1_dir.as_ref().map(Era1History::new).transpose()?;
+            BlockSource::Local { factory, era1_history }
+        };
 
         let mut prev_big_block_hash: Option<B256> = None;
         let mut accumulated_block_hashes: Vec<(u64, B256)> = Vec::new();
@@ -339,69 +523,26 @@ impl Command {
                 let block_number = next_block;
                 info!(target: "reth-bench", block_number, big_block = big_block_idx, "Fetching block");
 
-                let fetch_result = tokio::try_join!(
-                    provider.get_block_by_number(block_number.into()).full(),
-                    provider.get_block_receipts(block_number.into()),
-                );
-
-                let (rpc_block, receipts) = match fetch_result {
-                    Ok((Some(block), Some(receipts))) => (block, receipts),
-                    Ok((None, _) | (_, None)) => {
-                        warn!(
-                            target: "reth-bench",
-                            block_number,
-                            "Block not found — reached chain tip"
-                        );
-                        reached_chain_tip = true;
-                        break;
-                    }
-                    Err(e) => return Err(e.into()),
+                let Some((execution_data, consensus_receipts)) =
+                    source.fetch_block(block_number).await?
+                else {
+                    warn!(
+                        target: "reth-bench",
+                        block_number,
+                        "Block not found — reached chain tip"
+                    );
+                    reached_chain_tip = true;
+                    break;
                 };
 
                 let block_access_list = if self.bal {
-                    Some(fetch_block_access_list(&provider, block_number).await.wrap_err_with(
+                    Some(source.fetch_block_access_list(block_number).await.wrap_err_with(
                         || format!("Failed to fetch BAL for block {block_number}"),
                     )?)
                 } else {
                     None
                 };
 
-                // Convert RPC receipts to consensus receipts
-                let consensus_receipts: Vec<Receipt> = receipts
-                    .iter()
-                    .map(|r| {
-                        let inner = &r.inner.inner.inner;
-                        let tx_type = r.inner.inner.r#type.try_into().unwrap_or_default();
-                        Receipt {
-                            tx_type,
-                            success: inner.receipt.status.coerce_status(),
-                            cumulative_gas_used: inner.receipt.cumulative_gas_used,
-                            logs: inner
-                                .receipt
-                                .logs
-                                .iter()
-                                .map(|log| alloy_primitives::Log {
-                                    address: log.inner.address,
-                                    data: log.inner.data.clone(),
-                                })
-                                .collect(),
-                        }
-                    })
-                    .collect();
-
-                // Convert to consensus block
-                let block = rpc_block
-                    .into_inner()
-                    .map_header(|header| header.map(|h| h.into_header_with_defaults()))
-                    .try_map_transactions(|tx| -> eyre::Result<TxEnvelope> {
-                        tx.try_into().map_err(|_| eyre::eyre!("unsupported tx type"))
-                    })?
-                    .into_consensus();
-
-                // Convert to ExecutionData
-                let (payload, sidecar) = ExecutionPayload::from_block_slow(&block);
-                let execution_data = ExecutionData { payload, sidecar };
-
                 let block_gas = execution_data.payload.as_v1().gas_used;
                 let block_blob_gas =
                     execution_data.payload.as_v3().map(|v3| v3.blob_gas_used).unwrap_or(0);
@@ -766,6 +907,81 @@ mod tests {
     use super::*;
     use alloy_eips::eip7928::{BalanceChange, CodeChange, NonceChange, StorageChange};
     use alloy_primitives::{Address, U256};
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, write_era1_file},
+        BlockWriter, StorageSettings, StorageSettingsCache,
+    };
+    use reth_testing_utils::generators::{self, random_block, BlockParams};
+
+    #[test]
+    fn read_pruned_receipts_from_era1() {
+        let era1_dir = tempfile::tempdir().unwrap();
+        write_era1_file(era1_dir.path(), 0, 4, 2);
+        let era1_history = Era1History::new(era1_dir.path()).unwrap();
+
+        // The receipts of the blocks are not written, like in a datadir with pruned receipts
+        let factory = create_test_provider_factory();
+        factory.set_storage_settings_cache(StorageSettings::v1());
+        let mut rng = generators::rng();
+        let blocks = (0..=2)
+            .map(|number| {
+                random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { tx_count: Some(2), ..Default::default() },
+                )
+            })
+            .collect::<Vec<_>>();
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider_rw.insert_block(&block.clone().try_recover().unwrap()).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        let (data, receipts) =
+            read_local_block(Some(&factory), Some(&era1_history), 1).unwrap().unwrap();
+        assert_eq!(data.payload.block_hash(), blocks[1].hash());
+        assert_eq!(
+            receipts.iter().map(|receipt| receipt.cumulative_gas_used).collect::<Vec<_>>(),
+            [1000, 1001]
+        );
+
+        // Blocks that are not in the datadir are read from the era1 files
+        let (data, receipts) =
+            read_local_block(Some(&factory), Some(&era1_history), 3).unwrap().unwrap();
+        assert_eq!(data.payload.block_number(), 3);
+        assert_eq!(receipts.len(), 2);
+
+        // Without era1 files the missing receipts are an error
+        assert!(read_local_block(Some(&factory), None, 1).is_err());
+        assert!(read_local_block(Some(&factory), Some(&era1_history), 4).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_block_sources() {
+        let args =
+            ["reth-bench", "--from-block", "1", "--target-gas", "100M", "--output-dir", "out"];
+
+        let command =
+            Command::try_parse_from(args.iter().chain(&["--datadir", "datadir"])).unwrap();
+        assert_eq!(command.datadir, Some(PathBuf::from("datadir")));
+        assert!(command.rpc_url.is_none());
+
+        // a source is required
+        assert!(Command::try_parse_from(args).is_err());
+        // RPC and local sources are exclusive
+        assert!(Command::try_parse_from(args.iter().chain(&[
+            "--rpc-url",
+            "http://localhost:8545",
+            "--era1-dir",
+            "era1"
+        ]))
+        .is_err());
+        // block access lists are only available over RPC
+        assert!(
+            Command::try_parse_from(args.iter().chain(&["--datadir", "datadir", "--bal"])).is_err()
+        );
+    }
 
     #[test]
     fn merge_block_access_list_offsets_and_merges_accounts() {
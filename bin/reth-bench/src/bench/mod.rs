@@ -5,6 +5,7 @@ use reth_cli_runner::CliContext;
 use reth_node_core::args::LogArgs;
 use reth_tracing::FileWorkerGuard;
 
+mod compare;
 mod context;
 mod generate_big_block;
 pub(crate) mod helpers;
@@ -61,6 +62,11 @@ pub enum Subcommands {
     ///
     /// `reth-bench generate-big-block --rpc-url http://localhost:8545 --from-block 20000000
     /// --count 10 --output-dir ./payloads`
+    ///
+    /// Blocks can also be read offline from a local datadir or a directory of era1 files:
+    ///
+    /// `reth-bench generate-big-block --datadir ~/.local/share/reth/mainnet --from-block 20000000
+    /// --target-gas 1G --output-dir ./payloads`
     GenerateBigBlock(generate_big_block::Command),
 
     /// Replay pre-generated payloads from a directory.
@@ -85,6 +91,17 @@ pub enum Subcommands {
     /// `cast block latest --full --json | reth-bench send-invalid-payload --rpc-url localhost:5000
     /// --jwt-secret $(cat ~/.local/share/reth/mainnet/jwt.hex) --invalid-state-root`
     SendInvalidPayload(Box<send_invalid_payload::Command>),
+
+    /// Compare the results of a baseline and a candidate benchmark run.
+    ///
+    /// Aligns the `combined_latency.csv` outputs of both runs by block number, computes per-block
+    /// and aggregate deltas with confidence intervals, flags regressions above a threshold, and
+    /// writes `comparison.md` and `comparison.json` reports.
+    ///
+    /// Example:
+    ///
+    /// `reth-bench compare ./baseline ./candidate --threshold 5 --output ./report`
+    Compare(compare::Command),
 }
 
 impl BenchmarkCommand {
@@ -100,6 +117,7 @@ impl BenchmarkCommand {
             Subcommands::GenerateBigBlock(command) => command.execute(ctx).await,
             Subcommands::ReplayPayloads(command) => command.execute(ctx).await,
             Subcommands::SendInvalidPayload(command) => (*command).execute(ctx).await,
+            Subcommands::Compare(command) => command.execute(ctx).await,
         }
     }
 
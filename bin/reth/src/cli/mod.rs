@@ -13,3 +13,6 @@ pub use crate::core::cli::*;
 /// existed in `reth::cli` but were moved to the [`reth_ethereum_cli`] crate. This re-export
 /// avoids a breaking change.
 pub use reth_ethereum_cli::interface::*;
+
+pub mod rpc_replica;
+pub use rpc_replica::{RethSubCommands, RpcReplicaCommand};
@@ -0,0 +1,164 @@
+//! Command that serves RPC from the datadir of another reth node.
+
+use clap::{Parser, Subcommand};
+use reth_chainspec::ChainSpecProvider;
+use reth_cli_commands::common::{AccessRights, Environment, EnvironmentArgs};
+use reth_cli_runner::{CliContext, CliRunner};
+use reth_cli_util::parse_duration_from_secs_or_ms;
+use reth_ethereum_cli::{chainspec::EthereumChainSpecParser, ExtendedCommand};
+use reth_network_api::noop::NoopNetwork;
+use reth_node_core::args::RpcServerArgs;
+use reth_node_ethereum::{consensus::EthBeaconConsensus, EthEvmConfig, EthereumNode};
+use reth_provider::{
+    providers::{BlockchainProvider, ReplicaFollower, DEFAULT_REPLICA_MAX_BLOCKS_PER_POLL},
+    CanonStateSubscriptions,
+};
+use reth_rpc::EthApiBuilder;
+use reth_rpc_builder::{config::RethRpcServerConfig, RpcModuleBuilder};
+use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthStateCache};
+use reth_transaction_pool::noop::NoopTransactionPool;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// Extension subcommands of the `reth` binary.
+#[derive(Debug, Subcommand)]
+pub enum RethSubCommands {
+    /// Serve RPC read-only from the datadir of a running node.
+    #[command(name = "rpc-replica")]
+    RpcReplica(Box<RpcReplicaCommand>),
+}
+
+impl ExtendedCommand for RethSubCommands {
+    fn execute(self, runner: CliRunner) -> eyre::Result<()> {
+        match self {
+            Self::RpcReplica(command) => runner.run_command_until_exit(|ctx| command.execute(ctx)),
+        }
+    }
+}
+
+/// `reth rpc-replica` command.
+///
+/// Opens the database, static files and `RocksDB` of a datadir that is owned by a syncing node
+/// read-only, follows the blocks it persists and serves RPC from them. Several replicas can share
+/// the datadir of one primary to scale RPC horizontally. The replica has no transaction pool and no
+/// network, so transactions submitted to it are rejected.
+#[derive(Debug, Parser)]
+pub struct RpcReplicaCommand {
+    #[command(flatten)]
+    env: EnvironmentArgs<EthereumChainSpecParser>,
+
+    /// All rpc related arguments
+    #[command(flatten)]
+    rpc: RpcServerArgs,
+
+    /// How often to check the primary's database for new blocks.
+    #[arg(long = "replica.poll-interval", value_name = "DURATION", value_parser = parse_duration_from_secs_or_ms, default_value = "1")]
+    poll_interval: Duration,
+
+    /// Maximum number of new blocks committed per poll, if the primary is further ahead the
+    /// replica catches up over multiple polls.
+    #[arg(long = "replica.max-blocks-per-poll", default_value_t = DEFAULT_REPLICA_MAX_BLOCKS_PER_POLL)]
+    max_blocks_per_poll: u64,
+}
+
+impl RpcReplicaCommand {
+    /// Execute the `rpc-replica` command
+    pub async fn execute(self, ctx: CliContext) -> eyre::Result<()> {
+        let Environment { provider_factory, .. } =
+            self.env.init::<EthereumNode>(AccessRights::RO, ctx.task_executor.clone())?;
+        // Catch up with the primary whenever it commits to the database.
+        let provider_factory = provider_factory.with_read_only_sync(true);
+        let provider = BlockchainProvider::new(provider_factory)?;
+        let chain_spec = provider.chain_spec();
+        let evm_config = EthEvmConfig::new(chain_spec.clone());
+
+        let eth_config = self.rpc.eth_config();
+        let cache = EthStateCache::spawn_with(
+            provider.clone(),
+            eth_config.cache,
+            ctx.task_executor.clone(),
+        );
+
+        // Keep the cache in sync with the blocks the follower commits or reorgs.
+        let new_canonical_blocks = provider.canonical_state_stream();
+        let c = cache.clone();
+        ctx.task_executor.spawn_critical_task("cache canonical blocks task", async move {
+            cache_new_blocks_task(c, new_canonical_blocks).await;
+        });
+
+        let eth_api = EthApiBuilder::new(
+            provider.clone(),
+            NoopTransactionPool::default(),
+            NoopNetwork::default(),
+            evm_config.clone(),
+        )
+        .task_spawner(ctx.task_executor.clone())
+        .eth_cache(cache)
+        .gas_cap(eth_config.rpc_gas_cap.into())
+        .max_simulate_blocks(eth_config.rpc_max_simulate_blocks)
+        .eth_proof_window(eth_config.eth_proof_window)
+        .fee_history_cache_config(eth_config.fee_history_cache)
+        .proof_permits(eth_config.proof_permits)
+        .gas_oracle_config(eth_config.gas_oracle)
+        .max_batch_size(eth_config.max_batch_size)
+        .max_blocking_io_requests(eth_config.max_blocking_io_requests)
+        .pending_block_kind(eth_config.pending_block_kind)
+        .build();
+
+        let modules = RpcModuleBuilder::default()
+            .with_provider(provider.clone())
+            .with_noop_pool()
+            .with_noop_network()
+            .with_executor(ctx.task_executor.clone())
+            .with_evm_config(evm_config)
+            .with_consensus(EthBeaconConsensus::new(chain_spec))
+            .build(self.rpc.transport_rpc_module_config(), eth_api, Default::default());
+
+        let _handle = self.rpc.rpc_server_config().start(&modules).await?;
+        info!(target: "reth::cli", head = ?provider.canonical_in_memory_state().get_canonical_head().num_hash(), "RPC replica started");
+
+        let mut follower =
+            ReplicaFollower::new(provider).with_max_blocks_per_poll(self.max_blocks_per_poll);
+        let mut interval = tokio::time::interval(self.poll_interval);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        loop {
+            interval.tick().await;
+            match follower.poll() {
+                Ok(Some(notification)) => {
+                    let reverted = notification.reverted().map(|old| old.range());
+                    info!(target: "reth::cli", range = ?notification.committed().range(), ?reverted, "Followed primary");
+                }
+                Ok(None) => {}
+                Err(err) => warn!(target: "reth::cli", %err, "Failed to follow primary"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Parser)]
+    struct CommandParser {
+        #[command(subcommand)]
+        command: RethSubCommands,
+    }
+
+    #[test]
+    fn parse_rpc_replica() {
+        let RethSubCommands::RpcReplica(command) = CommandParser::parse_from([
+            "reth",
+            "rpc-replica",
+            "--datadir",
+            "/data/reth",
+            "--http",
+            "--replica.poll-interval",
+            "500ms",
+        ])
+        .command;
+        assert_eq!(command.poll_interval, Duration::from_millis(500));
+        assert_eq!(command.max_blocks_per_poll, DEFAULT_REPLICA_MAX_BLOCKS_PER_POLL);
+        assert!(command.rpc.http);
+    }
+}
@@ -12,9 +12,11 @@ use reth_cli_util::allocator::tikv_jemalloc_sys as _;
 static MALLOC_CONF: &[u8] = b"prof:true,prof_active:true,lg_prof_sample:19\0";
 
 use clap::Parser;
-use reth::cli::Cli;
+use reth::cli::{Cli, RethSubCommands};
+use reth_cli_commands::node::NoArgs;
 use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
 use reth_node_ethereum::EthereumNode;
+use reth_rpc_server_types::DefaultRpcModuleValidator;
 use tracing::info;
 
 fn main() {
@@ -25,12 +27,16 @@ fn main() {
         unsafe { std::env::set_var("RUST_BACKTRACE", "1") };
     }
 
-    if let Err(err) = Cli::<EthereumChainSpecParser>::parse().run(async move |builder, _| {
-        info!(target: "reth::cli", "Launching node");
-        let handle = builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
+    if let Err(err) =
+        Cli::<EthereumChainSpecParser, NoArgs, DefaultRpcModuleValidator, RethSubCommands>::parse()
+            .run(async move |builder, _| {
+                info!(target: "reth::cli", "Launching node");
+                let handle =
+                    builder.node(EthereumNode::default()).launch_with_debug_capabilities().await?;
 
-        handle.wait_for_node_exit().await
-    }) {
+                handle.wait_for_node_exit().await
+            })
+    {
         eprintln!("Error: {err:?}");
         std::process::exit(1);
     }
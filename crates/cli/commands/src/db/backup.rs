@@ -0,0 +1,630 @@
+//! `reth db backup` command
+
+use alloy_primitives::{BlockNumber, B256};
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::EthChainSpec;
+use reth_db::{
+    init_db,
+    lockfile::StorageLock,
+    mdbx::{self, ffi, DatabaseArguments},
+    version::db_version_file_path,
+    ClientVersion, DatabaseEnv,
+};
+use reth_db_common::DbTool;
+use reth_fs_util as fs;
+use reth_node_core::dirs::{ChainPath, DataDirPath};
+use reth_provider::{
+    providers::{ProviderNodeTypes, RocksDBProvider, StaticFileProviderBuilder},
+    BlockHashReader, ChainSpecProvider, DBProvider, ProviderFactory, RocksDBProviderFactory,
+    StageCheckpointReader, StaticFileProviderFactory,
+};
+use reth_stages_types::StageId;
+use reth_static_file_types::StaticFileSegment;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ffi::CString,
+    fs::OpenOptions,
+    io::{Read, Seek, SeekFrom, Write},
+    path::{Path, PathBuf},
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+use tracing::{info, warn};
+
+/// The file name of the manifest in every backup directory.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// The name of the `TrieDB` directory in the data directory and in backups.
+const TRIEDB_DIR_NAME: &str = "rust_eth_triedb";
+
+/// The size of the chunks an incremental MDBX backup is compared with its base in.
+const MDBX_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The arguments for the `reth db backup` command
+///
+/// Backs up the database, static files, `RocksDB` and `TrieDB` of a node at a single consistent
+/// block. All but `TrieDB` can be backed up while the node is running.
+///
+/// The MDBX database is copied from a read transaction, whose block is recorded in the manifest.
+/// Static files and `RocksDB` are captured afterwards and truncated to that block, so that the
+/// backup is consistent at it. `TrieDB` can't be captured at a single block while it's written to,
+/// so it's only backed up while the node is stopped, and the node aligns MDBX to it on startup.
+///
+/// Files are copied, which reflinks them on filesystems that support it.
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The directory the backups are written to.
+    ///
+    /// Every backup is written to a subdirectory named after its block number. Static files that
+    /// can't change anymore and are already part of the latest backup in this directory are
+    /// hard-linked from it instead of being copied again.
+    dest: PathBuf,
+
+    /// Copy the MDBX database as-is instead of compacting it
+    #[arg(long)]
+    no_compact: bool,
+
+    /// Only rewrite the parts of the MDBX database that changed since the latest backup in the
+    /// destination directory.
+    ///
+    /// The database is written as a copy of the previous backup, with the chunks that differ
+    /// from the database rewritten as it's streamed. Unchanged pages only share storage with the
+    /// previous backup on filesystems that support reflinks, e.g. btrfs or XFS, elsewhere this
+    /// costs as much disk space as a full copy. Implies `--no-compact`, since compaction moves
+    /// pages.
+    #[arg(long)]
+    incremental: bool,
+}
+
+/// The manifest of a backup.
+#[derive(Debug, Serialize, Deserialize)]
+struct Manifest {
+    /// The block the backup is consistent at.
+    block_number: BlockNumber,
+    /// The hash of the block the backup is consistent at.
+    block_hash: B256,
+    /// Unix timestamp in seconds of when the backup was taken.
+    created_at: u64,
+    /// The name of the backup unchanged static files were linked from, if any.
+    base: Option<String>,
+    /// Whether the MDBX database was written incrementally on top of the base backup.
+    #[serde(default)]
+    incremental_mdbx: bool,
+    /// Whether `TrieDB` was backed up.
+    #[serde(default)]
+    triedb: bool,
+    /// The backed up static files by file name.
+    static_files: BTreeMap<String, StaticFileEntry>,
+}
+
+/// A static file that is part of a backup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+struct StaticFileEntry {
+    /// The size of the file in bytes.
+    size: u64,
+    /// The modification time of the file in the data directory when it was backed up.
+    #[serde(default)]
+    modified: Option<SystemTime>,
+    /// Whether the file belongs to a jar that isn't written to anymore.
+    immutable: bool,
+}
+
+/// How the files of a static files backup were obtained.
+#[derive(Debug, Default)]
+struct StaticFilesStats {
+    /// Files linked from the previous backup.
+    reused: usize,
+    /// Files copied from the data directory.
+    copied: usize,
+}
+
+impl Command {
+    /// Execute `db backup` command
+    pub fn execute<N: ProviderNodeTypes<DB = DatabaseEnv>>(
+        self,
+        data_dir: &ChainPath<DataDirPath>,
+        tool: &DbTool<N>,
+        runtime: reth_tasks::Runtime,
+    ) -> eyre::Result<()> {
+        let factory = &tool.provider_factory;
+        fs::create_dir_all(&self.dest)?;
+        let base = latest_backup(&self.dest)?;
+
+        // Holding the lock keeps the node from starting and writing to `TrieDB` during the backup.
+        let triedb_dir = data_dir.data_dir().join(TRIEDB_DIR_NAME);
+        let _lock = triedb_dir
+            .exists()
+            .then(|| StorageLock::try_acquire(&data_dir.db()))
+            .transpose()
+            .map_err(|err| {
+                eyre::eyre!("The node must be stopped to back up TrieDB at {triedb_dir:?}: {err}")
+            })?;
+
+        // The copy can take longer than the read transaction timeout, and the snapshot must not
+        // change while it runs.
+        let provider = factory.provider()?.disable_long_read_transaction_safety();
+        let block_number = provider
+            .get_stage_checkpoint(StageId::Finish)?
+            .map(|checkpoint| checkpoint.block_number)
+            .unwrap_or_default();
+        let block_hash = provider
+            .block_hash(block_number)?
+            .ok_or_else(|| eyre::eyre!("Canonical hash of block {block_number} not found"))?;
+
+        let name = block_number.to_string();
+        let backup_dir = self.dest.join(&name);
+        eyre::ensure!(
+            !backup_dir.exists(),
+            "Backup of block {block_number} already exists: {backup_dir:?}"
+        );
+
+        // Leftover of an interrupted backup
+        let tmp_dir = self.dest.join(format!("{name}.tmp"));
+        if tmp_dir.exists() {
+            fs::remove_dir_all(&tmp_dir)?;
+        }
+
+        let started_at = Instant::now();
+        info!(target: "reth::cli", block_number, %block_hash, "Copying MDBX database");
+        let db_dir = tmp_dir.join("db");
+        fs::create_dir_all(&db_dir)?;
+        let base_mdbx = base
+            .as_ref()
+            .map(|(dir, _)| dir.join("db").join("mdbx.dat"))
+            .filter(|path| self.incremental && path.is_file());
+        if let Some(base_mdbx) = &base_mdbx {
+            info!(target: "reth::cli", ?base_mdbx, "Writing MDBX database on top of the previous backup");
+            let rewritten =
+                copy_mdbx_incremental(provider.tx_ref(), base_mdbx, &db_dir.join("mdbx.dat"))?;
+            drop(provider);
+            info!(target: "reth::cli", rewritten_bytes = rewritten, "Wrote MDBX database");
+        } else {
+            let compact = !self.no_compact && !self.incremental;
+            copy_mdbx(provider.tx_ref(), &db_dir.join("mdbx.dat"), compact)?;
+            drop(provider);
+        }
+
+        let version_file = db_version_file_path(data_dir.db());
+        if version_file.exists() {
+            std::fs::copy(&version_file, db_version_file_path(&db_dir))?;
+        }
+
+        info!(target: "reth::cli", "Backing up static files");
+        let (static_files, stats) = backup_static_files(
+            factory.static_file_provider().directory(),
+            &tmp_dir.join("static_files"),
+            base.as_ref(),
+        )?;
+        info!(
+            target: "reth::cli",
+            reused = stats.reused,
+            copied = stats.copied,
+            "Backed up static files"
+        );
+
+        if RocksDBProvider::exists(data_dir.rocksdb()) {
+            info!(target: "reth::cli", "Checkpointing RocksDB");
+            factory.rocksdb_provider().checkpoint(tmp_dir.join("rocksdb"))?;
+        }
+
+        info!(target: "reth::cli", block_number, "Truncating static files and RocksDB to the MDBX snapshot");
+        truncate_to_mdbx::<N>(&tmp_dir, factory.chain_spec(), runtime)?;
+        // Truncation may have changed the copied jars.
+        let static_files = static_files
+            .into_iter()
+            .filter_map(|(file_name, entry)| {
+                let size = fs::metadata(tmp_dir.join("static_files").join(&file_name)).ok()?.len();
+                Some((file_name, StaticFileEntry { size, ..entry }))
+            })
+            .collect();
+
+        if triedb_dir.exists() {
+            info!(target: "reth::cli", ?triedb_dir, "Copying TrieDB");
+            copy_dir(&triedb_dir, &tmp_dir.join(TRIEDB_DIR_NAME))?;
+        }
+
+        let manifest = Manifest {
+            block_number,
+            block_hash,
+            created_at: SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs(),
+            base: base
+                .map(|(dir, _)| dir.file_name().unwrap_or_default().to_string_lossy().into_owned()),
+            incremental_mdbx: base_mdbx.is_some(),
+            triedb: triedb_dir.exists(),
+            static_files,
+        };
+        fs::atomic_write_file(&tmp_dir.join(MANIFEST_FILE_NAME), |file| {
+            serde_json::to_writer_pretty(file, &manifest)
+        })?;
+        fs::rename(&tmp_dir, &backup_dir)?;
+
+        println!(
+            "Backup of block {block_number} written to {} in {:?}",
+            backup_dir.display(),
+            started_at.elapsed()
+        );
+
+        Ok(())
+    }
+}
+
+/// Returns the directory and manifest of the backup of the highest block in `dest`, if any.
+fn latest_backup(dest: &Path) -> eyre::Result<Option<(PathBuf, Manifest)>> {
+    let mut latest: Option<(PathBuf, Manifest)> = None;
+    for entry in fs::read_dir(dest)? {
+        let path = entry?.path();
+        let manifest_path = path.join(MANIFEST_FILE_NAME);
+        if !manifest_path.is_file() {
+            continue
+        }
+
+        let manifest: Manifest = match fs::read_json_file(&manifest_path) {
+            Ok(manifest) => manifest,
+            Err(err) => {
+                warn!(target: "reth::cli", ?manifest_path, %err, "Skipping backup with invalid manifest");
+                continue
+            }
+        };
+        if latest.as_ref().is_none_or(|(_, latest)| manifest.block_number > latest.block_number) {
+            latest = Some((path, manifest));
+        }
+    }
+
+    Ok(latest)
+}
+
+/// Copies the MDBX database as seen by the read transaction to `dest`.
+fn copy_mdbx(tx: &mdbx::tx::Tx<mdbx::RO>, dest: &Path, compact: bool) -> eyre::Result<()> {
+    let mut flags: ffi::MDBX_copy_flags_t = ffi::MDBX_CP_DEFAULTS;
+    if compact {
+        flags |= ffi::MDBX_CP_COMPACT;
+    }
+
+    let dest = CString::new(
+        dest.to_str().ok_or_else(|| eyre::eyre!("destination path must be valid UTF-8"))?,
+    )?;
+    let rc = tx
+        .inner()
+        .txn_execute(|txn| unsafe { ffi::mdbx_txn_copy2pathname(txn, dest.as_ptr(), flags) })?;
+
+    if rc != 0 {
+        eyre::bail!("mdbx_txn_copy2pathname failed with error code {rc}: {}", unsafe {
+            std::ffi::CStr::from_ptr(ffi::mdbx_strerror(rc)).to_string_lossy()
+        });
+    }
+
+    Ok(())
+}
+
+/// Copies the MDBX database as seen by the read transaction to `dest` on top of a copy of
+/// `base`. Returns the number of rewritten bytes.
+///
+/// The database is streamed through a pipe into [`patch_file`], so it's never written in full
+/// next to the copy.
+fn copy_mdbx_incremental(
+    tx: &mdbx::tx::Tx<mdbx::RO>,
+    base: &Path,
+    dest: &Path,
+) -> eyre::Result<u64> {
+    let (reader, writer) = std::io::pipe()?;
+    std::thread::scope(|scope| {
+        let patch = scope.spawn(|| patch_file(reader, base, dest));
+
+        #[cfg(unix)]
+        let fd = std::os::fd::AsRawFd::as_raw_fd(&writer);
+        #[cfg(windows)]
+        let fd = std::os::windows::io::AsRawHandle::as_raw_handle(&writer);
+        let rc = tx
+            .inner()
+            .txn_execute(|txn| unsafe { ffi::mdbx_txn_copy2fd(txn, fd, ffi::MDBX_CP_DEFAULTS) })?;
+        // Closing the pipe ends the stream.
+        drop(writer);
+
+        // A failed write also fails the copy, so its error is returned first.
+        let rewritten =
+            patch.join().map_err(|_| eyre::eyre!("Writing MDBX database panicked"))??;
+        if rc != 0 {
+            eyre::bail!("mdbx_txn_copy2fd failed with error code {rc}: {}", unsafe {
+                std::ffi::CStr::from_ptr(ffi::mdbx_strerror(rc)).to_string_lossy()
+            });
+        }
+        Ok(rewritten)
+    })
+}
+
+/// Writes the contents read from `snapshot` to `dest` as a copy of `base`, with only the chunks
+/// that differ from it rewritten. Returns the number of rewritten bytes.
+///
+/// Copying reflinks `base` on filesystems that support it, so unchanged chunks share storage.
+/// Zeroed chunks past the end of `base` are left as holes.
+fn patch_file(mut snapshot: impl Read, base: &Path, dest: &Path) -> eyre::Result<u64> {
+    let base_len =
+        std::fs::copy(base, dest).wrap_err_with(|| format!("Failed to copy {base:?}"))?;
+    let mut dest = OpenOptions::new().read(true).write(true).open(dest)?;
+
+    let (mut expected, mut current) = (Vec::new(), Vec::new());
+    let (mut offset, mut rewritten) = (0, 0);
+    loop {
+        expected.clear();
+        (&mut snapshot).take(MDBX_CHUNK_SIZE as u64).read_to_end(&mut expected)?;
+        if expected.is_empty() {
+            break
+        }
+
+        current.clear();
+        (&mut dest).take(expected.len() as u64).read_to_end(&mut current)?;
+        let is_hole = offset >= base_len && expected.iter().all(|byte| *byte == 0);
+        if expected != current && !is_hole {
+            dest.seek(SeekFrom::Start(offset))?;
+            dest.write_all(&expected)?;
+            rewritten += expected.len() as u64;
+        }
+        offset += expected.len() as u64;
+        dest.seek(SeekFrom::Start(offset))?;
+    }
+    dest.set_len(offset)?;
+    dest.sync_all()?;
+
+    Ok(rewritten)
+}
+
+/// Truncates the static files and `RocksDB` of the backup in `dir` to the block of its MDBX
+/// database.
+///
+/// Only the highest jar of every segment is truncated, which is copied rather than linked from a
+/// previous backup.
+fn truncate_to_mdbx<N: ProviderNodeTypes<DB = DatabaseEnv>>(
+    dir: &Path,
+    chain_spec: std::sync::Arc<N::ChainSpec>,
+    runtime: reth_tasks::Runtime,
+) -> eyre::Result<()> {
+    let genesis_block_number = chain_spec.genesis().number.unwrap_or_default();
+    let db = init_db(dir.join("db"), DatabaseArguments::new(ClientVersion::default()))?;
+    let static_file_provider = StaticFileProviderBuilder::read_write(dir.join("static_files"))
+        .with_genesis_block_number(genesis_block_number)
+        .build()?;
+    let rocksdb_provider =
+        RocksDBProvider::builder(dir.join("rocksdb")).with_default_tables().build()?;
+    let factory =
+        ProviderFactory::<N>::new(db, chain_spec, static_file_provider, rocksdb_provider, runtime)?;
+
+    let (rocksdb_unwind, static_file_unwind) = factory.check_consistency()?;
+    if let Some(unwind_to) = rocksdb_unwind.into_iter().chain(static_file_unwind).min() {
+        eyre::bail!(
+            "Backup is behind its MDBX database and would have to be unwound to block {unwind_to}"
+        );
+    }
+
+    Ok(())
+}
+
+/// Recursively copies the directory `src` to `dst`.
+fn copy_dir(src: &Path, dst: &Path) -> eyre::Result<()> {
+    fs::create_dir_all(dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        let (src, dst) = (entry.path(), dst.join(entry.file_name()));
+        if entry.file_type()?.is_dir() {
+            copy_dir(&src, &dst)?;
+        } else {
+            std::fs::copy(&src, &dst).wrap_err_with(|| format!("Failed to copy {src:?}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Backs up the static files in `source` to `dest`.
+///
+/// Files of jars that are still written to are copied, configuration and offsets before the
+/// data, so that the copy is never ahead of its data file. All other files are hard-linked from
+/// the base backup if they're part of it, or copied from `source` otherwise, since jars in the
+/// data directory may still be rewritten in place.
+fn backup_static_files(
+    source: &Path,
+    dest: &Path,
+    base: Option<&(PathBuf, Manifest)>,
+) -> eyre::Result<(BTreeMap<String, StaticFileEntry>, StaticFilesStats)> {
+    fs::create_dir_all(dest)?;
+
+    let mut files = Vec::new();
+    let mut highest_jars = HashMap::<StaticFileSegment, BlockNumber>::default();
+    for entry in fs::read_dir(source)? {
+        let file_name = entry?.file_name().to_string_lossy().into_owned();
+        let (jar_name, extension) = file_name.split_once('.').unwrap_or((&file_name, ""));
+        let Some((segment, range)) = StaticFileSegment::parse_filename(jar_name) else { continue };
+
+        let highest = highest_jars.entry(segment).or_default();
+        *highest = (*highest).max(range.end());
+        files.push((segment, range.end(), copy_order(extension), file_name));
+    }
+    files.sort_unstable_by_key(|(_, _, order, _)| *order);
+
+    let mut entries = BTreeMap::new();
+    let mut stats = StaticFilesStats::default();
+    for (segment, range_end, _, file_name) in files {
+        let (src, dst) = (source.join(&file_name), dest.join(&file_name));
+        let immutable = highest_jars.get(&segment).is_some_and(|highest| range_end < *highest);
+        let modified = fs::metadata(&src)?.modified().ok();
+
+        // Jars can be rewritten in place with the same size, e.g. when they're recompressed, so
+        // the modification time has to match as well.
+        let base_file = base.and_then(|(dir, manifest)| {
+            let entry = manifest.static_files.get(&file_name)?;
+            let path = dir.join("static_files").join(&file_name);
+            (entry.immutable &&
+                entry.modified.is_some() &&
+                entry.modified == modified &&
+                fs::metadata(&src).ok()?.len() == entry.size)
+                .then_some(path)
+        });
+
+        if immutable &&
+            let Some(base_file) = base_file &&
+            std::fs::hard_link(&base_file, &dst).is_ok()
+        {
+            stats.reused += 1;
+        } else {
+            std::fs::copy(&src, &dst)
+                .wrap_err_with(|| format!("Failed to copy static file {src:?}"))?;
+            stats.copied += 1;
+        }
+
+        entries.insert(
+            file_name,
+            StaticFileEntry { size: fs::metadata(&dst)?.len(), modified, immutable },
+        );
+    }
+
+    Ok((entries, stats))
+}
+
+/// Returns the position of a jar file with the given extension in the order files are copied.
+fn copy_order(extension: &str) -> u8 {
+    match extension {
+        "conf" => 0,
+        "csoff" => 1,
+        "off" => 2,
+        _ => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_files(dir: &Path, files: &[(&str, &[u8])]) {
+        fs::create_dir_all(dir).unwrap();
+        for (name, contents) in files {
+            fs::write(dir.join(name), contents).unwrap();
+        }
+    }
+
+    #[test]
+    fn backup_static_files_links_immutable_jars() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join("static_files");
+        write_files(
+            &source,
+            &[
+                ("static_file_headers_0_499999", b"immutable"),
+                ("static_file_headers_0_499999.off", b"immutable"),
+                ("static_file_headers_500000_999999", b"mutable"),
+                ("static_file_headers_500000_999999.conf", b"mutable"),
+                ("lock", b""),
+            ],
+        );
+
+        let first = temp_dir.path().join("1");
+        let (entries, stats) =
+            backup_static_files(&source, &first.join("static_files"), None).unwrap();
+        assert_eq!((stats.reused, stats.copied), (0, 4));
+        let entry = entries["static_file_headers_0_499999.off"];
+        assert_eq!((entry.size, entry.immutable), (9, true));
+        assert!(entry.modified.is_some());
+        let entry = entries["static_file_headers_500000_999999.conf"];
+        assert_eq!((entry.size, entry.immutable), (7, false));
+        assert!(!entries.contains_key("lock"));
+
+        // The previously mutable jar became immutable
+        write_files(&source, &[("static_file_headers_1000000_1499999", b"mutable")]);
+        let manifest = Manifest {
+            block_number: 1,
+            block_hash: B256::ZERO,
+            created_at: 0,
+            base: None,
+            incremental_mdbx: false,
+            triedb: false,
+            static_files: entries,
+        };
+        let (entries, stats) = backup_static_files(
+            &source,
+            &temp_dir.path().join("2").join("static_files"),
+            Some(&(first, manifest)),
+        )
+        .unwrap();
+        assert_eq!((stats.reused, stats.copied), (2, 3));
+        assert_eq!(entries.len(), 5);
+
+        // Jars in the data directory are copied, so rewriting them doesn't change the backup.
+        write_files(&source, &[("static_file_headers_0_499999", b"rewritten")]);
+        assert_eq!(
+            fs::read(first.join("static_files").join("static_file_headers_0_499999")).unwrap(),
+            b"immutable"
+        );
+
+        // A jar rewritten in place with the same size is copied again.
+        let second = temp_dir.path().join("2");
+        let manifest = Manifest {
+            block_number: 2,
+            block_hash: B256::ZERO,
+            created_at: 0,
+            base: None,
+            incremental_mdbx: false,
+            triedb: false,
+            static_files: entries,
+        };
+        std::fs::File::options()
+            .write(true)
+            .open(source.join("static_file_headers_0_499999"))
+            .unwrap()
+            .set_modified(SystemTime::now() + std::time::Duration::from_secs(60))
+            .unwrap();
+        let (_, stats) = backup_static_files(
+            &source,
+            &temp_dir.path().join("3").join("static_files"),
+            Some(&(second, manifest)),
+        )
+        .unwrap();
+        assert_eq!((stats.reused, stats.copied), (3, 2));
+        assert_eq!(
+            fs::read(
+                temp_dir.path().join("3").join("static_files").join("static_file_headers_0_499999")
+            )
+            .unwrap(),
+            b"rewritten"
+        );
+    }
+
+    #[test]
+    fn patch_file_rewrites_changed_chunks() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let (base, dest) = (temp_dir.path().join("base"), temp_dir.path().join("dest"));
+
+        let base_contents = vec![1u8; MDBX_CHUNK_SIZE * 3];
+        let mut snapshot_contents = base_contents.clone();
+        snapshot_contents[MDBX_CHUNK_SIZE + 1] = 2;
+        snapshot_contents.extend_from_slice(&[3; 10]);
+        fs::write(&base, &base_contents).unwrap();
+
+        let rewritten = patch_file(&snapshot_contents[..], &base, &dest).unwrap();
+        assert_eq!(rewritten, MDBX_CHUNK_SIZE as u64 + 10);
+        assert_eq!(fs::read(&dest).unwrap(), snapshot_contents);
+        assert_eq!(fs::read(&base).unwrap(), base_contents);
+
+        // A shorter snapshot truncates the copy.
+        fs::remove_file(&dest).unwrap();
+        assert_eq!(patch_file(&base_contents[..MDBX_CHUNK_SIZE], &base, &dest).unwrap(), 0);
+        assert_eq!(fs::read(&dest).unwrap(), &base_contents[..MDBX_CHUNK_SIZE]);
+
+        // Zeroes past the end of the base aren't written.
+        let mut snapshot_contents = base_contents.clone();
+        snapshot_contents.extend_from_slice(&vec![0; MDBX_CHUNK_SIZE * 2]);
+        fs::remove_file(&dest).unwrap();
+        assert_eq!(patch_file(&snapshot_contents[..], &base, &dest).unwrap(), 0);
+        assert_eq!(fs::read(&dest).unwrap(), snapshot_contents);
+    }
+
+    #[test]
+    fn copy_dir_copies_nested_files() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let source = temp_dir.path().join(TRIEDB_DIR_NAME);
+        write_files(&source, &[("CURRENT", b"current")]);
+        write_files(&source.join("pathdb"), &[("000001.sst", b"sst")]);
+
+        let dest = temp_dir.path().join("backup").join(TRIEDB_DIR_NAME);
+        copy_dir(&source, &dest).unwrap();
+        assert_eq!(fs::read(dest.join("CURRENT")).unwrap(), b"current");
+        assert_eq!(fs::read(dest.join("pathdb").join("000001.sst")).unwrap(), b"sst");
+    }
+}
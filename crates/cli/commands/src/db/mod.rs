@@ -10,6 +10,7 @@ use std::{
     sync::Arc,
 };
 mod account_storage;
+mod backup;
 mod checksum;
 mod clear;
 mod copy;
@@ -23,10 +24,12 @@ mod repair_trie;
 mod settings;
 mod stage_checkpoints;
 mod state;
+mod static_file;
 mod static_file_header;
 mod stats;
 /// DB List TUI
 mod tui;
+mod verify;
 
 /// `reth db` command
 #[derive(Debug, Parser)]
@@ -49,6 +52,8 @@ pub enum Subcommands {
     Checksum(checksum::Command),
     /// Copies the MDBX database to a new location (bundled mdbx_copy)
     Copy(copy::Command),
+    /// Backs up the database, static files, `RocksDB` and `TrieDB` of a node at a consistent block
+    Backup(backup::Command),
     /// Create a diff between two database tables or two entire databases.
     Diff(diff::Command),
     /// Gets the content of a table for the given key
@@ -65,8 +70,12 @@ pub enum Subcommands {
     Migrate(migrate::Command),
     /// Verifies trie consistency and outputs any inconsistencies
     RepairTrie(repair_trie::Command),
+    /// Verifies the consistency of the database, static files and `RocksDB`
+    Verify(verify::Command),
     /// Reads and displays the static file segment header
     StaticFileHeader(static_file_header::Command),
+    /// Maintains static files
+    StaticFile(static_file::Command),
     /// Lists current and local database versions
     Version,
     /// Returns the full database path
@@ -146,6 +155,11 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(tool.provider_factory.db_ref())?;
                 });
             }
+            Subcommands::Backup(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RO, {
+                    command.execute(&data_dir, &tool, ctx.task_executor.clone())?;
+                });
+            }
             Subcommands::Diff(command) => {
                 db_exec!(self.env, tool, N, AccessRights::RO, {
                     command.execute(&tool)?;
@@ -197,11 +211,23 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C>
                     command.execute(&tool, ctx.task_executor, &data_dir)?;
                 });
             }
+            Subcommands::Verify(command) => {
+                let Environment { provider_factory, config, .. } =
+                    self.env.init::<N>(AccessRights::RO, ctx.task_executor.clone())?;
+
+                let tool = DbTool::new(provider_factory)?;
+                command.execute(&tool, config.statedb.as_ref())?;
+            }
             Subcommands::StaticFileHeader(command) => {
                 db_exec!(self.env, tool, N, AccessRights::RoInconsistent, {
                     command.execute(&tool)?;
                 });
             }
+            Subcommands::StaticFile(command) => {
+                db_exec!(self.env, tool, N, AccessRights::RW, {
+                    command.execute(&tool)?;
+                });
+            }
             Subcommands::Version => {
                 let local_db_version = match get_db_version(&db_path) {
                     Ok(version) => Some(version),
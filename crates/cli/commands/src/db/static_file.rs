@@ -0,0 +1,143 @@
+//! `reth db static-file` command for maintaining static files
+
+use clap::{Parser, Subcommand};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_db_common::DbTool;
+use reth_provider::{
+    providers::ProviderNodeTypes, ChainStateBlockReader, StaticFileProviderFactory,
+};
+use reth_static_file::{SegmentRecompressionReport, DEFAULT_RECOMPRESSION_SEGMENTS};
+use reth_static_file_types::{Compression, StaticFileSegment};
+
+/// `reth db static-file` subcommand
+#[derive(Debug, Parser)]
+pub struct Command {
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    /// Rewrites complete static files with a compression, zstd with trained dictionaries by
+    /// default
+    Recompress(RecompressCommand),
+}
+
+/// The arguments for the `reth db static-file recompress` command
+#[derive(Debug, Parser)]
+struct RecompressCommand {
+    /// Static file segments to recompress, transactions and receipts by default
+    #[arg(long, value_enum, value_delimiter = ',')]
+    segments: Vec<StaticFileSegment>,
+
+    /// Compression to rewrite the static files with
+    #[arg(long, value_enum, default_value = "zstd-with-dictionary")]
+    compression: Compression,
+
+    /// Only recompress static files whose blocks are all at or below this block, the last
+    /// finalized block by default
+    #[arg(long)]
+    to_block: Option<u64>,
+}
+
+impl Command {
+    /// Execute `db static-file` command
+    pub fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Recompress(command) => command.execute(tool),
+        }
+    }
+}
+
+impl RecompressCommand {
+    /// Execute `db static-file recompress` command
+    fn execute<N: ProviderNodeTypes>(self, tool: &DbTool<N>) -> eyre::Result<()> {
+        let to_block = match self.to_block {
+            Some(block) => block,
+            None => tool.provider_factory.provider()?.last_finalized_block_number()?.ok_or_else(
+                || eyre::eyre!("No finalized block in the database, pass --to-block instead"),
+            )?,
+        };
+        let segments = if self.segments.is_empty() {
+            DEFAULT_RECOMPRESSION_SEGMENTS.to_vec()
+        } else {
+            self.segments
+        };
+
+        let static_file_provider = tool.provider_factory.static_file_provider();
+        let mut table = ComfyTable::new();
+        table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+        table.set_header(["Segment", "Block Range", "Size Before", "Size After", "Saved"]);
+
+        let mut reports = Vec::with_capacity(segments.len());
+        for segment in segments {
+            let files =
+                static_file_provider.recompress_segment(segment, self.compression, to_block)?;
+            for file in &files {
+                let mut row = Row::new();
+                row.add_cell(Cell::new(segment))
+                    .add_cell(Cell::new(file.block_range))
+                    .add_cell(Cell::new(human_bytes(file.size_before as f64)))
+                    .add_cell(Cell::new(human_bytes(file.size_after as f64)))
+                    .add_cell(Cell::new(human_bytes(file.saved() as f64)));
+                table.add_row(row);
+            }
+            reports.push(SegmentRecompressionReport::new(segment, &files));
+        }
+
+        let max_widths = table.column_max_content_widths();
+        let mut separator = Row::new();
+        for width in max_widths {
+            separator.add_cell(Cell::new("-".repeat(width as usize)));
+        }
+        table.add_row(separator);
+
+        for report in reports {
+            let mut row = Row::new();
+            row.add_cell(Cell::new(format!("{} ({} files)", report.segment, report.files)))
+                .add_cell(Cell::new(""))
+                .add_cell(Cell::new(human_bytes(report.size_before as f64)))
+                .add_cell(Cell::new(human_bytes(report.size_after as f64)))
+                .add_cell(Cell::new(human_bytes(report.saved() as f64)));
+            table.add_row(row);
+        }
+
+        println!("{table}");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_recompress() {
+        let command = Command::try_parse_from(["reth", "recompress"]).unwrap();
+        let Subcommands::Recompress(command) = command.command;
+        assert!(command.segments.is_empty());
+        assert!(matches!(command.compression, Compression::ZstdWithDictionary));
+        assert_eq!(command.to_block, None);
+
+        let command = Command::try_parse_from([
+            "reth",
+            "recompress",
+            "--segments",
+            "transactions,headers",
+            "--compression",
+            "lz4",
+            "--to-block",
+            "100",
+        ])
+        .unwrap();
+        let Subcommands::Recompress(command) = command.command;
+        assert_eq!(
+            command.segments,
+            vec![StaticFileSegment::Transactions, StaticFileSegment::Headers]
+        );
+        assert!(matches!(command.compression, Compression::Lz4));
+        assert_eq!(command.to_block, Some(100));
+    }
+}
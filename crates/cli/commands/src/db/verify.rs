@@ -0,0 +1,883 @@
+//! `reth db verify` command
+
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use clap::{Parser, ValueEnum};
+use eyre::WrapErr;
+use rayon::prelude::*;
+use reth_config::config::StateDbConfig;
+use reth_db_api::{tables, transaction::DbTx};
+use reth_db_common::DbTool;
+use reth_primitives_traits::{SealedHeader, SignedTransaction, SignerRecoverable};
+use reth_provider::{
+    providers::{HistoryInfo, ProviderNodeTypes},
+    BlockBodyIndicesProvider, BlockHashReader, BlockNumReader, ChangeSetReader, DBProvider,
+    EitherReader, HeaderProvider, NodePrimitivesProvider, ProviderError, ProviderFactory,
+    PruneCheckpointReader, RocksDBProviderFactory, StaticFileProviderFactory,
+    StorageChangeSetReader, TransactionsProvider,
+};
+use reth_prune_types::PruneSegment;
+use reth_static_file_types::StaticFileSegment;
+use reth_storage_api::StorageSettingsCache;
+use reth_trie::StateRoot;
+use reth_trie_db::{DatabaseHashedCursorFactory, DatabaseStateRoot, DatabaseTrieCursorFactory};
+use rust_eth_triedb::{get_global_triedb, triedb_manager::init_global_triedb_manager};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, fs, ops::RangeInclusive, path::PathBuf, time::Instant};
+use tracing::info;
+
+type DbStateRoot<'a, TX, A> =
+    StateRoot<DatabaseTrieCursorFactory<&'a TX, A>, DatabaseHashedCursorFactory<&'a TX>>;
+
+/// The arguments for the `reth db verify` command
+#[derive(Parser, Debug)]
+pub struct Command {
+    /// The checks to run, all of them by default
+    #[arg(long, value_enum, value_delimiter = ',')]
+    checks: Vec<Check>,
+
+    /// The first block to verify
+    #[arg(long, default_value_t = 0)]
+    from: BlockNumber,
+
+    /// The last block to verify, the tip of the database by default
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// The number of blocks verified by a single task
+    #[arg(long, default_value_t = 10_000)]
+    chunk_size: u64,
+
+    /// The number of threads used for verification, all available cores by default
+    #[arg(long, short)]
+    jobs: Option<usize>,
+
+    /// The file the JSON report is written to after every batch of verified blocks.
+    ///
+    /// If the file already exists, the verification resumes from the progress recorded in it. If
+    /// the tip has moved since, the recorded checks are extended up to the new tip.
+    #[arg(long, value_name = "FILE")]
+    report: Option<PathBuf>,
+
+    /// The maximum number of inconsistencies recorded in the report for each check
+    #[arg(long, default_value_t = 100)]
+    max_errors: usize,
+
+    /// Print the report as JSON instead of a summary
+    #[arg(long)]
+    json: bool,
+}
+
+/// A consistency check of `reth db verify`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum Check {
+    /// Headers chain by parent hash, and canonical hashes and header numbers map to each other
+    Headers,
+    /// Block body indices are contiguous and covered by the transactions static files
+    BodyIndices,
+    /// Every transaction hash maps to its transaction number
+    TransactionHashes,
+    /// Stored senders match the senders recovered from the transaction signatures
+    Senders,
+    /// Every account and storage changeset is present in the history indices
+    History,
+    /// The state root computed from the trie matches the header at the tip, or the state root
+    /// persisted by `TrieDB` if it's the state database
+    StateRoot,
+}
+
+impl Check {
+    /// Returns the segment whose pruning makes the data of this check unavailable, if any.
+    const fn prune_segment(&self) -> Option<PruneSegment> {
+        match self {
+            Self::BodyIndices => Some(PruneSegment::Bodies),
+            Self::TransactionHashes => Some(PruneSegment::TransactionLookup),
+            Self::Senders => Some(PruneSegment::SenderRecovery),
+            Self::History => Some(PruneSegment::AccountHistory),
+            Self::Headers | Self::StateRoot => None,
+        }
+    }
+}
+
+/// The report of a `reth db verify` run, which also records its progress.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Report {
+    /// The first verified block.
+    from: BlockNumber,
+    /// The last verified block.
+    to: BlockNumber,
+    /// The outcome of each check.
+    checks: BTreeMap<Check, CheckReport>,
+}
+
+impl Report {
+    /// Returns the total number of inconsistencies found.
+    fn error_count(&self) -> u64 {
+        self.checks.values().map(|check| check.error_count).sum()
+    }
+}
+
+/// The outcome of a single check.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+struct CheckReport {
+    /// Whether the check was completed.
+    done: bool,
+    /// The block up to which the check was completed.
+    verified_to: Option<BlockNumber>,
+    /// The highest block that was skipped because its data is pruned.
+    pruned_to: Option<BlockNumber>,
+    /// The number of verified entries.
+    entries: u64,
+    /// The number of inconsistencies found.
+    error_count: u64,
+    /// The first inconsistencies found.
+    errors: Vec<String>,
+    /// Time spent on the check, in seconds.
+    elapsed_secs: f64,
+}
+
+impl CheckReport {
+    /// Merges the findings of a verified chunk into the report.
+    fn record(&mut self, findings: Findings) {
+        self.entries += findings.entries;
+        self.error_count += findings.error_count;
+        let remaining = findings.max_errors.saturating_sub(self.errors.len());
+        self.errors.extend(findings.errors.into_iter().take(remaining));
+    }
+}
+
+/// The findings of a check over a range of blocks.
+#[derive(Debug)]
+struct Findings {
+    entries: u64,
+    error_count: u64,
+    errors: Vec<String>,
+    max_errors: usize,
+}
+
+impl Findings {
+    const fn new(max_errors: usize) -> Self {
+        Self { entries: 0, error_count: 0, errors: Vec::new(), max_errors }
+    }
+
+    /// Records an inconsistency. The message is only built if it's kept.
+    fn error(&mut self, message: impl FnOnce() -> String) {
+        self.error_count += 1;
+        if self.errors.len() < self.max_errors {
+            self.errors.push(message());
+        }
+    }
+}
+
+impl Command {
+    /// Execute `db verify` command
+    pub fn execute<N: ProviderNodeTypes>(
+        self,
+        tool: &DbTool<N>,
+        statedb: Option<&StateDbConfig>,
+    ) -> eyre::Result<()> {
+        eyre::ensure!(self.chunk_size > 0, "--chunk-size must be positive");
+
+        let factory = &tool.provider_factory;
+        let tip = factory.provider()?.best_block_number()?;
+        let to = self.to.map_or(tip, |to| to.min(tip));
+        eyre::ensure!(self.from <= to, "--from {} is above the last block {to}", self.from);
+
+        let mut checks = if self.checks.is_empty() {
+            Check::value_variants().to_vec()
+        } else {
+            self.checks.clone()
+        };
+        checks.sort_unstable();
+        checks.dedup();
+
+        let mut report = self.load_report(self.from, to)?;
+        let jobs = self
+            .jobs
+            .unwrap_or_else(|| std::thread::available_parallelism().map_or(1, |jobs| jobs.get()));
+        let pool = rayon::ThreadPoolBuilder::new().num_threads(jobs).build()?;
+
+        for check in checks {
+            let mut check_report = report.checks.get(&check).cloned().unwrap_or_default();
+            if check_report.done {
+                info!(target: "reth::cli", ?check, "Check was already completed, skipping");
+                continue
+            }
+
+            let started_at = Instant::now();
+            let elapsed_secs = check_report.elapsed_secs;
+            if check == Check::StateRoot {
+                let mut findings = Findings::new(self.max_errors);
+                if to != tip {
+                    info!(target: "reth::cli", to, tip, "State root can only be verified at the tip, skipping");
+                } else if let Some(statedb) = statedb.filter(|statedb| statedb.r#type == "triedb") {
+                    init_global_triedb_manager(&statedb.path.to_string_lossy());
+                    verify_triedb_state_root(&factory.provider()?, tip, &mut findings)?;
+                } else {
+                    verify_state_root(factory, tip, &mut findings)?;
+                }
+                check_report.record(findings);
+                check_report.verified_to = Some(to);
+            } else {
+                let mut start = check_report.verified_to.map_or(self.from, |block| block + 1);
+                if let Some(segment) = check.prune_segment() &&
+                    let Some(pruned_to) = factory
+                        .provider()?
+                        .get_prune_checkpoint(segment)?
+                        .and_then(|checkpoint| checkpoint.block_number) &&
+                    pruned_to >= start
+                {
+                    check_report.pruned_to = Some(pruned_to.min(to));
+                    start = pruned_to + 1;
+                }
+
+                let chunks = (start..=to)
+                    .step_by(self.chunk_size as usize)
+                    .map(|start| start..=start.saturating_add(self.chunk_size - 1).min(to))
+                    .collect::<Vec<_>>();
+                for batch in chunks.chunks(jobs) {
+                    let outcomes = pool.install(|| {
+                        batch
+                            .par_iter()
+                            .map(|range| self.verify_chunk(factory, check, range.clone()))
+                            .collect::<eyre::Result<Vec<_>>>()
+                    })?;
+                    for findings in outcomes {
+                        check_report.record(findings);
+                    }
+                    check_report.verified_to = batch.last().map(|range| *range.end());
+                    check_report.elapsed_secs = elapsed_secs + started_at.elapsed().as_secs_f64();
+
+                    info!(
+                        target: "reth::cli",
+                        ?check,
+                        verified_to = ?check_report.verified_to,
+                        to,
+                        errors = check_report.error_count,
+                        "Verifying"
+                    );
+                    report.checks.insert(check, check_report.clone());
+                    self.save_report(&report)?;
+                }
+
+                // Header numbers are only written for canonical headers, so if all headers map
+                // to their numbers and there are as many header numbers as headers, the mapping
+                // is a bijection.
+                if check == Check::Headers && self.from == 0 && to == tip {
+                    let header_numbers =
+                        factory.provider()?.tx_ref().entries::<tables::HeaderNumbers>()? as u64;
+                    if header_numbers != tip + 1 {
+                        check_report.record(Findings {
+                            entries: 0,
+                            error_count: 1,
+                            errors: vec![format!(
+                                "HeaderNumbers has {header_numbers} entries, expected {}",
+                                tip + 1
+                            )],
+                            max_errors: self.max_errors,
+                        });
+                    }
+                }
+            }
+
+            check_report.done = true;
+            check_report.elapsed_secs = elapsed_secs + started_at.elapsed().as_secs_f64();
+            info!(target: "reth::cli", ?check, entries = check_report.entries, errors = check_report.error_count, "Check completed");
+            report.checks.insert(check, check_report);
+            self.save_report(&report)?;
+        }
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            for (check, check_report) in &report.checks {
+                println!(
+                    "{check:?}: {} entries, {} inconsistencies",
+                    check_report.entries, check_report.error_count
+                );
+                for error in &check_report.errors {
+                    println!("  {error}");
+                }
+            }
+        }
+
+        let error_count = report.error_count();
+        eyre::ensure!(error_count == 0, "Found {error_count} inconsistencies in the database");
+
+        Ok(())
+    }
+
+    /// Loads the report to resume from, or creates a new one.
+    fn load_report(&self, from: BlockNumber, to: BlockNumber) -> eyre::Result<Report> {
+        let Some(path) = self.report.as_ref().filter(|path| path.exists()) else {
+            return Ok(Report { from, to, checks: BTreeMap::new() })
+        };
+
+        let mut report: Report = serde_json::from_slice(&fs::read(path)?)
+            .wrap_err_with(|| format!("failed to parse report {}", path.display()))?;
+        eyre::ensure!(
+            report.from == from && report.to <= to,
+            "Report {} covers blocks {}..={}, remove it to verify blocks {from}..={to}",
+            path.display(),
+            report.from,
+            report.to
+        );
+        info!(target: "reth::cli", path = %path.display(), verified_to = report.to, to, "Resuming from report");
+
+        if report.to < to {
+            report.to = to;
+            // The state root is only verified at the tip, so it has to be verified again.
+            report.checks.remove(&Check::StateRoot);
+            for check_report in report.checks.values_mut() {
+                check_report.done = false;
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Writes the report to the report file, if any.
+    fn save_report(&self, report: &Report) -> eyre::Result<()> {
+        if let Some(path) = &self.report {
+            reth_fs_util::atomic_write_file(path, |file| {
+                serde_json::to_writer_pretty(file, report)
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Runs a check over a range of blocks with its own read-only provider.
+    fn verify_chunk<N: ProviderNodeTypes>(
+        &self,
+        factory: &ProviderFactory<N>,
+        check: Check,
+        range: RangeInclusive<BlockNumber>,
+    ) -> eyre::Result<Findings> {
+        let provider = factory.provider()?;
+        let mut findings = Findings::new(self.max_errors);
+        match check {
+            Check::Headers => verify_headers(&provider, range, &mut findings)?,
+            Check::BodyIndices => verify_body_indices(&provider, range, &mut findings)?,
+            Check::TransactionHashes => verify_transaction_hashes(&provider, range, &mut findings)?,
+            Check::Senders => verify_senders(&provider, range, &mut findings)?,
+            Check::History => verify_history(&provider, range, &mut findings)?,
+            Check::StateRoot => unreachable!("state root is not verified in chunks"),
+        }
+        Ok(findings)
+    }
+}
+
+/// Verifies that headers chain by parent hash, and that `CanonicalHeaders` and `HeaderNumbers`
+/// map the blocks and their hashes to each other.
+fn verify_headers<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    findings: &mut Findings,
+) -> eyre::Result<()>
+where
+    Provider: HeaderProvider + BlockHashReader + BlockNumReader,
+{
+    let mut parent_hash = match range.start().checked_sub(1) {
+        Some(parent) => provider.block_hash(parent)?,
+        None => None,
+    };
+
+    for number in range {
+        findings.entries += 1;
+        let Some(header) = provider.header_by_number(number)? else {
+            findings.error(|| format!("block {number}: header is missing"));
+            parent_hash = None;
+            continue
+        };
+        let header = SealedHeader::seal_slow(header);
+        let hash = header.hash();
+
+        if let Some(parent_hash) = parent_hash &&
+            header.parent_hash() != parent_hash
+        {
+            findings.error(|| {
+                format!(
+                    "block {number}: parent hash {} doesn't match the hash of the previous block {parent_hash}",
+                    header.parent_hash()
+                )
+            });
+        }
+
+        match provider.block_hash(number)? {
+            Some(canonical) if canonical == hash => {}
+            canonical => findings
+                .error(|| format!("block {number}: canonical hash {canonical:?}, expected {hash}")),
+        }
+
+        match provider.block_number(hash)? {
+            Some(header_number) if header_number == number => {}
+            header_number => findings
+                .error(|| format!("block {number}: header number of {hash} is {header_number:?}")),
+        }
+
+        parent_hash = Some(hash);
+    }
+
+    Ok(())
+}
+
+/// Verifies that `BlockBodyIndices` are contiguous, and that the transactions of each block are
+/// in the transactions static file of the block.
+fn verify_body_indices<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    findings: &mut Findings,
+) -> eyre::Result<()>
+where
+    Provider: BlockBodyIndicesProvider + StaticFileProviderFactory,
+{
+    let static_file_provider = provider.static_file_provider();
+    let mut next_tx_num = match range.start().checked_sub(1) {
+        Some(parent) => provider.block_body_indices(parent)?.map(|indices| indices.next_tx_num()),
+        None => Some(0),
+    };
+
+    for number in range {
+        findings.entries += 1;
+        let Some(indices) = provider.block_body_indices(number)? else {
+            findings.error(|| format!("block {number}: body indices are missing"));
+            next_tx_num = None;
+            continue
+        };
+
+        if let Some(next_tx_num) = next_tx_num &&
+            indices.first_tx_num() != next_tx_num
+        {
+            findings.error(|| {
+                format!(
+                    "block {number}: first transaction {}, expected {next_tx_num}",
+                    indices.first_tx_num()
+                )
+            });
+        }
+        next_tx_num = Some(indices.next_tx_num());
+
+        if indices.tx_count() == 0 {
+            continue
+        }
+        let tx_range = static_file_provider
+            .get_segment_provider_for_block(StaticFileSegment::Transactions, number, None)
+            .ok()
+            .and_then(|jar| jar.user_header().tx_range());
+        match tx_range {
+            Some(tx_range)
+                if tx_range.contains(indices.first_tx_num()) &&
+                    tx_range.contains(indices.last_tx_num()) => {}
+            tx_range => findings.error(|| {
+                format!(
+                    "block {number}: transactions {:?} are not in the static file transactions {tx_range:?}",
+                    indices.tx_num_range()
+                )
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that the hash of every transaction maps to its transaction number.
+fn verify_transaction_hashes<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    findings: &mut Findings,
+) -> eyre::Result<()>
+where
+    Provider: BlockBodyIndicesProvider + TransactionsProvider,
+{
+    let Some(tx_range) = tx_range(provider, range, findings)? else { return Ok(()) };
+
+    for (tx_num, transaction) in tx_range.clone().zip(provider.transactions_by_tx_range(tx_range)?)
+    {
+        findings.entries += 1;
+        let hash = *transaction.tx_hash();
+        match provider.transaction_id(hash)? {
+            Some(number) if number == tx_num => {}
+            number => findings.error(|| {
+                format!("transaction {tx_num}: hash {hash} maps to transaction {number:?}")
+            }),
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that the stored senders match the senders recovered from the signatures.
+fn verify_senders<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    findings: &mut Findings,
+) -> eyre::Result<()>
+where
+    Provider: BlockBodyIndicesProvider + TransactionsProvider,
+{
+    let Some(tx_range) = tx_range(provider, range, findings)? else { return Ok(()) };
+
+    let transactions = provider.transactions_by_tx_range(tx_range.clone())?;
+    let senders = provider.senders_by_tx_range(tx_range.clone())?;
+    if senders.len() != transactions.len() {
+        findings.error(|| {
+            format!(
+                "transactions {tx_range:?}: {} senders for {} transactions",
+                senders.len(),
+                transactions.len()
+            )
+        });
+    }
+
+    let recovered = transactions
+        .par_iter()
+        .map(|transaction| transaction.recover_signer().ok())
+        .collect::<Vec<_>>();
+    for ((tx_num, sender), recovered) in tx_range.zip(senders).zip(recovered) {
+        findings.entries += 1;
+        if recovered != Some(sender) {
+            findings.error(|| {
+                format!("transaction {tx_num}: sender {sender}, recovered {recovered:?}")
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Verifies that every account and storage changeset is present in the history indices.
+fn verify_history<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    findings: &mut Findings,
+) -> eyre::Result<()>
+where
+    Provider: DBProvider
+        + BlockNumReader
+        + ChangeSetReader
+        + StorageChangeSetReader
+        + PruneCheckpointReader
+        + NodePrimitivesProvider
+        + StorageSettingsCache
+        + RocksDBProviderFactory,
+{
+    let visible_tip = provider.best_block_number()?;
+    // Storage history may be pruned further than account history.
+    let storage_pruned_to = provider
+        .get_prune_checkpoint(PruneSegment::StorageHistory)?
+        .and_then(|checkpoint| checkpoint.block_number);
+
+    provider.with_rocksdb_snapshot(|rocksdb| {
+        let mut reader = EitherReader::new_accounts_history(provider, rocksdb)?;
+        for block in range.clone() {
+            for changeset in provider.account_block_changeset(block)? {
+                findings.entries += 1;
+                let info =
+                    reader.account_history_info(changeset.address, block, None, visible_tip)?;
+                if !matches!(info, HistoryInfo::InChangeset(changed) if changed == block) {
+                    findings.error(|| {
+                        format!(
+                            "block {block}: account {} is not indexed, found {info:?}",
+                            changeset.address
+                        )
+                    });
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    provider.with_rocksdb_snapshot(|rocksdb| {
+        let mut reader = EitherReader::new_storages_history(provider, rocksdb)?;
+        for block in range {
+            if storage_pruned_to.is_some_and(|pruned_to| block <= pruned_to) {
+                continue
+            }
+            for (key, entry) in provider.storage_changeset(block)? {
+                findings.entries += 1;
+                let address = key.address();
+                let info =
+                    reader.storage_history_info(address, entry.key, block, None, visible_tip)?;
+                if !matches!(info, HistoryInfo::InChangeset(changed) if changed == block) {
+                    findings.error(|| {
+                        format!(
+                            "block {block}: storage slot {} of {address} is not indexed, found {info:?}",
+                            entry.key
+                        )
+                    });
+                }
+            }
+        }
+        Ok(())
+    })?;
+
+    Ok(())
+}
+
+/// Verifies that the state root computed from the trie matches the header at the tip.
+fn verify_state_root<N: ProviderNodeTypes>(
+    factory: &ProviderFactory<N>,
+    tip: BlockNumber,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let provider = factory.provider()?.disable_long_read_transaction_safety();
+    let Some(header) = provider.header_by_number(tip)? else {
+        findings.error(|| format!("block {tip}: header is missing"));
+        return Ok(())
+    };
+
+    info!(target: "reth::cli", tip, "Computing state root");
+    let tx = provider.tx_ref();
+    let root = reth_trie_db::with_adapter!(provider, |A| DbStateRoot::<_, A>::from_tx(tx).root())?;
+    findings.entries += 1;
+    if root != header.state_root() {
+        findings
+            .error(|| format!("block {tip}: state root {root}, expected {}", header.state_root()));
+    }
+
+    Ok(())
+}
+
+/// Verifies that the state root persisted by `TrieDB` matches the header of its block.
+///
+/// The trie tables in MDBX aren't maintained if `TrieDB` is the state database.
+fn verify_triedb_state_root<Provider: HeaderProvider>(
+    provider: &Provider,
+    tip: BlockNumber,
+    findings: &mut Findings,
+) -> eyre::Result<()> {
+    let (block, root) = get_global_triedb().latest_persist_state().map_err(ProviderError::other)?;
+    if block != tip {
+        info!(target: "reth::cli", block, tip, "TrieDB state is not persisted at the tip, verifying its last persisted block");
+    }
+
+    findings.entries += 1;
+    let Some(header) = provider.header_by_number(block)? else {
+        findings.error(|| format!("block {block}: header is missing"));
+        return Ok(())
+    };
+    if root != header.state_root() {
+        findings.error(|| {
+            format!("block {block}: TrieDB state root {root}, expected {}", header.state_root())
+        });
+    }
+
+    Ok(())
+}
+
+/// Returns the range of transactions of the blocks, if there are any.
+///
+/// Missing body indices are recorded as an inconsistency.
+fn tx_range<Provider: BlockBodyIndicesProvider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    findings: &mut Findings,
+) -> eyre::Result<Option<RangeInclusive<u64>>> {
+    let (Some(first), Some(last)) =
+        (provider.block_body_indices(*range.start())?, provider.block_body_indices(*range.end())?)
+    else {
+        findings.error(|| format!("blocks {range:?}: body indices are missing"));
+        return Ok(None)
+    };
+    if last.next_tx_num() <= first.first_tx_num() {
+        return Ok(None)
+    }
+    Ok(Some(first.first_tx_num()..=last.next_tx_num() - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::{Address, B256};
+    use reth_db_api::{
+        models::{AccountBeforeTx, StorageSettings, StoredBlockBodyIndices},
+        transaction::DbTxMut,
+    };
+    use reth_provider::{
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockWriter, HistoryWriter, StageCheckpointWriter,
+    };
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+
+    const TIP: BlockNumber = 3;
+
+    /// Inserts blocks `0..=TIP` with two transactions each, and returns the block hashes and the
+    /// transaction hashes.
+    fn setup() -> (ProviderFactory<MockNodeTypesWithDB>, Vec<B256>, Vec<B256>) {
+        let factory = create_test_provider_factory();
+        factory.set_storage_settings_cache(StorageSettings::v1());
+
+        let blocks = random_block_range(
+            &mut generators::rng(),
+            0..=TIP,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 2..3, ..Default::default() },
+        );
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider_rw.insert_block(&block.clone().try_recover().unwrap()).unwrap();
+        }
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(TIP)).unwrap();
+        provider_rw.commit().unwrap();
+
+        let block_hashes = blocks.iter().map(|block| block.hash()).collect();
+        let tx_hashes = blocks
+            .iter()
+            .flat_map(|block| block.body().transactions.iter().map(|tx| *tx.tx_hash()))
+            .collect();
+        (factory, block_hashes, tx_hashes)
+    }
+
+    fn verify(factory: &ProviderFactory<MockNodeTypesWithDB>, check: Check) -> Findings {
+        let command = Command::try_parse_from(["reth"]).unwrap();
+        command.verify_chunk(factory, check, 0..=TIP).unwrap()
+    }
+
+    #[test]
+    fn parse_verify_command() {
+        let command = Command::try_parse_from([
+            "reth",
+            "--checks",
+            "headers,history",
+            "--to",
+            "100",
+            "--report",
+            "report.json",
+        ])
+        .unwrap();
+        assert_eq!(command.checks, vec![Check::Headers, Check::History]);
+        assert_eq!(command.from, 0);
+        assert_eq!(command.to, Some(100));
+        assert_eq!(command.chunk_size, 10_000);
+        assert_eq!(command.report, Some(PathBuf::from("report.json")));
+    }
+
+    #[test]
+    fn verify_headers_detects_missing_header_number() {
+        let (factory, block_hashes, _) = setup();
+        let findings = verify(&factory, Check::Headers);
+        assert_eq!((findings.entries, findings.error_count), (TIP + 1, 0));
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.tx_ref().delete::<tables::HeaderNumbers>(block_hashes[2], None).unwrap();
+        provider_rw.commit().unwrap();
+
+        let findings = verify(&factory, Check::Headers);
+        assert_eq!(findings.error_count, 1);
+        assert!(findings.errors[0].starts_with("block 2: header number"));
+    }
+
+    #[test]
+    fn verify_body_indices_detects_gap() {
+        let (factory, _, _) = setup();
+        let findings = verify(&factory, Check::BodyIndices);
+        assert_eq!((findings.entries, findings.error_count), (TIP + 1, 0));
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let indices = provider_rw.block_body_indices(2).unwrap().unwrap();
+        let shifted = StoredBlockBodyIndices {
+            first_tx_num: indices.first_tx_num + 1,
+            tx_count: indices.tx_count,
+        };
+        provider_rw.tx_ref().put::<tables::BlockBodyIndices>(2, shifted).unwrap();
+        provider_rw.commit().unwrap();
+
+        // Both the shifted block and its child don't continue their parent.
+        let findings = verify(&factory, Check::BodyIndices);
+        assert_eq!(findings.error_count, 2);
+        assert!(findings.errors[0].starts_with("block 2: first transaction"));
+        assert!(findings.errors[1].starts_with("block 3: first transaction"));
+    }
+
+    #[test]
+    fn verify_transaction_hashes_detects_missing_hash() {
+        let (factory, _, tx_hashes) = setup();
+        let findings = verify(&factory, Check::TransactionHashes);
+        assert_eq!((findings.entries, findings.error_count), (tx_hashes.len() as u64, 0));
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.tx_ref().delete::<tables::TransactionHashNumbers>(tx_hashes[3], None).unwrap();
+        provider_rw.commit().unwrap();
+
+        let findings = verify(&factory, Check::TransactionHashes);
+        assert_eq!(findings.error_count, 1);
+        assert!(findings.errors[0].starts_with("transaction 3:"));
+    }
+
+    #[test]
+    fn verify_senders_detects_wrong_sender() {
+        let (factory, _, tx_hashes) = setup();
+        let findings = verify(&factory, Check::Senders);
+        assert_eq!((findings.entries, findings.error_count), (tx_hashes.len() as u64, 0));
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.tx_ref().put::<tables::TransactionSenders>(5, Address::ZERO).unwrap();
+        provider_rw.commit().unwrap();
+
+        let findings = verify(&factory, Check::Senders);
+        assert_eq!(findings.error_count, 1);
+        assert!(findings.errors[0].starts_with("transaction 5:"));
+    }
+
+    #[test]
+    fn verify_history_detects_unindexed_changeset() {
+        let (factory, _, _) = setup();
+        let address = Address::with_last_byte(1);
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .tx_ref()
+            .put::<tables::AccountChangeSets>(2, AccountBeforeTx { address, info: None })
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let findings = verify(&factory, Check::History);
+        assert_eq!((findings.entries, findings.error_count), (1, 1));
+        assert!(findings.errors[0].starts_with(&format!("block 2: account {address}")));
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw.update_history_indices(0..=TIP).unwrap();
+        provider_rw.commit().unwrap();
+
+        let findings = verify(&factory, Check::History);
+        assert_eq!((findings.entries, findings.error_count), (1, 0));
+    }
+
+    #[test]
+    fn verify_state_root_detects_mismatch() {
+        // The state is empty, so the computed root doesn't match the zero root of the generated
+        // tip.
+        let (factory, _, _) = setup();
+        let mut findings = Findings::new(10);
+        verify_state_root(&factory, TIP, &mut findings).unwrap();
+        assert_eq!((findings.entries, findings.error_count), (1, 1));
+        assert!(findings.errors[0].starts_with(&format!("block {TIP}: state root")));
+    }
+
+    #[test]
+    fn resume_extends_report_to_new_tip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        let command =
+            Command::try_parse_from(["reth", "--report", path.to_str().unwrap()]).unwrap();
+
+        let mut report = command.load_report(0, 10).unwrap();
+        for check in [Check::Headers, Check::StateRoot] {
+            report.checks.insert(
+                check,
+                CheckReport { done: true, verified_to: Some(10), ..Default::default() },
+            );
+        }
+        command.save_report(&report).unwrap();
+
+        // An unwound tip can't be resumed.
+        assert!(command.load_report(0, 5).is_err());
+
+        let report = command.load_report(0, 20).unwrap();
+        assert_eq!(report.to, 20);
+        assert!(!report.checks.contains_key(&Check::StateRoot));
+        let headers = &report.checks[&Check::Headers];
+        assert!(!headers.done);
+        assert_eq!(headers.verified_to, Some(10));
+    }
+}
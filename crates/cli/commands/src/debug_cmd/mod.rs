@@ -0,0 +1,80 @@
+//! `reth debug` command for debugging the consensus engine
+
+use crate::{
+    common::{CliComponentsBuilder, CliNodeTypes},
+    launcher::Launcher,
+    node::NoArgs,
+};
+use clap::{Parser, Subcommand};
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use std::{fmt, sync::Arc};
+
+pub mod replay_engine;
+pub mod reproduce;
+pub mod stateless;
+pub mod txpool_replay;
+
+/// `reth debug` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs> {
+    #[command(subcommand)]
+    command: Subcommands<C, Ext>,
+}
+
+/// `reth debug` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> {
+    /// Starts a node and replays the engine API messages stored with
+    /// `--debug.engine-api-store` against it
+    #[command(name = "replay-engine")]
+    ReplayEngine(Box<replay_engine::Command<C, Ext>>),
+    /// Re-executes an invalid block from a bundle written by the `witness` invalid block hook
+    #[command(name = "reproduce")]
+    Reproduce(reproduce::Command<C>),
+    /// Validates a block offline using only its execution witness
+    #[command(name = "stateless")]
+    Stateless(stateless::Command<C>),
+    /// Replays transaction pool events recorded with `--txpool.record` against a fresh pool
+    #[command(name = "txpool-replay")]
+    TxPoolReplay(Box<txpool_replay::Command>),
+}
+
+impl<C, Ext> Command<C, Ext>
+where
+    C: ChainSpecParser,
+    C::ChainSpec: EthChainSpec + EthereumHardforks,
+    Ext: clap::Args + fmt::Debug,
+{
+    /// Execute `debug` command
+    pub async fn execute<N, L>(
+        self,
+        ctx: CliContext,
+        launcher: L,
+        components: impl CliComponentsBuilder<N>,
+    ) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+        L: Launcher<C, Ext>,
+    {
+        match self.command {
+            Subcommands::ReplayEngine(command) => command.execute(ctx, launcher).await,
+            Subcommands::Reproduce(command) => command.execute::<N>(components).await,
+            Subcommands::Stateless(command) => command.execute::<N>(components).await,
+            Subcommands::TxPoolReplay(command) => command.execute().await,
+        }
+    }
+}
+
+impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Command<C, Ext> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Subcommands::ReplayEngine(command) => command.chain_spec(),
+            Subcommands::Reproduce(command) => command.chain_spec(),
+            Subcommands::Stateless(command) => command.chain_spec(),
+            Subcommands::TxPoolReplay(_) => None,
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! `reth debug replay-engine` command
+
+use crate::{
+    launcher::Launcher,
+    node::{NoArgs, NodeCommand},
+};
+use clap::Parser;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_cli_runner::CliContext;
+use std::{fmt, path::PathBuf, sync::Arc};
+
+/// `reth debug replay-engine` command
+///
+/// Starts a node and sends the engine API messages stored in a directory to its engine, in the
+/// order they were received. The latency of every message and all responses that differ from the
+/// recorded ones are logged. The command exits once the last message was answered, and fails if
+/// any response differed.
+///
+/// The node should be started on a fresh data directory, or one that's at the state the messages
+/// were recorded at.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs> {
+    /// The path to the directory with the engine API messages stored with
+    /// `--debug.engine-api-store`.
+    #[arg(long = "dir", value_name = "PATH")]
+    dir: PathBuf,
+
+    #[command(flatten)]
+    node: NodeCommand<C, Ext>,
+}
+
+impl<C, Ext> Command<C, Ext>
+where
+    C: ChainSpecParser,
+    C::ChainSpec: EthChainSpec + EthereumHardforks,
+    Ext: clap::Args + fmt::Debug,
+{
+    /// Execute `debug replay-engine` command
+    pub async fn execute<L>(self, ctx: CliContext, launcher: L) -> eyre::Result<()>
+    where
+        L: Launcher<C, Ext>,
+    {
+        let Self { dir, mut node } = self;
+        node.debug.engine_api_replay = Some(dir);
+        node.execute(ctx, launcher).await
+    }
+}
+
+impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug> Command<C, Ext> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        self.node.chain_spec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_replay_engine() {
+        let command: Command<EthereumChainSpecParser> =
+            Command::try_parse_from(["reth", "--dir", "engine-messages", "--datadir", "replay"])
+                .unwrap();
+        assert_eq!(command.dir, PathBuf::from("engine-messages"));
+        assert_eq!(command.node.debug.engine_api_replay, None);
+    }
+}
@@ -0,0 +1,135 @@
+//! `reth debug reproduce` command
+
+use crate::common::{CliComponentsBuilder, CliNodeComponents, CliNodeTypes};
+use alloy_consensus::BlockHeader;
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_invalid_block_hooks::InvalidBlockBundle;
+use reth_node_api::BlockTy;
+use reth_primitives_traits::SealedBlock;
+use reth_stateless::stateless_validation;
+use std::{path::PathBuf, sync::Arc};
+use tracing::*;
+
+/// `reth debug reproduce` command
+///
+/// Re-executes an invalid block from a bundle written by the `witness` invalid block hook. The
+/// block is validated statelessly on top of the execution witness of the bundle, so neither a
+/// database nor the chain history is needed.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The chain the block was rejected on. It must match the chain recorded in the bundle.
+    ///
+    /// The bundle only records the genesis configuration of the chain, which lacks the parameters
+    /// of chain specific forks, so the full chain spec is required.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    /// The path to the `<block_number>_<block_hash>.bundle.json` file.
+    #[arg(value_name = "BUNDLE")]
+    bundle: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug reproduce` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let bundle = InvalidBlockBundle::read(&self.bundle)
+            .wrap_err_with(|| format!("failed to read bundle {}", self.bundle.display()))?;
+
+        let chain = self.chain;
+        if bundle.chain_id != chain.chain_id() || bundle.genesis_hash != chain.genesis_hash() {
+            eyre::bail!(
+                "bundle was recorded on chain {} with genesis {}, but --chain is chain {} with \
+                 genesis {}",
+                bundle.chain_id,
+                bundle.genesis_hash,
+                chain.chain_id(),
+                chain.genesis_hash()
+            )
+        }
+        if bundle.hardforks != chain.display_hardforks().to_string() {
+            warn!(
+                target: "reth::cli",
+                recorded = %bundle.hardforks,
+                "Hardfork activations differ from the ones recorded in the bundle"
+            );
+        }
+
+        let block = SealedBlock::seal_slow(bundle.decode_block::<BlockTy<N>>()?).try_recover()?;
+        info!(
+            target: "reth::cli",
+            number = block.number(),
+            hash = %block.hash(),
+            node_version = %bundle.node_version,
+            error = %bundle.error,
+            "Reproducing invalid block"
+        );
+
+        let components = components(chain);
+        let result = stateless_validation(
+            &block,
+            &bundle.witness,
+            components.evm_config(),
+            components.consensus(),
+        )
+        .map_err(|err| err.to_string());
+
+        match result {
+            Ok(_) => eyre::bail!(
+                "block {} is valid when re-executed, the recorded error was: {}",
+                block.hash(),
+                bundle.error
+            ),
+            Err(err) if err == bundle.error => {
+                info!(target: "reth::cli", %err, "Reproduced the recorded error");
+            }
+            Err(err) => eyre::bail!(
+                "block {} is invalid when re-executed, but with a different error than the \
+                 recorded one: {err}, the recorded error was: {}",
+                block.hash(),
+                bundle.error
+            ),
+        }
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.chain)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_reproduce() {
+        let command: Command<EthereumChainSpecParser> =
+            Command::try_parse_from(["reth", "--chain", "sepolia", "1_0xabcd.bundle.json"])
+                .unwrap();
+        assert_eq!(command.bundle, PathBuf::from("1_0xabcd.bundle.json"));
+        assert_eq!(command.chain.chain_id(), 11155111);
+
+        // The bundle doesn't record the full chain spec
+        assert!(Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "1_0xabcd.bundle.json"
+        ])
+        .is_err());
+    }
+}
@@ -0,0 +1,103 @@
+//! `reth debug stateless` command
+
+use crate::common::{CliComponentsBuilder, CliNodeComponents, CliNodeTypes};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::Bytes;
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use clap::Parser;
+use eyre::WrapErr;
+use reth_chainspec::{EthChainSpec, EthereumHardforks};
+use reth_cli::chainspec::ChainSpecParser;
+use reth_node_api::BlockTy;
+use reth_primitives_traits::SealedBlock;
+use reth_stateless::stateless_validation;
+use serde::de::DeserializeOwned;
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tracing::*;
+
+/// `reth debug stateless` command
+///
+/// Validates a block offline using only its execution witness. The block is executed on top of
+/// the state of the witness, and its receipts root and post-state root are checked against the
+/// header.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    /// The chain the block belongs to.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::default_value(),
+        value_parser = C::parser()
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    /// The path to the RLP-encoded block as a JSON hex string, as returned by
+    /// `debug_getRawBlock`.
+    #[arg(long, value_name = "PATH")]
+    block: PathBuf,
+
+    /// The path to the execution witness of the block, as returned by `debug_executionWitness`.
+    #[arg(long, value_name = "PATH")]
+    witness: PathBuf,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec + EthereumHardforks>> Command<C> {
+    /// Execute `debug stateless` command
+    pub async fn execute<N>(self, components: impl CliComponentsBuilder<N>) -> eyre::Result<()>
+    where
+        N: CliNodeTypes<ChainSpec = C::ChainSpec>,
+    {
+        let block: Bytes = read_json(&self.block)?;
+        let block =
+            SealedBlock::seal_slow(BlockTy::<N>::decode(&mut block.as_ref())?).try_recover()?;
+        let witness: ExecutionWitness = read_json(&self.witness)?;
+
+        let components = components(self.chain.clone());
+        let output =
+            stateless_validation(&block, &witness, components.evm_config(), components.consensus())
+                .wrap_err_with(|| format!("block {} is invalid", block.hash()))?;
+
+        info!(
+            target: "reth::cli",
+            number = block.number(),
+            hash = %block.hash(),
+            gas_used = output.result.gas_used,
+            "Block is valid"
+        );
+
+        Ok(())
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub const fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.chain)
+    }
+}
+
+/// Reads and deserializes a JSON file.
+fn read_json<T: DeserializeOwned>(path: &Path) -> eyre::Result<T> {
+    serde_json::from_slice(&reth_fs_util::read(path)?)
+        .wrap_err_with(|| format!("failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_stateless() {
+        let command: Command<EthereumChainSpecParser> =
+            Command::try_parse_from(["reth", "--block", "block.json", "--witness", "witness.json"])
+                .unwrap();
+        assert_eq!(command.block, PathBuf::from("block.json"));
+        assert_eq!(command.witness, PathBuf::from("witness.json"));
+    }
+}
@@ -0,0 +1,59 @@
+//! `reth debug txpool-replay` command
+
+use clap::Parser;
+use eyre::WrapErr;
+use reth_node_core::{args::TxPoolArgs, cli::config::RethTransactionPoolConfig};
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore,
+    replay::{read_records, PoolReplay, ReplayNonces, ReplayValidator},
+    ConfiguredOrdering, EthPooledTransaction, Pool,
+};
+use std::{io::BufReader, path::PathBuf};
+use tracing::*;
+
+/// `reth debug txpool-replay` command
+///
+/// Replays the transaction pool events recorded with `--txpool.record` against a fresh pool
+/// with the given `--txpool.*` configuration and prints insertion latencies, sub-pool sizes and
+/// eviction decisions compared to the recording.
+#[derive(Debug, Parser)]
+pub struct Command {
+    /// The path to the recording written with `--txpool.record`.
+    #[arg(long, value_name = "PATH")]
+    recording: PathBuf,
+
+    /// The configuration of the replayed pool.
+    #[command(flatten)]
+    txpool: TxPoolArgs,
+}
+
+impl Command {
+    /// Execute `debug txpool-replay` command
+    pub async fn execute(self) -> eyre::Result<()> {
+        let file = BufReader::new(reth_fs_util::open(&self.recording)?);
+        let records = read_records(file)
+            .collect::<Result<Vec<_>, _>>()
+            .wrap_err_with(|| format!("failed to read {}", self.recording.display()))?;
+        info!(target: "reth::cli", records = records.len(), "Replaying transaction pool recording");
+
+        let nonces = ReplayNonces::default();
+        let pool = Pool::new(
+            ReplayValidator::<EthPooledTransaction>::new(nonces.clone()),
+            ConfiguredOrdering::new(&self.txpool.ordering_config()),
+            InMemoryBlobStore::default(),
+            self.txpool.pool_config(),
+        );
+        let report = PoolReplay::new(pool).with_nonces(nonces).run(records).await;
+
+        println!("{report}");
+        if report.placement.mismatches() > 0 || report.evictions.replayed_only().next().is_some() {
+            info!(
+                target: "reth::cli",
+                placement_mismatches = report.placement.mismatches(),
+                replayed_only_evictions = report.evictions.replayed_only().count(),
+                "Replayed pool diverged from the recording"
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+//! `reth exex` command for maintaining execution extensions
+
+use crate::common::CliNodeTypes;
+use clap::{Parser, Subcommand};
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use std::sync::Arc;
+
+pub mod wal;
+
+/// `reth exex` command
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(subcommand)]
+    command: Subcommands<C>,
+}
+
+/// `reth exex` subcommands
+#[derive(Debug, Subcommand)]
+pub enum Subcommands<C: ChainSpecParser> {
+    /// Inspect, export and compact the `ExEx` Write-Ahead Log
+    #[command(name = "wal")]
+    Wal(wal::Command<C>),
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> Command<C> {
+    /// Execute `exex` command
+    pub fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        match self.command {
+            Subcommands::Wal(command) => command.execute::<N>(),
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        match &self.command {
+            Subcommands::Wal(command) => command.chain_spec(),
+        }
+    }
+}
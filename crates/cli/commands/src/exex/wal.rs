@@ -0,0 +1,327 @@
+//! `reth exex wal` command for maintaining the `ExEx` Write-Ahead Log
+
+use crate::common::CliNodeTypes;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::BlockNumber;
+use clap::{Parser, Subcommand};
+use comfy_table::{Cell, Row, Table as ComfyTable};
+use human_bytes::human_bytes;
+use reth_chainspec::EthChainSpec;
+use reth_cli::chainspec::ChainSpecParser;
+use reth_db::lockfile::StorageLock;
+use reth_exex::{
+    serde_bincode_compat, ExExNotification, Wal, WalCompaction, DEFAULT_WAL_COMPACTION_FILE_SIZE,
+};
+use reth_node_api::NodePrimitives;
+use reth_node_core::args::{parse_byte_size, DatadirArgs};
+use reth_provider::Chain;
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::RangeInclusive,
+    path::PathBuf,
+    sync::Arc,
+};
+use tracing::info;
+
+/// `reth exex wal` command
+///
+/// The node must not be running while the WAL is compacted.
+#[derive(Debug, Parser)]
+pub struct Command<C: ChainSpecParser> {
+    #[command(flatten)]
+    datadir: DatadirArgs,
+
+    /// The chain this node is running.
+    ///
+    /// Possible values are either a built-in chain or the path to a chain specification file.
+    #[arg(
+        long,
+        value_name = "CHAIN_OR_PATH",
+        long_help = C::help_message(),
+        default_value = C::default_value(),
+        value_parser = C::parser(),
+        global = true
+    )]
+    chain: Arc<C::ChainSpec>,
+
+    #[command(subcommand)]
+    command: Subcommands,
+}
+
+#[derive(Debug, Subcommand)]
+enum Subcommands {
+    /// Lists the files of the WAL with the blocks they contain
+    List,
+    /// Shows a summary of the notification stored in a WAL file
+    Show(ShowCommand),
+    /// Exports the notifications touching a range of blocks as JSON lines
+    Export(ExportCommand),
+    /// Merges small files and drops the state diffs and trie data of blocks processed by all
+    /// `ExExes`
+    Compact(CompactCommand),
+}
+
+/// The arguments for the `reth exex wal show` command
+#[derive(Debug, Parser)]
+struct ShowCommand {
+    /// The ID of the WAL file
+    id: u32,
+}
+
+/// The arguments for the `reth exex wal export` command
+#[derive(Debug, Parser)]
+struct ExportCommand {
+    /// Only export notifications touching blocks at or above this block
+    #[arg(long)]
+    from: Option<BlockNumber>,
+
+    /// Only export notifications touching blocks at or below this block
+    #[arg(long)]
+    to: Option<BlockNumber>,
+
+    /// The file to write the notifications to, stdout by default
+    #[arg(long, short, value_name = "FILE")]
+    output: Option<PathBuf>,
+}
+
+/// The arguments for the `reth exex wal compact` command
+#[derive(Debug, Parser)]
+struct CompactCommand {
+    /// Consecutive committed notifications are merged as long as the merged file stays below
+    /// this size
+    #[arg(
+        long,
+        value_parser = parse_byte_size,
+        default_value_t = DEFAULT_WAL_COMPACTION_FILE_SIZE as usize
+    )]
+    merge_below: usize,
+
+    /// The lowest block processed by all `ExExes`. The state diffs and trie data of committed
+    /// blocks up to and including this block are dropped
+    #[arg(long)]
+    finished_height: Option<BlockNumber>,
+}
+
+impl<C: ChainSpecParser<ChainSpec: EthChainSpec>> Command<C> {
+    /// Execute `exex wal` command
+    pub fn execute<N: CliNodeTypes<ChainSpec = C::ChainSpec>>(self) -> eyre::Result<()> {
+        let data_dir = self.datadir.resolve_datadir(self.chain.chain());
+        let path = data_dir.exex_wal();
+        if !path.exists() {
+            eyre::bail!("No ExEx WAL found at {}", path.display())
+        }
+
+        // Compaction rewrites the WAL, so the node must not be running. Holding the lock of its
+        // database for the duration of the compaction also keeps it from starting.
+        let _lock = matches!(self.command, Subcommands::Compact(_))
+            .then(|| StorageLock::try_acquire(&data_dir.db()))
+            .transpose()
+            .map_err(|err| {
+                eyre::eyre!("The node must be stopped to compact the ExEx WAL: {err}")
+            })?;
+        let wal = Wal::<N::Primitives>::new(&path)?;
+
+        match self.command {
+            Subcommands::List => list(&wal),
+            Subcommands::Show(command) => show(&wal, command.id),
+            Subcommands::Export(command) => command.execute(&wal),
+            Subcommands::Compact(command) => command.execute(&wal),
+        }
+    }
+}
+
+impl<C: ChainSpecParser> Command<C> {
+    /// Returns the underlying chain being used to run this command
+    pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
+        Some(&self.chain)
+    }
+}
+
+/// Prints a table of the WAL files.
+fn list<N: NodePrimitives>(wal: &Wal<N>) -> eyre::Result<()> {
+    let files = wal.files()?;
+
+    let mut table = ComfyTable::new();
+    table.load_preset(comfy_table::presets::ASCII_MARKDOWN);
+    table.set_header(["ID", "Committed Blocks", "Reverted Blocks", "Size"]);
+    for file in &files {
+        let mut row = Row::new();
+        row.add_cell(Cell::new(file.id))
+            .add_cell(Cell::new(format_range(file.committed.as_ref())))
+            .add_cell(Cell::new(format_range(file.reverted.as_ref())))
+            .add_cell(Cell::new(human_bytes(file.size as f64)));
+        table.add_row(row);
+    }
+
+    let max_widths = table.column_max_content_widths();
+    let mut separator = Row::new();
+    for width in max_widths {
+        separator.add_cell(Cell::new("-".repeat(width as usize)));
+    }
+    table.add_row(separator);
+
+    let mut row = Row::new();
+    row.add_cell(Cell::new(format!("{} files", files.len())))
+        .add_cell(Cell::new(format!("{} blocks", wal.num_blocks())))
+        .add_cell(Cell::new(""))
+        .add_cell(Cell::new(human_bytes(wal.size_bytes() as f64)));
+    table.add_row(row);
+
+    println!("{table}");
+
+    Ok(())
+}
+
+/// Prints a summary of the notification in the given WAL file.
+fn show<N: NodePrimitives>(wal: &Wal<N>, id: u32) -> eyre::Result<()> {
+    let notification =
+        wal.notification(id)?.ok_or_else(|| eyre::eyre!("WAL file {id} not found"))?;
+
+    let kind = match &notification {
+        ExExNotification::ChainCommitted { .. } => "ChainCommitted",
+        ExExNotification::ChainReorged { .. } => "ChainReorged",
+        ExExNotification::ChainReverted { .. } => "ChainReverted",
+    };
+    println!("Notification: {kind}");
+    if let Some(chain) = notification.reverted_chain() {
+        print_chain("Reverted chain", &chain);
+    }
+    if let Some(chain) = notification.committed_chain() {
+        print_chain("Committed chain", &chain);
+    }
+
+    Ok(())
+}
+
+/// Prints a summary of a chain of a notification.
+fn print_chain<N: NodePrimitives>(title: &str, chain: &Chain<N>) {
+    let first = chain.first();
+    let tip = chain.tip();
+    println!("{title}:");
+    println!("  Blocks: {} ({} blocks)", format_range(Some(&chain.range())), chain.len());
+    println!("  First block: {} (parent {})", first.hash(), first.parent_hash());
+    println!("  Tip: {}", tip.hash());
+    println!("  Transactions: {}", chain.transactions_iter().count());
+    println!("  Receipts: {}", chain.receipts_iter().count());
+    println!("  Changed accounts: {}", chain.execution_outcome().state().state.len());
+    println!("  Blocks with trie data: {}", chain.trie_data().len());
+}
+
+impl ExportCommand {
+    /// Execute `exex wal export` command
+    fn execute<N: NodePrimitives>(self, wal: &Wal<N>) -> eyre::Result<()> {
+        let range = self.from.unwrap_or_default()..=self.to.unwrap_or(BlockNumber::MAX);
+        let mut writer: Box<dyn Write> = match &self.output {
+            Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+            None => Box::new(BufWriter::new(io::stdout().lock())),
+        };
+
+        let mut exported = 0;
+        for notification in wal.iter_notifications()? {
+            let notification = notification?;
+            let touches_range = notification
+                .committed_chain()
+                .into_iter()
+                .chain(notification.reverted_chain())
+                .any(|chain| {
+                    chain.first().number() <= *range.end() && chain.tip().number() >= *range.start()
+                });
+            if !touches_range {
+                continue
+            }
+
+            serde_json::to_writer(
+                &mut writer,
+                &serde_bincode_compat::ExExNotification::from(&notification),
+            )?;
+            writeln!(writer)?;
+            exported += 1;
+        }
+        writer.flush()?;
+
+        if let Some(path) = &self.output {
+            info!(target: "reth::cli", exported, path = %path.display(), "Exported WAL notifications");
+        }
+
+        Ok(())
+    }
+}
+
+impl CompactCommand {
+    /// Execute `exex wal compact` command
+    fn execute<N: NodePrimitives>(self, wal: &Wal<N>) -> eyre::Result<()> {
+        let report = wal.compact(WalCompaction {
+            merge_below: self.merge_below as u64,
+            finished_height: self.finished_height,
+        })?;
+
+        info!(
+            target: "reth::cli",
+            files_before = report.files_before,
+            files_after = report.files_after,
+            size_before = %human_bytes(report.size_before as f64),
+            size_after = %human_bytes(report.size_after as f64),
+            saved = %human_bytes(report.saved() as f64),
+            "Compacted the ExEx WAL"
+        );
+
+        Ok(())
+    }
+}
+
+/// Formats an optional block range, or `-` if there is none.
+fn format_range(range: Option<&RangeInclusive<BlockNumber>>) -> String {
+    range.map_or_else(|| "-".to_string(), |range| format!("{}..={}", range.start(), range.end()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_ethereum_cli::chainspec::EthereumChainSpecParser;
+
+    #[test]
+    fn parse_wal_commands() {
+        let command = Command::<EthereumChainSpecParser>::try_parse_from(["reth", "list"]).unwrap();
+        assert!(matches!(command.command, Subcommands::List));
+
+        let command =
+            Command::<EthereumChainSpecParser>::try_parse_from(["reth", "show", "12"]).unwrap();
+        assert!(matches!(command.command, Subcommands::Show(ShowCommand { id: 12 })));
+
+        let command = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "export",
+            "--from",
+            "10",
+            "--to",
+            "20",
+            "-o",
+            "wal.jsonl",
+        ])
+        .unwrap();
+        let Subcommands::Export(export) = command.command else { panic!("expected export") };
+        assert_eq!(export.from, Some(10));
+        assert_eq!(export.to, Some(20));
+        assert_eq!(export.output, Some(PathBuf::from("wal.jsonl")));
+
+        let command =
+            Command::<EthereumChainSpecParser>::try_parse_from(["reth", "compact"]).unwrap();
+        let Subcommands::Compact(compact) = command.command else { panic!("expected compact") };
+        assert_eq!(compact.merge_below, DEFAULT_WAL_COMPACTION_FILE_SIZE as usize);
+        assert_eq!(compact.finished_height, None);
+
+        let command = Command::<EthereumChainSpecParser>::try_parse_from([
+            "reth",
+            "compact",
+            "--merge-below",
+            "4MB",
+            "--finished-height",
+            "100",
+        ])
+        .unwrap();
+        let Subcommands::Compact(compact) = command.command else { panic!("expected compact") };
+        assert_eq!(compact.merge_below, 4 * 1024 * 1024);
+        assert_eq!(compact.finished_height, Some(100));
+    }
+}
@@ -11,8 +11,10 @@
 pub mod common;
 pub mod config_cmd;
 pub mod db;
+pub mod debug_cmd;
 pub mod download;
 pub mod dump_genesis;
+pub mod exex;
 pub mod export_era;
 pub mod import;
 pub mod import_core;
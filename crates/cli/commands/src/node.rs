@@ -9,9 +9,9 @@ use reth_db::init_db;
 use reth_node_builder::NodeBuilder;
 use reth_node_core::{
     args::{
-        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, MetricArgs,
-        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, StateDbArgs, StaticFilesArgs,
-        StorageArgs, TxPoolArgs,
+        DatabaseArgs, DatadirArgs, DebugArgs, DevArgs, EngineArgs, EraArgs, ExExArgs, MetricArgs,
+        NetworkArgs, PayloadBuilderArgs, PruningArgs, RpcServerArgs, StateDbArgs,
+        StaticFileRecompressionArgs, StaticFilesArgs, StorageArgs, TxPoolArgs,
     },
     node_config::NodeConfig,
     version,
@@ -111,10 +111,18 @@ pub struct NodeCommand<C: ChainSpecParser, Ext: clap::Args + fmt::Debug = NoArgs
     #[command(flatten, next_help_heading = "ERA")]
     pub era: EraArgs,
 
+    /// All `ExEx` related arguments with --exex prefix
+    #[command(flatten, next_help_heading = "ExEx")]
+    pub exex: ExExArgs,
+
     /// All static files related arguments
     #[command(flatten, next_help_heading = "Static Files")]
     pub static_files: StaticFilesArgs,
 
+    /// Background static file recompression arguments
+    #[command(flatten, next_help_heading = "Static Files")]
+    pub static_file_recompression: StaticFileRecompressionArgs,
+
     /// All state database related arguments
     #[command(flatten, next_help_heading = "StateDB")]
     pub statedb: StateDbArgs,
@@ -177,7 +185,9 @@ where
             pruning,
             engine,
             era,
+            exex,
             static_files,
+            static_file_recompression,
             statedb,
             storage,
             ext,
@@ -202,7 +212,9 @@ where
             pruning,
             engine,
             era,
+            exex,
             static_files,
+            static_file_recompression,
             statedb,
             storage,
         };
@@ -5,7 +5,8 @@ use crate::common::{
     EnvironmentArgs,
 };
 use alloy_consensus::{transaction::TxHashRef, BlockHeader, TxReceipt};
-use alloy_primitives::{Address, B256, U256};
+use alloy_eips::NumHash;
+use alloy_primitives::{Address, BlockNumber, B256, U256};
 use clap::Parser;
 use eyre::WrapErr;
 use reth_chainspec::{EthChainSpec, EthereumHardforks, Hardforks};
@@ -15,8 +16,8 @@ use reth_consensus::FullConsensus;
 use reth_evm::{execute::Executor, ConfigureEvm};
 use reth_primitives_traits::{format_gas_throughput, Account, BlockBody, GotExpected};
 use reth_provider::{
-    BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory, ReceiptProvider,
-    StaticFileProviderFactory, TransactionVariant,
+    BlockHashReader, BlockNumReader, BlockReader, ChainSpecProvider, DatabaseProviderFactory,
+    ReceiptProvider, StaticFileProviderFactory, TransactionVariant,
 };
 use reth_revm::{
     database::StateProviderDatabase,
@@ -26,9 +27,14 @@ use reth_revm::{
     },
 };
 use reth_stages::stages::calculate_gas_used_from_headers;
-use reth_storage_api::{ChangeSetReader, DBProvider, StorageChangeSetReader};
+use reth_storage_api::{
+    AccountReader, ChangeSetReader, DBProvider, StateProvider, StorageChangeSetReader,
+};
+use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fs,
+    path::PathBuf,
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
@@ -63,8 +69,78 @@ pub struct Command<C: ChainSpecParser> {
     blocks_per_chunk: u64,
 
     /// Continues with execution when an invalid block is encountered and collects these blocks.
+    ///
+    /// State that diverges from the changesets or the plain state is collected as well, against
+    /// the last block of the verified range.
     #[arg(long)]
     skip_invalid_blocks: bool,
+
+    /// The file the progress is written to after every verified chunk.
+    ///
+    /// If the file already exists, the re-execution resumes from the progress recorded in it.
+    /// Without `--to`, the resumed re-execution ends at the block recorded in the file.
+    #[arg(long, value_name = "FILE")]
+    progress: Option<PathBuf>,
+}
+
+/// The progress of a `reth re-execute` run.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Progress {
+    /// The first re-executed block.
+    from: BlockNumber,
+    /// The block the re-execution ends at.
+    to: BlockNumber,
+    /// The number of blocks in a chunk.
+    blocks_per_chunk: u64,
+    /// The first blocks of the chunks that were executed and verified.
+    completed_chunks: BTreeSet<BlockNumber>,
+    /// The number of blocks in the completed chunks.
+    executed_blocks: u64,
+    /// The gas used by the blocks in the completed chunks.
+    executed_gas: u64,
+    /// The invalid blocks found so far, by block number.
+    invalid_blocks: BTreeMap<BlockNumber, InvalidBlock>,
+}
+
+impl Progress {
+    /// Records a chunk that was executed and verified.
+    fn record_chunk(&mut self, chunk: CompletedChunk) {
+        self.completed_chunks.insert(chunk.start);
+        self.executed_blocks += chunk.blocks;
+        self.executed_gas += chunk.gas;
+        for (block, error) in chunk.invalid_blocks {
+            self.record_invalid_block(block, &error);
+        }
+    }
+
+    /// Records a block that failed execution or validation.
+    fn record_invalid_block(&mut self, block: NumHash, error: &eyre::Report) {
+        error!(?error, ?block, "Invalid block");
+        self.invalid_blocks
+            .insert(block.number, InvalidBlock { hash: block.hash, error: format!("{error:?}") });
+    }
+}
+
+/// An invalid block recorded in the [`Progress`].
+#[derive(Debug, Serialize, Deserialize)]
+struct InvalidBlock {
+    /// The hash of the block.
+    hash: B256,
+    /// The error the block failed with.
+    error: String,
+}
+
+/// A chunk of blocks executed and verified by a worker.
+#[derive(Debug)]
+struct CompletedChunk {
+    /// The first block of the chunk.
+    start: BlockNumber,
+    /// The number of executed blocks.
+    blocks: u64,
+    /// The gas used by the executed blocks.
+    gas: u64,
+    /// The blocks of the chunk that failed execution or validation.
+    invalid_blocks: Vec<(NumHash, eyre::Report)>,
 }
 
 impl<C: ChainSpecParser> Command<C> {
@@ -72,6 +148,49 @@ impl<C: ChainSpecParser> Command<C> {
     pub fn chain_spec(&self) -> Option<&Arc<C::ChainSpec>> {
         Some(&self.env.chain)
     }
+
+    /// Loads the progress to resume from, or creates a new one.
+    ///
+    /// Without `--to`, `to` is the current tip, which may have moved since the progress file was
+    /// written. The block recorded in the file is kept then, as long as it is still available.
+    fn load_progress(&self, from: BlockNumber, to: BlockNumber) -> eyre::Result<Progress> {
+        let blocks_per_chunk = self.blocks_per_chunk;
+        let Some(path) = self.progress.as_ref().filter(|path| path.exists()) else {
+            return Ok(Progress { from, to, blocks_per_chunk, ..Default::default() })
+        };
+
+        let progress: Progress = serde_json::from_slice(&fs::read(path)?)
+            .wrap_err_with(|| format!("failed to parse progress file {}", path.display()))?;
+        eyre::ensure!(
+            progress.from == from &&
+                (progress.to == to || (self.to.is_none() && progress.to <= to)) &&
+                progress.blocks_per_chunk == blocks_per_chunk,
+            "Progress file {} covers blocks {}..={} in chunks of {}, remove it to re-execute \
+             blocks {from}..={to} in chunks of {blocks_per_chunk}",
+            path.display(),
+            progress.from,
+            progress.to,
+            progress.blocks_per_chunk
+        );
+        info!(
+            target: "reth::cli",
+            path = %path.display(),
+            completed_chunks = progress.completed_chunks.len(),
+            "Resuming from progress file"
+        );
+
+        Ok(progress)
+    }
+
+    /// Writes the progress to the progress file, if any.
+    fn save_progress(&self, progress: &Progress) -> eyre::Result<()> {
+        if let Some(path) = &self.progress {
+            reth_fs_util::atomic_write_file(path, |file| {
+                serde_json::to_writer_pretty(file, progress)
+            })?;
+        }
+        Ok(())
+    }
 }
 
 impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>> Command<C> {
@@ -117,15 +236,19 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             std::thread::available_parallelism().map(|n| n.get() as u64).unwrap_or(10)
         });
 
+        let mut progress = self.load_progress(min_block, max_block)?;
+        let max_block = progress.to;
+
         let total_gas = calculate_gas_used_from_headers(
             &provider_factory.static_file_provider(),
             min_block..=max_block,
         )?;
+        let completed_chunks = Arc::new(progress.completed_chunks.clone());
 
         let skip_invalid_blocks = self.skip_invalid_blocks;
         let blocks_per_chunk = self.blocks_per_chunk;
         let (stats_tx, mut stats_rx) = mpsc::unbounded_channel();
-        let (info_tx, mut info_rx) = mpsc::unbounded_channel();
+        let (chunk_tx, mut chunk_rx) = mpsc::unbounded_channel();
         let cancellation = CancellationToken::new();
         let _guard = cancellation.drop_guard();
 
@@ -138,9 +261,10 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
             let evm_config = components.evm_config().clone();
             let consensus = components.consensus().clone();
             let stats_tx = stats_tx.clone();
-            let info_tx = info_tx.clone();
+            let chunk_tx = chunk_tx.clone();
             let cancellation = cancellation.clone();
             let next_block = Arc::clone(&next_block);
+            let completed_chunks = Arc::clone(&completed_chunks);
             tasks.spawn_blocking(move || {
                 let executor_lifetime = Duration::from_secs(600);
                 let provider = provider_factory.database_provider_ro()?.disable_long_read_transaction_safety();
@@ -155,6 +279,32 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     }
                 };
 
+                // Verifies the state changes of the blocks up to `last_block` against the
+                // changesets, and the resulting state against the state after `last_block`.
+                let verify = |bundle: &BundleState, last_block: u64| {
+                    verify_bundle_against_changesets(&provider, bundle, last_block)?;
+                    verify_bundle_against_plain_state(
+                        &*provider_factory.history_by_block_number(last_block)?,
+                        bundle,
+                        last_block,
+                    )
+                };
+
+                // Verifies the state changes of the blocks up to `last_block`, collecting a
+                // divergence as an invalid block instead of failing with `--skip-invalid-blocks`.
+                let verify_or_skip = |bundle: &BundleState,
+                                      last_block: NumHash,
+                                      invalid_blocks: &mut Vec<(NumHash, eyre::Report)>| {
+                    match verify(bundle, last_block.number) {
+                        Err(err) if skip_invalid_blocks => {
+                            error!(block = ?last_block, %err, "State diverged");
+                            invalid_blocks.push((last_block, err));
+                            eyre::Ok(())
+                        }
+                        result => result,
+                    }
+                };
+
                 loop {
                     if cancellation.is_cancelled() {
                         break;
@@ -166,10 +316,16 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     if chunk_start >= max_block {
                         break;
                     }
+                    if completed_chunks.contains(&chunk_start) {
+                        continue;
+                    }
                     let chunk_end = (chunk_start + blocks_per_chunk).min(max_block);
 
                     let mut executor = evm_config.batch_executor(db_at(chunk_start - 1));
                     let mut executor_created = Instant::now();
+                    let mut chunk_blocks = 0;
+                    let mut chunk_gas = 0;
+                    let mut chunk_invalid_blocks = Vec::new();
 
                     'blocks: for block in chunk_start..chunk_end {
                         if cancellation.is_cancelled() {
@@ -184,10 +340,20 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                             Ok(result) => result,
                             Err(err) => {
                                 if skip_invalid_blocks {
-                                    executor =
-                                        evm_config.batch_executor(db_at(block.number()));
-                                    let _ =
-                                        info_tx.send((block, eyre::Report::new(err)));
+                                    // The blocks before the invalid one are verified before the
+                                    // executor continues from the state after it
+                                    let old_executor = std::mem::replace(
+                                        &mut executor,
+                                        evm_config.batch_executor(db_at(block.number())),
+                                    );
+                                    executor_created = Instant::now();
+                                    verify_or_skip(
+                                        &old_executor.into_state().take_bundle(),
+                                        NumHash::new(block.number() - 1, block.parent_hash()),
+                                        &mut chunk_invalid_blocks,
+                                    )?;
+                                    chunk_invalid_blocks
+                                        .push((block.num_hash(), eyre::Report::new(err)));
                                     continue
                                 }
                                 return Err(err.into())
@@ -243,9 +409,27 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
 
                                         error!(number=?block.number(), ?mismatch, "Gas usage mismatch");
                                         if skip_invalid_blocks {
-                                            executor = evm_config
-                                                .batch_executor(db_at(block.number()));
-                                            let _ = info_tx.send((block, err));
+                                            let old_executor = std::mem::replace(
+                                                &mut executor,
+                                                evm_config.batch_executor(db_at(block.number())),
+                                            );
+                                            executor_created = Instant::now();
+                                            // The invalid block was already merged into the
+                                            // bundle, so it's reverted before verifying the
+                                            // blocks before it
+                                            let mut bundle =
+                                                old_executor.into_state().take_bundle();
+                                            bundle.revert(1);
+                                            verify_or_skip(
+                                                &bundle,
+                                                NumHash::new(
+                                                    block.number() - 1,
+                                                    block.parent_hash(),
+                                                ),
+                                                &mut chunk_invalid_blocks,
+                                            )?;
+                                            chunk_invalid_blocks
+                                                .push((block.num_hash(), err));
                                             continue 'blocks;
                                         }
                                         return Err(err);
@@ -258,6 +442,8 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                             return Err(err);
                         }
                         let _ = stats_tx.send(block.gas_used());
+                        chunk_blocks += 1;
+                        chunk_gas += block.gas_used();
 
                         // Reset DB once in a while to avoid OOM or read tx timeouts
                         if executor.size_hint() > 5_000_000 ||
@@ -269,65 +455,70 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                                 evm_config.batch_executor(db_at(last_block)),
                             );
                             let bundle = old_executor.into_state().take_bundle();
-                            verify_bundle_against_changesets(
-                                &provider,
-                                &bundle,
-                                last_block,
-                            )?;
+                            verify_or_skip(&bundle, block.num_hash(), &mut chunk_invalid_blocks)?;
                             executor_created = Instant::now();
                         }
                     }
 
+                    // A cancelled chunk is incomplete and must be executed again on resume
+                    if cancellation.is_cancelled() {
+                        break;
+                    }
+
                     // Full verification at chunk end for remaining unverified blocks
                     let bundle = executor.into_state().take_bundle();
-                    verify_bundle_against_changesets(
-                        &provider,
+                    let last_block = chunk_end - 1;
+                    let last_block_hash = provider_factory
+                        .block_hash(last_block)?
+                        .ok_or_else(|| eyre::eyre!("Block hash of {last_block} not found"))?;
+                    verify_or_skip(
                         &bundle,
-                        chunk_end - 1,
+                        NumHash::new(last_block, last_block_hash),
+                        &mut chunk_invalid_blocks,
                     )?;
+                    let _ = chunk_tx.send(CompletedChunk {
+                        start: chunk_start,
+                        blocks: chunk_blocks,
+                        gas: chunk_gas,
+                        invalid_blocks: chunk_invalid_blocks,
+                    });
                 }
 
                 eyre::Ok(())
             });
         }
 
+        // Only the workers may hold senders, so that the loop below ends once they are done
+        drop(chunk_tx);
+
         let instant = Instant::now();
+        let resumed_gas = progress.executed_gas;
         let mut total_executed_blocks = 0;
         let mut total_executed_gas = 0;
 
         let mut last_logged_gas = 0;
         let mut last_logged_blocks = 0;
         let mut last_logged_time = Instant::now();
-        let mut invalid_blocks = Vec::new();
 
         let mut interval = tokio::time::interval(Duration::from_secs(10));
 
+        // Biased so that chunks sent by the workers are recorded before the workers are joined,
+        // and the throughput is logged even while the stats channel is kept busy
         loop {
             tokio::select! {
-                Some(gas_used) = stats_rx.recv() => {
-                    total_executed_blocks += 1;
-                    total_executed_gas += gas_used;
-                }
-                Some((block, err)) = info_rx.recv() => {
-                    error!(?err, block=?block.num_hash(), "Invalid block");
-                    invalid_blocks.push(block.num_hash());
-                }
-                result = tasks.join_next() => {
-                    if let Some(result) = result {
-                        if matches!(result, Err(_) | Ok(Err(_))) {
-                            error!(?result);
-                            return Err(eyre::eyre!("Re-execution failed: {result:?}"));
-                        }
-                    } else {
-                        break;
-                    }
+                biased;
+
+                Some(chunk) = chunk_rx.recv() => {
+                    progress.record_chunk(chunk);
+                    self.save_progress(&progress)?;
                 }
                 _ = interval.tick() => {
                     let blocks_executed = total_executed_blocks - last_logged_blocks;
                     let gas_executed = total_executed_gas - last_logged_gas;
 
                     if blocks_executed > 0 {
-                        let progress = 100.0 * total_executed_gas as f64 / total_gas as f64;
+                        let progress =
+                            100.0 * (resumed_gas + total_executed_gas) as f64 / total_gas as f64;
                         info!(
                             throughput=?format_gas_throughput(gas_executed, last_logged_time.elapsed()),
                             progress=format!("{progress:.2}%"),
@@ -339,9 +530,28 @@ impl<C: ChainSpecParser<ChainSpec: EthChainSpec + Hardforks + EthereumHardforks>
                     last_logged_gas = total_executed_gas;
                     last_logged_time = Instant::now();
                 }
+                Some(gas_used) = stats_rx.recv() => {
+                    total_executed_blocks += 1;
+                    total_executed_gas += gas_used;
+                }
+                result = tasks.join_next() => {
+                    if let Some(result) = result {
+                        if matches!(result, Err(_) | Ok(Err(_))) {
+                            error!(?result);
+                            return Err(eyre::eyre!("Re-execution failed: {result:?}"));
+                        }
+                    } else {
+                        break;
+                    }
+                }
             }
         }
 
+        let invalid_blocks = progress
+            .invalid_blocks
+            .iter()
+            .map(|(number, block)| NumHash::new(*number, block.hash))
+            .collect::<Vec<_>>();
         if invalid_blocks.is_empty() {
             info!(
                 start_block = min_block,
@@ -379,9 +589,11 @@ fn verify_bundle_against_changesets<P>(
 where
     P: ChangeSetReader + StorageChangeSetReader,
 {
-    // Verify reverts against changesets per block
-    for (i, block_reverts) in bundle.reverts.iter().rev().enumerate() {
-        let block_number = last_block - i as u64;
+    // Verify reverts against changesets per block, oldest first, so that the first divergent
+    // block is reported
+    let first_block = last_block + 1 - bundle.reverts.len() as u64;
+    for (i, block_reverts) in bundle.reverts.iter().enumerate() {
+        let block_number = first_block + i as u64;
 
         let mut cs_accounts: HashMap<Address, Option<Account>> = provider
             .account_block_changeset(block_number)?
@@ -465,3 +677,117 @@ where
 
     Ok(())
 }
+
+/// Verifies the state after `last_block` in the bundle against the database.
+///
+/// `state` is the state after `last_block`, which is the plain state if `last_block` is the tip
+/// and otherwise reconstructed from the plain state and changesets. Accounts and slots are
+/// checked in order and the first divergence is reported with both values.
+fn verify_bundle_against_plain_state(
+    state: &dyn StateProvider,
+    bundle: &BundleState,
+    last_block: u64,
+) -> eyre::Result<()> {
+    let mut accounts = bundle.state.iter().collect::<Vec<_>>();
+    accounts.sort_unstable_by_key(|(addr, _)| *addr);
+
+    for (addr, bundle_account) in accounts {
+        let expected = bundle_account.info.as_ref().map(Account::from);
+        let db_account = state.basic_account(addr)?;
+        eyre::ensure!(
+            expected == db_account,
+            "Block {last_block}: account {addr} mismatch: bundle={expected:?} db={db_account:?}",
+        );
+
+        let mut slots = bundle_account.storage.iter().collect::<Vec<_>>();
+        slots.sort_unstable_by_key(|(key, _)| *key);
+        for (slot_key, slot) in slots {
+            let b256_key = B256::from(*slot_key);
+            let db_value = state.storage(*addr, b256_key)?.unwrap_or_default();
+            eyre::ensure!(
+                slot.present_value == db_value,
+                "Block {last_block}: {addr} slot {b256_key} mismatch: bundle={} db={db_value}",
+                slot.present_value,
+            );
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_revm::{state::AccountInfo, test_utils::StateProviderTest};
+
+    #[test]
+    fn progress_roundtrip() {
+        let mut progress =
+            Progress { from: 1, to: 100, blocks_per_chunk: 10, ..Default::default() };
+        progress.record_chunk(CompletedChunk {
+            start: 11,
+            blocks: 10,
+            gas: 21_000,
+            invalid_blocks: Vec::new(),
+        });
+        progress.record_chunk(CompletedChunk {
+            start: 21,
+            blocks: 9,
+            gas: 0,
+            invalid_blocks: vec![(
+                NumHash::new(25, B256::with_last_byte(25)),
+                eyre::eyre!("invalid"),
+            )],
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("progress.json");
+        reth_fs_util::atomic_write_file(&path, |file| serde_json::to_writer(file, &progress))
+            .unwrap();
+
+        let progress: Progress = serde_json::from_slice(&fs::read(path).unwrap()).unwrap();
+        assert_eq!(progress.completed_chunks, BTreeSet::from([11, 21]));
+        assert_eq!(progress.executed_blocks, 19);
+        assert_eq!(progress.executed_gas, 21_000);
+        assert_eq!(progress.invalid_blocks[&25].hash, B256::with_last_byte(25));
+    }
+
+    #[test]
+    fn plain_state_verification_reports_divergent_slot() {
+        let address = Address::with_last_byte(1);
+        let info = AccountInfo { balance: U256::from(10), nonce: 1, ..Default::default() };
+        let slot = U256::from(2);
+        let bundle = BundleState::new(
+            [(
+                address,
+                None,
+                Some(info.clone()),
+                [(slot, (U256::ZERO, U256::from(7)))].into_iter().collect(),
+            )],
+            vec![vec![(address, Some(None), vec![(slot, U256::ZERO)])]],
+            vec![],
+        );
+
+        let mut state = StateProviderTest::default();
+        state.insert_account(
+            address,
+            Account::from(&info),
+            None,
+            [(B256::from(slot), U256::from(7))].into_iter().collect(),
+        );
+        verify_bundle_against_plain_state(&state, &bundle, 10).unwrap();
+
+        let mut state = StateProviderTest::default();
+        state.insert_account(
+            address,
+            Account::from(&info),
+            None,
+            [(B256::from(slot), U256::from(8))].into_iter().collect(),
+        );
+        let err = verify_bundle_against_plain_state(&state, &bundle, 10).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            format!("Block 10: {address} slot {} mismatch: bundle=7 db=8", B256::from(slot))
+        );
+    }
+}
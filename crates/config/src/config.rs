@@ -32,6 +32,12 @@ pub struct Config {
     /// Configuration for static files.
     #[cfg_attr(feature = "serde", serde(default))]
     pub static_files: StaticFilesConfig,
+    /// Configuration for the transaction pool limits.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "TxPoolConfig::is_default")
+    )]
+    pub txpool: TxPoolConfig,
 
     /// Configuration for state database.
     #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
@@ -137,6 +143,8 @@ pub struct StageConfig {
     pub index_account_history: IndexHistoryConfig,
     /// Index Storage History stage configuration.
     pub index_storage_history: IndexHistoryConfig,
+    /// Index Balance Changes stage configuration.
+    pub index_balance_changes: IndexBalanceChangesConfig,
     /// Common ETL related configuration.
     pub etl: EtlConfig,
     /// Disable hashing stages for fastnode mode.
@@ -541,6 +549,26 @@ impl Default for IndexHistoryConfig {
     }
 }
 
+/// Balance changes index stage configuration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct IndexBalanceChangesConfig {
+    /// Whether to build the index of account balance changes.
+    ///
+    /// Once the stage has run, the index is also maintained for blocks persisted by the engine. If
+    /// enabled on a synced node, the pipeline backfills the index once on the next startup.
+    pub enabled: bool,
+    /// The maximum number of blocks to process before committing progress to the database.
+    pub commit_threshold: u64,
+}
+
+impl Default for IndexBalanceChangesConfig {
+    fn default() -> Self {
+        Self { enabled: false, commit_threshold: 10_000 }
+    }
+}
+
 /// Pruning configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -628,6 +656,41 @@ impl PruneConfig {
     }
 }
 
+/// Transaction pool limits configuration.
+///
+/// Every limit that is set overrides the value of the corresponding `--txpool.*` CLI argument.
+/// These limits can be changed while the node is running by reloading the configuration.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct TxPoolConfig {
+    /// Max number of transaction in the pending sub-pool.
+    pub pending_max_count: Option<usize>,
+    /// Max size of the pending sub-pool in megabytes.
+    pub pending_max_size: Option<usize>,
+    /// Max number of transaction in the basefee sub-pool.
+    pub basefee_max_count: Option<usize>,
+    /// Max size of the basefee sub-pool in megabytes.
+    pub basefee_max_size: Option<usize>,
+    /// Max number of transaction in the queued sub-pool.
+    pub queued_max_count: Option<usize>,
+    /// Max size of the queued sub-pool in megabytes.
+    pub queued_max_size: Option<usize>,
+    /// Max number of transaction in the blobpool.
+    pub blobpool_max_count: Option<usize>,
+    /// Max size of the blobpool in megabytes.
+    pub blobpool_max_size: Option<usize>,
+    /// Max number of executable transaction slots guaranteed per account.
+    pub max_account_slots: Option<usize>,
+}
+
+impl TxPoolConfig {
+    /// Returns whether this configuration is the default one, i.e. no limit is overridden.
+    pub fn is_default(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
 /// State database configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -9,4 +9,7 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
 pub mod config;
-pub use config::{BodiesConfig, Config, PruneConfig, StateDbConfig};
+pub use config::{BodiesConfig, Config, PruneConfig, StateDbConfig, TxPoolConfig};
+
+pub mod reload;
+pub use reload::ConfigChanges;
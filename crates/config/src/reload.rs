@@ -0,0 +1,319 @@
+//! Support for reloading the configuration of a running node.
+//!
+//! Only a subset of the configuration can be applied without restarting the node:
+//! - the distance or block of every prune segment that is pruned with [`PruneMode::Distance`] or
+//!   [`PruneMode::Before`], except for receipts
+//! - the `[txpool]` limits
+//! - the trusted and banned peers
+//!
+//! Any other change is rejected.
+
+use crate::{config::TxPoolConfig, Config, PruneConfig};
+use reth_network_peers::{PeerId, TrustedPeer};
+use reth_prune_types::{PruneMode, PruneModes};
+use std::{collections::HashSet, fmt::Debug, hash::Hash};
+
+/// The changes between two configurations that can be applied to a running node.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigChanges {
+    /// The new prune modes, if any of them changed.
+    pub prune_modes: Option<PruneModes>,
+    /// The new transaction pool limits, if any of them changed.
+    pub txpool: Option<TxPoolConfig>,
+    /// Trusted peers that were added.
+    pub added_trusted_nodes: Vec<TrustedPeer>,
+    /// Trusted peers that were removed.
+    pub removed_trusted_nodes: Vec<TrustedPeer>,
+    /// Peers that were added to the banned nodes.
+    pub banned_nodes: Vec<PeerId>,
+    /// Peers that were removed from the banned nodes.
+    pub unbanned_nodes: Vec<PeerId>,
+    /// Human-readable description of every change, one line per changed setting.
+    pub diff: Vec<String>,
+}
+
+impl ConfigChanges {
+    /// Returns `true` if there is nothing to apply.
+    pub fn is_empty(&self) -> bool {
+        self.diff.is_empty()
+    }
+}
+
+impl Config {
+    /// Computes the changes from `self` to `new` that can be applied to a running node.
+    ///
+    /// Returns an error that lists every changed setting if any of them can't be reloaded, in
+    /// which case nothing should be applied.
+    pub fn reloadable_changes(&self, new: &Self) -> eyre::Result<ConfigChanges> {
+        let mut changes = ConfigChanges::default();
+        let mut rejected = Vec::new();
+
+        let Self { stages, prune, peers, sessions, static_files, txpool, statedb } = self;
+
+        if stages != &new.stages {
+            rejected.push("stages".to_string());
+        }
+        if sessions != &new.sessions {
+            rejected.push("sessions".to_string());
+        }
+        if static_files != &new.static_files {
+            rejected.push("static_files".to_string());
+        }
+        if statedb != &new.statedb {
+            rejected.push("statedb".to_string());
+        }
+
+        diff_prune(prune, &new.prune, &mut changes, &mut rejected);
+
+        if txpool != &new.txpool {
+            diff_txpool(txpool, &new.txpool, &mut changes.diff);
+            changes.txpool = Some(new.txpool);
+        }
+
+        // Everything except the trusted and banned nodes requires a restart.
+        let mut old_peers = peers.clone();
+        let mut new_peers = new.peers.clone();
+        old_peers.trusted_nodes.clear();
+        old_peers.banned_nodes.clear();
+        new_peers.trusted_nodes.clear();
+        new_peers.banned_nodes.clear();
+        if old_peers != new_peers {
+            rejected
+                .push("peers (only trusted_nodes and banned_nodes can be reloaded)".to_string());
+        }
+
+        (changes.added_trusted_nodes, changes.removed_trusted_nodes) =
+            diff_set(&peers.trusted_nodes, &new.peers.trusted_nodes);
+        (changes.banned_nodes, changes.unbanned_nodes) =
+            diff_set(&peers.banned_nodes, &new.peers.banned_nodes);
+        for peer in &changes.added_trusted_nodes {
+            changes.diff.push(format!("peers.trusted_nodes: + {peer}"));
+        }
+        for peer in &changes.removed_trusted_nodes {
+            changes.diff.push(format!("peers.trusted_nodes: - {peer}"));
+        }
+        for peer in &changes.banned_nodes {
+            changes.diff.push(format!("peers.banned_nodes: + {peer}"));
+        }
+        for peer in &changes.unbanned_nodes {
+            changes.diff.push(format!("peers.banned_nodes: - {peer}"));
+        }
+
+        if !rejected.is_empty() {
+            eyre::bail!(
+                "the following settings can't be changed without restarting the node: {}",
+                rejected.join(", ")
+            )
+        }
+
+        Ok(changes)
+    }
+}
+
+/// Diffs the prune configurations, rejecting changes that would leave the data written by the
+/// node inconsistent with its prune configuration.
+fn diff_prune(
+    old: &PruneConfig,
+    new: &PruneConfig,
+    changes: &mut ConfigChanges,
+    rejected: &mut Vec<String>,
+) {
+    if old.block_interval != new.block_interval {
+        rejected.push("prune.block_interval".to_string());
+    }
+    if old.minimum_pruning_distance != new.minimum_pruning_distance {
+        rejected.push("prune.minimum_pruning_distance".to_string());
+    }
+
+    let PruneModes {
+        sender_recovery,
+        transaction_lookup,
+        receipts,
+        account_history,
+        storage_history,
+        bodies_history,
+        receipts_log_filter,
+    } = &old.segments;
+
+    // Receipts are pruned while they're written, so the running node keeps using the
+    // configuration it was started with.
+    if receipts != &new.segments.receipts {
+        rejected.push("prune.segments.receipts".to_string());
+    }
+    if receipts_log_filter != &new.segments.receipts_log_filter {
+        rejected.push("prune.segments.receipts_log_filter".to_string());
+    }
+
+    let mut changed = false;
+    for (name, old, new) in [
+        ("sender_recovery", sender_recovery, &new.segments.sender_recovery),
+        ("transaction_lookup", transaction_lookup, &new.segments.transaction_lookup),
+        ("account_history", account_history, &new.segments.account_history),
+        ("storage_history", storage_history, &new.segments.storage_history),
+        ("bodies_history", bodies_history, &new.segments.bodies_history),
+    ] {
+        if old == new {
+            continue
+        }
+
+        match (old, new) {
+            (Some(old), Some(new)) if !old.is_full() && !new.is_full() => {
+                changes.diff.push(format!("prune.segments.{name}: {old:?} -> {new:?}"));
+                changed = true;
+            }
+            // Enabling, disabling or switching to or from full pruning changes which data is
+            // written in the first place.
+            _ => rejected.push(format!("prune.segments.{name}")),
+        }
+    }
+
+    if changed {
+        changes.prune_modes = Some(new.segments.clone());
+    }
+}
+
+/// Appends a diff line for every changed transaction pool limit.
+fn diff_txpool(old: &TxPoolConfig, new: &TxPoolConfig, diff: &mut Vec<String>) {
+    let TxPoolConfig {
+        pending_max_count,
+        pending_max_size,
+        basefee_max_count,
+        basefee_max_size,
+        queued_max_count,
+        queued_max_size,
+        blobpool_max_count,
+        blobpool_max_size,
+        max_account_slots,
+    } = old;
+
+    for (name, old, new) in [
+        ("pending_max_count", pending_max_count, new.pending_max_count),
+        ("pending_max_size", pending_max_size, new.pending_max_size),
+        ("basefee_max_count", basefee_max_count, new.basefee_max_count),
+        ("basefee_max_size", basefee_max_size, new.basefee_max_size),
+        ("queued_max_count", queued_max_count, new.queued_max_count),
+        ("queued_max_size", queued_max_size, new.queued_max_size),
+        ("blobpool_max_count", blobpool_max_count, new.blobpool_max_count),
+        ("blobpool_max_size", blobpool_max_size, new.blobpool_max_size),
+        ("max_account_slots", max_account_slots, new.max_account_slots),
+    ] {
+        if *old != new {
+            diff.push(format!("txpool.{name}: {} -> {}", display_limit(*old), display_limit(new)));
+        }
+    }
+}
+
+/// Formats an optional limit, where `None` means the CLI value is used.
+fn display_limit(limit: Option<usize>) -> String {
+    limit.map_or_else(|| "unset".to_string(), |limit| limit.to_string())
+}
+
+/// Returns the entries that were added to and removed from `old` to get `new`.
+fn diff_set<T: Clone + Eq + Hash + Debug>(old: &[T], new: &[T]) -> (Vec<T>, Vec<T>) {
+    let old_set = old.iter().collect::<HashSet<_>>();
+    let new_set = new.iter().collect::<HashSet<_>>();
+    let added = new.iter().filter(|entry| !old_set.contains(entry)).cloned().collect();
+    let removed = old.iter().filter(|entry| !new_set.contains(entry)).cloned().collect();
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const TRUSTED_PEER: &str = "enode://0401e494dbd0c84c5c0f72adac5985d2f2525e08b68d448958aae218f5ac8198a80d1498e0ebec2ce38b1b18d6750f6e61a56b4614c5a6c6cf0981c39aed47dc@34.159.32.127:30303";
+
+    #[test]
+    fn no_changes() {
+        let config = Config::default();
+        let changes = config.reloadable_changes(&config.clone()).unwrap();
+        assert!(changes.is_empty());
+        assert_eq!(changes, ConfigChanges::default());
+    }
+
+    #[test]
+    fn reload_prune_distance() {
+        let mut old = Config::default();
+        old.prune.segments.account_history = Some(PruneMode::Distance(10_064));
+        let mut new = old.clone();
+        new.prune.segments.account_history = Some(PruneMode::Distance(100_000));
+
+        let changes = old.reloadable_changes(&new).unwrap();
+        assert_eq!(changes.prune_modes, Some(new.prune.segments));
+        assert_eq!(
+            changes.diff,
+            vec!["prune.segments.account_history: Distance(10064) -> Distance(100000)"]
+        );
+    }
+
+    #[test]
+    fn reject_prune_mode_changes() {
+        let mut old = Config::default();
+        old.prune.segments.sender_recovery = Some(PruneMode::Distance(10_064));
+
+        // Switching to full pruning
+        let mut new = old.clone();
+        new.prune.segments.sender_recovery = Some(PruneMode::Full);
+        let err = old.reloadable_changes(&new).unwrap_err();
+        assert!(err.to_string().contains("prune.segments.sender_recovery"));
+
+        // Enabling a segment
+        let mut new = old.clone();
+        new.prune.segments.storage_history = Some(PruneMode::Distance(10_064));
+        let err = old.reloadable_changes(&new).unwrap_err();
+        assert!(err.to_string().contains("prune.segments.storage_history"));
+
+        // Receipts
+        let mut new = old.clone();
+        new.prune.segments.receipts = Some(PruneMode::Distance(10_064));
+        let err = old.reloadable_changes(&new).unwrap_err();
+        assert!(err.to_string().contains("prune.segments.receipts"));
+    }
+
+    #[test]
+    fn reload_txpool() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.txpool.pending_max_count = Some(20_000);
+
+        let changes = old.reloadable_changes(&new).unwrap();
+        assert_eq!(changes.txpool, Some(new.txpool));
+        assert_eq!(changes.diff, vec!["txpool.pending_max_count: unset -> 20000"]);
+    }
+
+    #[test]
+    fn reload_peers() {
+        let trusted = TrustedPeer::from_str(TRUSTED_PEER).unwrap();
+        let banned = PeerId::random();
+
+        let mut old = Config::default();
+        old.peers.trusted_nodes.push(trusted.clone());
+        let mut new = Config::default();
+        new.peers.banned_nodes.push(banned);
+
+        let changes = old.reloadable_changes(&new).unwrap();
+        assert!(changes.added_trusted_nodes.is_empty());
+        assert_eq!(changes.removed_trusted_nodes, vec![trusted]);
+        assert_eq!(changes.banned_nodes, vec![banned]);
+        assert!(changes.unbanned_nodes.is_empty());
+        assert_eq!(changes.diff.len(), 2);
+    }
+
+    #[test]
+    fn reject_non_reloadable_settings() {
+        let old = Config::default();
+        let mut new = old.clone();
+        new.prune.block_interval += 1;
+        new.peers.max_backoff_count += 1;
+        new.stages.headers.commit_threshold += 1;
+        // Reloadable changes are rejected as well if anything else changed.
+        new.txpool.max_account_slots = Some(32);
+
+        let err = old.reloadable_changes(&new).unwrap_err().to_string();
+        assert!(err.contains("stages"));
+        assert!(err.contains("prune.block_interval"));
+        assert!(err.contains("peers"));
+        assert!(!err.contains("txpool"));
+    }
+}
@@ -0,0 +1,57 @@
+use alloy_genesis::ChainConfig;
+use alloy_primitives::{Bytes, B256};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A self-contained reproduction of an invalid block.
+///
+/// [`InvalidBlockWitnessHook`](crate::InvalidBlockWitnessHook) writes one for every invalid block
+/// to `<block_number>_<block_hash>.bundle.json`. The block can be re-executed from the witness
+/// without a database with `reth debug reproduce`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InvalidBlockBundle {
+    /// Version of the node that rejected the block.
+    pub node_version: String,
+    /// Chain ID of the chain.
+    pub chain_id: u64,
+    /// Hash of the genesis block of the chain.
+    pub genesis_hash: B256,
+    /// Configuration of the chain from its genesis.
+    pub chain_config: ChainConfig,
+    /// Hardfork activations of the chain, as printed on startup.
+    pub hardforks: String,
+    /// RLP-encoded header of the parent block.
+    pub parent_header: Bytes,
+    /// RLP-encoded block.
+    pub block: Bytes,
+    /// Execution witness of the block on top of its parent, including the ancestor headers.
+    pub witness: ExecutionWitness,
+    /// The error that the block was rejected with, empty if the hook wasn't given one.
+    pub error: String,
+}
+
+impl InvalidBlockBundle {
+    /// Reads a bundle from a JSON file.
+    pub fn read(path: impl AsRef<Path>) -> eyre::Result<Self> {
+        Ok(serde_json::from_slice(&reth_fs_util::read(path)?)?)
+    }
+
+    /// Writes the bundle to a JSON file.
+    pub fn write(&self, path: impl AsRef<Path>) -> eyre::Result<()> {
+        reth_fs_util::write(path, serde_json::to_vec(self)?)?;
+        Ok(())
+    }
+
+    /// Decodes the block.
+    pub fn decode_block<B: Decodable>(&self) -> alloy_rlp::Result<B> {
+        B::decode(&mut self.block.as_ref())
+    }
+
+    /// Decodes the header of the parent block.
+    pub fn decode_parent_header<H: Decodable>(&self) -> alloy_rlp::Result<H> {
+        H::decode(&mut self.parent_header.as_ref())
+    }
+}
@@ -1,5 +1,12 @@
 //! Invalid block hook implementations.
 
+mod bundle;
+mod opcode;
+mod prestate;
+mod replay;
 mod witness;
 
+pub use bundle::InvalidBlockBundle;
+pub use opcode::InvalidBlockOpcodeHook;
+pub use prestate::InvalidBlockPreStateHook;
 pub use witness::InvalidBlockWitnessHook;
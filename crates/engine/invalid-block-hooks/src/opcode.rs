@@ -0,0 +1,235 @@
+use crate::replay::{save_and_compare_traces, trace_block};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{Bytes, B256, U256, U64};
+use alloy_rpc_types_trace::geth::{DefaultFrame, GethDebugTracingOptions, GethTrace, TraceResult};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{BlockExecutionOutput, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use revm_bytecode::OpCode;
+use serde::Serialize;
+use std::{
+    collections::HashMap,
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+/// Generates opcode-level traces of every transaction of the invalid block and saves them to the
+/// output directory.
+///
+/// The block is traced once with the default struct logger of `debug_traceBlockByHash`. The traces
+/// are compared with the traces of the healthy node if one is configured, and the steps of every
+/// transaction are also saved as an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) JSON lines
+/// file.
+///
+/// Tracing every opcode of a block is slow, so the hook runs on its own thread instead of blocking
+/// the engine.
+#[derive(Debug, Clone)]
+pub struct InvalidBlockOpcodeHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The directory to write the traces to.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces with.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, E> InvalidBlockOpcodeHook<P, E> {
+    /// Creates a new opcode invalid block hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, E, N> InvalidBlockOpcodeHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        let opts = GethDebugTracingOptions::default();
+        let traces = trace_block(&self.provider, &self.evm_config, parent_header, block, &opts)?;
+
+        for (index, trace) in traces.iter().enumerate() {
+            if let TraceResult::Success { result: GethTrace::Default(frame), tx_hash } = trace {
+                let tx_hash = tx_hash.unwrap_or_default();
+                save_eip3155_trace(
+                    &self
+                        .output_directory
+                        .join(format!("{block_prefix}.{index}_{tx_hash}.opcode.eip3155.jsonl")),
+                    frame,
+                )?;
+            }
+        }
+
+        save_and_compare_traces(
+            &self.output_directory,
+            self.healthy_node_client.as_ref(),
+            block.hash(),
+            &block_prefix,
+            "opcode",
+            &opts,
+            &traces,
+        )
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockOpcodeHook<P, E>
+where
+    P: StateProviderFactory + Clone + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        let hook = self.clone();
+        let parent_header = parent_header.clone();
+        let block = block.clone();
+        reth_tasks::spawn_os_thread("opcode-hook", move || {
+            if let Err(err) = hook.on_invalid_block(&parent_header, &block) {
+                warn!(target: "engine::invalid_block_hooks::opcode", %err, "Failed to invoke hook");
+            }
+        });
+    }
+}
+
+/// A single step of an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Step<'a> {
+    pc: u64,
+    op: u8,
+    gas: U64,
+    gas_cost: U64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mem_size: Option<u64>,
+    stack: &'a [U256],
+    depth: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    return_data: Option<&'a Bytes>,
+    refund: U64,
+    op_name: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+/// The summary line at the end of an [EIP-3155](https://eips.ethereum.org/EIPS/eip-3155) trace.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Eip3155Summary<'a> {
+    output: &'a Bytes,
+    gas_used: U64,
+    pass: bool,
+}
+
+/// Saves the struct logs of a transaction as an EIP-3155 JSON lines file.
+fn save_eip3155_trace(path: &Path, frame: &DefaultFrame) -> eyre::Result<()> {
+    let opcodes = (0..=u8::MAX)
+        .filter_map(|op| OpCode::new(op).map(|opcode| (opcode.as_str(), op)))
+        .collect::<HashMap<_, _>>();
+
+    let mut writer = BufWriter::new(File::create(path)?);
+    for log in &frame.struct_logs {
+        let op_name = &*log.op;
+        let step = Eip3155Step {
+            pc: log.pc,
+            // Opcodes that are not defined are reported as `INVALID`
+            op: opcodes.get(op_name).copied().unwrap_or(0xfe),
+            gas: U64::from(log.gas),
+            gas_cost: U64::from(log.gas_cost),
+            mem_size: log.memory_size,
+            stack: log.stack.as_deref().unwrap_or_default(),
+            depth: log.depth,
+            return_data: log.return_data.as_ref(),
+            refund: U64::from(log.refund_counter.unwrap_or_default()),
+            op_name,
+            error: log.error.as_deref(),
+        };
+        serde_json::to_writer(&mut writer, &step)?;
+        writer.write_all(b"\n")?;
+    }
+
+    let summary = Eip3155Summary {
+        output: &frame.return_value,
+        gas_used: U64::from(frame.gas),
+        pass: !frame.failed,
+    };
+    serde_json::to_writer(&mut writer, &summary)?;
+    writer.write_all(b"\n")?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::tests::test_block;
+    use alloy_consensus::transaction::TxHashRef;
+    use reth_evm_ethereum::EthEvmConfig;
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_opcode_traces_saved() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let (provider, parent_header, block) = test_block();
+        let hook = InvalidBlockOpcodeHook::new(
+            provider,
+            EthEvmConfig::mainnet(),
+            temp_dir.path().to_path_buf(),
+            None,
+        );
+
+        hook.on_invalid_block(&parent_header, &block).unwrap();
+
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        let tx_hash = block.body().transactions[0].tx_hash();
+        let lines = std::fs::read_to_string(
+            temp_dir.path().join(format!("{block_prefix}.0_{tx_hash}.opcode.eip3155.jsonl")),
+        )
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<Value>(line).unwrap())
+        .collect::<Vec<_>>();
+
+        // Four steps and the summary
+        assert_eq!(lines.len(), 5);
+        assert_eq!(
+            lines[..4].iter().map(|step| step["op"].as_u64().unwrap()).collect::<Vec<_>>(),
+            [0x60, 0x60, 0x55, 0x00]
+        );
+        assert_eq!(lines[2]["opName"], "SSTORE");
+        assert_eq!(lines[2]["stack"], serde_json::json!(["0x2a", "0x0"]));
+        assert_eq!(lines[4]["pass"], true);
+
+        let re_executed: Value = serde_json::from_str(
+            &std::fs::read_to_string(
+                temp_dir.path().join(format!("{block_prefix}.opcode.re_executed.json")),
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(re_executed[0]["result"]["structLogs"].as_array().unwrap().len(), 4);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::replay::{save_and_compare_traces, trace_block};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{
+    GethDebugBuiltInTracerType, GethDebugTracerType, GethDebugTracingOptions,
+};
+use reth_engine_primitives::InvalidBlockHook;
+use reth_evm::ConfigureEvm;
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::{BlockExecutionOutput, StateProviderFactory};
+use reth_tracing::tracing::warn;
+use reth_trie::updates::TrieUpdates;
+use std::path::PathBuf;
+
+/// Generates a `prestateTracer` trace of every transaction of the invalid block and saves it
+/// to the output directory, next to the traces of the healthy node if one is configured.
+#[derive(Debug)]
+pub struct InvalidBlockPreStateHook<P, E> {
+    /// The provider to read the historical state and do the EVM execution.
+    provider: P,
+    /// The EVM configuration to use for the execution.
+    evm_config: E,
+    /// The directory to write the traces to.
+    output_directory: PathBuf,
+    /// The healthy node client to compare the traces with.
+    healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+}
+
+impl<P, E> InvalidBlockPreStateHook<P, E> {
+    /// Creates a new prestate invalid block hook.
+    pub const fn new(
+        provider: P,
+        evm_config: E,
+        output_directory: PathBuf,
+        healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    ) -> Self {
+        Self { provider, evm_config, output_directory, healthy_node_client }
+    }
+}
+
+impl<P, E, N> InvalidBlockPreStateHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+    N: NodePrimitives,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+    ) -> eyre::Result<()> {
+        let opts = GethDebugTracingOptions::default().with_tracer(
+            GethDebugTracerType::BuiltInTracer(GethDebugBuiltInTracerType::PreStateTracer),
+        );
+        let traces = trace_block(&self.provider, &self.evm_config, parent_header, block, &opts)?;
+
+        let block_prefix = format!("{}_{}", block.number(), block.hash());
+        save_and_compare_traces(
+            &self.output_directory,
+            self.healthy_node_client.as_ref(),
+            block.hash(),
+            &block_prefix,
+            "prestate",
+            &opts,
+            &traces,
+        )
+    }
+}
+
+impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockPreStateHook<P, E>
+where
+    P: StateProviderFactory + Send + Sync + 'static,
+    E: ConfigureEvm<Primitives = N> + 'static,
+{
+    fn on_invalid_block(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        _output: &BlockExecutionOutput<N::Receipt>,
+        _trie_updates: Option<(&TrieUpdates, B256)>,
+    ) {
+        if let Err(err) = self.on_invalid_block(parent_header, block) {
+            warn!(target: "engine::invalid_block_hooks::prestate", %err, "Failed to invoke hook");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::replay::tests::{test_block, CONTRACT, CONTRACT_CODE};
+    use alloy_primitives::{hex, Address};
+    use reth_evm_ethereum::EthEvmConfig;
+    use serde_json::Value;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_prestate_traces_saved() {
+        let temp_dir = TempDir::new().expect("Failed to create temp dir");
+        let (provider, parent_header, block) = test_block();
+        let hook = InvalidBlockPreStateHook::new(
+            provider,
+            EthEvmConfig::mainnet(),
+            temp_dir.path().to_path_buf(),
+            None,
+        );
+
+        hook.on_invalid_block(&parent_header, &block).unwrap();
+
+        let path = temp_dir.path().join(format!(
+            "{}_{}.prestate.re_executed.json",
+            block.number(),
+            block.hash()
+        ));
+        let traces: Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        let accounts = traces[0]["result"].as_object().unwrap();
+        let account = |address: Address| {
+            accounts
+                .iter()
+                .find(|(key, _)| key.parse::<Address>().unwrap() == address)
+                .map(|(_, account)| account)
+        };
+
+        // The sender is funded and the contract is deployed before the transaction
+        let sender = account(block.senders()[0]).unwrap();
+        assert_eq!(sender["balance"], "0xf4240");
+        let contract = account(CONTRACT).unwrap();
+        assert_eq!(contract["code"], hex::encode_prefixed(CONTRACT_CODE));
+    }
+}
@@ -0,0 +1,304 @@
+//! Transaction-level replay of invalid blocks, shared by the tracing hooks.
+
+use alloy_consensus::transaction::TxHashRef;
+use alloy_primitives::B256;
+use alloy_rpc_types_trace::geth::{GethDebugTracingOptions, TraceResult};
+use jsonrpsee::http_client::HttpClient;
+use pretty_assertions::Comparison;
+use reth_evm::{block::BlockExecutor, handle_bsc_system_transaction, ConfigureEvm, Evm};
+use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
+use reth_provider::StateProviderFactory;
+use reth_revm::{database::StateProviderDatabase, db::State};
+use reth_rpc_api::DebugApiClient;
+use reth_tracing::tracing::warn;
+use revm::DatabaseCommit;
+use revm_inspectors::tracing::{DebugInspector, TransactionContext};
+use serde::Serialize;
+use std::{
+    fmt::Debug,
+    fs::File,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+/// Replays the transactions of the block on top of the state of its parent and traces each of them
+/// with the given options, producing the same output as `debug_traceBlockByHash`.
+///
+/// A transaction that fails to replay, e.g. because of an invalid nonce or insufficient balance,
+/// leaves the state unchanged, so its error is recorded in place of its trace and the following
+/// transactions are still traced.
+pub(crate) fn trace_block<P, E, N>(
+    provider: &P,
+    evm_config: &E,
+    parent_header: &SealedHeader<N::BlockHeader>,
+    block: &RecoveredBlock<N::Block>,
+    opts: &GethDebugTracingOptions,
+) -> eyre::Result<Vec<TraceResult>>
+where
+    P: StateProviderFactory,
+    E: ConfigureEvm<Primitives = N>,
+    N: NodePrimitives,
+{
+    let mut db = State::builder()
+        .with_database(StateProviderDatabase::new(
+            provider.state_by_block_hash(parent_header.hash())?,
+        ))
+        .with_bundle_update()
+        .build();
+
+    let mut executor = evm_config.executor_for_block(&mut db, block.sealed_block())?;
+    executor.apply_pre_execution_changes()?;
+    drop(executor);
+
+    let block_evm_env = evm_config.evm_env(block.header())?;
+    let mut inspector = DebugInspector::new(opts.clone())?;
+    let mut results = Vec::new();
+    for (index, tx) in block.transactions_recovered().enumerate() {
+        let tx_hash = *tx.tx_hash();
+        let tx_env = evm_config.tx_env(tx);
+
+        let mut evm_env = block_evm_env.clone();
+        handle_bsc_system_transaction::<E>(&mut evm_env, &tx_env);
+
+        let res = evm_config
+            .evm_with_env_and_inspector(&mut db, evm_env.clone(), &mut inspector)
+            .transact(tx_env.clone());
+        let res = match res {
+            Ok(res) => res,
+            Err(err) => {
+                warn!(target: "engine::invalid_block_hooks", %tx_hash, %err, "Failed to replay transaction");
+                results.push(TraceResult::Error { error: err.to_string(), tx_hash: Some(tx_hash) });
+                inspector.fuse()?;
+                continue
+            }
+        };
+        let tx_context = TransactionContext {
+            block_hash: Some(block.hash()),
+            tx_hash: Some(tx_hash),
+            tx_index: Some(index),
+        };
+        let trace =
+            inspector.get_result(Some(tx_context), &tx_env, &evm_env.block_env, &res, &mut db)?;
+        results.push(TraceResult::Success { result: trace, tx_hash: Some(tx_hash) });
+
+        inspector.fuse()?;
+        db.commit(res.state);
+    }
+
+    Ok(results)
+}
+
+/// Saves the traces of the re-executed block and, if a healthy node is configured, compares them
+/// with the traces of the healthy node and saves their diff.
+///
+/// Files are named `<block_prefix>.<kind>.re_executed.json`, `<block_prefix>.<kind>.healthy.json`
+/// and `<block_prefix>.<kind>.diff`.
+pub(crate) fn save_and_compare_traces(
+    output_directory: &Path,
+    healthy_node_client: Option<&HttpClient>,
+    block_hash: B256,
+    block_prefix: &str,
+    kind: &str,
+    opts: &GethDebugTracingOptions,
+    traces: &[TraceResult],
+) -> eyre::Result<()> {
+    let re_executed_path =
+        save_file(output_directory, format!("{block_prefix}.{kind}.re_executed.json"), traces)?;
+
+    if let Some(healthy_node_client) = healthy_node_client {
+        let healthy_traces = futures::executor::block_on(async move {
+            DebugApiClient::<()>::debug_trace_block_by_hash(
+                healthy_node_client,
+                block_hash,
+                Some(opts.clone()),
+            )
+            .await
+        })?;
+
+        let healthy_path = save_file(
+            output_directory,
+            format!("{block_prefix}.{kind}.healthy.json"),
+            &healthy_traces,
+        )?;
+
+        if traces != healthy_traces.as_slice() {
+            let diff_path = save_diff(
+                output_directory,
+                format!("{block_prefix}.{kind}.diff"),
+                healthy_traces.as_slice(),
+                traces,
+            )?;
+            warn!(
+                target: "engine::invalid_block_hooks",
+                %kind,
+                diff_path = %diff_path.display(),
+                re_executed_path = %re_executed_path.display(),
+                healthy_path = %healthy_path.display(),
+                "Transaction traces mismatch"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// Serializes and saves a value to a JSON file in the output directory.
+pub(crate) fn save_file<T: Serialize + ?Sized>(
+    output_directory: &Path,
+    filename: String,
+    value: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    File::create(&path)?.write_all(serde_json::to_string(value)?.as_bytes())?;
+
+    Ok(path)
+}
+
+/// Compares two values and saves their diff to a file in the output directory.
+fn save_diff<T: PartialEq + Debug + ?Sized>(
+    output_directory: &Path,
+    filename: String,
+    original: &T,
+    new: &T,
+) -> eyre::Result<PathBuf> {
+    let path = output_directory.join(filename);
+    let diff = Comparison::new(original, new);
+    File::create(&path)?.write_all(diff.to_string().as_bytes())?;
+
+    Ok(path)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_consensus::{Header, TxLegacy};
+    use alloy_primitives::{Address, Bytes, TxKind, U256};
+    use alloy_rpc_types_trace::geth::GethTrace;
+    use reth_chainspec::ChainSpec;
+    use reth_ethereum_primitives::{Block, BlockBody, EthPrimitives, Transaction};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::Block as _;
+    use reth_provider::test_utils::{ExtendedAccount, MockEthProvider};
+    use reth_testing_utils::generators::{self, sign_tx_with_random_key_pair};
+
+    /// Address of the contract called by the transaction of [`test_block`].
+    pub(crate) const CONTRACT: Address = Address::with_last_byte(0x42);
+
+    /// Code of [`CONTRACT`], storing `0x2a` in slot 0.
+    pub(crate) const CONTRACT_CODE: [u8; 6] = [0x60, 0x2a, 0x60, 0x00, 0x55, 0x00];
+
+    /// Creates a block with a single transaction that calls [`CONTRACT`], and a provider with the
+    /// state of its parent, where the sender is funded and the contract is deployed.
+    pub(crate) fn test_block(
+    ) -> (MockEthProvider<EthPrimitives, ChainSpec>, SealedHeader, RecoveredBlock<Block>) {
+        let mut rng = generators::rng();
+        let parent_header = generators::random_header(&mut rng, 1, None);
+        let tx = sign_tx_with_random_key_pair(
+            &mut rng,
+            Transaction::Legacy(TxLegacy {
+                chain_id: None,
+                nonce: 0,
+                gas_price: 1,
+                gas_limit: 100_000,
+                to: TxKind::Call(CONTRACT),
+                value: U256::ZERO,
+                input: Bytes::default(),
+            }),
+        );
+        let block = Block::new(
+            Header {
+                number: 2,
+                parent_hash: parent_header.hash(),
+                gas_limit: 1_000_000,
+                ..Default::default()
+            },
+            BlockBody { transactions: vec![tx], ..Default::default() },
+        )
+        .seal_slow()
+        .try_recover()
+        .unwrap();
+
+        let provider = MockEthProvider::default();
+        provider.add_account(block.senders()[0], ExtendedAccount::new(0, U256::from(1_000_000)));
+        provider.add_account(
+            CONTRACT,
+            ExtendedAccount::new(0, U256::ZERO).with_bytecode(Bytes::from_static(&CONTRACT_CODE)),
+        );
+
+        (provider, parent_header, block)
+    }
+
+    #[test]
+    fn trace_block_replays_transactions() {
+        let (provider, parent_header, block) = test_block();
+
+        let traces = trace_block(
+            &provider,
+            &EthEvmConfig::mainnet(),
+            &parent_header,
+            &block,
+            &GethDebugTracingOptions::default(),
+        )
+        .unwrap();
+
+        let [TraceResult::Success { result: GethTrace::Default(frame), tx_hash }] =
+            traces.as_slice()
+        else {
+            panic!("expected a single struct log trace, got {traces:?}")
+        };
+        assert_eq!(*tx_hash, Some(*block.body().transactions[0].tx_hash()));
+        assert!(!frame.failed);
+        assert_eq!(
+            frame.struct_logs.iter().map(|log| &*log.op).collect::<Vec<_>>(),
+            ["PUSH1", "PUSH1", "SSTORE", "STOP"]
+        );
+    }
+
+    #[test]
+    fn trace_block_records_failed_transactions() {
+        let (provider, parent_header, block) = test_block();
+
+        // A transaction of an unfunded sender fails before the valid transaction of the block
+        let unfunded_tx = sign_tx_with_random_key_pair(
+            &mut generators::rng(),
+            Transaction::Legacy(TxLegacy {
+                gas_price: 1,
+                gas_limit: 100_000,
+                to: TxKind::Call(CONTRACT),
+                ..Default::default()
+            }),
+        );
+        let valid_tx = block.body().transactions[0].clone();
+        let block = Block::new(
+            block.header().clone(),
+            BlockBody {
+                transactions: vec![unfunded_tx.clone(), valid_tx.clone()],
+                ..Default::default()
+            },
+        )
+        .seal_slow()
+        .try_recover()
+        .unwrap();
+
+        let traces = trace_block(
+            &provider,
+            &EthEvmConfig::mainnet(),
+            &parent_header,
+            &block,
+            &GethDebugTracingOptions::default(),
+        )
+        .unwrap();
+
+        let [failed, traced] = traces.as_slice() else {
+            panic!("expected an error and a trace, got {traces:?}")
+        };
+        assert!(matches!(
+            failed,
+            TraceResult::Error { tx_hash, .. } if *tx_hash == Some(*unfunded_tx.tx_hash())
+        ));
+        assert!(matches!(
+            traced,
+            TraceResult::Success { tx_hash, .. } if *tx_hash == Some(*valid_tx.tx_hash())
+        ));
+    }
+}
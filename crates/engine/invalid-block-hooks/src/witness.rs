@@ -1,17 +1,23 @@
+use crate::InvalidBlockBundle;
 use alloy_consensus::BlockHeader;
 use alloy_primitives::{keccak256, Address, Bytes, B256, U256};
 use alloy_rpc_types_debug::ExecutionWitness;
 use pretty_assertions::Comparison;
+use reth_chainspec::EthChainSpec;
+use reth_consensus::ConsensusError;
 use reth_engine_primitives::InvalidBlockHook;
 use reth_evm::{execute::Executor, ConfigureEvm};
 use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
-use reth_provider::{BlockExecutionOutput, StateProvider, StateProviderBox, StateProviderFactory};
+use reth_provider::{
+    BlockExecutionOutput, ChainSpecProvider, HeaderProvider, StateProvider, StateProviderBox,
+    StateProviderFactory,
+};
 use reth_revm::{
     database::StateProviderDatabase,
     db::{BundleState, State},
 };
 use reth_rpc_api::DebugApiClient;
-use reth_tracing::tracing::warn;
+use reth_tracing::tracing::{info, warn};
 use reth_trie::{updates::TrieUpdates, HashedStorage};
 use revm::state::AccountInfo;
 use revm_bytecode::Bytecode;
@@ -20,7 +26,7 @@ use revm_database::{
     AccountStatus, RevertToSlot,
 };
 use serde::Serialize;
-use std::{collections::BTreeMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
+use std::{borrow::Cow, collections::BTreeMap, fmt::Debug, fs::File, io::Write, path::PathBuf};
 
 type CollectionResult =
     (BTreeMap<B256, Bytes>, BTreeMap<B256, Bytes>, reth_trie::HashedPostState, BundleState);
@@ -176,7 +182,8 @@ fn generate(
 /// Hook for generating execution witnesses when invalid blocks are detected.
 ///
 /// This hook captures the execution state and generates witness data that can be used
-/// for debugging and analysis of invalid block execution.
+/// for debugging and analysis of invalid block execution. Additionally, it writes an
+/// [`InvalidBlockBundle`] that reproduces the invalid block in a single file.
 #[derive(Debug)]
 pub struct InvalidBlockWitnessHook<P, E> {
     /// The provider to read the historical state and do the EVM execution.
@@ -188,6 +195,8 @@ pub struct InvalidBlockWitnessHook<P, E> {
     output_directory: PathBuf,
     /// The healthy node client to compare the witness against.
     healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
+    /// The node version recorded in the bundle.
+    node_version: Cow<'static, str>,
 }
 
 impl<P, E> InvalidBlockWitnessHook<P, E> {
@@ -198,13 +207,31 @@ impl<P, E> InvalidBlockWitnessHook<P, E> {
         output_directory: PathBuf,
         healthy_node_client: Option<jsonrpsee::http_client::HttpClient>,
     ) -> Self {
-        Self { provider, evm_config, output_directory, healthy_node_client }
+        Self {
+            provider,
+            evm_config,
+            output_directory,
+            healthy_node_client,
+            node_version: Cow::Borrowed(env!("CARGO_PKG_VERSION")),
+        }
+    }
+
+    /// Sets the node version that is recorded in the bundle. Defaults to the version of this
+    /// crate.
+    pub fn with_node_version(mut self, node_version: impl Into<Cow<'static, str>>) -> Self {
+        self.node_version = node_version.into();
+        self
     }
 }
 
 impl<P, E, N> InvalidBlockWitnessHook<P, E>
 where
-    P: StateProviderFactory + Send + Sync + 'static,
+    P: StateProviderFactory
+        + ChainSpecProvider
+        + HeaderProvider<Header = N::BlockHeader>
+        + Send
+        + Sync
+        + 'static,
     E: ConfigureEvm<Primitives = N> + 'static,
     N: NodePrimitives,
 {
@@ -220,10 +247,22 @@ where
 
         executor.execute_one(block)?;
         let db = executor.into_state();
+        // The ancestor headers from the lowest block hash accessed with BLOCKHASH, or just the
+        // parent header.
+        let lowest_block_number = db
+            .block_hashes
+            .lowest()
+            .map_or_else(|| parent_header.number(), |(block_number, _)| block_number);
         let (codes, preimages, hashed_state, bundle_state) = collect_execution_data(db)?;
 
         let state_provider = self.provider.state_by_block_hash(parent_header.hash())?;
-        let witness = generate(codes, preimages, hashed_state, state_provider)?;
+        let mut witness = generate(codes, preimages, hashed_state, state_provider)?;
+        witness.headers = self
+            .provider
+            .headers_range(lowest_block_number..block.number())?
+            .iter()
+            .map(|header| alloy_rlp::encode(header).into())
+            .collect();
 
         Ok((witness, bundle_state))
     }
@@ -266,6 +305,40 @@ where
         Ok(())
     }
 
+    /// Saves an [`InvalidBlockBundle`] with everything that is needed to reproduce the invalid
+    /// block.
+    fn save_bundle(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        witness: ExecutionWitness,
+        error: Option<&ConsensusError>,
+        block_prefix: &str,
+    ) -> eyre::Result<()> {
+        let chain_spec = self.provider.chain_spec();
+        let bundle = InvalidBlockBundle {
+            node_version: self.node_version.to_string(),
+            chain_id: chain_spec.chain_id(),
+            genesis_hash: chain_spec.genesis_hash(),
+            chain_config: chain_spec.genesis().config.clone(),
+            hardforks: chain_spec.display_hardforks().to_string(),
+            parent_header: alloy_rlp::encode(parent_header.header()).into(),
+            block: alloy_rlp::encode(block.clone_block()).into(),
+            witness,
+            error: error.map(ToString::to_string).unwrap_or_default(),
+        };
+
+        let path = self.output_directory.join(format!("{block_prefix}.bundle.json"));
+        bundle.write(&path)?;
+        info!(
+            target: "engine::invalid_block_hooks::witness",
+            path = %path.display(),
+            "Saved invalid block bundle"
+        );
+
+        Ok(())
+    }
+
     /// Validates that the bundle state after re-execution matches the original
     fn validate_bundle_state(
         &self,
@@ -349,12 +422,14 @@ where
         block: &RecoveredBlock<N::Block>,
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
+        error: Option<&ConsensusError>,
     ) -> eyre::Result<()> {
         // TODO(alexey): unify with `DebugApi::debug_execution_witness`
         let (witness, bundle_state) = self.re_execute_block(parent_header, block)?;
 
         let block_prefix = format!("{}_{}", block.number(), block.hash());
         self.handle_witness_operations(&witness, &block_prefix, block.number())?;
+        self.save_bundle(parent_header, block, witness, error, &block_prefix)?;
 
         self.validate_bundle_state(&bundle_state, &output.state, &block_prefix)?;
 
@@ -394,7 +469,12 @@ where
 
 impl<P, E, N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockWitnessHook<P, E>
 where
-    P: StateProviderFactory + Send + Sync + 'static,
+    P: StateProviderFactory
+        + ChainSpecProvider
+        + HeaderProvider<Header = N::BlockHeader>
+        + Send
+        + Sync
+        + 'static,
     E: ConfigureEvm<Primitives = N> + 'static,
 {
     fn on_invalid_block(
@@ -404,7 +484,22 @@ where
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
     ) {
-        if let Err(err) = self.on_invalid_block(parent_header, block, output, trie_updates) {
+        if let Err(err) = self.on_invalid_block(parent_header, block, output, trie_updates, None) {
+            warn!(target: "engine::invalid_block_hooks::witness", %err, "Failed to invoke hook");
+        }
+    }
+
+    fn on_invalid_block_with_error(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+        error: &ConsensusError,
+    ) {
+        if let Err(err) =
+            self.on_invalid_block(parent_header, block, output, trie_updates, Some(error))
+        {
             warn!(target: "engine::invalid_block_hooks::witness", %err, "Failed to invoke hook");
         }
     }
@@ -418,6 +513,7 @@ mod tests {
     use reth_chainspec::ChainSpec;
     use reth_ethereum_primitives::EthPrimitives;
     use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::GotExpected;
     use reth_provider::test_utils::MockEthProvider;
     use reth_revm::db::{BundleAccount, BundleState};
     use revm_database::states::reverts::AccountRevert;
@@ -856,11 +952,15 @@ mod tests {
         // by checking that it doesn't panic and tries to create files
         let files_before = output_dir.read_dir().unwrap().count();
 
+        let error = ConsensusError::BodyStateRootDiff(
+            GotExpected { got: state_root, expected: recovered_block.state_root() }.into(),
+        );
         let _result = hook.on_invalid_block(
             &parent_header,
             &recovered_block,
             &output,
             Some((&trie_updates, state_root)),
+            Some(&error),
         );
 
         // Verify that the function attempted to process the block:
@@ -874,6 +974,59 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_on_invalid_block_saves_bundle() {
+        let (hook, output_dir, _temp_dir) = create_test_hook();
+
+        let mut rng = generators::rng();
+        let parent_header = generators::random_header(&mut rng, 1, None);
+        let recovered_block = random_block(
+            &mut rng,
+            2,
+            BlockParams {
+                parent: Some(parent_header.hash()),
+                tx_count: Some(0),
+                ..Default::default()
+            },
+        )
+        .try_recover()
+        .unwrap();
+        let error = ConsensusError::BlockGasUsed {
+            gas: GotExpected { got: 1, expected: 0 },
+            gas_spent_by_tx: vec![],
+        };
+
+        let output = BlockExecutionOutput {
+            state: BundleState::default(),
+            result: reth_provider::BlockExecutionResult {
+                receipts: vec![],
+                requests: Requests::default(),
+                gas_used: 1,
+                blob_gas_used: 0,
+            },
+        };
+
+        let _result =
+            hook.on_invalid_block(&parent_header, &recovered_block, &output, None, Some(&error));
+
+        let bundle = InvalidBlockBundle::read(output_dir.join(format!(
+            "{}_{}.bundle.json",
+            recovered_block.number(),
+            recovered_block.hash()
+        )))
+        .unwrap();
+        assert_eq!(bundle.chain_id, 1);
+        assert_eq!(bundle.error, error.to_string());
+        assert_eq!(
+            bundle.decode_block::<reth_ethereum_primitives::Block>().unwrap(),
+            recovered_block.clone_block()
+        );
+        assert_eq!(
+            bundle.decode_parent_header::<alloy_consensus::Header>().unwrap(),
+            *parent_header.header()
+        );
+    }
+
     #[test]
     fn test_handle_witness_operations_with_empty_witness() {
         let (hook, _output_dir, _temp_dir) = create_test_hook();
@@ -243,6 +243,15 @@ pub struct TreeConfig {
     /// When set, BAL storage slots are not read into the execution cache. BAL hashed-state
     /// streaming for parallel state-root computation is controlled separately.
     disable_bal_batch_io: bool,
+    /// Number of recent sparse trie state root computations whose inputs are kept in memory and
+    /// dumped when the state root task result disagrees with the header or the fallback
+    /// computation. A computation that reuses the preserved sparse trie also records the leaves
+    /// the trie retained. `0` disables the recorder.
+    sparse_trie_mismatch_recorder_blocks: usize,
+    /// Cross-checks the state root task against the parallel and serial state root computations
+    /// off the critical path for every block whose number is a multiple of this interval. `0`
+    /// disables the cross-check.
+    state_root_cross_check_interval: u64,
     /// Maximum random jitter applied before each proof computation (trie-debug only).
     /// When set, each proof worker sleeps for a random duration up to this value
     /// before starting a proof calculation.
@@ -304,6 +313,8 @@ impl Default for TreeConfig {
             disable_bal_parallel_execution: true,
             disable_bal_parallel_state_root: false,
             disable_bal_batch_io: false,
+            sparse_trie_mismatch_recorder_blocks: 0,
+            state_root_cross_check_interval: 0,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
             storage_worker_count: default_storage_worker_count(),
@@ -391,6 +402,8 @@ impl TreeConfig {
             disable_bal_parallel_execution: true,
             disable_bal_parallel_state_root: false,
             disable_bal_batch_io: false,
+            sparse_trie_mismatch_recorder_blocks: 0,
+            state_root_cross_check_interval: 0,
             #[cfg(feature = "trie-debug")]
             proof_jitter: None,
             storage_worker_count,
@@ -887,6 +900,31 @@ impl TreeConfig {
         self
     }
 
+    /// Returns the number of recent sparse trie state root computations kept by the mismatch
+    /// recorder.
+    pub const fn sparse_trie_mismatch_recorder_blocks(&self) -> usize {
+        self.sparse_trie_mismatch_recorder_blocks
+    }
+
+    /// Setter for the number of recent sparse trie state root computations kept by the mismatch
+    /// recorder, `0` disables it.
+    pub const fn with_sparse_trie_mismatch_recorder_blocks(mut self, blocks: usize) -> Self {
+        self.sparse_trie_mismatch_recorder_blocks = blocks;
+        self
+    }
+
+    /// Returns the interval of blocks whose state root is cross-checked, `0` if disabled.
+    pub const fn state_root_cross_check_interval(&self) -> u64 {
+        self.state_root_cross_check_interval
+    }
+
+    /// Setter for the interval of blocks whose state root is cross-checked against the parallel
+    /// and serial state root computations, `0` disables it.
+    pub const fn with_state_root_cross_check_interval(mut self, interval: u64) -> Self {
+        self.state_root_cross_check_interval = interval;
+        self
+    }
+
     /// Returns the proof jitter duration, if configured (trie-debug only).
     #[cfg(feature = "trie-debug")]
     pub const fn proof_jitter(&self) -> Option<Duration> {
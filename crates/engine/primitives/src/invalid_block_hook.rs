@@ -1,5 +1,6 @@
 use alloc::{boxed::Box, fmt, vec::Vec};
 use alloy_primitives::B256;
+use reth_errors::ConsensusError;
 use reth_execution_types::BlockExecutionOutput;
 use reth_primitives_traits::{NodePrimitives, RecoveredBlock, SealedHeader};
 use reth_trie_common::updates::TrieUpdates;
@@ -14,6 +15,23 @@ pub trait InvalidBlockHook<N: NodePrimitives>: Send + Sync {
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
     );
+
+    /// Invoked when an invalid block is encountered, with the error that the block was rejected
+    /// with.
+    ///
+    /// This is what the engine calls. By default the error is ignored and
+    /// [`Self::on_invalid_block`] is called, so hooks only need to implement this if they use the
+    /// error.
+    fn on_invalid_block_with_error(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+        _error: &ConsensusError,
+    ) {
+        self.on_invalid_block(parent_header, block, output, trie_updates)
+    }
 }
 
 impl<F, N> InvalidBlockHook<N> for F
@@ -75,4 +93,17 @@ impl<N: NodePrimitives> InvalidBlockHook<N> for InvalidBlockHooks<N> {
             hook.on_invalid_block(parent_header, block, output, trie_updates);
         }
     }
+
+    fn on_invalid_block_with_error(
+        &self,
+        parent_header: &SealedHeader<N::BlockHeader>,
+        block: &RecoveredBlock<N::Block>,
+        output: &BlockExecutionOutput<N::Receipt>,
+        trie_updates: Option<(&TrieUpdates, B256)>,
+        error: &ConsensusError,
+    ) {
+        for hook in &self.0 {
+            hook.on_invalid_block_with_error(parent_header, block, output, trie_updates, error);
+        }
+    }
 }
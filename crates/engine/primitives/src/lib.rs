@@ -40,6 +40,11 @@ pub use message::*;
 mod event;
 pub use event::*;
 
+#[cfg(feature = "std")]
+mod precompile_cache;
+#[cfg(feature = "std")]
+pub use precompile_cache::{PrecompileCacheControl, PrecompileCacheHandle, PrecompileCacheStats};
+
 mod invalid_block_hook;
 pub use invalid_block_hook::{InvalidBlockHook, InvalidBlockHooks, NoopInvalidBlockHook};
 
@@ -0,0 +1,75 @@
+//! Types to inspect and control the precompile caches of the engine.
+
+use alloy_primitives::Address;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    sync::{Arc, RwLock},
+};
+
+/// Statistics of the precompile cache of a single precompile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PrecompileCacheStats {
+    /// The address of the precompile.
+    pub address: Address,
+    /// The number of entries held in memory.
+    pub entries: u64,
+    /// The number of entries loaded from disk that have not been used yet.
+    pub persisted_entries: u64,
+    /// The number of calls served from the cache.
+    pub hits: u64,
+    /// The number of calls that executed the precompile.
+    pub misses: u64,
+}
+
+/// A precompile cache that can be inspected and cleared without knowing its spec type.
+pub trait PrecompileCacheControl: Send + Sync {
+    /// Returns the statistics of the cache of every precompile.
+    fn stats(&self) -> Vec<PrecompileCacheStats>;
+
+    /// Clears all cached entries, returning the number of removed entries.
+    fn clear(&self) -> u64;
+}
+
+/// A shared handle to the precompile caches of the node.
+///
+/// Engine validators register their caches on startup, and the handle is used by the admin API to
+/// inspect and clear them.
+#[derive(Clone, Default)]
+pub struct PrecompileCacheHandle {
+    caches: Arc<RwLock<Vec<Arc<dyn PrecompileCacheControl>>>>,
+}
+
+impl PrecompileCacheHandle {
+    /// Registers a precompile cache.
+    pub fn register(&self, cache: Arc<dyn PrecompileCacheControl>) {
+        self.caches.write().unwrap_or_else(|err| err.into_inner()).push(cache);
+    }
+
+    /// Returns `true` if no cache was registered yet.
+    pub fn is_empty(&self) -> bool {
+        self.caches.read().unwrap_or_else(|err| err.into_inner()).is_empty()
+    }
+
+    /// Returns the statistics of all registered caches.
+    pub fn stats(&self) -> Vec<PrecompileCacheStats> {
+        self.caches
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .flat_map(|cache| cache.stats())
+            .collect()
+    }
+
+    /// Clears all registered caches, returning the number of removed entries.
+    pub fn clear(&self) -> u64 {
+        self.caches.read().unwrap_or_else(|err| err.into_inner()).iter().map(|c| c.clear()).sum()
+    }
+}
+
+impl fmt::Debug for PrecompileCacheHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrecompileCacheHandle").finish_non_exhaustive()
+    }
+}
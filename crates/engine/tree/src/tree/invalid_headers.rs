@@ -82,6 +82,12 @@ impl InvalidHeaderCache {
             self.metrics.count.set(self.headers.len() as f64);
         }
     }
+
+    /// Returns all cached headers with their invalid ancestor, without counting as hits.
+    #[cfg(test)]
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&B256, &BlockWithParent)> {
+        self.headers.iter().map(|(hash, entry)| (hash, &entry.header))
+    }
 }
 
 struct HeaderEntry {
@@ -38,6 +38,8 @@ pub struct EngineApiMetrics {
     pub(crate) execution_gas_buckets: ExecutionGasBucketMetrics,
     /// Gas-bucketed block validation sub-phase metrics.
     pub(crate) block_validation_gas_buckets: BlockValidationGasBucketMetrics,
+    /// Metrics for the sampled state root cross-check.
+    pub(crate) state_root_cross_check: StateRootCrossCheckMetrics,
 }
 
 impl EngineApiMetrics {
@@ -573,6 +575,31 @@ impl BlockValidationMetrics {
     }
 }
 
+/// Metrics for the background cross-check of the state root task against the parallel and serial
+/// state root computations.
+#[derive(Metrics, Clone)]
+#[metrics(scope = "sync.block_validation.state_root_cross_check")]
+pub(crate) struct StateRootCrossCheckMetrics {
+    /// Total number of blocks that were cross-checked.
+    pub(crate) checks_total: Counter,
+    /// Total number of sampled blocks skipped because the previous cross-check was still running.
+    pub(crate) skipped_total: Counter,
+    /// Total number of cross-checks that failed to compute a state root or compare trie updates.
+    pub(crate) errors_total: Counter,
+    /// Total number of cross-checks where the state roots disagreed.
+    pub(crate) root_mismatches_total: Counter,
+    /// Total number of cross-checks where the trie updates disagreed.
+    pub(crate) trie_updates_mismatches_total: Counter,
+    /// Total number of differing trie nodes found across all cross-checks.
+    pub(crate) differing_trie_nodes_total: Counter,
+    /// Histogram of the state root task duration of cross-checked blocks.
+    pub(crate) task_duration: Histogram,
+    /// Histogram of the parallel state root computation duration.
+    pub(crate) parallel_duration: Histogram,
+    /// Histogram of the serial state root computation duration.
+    pub(crate) serial_duration: Histogram,
+}
+
 /// Metrics for the blockchain tree block buffer
 #[derive(Metrics)]
 #[metrics(scope = "blockchain_tree.block_buffer")]
@@ -67,6 +67,7 @@ pub mod payload_processor;
 pub mod payload_validator;
 mod persistence_state;
 pub mod precompile_cache;
+mod state_root_cross_check;
 #[cfg(test)]
 mod tests;
 mod trie_updates;
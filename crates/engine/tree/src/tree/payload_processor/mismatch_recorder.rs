@@ -0,0 +1,779 @@
+//! Ring buffer of the inputs consumed by recent sparse trie state root computations.
+//!
+//! Unlike the `trie-debug` recorder of `reth-trie-sparse`, which records every sparse trie
+//! mutation, this recorder is available in production builds. It only keeps the inputs of the
+//! sparse trie task (hashed state updates and revealed multiproofs, in the order they were
+//! received) for the last N computations. A computation that reuses the trie preserved by the
+//! previous one also records a [`PreservedTrieSnapshot`] of it, so that it can be replayed offline
+//! against the same parent state once the state root task disagrees with the header or the
+//! fallback computation. Dumps are read back with [`SparseTrieMismatchDump::load`].
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::{hex, B256, U256};
+use parking_lot::Mutex;
+use reth_primitives_traits::Account;
+use reth_trie::{
+    prefix_set::{PrefixSetMut, TriePrefixSetsMut},
+    HashedPostState, HashedStorage, Nibbles,
+};
+use reth_trie_common::{
+    BranchNodeMasks, BranchNodeV2, DecodedMultiProofV2, ProofTrieNodeV2, RlpNode, TrieMask,
+    TrieNodeV2,
+};
+use reth_trie_sparse::SparseStateTrie;
+use serde_json::{json, Map, Value};
+use std::{
+    collections::VecDeque,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// An input consumed by the sparse trie task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SparseTrieInput {
+    /// Hashed state update produced by execution.
+    HashedState(Arc<HashedPostState>),
+    /// Multiproof revealed in the sparse trie.
+    Multiproof(Arc<DecodedMultiProofV2>),
+}
+
+/// Snapshot of the preserved trie a computation reused, taken before the computation started.
+///
+/// Instead of the trie nodes, it holds the leaves that the pruning of the previous computation
+/// retained. Revealing the proofs of these leaves against the anchor state rebuilds the revealed
+/// part of the trie, unless cache pruning is disabled.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PreservedTrieSnapshot {
+    /// State root the preserved trie is anchored at.
+    pub anchor: B256,
+    /// Hashed addresses of the retained accounts.
+    pub accounts: Vec<B256>,
+    /// Hashed addresses and slots of the retained storage slots.
+    pub storage_slots: Vec<(B256, B256)>,
+}
+
+impl PreservedTrieSnapshot {
+    /// Takes a snapshot of the given trie, anchored at the given state root.
+    pub fn new<A, S>(anchor: B256, trie: &SparseStateTrie<A, S>) -> Self {
+        Self {
+            anchor,
+            accounts: trie.hot_accounts().collect(),
+            storage_slots: trie.hot_slots().collect(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let storage_slots = self
+            .storage_slots
+            .iter()
+            .map(|(address, slot)| json!([address.to_string(), slot.to_string()]))
+            .collect::<Vec<_>>();
+        json!({
+            "anchor": self.anchor.to_string(),
+            "accounts": self.accounts.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            "storage_slots": storage_slots,
+        })
+    }
+
+    fn from_json(value: &Value) -> io::Result<Self> {
+        let accounts = value["accounts"]
+            .as_array()
+            .ok_or_else(|| invalid_data("accounts"))?
+            .iter()
+            .map(|address| parse(address, "accounts"))
+            .collect::<io::Result<_>>()?;
+        let storage_slots = value["storage_slots"]
+            .as_array()
+            .ok_or_else(|| invalid_data("storage_slots"))?
+            .iter()
+            .map(|pair| Ok((parse(&pair[0], "storage_slots")?, parse(&pair[1], "storage_slots")?)))
+            .collect::<io::Result<_>>()?;
+        Ok(Self { anchor: parse(&value["anchor"], "anchor")?, accounts, storage_slots })
+    }
+}
+
+/// Inputs of a single sparse trie state root computation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseTrieRun {
+    /// State root of the parent block the computation started from.
+    pub parent_state_root: B256,
+    /// Snapshot of the preserved trie the computation reused, `None` if it started from a fresh
+    /// trie.
+    pub preserved_trie: Option<PreservedTrieSnapshot>,
+    /// Inputs in the order they were consumed.
+    pub inputs: Vec<SparseTrieInput>,
+    /// The computed state root, `None` if the computation failed.
+    pub state_root: Option<B256>,
+}
+
+impl SparseTrieRun {
+    /// Records a hashed state update.
+    pub fn record_hashed_state(&mut self, state: Arc<HashedPostState>) {
+        self.inputs.push(SparseTrieInput::HashedState(state));
+    }
+
+    /// Records a multiproof before it is revealed.
+    pub fn record_multiproof(&mut self, multiproof: Arc<DecodedMultiProofV2>) {
+        self.inputs.push(SparseTrieInput::Multiproof(multiproof));
+    }
+
+    fn to_json(&self) -> Value {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| match input {
+                SparseTrieInput::HashedState(state) => {
+                    json!({ "hashed_state": hashed_state_json(state) })
+                }
+                SparseTrieInput::Multiproof(multiproof) => {
+                    json!({ "multiproof": multiproof_json(multiproof) })
+                }
+            })
+            .collect::<Vec<_>>();
+        json!({
+            "parent_state_root": self.parent_state_root.to_string(),
+            "preserved_trie": self.preserved_trie.as_ref().map(PreservedTrieSnapshot::to_json),
+            "state_root": self.state_root.map(|root| root.to_string()),
+            "inputs": inputs,
+        })
+    }
+
+    fn from_json(value: &Value) -> io::Result<Self> {
+        let inputs = value["inputs"]
+            .as_array()
+            .ok_or_else(|| invalid_data("inputs"))?
+            .iter()
+            .map(|input| {
+                if let Some(state) = input.get("hashed_state") {
+                    Ok(SparseTrieInput::HashedState(Arc::new(parse_hashed_state(state)?)))
+                } else if let Some(multiproof) = input.get("multiproof") {
+                    Ok(SparseTrieInput::Multiproof(Arc::new(parse_multiproof(multiproof)?)))
+                } else {
+                    Err(invalid_data("inputs"))
+                }
+            })
+            .collect::<io::Result<_>>()?;
+        let preserved_trie = if value["preserved_trie"].is_null() {
+            None
+        } else {
+            Some(PreservedTrieSnapshot::from_json(&value["preserved_trie"])?)
+        };
+        Ok(Self {
+            parent_state_root: parse(&value["parent_state_root"], "parent_state_root")?,
+            preserved_trie,
+            inputs,
+            state_root: parse_optional(&value["state_root"], "state_root")?,
+        })
+    }
+}
+
+/// Describes a state root mismatch of the sparse trie task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SparseTrieMismatch {
+    /// The block whose state root was computed.
+    pub block: BlockNumHash,
+    /// The state root of the block parent.
+    pub parent_state_root: B256,
+    /// The state root in the block header.
+    pub header_state_root: B256,
+    /// The state root returned by the state root task.
+    pub task_state_root: B256,
+    /// The state root returned by the fallback computation, if it ran.
+    pub fallback_state_root: Option<B256>,
+}
+
+/// Keeps the inputs of the last N sparse trie state root computations in a ring buffer.
+///
+/// Cloning the recorder returns a handle to the same buffer. A capacity of `0` disables it, in
+/// which case [`Self::start`] returns `None` and nothing is cloned.
+#[derive(Debug, Clone, Default)]
+pub struct SparseTrieMismatchRecorder {
+    capacity: usize,
+    runs: Arc<Mutex<VecDeque<SparseTrieRun>>>,
+}
+
+impl SparseTrieMismatchRecorder {
+    /// Creates a recorder that keeps the last `capacity` computations.
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, runs: Arc::new(Mutex::new(VecDeque::with_capacity(capacity))) }
+    }
+
+    /// Returns `true` if the recorder keeps any computations.
+    pub const fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    /// Starts recording a computation, returns `None` if the recorder is disabled.
+    ///
+    /// `preserved_trie` is the snapshot of the preserved trie, if the computation reuses it.
+    pub fn start(
+        &self,
+        parent_state_root: B256,
+        preserved_trie: Option<PreservedTrieSnapshot>,
+    ) -> Option<SparseTrieRun> {
+        self.is_enabled().then(|| SparseTrieRun {
+            parent_state_root,
+            preserved_trie,
+            inputs: Vec::new(),
+            state_root: None,
+        })
+    }
+
+    /// Pushes a finished computation, evicting the oldest one if the buffer is full.
+    pub fn finish(&self, run: SparseTrieRun) {
+        let mut runs = self.runs.lock();
+        while runs.len() >= self.capacity {
+            runs.pop_front();
+        }
+        runs.push_back(run);
+    }
+
+    /// Returns the number of recorded computations.
+    pub fn len(&self) -> usize {
+        self.runs.lock().len()
+    }
+
+    /// Returns `true` if no computations are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.runs.lock().is_empty()
+    }
+
+    /// Writes the recorded computations together with the mismatch and the prefix sets of the
+    /// block to `sparse_trie_mismatch_{number}_{hash}.json` in `dir`.
+    ///
+    /// Returns the path of the written file.
+    pub fn dump(
+        &self,
+        dir: &Path,
+        mismatch: &SparseTrieMismatch,
+        prefix_sets: &TriePrefixSetsMut,
+    ) -> io::Result<PathBuf> {
+        let runs = self.runs.lock().iter().map(SparseTrieRun::to_json).collect::<Vec<_>>();
+        let json = json!({
+            "block": {
+                "number": mismatch.block.number,
+                "hash": mismatch.block.hash.to_string(),
+            },
+            "parent_state_root": mismatch.parent_state_root.to_string(),
+            "header_state_root": mismatch.header_state_root.to_string(),
+            "task_state_root": mismatch.task_state_root.to_string(),
+            "fallback_state_root": mismatch.fallback_state_root.map(|root| root.to_string()),
+            "prefix_sets": prefix_sets_json(prefix_sets),
+            "runs": runs,
+        });
+
+        std::fs::create_dir_all(dir)?;
+        let path = dir.join(format!(
+            "sparse_trie_mismatch_{}_{}.json",
+            mismatch.block.number, mismatch.block.hash
+        ));
+        std::fs::write(&path, serde_json::to_vec_pretty(&json)?)?;
+        Ok(path)
+    }
+}
+
+/// A dump written by [`SparseTrieMismatchRecorder::dump`], loaded back for replay.
+#[derive(Debug, Clone)]
+pub struct SparseTrieMismatchDump {
+    /// The mismatch that triggered the dump.
+    pub mismatch: SparseTrieMismatch,
+    /// Prefix sets of the mismatching block.
+    pub prefix_sets: TriePrefixSetsMut,
+    /// The recorded computations, oldest first. The last one is the mismatching computation.
+    pub runs: Vec<SparseTrieRun>,
+}
+
+impl SparseTrieMismatchDump {
+    /// Loads a dump from the given file.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let json: Value = serde_json::from_slice(&std::fs::read(path)?)?;
+
+        let mismatch = SparseTrieMismatch {
+            block: BlockNumHash::new(
+                json["block"]["number"].as_u64().ok_or_else(|| invalid_data("block.number"))?,
+                parse(&json["block"]["hash"], "block.hash")?,
+            ),
+            parent_state_root: parse(&json["parent_state_root"], "parent_state_root")?,
+            header_state_root: parse(&json["header_state_root"], "header_state_root")?,
+            task_state_root: parse(&json["task_state_root"], "task_state_root")?,
+            fallback_state_root: parse_optional(
+                &json["fallback_state_root"],
+                "fallback_state_root",
+            )?,
+        };
+        let runs = json["runs"]
+            .as_array()
+            .ok_or_else(|| invalid_data("runs"))?
+            .iter()
+            .map(SparseTrieRun::from_json)
+            .collect::<io::Result<_>>()?;
+
+        Ok(Self { mismatch, prefix_sets: parse_prefix_sets(&json["prefix_sets"])?, runs })
+    }
+}
+
+fn nibbles_hex(path: &Nibbles) -> String {
+    path.to_vec().iter().map(|nibble| format!("{nibble:x}")).collect()
+}
+
+fn hashed_state_json(state: &HashedPostState) -> Value {
+    let accounts = state
+        .accounts
+        .iter()
+        .map(|(address, account)| {
+            let account = account.as_ref().map(|account| {
+                json!({
+                    "nonce": account.nonce,
+                    "balance": format!("{:#x}", account.balance),
+                    "bytecode_hash": account.bytecode_hash.map(|hash| hash.to_string()),
+                })
+            });
+            (address.to_string(), account.unwrap_or(Value::Null))
+        })
+        .collect::<Map<_, _>>();
+    let storages = state
+        .storages
+        .iter()
+        .map(|(address, storage)| {
+            let slots = storage
+                .storage
+                .iter()
+                .map(|(slot, value)| (slot.to_string(), Value::String(format!("{value:#x}"))))
+                .collect::<Map<_, _>>();
+            (address.to_string(), json!({ "wiped": storage.wiped, "storage": slots }))
+        })
+        .collect::<Map<_, _>>();
+    json!({ "accounts": accounts, "storages": storages })
+}
+
+/// Branches are written field by field, since their RLP encoding drops the cached RLP node and
+/// turns branches with a hashed extension child into extensions.
+fn trie_node_json(node: &TrieNodeV2) -> Value {
+    match node {
+        TrieNodeV2::Branch(branch) => json!({
+            "branch": {
+                "key": nibbles_hex(&branch.key),
+                "stack": branch.stack.iter().map(hex::encode_prefixed).collect::<Vec<_>>(),
+                "state_mask": branch.state_mask.get(),
+                "branch_rlp_node": branch.branch_rlp_node.as_ref().map(hex::encode_prefixed),
+            }
+        }),
+        node => json!({ "rlp": hex::encode_prefixed(alloy_rlp::encode(node)) }),
+    }
+}
+
+fn proof_nodes_json(nodes: &[ProofTrieNodeV2]) -> Value {
+    nodes
+        .iter()
+        .map(|node| {
+            json!({
+                "path": nibbles_hex(&node.path),
+                "node": trie_node_json(&node.node),
+                "hash_mask": node.masks.map(|masks| masks.hash_mask.get()),
+                "tree_mask": node.masks.map(|masks| masks.tree_mask.get()),
+            })
+        })
+        .collect()
+}
+
+fn multiproof_json(multiproof: &DecodedMultiProofV2) -> Value {
+    let storage_proofs = multiproof
+        .storage_proofs
+        .iter()
+        .map(|(address, nodes)| (address.to_string(), proof_nodes_json(nodes)))
+        .collect::<Map<_, _>>();
+    json!({
+        "account_proofs": proof_nodes_json(&multiproof.account_proofs),
+        "storage_proofs": storage_proofs,
+    })
+}
+
+fn prefix_sets_json(prefix_sets: &TriePrefixSetsMut) -> Value {
+    let prefix_set_json = |prefix_set: &PrefixSetMut| {
+        let prefix_set = prefix_set.clone().freeze();
+        json!({
+            "all": prefix_set.all(),
+            "keys": prefix_set.iter().map(nibbles_hex).collect::<Vec<_>>(),
+        })
+    };
+    let storage = prefix_sets
+        .storage_prefix_sets
+        .iter()
+        .map(|(address, prefix_set)| (address.to_string(), prefix_set_json(prefix_set)))
+        .collect::<Map<_, _>>();
+    let mut destroyed_accounts =
+        prefix_sets.destroyed_accounts.iter().map(ToString::to_string).collect::<Vec<_>>();
+    destroyed_accounts.sort_unstable();
+    json!({
+        "account": prefix_set_json(&prefix_sets.account_prefix_set),
+        "storage": storage,
+        "destroyed_accounts": destroyed_accounts,
+    })
+}
+
+fn invalid_data(field: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, format!("invalid `{field}` in sparse trie dump"))
+}
+
+fn parse<T: FromStr>(value: &Value, field: &str) -> io::Result<T> {
+    value.as_str().and_then(|value| value.parse().ok()).ok_or_else(|| invalid_data(field))
+}
+
+fn parse_optional<T: FromStr>(value: &Value, field: &str) -> io::Result<Option<T>> {
+    if value.is_null() {
+        Ok(None)
+    } else {
+        parse(value, field).map(Some)
+    }
+}
+
+fn parse_bytes(value: &Value, field: &str) -> io::Result<Vec<u8>> {
+    value.as_str().and_then(|value| hex::decode(value).ok()).ok_or_else(|| invalid_data(field))
+}
+
+fn parse_rlp_node(value: &Value, field: &str) -> io::Result<RlpNode> {
+    RlpNode::from_raw(&parse_bytes(value, field)?).ok_or_else(|| invalid_data(field))
+}
+
+fn parse_nibbles(value: &Value, field: &str) -> io::Result<Nibbles> {
+    let nibbles = value
+        .as_str()
+        .ok_or_else(|| invalid_data(field))?
+        .chars()
+        .map(|nibble| nibble.to_digit(16).map(|nibble| nibble as u8))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| invalid_data(field))?;
+    Ok(Nibbles::from_nibbles(nibbles))
+}
+
+fn parse_object<'a>(value: &'a Value, field: &str) -> io::Result<&'a Map<String, Value>> {
+    value.as_object().ok_or_else(|| invalid_data(field))
+}
+
+fn parse_hashed_state(value: &Value) -> io::Result<HashedPostState> {
+    let mut state = HashedPostState::default();
+    for (address, account) in parse_object(&value["accounts"], "accounts")? {
+        let account = if account.is_null() {
+            None
+        } else {
+            Some(Account {
+                nonce: account["nonce"].as_u64().ok_or_else(|| invalid_data("nonce"))?,
+                balance: parse(&account["balance"], "balance")?,
+                bytecode_hash: parse_optional(&account["bytecode_hash"], "bytecode_hash")?,
+            })
+        };
+        state.accounts.insert(address.parse().map_err(|_| invalid_data("accounts"))?, account);
+    }
+    for (address, storage) in parse_object(&value["storages"], "storages")? {
+        let mut hashed_storage =
+            HashedStorage::new(storage["wiped"].as_bool().ok_or_else(|| invalid_data("wiped"))?);
+        for (slot, value) in parse_object(&storage["storage"], "storage")? {
+            hashed_storage.storage.insert(
+                slot.parse().map_err(|_| invalid_data("storage"))?,
+                parse::<U256>(value, "storage")?,
+            );
+        }
+        state
+            .storages
+            .insert(address.parse().map_err(|_| invalid_data("storages"))?, hashed_storage);
+    }
+    Ok(state)
+}
+
+fn parse_trie_node(value: &Value) -> io::Result<TrieNodeV2> {
+    if let Some(branch) = value.get("branch") {
+        let stack = branch["stack"]
+            .as_array()
+            .ok_or_else(|| invalid_data("stack"))?
+            .iter()
+            .map(|node| parse_rlp_node(node, "stack"))
+            .collect::<io::Result<_>>()?;
+        let state_mask = branch["state_mask"]
+            .as_u64()
+            .and_then(|mask| u16::try_from(mask).ok())
+            .ok_or_else(|| invalid_data("state_mask"))?;
+        let branch_rlp_node = if branch["branch_rlp_node"].is_null() {
+            None
+        } else {
+            Some(parse_rlp_node(&branch["branch_rlp_node"], "branch_rlp_node")?)
+        };
+        return Ok(TrieNodeV2::Branch(BranchNodeV2::new(
+            parse_nibbles(&branch["key"], "key")?,
+            stack,
+            TrieMask::new(state_mask),
+            branch_rlp_node,
+        )))
+    }
+
+    let rlp = parse_bytes(&value["rlp"], "node")?;
+    alloy_rlp::Decodable::decode(&mut rlp.as_slice()).map_err(|_| invalid_data("node"))
+}
+
+fn parse_proof_nodes(value: &Value) -> io::Result<Vec<ProofTrieNodeV2>> {
+    let mask = |mask: &Value, field: &str| -> io::Result<Option<TrieMask>> {
+        if mask.is_null() {
+            return Ok(None)
+        }
+        let mask = mask.as_u64().and_then(|mask| u16::try_from(mask).ok());
+        mask.map(|mask| Some(TrieMask::new(mask))).ok_or_else(|| invalid_data(field))
+    };
+    value
+        .as_array()
+        .ok_or_else(|| invalid_data("proof nodes"))?
+        .iter()
+        .map(|node| {
+            Ok(ProofTrieNodeV2 {
+                path: parse_nibbles(&node["path"], "path")?,
+                node: parse_trie_node(&node["node"])?,
+                masks: BranchNodeMasks::from_optional(
+                    mask(&node["hash_mask"], "hash_mask")?,
+                    mask(&node["tree_mask"], "tree_mask")?,
+                ),
+            })
+        })
+        .collect()
+}
+
+fn parse_multiproof(value: &Value) -> io::Result<DecodedMultiProofV2> {
+    let storage_proofs = parse_object(&value["storage_proofs"], "storage_proofs")?
+        .iter()
+        .map(|(address, nodes)| {
+            Ok((
+                address.parse().map_err(|_| invalid_data("storage_proofs"))?,
+                parse_proof_nodes(nodes)?,
+            ))
+        })
+        .collect::<io::Result<_>>()?;
+    Ok(DecodedMultiProofV2 {
+        account_proofs: parse_proof_nodes(&value["account_proofs"])?,
+        storage_proofs,
+    })
+}
+
+fn parse_prefix_sets(value: &Value) -> io::Result<TriePrefixSetsMut> {
+    let prefix_set = |value: &Value| -> io::Result<PrefixSetMut> {
+        if value["all"].as_bool().ok_or_else(|| invalid_data("all"))? {
+            return Ok(PrefixSetMut::all())
+        }
+        let keys = value["keys"]
+            .as_array()
+            .ok_or_else(|| invalid_data("keys"))?
+            .iter()
+            .map(|key| parse_nibbles(key, "keys"))
+            .collect::<io::Result<Vec<_>>>()?;
+        Ok(PrefixSetMut::from(keys))
+    };
+    let storage_prefix_sets = parse_object(&value["storage"], "storage")?
+        .iter()
+        .map(|(address, value)| {
+            Ok((address.parse().map_err(|_| invalid_data("storage"))?, prefix_set(value)?))
+        })
+        .collect::<io::Result<_>>()?;
+    let destroyed_accounts = value["destroyed_accounts"]
+        .as_array()
+        .ok_or_else(|| invalid_data("destroyed_accounts"))?
+        .iter()
+        .map(|address| parse(address, "destroyed_accounts"))
+        .collect::<io::Result<_>>()?;
+    Ok(TriePrefixSetsMut {
+        account_prefix_set: prefix_set(&value["account"])?,
+        storage_prefix_sets,
+        destroyed_accounts,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::map::B256Map;
+
+    fn mismatch(number: u64) -> SparseTrieMismatch {
+        SparseTrieMismatch {
+            block: BlockNumHash::new(number, B256::with_last_byte(number as u8)),
+            parent_state_root: B256::with_last_byte(1),
+            header_state_root: B256::with_last_byte(2),
+            task_state_root: B256::with_last_byte(3),
+            fallback_state_root: Some(B256::with_last_byte(2)),
+        }
+    }
+
+    #[test]
+    fn disabled_recorder_does_not_record() {
+        let recorder = SparseTrieMismatchRecorder::default();
+        assert!(!recorder.is_enabled());
+        assert!(recorder.start(B256::ZERO, None).is_none());
+    }
+
+    #[test]
+    fn snapshots_hot_leaves_of_preserved_trie() {
+        let account = B256::with_last_byte(0xaa);
+        let slot = B256::with_last_byte(0xbb);
+        let mut trie = SparseStateTrie::<reth_trie_sparse::ParallelSparseTrie>::default()
+            .with_hot_cache_capacities(4, 4);
+        trie.record_account_touch(account);
+        trie.record_slot_touch(account, slot);
+
+        let snapshot = PreservedTrieSnapshot::new(B256::with_last_byte(1), &trie);
+        assert_eq!(snapshot.anchor, B256::with_last_byte(1));
+        assert_eq!(snapshot.accounts, vec![account]);
+        assert_eq!(snapshot.storage_slots, vec![(account, slot)]);
+    }
+
+    #[test]
+    fn keeps_last_runs() {
+        let recorder = SparseTrieMismatchRecorder::new(2);
+        for i in 0..5u8 {
+            let mut run = recorder.start(B256::with_last_byte(i), None).unwrap();
+            run.state_root = Some(B256::with_last_byte(i + 1));
+            recorder.finish(run);
+        }
+
+        assert_eq!(recorder.len(), 2);
+        let roots =
+            recorder.runs.lock().iter().map(|run| run.parent_state_root).collect::<Vec<_>>();
+        assert_eq!(roots, vec![B256::with_last_byte(3), B256::with_last_byte(4)]);
+    }
+
+    #[test]
+    fn dumps_runs_with_prefix_sets() {
+        let recorder = SparseTrieMismatchRecorder::new(4);
+        let address = B256::with_last_byte(0xaa);
+        let slot = B256::with_last_byte(0xbb);
+        let state = HashedPostState::default()
+            .with_storages([(address, HashedStorage::from_iter(false, [(slot, U256::from(7))]))]);
+
+        let mut run = recorder.start(B256::with_last_byte(1), None).unwrap();
+        run.record_hashed_state(Arc::new(state.clone()));
+        run.record_multiproof(Arc::default());
+        run.state_root = Some(B256::with_last_byte(3));
+        recorder.finish(run);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path =
+            recorder.dump(dir.path(), &mismatch(10), &state.construct_prefix_sets()).unwrap();
+        let json: Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+
+        assert_eq!(json["block"]["number"], 10);
+        assert_eq!(json["fallback_state_root"], B256::with_last_byte(2).to_string());
+        assert_eq!(
+            json["prefix_sets"]["storage"][address.to_string()]["keys"][0],
+            nibbles_hex(&Nibbles::unpack(slot))
+        );
+
+        let inputs = json["runs"][0]["inputs"].as_array().unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(
+            inputs[0]["hashed_state"]["storages"][address.to_string()]["storage"][slot.to_string()],
+            "0x7"
+        );
+        assert!(inputs[1]["multiproof"]["account_proofs"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn loads_dumped_runs() {
+        let recorder = SparseTrieMismatchRecorder::new(4);
+        let address = B256::with_last_byte(0xaa);
+        let state = HashedPostState::default()
+            .with_accounts([
+                (address, Some(Account { nonce: 1, balance: U256::MAX, bytecode_hash: None })),
+                (B256::with_last_byte(0xcc), None),
+            ])
+            .with_storages([(
+                address,
+                HashedStorage::from_iter(true, [(B256::with_last_byte(0xbb), U256::from(7))]),
+            )]);
+
+        // a leaf, an extension with a hashed child, and branches with and without an extension
+        // key, whose RLP encoding doesn't round-trip
+        let leaf_rlp = RlpNode::from_rlp(&alloy_rlp::encode(&TrieNodeV2::Leaf(
+            reth_trie_common::LeafNode::new(Nibbles::from_nibbles([1, 2]), vec![0x42]),
+        )));
+        let hashed_child = RlpNode::word_rlp(&B256::with_last_byte(0xdd));
+        let node = |path: &[u8], node, masks| ProofTrieNodeV2 {
+            path: Nibbles::from_nibbles(path),
+            node,
+            masks,
+        };
+        let multiproof = DecodedMultiProofV2 {
+            account_proofs: vec![
+                node(
+                    &[],
+                    TrieNodeV2::Branch(BranchNodeV2::new(
+                        Nibbles::from_nibbles([0xa, 0xb]),
+                        vec![hashed_child.clone(), leaf_rlp.clone()],
+                        TrieMask::new(0b1001),
+                        Some(hashed_child.clone()),
+                    )),
+                    BranchNodeMasks::from_optional(
+                        Some(TrieMask::new(0b1)),
+                        Some(TrieMask::new(0)),
+                    ),
+                ),
+                node(
+                    &[0xa, 0xb, 0x3],
+                    TrieNodeV2::Leaf(reth_trie_common::LeafNode::new(
+                        Nibbles::from_nibbles([4, 5]),
+                        vec![0x01],
+                    )),
+                    None,
+                ),
+            ],
+            storage_proofs: B256Map::from_iter([(
+                address,
+                vec![
+                    node(
+                        &[],
+                        TrieNodeV2::Branch(BranchNodeV2::new(
+                            Nibbles::default(),
+                            vec![leaf_rlp, hashed_child.clone()],
+                            TrieMask::new(0b11),
+                            None,
+                        )),
+                        None,
+                    ),
+                    node(
+                        &[1],
+                        TrieNodeV2::Extension(reth_trie_common::ExtensionNode::new(
+                            Nibbles::from_nibbles([7]),
+                            hashed_child,
+                        )),
+                        None,
+                    ),
+                ],
+            )]),
+        };
+
+        let mut first = recorder.start(B256::with_last_byte(1), None).unwrap();
+        first.record_hashed_state(Arc::new(state.clone()));
+        first.record_multiproof(Arc::new(multiproof));
+        first.state_root = Some(B256::with_last_byte(2));
+        recorder.finish(first.clone());
+        let preserved_trie = PreservedTrieSnapshot {
+            anchor: B256::with_last_byte(2),
+            accounts: vec![address],
+            storage_slots: vec![(address, B256::with_last_byte(0xbb))],
+        };
+        let mut second = recorder.start(B256::with_last_byte(2), Some(preserved_trie)).unwrap();
+        second.record_multiproof(Arc::default());
+        recorder.finish(second.clone());
+
+        let dir = tempfile::tempdir().unwrap();
+        let prefix_sets = state.construct_prefix_sets();
+        let path = recorder.dump(dir.path(), &mismatch(10), &prefix_sets).unwrap();
+        let dump = SparseTrieMismatchDump::load(&path).unwrap();
+
+        assert_eq!(dump.mismatch, mismatch(10));
+        assert_eq!(dump.runs, vec![first, second]);
+        assert_eq!(
+            dump.prefix_sets.account_prefix_set.clone().freeze().iter().collect::<Vec<_>>(),
+            prefix_sets.account_prefix_set.clone().freeze().iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            dump.prefix_sets.storage_prefix_sets[&address].clone().freeze().all(),
+            prefix_sets.storage_prefix_sets[&address].clone().freeze().all()
+        );
+        assert_eq!(dump.prefix_sets.destroyed_accounts, prefix_sets.destroyed_accounts);
+    }
+}
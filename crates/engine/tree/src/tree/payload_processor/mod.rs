@@ -47,6 +47,7 @@ use std::{
 };
 use tracing::{debug, debug_span, instrument, trace, warn, Span};
 
+pub mod mismatch_recorder;
 pub mod multiproof;
 mod preserved_sparse_trie;
 pub mod prewarm;
@@ -54,6 +55,7 @@ pub mod receipt_root_task;
 pub mod sparse_trie;
 pub mod triedb_prefetcher;
 
+use mismatch_recorder::{PreservedTrieSnapshot, SparseTrieMismatchRecorder};
 use preserved_sparse_trie::{PreservedSparseTrie, SharedPreservedSparseTrie};
 use triedb_prefetcher::TrieDBPrefetchHandle;
 
@@ -127,6 +129,8 @@ where
     sparse_trie_max_hot_accounts: usize,
     /// Whether sparse trie cache pruning is fully disabled.
     disable_sparse_trie_cache_pruning: bool,
+    /// Inputs of the most recent sparse trie computations, dumped on state root mismatches.
+    sparse_trie_mismatch_recorder: SparseTrieMismatchRecorder,
     /// Whether to disable BAL-based parallel execution (falls back to tx-based prewarming).
     disable_bal_parallel_execution: bool,
     /// Whether to disable BAL-driven parallel state root computation.
@@ -145,6 +149,11 @@ where
         &self.executor
     }
 
+    /// Returns the recorder of the most recent sparse trie computations.
+    pub const fn sparse_trie_mismatch_recorder(&self) -> &SparseTrieMismatchRecorder {
+        &self.sparse_trie_mismatch_recorder
+    }
+
     /// Creates a new payload processor.
     pub fn new(
         executor: Runtime,
@@ -166,6 +175,9 @@ where
             sparse_trie_max_hot_slots: config.sparse_trie_max_hot_slots(),
             sparse_trie_max_hot_accounts: config.sparse_trie_max_hot_accounts(),
             disable_sparse_trie_cache_pruning: config.disable_sparse_trie_cache_pruning(),
+            sparse_trie_mismatch_recorder: SparseTrieMismatchRecorder::new(
+                config.sparse_trie_mismatch_recorder_blocks(),
+            ),
             cache_metrics: (!config.disable_cache_metrics())
                 .then(|| CachedStateMetrics::zeroed(CachedStateMetricsSource::Engine)),
             disable_bal_parallel_execution: config.disable_bal_parallel_execution(),
@@ -630,6 +642,7 @@ where
         let max_hot_slots = self.sparse_trie_max_hot_slots;
         let max_hot_accounts = self.sparse_trie_max_hot_accounts;
         let disable_cache_pruning = self.disable_sparse_trie_cache_pruning;
+        let mismatch_recorder = self.sparse_trie_mismatch_recorder.clone();
         let executor = self.executor.clone();
 
         let parent_span = Span::current();
@@ -649,6 +662,8 @@ where
                 .sparse_trie_cache_wait_duration_histogram
                 .record(start.elapsed().as_secs_f64());
 
+            let reused_trie = preserved.as_ref().and_then(PreservedSparseTrie::anchor) ==
+                Some(parent_state_root);
             let mut sparse_state_trie = preserved
                 .map(|preserved| preserved.into_trie_for(parent_state_root))
                 .unwrap_or_else(|| {
@@ -666,6 +681,11 @@ where
                 });
             sparse_state_trie.set_hot_cache_capacities(max_hot_slots, max_hot_accounts);
 
+            // A reused trie holds nodes revealed by earlier blocks, which replaying the recorded
+            // computation needs as well.
+            let preserved_trie = (reused_trie && mismatch_recorder.is_enabled())
+                .then(|| PreservedTrieSnapshot::new(parent_state_root, &sparse_state_trie));
+
             let mut task = SparseTrieCacheTask::new_with_trie(
                 &executor,
                 from_multi_proof,
@@ -674,10 +694,18 @@ where
                 sparse_state_trie,
                 parent_state_root,
                 chunk_size,
-            );
+            )
+            .with_mismatch_recording(mismatch_recorder.start(parent_state_root, preserved_trie));
 
             let result = task.run();
 
+            // Push the recorded inputs before sending the result so that they are available when
+            // the result is checked against the header.
+            if let Some(mut run) = task.take_mismatch_recording() {
+                run.state_root = result.as_ref().ok().map(|outcome| outcome.state_root);
+                mismatch_recorder.finish(run);
+            }
+
             // Acquire the guard before sending the result to prevent a race condition:
             // Without this, the next block could start after send() but before store(),
             // causing take() to return None and forcing it to create a new empty trie
@@ -100,6 +100,14 @@ impl PreservedSparseTrie {
         Self::Cleared { trie }
     }
 
+    /// Returns the state root the trie is anchored at, if any.
+    pub(super) const fn anchor(&self) -> Option<B256> {
+        match self {
+            Self::Anchored { state_root, .. } => Some(*state_root),
+            Self::Cleared { .. } => None,
+        }
+    }
+
     /// Consumes self and returns the trie for reuse.
     ///
     /// If the preserved trie is anchored and the parent state root matches, the pruned
@@ -7,7 +7,7 @@ use crate::tree::{
         dispatch_with_chunking, evm_state_to_hashed_post_state, StateRootComputeOutcome,
         StateRootMessage, DEFAULT_MAX_TARGETS_FOR_CHUNKING,
     },
-    payload_processor::multiproof::MultiProofTaskMetrics,
+    payload_processor::{mismatch_recorder::SparseTrieRun, multiproof::MultiProofTaskMetrics},
 };
 use alloy_primitives::B256;
 use alloy_rlp::{Decodable, Encodable};
@@ -106,6 +106,8 @@ pub(super) struct SparseTrieCacheTask<A = ConfigurableSparseTrie, S = Configurab
     /// Number of pending execution/prewarming updates received but not yet passed to
     /// `update_leaves`.
     pending_updates: usize,
+    /// Inputs consumed by this computation, recorded only when the mismatch recorder is enabled.
+    mismatch_recording: Option<SparseTrieRun>,
 
     /// Metrics for the sparse trie.
     metrics: MultiProofTaskMetrics,
@@ -160,10 +162,22 @@ where
             storage_cache_misses: 0,
             pending_targets: Default::default(),
             pending_updates: Default::default(),
+            mismatch_recording: None,
             metrics,
         }
     }
 
+    /// Records the inputs consumed by this computation into the given run.
+    pub(super) fn with_mismatch_recording(mut self, run: Option<SparseTrieRun>) -> Self {
+        self.mismatch_recording = run;
+        self
+    }
+
+    /// Takes the inputs recorded by this computation, if recording was enabled.
+    pub(super) const fn take_mismatch_recording(&mut self) -> Option<SparseTrieRun> {
+        self.mismatch_recording.take()
+    }
+
     /// Runs the hashing task that drains updates from the channel and converts them to
     /// `HashedPostState` in parallel.
     fn run_hashing_task(
@@ -413,7 +427,10 @@ where
         match message {
             SparseTrieTaskMessage::PrefetchProofs(targets) => self.on_prewarm_targets(targets),
             SparseTrieTaskMessage::HashedState(hashed_state) => {
-                self.on_hashed_state_update(hashed_state)
+                self.on_hashed_state_update(&hashed_state);
+                if let Some(run) = &mut self.mismatch_recording {
+                    run.record_hashed_state(Arc::new(hashed_state));
+                }
             }
             SparseTrieTaskMessage::FinishedStateUpdates => self.finished_state_updates = true,
         }
@@ -452,20 +469,20 @@ where
         target = "engine::tree::payload_processor::sparse_trie",
         skip_all
     )]
-    fn on_hashed_state_update(&mut self, hashed_state_update: HashedPostState) {
-        for (address, storage) in hashed_state_update.storages {
+    fn on_hashed_state_update(&mut self, hashed_state_update: &HashedPostState) {
+        for (&address, storage) in &hashed_state_update.storages {
             if !storage.storage.is_empty() {
                 // Look up outer maps once per address instead of once per slot.
                 let new_updates = self.new_storage_updates.entry(address).or_default();
                 let mut existing_updates = self.storage_updates.get_mut(&address);
 
-                for (slot, value) in storage.storage {
+                for (&slot, value) in &storage.storage {
                     self.trie.record_slot_touch(address, slot);
 
                     let encoded = if value.is_zero() {
                         Vec::new()
                     } else {
-                        alloy_rlp::encode_fixed_size(&value).to_vec()
+                        alloy_rlp::encode_fixed_size(value).to_vec()
                     };
                     new_updates.insert(slot, LeafUpdate::Changed(encoded));
 
@@ -485,7 +502,7 @@ where
             self.pending_account_updates.entry(address).or_insert(None);
         }
 
-        for (address, account) in hashed_state_update.accounts {
+        for (&address, &account) in &hashed_state_update.accounts {
             self.trie.record_account_touch(address);
 
             // Track account as touched.
@@ -504,6 +521,10 @@ where
         &mut self,
         result: DecodedMultiProofV2,
     ) -> Result<(), ParallelStateRootError> {
+        // Revealing takes the proof nodes out of the multiproof, so the recording keeps a copy.
+        if let Some(run) = &mut self.mismatch_recording {
+            run.record_multiproof(Arc::new(result.clone()));
+        }
         self.trie.reveal_decoded_multiproof_v2(result).map_err(|e| {
             ParallelStateRootError::Other(format!("could not reveal multiproof: {e:?}"))
         })
@@ -42,8 +42,9 @@ use crate::tree::{
     error::{InsertBlockError, InsertBlockErrorKind, InsertPayloadError},
     instrumented_state::{InstrumentedStateProvider, StateProviderStats},
     multiproof::{StateRootComputeOutcome, StateRootHandle},
-    payload_processor::PayloadProcessor,
+    payload_processor::{mismatch_recorder::SparseTrieMismatch, PayloadProcessor},
     precompile_cache::{CachedPrecompile, CachedPrecompileMetrics, PrecompileCacheMap},
+    state_root_cross_check::{StateRootCrossCheckGate, StateRootCrossCheckReport},
     CacheWaitDurations, CachedStateProvider, EngineApiMetrics, EngineApiTreeState, ExecutionEnv,
     PayloadHandle, StateProviderBuilder, StateProviderDatabase, TreeConfig, WaitForCaches,
 };
@@ -69,8 +70,9 @@ use reth_engine_primitives::{
 };
 use reth_errors::{BlockExecutionError, ProviderResult};
 use reth_evm::{
-    block::BlockExecutor, execute::ExecutableTxFor, ConfigureEvm, EvmEnvFor, ExecutionCtxFor,
-    OnStateHook, SpecFor,
+    block::{BlockExecutionResult, BlockExecutor},
+    execute::ExecutableTxFor,
+    ConfigureEvm, EvmEnvFor, ExecutionCtxFor, OnStateHook, SpecFor,
 };
 use reth_execution_cache::{CacheStats, SavedCache};
 use reth_payload_primitives::{
@@ -96,6 +98,7 @@ use rust_eth_triedb::{get_global_triedb, TrieDBError};
 use std::{
     collections::HashMap,
     panic::{self, AssertUnwindSafe},
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicUsize, Ordering},
         mpsc::RecvTimeoutError,
@@ -202,6 +205,12 @@ where
     changeset_cache: ChangesetCache,
     /// Task runtime for spawning parallel work.
     runtime: reth_tasks::Runtime,
+    /// Directory sparse trie mismatch dumps are written to, defaults to the current directory.
+    sparse_trie_mismatch_dir: Option<PathBuf>,
+    /// Directory state root cross-check dumps are written to, defaults to the current directory.
+    state_root_cross_check_dir: Option<PathBuf>,
+    /// Ensures at most one state root cross-check runs at a time.
+    state_root_cross_check_gate: StateRootCrossCheckGate,
 }
 
 impl<N, P, Evm, V> BasicEngineValidator<P, Evm, V>
@@ -260,9 +269,29 @@ where
             validator,
             changeset_cache,
             runtime,
+            sparse_trie_mismatch_dir: None,
+            state_root_cross_check_dir: None,
+            state_root_cross_check_gate: Default::default(),
         }
     }
 
+    /// Sets the directory sparse trie mismatch dumps are written to.
+    pub fn with_sparse_trie_mismatch_dir(mut self, dir: PathBuf) -> Self {
+        self.sparse_trie_mismatch_dir = Some(dir);
+        self
+    }
+
+    /// Sets the directory state root cross-check dumps are written to.
+    pub fn with_state_root_cross_check_dir(mut self, dir: PathBuf) -> Self {
+        self.state_root_cross_check_dir = Some(dir);
+        self
+    }
+
+    /// Returns the precompile cache map shared by block validation and prewarming.
+    pub const fn precompile_cache_map(&self) -> &PrecompileCacheMap<SpecFor<Evm>> {
+        &self.precompile_cache_map
+    }
+
     /// Converts a [`BlockOrPayload`] to a recovered block.
     #[instrument(level = "debug", target = "engine::tree::payload_validator", skip_all)]
     pub fn convert_to_block<T: PayloadTypes<BuiltPayload: BuiltPayload<Primitives = N>>>(
@@ -342,6 +371,7 @@ where
         input: BlockOrPayload<T>,
         execution_err: InsertBlockErrorKind,
         parent_block: &SealedHeader<N::BlockHeader>,
+        state: &mut EngineApiTreeState<N>,
     ) -> InsertPayloadResult<N>
     where
         V: PayloadValidator<T, Block = N::Block>,
@@ -371,7 +401,33 @@ where
             return Err(InsertBlockError::new(block, consensus_err.into()).into())
         }
 
-        // No header validation errors, return the original execution error
+        // No header validation errors, so the block is invalid if it failed validation during
+        // execution. Internal execution errors don't make the block invalid.
+        if let InsertBlockErrorKind::Execution(BlockExecutionError::Validation(err)) =
+            &execution_err &&
+            let Ok(recovered) = block.clone().try_recover()
+        {
+            // The block wasn't executed to the end, so there is no output to pass to the hook
+            let output = BlockExecutionOutput {
+                result: BlockExecutionResult {
+                    receipts: Vec::new(),
+                    requests: Default::default(),
+                    gas_used: 0,
+                    blob_gas_used: 0,
+                },
+                state: Default::default(),
+            };
+            self.on_invalid_block(
+                parent_block,
+                &recovered,
+                &output,
+                None,
+                &ConsensusError::msg(err),
+                state,
+            );
+        }
+
+        // Return the original execution error
         Err(InsertBlockError::new(block, execution_err).into())
     }
 
@@ -501,7 +557,7 @@ where
             match self.execute_block(state_provider, env, &input, &mut handle) {
                 Ok(result) => result,
                 Err(e) => {
-                    return Err(InsertBlockError::new(self.convert_to_block(input)?, e).into())
+                    return self.handle_execution_error(input, e, &parent_block, ctx.state_mut())
                 }
             };
 
@@ -623,15 +679,11 @@ where
                 has_difflayers = difflayers.is_some(),
                 "mismatched block state root (triedb validate)"
             );
-            self.on_invalid_block(&parent_block, &block, &*output, None, ctx.state_mut());
-            return Err(InsertBlockError::new(
-                block.into_sealed_block(),
-                ConsensusError::BodyStateRootDiff(
-                    GotExpected { got: new_root, expected: block_state_root }.into(),
-                )
-                .into(),
-            )
-            .into());
+            let err = ConsensusError::BodyStateRootDiff(
+                GotExpected { got: new_root, expected: block_state_root }.into(),
+            );
+            self.on_invalid_block(&parent_block, &block, &*output, None, &err, ctx.state_mut());
+            return Err(InsertBlockError::new(block.into_sealed_block(), err.into()).into());
         }
 
         if let Some(valid_block_tx) = valid_block_tx {
@@ -873,7 +925,9 @@ where
         let (output, senders, receipt_root_rx) =
             match self.execute_block(state_provider, env, &input, &mut handle) {
                 Ok(output) => output,
-                Err(err) => return self.handle_execution_error(input, err, &parent_block),
+                Err(err) => {
+                    return self.handle_execution_error(input, err, &parent_block, ctx.state_mut())
+                }
             };
         let execution_duration = execute_block_start.elapsed();
 
@@ -959,6 +1013,14 @@ where
         #[cfg(feature = "trie-debug")]
         let mut trie_debug_recorders = Vec::new();
 
+        // Inputs of the background state root cross-check, if this block is sampled.
+        let cross_check_interval = self.config.state_root_cross_check_interval();
+        let cross_check_inputs = (matches!(strategy, StateRootStrategy::StateRootTask) &&
+            cross_check_interval > 0 &&
+            block.header().number().is_multiple_of(cross_check_interval))
+        .then(|| (provider_builder.clone(), provider_factory.clone(), overlay_builder.clone()));
+        let mut task_outcome = None;
+
         let (state_root, trie_output, root_elapsed) = if self.config.skip_state_root_validation() {
             debug!(target: "engine::tree::payload_validator", "Skipping state root calculation in fastnode mode");
             (
@@ -970,6 +1032,7 @@ where
             let root_time = Instant::now();
             let mut maybe_state_root = None;
             let mut state_root_task_failed = false;
+            let mut task_state_root_mismatch = None;
 
             match strategy {
                 StateRootStrategy::StateRootTask => {
@@ -999,17 +1062,31 @@ where
                                 trie_debug_recorders = debug_recorders;
                             }
 
+                            if cross_check_inputs.is_some() {
+                                task_outcome = Some((state_root, trie_updates.clone(), elapsed));
+                            }
+
                             // Compare trie updates with serial computation if configured
                             if self.config.always_compare_trie_updates() {
-                                let _has_diff = self.compare_trie_updates_with_serial(
+                                let has_diff = self.compare_trie_updates_with_serial(
                                     provider_builder.clone(),
                                     provider_factory,
                                     overlay_builder,
                                     &hashed_state,
                                     trie_updates.as_ref().clone(),
                                 );
-                                #[cfg(feature = "trie-debug")]
-                                if _has_diff {
+                                if has_diff {
+                                    self.dump_sparse_trie_mismatch(
+                                        SparseTrieMismatch {
+                                            block: block.num_hash(),
+                                            parent_state_root: parent_block.state_root(),
+                                            header_state_root: block.header().state_root(),
+                                            task_state_root: state_root,
+                                            fallback_state_root: None,
+                                        },
+                                        &hashed_state,
+                                    );
+                                    #[cfg(feature = "trie-debug")]
                                     Self::write_trie_debug_recorders(
                                         block.header().number(),
                                         &trie_debug_recorders,
@@ -1027,6 +1104,7 @@ where
                                     block_state_root = ?block.header().state_root(),
                                     "State root task returned incorrect state root"
                                 );
+                                task_state_root_mismatch = Some(state_root);
                                 #[cfg(feature = "trie-debug")]
                                 Self::write_trie_debug_recorders(
                                     block.header().number(),
@@ -1080,12 +1158,23 @@ where
                     self.metrics.block_validation.state_root_parallel_fallback_total.increment(1);
                 }
 
-                let (root, updates) = ensure_ok_post_block!(
-                    provider_builder.clone().build().and_then(|provider| {
-                        Self::compute_state_root_serial(provider, &hashed_state)
-                    }),
-                    block
-                );
+                let fallback = provider_builder
+                    .clone()
+                    .build()
+                    .and_then(|provider| Self::compute_state_root_serial(provider, &hashed_state));
+                if let Some(task_state_root) = task_state_root_mismatch {
+                    self.dump_sparse_trie_mismatch(
+                        SparseTrieMismatch {
+                            block: block.num_hash(),
+                            parent_state_root: parent_block.state_root(),
+                            header_state_root: block.header().state_root(),
+                            task_state_root,
+                            fallback_state_root: fallback.as_ref().ok().map(|(root, _)| *root),
+                        },
+                        &hashed_state,
+                    );
+                }
+                let (root, updates) = ensure_ok_post_block!(fallback, block);
 
                 if state_root_task_failed {
                     self.metrics
@@ -1108,23 +1197,19 @@ where
             #[cfg(feature = "trie-debug")]
             Self::write_trie_debug_recorders(block.header().number(), &trie_debug_recorders);
 
+            let err = ConsensusError::BodyStateRootDiff(
+                GotExpected { got: state_root, expected: block.header().state_root() }.into(),
+            );
             // call post-block hook
             self.on_invalid_block(
                 &parent_block,
                 &block,
                 &output,
                 Some((&trie_output, state_root)),
+                &err,
                 ctx.state_mut(),
             );
-            let block_state_root = block.header().state_root();
-            return Err(InsertBlockError::new(
-                block.into_sealed_block(),
-                ConsensusError::BodyStateRootDiff(
-                    GotExpected { got: state_root, expected: block_state_root }.into(),
-                )
-                .into(),
-            )
-            .into())
+            return Err(InsertBlockError::new(block.into_sealed_block(), err.into()).into())
         }
 
         let timing_stats = state_provider_stats.map(|stats| {
@@ -1149,6 +1234,20 @@ where
         // avoiding a long-lived reader that blocks MDBX GC during write commits.
         ensure_ok_post_block!(overlay_factory.database_provider_ro(), block);
 
+        if let Some(((provider_builder, provider_factory, overlay_builder), task_outcome)) =
+            cross_check_inputs.zip(task_outcome)
+        {
+            self.spawn_state_root_cross_check(
+                block.num_hash(),
+                block.header().state_root(),
+                task_outcome,
+                provider_builder,
+                provider_factory,
+                overlay_builder,
+                hashed_state.clone(),
+            );
+        }
+
         let executed_block = self.spawn_deferred_trie_task(
             block,
             output,
@@ -1615,6 +1714,161 @@ where
         false
     }
 
+    /// Cross-checks the state root and trie updates of the state root task against the parallel
+    /// and serial state root computations on a background thread.
+    ///
+    /// Disagreements are recorded as metrics and written to the cross-check directory. The
+    /// sampled block is skipped if the previous cross-check is still running.
+    #[expect(clippy::too_many_arguments)]
+    fn spawn_state_root_cross_check(
+        &self,
+        block: NumHash,
+        header_state_root: B256,
+        (task_state_root, task_trie_updates, task_elapsed): (B256, Arc<TrieUpdates>, Duration),
+        provider_builder: StateProviderBuilder<N, P>,
+        provider_factory: P,
+        overlay_builder: OverlayBuilder<N>,
+        hashed_state: LazyHashedPostState,
+    ) {
+        let metrics = self.metrics.state_root_cross_check.clone();
+        let Some(guard) = self.state_root_cross_check_gate.try_acquire() else {
+            debug!(
+                target: "engine::tree::payload_validator",
+                ?block,
+                "Skipping state root cross-check, previous one is still running"
+            );
+            metrics.skipped_total.increment(1);
+            return
+        };
+        let runtime = self.runtime.clone();
+        let dir = self.state_root_cross_check_dir.clone().unwrap_or_default();
+
+        self.runtime.spawn_blocking(move || {
+            let _guard = guard;
+            let _span = debug_span!(
+                target: "engine::tree::payload_validator",
+                "state_root_cross_check",
+                ?block
+            )
+            .entered();
+
+            let mut report = StateRootCrossCheckReport::new(block, header_state_root);
+            report.push_result("task", task_state_root, task_elapsed);
+
+            let hashed_state = hashed_state.get();
+            let start = Instant::now();
+            let parallel = ParallelStateRoot::new(
+                OverlayStateProviderFactory::new(
+                    provider_factory.clone(),
+                    overlay_builder
+                        .clone()
+                        .with_extended_hashed_state_overlay(hashed_state.clone_into_sorted()),
+                ),
+                hashed_state.construct_prefix_sets().freeze(),
+                runtime,
+            )
+            .incremental_root_with_updates()
+            .map(|result| (result, start.elapsed()))
+            .map_err(|err| err.to_string());
+
+            let start = Instant::now();
+            let serial = provider_builder
+                .build()
+                .and_then(|provider| provider.state_root_with_updates(hashed_state.clone()))
+                .map(|result| (result, start.elapsed()))
+                .map_err(|err| err.to_string());
+
+            let overlay_factory =
+                OverlayStateProviderFactory::new(provider_factory, overlay_builder);
+            let provider = overlay_factory.database_provider_ro();
+            for (name, result) in [("parallel", parallel), ("serial", serial)] {
+                let ((state_root, trie_updates), elapsed) = match result {
+                    Ok(result) => result,
+                    Err(err) => {
+                        report.push_error(name, err);
+                        continue
+                    }
+                };
+                report.push_result(name, state_root, elapsed);
+
+                let diff = provider.as_ref().map_err(ToString::to_string).and_then(|provider| {
+                    super::trie_updates::diff_trie_updates(
+                        provider,
+                        task_trie_updates.as_ref().clone(),
+                        trie_updates,
+                    )
+                    .map_err(|err| err.to_string())
+                });
+                match diff {
+                    Ok(diff) => report.push_trie_updates_diff(name, diff),
+                    Err(err) => report.push_error(name, err),
+                }
+            }
+
+            report.record_metrics(&metrics);
+            if !report.has_disagreements() {
+                debug!(
+                    target: "engine::tree::payload_validator",
+                    ?block,
+                    "State root cross-check passed"
+                );
+                return
+            }
+
+            match report.write(&dir) {
+                Ok(path) => warn!(
+                    target: "engine::tree::payload_validator",
+                    ?block,
+                    root_mismatch = report.has_root_mismatch(),
+                    path = %path.display(),
+                    "State root cross-check found disagreements"
+                ),
+                Err(err) => warn!(
+                    target: "engine::tree::payload_validator",
+                    ?block,
+                    root_mismatch = report.has_root_mismatch(),
+                    %err,
+                    "State root cross-check found disagreements, failed to write report"
+                ),
+            }
+        });
+    }
+
+    /// Dumps the inputs of the most recent sparse trie computations, if the mismatch recorder is
+    /// enabled.
+    fn dump_sparse_trie_mismatch(
+        &self,
+        mismatch: SparseTrieMismatch,
+        hashed_state: &LazyHashedPostState,
+    ) {
+        let recorder = self.payload_processor.sparse_trie_mismatch_recorder();
+        if !recorder.is_enabled() {
+            return
+        }
+
+        let dir = self.sparse_trie_mismatch_dir.as_deref().unwrap_or_else(|| Path::new("."));
+        let prefix_sets = hashed_state.get().construct_prefix_sets();
+        match recorder.dump(dir, &mismatch, &prefix_sets) {
+            Ok(path) => {
+                warn!(
+                    target: "engine::tree::payload_validator",
+                    block = ?mismatch.block,
+                    path = %path.display(),
+                    runs = recorder.len(),
+                    "Wrote sparse trie mismatch dump"
+                );
+            }
+            Err(err) => {
+                warn!(
+                    target: "engine::tree::payload_validator",
+                    block = ?mismatch.block,
+                    %err,
+                    "Failed to write sparse trie mismatch dump"
+                );
+            }
+        }
+    }
+
     /// Writes trie debug recorders to a JSON file for the given block number.
     ///
     /// The file is written to the current working directory as
@@ -1705,7 +1959,7 @@ where
             self.consensus.validate_block_post_execution(block, output, receipt_root_bloom)
         {
             // call post-block hook
-            self.on_invalid_block(parent_block, block, output, None, ctx.state_mut());
+            self.on_invalid_block(parent_block, block, output, None, &err, ctx.state_mut());
             return Err(err.into())
         }
         drop(_enter);
@@ -1721,7 +1975,7 @@ where
             self.validator.validate_block_post_execution_with_hashed_state(hashed_state_ref, block)
         {
             // call post-block hook
-            self.on_invalid_block(parent_block, block, output, None, ctx.state_mut());
+            self.on_invalid_block(parent_block, block, output, None, &err, ctx.state_mut());
             return Err(err.into())
         }
 
@@ -1859,13 +2113,20 @@ where
         block: &RecoveredBlock<N::Block>,
         output: &BlockExecutionOutput<N::Receipt>,
         trie_updates: Option<(&TrieUpdates, B256)>,
+        error: &ConsensusError,
         state: &mut EngineApiTreeState<N>,
     ) {
         if state.invalid_headers.get(&block.hash()).is_some() {
             // we already marked this block as invalid
             return
         }
-        self.invalid_block_hook.on_invalid_block(parent_header, block, output, trie_updates);
+        self.invalid_block_hook.on_invalid_block_with_error(
+            parent_header,
+            block,
+            output,
+            trie_updates,
+            error,
+        );
     }
 
     /// Creates a [`LazyOverlay`] for the parent block without blocking.
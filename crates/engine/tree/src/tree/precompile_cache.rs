@@ -1,20 +1,40 @@
 //! Contains a precompile cache backed by `schnellru::LruMap` (LRU by length).
 
 use alloy_primitives::{
+    keccak256,
     map::{DefaultHashBuilder, FbBuildHasher},
-    Bytes,
+    Bytes, B256,
 };
 use moka::policy::EvictionPolicy;
+use reth_engine_primitives::{PrecompileCacheControl, PrecompileCacheStats};
 use reth_evm::precompiles::{DynPrecompile, Precompile, PrecompileInput};
 use reth_primitives_traits::dashmap::DashMap;
-use revm::precompile::{PrecompileId, PrecompileOutput, PrecompileResult};
+use revm::precompile::{PrecompileId, PrecompileOutput, PrecompileResult, PrecompileStatus};
 use revm_primitives::Address;
-use std::{hash::Hash, sync::Arc};
+use std::{
+    fs,
+    hash::Hash,
+    io::{self, Write},
+    path::Path,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+};
 use tracing::error;
 
 /// Default max cache size for [`PrecompileCache`]
 const MAX_CACHE_SIZE: u32 = 1024 * 1024;
 
+/// Magic bytes at the start of a persisted precompile cache file.
+const PERSISTED_CACHE_MAGIC: &[u8; 4] = b"RPCC";
+
+/// Version of the persisted precompile cache file format.
+const PERSISTED_CACHE_VERSION: u8 = 2;
+
+/// Length of the checksum at the end of a persisted precompile cache file.
+const PERSISTED_CACHE_CHECKSUM_LEN: usize = 32;
+
 /// Stores caches for each precompile.
 #[derive(Debug, Clone, Default)]
 pub struct PrecompileCacheMap<S>(Arc<DashMap<Address, PrecompileCache<S>, FbBuildHasher<20>>>)
@@ -37,28 +57,156 @@ where
         // first EVM is created.
         self.0.entry(address).or_default().clone()
     }
+
+    /// Loads the entries persisted by [`Self::persist`] from the given file, returning the number
+    /// of loaded entries.
+    ///
+    /// Loaded entries are kept aside, keyed by spec and input hash, and are moved into the
+    /// in-memory cache on first use. A missing file is not an error, and a file that doesn't match
+    /// its checksum is removed.
+    pub fn load(&self, path: &Path) -> io::Result<usize> {
+        let data = match fs::read(path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(err) => return Err(err),
+        };
+
+        let Some(records) = verify_checksum(&data) else {
+            fs::remove_file(path)?;
+            return Err(invalid_data("precompile cache checksum mismatch, file discarded"))
+        };
+
+        let mut reader = PersistedCacheReader::new(records)?;
+        let mut loaded = 0;
+        while let Some(record) = reader.next_record()? {
+            let output = PrecompileOutput {
+                status: PrecompileStatus::Success,
+                gas_used: record.gas_used,
+                state_gas_used: 0,
+                reservoir: 0,
+                gas_refunded: 0,
+                bytes: record.output,
+            };
+            self.cache_for_address(record.address)
+                .persisted
+                .insert((record.input_hash, record.spec), output);
+            loaded += 1;
+        }
+        Ok(loaded)
+    }
+
+    /// Writes the most frequently hit entries of all caches to the given file, returning the
+    /// number of written entries.
+    ///
+    /// Entries are written until the file reaches `max_size` bytes. Loaded entries that were not
+    /// used since the start are kept with the lowest priority.
+    pub fn persist(&self, path: &Path, max_size: usize) -> io::Result<usize> {
+        let mut records = Vec::new();
+        for cache in self.0.iter() {
+            let address = *cache.key();
+            for (input, entry) in cache.entries.iter() {
+                // Refunds are not persisted, and precompiles are not expected to refund gas.
+                if entry.output.gas_refunded != 0 {
+                    continue
+                }
+                records.push((
+                    entry.hits.load(Ordering::Relaxed),
+                    PersistedRecord {
+                        address,
+                        spec: format!("{:?}", entry.spec),
+                        input_hash: keccak256(input.as_ref()),
+                        gas_used: entry.gas_used(),
+                        output: entry.output.bytes,
+                    },
+                ));
+            }
+            for persisted in cache.persisted.iter() {
+                let ((input_hash, spec), output) = persisted.pair();
+                records.push((
+                    0,
+                    PersistedRecord {
+                        address,
+                        spec: spec.clone(),
+                        input_hash: *input_hash,
+                        gas_used: output.gas_used,
+                        output: output.bytes.clone(),
+                    },
+                ));
+            }
+        }
+        records.sort_unstable_by_key(|(hits, _)| std::cmp::Reverse(*hits));
+
+        let mut data = Vec::new();
+        data.extend_from_slice(PERSISTED_CACHE_MAGIC);
+        data.push(PERSISTED_CACHE_VERSION);
+
+        let mut written = 0;
+        for (_, record) in records {
+            if data.len() + record.encoded_len() + PERSISTED_CACHE_CHECKSUM_LEN > max_size {
+                break
+            }
+            record.write(&mut data)?;
+            written += 1;
+        }
+        let checksum = keccak256(&data);
+        data.extend_from_slice(checksum.as_slice());
+
+        let tmp_path = path.with_extension("tmp");
+        let mut file = fs::File::create(&tmp_path)?;
+        file.write_all(&data)?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, path)?;
+
+        Ok(written)
+    }
+}
+
+impl<S> PrecompileCacheControl for PrecompileCacheMap<S>
+where
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    fn stats(&self) -> Vec<PrecompileCacheStats> {
+        self.0.iter().map(|cache| cache.stats(*cache.key())).collect()
+    }
+
+    fn clear(&self) -> u64 {
+        self.0.iter().map(|cache| cache.clear()).sum()
+    }
 }
 
 /// Cache for precompiles, for each input stores the result.
 #[derive(Debug, Clone)]
-pub struct PrecompileCache<S>(moka::sync::Cache<Bytes, CacheEntry<S>, DefaultHashBuilder>)
+pub struct PrecompileCache<S>
 where
-    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static;
+    S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
+{
+    /// In-memory cache of precompile results, keyed by input.
+    entries: moka::sync::Cache<Bytes, CacheEntry<S>, DefaultHashBuilder>,
+    /// Results loaded from disk that were not used yet, keyed by input hash and spec.
+    persisted: Arc<DashMap<(B256, String), PrecompileOutput>>,
+    /// Number of calls served from the cache.
+    hits: Arc<AtomicU64>,
+    /// Number of calls that executed the precompile.
+    misses: Arc<AtomicU64>,
+}
 
 impl<S> Default for PrecompileCache<S>
 where
     S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
 {
     fn default() -> Self {
-        Self(
-            moka::sync::CacheBuilder::new(MAX_CACHE_SIZE as u64)
+        Self {
+            entries: moka::sync::CacheBuilder::new(MAX_CACHE_SIZE as u64)
                 .initial_capacity(MAX_CACHE_SIZE as usize)
                 .eviction_policy(EvictionPolicy::lru())
                 .weigher(|key: &Bytes, value: &CacheEntry<S>| {
                     (key.len() + value.output.bytes.len()) as u32
                 })
                 .build_with_hasher(Default::default()),
-        )
+            persisted: Default::default(),
+            hits: Default::default(),
+            misses: Default::default(),
+        }
     }
 }
 
@@ -67,26 +215,91 @@ where
     S: Eq + Hash + std::fmt::Debug + Send + Sync + Clone + 'static,
 {
     fn get(&self, input: &[u8], spec: S) -> Option<CacheEntry<S>> {
-        self.0.get(input).filter(|e| e.spec == spec)
+        if let Some(entry) = self.entries.get(input).filter(|e| e.spec == spec) {
+            return Some(entry)
+        }
+        if self.persisted.is_empty() {
+            return None
+        }
+
+        // Promote the persisted result into the in-memory cache.
+        let (_, output) = self.persisted.remove(&(keccak256(input), format!("{spec:?}")))?;
+        let entry = CacheEntry::new(output, spec);
+        self.entries.insert(Bytes::copy_from_slice(input), entry.clone());
+        Some(entry)
     }
 
     /// Inserts the given key and value into the cache, returning the new cache size.
     fn insert(&self, input: Bytes, value: CacheEntry<S>) -> usize {
-        self.0.insert(input, value);
-        self.0.entry_count() as usize
+        self.entries.insert(input, value);
+        self.entries.entry_count() as usize
+    }
+
+    /// Records a call served from the given entry.
+    fn record_hit(&self, entry: &CacheEntry<S>) {
+        entry.hits.fetch_add(1, Ordering::Relaxed);
+        self.hits.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records a call that executed the precompile, whether or not its result was cached.
+    fn record_miss(&self) {
+        self.misses.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Returns the share of calls served from the cache.
+    fn hit_rate(&self) -> f64 {
+        let hits = self.hits.load(Ordering::Relaxed);
+        let total = hits + self.misses.load(Ordering::Relaxed);
+        if total == 0 {
+            return 0.0
+        }
+        hits as f64 / total as f64
+    }
+
+    /// Returns the statistics of the cache.
+    fn stats(&self, address: Address) -> PrecompileCacheStats {
+        PrecompileCacheStats {
+            address,
+            entries: self.entries.entry_count(),
+            persisted_entries: self.persisted.len() as u64,
+            hits: self.hits.load(Ordering::Relaxed),
+            misses: self.misses.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Removes all entries, including the persisted ones, returning the number of removed entries.
+    fn clear(&self) -> u64 {
+        let removed = self.entries.entry_count() + self.persisted.len() as u64;
+        self.entries.invalidate_all();
+        self.persisted.clear();
+        removed
     }
 }
 
 /// Cache entry for a successful precompile output.
 ///
 /// We intentionally do not cache non-successful statuses or errors.
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct CacheEntry<S> {
     output: PrecompileOutput,
     spec: S,
+    /// Number of calls served from this entry, used to pick the entries to persist.
+    hits: Arc<AtomicU64>,
 }
 
+impl<S: PartialEq> PartialEq for CacheEntry<S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.output == other.output && self.spec == other.spec
+    }
+}
+
+impl<S: Eq> Eq for CacheEntry<S> {}
+
 impl<S> CacheEntry<S> {
+    fn new(output: PrecompileOutput, spec: S) -> Self {
+        Self { output, spec, hits: Default::default() }
+    }
+
     const fn gas_used(&self) -> u64 {
         self.output.gas_used
     }
@@ -150,12 +363,14 @@ where
     fn increment_by_one_precompile_cache_hits(&self) {
         if let Some(metrics) = &self.metrics {
             metrics.precompile_cache_hits.increment(1);
+            metrics.precompile_cache_hit_rate.set(self.cache.hit_rate());
         }
     }
 
     fn increment_by_one_precompile_cache_misses(&self) {
         if let Some(metrics) = &self.metrics {
             metrics.precompile_cache_misses.increment(1);
+            metrics.precompile_cache_hit_rate.set(self.cache.hit_rate());
         }
     }
 
@@ -184,6 +399,7 @@ where
         if let Some(entry) = &self.cache.get(input.data, self.spec_id.clone()) &&
             input.gas >= entry.gas_used()
         {
+            self.cache.record_hit(entry);
             self.increment_by_one_precompile_cache_hits();
             return entry.to_precompile_result(input.reservoir);
         }
@@ -191,6 +407,8 @@ where
         let calldata = input.data;
         let reservoir = input.reservoir;
         let result = self.precompile.call(input);
+        self.cache.record_miss();
+        self.increment_by_one_precompile_cache_misses();
 
         match &result {
             // Only successful outputs are cacheable. Non-success statuses and errors must execute
@@ -208,10 +426,9 @@ where
                 } else {
                     let size = self.cache.insert(
                         Bytes::copy_from_slice(calldata),
-                        CacheEntry { output: output.clone(), spec: self.spec_id.clone() },
+                        CacheEntry::new(output.clone(), self.spec_id.clone()),
                     );
                     self.set_precompile_cache_size_metric(size as f64);
-                    self.increment_by_one_precompile_cache_misses();
                 }
             }
             _ => {
@@ -222,6 +439,99 @@ where
     }
 }
 
+/// A precompile result as stored in the persisted cache file.
+///
+/// Records are encoded as `address (20) | spec length (1) | spec | input hash (32) | gas used (8) |
+/// output length (4) | output`, with integers in big-endian order. The spec is stored as its
+/// [`Debug`](std::fmt::Debug) representation, so entries of an unknown spec are never used. The
+/// file ends with the keccak256 hash of everything before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct PersistedRecord {
+    address: Address,
+    spec: String,
+    input_hash: B256,
+    gas_used: u64,
+    output: Bytes,
+}
+
+impl PersistedRecord {
+    /// Returns the length of the encoded record.
+    fn encoded_len(&self) -> usize {
+        20 + 1 + self.spec.len().min(u8::MAX as usize) + 32 + 8 + 4 + self.output.len()
+    }
+
+    /// Writes the encoded record.
+    fn write(&self, writer: &mut impl Write) -> io::Result<()> {
+        let spec = &self.spec.as_bytes()[..self.spec.len().min(u8::MAX as usize)];
+        let output_len = u32::try_from(self.output.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "output too large"))?;
+        writer.write_all(self.address.as_slice())?;
+        writer.write_all(&[spec.len() as u8])?;
+        writer.write_all(spec)?;
+        writer.write_all(self.input_hash.as_slice())?;
+        writer.write_all(&self.gas_used.to_be_bytes())?;
+        writer.write_all(&output_len.to_be_bytes())?;
+        writer.write_all(&self.output)
+    }
+}
+
+/// Reads [`PersistedRecord`]s from the contents of a persisted cache file.
+#[derive(Debug)]
+struct PersistedCacheReader<'a> {
+    data: &'a [u8],
+}
+
+impl<'a> PersistedCacheReader<'a> {
+    /// Checks the file header and returns a reader positioned at the first record.
+    fn new(data: &'a [u8]) -> io::Result<Self> {
+        let mut reader = Self { data };
+        if reader.take(PERSISTED_CACHE_MAGIC.len())? != PERSISTED_CACHE_MAGIC {
+            return Err(invalid_data("not a precompile cache file"))
+        }
+        let version = reader.take(1)?[0];
+        if version != PERSISTED_CACHE_VERSION {
+            return Err(invalid_data(format!("unsupported precompile cache version {version}")))
+        }
+        Ok(reader)
+    }
+
+    /// Returns the next record, or `None` at the end of the file.
+    fn next_record(&mut self) -> io::Result<Option<PersistedRecord>> {
+        if self.data.is_empty() {
+            return Ok(None)
+        }
+        let address = Address::from_slice(self.take(20)?);
+        let spec_len = self.take(1)?[0] as usize;
+        let spec = String::from_utf8(self.take(spec_len)?.to_vec()).map_err(invalid_data)?;
+        let input_hash = B256::from_slice(self.take(32)?);
+        let gas_used = u64::from_be_bytes(self.take(8)?.try_into().expect("8 bytes"));
+        let output_len = u32::from_be_bytes(self.take(4)?.try_into().expect("4 bytes")) as usize;
+        let output = Bytes::copy_from_slice(self.take(output_len)?);
+        Ok(Some(PersistedRecord { address, spec, input_hash, gas_used, output }))
+    }
+
+    fn take(&mut self, len: usize) -> io::Result<&'a [u8]> {
+        if self.data.len() < len {
+            return Err(invalid_data("truncated precompile cache file"))
+        }
+        let (head, tail) = self.data.split_at(len);
+        self.data = tail;
+        Ok(head)
+    }
+}
+
+/// Returns the contents of a persisted cache file without the checksum at its end, or `None` if
+/// the checksum doesn't match.
+fn verify_checksum(data: &[u8]) -> Option<&[u8]> {
+    let (contents, checksum) =
+        data.split_at_checked(data.len().checked_sub(PERSISTED_CACHE_CHECKSUM_LEN)?)?;
+    (keccak256(contents).as_slice() == checksum).then_some(contents)
+}
+
+fn invalid_data(err: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
 /// Metrics for the cached precompile.
 #[derive(reth_metrics::Metrics, Clone)]
 #[metrics(scope = "sync.caching")]
@@ -235,6 +545,9 @@ pub struct CachedPrecompileMetrics {
     /// Precompile cache size. Uses the LRU cache length as the size metric.
     pub precompile_cache_size: metrics::Gauge,
 
+    /// Share of precompile calls served from the cache.
+    pub precompile_cache_hit_rate: metrics::Gauge,
+
     /// Precompile execution errors.
     pub precompile_errors: metrics::Counter,
 }
@@ -256,7 +569,7 @@ mod tests {
     use reth_revm::db::EmptyDB;
     use revm::{
         context::TxEnv,
-        precompile::{PrecompileOutput, PrecompileStatus},
+        precompile::{PrecompileError, PrecompileOutput, PrecompileStatus},
     };
     use revm_primitives::hardfork::SpecId;
 
@@ -287,7 +600,7 @@ mod tests {
         };
 
         let input = b"test_input";
-        let expected = CacheEntry { output, spec: SpecId::PRAGUE };
+        let expected = CacheEntry::new(output, SpecId::PRAGUE);
         cache.cache.insert(input.into(), expected.clone());
 
         let actual = cache.cache.get(input, SpecId::PRAGUE).unwrap();
@@ -405,4 +718,130 @@ mod tests {
             .unwrap();
         assert_eq!(result3.as_ref(), b"output_from_precompile_1");
     }
+
+    #[test]
+    fn test_precompile_cache_persist_and_load() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("precompile-cache.bin");
+        let address = Address::with_last_byte(1);
+        let output = |bytes: &'static [u8]| PrecompileOutput {
+            status: PrecompileStatus::Success,
+            gas_used: 100,
+            state_gas_used: 0,
+            reservoir: 0,
+            gas_refunded: 0,
+            bytes: Bytes::from_static(bytes),
+        };
+
+        let cache_map = PrecompileCacheMap::default();
+        let cache = cache_map.cache_for_address(address);
+        cache.insert(Bytes::from_static(b"hot"), CacheEntry::new(output(b"hot"), SpecId::PRAGUE));
+        cache.insert(Bytes::from_static(b"cold"), CacheEntry::new(output(b"cold"), SpecId::PRAGUE));
+        cache.record_hit(&cache.get(b"hot", SpecId::PRAGUE).unwrap());
+
+        // only the hot entry fits
+        let header_len = PERSISTED_CACHE_MAGIC.len() + 1;
+        let record_len = 20 + 1 + "PRAGUE".len() + 32 + 8 + 4 + 3;
+        let max_size = header_len + record_len + PERSISTED_CACHE_CHECKSUM_LEN;
+        assert_eq!(cache_map.persist(&path, max_size).unwrap(), 1);
+
+        let loaded = PrecompileCacheMap::default();
+        assert_eq!(loaded.load(&path).unwrap(), 1);
+        let cache = loaded.cache_for_address(address);
+        assert_eq!(cache.stats(address).persisted_entries, 1);
+
+        // entries are only used for the same spec
+        assert!(cache.get(b"hot", SpecId::CANCUN).is_none());
+        assert!(cache.get(b"cold", SpecId::PRAGUE).is_none());
+        assert_eq!(cache.get(b"hot", SpecId::PRAGUE).unwrap().output, output(b"hot"));
+        assert_eq!(cache.stats(address).persisted_entries, 0);
+
+        assert_eq!(loaded.clear(), 1);
+        assert!(cache.get(b"hot", SpecId::PRAGUE).is_none());
+    }
+
+    #[test]
+    fn test_precompile_cache_load_discards_corrupted_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("precompile-cache.bin");
+
+        let cache_map = PrecompileCacheMap::default();
+        cache_map.cache_for_address(Address::with_last_byte(1)).insert(
+            Bytes::from_static(b"input"),
+            CacheEntry::new(
+                PrecompileOutput {
+                    status: PrecompileStatus::Success,
+                    gas_used: 100,
+                    state_gas_used: 0,
+                    reservoir: 0,
+                    gas_refunded: 0,
+                    bytes: Bytes::from_static(b"output"),
+                },
+                SpecId::PRAGUE,
+            ),
+        );
+        assert_eq!(cache_map.persist(&path, usize::MAX).unwrap(), 1);
+
+        // flip a byte of the output
+        let mut data = fs::read(&path).unwrap();
+        let len = data.len();
+        data[len - PERSISTED_CACHE_CHECKSUM_LEN - 1] ^= 1;
+        fs::write(&path, data).unwrap();
+
+        let loaded = PrecompileCacheMap::<SpecId>::default();
+        let err = loaded.load(&path).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(!path.exists());
+        assert!(loaded.stats().is_empty());
+
+        // the discarded file is treated as missing afterwards
+        assert_eq!(loaded.load(&path).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_precompile_cache_counts_every_execution_as_miss() {
+        let mut evm = EthEvmFactory::default().create_evm(EmptyDB::default(), EvmEnv::default());
+        let address = Address::with_last_byte(1);
+        let cache_map = PrecompileCacheMap::default();
+
+        let precompile: DynPrecompile = (PrecompileId::custom("custom"), {
+            |input: PrecompileInput<'_>| -> PrecompileResult {
+                if input.data == b"fail" {
+                    return Err(PrecompileError::OutOfGas)
+                }
+                Ok(PrecompileOutput {
+                    status: PrecompileStatus::Success,
+                    gas_used: 100,
+                    state_gas_used: 0,
+                    reservoir: 0,
+                    gas_refunded: 0,
+                    bytes: Bytes::from_static(b"output"),
+                })
+            }
+        })
+            .into();
+        let wrapped = CachedPrecompile::wrap(
+            precompile,
+            cache_map.cache_for_address(address),
+            SpecId::PRAGUE,
+            None,
+        );
+        evm.precompiles_mut().apply_precompile(&address, |_| Some(wrapped));
+
+        for data in [&b"ok"[..], b"ok", b"fail", b"fail"] {
+            let _ = evm
+                .transact_raw(TxEnv {
+                    caller: Address::ZERO,
+                    gas_limit: 100_000,
+                    data: Bytes::copy_from_slice(data),
+                    kind: address.into(),
+                    ..Default::default()
+                })
+                .unwrap();
+        }
+
+        // the first successful call and both failed calls executed the precompile
+        let stats = cache_map.cache_for_address(address).stats(address);
+        assert_eq!((stats.hits, stats.misses), (1, 3));
+    }
 }
@@ -0,0 +1,214 @@
+//! Sampled cross-check of the state root task against the other state root algorithms.
+//!
+//! For sampled blocks, the state root and trie updates returned by the sparse trie task are
+//! compared against [`ParallelStateRoot`](reth_trie_parallel::root::ParallelStateRoot) and the
+//! serial `StateRoot` in the background, after the block was validated. Disagreements are
+//! reported as metrics and written to a JSON file so that trie bugs are caught before a block
+//! with a wrong root halts the node.
+
+use crate::tree::{metrics::StateRootCrossCheckMetrics, trie_updates::TrieUpdatesDiff};
+use alloy_eips::NumHash;
+use alloy_primitives::B256;
+use serde_json::json;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+/// State root computed by one of the cross-checked algorithms.
+#[derive(Debug)]
+struct AlgorithmResult {
+    /// Name of the algorithm.
+    name: &'static str,
+    /// The computed state root.
+    state_root: B256,
+    /// Time spent computing the state root.
+    elapsed: Duration,
+}
+
+/// Outcome of cross-checking the state root of a single block.
+#[derive(Debug)]
+pub(crate) struct StateRootCrossCheckReport {
+    /// The cross-checked block.
+    block: NumHash,
+    /// The state root in the block header.
+    header_state_root: B256,
+    /// State roots of all algorithms that succeeded, starting with the state root task.
+    results: Vec<AlgorithmResult>,
+    /// Trie updates differences between the state root task and the named algorithm.
+    trie_updates_diffs: Vec<(&'static str, TrieUpdatesDiff)>,
+    /// Errors of algorithms or comparisons that failed.
+    errors: Vec<String>,
+}
+
+impl StateRootCrossCheckReport {
+    /// Creates a new report for the given block.
+    pub(crate) const fn new(block: NumHash, header_state_root: B256) -> Self {
+        Self {
+            block,
+            header_state_root,
+            results: Vec::new(),
+            trie_updates_diffs: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Records the state root computed by an algorithm.
+    pub(crate) fn push_result(&mut self, name: &'static str, state_root: B256, elapsed: Duration) {
+        self.results.push(AlgorithmResult { name, state_root, elapsed });
+    }
+
+    /// Records the trie updates differences between the state root task and an algorithm.
+    pub(crate) fn push_trie_updates_diff(&mut self, name: &'static str, diff: TrieUpdatesDiff) {
+        if diff.has_differences() {
+            self.trie_updates_diffs.push((name, diff));
+        }
+    }
+
+    /// Records an error of an algorithm or comparison.
+    pub(crate) fn push_error(&mut self, name: &'static str, error: impl std::fmt::Display) {
+        self.errors.push(format!("{name}: {error}"));
+    }
+
+    /// Returns `true` if any of the computed state roots differs from the header.
+    pub(crate) fn has_root_mismatch(&self) -> bool {
+        self.results.iter().any(|result| result.state_root != self.header_state_root)
+    }
+
+    /// Returns `true` if the state root task and any other algorithm disagree, or if any of them
+    /// failed.
+    pub(crate) fn has_disagreements(&self) -> bool {
+        self.has_root_mismatch() || !self.trie_updates_diffs.is_empty() || !self.errors.is_empty()
+    }
+
+    /// Records the outcome of the cross-check.
+    pub(crate) fn record_metrics(&self, metrics: &StateRootCrossCheckMetrics) {
+        metrics.checks_total.increment(1);
+        metrics.errors_total.increment(self.errors.len() as u64);
+        if self.has_root_mismatch() {
+            metrics.root_mismatches_total.increment(1);
+        }
+        if !self.trie_updates_diffs.is_empty() {
+            metrics.trie_updates_mismatches_total.increment(1);
+        }
+        metrics.differing_trie_nodes_total.increment(
+            self.trie_updates_diffs.iter().map(|(_, diff)| diff.num_differences() as u64).sum(),
+        );
+        for result in &self.results {
+            let histogram = match result.name {
+                "task" => &metrics.task_duration,
+                "parallel" => &metrics.parallel_duration,
+                _ => &metrics.serial_duration,
+            };
+            histogram.record(result.elapsed.as_secs_f64());
+        }
+    }
+
+    /// Writes the report to `state_root_cross_check_{number}_{hash}.json` in `dir`.
+    ///
+    /// Returns the path of the written file.
+    pub(crate) fn write(&self, dir: &Path) -> io::Result<PathBuf> {
+        let json = json!({
+            "block": {
+                "number": self.block.number,
+                "hash": self.block.hash.to_string(),
+            },
+            "header_state_root": self.header_state_root.to_string(),
+            "results": self
+                .results
+                .iter()
+                .map(|result| json!({
+                    "algorithm": result.name,
+                    "state_root": result.state_root.to_string(),
+                    "elapsed_ms": result.elapsed.as_secs_f64() * 1000.0,
+                }))
+                .collect::<Vec<_>>(),
+            "trie_updates_diffs": self
+                .trie_updates_diffs
+                .iter()
+                .map(|(name, diff)| (format!("task_vs_{name}"), diff.to_json()))
+                .collect::<serde_json::Map<_, _>>(),
+            "errors": self.errors,
+        });
+
+        std::fs::create_dir_all(dir)?;
+        let path = dir
+            .join(format!("state_root_cross_check_{}_{}.json", self.block.number, self.block.hash));
+        std::fs::write(&path, serde_json::to_vec_pretty(&json)?)?;
+        Ok(path)
+    }
+}
+
+/// Ensures at most one cross-check runs at a time, so that a slow cross-check never piles up
+/// work behind the live chain.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct StateRootCrossCheckGate(Arc<AtomicBool>);
+
+impl StateRootCrossCheckGate {
+    /// Returns a guard if no other cross-check is running, released when the guard is dropped.
+    pub(crate) fn try_acquire(&self) -> Option<StateRootCrossCheckGuard> {
+        self.0
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+            .then(|| StateRootCrossCheckGuard(self.0.clone()))
+    }
+}
+
+/// Guard of a running cross-check, see [`StateRootCrossCheckGate`].
+#[derive(Debug)]
+pub(crate) struct StateRootCrossCheckGuard(Arc<AtomicBool>);
+
+impl Drop for StateRootCrossCheckGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    fn report() -> StateRootCrossCheckReport {
+        let root = B256::with_last_byte(1);
+        let mut report = StateRootCrossCheckReport::new(NumHash::new(7, B256::ZERO), root);
+        report.push_result("task", root, Duration::from_millis(1));
+        report.push_result("parallel", root, Duration::from_millis(2));
+        report
+    }
+
+    #[test]
+    fn agreeing_roots_have_no_disagreements() {
+        let mut report = report();
+        report.push_trie_updates_diff("parallel", TrieUpdatesDiff::default());
+        assert!(!report.has_disagreements());
+    }
+
+    #[test]
+    fn reports_root_mismatch() {
+        let mut report = report();
+        report.push_result("serial", B256::with_last_byte(2), Duration::from_millis(3));
+        assert!(report.has_root_mismatch());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = report.write(dir.path()).unwrap();
+        let json: Value = serde_json::from_slice(&std::fs::read(path).unwrap()).unwrap();
+        assert_eq!(json["block"]["number"], 7);
+        assert_eq!(json["results"][2]["algorithm"], "serial");
+        assert_eq!(json["results"][2]["state_root"], B256::with_last_byte(2).to_string());
+    }
+
+    #[test]
+    fn gate_allows_single_cross_check() {
+        let gate = StateRootCrossCheckGate::default();
+        let guard = gate.try_acquire().unwrap();
+        assert!(gate.try_acquire().is_none());
+        drop(guard);
+        assert!(gate.try_acquire().is_some());
+    }
+}
@@ -2478,3 +2478,577 @@ fn query_header_with_td_fork_below_tip_unknown_parent_returns_ok_none() {
     assert_eq!(SealedHeader::seal_slow(header).hash(), orphan_fork.recovered_block().hash(),);
     assert_eq!(td, None);
 }
+
+/// Property-based tests that drive the tree with random `newPayload` and `forkchoiceUpdated`
+/// sequences over a generated block tree, and check its invariants after every step.
+///
+/// The tree runs on top of a real test database, and every message goes through its public entry
+/// points. Only execution is mocked: `FuzzValidator` returns the precomputed output of valid
+/// blocks and a consensus error for invalid ones. Persistence tasks are played against the same
+/// database, so reorgs below the persisted tip unwind it like the persistence service would.
+/// Failing cases are shrunk by proptest to a minimal sequence of blocks and messages.
+mod fuzz_tests {
+    use super::*;
+    use proptest::{prelude::*, sample::Index, test_runner::RngSeed};
+    use reth_primitives_traits::GotExpected;
+    use reth_provider::{
+        providers::BlockchainProvider,
+        test_utils::{create_test_provider_factory_with_chain_spec, MockNodeTypesWithDB},
+        BlockExecutionWriter, BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
+        ProviderFactory, SaveBlocksMode,
+    };
+    use std::sync::mpsc::channel;
+
+    /// Seed of the generated sequences, pinned so that failures reproduce across runs.
+    const RNG_SEED: u64 = 0x7265_7468_7472_6565;
+
+    /// A block of the generated block tree.
+    #[derive(Debug, Clone, Copy)]
+    struct BlockSpec {
+        /// Selects the parent among genesis and the blocks generated before this one.
+        parent: Index,
+        /// Whether the block fails execution.
+        invalid: bool,
+    }
+
+    /// A single step of the fuzzed sequence.
+    #[derive(Debug, Clone, Copy)]
+    enum Op {
+        /// Sends the selected block as `newPayload`.
+        NewPayload(Index),
+        /// Sends a `forkchoiceUpdated` with the selected block as head.
+        ForkchoiceUpdated(Index),
+        /// Lets the tree start a persistence task if it wants to.
+        AdvancePersistence,
+        /// Completes the in-flight persistence task, if any.
+        CompletePersistence,
+    }
+
+    fn block_spec_strategy() -> impl Strategy<Value = BlockSpec> {
+        (any::<Index>(), prop::bool::weighted(0.15))
+            .prop_map(|(parent, invalid)| BlockSpec { parent, invalid })
+    }
+
+    fn op_strategy() -> impl Strategy<Value = Op> {
+        prop_oneof![
+            4 => any::<Index>().prop_map(Op::NewPayload),
+            3 => any::<Index>().prop_map(Op::ForkchoiceUpdated),
+            1 => Just(Op::AdvancePersistence),
+            1 => Just(Op::CompletePersistence),
+        ]
+    }
+
+    /// A block known to the test.
+    #[derive(Debug, Clone)]
+    struct FuzzBlock {
+        executed: ExecutedBlock,
+        /// Whether executing the block itself fails.
+        fails_execution: bool,
+        /// Whether the block or any of its ancestors fails execution.
+        invalid: bool,
+    }
+
+    impl FuzzBlock {
+        fn sealed_block(&self) -> &SealedBlock<Block> {
+            self.executed.sealed_block()
+        }
+
+        fn num_hash(&self) -> BlockNumHash {
+            self.executed.recovered_block().num_hash()
+        }
+
+        fn parent_hash(&self) -> B256 {
+            self.executed.recovered_block().parent_hash
+        }
+    }
+
+    /// Generates a block that survives the round trip through an `ExecutionPayloadV1`.
+    fn payload_v1_block(
+        builder: &mut TestBlockBuilder,
+        number: u64,
+        parent_hash: B256,
+    ) -> ExecutedBlock {
+        let mut block = builder.generate_random_block(number, parent_hash).into_block();
+        block.header.withdrawals_root = None;
+        block.header.blob_gas_used = None;
+        block.header.excess_blob_gas = None;
+        block.header.parent_beacon_block_root = None;
+        block.body.withdrawals = None;
+
+        let block = block.seal_slow();
+        let senders = vec![builder.signer; block.body().transactions.len()];
+        ExecutedBlock::new(
+            Arc::new(RecoveredBlock::new_sealed(block, senders)),
+            Arc::new(BlockExecutionOutput::default()),
+            ComputedTrieData::default(),
+        )
+    }
+
+    /// Validator that stands in for execution with the outcome known to the test.
+    #[derive(Debug)]
+    struct FuzzValidator {
+        /// Output of the blocks that execute successfully.
+        executed: B256Map<ExecutedBlock>,
+        /// Blocks that fail execution.
+        fails_execution: B256Set,
+    }
+
+    impl FuzzValidator {
+        fn execute(&self, block: SealedBlock<Block>) -> ValidationOutcome<EthPrimitives> {
+            if self.fails_execution.contains(&block.hash()) {
+                let error = ConsensusError::BodyStateRootDiff(
+                    GotExpected { got: B256::ZERO, expected: block.header().state_root }.into(),
+                );
+                return Err(InsertBlockError::consensus_error(error, block).into())
+            }
+
+            let executed = self.executed.get(&block.hash()).unwrap_or_else(|| {
+                panic!("block {} with an invalid ancestor executed", block.hash())
+            });
+            Ok((executed.clone(), None))
+        }
+    }
+
+    impl EngineValidator<EthEngineTypes> for FuzzValidator {
+        fn validate_payload_attributes_against_header(
+            &self,
+            _attr: &alloy_rpc_types_engine::PayloadAttributes,
+            _header: &alloy_consensus::Header,
+        ) -> Result<(), reth_payload_primitives::InvalidPayloadAttributesError> {
+            Ok(())
+        }
+
+        fn convert_payload_to_block(
+            &self,
+            payload: ExecutionData,
+        ) -> Result<SealedBlock<Block>, NewPayloadError> {
+            reth_engine_primitives::PayloadValidator::<EthEngineTypes>::convert_payload_to_block(
+                &MockEngineValidator,
+                payload,
+            )
+        }
+
+        fn validate_payload(
+            &mut self,
+            payload: ExecutionData,
+            _ctx: TreeCtx<'_, EthPrimitives>,
+        ) -> ValidationOutcome<EthPrimitives> {
+            let block = self.convert_payload_to_block(payload)?;
+            self.execute(block)
+        }
+
+        fn validate_block(
+            &mut self,
+            block: SealedBlock<Block>,
+            _ctx: TreeCtx<'_, EthPrimitives>,
+        ) -> ValidationOutcome<EthPrimitives> {
+            self.execute(block)
+        }
+
+        fn on_inserted_executed_block(&self, _block: ExecutedBlock) {}
+
+        fn cache_for(&self, _block_hash: B256) -> Option<SavedCache> {
+            None
+        }
+
+        fn sparse_trie_handle_for(
+            &self,
+            _parent_hash: B256,
+            _parent_state_root: B256,
+            _state: &EngineApiTreeState<EthPrimitives>,
+        ) -> Option<crate::tree::multiproof::StateRootHandle> {
+            None
+        }
+    }
+
+    impl WaitForCaches for FuzzValidator {
+        fn wait_for_caches(&self) -> CacheWaitDurations {
+            CacheWaitDurations::default()
+        }
+    }
+
+    type FuzzTree = EngineApiTreeHandler<
+        EthPrimitives,
+        BlockchainProvider<MockNodeTypesWithDB>,
+        EthEngineTypes,
+        FuzzValidator,
+        MockEvmConfig,
+    >;
+
+    struct FuzzHarness {
+        tree: FuzzTree,
+        /// The database behind the tree's provider.
+        factory: ProviderFactory<MockNodeTypesWithDB>,
+        action_rx: Receiver<PersistenceAction>,
+        _from_tree_rx: UnboundedReceiver<EngineApiEvent>,
+        /// All blocks known to the test, starting with genesis.
+        blocks: Vec<FuzzBlock>,
+        /// Index into `blocks` by hash.
+        by_hash: B256Map<usize>,
+        /// The persistence task the tree is waiting on.
+        pending_persistence: Option<PersistenceAction>,
+    }
+
+    impl FuzzHarness {
+        fn new(specs: &[BlockSpec]) -> Self {
+            let mut builder = TestBlockBuilder::eth().with_chain_spec((*MAINNET).clone());
+            let genesis = payload_v1_block(&mut builder, 0, B256::ZERO);
+            let mut blocks =
+                vec![FuzzBlock { executed: genesis, fails_execution: false, invalid: false }];
+            for spec in specs {
+                let parent = &blocks[spec.parent.index(blocks.len())];
+                let (parent, parent_invalid) = (parent.num_hash(), parent.invalid);
+                let executed = payload_v1_block(&mut builder, parent.number + 1, parent.hash);
+                blocks.push(FuzzBlock {
+                    executed,
+                    fails_execution: spec.invalid,
+                    invalid: spec.invalid || parent_invalid,
+                });
+            }
+            let by_hash =
+                blocks.iter().enumerate().map(|(idx, b)| (b.num_hash().hash, idx)).collect();
+
+            // the tree starts with only genesis, which is persisted
+            let factory = create_test_provider_factory_with_chain_spec(MAINNET.clone());
+            let provider_rw = factory.database_provider_rw().unwrap();
+            provider_rw
+                .save_blocks(vec![blocks[0].executed.clone()], SaveBlocksMode::Full)
+                .unwrap();
+            provider_rw.commit().unwrap();
+            let provider = BlockchainProvider::new(factory.clone()).unwrap();
+
+            let validator = FuzzValidator {
+                executed: blocks
+                    .iter()
+                    .filter(|b| !b.invalid)
+                    .map(|b| (b.num_hash().hash, b.executed.clone()))
+                    .collect(),
+                fails_execution: blocks
+                    .iter()
+                    .filter(|b| b.fails_execution)
+                    .map(|b| b.num_hash().hash)
+                    .collect(),
+            };
+
+            let genesis = blocks[0].num_hash();
+            let config = TreeConfig::default()
+                .with_legacy_state_root(false)
+                .with_has_enough_parallelism(true)
+                .with_persistence_threshold(1)
+                .with_memory_block_buffer_target(1);
+            let state = EngineApiTreeState::new(
+                10,
+                10,
+                config.invalid_header_hit_eviction_threshold(),
+                genesis,
+                EngineApiKind::Ethereum,
+            );
+            let (action_tx, action_rx) = channel();
+            let (from_tree_tx, from_tree_rx) = unbounded_channel();
+            let (to_payload_service, _payload_command_rx) = unbounded_channel();
+
+            let tree = EngineApiTreeHandler::new(
+                provider.clone(),
+                Arc::new(EthBeaconConsensus::new(MAINNET.clone())),
+                validator,
+                from_tree_tx,
+                state,
+                provider.canonical_in_memory_state(),
+                PersistenceHandle::new(action_tx),
+                PersistenceState { last_persisted_block: genesis, rx: None },
+                PayloadBuilderHandle::new(to_payload_service),
+                config,
+                EngineApiKind::Ethereum,
+                MockEvmConfig::default(),
+                ChangesetCache::new(),
+                reth_tasks::Runtime::test(),
+            );
+
+            Self {
+                tree,
+                factory,
+                action_rx,
+                _from_tree_rx: from_tree_rx,
+                blocks,
+                by_hash,
+                pending_persistence: None,
+            }
+        }
+
+        fn block(&self, hash: &B256) -> Option<&FuzzBlock> {
+            self.by_hash.get(hash).map(|idx| &self.blocks[*idx])
+        }
+
+        /// Returns the hash of the persisted block at the given height.
+        fn persisted_hash(&self, number: u64) -> Option<B256> {
+            self.factory.block_hash(number).unwrap()
+        }
+
+        fn apply(&mut self, op: Op) {
+            match op {
+                Op::NewPayload(index) => {
+                    // genesis is already persisted
+                    let generated = self.blocks.len() - 1;
+                    if generated > 0 {
+                        self.new_payload(1 + index.index(generated));
+                    }
+                }
+                Op::ForkchoiceUpdated(index) => {
+                    self.forkchoice_updated(index.index(self.blocks.len()));
+                }
+                Op::AdvancePersistence => {
+                    self.tree.advance_persistence().unwrap();
+                    self.receive_persistence_action();
+                }
+                Op::CompletePersistence => self.complete_persistence(),
+            }
+        }
+
+        fn new_payload(&mut self, idx: usize) {
+            let block = &self.blocks[idx];
+            let num_hash = block.num_hash();
+            let invalid = block.invalid;
+
+            let payload = ExecutionPayloadV1::from_block_unchecked(
+                num_hash.hash,
+                &block.sealed_block().clone().into_block(),
+            );
+            let status = self
+                .tree
+                .on_new_payload(ExecutionData {
+                    payload: payload.into(),
+                    sidecar: ExecutionPayloadSidecar::none(),
+                })
+                .unwrap()
+                .outcome;
+
+            match status.status {
+                PayloadStatusEnum::Valid => {
+                    assert!(!invalid, "invalid block {num_hash:?} reported as valid")
+                }
+                PayloadStatusEnum::Invalid { .. } => {
+                    assert!(invalid, "valid block {num_hash:?} reported as invalid")
+                }
+                PayloadStatusEnum::Syncing | PayloadStatusEnum::Accepted => {}
+            }
+        }
+
+        fn forkchoice_updated(&mut self, idx: usize) {
+            let block = &self.blocks[idx];
+            let head = block.num_hash();
+            let invalid = block.invalid;
+
+            let state = ForkchoiceState {
+                head_block_hash: head.hash,
+                safe_block_hash: B256::ZERO,
+                finalized_block_hash: B256::ZERO,
+            };
+            let outcome = self.tree.on_forkchoice_updated(state, None).unwrap();
+
+            match outcome.outcome.forkchoice_status() {
+                ForkchoiceStatus::Valid => {
+                    assert!(!invalid, "invalid head {head:?} accepted");
+                    assert!(
+                        self.canonical_chain().contains(&head.hash),
+                        "valid forkchoice update to {head:?} is not canonical"
+                    );
+                }
+                ForkchoiceStatus::Invalid => {
+                    assert!(invalid, "valid head {head:?} rejected")
+                }
+                ForkchoiceStatus::Syncing => {}
+            }
+        }
+
+        fn receive_persistence_action(&mut self) {
+            while let Ok(action) = self.action_rx.try_recv() {
+                match action {
+                    PersistenceAction::SaveBlocks(..) |
+                    PersistenceAction::RemoveBlocksAbove(..) => {
+                        assert!(
+                            self.pending_persistence.is_none(),
+                            "persistence task started while another one is in flight"
+                        );
+                        self.pending_persistence = Some(action);
+                    }
+                    PersistenceAction::SaveFinalizedBlock(_) |
+                    PersistenceAction::SaveSafeBlock(_) => {}
+                }
+            }
+        }
+
+        /// Plays the persistence service for the in-flight task against the database.
+        fn complete_persistence(&mut self) {
+            let Some(action) = self.pending_persistence.take() else { return };
+            let provider_rw = self.factory.database_provider_rw().unwrap();
+
+            match action {
+                PersistenceAction::SaveBlocks(blocks, tx) => {
+                    // divergent persisted blocks are always removed before new ones are saved
+                    let tip = provider_rw.last_block_number().unwrap();
+                    let tip = BlockNumHash::new(tip, provider_rw.block_hash(tip).unwrap().unwrap());
+                    assert_eq!(
+                        blocks[0].recovered_block().parent_num_hash(),
+                        tip,
+                        "saved blocks don't extend the persisted chain"
+                    );
+
+                    let last_block = blocks.last().map(|block| block.recovered_block().num_hash());
+                    provider_rw.save_blocks(blocks, SaveBlocksMode::Full).unwrap();
+                    provider_rw.commit().unwrap();
+                    tx.send(PersistenceResult {
+                        last_block,
+                        commit_duration: Some(Duration::ZERO),
+                    })
+                    .unwrap();
+                }
+                PersistenceAction::RemoveBlocksAbove(new_tip, tx) => {
+                    let new_tip_hash = provider_rw.block_hash(new_tip).unwrap();
+                    provider_rw.remove_block_and_execution_above(new_tip).unwrap();
+                    provider_rw.commit().unwrap();
+
+                    let last_block = new_tip_hash.map(|hash| BlockNumHash::new(new_tip, hash));
+                    tx.send(PersistenceResult { last_block, commit_duration: None }).unwrap();
+                }
+                PersistenceAction::SaveFinalizedBlock(_) | PersistenceAction::SaveSafeBlock(_) => {
+                    unreachable!("only block persistence tasks are tracked")
+                }
+            }
+
+            assert!(self.tree.try_poll_persistence().unwrap());
+            self.receive_persistence_action();
+        }
+
+        /// Returns the hashes of the canonical chain by block number, derived from the tree's head.
+        fn canonical_chain(&self) -> Vec<B256> {
+            let head = self.tree.state.tree_state.current_canonical_head;
+            let mut chain = Vec::with_capacity(head.number as usize + 1);
+            let mut hash = head.hash;
+            while let Some(block) = self.block(&hash) {
+                chain.push(hash);
+                if block.num_hash().number == 0 {
+                    break
+                }
+                hash = block.parent_hash();
+            }
+            chain.reverse();
+            assert_eq!(chain.len() as u64, head.number + 1, "canonical chain is not connected");
+            chain
+        }
+
+        fn check_invariants(&self) {
+            let tree = &self.tree;
+            let head = tree.state.tree_state.current_canonical_head;
+            let persisted = tree.persistence_state.last_persisted_block;
+
+            // canonical head consistency
+            assert_eq!(
+                tree.canonical_in_memory_state.get_canonical_head().num_hash(),
+                head,
+                "tree state and in-memory state disagree on the canonical head"
+            );
+            assert!(
+                tree.state.tree_state.contains_hash(&head.hash) ||
+                    self.persisted_hash(head.number) == Some(head.hash),
+                "canonical head {head:?} is neither executed nor persisted"
+            );
+
+            // only valid blocks are ever executed
+            for hash in tree.state.tree_state.blocks_by_hash.keys() {
+                let block = self.block(hash).expect("unknown block in tree state");
+                assert!(!block.invalid, "invalid block {hash} in tree state");
+            }
+
+            // block buffer bounds
+            let buffer = &tree.state.buffer;
+            assert!(buffer.blocks.len() <= buffer.max_blocks, "block buffer exceeds its limit");
+            assert_eq!(buffer.block_queue.len(), buffer.blocks.len(), "eviction queue out of sync");
+            for hash in buffer.blocks.keys() {
+                assert!(
+                    !tree.state.tree_state.contains_hash(hash),
+                    "block {hash} is both buffered and executed"
+                );
+            }
+
+            // invalid header cache correctness
+            for (hash, ancestor) in tree.state.invalid_headers.iter() {
+                let block = self.block(hash).expect("unknown block in invalid header cache");
+                assert!(block.invalid, "valid block {hash} cached as invalid");
+                let ancestor_block =
+                    self.block(&ancestor.block.hash).expect("unknown invalid ancestor");
+                assert!(
+                    ancestor_block.fails_execution,
+                    "block {hash} cached with valid ancestor {:?}",
+                    ancestor.block
+                );
+                let mut current = block;
+                while current.num_hash() != ancestor.block {
+                    current = self.block(&current.parent_hash()).unwrap_or_else(|| {
+                        panic!("{:?} is not an ancestor of {hash}", ancestor.block)
+                    });
+                }
+            }
+
+            // reads of the canonical chain, from memory above the persisted tip and from the
+            // database below it
+            let chain = self.canonical_chain();
+            for number in persisted.number + 1..=head.number {
+                assert_eq!(
+                    tree.canonical_in_memory_state.hash_by_number(number),
+                    Some(chain[number as usize]),
+                    "in-memory canonical block {number} doesn't match the canonical chain"
+                );
+            }
+            // the database only matches the canonical chain once a pending unwind is done
+            if !tree.persistence_state.in_progress() && tree.find_disk_reorg().unwrap().is_none() {
+                assert_eq!(
+                    self.factory.last_block_number().unwrap(),
+                    persisted.number,
+                    "database tip doesn't match the persisted block"
+                );
+                for (number, hash) in chain.iter().enumerate().take(persisted.number as usize + 1) {
+                    assert_eq!(
+                        self.persisted_hash(number as u64),
+                        Some(*hash),
+                        "persisted block {number} doesn't match the canonical chain"
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_sequence(specs: &[BlockSpec], ops: &[Op]) {
+        let mut harness = FuzzHarness::new(specs);
+        harness.check_invariants();
+        for op in ops {
+            harness.apply(*op);
+            harness.check_invariants();
+        }
+
+        // settle persistence and check the database caught up with the canonical chain
+        loop {
+            harness.apply(Op::AdvancePersistence);
+            if harness.pending_persistence.is_none() {
+                break
+            }
+            harness.apply(Op::CompletePersistence);
+            harness.check_invariants();
+        }
+        assert_eq!(harness.tree.find_disk_reorg().unwrap(), None, "database left on a stale fork");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig {
+            rng_seed: RngSeed::Fixed(RNG_SEED),
+            ..ProptestConfig::with_cases(64)
+        })]
+
+        #[test]
+        fn fuzz_engine_tree_forks_and_reorgs(
+            specs in prop::collection::vec(block_spec_strategy(), 1..24),
+            ops in prop::collection::vec(op_strategy(), 1..64),
+        ) {
+            check_sequence(&specs, &ops);
+        }
+    }
+}
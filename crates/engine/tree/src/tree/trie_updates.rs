@@ -8,7 +8,8 @@ use reth_trie::{
     updates::{StorageTrieUpdates, TrieUpdates},
     BranchNodeCompact, Nibbles,
 };
-use std::collections::BTreeSet;
+use serde_json::{json, Map, Value};
+use std::{collections::BTreeSet, fmt::Debug};
 use tracing::warn;
 
 #[derive(Debug)]
@@ -18,20 +19,56 @@ struct EntryDiff<T> {
     database: T,
 }
 
+impl<T: Debug> EntryDiff<T> {
+    fn to_json(&self) -> Value {
+        json!({
+            "task": format!("{:?}", self.task),
+            "regular": format!("{:?}", self.regular),
+            "database": format!("{:?}", self.database),
+        })
+    }
+}
+
+/// Node by node differences between the trie updates of the state root task and another state
+/// root computation.
 #[derive(Debug, Default)]
-struct TrieUpdatesDiff {
+pub(crate) struct TrieUpdatesDiff {
     account_nodes: HashMap<Nibbles, EntryDiff<Option<BranchNodeCompact>>>,
     removed_nodes: HashMap<Nibbles, EntryDiff<bool>>,
     storage_tries: B256Map<StorageTrieUpdatesDiff>,
 }
 
 impl TrieUpdatesDiff {
-    fn has_differences(&self) -> bool {
+    /// Returns `true` if there are any differences.
+    pub(crate) fn has_differences(&self) -> bool {
         !self.account_nodes.is_empty() ||
             !self.removed_nodes.is_empty() ||
             !self.storage_tries.is_empty()
     }
 
+    /// Returns the number of differing account and storage trie nodes.
+    pub(crate) fn num_differences(&self) -> usize {
+        self.account_nodes.len() +
+            self.removed_nodes.len() +
+            self.storage_tries
+                .values()
+                .map(StorageTrieUpdatesDiff::num_differences)
+                .sum::<usize>()
+    }
+
+    /// Returns the differences as JSON, keyed by node path.
+    pub(crate) fn to_json(&self) -> Value {
+        json!({
+            "account_nodes": nodes_json(&self.account_nodes),
+            "removed_nodes": nodes_json(&self.removed_nodes),
+            "storage_tries": self
+                .storage_tries
+                .iter()
+                .map(|(address, diff)| (address.to_string(), diff.to_json()))
+                .collect::<Map<_, _>>(),
+        })
+    }
+
     pub(super) fn log_differences(mut self) {
         if self.has_differences() {
             for (path, EntryDiff { task, regular, database }) in &mut self.account_nodes {
@@ -71,6 +108,18 @@ impl StorageTrieUpdatesDiff {
             !self.removed_nodes.is_empty()
     }
 
+    fn num_differences(&self) -> usize {
+        usize::from(self.is_deleted.is_some()) + self.storage_nodes.len() + self.removed_nodes.len()
+    }
+
+    fn to_json(&self) -> Value {
+        json!({
+            "is_deleted": self.is_deleted.as_ref().map(EntryDiff::to_json),
+            "storage_nodes": nodes_json(&self.storage_nodes),
+            "removed_nodes": nodes_json(&self.removed_nodes),
+        })
+    }
+
     fn log_differences(&self, address: B256) {
         if let Some(EntryDiff {
             task: task_deleted,
@@ -99,6 +148,10 @@ impl StorageTrieUpdatesDiff {
     }
 }
 
+fn nodes_json<T: Debug>(nodes: &HashMap<Nibbles, EntryDiff<T>>) -> Map<String, Value> {
+    nodes.iter().map(|(path, diff)| (format!("{path:?}"), diff.to_json())).collect()
+}
+
 /// Compares the trie updates from state root task, regular state root calculation and database,
 /// and logs the differences if there's any.
 ///
@@ -108,6 +161,22 @@ pub(crate) fn compare_trie_updates(
     task: TrieUpdates,
     regular: TrieUpdates,
 ) -> Result<bool, DatabaseError> {
+    let diff = diff_trie_updates(trie_cursor_factory, task, regular)?;
+
+    // log differences
+    let has_differences = diff.has_differences();
+    diff.log_differences();
+
+    Ok(has_differences)
+}
+
+/// Compares the trie updates from state root task, another state root calculation and database
+/// node by node.
+pub(crate) fn diff_trie_updates(
+    trie_cursor_factory: impl TrieCursorFactory,
+    task: TrieUpdates,
+    regular: TrieUpdates,
+) -> Result<TrieUpdatesDiff, DatabaseError> {
     let mut task = adjust_trie_updates(task);
     let mut regular = adjust_trie_updates(regular);
 
@@ -180,11 +249,7 @@ pub(crate) fn compare_trie_updates(
         }
     }
 
-    // log differences
-    let has_differences = diff.has_differences();
-    diff.log_differences();
-
-    Ok(has_differences)
+    Ok(diff)
 }
 
 fn compare_storage_trie_updates<C: TrieCursor>(
@@ -1,20 +1,26 @@
 //! Stores engine API messages to disk for later inspection and replay.
 
-use alloy_rpc_types_engine::ForkchoiceState;
-use futures::{Stream, StreamExt};
-use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload};
+use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus};
+use futures::{Future, Stream, StreamExt};
+use reth_engine_primitives::{BeaconEngineMessage, ExecutionPayload, ForkchoiceStatus};
 use reth_fs_util as fs;
 use reth_payload_primitives::PayloadTypes;
 use serde::{Deserialize, Serialize};
 use std::{
     collections::BTreeMap,
-    path::PathBuf,
+    mem,
+    path::{Path, PathBuf},
     pin::Pin,
     task::{ready, Context, Poll},
     time::SystemTime,
 };
+use tokio::sync::oneshot;
 use tracing::*;
 
+/// The extension of the files that store the engine responses to stored messages, replacing the
+/// `json` extension of the message file.
+const RESPONSE_FILE_EXTENSION: &str = "response.json";
+
 /// A message from the engine API that has been stored to disk.
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -34,8 +40,50 @@ pub enum StoredEngineApiMessage<T: PayloadTypes> {
     },
 }
 
+/// The response of the engine to a [`StoredEngineApiMessage`] that has been stored to disk.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StoredEngineApiResponse {
+    /// The status the engine assigned to the forkchoice state of an `engine_forkchoiceUpdated`
+    /// call.
+    ForkchoiceUpdated {
+        /// The [`ForkchoiceStatus`] of the forkchoice state.
+        status: ForkchoiceStatus,
+    },
+    /// The [`PayloadStatus`] returned for an `engine_newPayload` call.
+    NewPayload {
+        /// The returned [`PayloadStatus`].
+        status: PayloadStatus,
+    },
+    /// The engine failed to process the message.
+    Error {
+        /// The error message.
+        message: String,
+    },
+}
+
+impl StoredEngineApiResponse {
+    /// Returns `true` if both responses have the same outcome.
+    ///
+    /// Payload statuses are compared by kind and latest valid hash only, validation and error
+    /// messages are allowed to differ between node versions.
+    pub fn matches(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::ForkchoiceUpdated { status }, Self::ForkchoiceUpdated { status: other }) => {
+                status == other
+            }
+            (Self::NewPayload { status }, Self::NewPayload { status: other }) => {
+                mem::discriminant(&status.status) == mem::discriminant(&other.status) &&
+                    status.latest_valid_hash == other.latest_valid_hash
+            }
+            (Self::Error { .. }, Self::Error { .. }) => true,
+            _ => false,
+        }
+    }
+}
+
 /// This can read and write engine API messages in a specific directory.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct EngineMessageStore {
     /// The path to the directory that stores the engine API messages.
     path: PathBuf,
@@ -51,48 +99,92 @@ impl EngineMessageStore {
 
     /// Stores the received [`BeaconEngineMessage`] to disk, appending the `received_at` time to the
     /// path.
+    ///
+    /// Returns the path of the message file, if the message was stored.
     pub fn on_message<T>(
         &self,
         msg: &BeaconEngineMessage<T>,
         received_at: SystemTime,
-    ) -> eyre::Result<()>
+    ) -> eyre::Result<Option<PathBuf>>
     where
         T: PayloadTypes,
     {
         fs::create_dir_all(&self.path)?; // ensure that store path had been created
         let timestamp = received_at.duration_since(SystemTime::UNIX_EPOCH).unwrap().as_millis();
-        match msg {
+        let path = match msg {
             BeaconEngineMessage::ForkchoiceUpdated { state, payload_attrs, tx: _tx } => {
-                let filename = format!("{}-fcu-{}.json", timestamp, state.head_block_hash);
+                let path =
+                    self.path.join(format!("{}-fcu-{}.json", timestamp, state.head_block_hash));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::ForkchoiceUpdated {
                         state: *state,
                         payload_attrs: payload_attrs.clone(),
                     })?,
                 )?;
+                path
             }
             BeaconEngineMessage::NewPayload { payload, .. } |
             BeaconEngineMessage::RethNewPayload { payload, .. } => {
-                let filename = format!("{}-new_payload-{}.json", timestamp, payload.block_hash());
+                let path = self.path.join(format!(
+                    "{}-new_payload-{}.json",
+                    timestamp,
+                    payload.block_hash()
+                ));
                 fs::write(
-                    self.path.join(filename),
+                    &path,
                     serde_json::to_vec(&StoredEngineApiMessage::<T>::NewPayload {
                         payload: payload.clone(),
                     })?,
                 )?;
+                path
             }
-            _ => {}
+            _ => return Ok(None),
         };
+        Ok(Some(path))
+    }
+
+    /// Stores the engine response to the message stored at `message_path` next to it.
+    pub fn on_response(
+        &self,
+        message_path: &Path,
+        response: &StoredEngineApiResponse,
+    ) -> eyre::Result<()> {
+        fs::write(Self::response_path(message_path), serde_json::to_vec(response)?)?;
         Ok(())
     }
 
+    /// Reads the engine API message stored at the given path.
+    pub fn message<T>(&self, message_path: &Path) -> eyre::Result<StoredEngineApiMessage<T>>
+    where
+        T: PayloadTypes,
+    {
+        Ok(serde_json::from_slice(&fs::read(message_path)?)?)
+    }
+
+    /// Reads the engine response recorded for the message stored at `message_path`, if any.
+    pub fn response(&self, message_path: &Path) -> eyre::Result<Option<StoredEngineApiResponse>> {
+        let path = Self::response_path(message_path);
+        if !path.exists() {
+            return Ok(None)
+        }
+        Ok(Some(serde_json::from_slice(&fs::read(path)?)?))
+    }
+
+    /// Returns the path of the file that stores the response to the message at `message_path`.
+    fn response_path(message_path: &Path) -> PathBuf {
+        message_path.with_extension(RESPONSE_FILE_EXTENSION)
+    }
+
     /// Finds and iterates through any stored engine API message files, ordered by timestamp.
     pub fn engine_messages_iter(&self) -> eyre::Result<impl Iterator<Item = PathBuf>> {
         let mut filenames_by_ts = BTreeMap::<u64, Vec<PathBuf>>::default();
         for entry in fs::read_dir(&self.path)? {
             let entry = entry?;
             let filename = entry.file_name();
+            if filename.to_str().is_some_and(|n| n.ends_with(RESPONSE_FILE_EXTENSION)) {
+                continue
+            }
             if let Some(filename) = filename.to_str().filter(|n| n.ends_with(".json")) {
                 if let Some(Ok(timestamp)) = filename.split('-').next().map(|n| n.parse::<u64>()) {
                     filenames_by_ts.entry(timestamp).or_default().push(entry.path());
@@ -108,8 +200,17 @@ impl EngineMessageStore {
     }
 }
 
+/// A future that resolves once the engine responded to a stored message, after forwarding the
+/// response to the original sender.
+type PendingResponse =
+    Pin<Box<dyn Future<Output = Option<(PathBuf, StoredEngineApiResponse)>> + Send>>;
+
 /// A wrapper stream that stores Engine API messages in
 /// the specified directory.
+///
+/// The responses of the engine to the stored messages are written next to them, see
+/// [`EngineMessageStore::on_response`]. Responses are forwarded to the original senders by a
+/// spawned task, so they don't depend on this stream being polled again.
 #[derive(Debug)]
 #[pin_project::pin_project]
 pub struct EngineStoreStream<S> {
@@ -127,6 +228,60 @@ impl<S> EngineStoreStream<S> {
     }
 }
 
+/// Replaces the response channel of the message with one that records the engine response before
+/// forwarding it to the original sender.
+fn intercept_response<T: PayloadTypes>(
+    msg: &mut BeaconEngineMessage<T>,
+    path: PathBuf,
+) -> Option<PendingResponse> {
+    match msg {
+        BeaconEngineMessage::NewPayload { tx, .. } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let tx = mem::replace(tx, response_tx);
+            Some(Box::pin(async move {
+                let result = response_rx.await.ok()?;
+                let response = match &result {
+                    Ok(status) => StoredEngineApiResponse::NewPayload { status: status.clone() },
+                    Err(error) => StoredEngineApiResponse::Error { message: error.to_string() },
+                };
+                let _ = tx.send(result);
+                Some((path, response))
+            }))
+        }
+        BeaconEngineMessage::RethNewPayload { tx, .. } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let tx = mem::replace(tx, response_tx);
+            Some(Box::pin(async move {
+                let result = response_rx.await.ok()?;
+                let response = match &result {
+                    Ok((status, _)) => {
+                        StoredEngineApiResponse::NewPayload { status: status.clone() }
+                    }
+                    Err(error) => StoredEngineApiResponse::Error { message: error.to_string() },
+                };
+                let _ = tx.send(result);
+                Some((path, response))
+            }))
+        }
+        BeaconEngineMessage::ForkchoiceUpdated { tx, .. } => {
+            let (response_tx, response_rx) = oneshot::channel();
+            let tx = mem::replace(tx, response_tx);
+            Some(Box::pin(async move {
+                let result = response_rx.await.ok()?;
+                let response = match &result {
+                    Ok(on_updated) => StoredEngineApiResponse::ForkchoiceUpdated {
+                        status: on_updated.forkchoice_status(),
+                    },
+                    Err(error) => StoredEngineApiResponse::Error { message: error.to_string() },
+                };
+                let _ = tx.send(result);
+                Some((path, response))
+            }))
+        }
+        _ => None,
+    }
+}
+
 impl<S, T> Stream for EngineStoreStream<S>
 where
     S: Stream<Item = BeaconEngineMessage<T>>,
@@ -136,12 +291,76 @@ where
 
     fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         let mut this = self.project();
-        let next = ready!(this.stream.poll_next_unpin(cx));
-        if let Some(msg) = &next &&
-            let Err(error) = this.store.on_message(msg, SystemTime::now())
-        {
-            error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+        let mut next = ready!(this.stream.poll_next_unpin(cx));
+        if let Some(msg) = &mut next {
+            match this.store.on_message(msg, SystemTime::now()) {
+                Ok(Some(path)) => {
+                    if let Some(response) = intercept_response(msg, path) {
+                        let store = this.store.clone();
+                        tokio::spawn(async move {
+                            let Some((path, response)) = response.await else { return };
+                            if let Err(error) = store.on_response(&path, &response) {
+                                error!(target: "engine::stream::store", ?path, %error, "Error storing Engine API response");
+                            }
+                        });
+                    }
+                }
+                Ok(None) => {}
+                Err(error) => {
+                    error!(target: "engine::stream::store", ?msg, %error, "Error handling Engine API message");
+                }
+            }
         }
         Poll::Ready(next)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reth_engine_primitives::OnForkChoiceUpdated;
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn stores_response_without_polling_stream() {
+        let dir = tempfile::tempdir().unwrap();
+        let (tx, rx) = oneshot::channel();
+        let msg = BeaconEngineMessage::<EthEngineTypes>::ForkchoiceUpdated {
+            state: ForkchoiceState::default(),
+            payload_attrs: None,
+            tx,
+        };
+        let mut stream =
+            EngineStoreStream::new(futures::stream::iter([msg]), dir.path().to_path_buf());
+
+        let Some(BeaconEngineMessage::ForkchoiceUpdated { tx, .. }) = stream.next().await else {
+            panic!("expected forkchoice update")
+        };
+        let _ = tx.send(Ok(OnForkChoiceUpdated::syncing()));
+
+        // the response reaches the original sender while the stream is idle
+        let response = rx.await.unwrap().unwrap();
+        assert_eq!(response.forkchoice_status(), ForkchoiceStatus::Syncing);
+
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+        let path = store.engine_messages_iter().unwrap().next().unwrap();
+        assert!(matches!(
+            store.message::<EthEngineTypes>(&path).unwrap(),
+            StoredEngineApiMessage::ForkchoiceUpdated { payload_attrs: None, .. }
+        ));
+
+        let mut stored = None;
+        for _ in 0..100 {
+            stored = store.response(&path).unwrap();
+            if stored.is_some() {
+                break
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(
+            stored,
+            Some(StoredEngineApiResponse::ForkchoiceUpdated { status: ForkchoiceStatus::Syncing })
+        );
+    }
+}
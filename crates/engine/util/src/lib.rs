@@ -26,6 +26,8 @@ use skip_new_payload::EngineSkipNewPayload;
 pub mod reorg;
 use reorg::EngineReorg;
 
+pub mod replay;
+
 /// The result type for `maybe_reorg` method.
 type MaybeReorgResult<S, T, Provider, Evm, Validator, E> =
     Result<Either<EngineReorg<S, T, Provider, Evm, Validator>, S>, E>;
@@ -0,0 +1,208 @@
+//! Replays engine API messages stored by an [`EngineMessageStore`] against a consensus engine.
+
+use crate::engine_store::{EngineMessageStore, StoredEngineApiMessage, StoredEngineApiResponse};
+use reth_engine_primitives::{ConsensusEngineHandle, ForkchoiceStatus};
+use reth_payload_primitives::PayloadTypes;
+use std::{
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use tracing::*;
+
+/// Sends the engine API messages of an [`EngineMessageStore`] directory to a consensus engine, in
+/// the order they were received, and compares the responses of the engine with the recorded ones.
+///
+/// This is meant to be run against a fresh node, to reproduce the engine API traffic a node
+/// received while it was started with `--debug.engine-api-store`.
+#[derive(Debug)]
+pub struct EngineApiReplay<T: PayloadTypes> {
+    /// The store to read the messages from.
+    store: EngineMessageStore,
+    /// Handle to the consensus engine to send the messages to.
+    engine: ConsensusEngineHandle<T>,
+}
+
+impl<T: PayloadTypes> EngineApiReplay<T> {
+    /// Creates a new [`EngineApiReplay`] of the messages stored in the given directory.
+    pub const fn new(path: PathBuf, engine: ConsensusEngineHandle<T>) -> Self {
+        Self { store: EngineMessageStore::new(path), engine }
+    }
+
+    /// Replays all stored messages, waiting for the engine to respond to each message before
+    /// sending the next one.
+    ///
+    /// Returns once the engine responded to the last stored message.
+    pub async fn run(self) -> eyre::Result<EngineApiReplayReport> {
+        let mut report = EngineApiReplayReport::default();
+        for path in self.store.engine_messages_iter()? {
+            let message = self.store.message::<T>(&path)?;
+            let recorded = self.store.response(&path)?;
+
+            let start = Instant::now();
+            let response = self.send(message).await;
+            let latency = start.elapsed();
+
+            let diverged = recorded.as_ref().is_some_and(|recorded| !recorded.matches(&response));
+            info!(target: "engine::replay", ?path, ?latency, ?response, diverged, "Replayed engine API message");
+            if diverged {
+                let recorded = recorded.expect("diverged responses are recorded");
+                warn!(target: "engine::replay", ?path, ?recorded, replayed = ?response, "Engine response diverged from the recorded one");
+                report.divergences.push(EngineApiReplayDivergence {
+                    path: path.clone(),
+                    recorded,
+                    replayed: response.clone(),
+                });
+            }
+
+            report.messages.push(ReplayedEngineApiMessage { path, latency, response });
+        }
+        Ok(report)
+    }
+
+    /// Sends the message to the engine and waits for the response.
+    async fn send(&self, message: StoredEngineApiMessage<T>) -> StoredEngineApiResponse {
+        match message {
+            StoredEngineApiMessage::ForkchoiceUpdated { state, payload_attrs } => {
+                match self.engine.fork_choice_updated(state, payload_attrs).await {
+                    Ok(updated) => StoredEngineApiResponse::ForkchoiceUpdated {
+                        status: ForkchoiceStatus::from(updated.payload_status.status),
+                    },
+                    Err(error) => StoredEngineApiResponse::Error { message: error.to_string() },
+                }
+            }
+            StoredEngineApiMessage::NewPayload { payload } => {
+                match self.engine.new_payload(payload).await {
+                    Ok(status) => StoredEngineApiResponse::NewPayload { status },
+                    Err(error) => StoredEngineApiResponse::Error { message: error.to_string() },
+                }
+            }
+        }
+    }
+}
+
+/// The outcome of an [`EngineApiReplay`].
+#[derive(Debug, Clone, Default)]
+pub struct EngineApiReplayReport {
+    /// The replayed messages, in the order they were sent.
+    pub messages: Vec<ReplayedEngineApiMessage>,
+    /// The messages the engine responded to differently than recorded, in the order they were
+    /// sent.
+    pub divergences: Vec<EngineApiReplayDivergence>,
+}
+
+impl EngineApiReplayReport {
+    /// Returns `true` if the engine responded to every replayed message as recorded.
+    pub const fn is_consistent(&self) -> bool {
+        self.divergences.is_empty()
+    }
+
+    /// Returns the total time the engine took to respond to the replayed messages.
+    pub fn total_latency(&self) -> Duration {
+        self.messages.iter().map(|message| message.latency).sum()
+    }
+
+    /// Returns the longest time the engine took to respond to a replayed message.
+    pub fn max_latency(&self) -> Duration {
+        self.messages.iter().map(|message| message.latency).max().unwrap_or_default()
+    }
+}
+
+/// A message sent to the engine by an [`EngineApiReplay`].
+#[derive(Debug, Clone)]
+pub struct ReplayedEngineApiMessage {
+    /// The path of the stored message.
+    pub path: PathBuf,
+    /// The time the engine took to respond.
+    pub latency: Duration,
+    /// The response of the engine.
+    pub response: StoredEngineApiResponse,
+}
+
+/// A replayed message the engine responded to differently than recorded.
+#[derive(Debug, Clone)]
+pub struct EngineApiReplayDivergence {
+    /// The path of the stored message.
+    pub path: PathBuf,
+    /// The response recorded when the message was stored.
+    pub recorded: StoredEngineApiResponse,
+    /// The response of the engine during the replay.
+    pub replayed: StoredEngineApiResponse,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_rpc_types_engine::{ForkchoiceState, PayloadStatus, PayloadStatusEnum};
+    use reth_engine_primitives::{BeaconEngineMessage, OnForkChoiceUpdated};
+    use reth_ethereum_engine_primitives::EthEngineTypes;
+    use std::time::SystemTime;
+    use tokio::sync::{mpsc, oneshot};
+
+    /// Stores a forkchoice update to `store`, along with the recorded response if any.
+    fn store_fcu(
+        store: &EngineMessageStore,
+        received_at: u64,
+        recorded: Option<ForkchoiceStatus>,
+    ) -> PathBuf {
+        let (tx, _rx) = oneshot::channel();
+        let msg = BeaconEngineMessage::<EthEngineTypes>::ForkchoiceUpdated {
+            state: ForkchoiceState::default(),
+            payload_attrs: None,
+            tx,
+        };
+        let received_at = SystemTime::UNIX_EPOCH + Duration::from_millis(received_at);
+        let path = store.on_message(&msg, received_at).unwrap().unwrap();
+        if let Some(status) = recorded {
+            store
+                .on_response(&path, &StoredEngineApiResponse::ForkchoiceUpdated { status })
+                .unwrap();
+        }
+        path
+    }
+
+    #[tokio::test]
+    async fn replay_reports_diverging_responses() {
+        let dir = tempfile::tempdir().unwrap();
+        let store = EngineMessageStore::new(dir.path().to_path_buf());
+        store_fcu(&store, 1, Some(ForkchoiceStatus::Valid));
+        let diverging = store_fcu(&store, 2, Some(ForkchoiceStatus::Valid));
+        store_fcu(&store, 3, None);
+
+        // responds to every forkchoice update as valid, except for the second one
+        let (to_engine, mut from_replay) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let mut received = 0;
+            while let Some(msg) = from_replay.recv().await {
+                let BeaconEngineMessage::<EthEngineTypes>::ForkchoiceUpdated { tx, .. } = msg
+                else {
+                    panic!("unexpected engine message")
+                };
+                received += 1;
+                let status = if received == 2 {
+                    PayloadStatusEnum::Syncing
+                } else {
+                    PayloadStatusEnum::Valid
+                };
+                let _ = tx.send(Ok(OnForkChoiceUpdated::valid(PayloadStatus::from_status(status))));
+            }
+        });
+
+        let replay =
+            EngineApiReplay::new(dir.path().to_path_buf(), ConsensusEngineHandle::new(to_engine));
+        let report = replay.run().await.unwrap();
+
+        assert_eq!(report.messages.len(), 3);
+        assert!(!report.is_consistent());
+        assert_eq!(report.divergences.len(), 1);
+        let divergence = &report.divergences[0];
+        assert_eq!(divergence.path, diverging);
+        assert_eq!(
+            divergence.recorded,
+            StoredEngineApiResponse::ForkchoiceUpdated { status: ForkchoiceStatus::Valid }
+        );
+        assert_eq!(
+            divergence.replayed,
+            StoredEngineApiResponse::ForkchoiceUpdated { status: ForkchoiceStatus::Syncing }
+        );
+    }
+}
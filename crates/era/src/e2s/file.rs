@@ -39,6 +39,16 @@ impl<R: Read + Seek> E2StoreReader<R> {
         Entry::read(&mut self.reader)
     }
 
+    /// Seek to the given position in the file, discarding any buffered data
+    pub fn seek(&mut self, pos: SeekFrom) -> Result<u64, E2sError> {
+        Ok(self.reader.seek(pos)?)
+    }
+
+    /// Read exactly `buf.len()` bytes from the current position
+    pub fn read_exact(&mut self, buf: &mut [u8]) -> Result<(), E2sError> {
+        Ok(self.reader.read_exact(buf)?)
+    }
+
     /// Read all entries from the file, including the version entry
     pub fn entries(&mut self) -> Result<Vec<Entry>, E2sError> {
         // Reset reader to beginning
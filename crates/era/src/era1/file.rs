@@ -25,7 +25,9 @@ use alloy_primitives::BlockNumber;
 use std::{
     collections::VecDeque,
     fs::File,
-    io::{Read, Seek, Write},
+    io::{Read, Seek, SeekFrom, Write},
+    ops::RangeInclusive,
+    path::Path,
 };
 
 /// Era1 file interface
@@ -258,6 +260,88 @@ impl<R: Read + Seek> Era1Reader<R> {
 
 impl FileReader for Era1Reader<File> {}
 
+/// Random access reader for Era1 files that reads single blocks located through the
+/// [`BlockIndex`] at the end of the file, without reading the blocks before them.
+#[derive(Debug)]
+pub struct Era1BlockReader<R: Read> {
+    reader: E2StoreReader<R>,
+    block_index: BlockIndex,
+    /// Position of the block index entry, which the offsets of the block index are relative to
+    block_index_position: u64,
+}
+
+impl<R: Read + Seek> Era1BlockReader<R> {
+    /// Create a new [`Era1BlockReader`], reading the [`BlockIndex`] from the end of the file
+    pub fn new(reader: R) -> Result<Self, E2sError> {
+        let mut reader = E2StoreReader::new(reader);
+
+        // The block index is the last entry and ends with the number of blocks, from which the
+        // size of the entry follows: header | starting-number | offsets... | count
+        let file_len = reader.seek(SeekFrom::End(0))?;
+        if file_len < 8 {
+            return Err(E2sError::Ssz("Era1 file too short to contain a block index".to_string()));
+        }
+        reader.seek(SeekFrom::End(-8))?;
+        let mut count = [0; 8];
+        reader.read_exact(&mut count)?;
+        let count = i64::from_le_bytes(count);
+        if count <= 0 || count as usize > MAX_BLOCKS_PER_ERA1 {
+            return Err(E2sError::Ssz(format!("Invalid block count in block index: {count}")));
+        }
+
+        let block_index_len = 8 + 8 + count as u64 * 8 + 8;
+        let block_index_position = file_len.checked_sub(block_index_len).ok_or_else(|| {
+            E2sError::Ssz("Era1 file too short to contain its block index".to_string())
+        })?;
+        reader.seek(SeekFrom::Start(block_index_position))?;
+        let entry = reader
+            .read_next_entry()?
+            .ok_or_else(|| E2sError::Ssz("Era1 file missing block index entry".to_string()))?;
+        let block_index = BlockIndex::from_entry(&entry)?;
+
+        Ok(Self { reader, block_index, block_index_position })
+    }
+
+    /// Returns the [`BlockIndex`] of the file
+    pub const fn block_index(&self) -> &BlockIndex {
+        &self.block_index
+    }
+
+    /// Get the range of block numbers contained in this file
+    pub fn block_range(&self) -> RangeInclusive<BlockNumber> {
+        let start = self.block_index.starting_number();
+        start..=start + self.block_index.offsets().len() as u64 - 1
+    }
+
+    /// Read the block with the given number, or `None` if it isn't contained in this file
+    pub fn read_block(&mut self, number: BlockNumber) -> Result<Option<BlockTuple>, E2sError> {
+        let Some(offset) = self.block_index.offset_for_block(number) else { return Ok(None) };
+        let position = self.block_index_position.checked_add_signed(offset).ok_or_else(|| {
+            E2sError::Ssz(format!("Invalid offset {offset} for block {number} in block index"))
+        })?;
+        self.reader.seek(SeekFrom::Start(position))?;
+
+        let mut next_entry = |name: &str| {
+            self.reader.read_next_entry()?.ok_or_else(|| {
+                E2sError::Ssz(format!("Era1 file missing {name} entry for block {number}"))
+            })
+        };
+        let header = CompressedHeader::from_entry(&next_entry("header")?)?;
+        let body = CompressedBody::from_entry(&next_entry("body")?)?;
+        let receipts = CompressedReceipts::from_entry(&next_entry("receipts")?)?;
+        let total_difficulty = TotalDifficulty::from_entry(&next_entry("total difficulty")?)?;
+
+        Ok(Some(BlockTuple::new(header, body, receipts, total_difficulty)))
+    }
+}
+
+impl Era1BlockReader<File> {
+    /// Opens the Era1 file at the given path for random access
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, E2sError> {
+        Self::new(File::open(path)?)
+    }
+}
+
 /// Writer for Era1 files that builds on top of [`E2StoreWriter`]
 #[derive(Debug)]
 pub struct Era1Writer<W: Write> {
@@ -530,4 +614,53 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_era1_block_reader() -> Result<(), E2sError> {
+        let start_block = 3000;
+        let blocks: Vec<_> =
+            (0..4).map(|i| create_test_block(start_block + i, 16 + i as usize)).collect();
+
+        // Compute the offsets of the blocks relative to the block index, which follows the
+        // accumulator after the last block. Every entry has an 8 byte header.
+        let mut positions = Vec::with_capacity(blocks.len());
+        let mut position = 8;
+        for block in &blocks {
+            positions.push(position);
+            for len in
+                [block.header.data.len(), block.body.data.len(), block.receipts.data.len(), 32]
+            {
+                position += 8 + len as i64;
+            }
+        }
+        let block_index_position = position + 8 + 32;
+        let offsets = positions.iter().map(|position| position - block_index_position).collect();
+
+        let group = Era1Group::new(
+            blocks,
+            Accumulator::new(B256::from([0xAA; 32])),
+            BlockIndex::new(start_block, offsets),
+        );
+        let era1_file = Era1File::new(group, Era1Id::new("testnet", start_block, 4));
+        let mut buffer = Vec::new();
+        Era1Writer::new(&mut buffer).write_file(&era1_file)?;
+
+        let mut reader = Era1BlockReader::new(Cursor::new(&buffer))?;
+        assert_eq!(reader.block_range(), 3000..=3003);
+
+        // Blocks can be read in any order
+        for number in [3002, 3000, 3003, 3001] {
+            let block = reader.read_block(number)?.expect("block should be in the file");
+            let expected = era1_file.get_block_by_number(number).unwrap();
+            assert_eq!(block.header.data, expected.header.data);
+            assert_eq!(block.body.data, expected.body.data);
+            assert_eq!(block.receipts.data, expected.receipts.data);
+            assert_eq!(block.total_difficulty.value, expected.total_difficulty.value);
+        }
+
+        assert!(reader.read_block(2999)?.is_none());
+        assert!(reader.read_block(3004)?.is_none());
+
+        Ok(())
+    }
 }
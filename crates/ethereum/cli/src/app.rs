@@ -121,7 +121,7 @@ where
         }
 
         // Apply node-specific log defaults before initializing tracing
-        if matches!(self.cli.command, Commands::Node(_)) {
+        if matches!(self.cli.command, Commands::Node(_) | Commands::Debug(_)) {
             self.cli.logs.apply_node_defaults();
         }
 
@@ -206,6 +206,10 @@ where
         Commands::ReExecute(command) => {
             runner.run_until_ctrl_c(command.execute::<N>(components, rt))
         }
+        Commands::Debug(command) => runner.run_command_until_exit(|ctx| {
+            command.execute::<N, _>(ctx, FnLauncher::new::<C, Ext>(launcher), components)
+        }),
+        Commands::ExEx(command) => command.execute::<N>(),
         Commands::Ext(command) => command.execute(runner),
     }
 }
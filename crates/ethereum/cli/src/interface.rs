@@ -6,9 +6,9 @@ use reth_chainspec::{ChainSpec, Hardforks};
 use reth_cli::chainspec::ChainSpecParser;
 use reth_cli_commands::{
     common::{CliComponentsBuilder, CliNodeTypes, HeaderMut},
-    config_cmd, db, download,
+    config_cmd, db, debug_cmd, download,
     download::manifest_cmd,
-    dump_genesis, export_era, import, import_era, init_cmd, init_state,
+    dump_genesis, exex, export_era, import, import_era, init_cmd, init_state,
     launcher::FnLauncher,
     node::{self, NoArgs},
     p2p, prune, re_execute, stage,
@@ -307,6 +307,12 @@ pub enum Commands<
     /// Re-execute blocks in parallel to verify historical sync correctness.
     #[command(name = "re-execute")]
     ReExecute(re_execute::Command<C>),
+    /// Debugging utilities for the consensus engine
+    #[command(name = "debug")]
+    Debug(Box<debug_cmd::Command<C, Ext>>),
+    /// Execution extension utilities
+    #[command(name = "exex")]
+    ExEx(exex::Command<C>),
     /// Extension subcommands provided by consumers.
     #[command(flatten)]
     Ext(SubCmd),
@@ -348,6 +354,8 @@ impl<C: ChainSpecParser, Ext: clap::Args + fmt::Debug, SubCmd: Subcommand + fmt:
             Self::Config(_) => None,
             Self::Prune(cmd) => cmd.chain_spec(),
             Self::ReExecute(cmd) => cmd.chain_spec(),
+            Self::Debug(cmd) => cmd.chain_spec(),
+            Self::ExEx(cmd) => cmd.chain_spec(),
             Self::Ext(_) => None,
         }
     }
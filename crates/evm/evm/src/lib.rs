@@ -20,7 +20,7 @@ extern crate alloc;
 use crate::execute::{BasicBlockBuilder, Executor};
 use alloc::vec::Vec;
 use alloy_eips::eip4895::Withdrawals;
-use alloy_evm::{block::BlockExecutorFactory, precompiles::PrecompilesMap};
+use alloy_evm::{block::BlockExecutorFactory, env::BlockEnvironment, precompiles::PrecompilesMap};
 use alloy_primitives::{Address, Bytes, B256};
 use core::{error::Error, fmt::Debug};
 use execute::{BasicBlockExecutor, BlockAssembler, BlockBuilder};
@@ -495,3 +495,20 @@ pub struct NextBlockEnvAttributes {
     /// Optional slot number for post-Amsterdam payloads.
     pub slot_number: Option<u64>,
 }
+
+/// Disables the block gas limit check of the EVM environment for BSC system transactions.
+///
+/// BSC system transactions are identified by:
+/// 1. `gas_limit` == `u64::MAX / 2`
+/// 2. caller == block beneficiary (coinbase)
+///
+/// Their gas limit exceeds the block gas limit, so they can't be replayed one by one outside of
+/// the block executor, e.g. for tracing, without this.
+pub fn handle_bsc_system_transaction<E: ConfigureEvm>(
+    evm_env: &mut EvmEnvFor<E>,
+    tx_env: &TxEnvFor<E>,
+) {
+    if tx_env.gas_limit() == u64::MAX / 2 && tx_env.caller() == evm_env.block_env.beneficiary() {
+        evm_env.cfg_env.disable_block_gas_limit = true;
+    }
+}
@@ -115,6 +115,11 @@ impl<N: NodePrimitives> Chain<N> {
         self.trie_data.clear();
     }
 
+    /// Remove the trie data of all blocks up to and including the given block number.
+    pub fn clear_trie_data_to(&mut self, block_number: BlockNumber) {
+        self.trie_data.retain(|number, _| *number > block_number);
+    }
+
     /// Get execution outcome of this chain
     pub const fn execution_outcome(&self) -> &ExecutionOutcome<N::Receipt> {
         &self.execution_outcome
@@ -106,6 +106,11 @@ pub use manager::*;
 mod notifications;
 pub use notifications::*;
 
+#[cfg(unix)]
+mod socket;
+#[cfg(unix)]
+pub use socket::*;
+
 mod wal;
 pub use wal::*;
 
@@ -13,7 +13,7 @@ use reth_metrics::{metrics::Counter, Metrics};
 use reth_node_api::NodePrimitives;
 use reth_primitives_traits::SealedHeader;
 use reth_provider::HeaderProvider;
-use reth_tracing::tracing::{debug, warn};
+use reth_tracing::tracing::{debug, info, warn};
 use std::{
     collections::VecDeque,
     fmt::Debug,
@@ -252,6 +252,14 @@ pub struct ExExManager<P, N: NodePrimitives> {
     finalized_header_stream: ForkChoiceStream<SealedHeader<N::BlockHeader>>,
     /// The threshold for the number of blocks in the WAL before emitting a warning.
     wal_blocks_warning: usize,
+    /// The maximum size of the WAL in bytes, if any.
+    wal_max_size: Option<u64>,
+    /// Whether the WAL reached [`Self::wal_max_size`], in which case no new notifications are
+    /// accepted.
+    wal_full: bool,
+    /// The last finalized header, used to finalize the WAL again as soon as `ExEx`es make
+    /// progress while it's full.
+    last_finalized_header: Option<SealedHeader<N::BlockHeader>>,
 
     /// A handle to the `ExEx` manager.
     handle: ExExManagerHandle<N>,
@@ -312,6 +320,9 @@ where
             wal,
             finalized_header_stream,
             wal_blocks_warning: DEFAULT_WAL_BLOCKS_WARNING,
+            wal_max_size: None,
+            wal_full: false,
+            last_finalized_header: None,
 
             handle: ExExManagerHandle {
                 exex_tx: handle_tx,
@@ -340,10 +351,45 @@ where
         self
     }
 
+    /// Sets the maximum size of the WAL in bytes.
+    ///
+    /// If an `ExEx` stalls, the WAL can't be finalized and keeps growing. Once it reaches this
+    /// size, the manager reports no capacity and stops accepting new notifications instead of
+    /// filling up the disk, until the `ExEx`es catch up and the WAL is finalized. Senders waiting
+    /// for capacity, like the forwarding of canonical state notifications, have to catch up on the
+    /// notifications they couldn't send.
+    pub const fn with_wal_max_size(mut self, max_size: u64) -> Self {
+        self.wal_max_size = Some(max_size);
+        self
+    }
+
+    /// Checks whether the WAL reached its maximum size and returns the result.
+    fn update_wal_full(&mut self) -> bool {
+        let size = self.wal.size_bytes();
+        let wal_full = self.wal_max_size.is_some_and(|max_size| size >= max_size);
+        if wal_full != self.wal_full {
+            if wal_full {
+                warn!(
+                    target: "exex::manager",
+                    size,
+                    max_size = ?self.wal_max_size,
+                    "WAL reached its maximum size, not accepting new notifications until it's finalized. Check that you emit the FinishedHeight event from your ExExes."
+                );
+            } else {
+                info!(target: "exex::manager", size, "WAL is below its maximum size again, accepting new notifications");
+            }
+            self.wal_full = wal_full;
+        }
+        wal_full
+    }
+
     /// Updates the current buffer capacity and notifies all `is_ready` watchers of the manager's
     /// readiness to receive notifications.
+    ///
+    /// The capacity is zero while the WAL is full.
     fn update_capacity(&self) {
-        let capacity = self.max_capacity.saturating_sub(self.buffer.len());
+        let capacity =
+            if self.wal_full { 0 } else { self.max_capacity.saturating_sub(self.buffer.len()) };
         self.current_capacity.store(capacity, Ordering::Relaxed);
         self.metrics.current_capacity.set(capacity as f64);
         self.metrics.buffer_size.set(self.buffer.len() as f64);
@@ -459,12 +505,16 @@ where
         let this = self.get_mut();
 
         // Handle incoming ExEx events
+        let mut finished_height_updated = false;
         for exex in &mut this.exex_handles {
             while let Poll::Ready(Some(event)) = exex.receiver.poll_recv(cx) {
                 debug!(target: "exex::manager", exex_id = %exex.id, ?event, "Received event from ExEx");
                 exex.metrics.events_sent_total.increment(1);
                 match event {
-                    ExExEvent::FinishedHeight(height) => exex.finished_height = Some(height),
+                    ExExEvent::FinishedHeight(height) => {
+                        exex.finished_height = Some(height);
+                        finished_height_updated = true;
+                    }
                 }
             }
         }
@@ -475,11 +525,19 @@ where
             last_finalized_header = finalized_header;
         }
         if let Some(header) = last_finalized_header {
+            this.finalize_wal(header.clone())?;
+            this.last_finalized_header = Some(header);
+        } else if finished_height_updated &&
+            this.wal_full &&
+            let Some(header) = this.last_finalized_header.clone()
+        {
+            // Free up space as soon as the ExExes make progress, instead of waiting for the next
+            // finalized header
             this.finalize_wal(header)?;
         }
 
         // Drain handle notifications
-        while this.buffer.len() < this.max_capacity {
+        while this.buffer.len() < this.max_capacity && !this.update_wal_full() {
             if let Poll::Ready(Some((source, notification))) = this.handle_rx.poll_recv(cx) {
                 let committed_tip =
                     notification.committed_chain().map(|chain| chain.tip().number());
@@ -532,6 +590,7 @@ where
         this.min_id = min_id;
 
         // Update capacity
+        this.update_wal_full();
         this.update_capacity();
 
         // If the buffer was full and we made space, we need to wake up to accept new notifications
@@ -1115,6 +1174,57 @@ mod tests {
         assert_eq!(pinned_manager.buffer.len(), 2);
     }
 
+    #[tokio::test]
+    async fn test_exex_manager_wal_max_size() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let wal = Wal::new(temp_dir.path()).unwrap();
+
+        let provider_factory = create_test_provider_factory();
+
+        let (exex_handle_1, _, _) = ExExHandle::new(
+            "test_exex_1".to_string(),
+            Default::default(),
+            (),
+            EthEvmConfig::mainnet(),
+            wal.handle(),
+        );
+
+        // Create an ExExManager with a WAL that is full after the first notification
+        let exex_manager = ExExManager::new(
+            provider_factory,
+            vec![exex_handle_1],
+            DEFAULT_EXEX_MANAGER_CAPACITY,
+            wal,
+            empty_finalized_header_stream(),
+        )
+        .with_wal_max_size(1);
+
+        let mut cx = Context::from_waker(futures::task::noop_waker_ref());
+
+        let notification = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![Default::default()],
+                Default::default(),
+                Default::default(),
+            )),
+        };
+        for _ in 0..2 {
+            exex_manager
+                .handle
+                .exex_tx
+                .send((ExExNotificationSource::BlockchainTree, notification.clone()))
+                .unwrap();
+        }
+
+        let mut pinned_manager = std::pin::pin!(exex_manager);
+        let _ = pinned_manager.as_mut().poll(&mut cx);
+
+        // Only the first notification was accepted, and the manager has no capacity left
+        assert_eq!(pinned_manager.next_id, 1);
+        assert_eq!(pinned_manager.wal.iter_notifications().unwrap().count(), 1);
+        assert!(!pinned_manager.handle.has_capacity());
+    }
+
     #[tokio::test]
     async fn exex_handle_new() {
         let provider_factory = create_test_provider_factory();
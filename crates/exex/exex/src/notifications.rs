@@ -113,6 +113,20 @@ where
     }
 }
 
+impl<P, E> ExExNotifications<P, E>
+where
+    E: ConfigureEvm,
+{
+    /// Returns the handle to the WAL that stores the notifications sent to the `ExEx`.
+    pub fn wal_handle(&self) -> &WalHandle<E::Primitives> {
+        match &self.inner {
+            ExExNotificationsInner::WithoutHead(notifications) => &notifications.wal_handle,
+            ExExNotificationsInner::WithHead(notifications) => &notifications.wal_handle,
+            ExExNotificationsInner::Invalid => unreachable!(),
+        }
+    }
+}
+
 impl<P, E> ExExNotificationsStream<E::Primitives> for ExExNotifications<P, E>
 where
     P: BlockReader + HeaderProvider + StateProviderFactory + Clone + Unpin + 'static,
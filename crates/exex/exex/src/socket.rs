@@ -0,0 +1,438 @@
+//! An `ExEx` that serves [`ExExNotification`]s to consumers outside of the node over a Unix
+//! socket.
+//!
+//! # Protocol
+//!
+//! Every message in either direction is a JSON document prefixed with its length in bytes, as a
+//! big-endian `u32`.
+//!
+//! After connecting, a consumer sends a [`SocketExExRequest::Subscribe`] with the last block it
+//! processed, if any, within [`SUBSCRIBE_TIMEOUT`]. The notifications still in the WAL that touch
+//! blocks above it are sent first, in the order the node committed them, followed by the live
+//! notifications. A replayed notification may contain blocks the consumer already processed, which
+//! it should skip.
+//!
+//! Consumers send a [`SocketExExRequest::Ack`] once they processed a block. The lowest block acked
+//! by the connected consumers, starting at the head they subscribed with, is reported to the node
+//! as [`ExExEvent::FinishedHeight`], so the WAL keeps the notifications a consumer needs when it
+//! reconnects. The last reported height is persisted next to the socket, with the `ack` extension,
+//! and kept while no consumer is connected, also across restarts of the node. The head of the
+//! node is reported only until a consumer acks its first block. Remove the file while the node is
+//! stopped to release the WAL if the consumer doesn't come back.
+//!
+//! [`ExExEvent::FinishedHeight`]: crate::ExExEvent::FinishedHeight
+
+use crate::{ExExContext, ExExNotification, WalHandle};
+use alloy_consensus::BlockHeader;
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use reth_exex_types::serde_bincode_compat;
+use reth_node_api::{FullNodeComponents, NodePrimitives};
+use reth_tracing::tracing::{debug, info, warn};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{
+    net::{UnixListener, UnixStream},
+    sync::{broadcast, mpsc},
+};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+/// The default number of live notifications buffered for a consumer before it's disconnected for
+/// falling behind.
+pub const DEFAULT_SOCKET_EXEX_BUFFER: usize = 1024;
+
+/// The time a consumer has to send its [`SocketExExRequest::Subscribe`] after connecting, before
+/// it's disconnected.
+pub const SUBSCRIBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A message sent by a consumer of the [`SocketExEx`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SocketExExRequest {
+    /// Starts the stream of notifications. Must be the first message of a connection.
+    Subscribe {
+        /// The last block processed by the consumer. Notifications still in the WAL that touch
+        /// blocks above it are replayed before the live ones.
+        head: Option<BlockNumHash>,
+    },
+    /// Acknowledges that the consumer processed all blocks up to and including the given one.
+    Ack {
+        /// The highest processed block.
+        height: BlockNumHash,
+    },
+}
+
+/// A message sent by the [`SocketExEx`] to a consumer.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase", bound = "")]
+pub enum SocketExExMessage<'a, N: NodePrimitives> {
+    /// A notification of the node.
+    Notification {
+        /// The notification, in the same format it's stored in the WAL.
+        notification: serde_bincode_compat::ExExNotification<'a, N>,
+    },
+    /// The connection is closed because of the given error.
+    Error {
+        /// The error message.
+        message: String,
+    },
+}
+
+/// An `ExEx` that serves [`ExExNotification`]s over a Unix socket, see the [module
+/// docs](self) for the protocol.
+pub struct SocketExEx<Node: FullNodeComponents> {
+    /// The context of the `ExEx`.
+    ctx: ExExContext<Node>,
+    /// The path of the socket to listen on.
+    path: PathBuf,
+    /// Number of live notifications buffered for a consumer.
+    buffer: usize,
+}
+
+impl<Node: FullNodeComponents> SocketExEx<Node> {
+    /// Creates a new [`SocketExEx`] listening on the given socket path.
+    pub const fn new(ctx: ExExContext<Node>, path: PathBuf) -> Self {
+        Self { ctx, path, buffer: DEFAULT_SOCKET_EXEX_BUFFER }
+    }
+
+    /// Sets the number of live notifications buffered for a consumer before it's disconnected for
+    /// falling behind.
+    pub const fn with_buffer(mut self, buffer: usize) -> Self {
+        self.buffer = buffer;
+        self
+    }
+}
+
+impl<Node: FullNodeComponents> fmt::Debug for SocketExEx<Node> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SocketExEx")
+            .field("ctx", &"...")
+            .field("path", &self.path)
+            .field("buffer", &self.buffer)
+            .finish()
+    }
+}
+
+impl<Node: FullNodeComponents> SocketExEx<Node> {
+    /// Accepts consumers and forwards the notifications of the node to them, until the node shuts
+    /// down.
+    pub async fn run(mut self) -> eyre::Result<()> {
+        if self.path.exists() {
+            // left behind by a previous run
+            reth_fs_util::remove_file(&self.path)?;
+        }
+        let listener = UnixListener::bind(&self.path)?;
+        info!(target: "exex::socket", path = ?self.path, "Listening for ExEx consumers");
+
+        let wal = self.ctx.notifications.wal_handle().clone();
+        let (live_tx, _) = broadcast::channel(self.buffer);
+        let (events_tx, mut events_rx) = mpsc::unbounded_channel();
+
+        let mut next_consumer_id = 0u64;
+        // The highest block processed by each connected consumer, `None` until it subscribed.
+        let mut acks = HashMap::<u64, Option<BlockNumHash>>::default();
+        let mut node_head = self.ctx.head;
+        // The lowest block acked by the consumers, kept while none is connected.
+        let ack_path = self.path.with_extension("ack");
+        let mut last_acked = load_ack(&ack_path)?;
+        let mut finished_height = None;
+
+        loop {
+            tokio::select! {
+                connection = listener.accept() => {
+                    let (stream, _) = connection?;
+                    let id = next_consumer_id;
+                    next_consumer_id += 1;
+                    acks.insert(id, None);
+                    debug!(target: "exex::socket", id, "ExEx consumer connected");
+
+                    let live = live_tx.subscribe();
+                    let wal = wal.clone();
+                    let events_tx = events_tx.clone();
+                    self.ctx.task_executor().spawn_task(async move {
+                        if let Err(err) = serve_consumer(id, stream, live, wal, &events_tx).await {
+                            warn!(target: "exex::socket", id, %err, "ExEx consumer failed");
+                        }
+                        let _ = events_tx.send(ConsumerEvent::Disconnected(id));
+                    });
+                }
+                notification = self.ctx.notifications.next() => {
+                    let Some(notification) = notification else { return Ok(()) };
+                    let notification = notification?;
+                    if let Some(head) = notification_head(&notification) {
+                        node_head = head;
+                    }
+                    // no receivers only means no consumer is connected, they catch up from the WAL
+                    let _ = live_tx.send(notification);
+                }
+                Some(event) = events_rx.recv() => {
+                    match event {
+                        ConsumerEvent::Subscribed(id, head) => {
+                            // consumers without a head only receive the notifications from now on
+                            acks.insert(id, Some(head.unwrap_or(node_head)));
+                        }
+                        ConsumerEvent::Ack(id, height) => {
+                            if let Some(ack) = acks.get_mut(&id) {
+                                *ack = Some(height);
+                            }
+                        }
+                        ConsumerEvent::Disconnected(id) => {
+                            debug!(target: "exex::socket", id, "ExEx consumer disconnected");
+                            acks.remove(&id);
+                        }
+                    }
+                }
+            }
+
+            // consumers that didn't subscribe yet hold back the finished height until they do or
+            // time out
+            if acks.values().any(Option::is_none) {
+                continue
+            }
+            let acked = acks.values().flatten().min_by_key(|height| height.number).copied();
+            if let Some(acked) = acked &&
+                last_acked != Some(acked)
+            {
+                if let Err(err) = save_ack(&ack_path, acked) {
+                    warn!(target: "exex::socket", path = ?ack_path, %err, "Failed to persist acked height");
+                }
+                last_acked = Some(acked);
+            }
+            let height = last_acked.unwrap_or(node_head);
+            if finished_height != Some(height) {
+                self.ctx.send_finished_height(height)?;
+                finished_height = Some(height);
+            }
+        }
+    }
+}
+
+/// Loads the acked height persisted at the given path, if any.
+fn load_ack(path: &Path) -> eyre::Result<Option<BlockNumHash>> {
+    if !path.exists() {
+        return Ok(None)
+    }
+    Ok(Some(reth_fs_util::read_json_file(path)?))
+}
+
+/// Persists the acked height at the given path.
+fn save_ack(path: &Path, height: BlockNumHash) -> eyre::Result<()> {
+    reth_fs_util::atomic_write_file(path, |file| serde_json::to_writer(file, &height))?;
+    Ok(())
+}
+
+/// Returns the head of the node after the given notification.
+fn notification_head<N: NodePrimitives>(
+    notification: &ExExNotification<N>,
+) -> Option<BlockNumHash> {
+    if let Some(committed) = notification.committed_chain() {
+        return Some(committed.tip().num_hash())
+    }
+    notification.reverted_chain().map(|reverted| {
+        let first = reverted.first();
+        BlockNumHash::new(first.number().saturating_sub(1), first.parent_hash())
+    })
+}
+
+/// An event of a consumer connection, sent to the [`SocketExEx`] task.
+#[derive(Debug)]
+enum ConsumerEvent {
+    /// The consumer subscribed with the last block it processed, if any.
+    Subscribed(u64, Option<BlockNumHash>),
+    /// The consumer acknowledged a block.
+    Ack(u64, BlockNumHash),
+    /// The consumer disconnected.
+    Disconnected(u64),
+}
+
+/// Serves notifications to a single consumer until it disconnects.
+async fn serve_consumer<N: NodePrimitives>(
+    id: u64,
+    stream: UnixStream,
+    mut live: broadcast::Receiver<ExExNotification<N>>,
+    wal: WalHandle<N>,
+    events_tx: &mpsc::UnboundedSender<ConsumerEvent>,
+) -> eyre::Result<()> {
+    let mut framed = Framed::new(stream, LengthDelimitedCodec::new());
+
+    let Ok(request) = tokio::time::timeout(SUBSCRIBE_TIMEOUT, next_request(&mut framed)).await
+    else {
+        let message = format!("No subscribe request within {SUBSCRIBE_TIMEOUT:?}");
+        send(&mut framed, SocketExExMessage::<N>::Error { message: message.clone() }).await?;
+        eyre::bail!(message)
+    };
+    let head = match request? {
+        Some(SocketExExRequest::Subscribe { head }) => head,
+        Some(request) => {
+            let message = format!("Expected a subscribe request, got {request:?}");
+            send(&mut framed, SocketExExMessage::<N>::Error { message: message.clone() }).await?;
+            eyre::bail!(message)
+        }
+        None => return Ok(()),
+    };
+    let _ = events_tx.send(ConsumerEvent::Subscribed(id, head));
+
+    // Notifications committed after subscribing to the live ones may also be read from the WAL,
+    // they're only sent once.
+    let mut replayed = HashSet::new();
+    if let Some(head) = head {
+        debug!(target: "exex::socket", id, ?head, "Replaying notifications from the WAL");
+        let mut wal_notifications = replay_from_wal(wal, head.number);
+        while let Some(notification) = wal_notifications.recv().await {
+            let notification = match notification {
+                Ok(notification) => notification,
+                Err(err) => {
+                    let message = err.to_string();
+                    send(&mut framed, SocketExExMessage::<N>::Error { message }).await?;
+                    return Err(err)
+                }
+            };
+            replayed.insert(notification_key(&notification));
+            let notification = (&notification).into();
+            send(&mut framed, SocketExExMessage::Notification { notification }).await?;
+        }
+    }
+
+    loop {
+        tokio::select! {
+            notification = live.recv() => match notification {
+                Ok(notification) => {
+                    if !replayed.is_empty() && replayed.remove(&notification_key(&notification)) {
+                        continue
+                    }
+                    let notification = (&notification).into();
+                    send(&mut framed, SocketExExMessage::Notification { notification }).await?;
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    let message = format!(
+                        "Fell behind by {skipped} notifications, reconnect to catch up from the WAL"
+                    );
+                    send(&mut framed, SocketExExMessage::<N>::Error { message: message.clone() })
+                        .await?;
+                    eyre::bail!(message)
+                }
+                Err(broadcast::error::RecvError::Closed) => return Ok(()),
+            },
+            request = next_request(&mut framed) => match request? {
+                Some(SocketExExRequest::Ack { height }) => {
+                    let _ = events_tx.send(ConsumerEvent::Ack(id, height));
+                }
+                Some(request @ SocketExExRequest::Subscribe { .. }) => {
+                    let message = format!("Already subscribed, got {request:?}");
+                    send(&mut framed, SocketExExMessage::<N>::Error { message: message.clone() })
+                        .await?;
+                    eyre::bail!(message)
+                }
+                None => return Ok(()),
+            },
+        }
+    }
+}
+
+/// Reads the notifications from the WAL that touch blocks above `head` on a blocking thread.
+///
+/// Fails if the WAL doesn't reach back to the block after `head`.
+fn replay_from_wal<N: NodePrimitives>(
+    wal: WalHandle<N>,
+    head: BlockNumber,
+) -> mpsc::Receiver<eyre::Result<ExExNotification<N>>> {
+    let (tx, rx) = mpsc::channel(1);
+    tokio::task::spawn_blocking(move || {
+        let notifications = match wal.iter_notifications() {
+            Ok(notifications) => notifications,
+            Err(err) => {
+                let _ = tx.blocking_send(Err(err.into()));
+                return
+            }
+        };
+
+        let mut first = true;
+        for notification in notifications {
+            let notification = notification.map_err(eyre::Error::from).and_then(|notification| {
+                let tip = notification
+                    .committed_chain()
+                    .into_iter()
+                    .chain(notification.reverted_chain())
+                    .map(|chain| chain.tip().number())
+                    .max()
+                    .unwrap_or_default();
+                if tip <= head {
+                    return Ok(None)
+                }
+
+                if first &&
+                    let Some(committed) = notification.committed_chain() &&
+                    notification.reverted_chain().is_none() &&
+                    committed.first().number() > head + 1
+                {
+                    eyre::bail!(
+                        "WAL starts at block {}, can't replay from block {}",
+                        committed.first().number(),
+                        head + 1
+                    )
+                }
+                first = false;
+                Ok(Some(notification))
+            });
+
+            let failed = notification.is_err();
+            if let Some(notification) = notification.transpose() &&
+                (tx.blocking_send(notification).is_err() || failed)
+            {
+                return
+            }
+        }
+    });
+    rx
+}
+
+/// Identifies a notification by the tips of its chains.
+fn notification_key<N: NodePrimitives>(
+    notification: &ExExNotification<N>,
+) -> (Option<BlockNumHash>, Option<BlockNumHash>) {
+    (
+        notification.committed_chain().map(|chain| chain.tip().num_hash()),
+        notification.reverted_chain().map(|chain| chain.tip().num_hash()),
+    )
+}
+
+/// Reads the next request of the consumer, returns `None` if it disconnected.
+async fn next_request(
+    framed: &mut Framed<UnixStream, LengthDelimitedCodec>,
+) -> eyre::Result<Option<SocketExExRequest>> {
+    let Some(frame) = framed.next().await.transpose()? else { return Ok(None) };
+    Ok(Some(serde_json::from_slice(&frame)?))
+}
+
+/// Sends a message to the consumer.
+async fn send<N: NodePrimitives>(
+    framed: &mut Framed<UnixStream, LengthDelimitedCodec>,
+    message: SocketExExMessage<'_, N>,
+) -> eyre::Result<()> {
+    framed.send(Bytes::from(serde_json::to_vec(&message)?)).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn request_json() {
+        let head = BlockNumHash::new(1, Default::default());
+        let request = SocketExExRequest::Subscribe { head: Some(head) };
+        let json = serde_json::to_string(&request).unwrap();
+        assert_eq!(serde_json::from_str::<SocketExExRequest>(&json).unwrap(), request);
+
+        let request: SocketExExRequest =
+            serde_json::from_str(r#"{"subscribe":{"head":null}}"#).unwrap();
+        assert_eq!(request, SocketExExRequest::Subscribe { head: None });
+    }
+}
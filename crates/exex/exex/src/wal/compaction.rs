@@ -0,0 +1,336 @@
+use std::sync::{atomic::Ordering, Arc};
+
+use alloy_primitives::BlockNumber;
+use reth_exex_types::ExExNotification;
+use reth_node_api::NodePrimitives;
+use reth_revm::db::BundleState;
+use reth_tracing::tracing::debug;
+
+use crate::wal::{BlockCache, WalInner, WalResult};
+
+/// The default size in bytes below which WAL files are merged by [`Wal::compact`].
+///
+/// [`Wal::compact`]: crate::Wal::compact
+pub const DEFAULT_WAL_COMPACTION_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Parameters of a [`Wal::compact`] run.
+///
+/// [`Wal::compact`]: crate::Wal::compact
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalCompaction {
+    /// Consecutive committed notifications are merged into a single file as long as the merged
+    /// file stays below this size in bytes.
+    pub merge_below: u64,
+    /// The lowest block processed by all `ExEx`es, if known.
+    ///
+    /// The state diffs and trie data of committed blocks up to and including this block are
+    /// dropped, since no `ExEx` will receive these blocks again.
+    pub finished_height: Option<BlockNumber>,
+}
+
+impl Default for WalCompaction {
+    fn default() -> Self {
+        Self { merge_below: DEFAULT_WAL_COMPACTION_FILE_SIZE, finished_height: None }
+    }
+}
+
+/// The outcome of a [`Wal::compact`] run.
+///
+/// [`Wal::compact`]: crate::Wal::compact
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WalCompactionReport {
+    /// Number of files before the compaction.
+    pub files_before: usize,
+    /// Number of files after the compaction.
+    pub files_after: usize,
+    /// Total size of the files before the compaction, in bytes.
+    pub size_before: u64,
+    /// Total size of the files after the compaction, in bytes.
+    pub size_after: u64,
+}
+
+impl WalCompactionReport {
+    /// Returns the number of bytes saved.
+    pub const fn saved(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+}
+
+/// A notification that is being compacted, together with the files it was read from.
+#[derive(Debug)]
+struct PendingFile<N: NodePrimitives> {
+    /// The IDs of the files merged into the notification, in ascending order.
+    file_ids: Vec<u32>,
+    /// The total size of the files in bytes.
+    size: u64,
+    /// Whether the notification differs from the one stored in the files.
+    modified: bool,
+    notification: ExExNotification<N>,
+}
+
+impl<N> WalInner<N>
+where
+    N: NodePrimitives,
+{
+    /// Merges small consecutive committed notifications and drops the state diffs and trie data
+    /// of blocks below the finished height, then rebuilds the block cache.
+    ///
+    /// The compacted files are renumbered from the first file ID on, so the file IDs of the WAL
+    /// stay contiguous.
+    pub(super) fn compact(&self, compaction: WalCompaction) -> WalResult<WalCompactionReport> {
+        let mut block_cache = self.block_cache.write();
+        let mut report = WalCompactionReport::default();
+        let Some(files_range) = self.storage.files_range()? else { return Ok(report) };
+
+        let mut new_block_cache = BlockCache::default();
+        let mut next_file_id = *files_range.start();
+        let mut pending: Option<PendingFile<N>> = None;
+        for entry in self.storage.iter_notifications(files_range) {
+            let (file_id, size, mut notification) = entry?;
+            report.files_before += 1;
+            report.size_before += size;
+
+            let modified = compaction
+                .finished_height
+                .is_some_and(|height| drop_state_diffs(&mut notification, height));
+            let file = PendingFile { file_ids: vec![file_id], size, modified, notification };
+
+            pending = Some(match pending.take() {
+                Some(mut pending) if pending.size + size < compaction.merge_below => {
+                    match merge(&mut pending, file) {
+                        Ok(()) => pending,
+                        Err(file) => {
+                            self.flush(
+                                pending,
+                                &mut next_file_id,
+                                &mut new_block_cache,
+                                &mut report,
+                            )?;
+                            file
+                        }
+                    }
+                }
+                Some(pending) => {
+                    self.flush(pending, &mut next_file_id, &mut new_block_cache, &mut report)?;
+                    file
+                }
+                None => file,
+            });
+        }
+        if let Some(pending) = pending {
+            self.flush(pending, &mut next_file_id, &mut new_block_cache, &mut report)?;
+        }
+
+        *block_cache = new_block_cache;
+        self.next_file_id.store(next_file_id, Ordering::Relaxed);
+        self.update_metrics(&block_cache, report.size_after as i64 - report.size_before as i64);
+        debug!(target: "exex::wal", ?report, "WAL was compacted");
+
+        Ok(report)
+    }
+
+    /// Writes the pending notification to the file with the next file ID, if it was modified or
+    /// stored under another ID, in place of the files it was read from.
+    ///
+    /// The next file ID is never above the IDs of the files the notification was read from, so
+    /// only files that were already read are overwritten.
+    fn flush(
+        &self,
+        file: PendingFile<N>,
+        next_file_id: &mut u32,
+        block_cache: &mut BlockCache,
+        report: &mut WalCompactionReport,
+    ) -> WalResult<()> {
+        let PendingFile { file_ids, mut size, modified, notification } = file;
+        let file_id = *next_file_id;
+        *next_file_id += 1;
+
+        if modified || file_ids != [file_id] {
+            size = self.storage.replace_notifications(file_id, &notification, &file_ids)?;
+        }
+
+        block_cache.insert_notification_blocks_with_file_id(file_id, &notification);
+        report.files_after += 1;
+        report.size_after += size;
+
+        Ok(())
+    }
+}
+
+/// Appends the committed chain of `next` to the committed chain of `pending`.
+///
+/// Returns `next` in [`Result::Err`] variant if they're not committed notifications of consecutive
+/// chains.
+fn merge<N: NodePrimitives>(
+    pending: &mut PendingFile<N>,
+    next: PendingFile<N>,
+) -> Result<(), PendingFile<N>> {
+    let (
+        ExExNotification::ChainCommitted { new: pending_chain },
+        ExExNotification::ChainCommitted { new: next_chain },
+    ) = (&mut pending.notification, &next.notification)
+    else {
+        return Err(next)
+    };
+    if pending_chain.tip().hash() != next_chain.fork_block().hash {
+        return Err(next)
+    }
+
+    let PendingFile { file_ids, size, notification, .. } = next;
+    let ExExNotification::ChainCommitted { new: next_chain } = notification else {
+        unreachable!("checked above")
+    };
+    Arc::make_mut(pending_chain)
+        .append_chain(Arc::unwrap_or_clone(next_chain))
+        .expect("chains are consecutive");
+    pending.file_ids.extend(file_ids);
+    pending.size += size;
+    pending.modified = true;
+
+    Ok(())
+}
+
+/// Drops the state diffs and trie data of the committed blocks up to and including the given
+/// block.
+///
+/// The reverts of these blocks are removed from the bundle state of the chain, and if the whole
+/// chain is finished, the bundle state is cleared. Blocks and receipts are kept.
+///
+/// Returns `true` if the notification was modified.
+fn drop_state_diffs<N: NodePrimitives>(
+    notification: &mut ExExNotification<N>,
+    to_block: BlockNumber,
+) -> bool {
+    let (ExExNotification::ChainCommitted { new } | ExExNotification::ChainReorged { new, .. }) =
+        notification
+    else {
+        return false
+    };
+
+    let finished = new.blocks().range(..=to_block).count();
+    if finished == 0 {
+        return false
+    }
+    let whole_chain = finished == new.len();
+    let bundle = &new.execution_outcome().bundle;
+    // reverts of finished blocks may have been dropped by a previous compaction already
+    let finished_reverts = bundle.reverts.len().saturating_sub(new.len() - finished);
+    let has_state_diffs = finished_reverts > 0 || (whole_chain && !bundle.state.is_empty());
+    let has_trie_data = new.trie_data().range(..=to_block).next().is_some();
+    if !has_state_diffs && !has_trie_data {
+        return false
+    }
+
+    let new = Arc::make_mut(new);
+    new.clear_trie_data_to(to_block);
+    let bundle = &mut new.execution_outcome_mut().bundle;
+    if whole_chain {
+        *bundle = BundleState::default();
+    } else {
+        bundle.take_n_reverts(finished_reverts);
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::wal::{Wal, WalCompaction};
+    use alloy_primitives::Address;
+    use reth_exex_types::ExExNotification;
+    use reth_provider::{Chain, ExecutionOutcome};
+    use reth_revm::db::BundleState;
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use reth_trie_common::LazyTrieData;
+    use std::{collections::BTreeMap, sync::Arc};
+
+    #[test]
+    fn test_compact() -> eyre::Result<()> {
+        reth_tracing::init_test_tracing();
+
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let wal = Wal::new(&temp_dir)?;
+
+        let blocks = random_block_range(&mut rng, 0..=3, BlockRangeParams::default())
+            .into_iter()
+            .map(|block| block.try_recover())
+            .collect::<Result<Vec<_>, _>>()?;
+        let trie_data = |blocks: &[u64]| {
+            blocks
+                .iter()
+                .map(|number| {
+                    (*number, LazyTrieData::ready(Default::default(), Default::default()))
+                })
+                .collect::<BTreeMap<_, _>>()
+        };
+        // An execution outcome with a revert for each of the blocks
+        let execution_outcome = |blocks: &[u64]| {
+            let range = blocks[0]..=blocks[blocks.len() - 1];
+            let bundle = blocks.iter().fold(BundleState::builder(range), |builder, number| {
+                builder.revert_account_info(*number, Address::with_last_byte(1), Some(None))
+            });
+            ExecutionOutcome {
+                bundle: bundle.build(),
+                first_block: blocks[0],
+                ..Default::default()
+            }
+        };
+
+        // Blocks 0 and 1, 2 and 3 are committed in separate notifications, then block 3 is
+        // reverted
+        let committed_notification_1 = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![blocks[0].clone(), blocks[1].clone()],
+                execution_outcome(&[0, 1]),
+                trie_data(&[0, 1]),
+            )),
+        };
+        let committed_notification_2 = ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![blocks[2].clone(), blocks[3].clone()],
+                execution_outcome(&[2, 3]),
+                trie_data(&[2, 3]),
+            )),
+        };
+        let reverted_notification = ExExNotification::ChainReverted {
+            old: Arc::new(Chain::new(vec![blocks[3].clone()], Default::default(), BTreeMap::new())),
+        };
+        wal.commit(&committed_notification_1)?;
+        wal.commit(&committed_notification_2)?;
+        wal.commit(&reverted_notification)?;
+
+        let report =
+            wal.compact(WalCompaction { merge_below: u64::MAX, finished_height: Some(1) })?;
+        assert_eq!(report.files_before, 3);
+        assert_eq!(report.files_after, 2);
+        assert_eq!(wal.size_bytes(), report.size_after);
+
+        // The committed notifications are merged into the first file, the state diffs and trie
+        // data of the finished blocks are dropped, and the file IDs stay contiguous
+        let files = wal.files()?;
+        assert_eq!(files.iter().map(|file| file.id).collect::<Vec<_>>(), vec![0, 1]);
+        assert_eq!(files[0].committed, Some(0..=3));
+        assert_eq!(files[1].reverted, Some(3..=3));
+
+        let merged = wal.notification(0)?.unwrap();
+        let merged_chain = merged.committed_chain().unwrap();
+        assert_eq!(merged_chain.range(), 0..=3);
+        assert_eq!(merged_chain.execution_outcome().bundle.reverts.len(), 2);
+        assert_eq!(merged_chain.trie_data().keys().copied().collect::<Vec<_>>(), vec![2, 3]);
+        assert_eq!(wal.notification(1)?, Some(reverted_notification));
+        assert_eq!(wal.notification(2)?, None);
+
+        // The block cache points to the merged file
+        assert_eq!(
+            wal.handle().get_committed_notification_by_block_hash(&blocks[0].hash())?,
+            Some(merged)
+        );
+
+        // New notifications are written after the compacted ones
+        wal.commit(&committed_notification_1)?;
+        assert_eq!(wal.files()?.last().map(|file| file.id), Some(2));
+
+        Ok(())
+    }
+}
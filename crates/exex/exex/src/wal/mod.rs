@@ -2,6 +2,8 @@
 
 mod cache;
 pub use cache::BlockCache;
+mod compaction;
+pub use compaction::{WalCompaction, WalCompactionReport, DEFAULT_WAL_COMPACTION_FILE_SIZE};
 mod storage;
 use reth_ethereum_primitives::EthPrimitives;
 use reth_node_api::NodePrimitives;
@@ -12,15 +14,16 @@ mod error;
 pub use error::{WalError, WalResult};
 
 use std::{
+    ops::RangeInclusive,
     path::Path,
     sync::{
-        atomic::{AtomicU32, Ordering},
+        atomic::{AtomicU32, AtomicU64, Ordering},
         Arc,
     },
 };
 
 use alloy_eips::BlockNumHash;
-use alloy_primitives::B256;
+use alloy_primitives::{BlockNumber, B256};
 use parking_lot::{RwLock, RwLockReadGuard};
 use reth_exex_types::ExExNotification;
 use reth_tracing::tracing::{debug, instrument};
@@ -35,6 +38,8 @@ use reth_tracing::tracing::{debug, instrument};
 /// 1. On every new canonical chain notification, call [`Wal::commit`].
 /// 2. When the chain is finalized, call [`Wal::finalize`] to prevent the infinite growth of the
 ///    WAL.
+///
+/// While the node is stopped, [`Wal::compact`] can be used to shrink the WAL further.
 #[derive(Debug, Clone)]
 pub struct Wal<N: NodePrimitives = EthPrimitives> {
     inner: Arc<WalInner<N>>,
@@ -78,6 +83,42 @@ where
     pub fn num_blocks(&self) -> usize {
         self.inner.block_cache().num_blocks()
     }
+
+    /// Returns the total size of the notifications in the WAL in bytes.
+    pub fn size_bytes(&self) -> u64 {
+        self.inner.size_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the files of the WAL, in the order they were committed.
+    pub fn files(&self) -> WalResult<Vec<WalFile>> {
+        self.inner.files()
+    }
+
+    /// Returns the notification stored in the file with the given ID, if it exists.
+    pub fn notification(&self, file_id: u32) -> WalResult<Option<ExExNotification<N>>> {
+        Ok(self.inner.storage.read_notification(file_id)?.map(|(notification, _)| notification))
+    }
+
+    /// Compacts the WAL according to the given [`WalCompaction`].
+    ///
+    /// Notifications are rewritten, so this should only be done while no `ExEx` reads from the
+    /// WAL, i.e. while the node is stopped.
+    pub fn compact(&self, compaction: WalCompaction) -> WalResult<WalCompactionReport> {
+        self.inner.compact(compaction)
+    }
+}
+
+/// A file of the [`Wal`], holding a single notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WalFile {
+    /// The ID of the file. Files are committed in the order of their IDs.
+    pub id: u32,
+    /// The size of the file in bytes.
+    pub size: u64,
+    /// The range of blocks committed by the notification.
+    pub committed: Option<RangeInclusive<BlockNumber>>,
+    /// The range of blocks reverted by the notification.
+    pub reverted: Option<RangeInclusive<BlockNumber>>,
 }
 
 /// Inner type for the WAL.
@@ -88,6 +129,8 @@ struct WalInner<N: NodePrimitives> {
     storage: Storage<N>,
     /// WAL block cache. See [`cache::BlockCache`] docs for more details.
     block_cache: RwLock<BlockCache>,
+    /// The total size of the notifications in the storage in bytes.
+    size_bytes: AtomicU64,
     metrics: Metrics,
 }
 
@@ -100,6 +143,7 @@ where
             next_file_id: AtomicU32::new(0),
             storage: Storage::new(directory)?,
             block_cache: RwLock::new(BlockCache::default()),
+            size_bytes: AtomicU64::new(0),
             metrics: Metrics::default(),
         };
         wal.fill_block_cache()?;
@@ -181,7 +225,9 @@ where
     }
 
     fn update_metrics(&self, block_cache: &BlockCache, size_delta: i64) {
-        self.metrics.size_bytes.increment(size_delta as f64);
+        let size_bytes = self.size_bytes.load(Ordering::Relaxed).saturating_add_signed(size_delta);
+        self.size_bytes.store(size_bytes, Ordering::Relaxed);
+        self.metrics.size_bytes.set(size_bytes as f64);
         self.metrics.notifications_count.set(block_cache.notification_max_blocks.len() as f64);
         self.metrics.committed_blocks_count.set(block_cache.committed_blocks.len() as f64);
 
@@ -204,6 +250,23 @@ where
 
         Ok(Box::new(self.storage.iter_notifications(range).map(|entry| Ok(entry?.2))))
     }
+
+    fn files(&self) -> WalResult<Vec<WalFile>> {
+        let Some(range) = self.storage.files_range()? else { return Ok(Vec::new()) };
+
+        self.storage
+            .iter_notifications(range)
+            .map(|entry| {
+                let (id, size, notification) = entry?;
+                Ok(WalFile {
+                    id,
+                    size,
+                    committed: notification.committed_chain().map(|chain| chain.range()),
+                    reverted: notification.reverted_chain().map(|chain| chain.range()),
+                })
+            })
+            .collect()
+    }
 }
 
 /// A read-only handle to the WAL that can be shared.
@@ -212,10 +275,23 @@ pub struct WalHandle<N: NodePrimitives> {
     wal: Arc<WalInner<N>>,
 }
 
+impl<N: NodePrimitives> Clone for WalHandle<N> {
+    fn clone(&self) -> Self {
+        Self { wal: self.wal.clone() }
+    }
+}
+
 impl<N> WalHandle<N>
 where
     N: NodePrimitives,
 {
+    /// Returns an iterator over all notifications in the WAL, in the order they were committed.
+    pub fn iter_notifications(
+        &self,
+    ) -> WalResult<Box<dyn Iterator<Item = WalResult<ExExNotification<N>>> + '_>> {
+        self.wal.iter_notifications()
+    }
+
     /// Returns the notification for the given committed block hash if it exists.
     pub fn get_committed_notification_by_block_hash(
         &self,
@@ -1,5 +1,6 @@
 use std::{
     fs::File,
+    io::Write,
     ops::RangeInclusive,
     path::{Path, PathBuf},
 };
@@ -13,6 +14,13 @@ use tracing::instrument;
 
 static FILE_EXTENSION: &str = "wal";
 
+/// The extension of a compacted notification file before it replaces the files it was merged
+/// from.
+static COMPACTED_FILE_EXTENSION: &str = "compacted";
+
+/// The file recording the compacted file that replaces a set of notification files.
+static COMPACTION_MARKER: &str = "compaction.marker";
+
 /// The underlying WAL storage backed by a directory of files.
 ///
 /// Each notification is represented by a single file that contains a MessagePack-encoded
@@ -33,13 +41,20 @@ where
     pub(super) fn new(path: impl AsRef<Path>) -> WalResult<Self> {
         reth_fs_util::create_dir_all(&path)?;
 
-        Ok(Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData })
+        let storage = Self { path: path.as_ref().to_path_buf(), _pd: std::marker::PhantomData };
+        storage.finish_compaction()?;
+
+        Ok(storage)
     }
 
     fn file_path(&self, id: u32) -> PathBuf {
         self.path.join(format!("{id}.{FILE_EXTENSION}"))
     }
 
+    fn compacted_file_path(&self, id: u32) -> PathBuf {
+        self.path.join(format!("{id}.{FILE_EXTENSION}.{COMPACTED_FILE_EXTENSION}"))
+    }
+
     fn parse_filename(filename: &str) -> WalResult<u32> {
         filename
             .strip_suffix(".wal")
@@ -160,7 +175,97 @@ where
         file_id: u32,
         notification: &ExExNotification<N>,
     ) -> WalResult<u64> {
-        let file_path = self.file_path(file_id);
+        self.write_notification_to(file_id, self.file_path(file_id), notification)
+    }
+
+    /// Replaces the files with the given IDs by a single file with the notification, stored under
+    /// `file_id`.
+    ///
+    /// The notification is written to a temporary file that is recorded in the compaction marker
+    /// before it's renamed and the replaced files are removed. If the process stops in between,
+    /// [`Self::finish_compaction`] finishes the replacement on startup, so the blocks are never
+    /// read from two files.
+    ///
+    /// # Returns
+    ///
+    /// The size of the file that was written in bytes.
+    #[instrument(skip(self, notification))]
+    pub(super) fn replace_notifications(
+        &self,
+        file_id: u32,
+        notification: &ExExNotification<N>,
+        replaced: &[u32],
+    ) -> WalResult<u64> {
+        let size =
+            self.write_notification_to(file_id, self.compacted_file_path(file_id), notification)?;
+
+        let replaced = replaced.iter().map(ToString::to_string).collect::<Vec<_>>().join(" ");
+        reth_fs_util::atomic_write_file(&self.path.join(COMPACTION_MARKER), |file| {
+            write!(file, "{file_id}\n{replaced}")
+        })?;
+        self.finish_compaction()?;
+
+        Ok(size)
+    }
+
+    /// Finishes the replacement recorded in the compaction marker, if any, and removes the
+    /// compacted files that weren't recorded yet.
+    ///
+    /// The files replaced by an unrecorded compacted file are still intact, so it's dropped.
+    pub(super) fn finish_compaction(&self) -> WalResult<()> {
+        let marker_path = self.path.join(COMPACTION_MARKER);
+        if marker_path.exists() {
+            let marker = reth_fs_util::read_to_string(&marker_path)?;
+            let (file_id, replaced) = Self::parse_compaction_marker(&marker)
+                .ok_or_else(|| WalError::Parse(marker_path.display().to_string()))?;
+
+            let compacted_path = self.compacted_file_path(file_id);
+            if compacted_path.exists() {
+                reth_fs_util::rename(&compacted_path, self.file_path(file_id))?;
+                self.sync_dir()?;
+            }
+            for id in replaced.into_iter().filter(|id| *id != file_id) {
+                reth_fs_util::remove_file_if_exists(self.file_path(id))?;
+            }
+            self.sync_dir()?;
+            reth_fs_util::remove_file(&marker_path)?;
+            debug!(target: "exex::wal::storage", file_id, "Finished compaction");
+        }
+
+        for entry in reth_fs_util::read_dir(&self.path)? {
+            let entry = entry.map_err(|err| WalError::DirEntry(self.path.clone(), err))?;
+            if entry.path().extension() == Some(COMPACTED_FILE_EXTENSION.as_ref()) {
+                debug!(target: "exex::wal::storage", path = ?entry.path(), "Removing unrecorded compacted file");
+                reth_fs_util::remove_file(entry.path())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses the compaction marker into the ID of the compacted file and the IDs of the files it
+    /// replaces.
+    fn parse_compaction_marker(marker: &str) -> Option<(u32, Vec<u32>)> {
+        let (file_id, replaced) = marker.split_once('\n')?;
+        let replaced =
+            replaced.split_whitespace().map(|id| id.parse().ok()).collect::<Option<_>>()?;
+        Some((file_id.parse().ok()?, replaced))
+    }
+
+    /// Fsyncs the storage directory, so that renames and removals in it are durable.
+    fn sync_dir(&self) -> WalResult<()> {
+        File::open(&self.path)
+            .and_then(|dir| dir.sync_all())
+            .map_err(|err| reth_fs_util::FsPathError::fsync(err, &self.path))?;
+        Ok(())
+    }
+
+    fn write_notification_to(
+        &self,
+        file_id: u32,
+        file_path: PathBuf,
+        notification: &ExExNotification<N>,
+    ) -> WalResult<u64> {
         debug!(target: "exex::wal::storage", ?file_path, "Writing notification to WAL");
 
         // Serialize using the bincode- and msgpack-compatible serde wrapper
@@ -315,6 +420,46 @@ mod tests {
         Ok(notification)
     }
 
+    #[test]
+    fn test_finish_compaction() -> eyre::Result<()> {
+        let mut rng = generators::rng();
+
+        let temp_dir = tempfile::tempdir()?;
+        let storage: Storage = Storage::new(&temp_dir)?;
+
+        let mut notification = |number| ExExNotification::ChainCommitted {
+            new: Arc::new(Chain::new(
+                vec![random_block(&mut rng, number, Default::default()).try_recover().unwrap()],
+                Default::default(),
+                BTreeMap::new(),
+            )),
+        };
+        for file_id in 0..3 {
+            storage.write_notification(file_id, &notification(file_id as u64))?;
+        }
+        let compacted = notification(0);
+
+        // The process stopped after recording the compaction of files 1 and 2 into file 1, but
+        // before the compacted file replaced them
+        storage.write_notification_to(1, storage.compacted_file_path(1), &compacted)?;
+        std::fs::write(temp_dir.path().join(super::COMPACTION_MARKER), "1\n1 2")?;
+        // and after writing a compacted file for file 0 that wasn't recorded
+        storage.write_notification_to(0, storage.compacted_file_path(0), &compacted)?;
+        let original = storage.read_notification(0)?;
+
+        let storage: Storage = Storage::new(&temp_dir)?;
+        assert_eq!(storage.files_range()?, Some(0..=1));
+        assert_eq!(storage.read_notification(0)?, original);
+        assert_eq!(
+            storage.read_notification(1)?.map(|(notification, _)| notification),
+            Some(compacted)
+        );
+        assert!(!temp_dir.path().join(super::COMPACTION_MARKER).exists());
+        assert!(!storage.compacted_file_path(0).exists());
+
+        Ok(())
+    }
+
     #[test]
     fn test_files_range() -> eyre::Result<()> {
         let temp_dir = tempfile::tempdir()?;
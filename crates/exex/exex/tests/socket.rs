@@ -0,0 +1,99 @@
+//! Drives the [`SocketExEx`] with a consumer connected over a real Unix socket.
+#![cfg(unix)]
+
+use alloy_eips::BlockNumHash;
+use alloy_primitives::B256;
+use bytes::Bytes;
+use futures::{SinkExt, StreamExt};
+use reth_exex::{ExExEvent, SocketExEx, SocketExExRequest};
+use reth_exex_test_utils::{test_exex_context, TestExExHandle};
+use reth_primitives_traits::RecoveredBlock;
+use reth_provider::Chain;
+use std::{path::Path, time::Duration};
+use tokio::{net::UnixStream, time::timeout};
+use tokio_util::codec::{Framed, LengthDelimitedCodec};
+
+type Consumer = Framed<UnixStream, LengthDelimitedCodec>;
+
+/// Returns a chain with a single block at the given height.
+fn chain(number: u64) -> (BlockNumHash, Chain) {
+    let mut block: RecoveredBlock<reth_ethereum_primitives::Block> = Default::default();
+    block.set_hash(B256::with_last_byte(number as u8));
+    block.set_block_number(number);
+    (block.num_hash(), Chain::new(vec![block], Default::default(), Default::default()))
+}
+
+async fn next_event(handle: &mut TestExExHandle) -> ExExEvent {
+    timeout(Duration::from_secs(10), handle.events_rx.recv())
+        .await
+        .expect("no event from the ExEx")
+        .expect("ExEx dropped the events channel")
+}
+
+async fn connect(path: &Path) -> eyre::Result<Consumer> {
+    for _ in 0..100 {
+        if let Ok(stream) = UnixStream::connect(path).await {
+            return Ok(Framed::new(stream, LengthDelimitedCodec::new()))
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    eyre::bail!("ExEx socket didn't come up")
+}
+
+async fn request(consumer: &mut Consumer, request: SocketExExRequest) -> eyre::Result<()> {
+    consumer.send(Bytes::from(serde_json::to_vec(&request)?)).await?;
+    Ok(())
+}
+
+#[tokio::test]
+async fn reports_consumer_progress() -> eyre::Result<()> {
+    let (ctx, mut handle) = test_exex_context().await?;
+    let genesis = handle.genesis.num_hash();
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("exex.ipc");
+    let exex = tokio::spawn(SocketExEx::new(ctx, path.clone()).run());
+
+    // without consumers, the head of the node is reported
+    let (block1, chain1) = chain(1);
+    handle.send_notification_chain_committed(chain1).await?;
+    assert_eq!(next_event(&mut handle).await, ExExEvent::FinishedHeight(block1));
+
+    // a new consumer holds the WAL back to the head it subscribed with
+    let mut consumer = connect(&path).await?;
+    request(&mut consumer, SocketExExRequest::Subscribe { head: Some(genesis) }).await?;
+    assert_eq!(next_event(&mut handle).await, ExExEvent::FinishedHeight(genesis));
+
+    // live notifications are forwarded, and only reported once acked
+    let (block2, chain2) = chain(2);
+    handle.send_notification_chain_committed(chain2).await?;
+    let frame =
+        timeout(Duration::from_secs(10), consumer.next()).await?.expect("connection open")?;
+    let message: serde_json::Value = serde_json::from_slice(&frame)?;
+    assert!(message.get("notification").is_some(), "unexpected message {message}");
+    handle.assert_events_empty();
+
+    request(&mut consumer, SocketExExRequest::Ack { height: block2 }).await?;
+    assert_eq!(next_event(&mut handle).await, ExExEvent::FinishedHeight(block2));
+    let persisted: BlockNumHash =
+        serde_json::from_slice(&std::fs::read(dir.path().join("exex.ack"))?)?;
+    assert_eq!(persisted, block2);
+
+    // once the consumer is gone, the acked height is kept until a consumer acks again
+    drop(consumer);
+    let (_, chain3) = chain(3);
+    handle.send_notification_chain_committed(chain3).await?;
+
+    let mut consumer = connect(&path).await?;
+    request(&mut consumer, SocketExExRequest::Subscribe { head: Some(block2) }).await?;
+    let (block4, chain4) = chain(4);
+    handle.send_notification_chain_committed(chain4).await?;
+    timeout(Duration::from_secs(10), consumer.next()).await?.expect("connection open")?;
+    handle.assert_events_empty();
+
+    request(&mut consumer, SocketExExRequest::Ack { height: block4 }).await?;
+    assert_eq!(next_event(&mut handle).await, ExExEvent::FinishedHeight(block4));
+
+    assert!(!exex.is_finished());
+    exex.abort();
+    Ok(())
+}
@@ -214,6 +214,12 @@ pub trait Peers: PeersInfo {
         udp_addr: Option<SocketAddr>,
     );
 
+    /// Bans the peer until it's unbanned, disconnecting it if it's connected.
+    fn ban_peer(&self, peer: PeerId);
+
+    /// Lifts the ban of the peer, if any.
+    fn unban_peer(&self, peer: PeerId);
+
     /// Send a reputation change for the given peer.
     fn reputation_change(&self, peer_id: PeerId, kind: ReputationChangeKind);
 
@@ -162,6 +162,10 @@ where
     ) {
     }
 
+    fn ban_peer(&self, _peer: PeerId) {}
+
+    fn unban_peer(&self, _peer: PeerId) {}
+
     fn reputation_change(&self, _peer_id: PeerId, _kind: ReputationChangeKind) {}
 
     async fn reputation_by_id(&self, _peer_id: PeerId) -> Result<Option<Reputation>, NetworkError> {
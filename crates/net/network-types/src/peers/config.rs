@@ -176,6 +176,8 @@ pub struct PeersConfig {
     pub enforce_enr_fork_id: bool,
     /// The node ids of the proxied nodes.
     pub proxied_node_ids: Vec<PeerId>,
+    /// Nodes that are banned until they're removed from this list.
+    pub banned_nodes: Vec<PeerId>,
 }
 
 impl Default for PeersConfig {
@@ -198,6 +200,7 @@ impl Default for PeersConfig {
             ip_filter: IpFilter::default(),
             enforce_enr_fork_id: false,
             proxied_node_ids: Vec::new(),
+            banned_nodes: Vec::new(),
         }
     }
 }
@@ -733,6 +733,12 @@ impl<N: NetworkPrimitives> NetworkManager<N> {
             NetworkHandleMessage::RemovePeer(peer_id, kind) => {
                 self.swarm.state_mut().remove_peer_kind(peer_id, kind);
             }
+            NetworkHandleMessage::BanPeer(peer_id) => {
+                self.swarm.state_mut().peers_mut().ban_peer_by_id(peer_id);
+            }
+            NetworkHandleMessage::UnbanPeer(peer_id) => {
+                self.swarm.state_mut().peers_mut().unban_peer_by_id(peer_id);
+            }
             NetworkHandleMessage::DisconnectPeer(peer_id, reason) => {
                 self.swarm.sessions_mut().disconnect(peer_id, reason);
             }
@@ -107,7 +107,7 @@ impl PeersManager {
             refill_slots_interval,
             connection_info,
             reputation_weights,
-            ban_list,
+            mut ban_list,
             ban_duration,
             backoff_durations,
             trusted_nodes,
@@ -120,6 +120,7 @@ impl PeersManager {
             ip_filter,
             enforce_enr_fork_id,
             proxied_node_ids,
+            banned_nodes,
         } = config;
         let (manager_tx, handle_rx) = mpsc::unbounded_channel();
         let now = Instant::now();
@@ -179,6 +180,10 @@ impl PeersManager {
             }
         }
 
+        for peer_id in banned_nodes {
+            ban_list.ban_peer(peer_id);
+        }
+
         trace!(target: "net::peers", trusted_peers=?trusted_peer_ids, "Initialized peers manager");
 
         Self {
@@ -523,6 +528,30 @@ impl PeersManager {
         }
     }
 
+    /// Bans the peer until it's unbanned with [`Self::unban_peer_by_id`], disconnecting it if it's
+    /// connected.
+    pub(crate) fn ban_peer_by_id(&mut self, peer_id: PeerId) {
+        self.ban_list.ban_peer(peer_id);
+        self.queued_actions.push_back(PeerAction::BanPeer { peer_id });
+
+        if let Some(peer) = self.peers.get_mut(&peer_id) &&
+            peer.state.is_connected()
+        {
+            peer.state.disconnect();
+            self.queued_actions.push_back(PeerAction::Disconnect {
+                peer_id,
+                reason: Some(DisconnectReason::DisconnectRequested),
+            });
+        }
+    }
+
+    /// Lifts the ban of the peer, if any.
+    pub(crate) fn unban_peer_by_id(&mut self, peer_id: PeerId) {
+        if self.ban_list.is_banned_peer(&peer_id) {
+            self.unban_peer(peer_id);
+        }
+    }
+
     /// Unbans the peer
     fn unban_peer(&mut self, peer_id: PeerId) {
         self.ban_list.unban_peer(&peer_id);
@@ -1563,6 +1592,33 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_banned_nodes() {
+        let banned = PeerId::random();
+        let peer = PeerId::random();
+        let mut peers =
+            PeersManager::new(PeersConfig { banned_nodes: vec![banned], ..PeersConfig::test() });
+        assert!(peers.ban_list.is_banned_peer(&banned));
+
+        peers.ban_peer_by_id(peer);
+        assert!(peers.ban_list.is_banned_peer(&peer));
+        match event!(peers) {
+            PeerAction::BanPeer { peer_id } => {
+                assert_eq!(peer_id, peer);
+            }
+            _ => unreachable!(),
+        }
+
+        peers.unban_peer_by_id(banned);
+        assert!(!peers.ban_list.is_banned_peer(&banned));
+        match event!(peers) {
+            PeerAction::UnBanPeer { peer_id } => {
+                assert_eq!(peer_id, banned);
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[tokio::test]
     async fn test_backoff_on_busy() {
         let peer = PeerId::random();
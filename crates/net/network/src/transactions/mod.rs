@@ -65,8 +65,8 @@ use reth_primitives_traits::{InMemorySize, SignedTransaction};
 use reth_tokio_util::EventStream;
 use reth_transaction_pool::{
     error::{PoolError, PoolResult},
-    AddedTransactionOutcome, GetPooledTransactionLimit, PoolTransaction, PropagateKind,
-    PropagatedTransactions, TransactionPool, ValidPoolTransaction,
+    AddedTransactionOutcome, AdmissionSource, GetPooledTransactionLimit, PoolConsensusTx,
+    PoolTransaction, PropagateKind, PropagatedTransactions, TransactionPool, ValidPoolTransaction,
 };
 use std::{
     collections::{hash_map::Entry, HashMap, HashSet},
@@ -914,7 +914,7 @@ where
         // filter all transactions unknown to the peer
         let mut full_transactions = FullTransactionsBuilder::new(peer.version);
 
-        let to_propagate = self.pool.get_all(txs).into_iter().map(PropagateTransaction::pool_tx);
+        let to_propagate = announceable_transactions(&self.pool, txs);
 
         if propagation_mode.is_forced() {
             // skip cache check if forced
@@ -1000,8 +1000,7 @@ where
                 return None
             };
 
-            let to_propagate =
-                self.pool.get_all(hashes).into_iter().map(PropagateTransaction::pool_tx);
+            let to_propagate = announceable_transactions(&self.pool, hashes);
 
             let mut propagated = PropagatedTransactions::default();
 
@@ -1158,7 +1157,7 @@ where
             return
         }
         let propagated = self.propagate_transactions(
-            self.pool.get_all(hashes).into_iter().map(PropagateTransaction::pool_tx).collect(),
+            announceable_transactions(&self.pool, hashes),
             PropagationMode::Basic,
         );
 
@@ -1335,9 +1334,11 @@ where
         }
 
         // Get transactions to broadcast
-        let pooled_txs = self.pool.pooled_transactions_max(
+        let mut pooled_txs = self.pool.pooled_transactions_max(
             SOFT_LIMIT_COUNT_HASHES_IN_NEW_POOLED_TRANSACTIONS_BROADCAST_MESSAGE,
         );
+        // private transactions are never announced
+        pooled_txs.retain(|tx| !tx.origin.is_private());
         if pooled_txs.is_empty() {
             trace!(target: "net::tx", ?peer_id, "No transactions in the pool to broadcast");
             return;
@@ -1566,7 +1567,9 @@ where
             trace!(target: "net::tx::propagation", new_txs_len=?new_txs.len(), "Importing new transactions");
             let import = Box::pin(async move {
                 let added = new_txs.len();
-                let res = pool.add_external_transactions(new_txs).await;
+                let res = pool
+                    .add_external_transactions_from_source(AdmissionSource::Peer(peer_id), new_txs)
+                    .await;
 
                 // update metrics
                 metric_pending_pool_imports.decrement(added as f64);
@@ -1862,6 +1865,20 @@ impl<T: SignedTransaction> PropagateTransaction<T> {
     }
 }
 
+/// Returns the transactions of the pool with the given hashes that may be announced to peers.
+///
+/// Private transactions are never announced, even if the pool considers them propagatable.
+fn announceable_transactions<P: TransactionPool>(
+    pool: &P,
+    hashes: Vec<TxHash>,
+) -> Vec<PropagateTransaction<PoolConsensusTx<P>>> {
+    pool.get_all(hashes)
+        .into_iter()
+        .filter(|tx| !tx.origin.is_private())
+        .map(PropagateTransaction::pool_tx)
+        .collect()
+}
+
 fn transaction_hashes_to_reannounce<T: PoolTransaction>(
     pending: impl IntoIterator<Item = Arc<ValidPoolTransaction<T>>>,
     now: Instant,
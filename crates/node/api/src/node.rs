@@ -5,10 +5,10 @@ use alloy_rpc_types_engine::JwtSecret;
 use reth_basic_payload_builder::PayloadBuilder;
 use reth_consensus::FullConsensus;
 use reth_db_api::{database_metrics::DatabaseMetrics, Database};
-use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle};
+use reth_engine_primitives::{ConsensusEngineEvent, ConsensusEngineHandle, PrecompileCacheHandle};
 use reth_evm::ConfigureEvm;
 use reth_network_api::FullNetwork;
-use reth_node_core::node_config::NodeConfig;
+use reth_node_core::{node_config::NodeConfig, reload::ConfigReloadHandle};
 use reth_node_types::{NodeTypes, NodeTypesWithDBAdapter, TxTy};
 use reth_payload_builder::PayloadBuilderHandle;
 use reth_provider::FullProvider;
@@ -103,7 +103,10 @@ pub trait FullNodeComponents: FullNodeTypes + Clone + 'static {
 }
 
 /// Context passed to [`NodeAddOns::launch_add_ons`],
+///
+/// Fields may be added over time, so it's created with [`AddOnsContext::new`].
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct AddOnsContext<'a, N: FullNodeComponents> {
     /// Node with all configured components.
     pub node: N,
@@ -115,6 +118,44 @@ pub struct AddOnsContext<'a, N: FullNodeComponents> {
     pub engine_events: EventSender<ConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
     /// JWT secret for the node.
     pub jwt_secret: JwtSecret,
+    /// Handle to reload the configuration file of the node.
+    pub config_reload: ConfigReloadHandle,
+    /// Handle to inspect and clear the precompile caches of the engine.
+    pub precompile_cache: PrecompileCacheHandle,
+}
+
+impl<'a, N: FullNodeComponents> AddOnsContext<'a, N> {
+    /// Creates a new context. The configuration can't be reloaded unless a handle is set with
+    /// [`Self::with_config_reload`].
+    pub fn new(
+        node: N,
+        config: &'a NodeConfig<<N::Types as NodeTypes>::ChainSpec>,
+        beacon_engine_handle: ConsensusEngineHandle<<N::Types as NodeTypes>::Payload>,
+        engine_events: EventSender<ConsensusEngineEvent<<N::Types as NodeTypes>::Primitives>>,
+        jwt_secret: JwtSecret,
+    ) -> Self {
+        Self {
+            node,
+            config,
+            beacon_engine_handle,
+            engine_events,
+            jwt_secret,
+            config_reload: Default::default(),
+            precompile_cache: Default::default(),
+        }
+    }
+
+    /// Sets the handle to reload the configuration file of the node.
+    pub fn with_config_reload(mut self, config_reload: ConfigReloadHandle) -> Self {
+        self.config_reload = config_reload;
+        self
+    }
+
+    /// Sets the handle that engine validators register their precompile caches with.
+    pub fn with_precompile_cache(mut self, precompile_cache: PrecompileCacheHandle) -> Self {
+        self.precompile_cache = precompile_cache;
+        self
+    }
 }
 
 /// Customizable node add-on types.
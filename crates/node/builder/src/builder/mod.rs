@@ -6,6 +6,7 @@
 use crate::{
     common::WithConfigs,
     components::NodeComponentsBuilder,
+    launch::config_reload::apply_txpool_config,
     node::FullNode,
     rpc::{RethRpcAddOns, RethRpcServerHandles, RpcContext},
     BlockReaderFor, DebugNode, DebugNodeLauncher, EngineNodeLauncher, LaunchNode, Node,
@@ -807,8 +808,13 @@ impl<Node: FullNodeTypes> BuilderContext<Node> {
     }
 
     /// Returns the transaction pool config of the node.
+    ///
+    /// The limits configured in the `[txpool]` section of the config file take precedence over the
+    /// command line arguments.
     pub fn pool_config(&self) -> PoolConfig {
-        self.config().txpool.pool_config()
+        let pool_config = self.config().txpool.pool_config();
+        let limits = apply_txpool_config(pool_config.limits(), &self.reth_config().txpool);
+        pool_config.with_limits(limits)
     }
 
     /// Loads `EnvKzgSettings::Default`.
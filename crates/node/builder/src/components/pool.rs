@@ -5,12 +5,14 @@ use alloy_primitives::map::AddressSet;
 use reth_chain_state::CanonStateSubscriptions;
 use reth_chainspec::EthereumHardforks;
 use reth_node_api::{BlockTy, NodeTypes, TxTy};
+use reth_node_core::cli::config::RethTransactionPoolConfig;
 use reth_transaction_pool::{
-    blobstore::DiskFileBlobStore, BlobStore, CoinbaseTipOrdering, PoolConfig, PoolTransaction,
+    blobstore::DiskFileBlobStore, BlobStore, ConfiguredOrdering, PoolConfig, PoolTransaction,
     SubPoolLimit, TransactionOrdering, TransactionPool, TransactionValidationTaskExecutor,
     TransactionValidator,
 };
 use std::future::Future;
+use tracing::{info, warn};
 
 /// A type that knows how to build the transaction pool.
 pub trait PoolBuilder<Node: FullNodeTypes, Evm>: Send {
@@ -137,23 +139,23 @@ where
         PoolTransaction<Consensus = TxTy<Node::Types>> + reth_transaction_pool::EthPoolTransaction,
 {
     /// Consume the type and build the [`reth_transaction_pool::Pool`] with the given config and
-    /// blob store.
+    /// blob store, ordered by the [`ConfiguredOrdering`] of the node's txpool arguments.
     pub fn build<BS>(
         self,
         blob_store: BS,
         pool_config: PoolConfig,
     ) -> reth_transaction_pool::Pool<
         TransactionValidationTaskExecutor<V>,
-        CoinbaseTipOrdering<V::Transaction>,
+        ConfiguredOrdering<V::Transaction>,
         BS,
     >
     where
         BS: BlobStore,
     {
-        let TxPoolBuilder { validator, .. } = self;
+        let TxPoolBuilder { ctx, validator } = self;
         reth_transaction_pool::Pool::new(
             validator,
-            CoinbaseTipOrdering::default(),
+            ConfiguredOrdering::new(&ctx.config().txpool.ordering_config()),
             blob_store,
             pool_config,
         )
@@ -168,18 +170,15 @@ where
     ) -> eyre::Result<
         reth_transaction_pool::Pool<
             TransactionValidationTaskExecutor<V>,
-            CoinbaseTipOrdering<V::Transaction>,
+            ConfiguredOrdering<V::Transaction>,
             BS,
         >,
     >
     where
         BS: BlobStore,
     {
-        self.build_with_ordering_and_spawn_maintenance_task(
-            CoinbaseTipOrdering::default(),
-            blob_store,
-            pool_config,
-        )
+        let ordering = ConfiguredOrdering::new(&self.ctx.config().txpool.ordering_config());
+        self.build_with_ordering_and_spawn_maintenance_task(ordering, blob_store, pool_config)
     }
 
     /// Build the transaction pool with a custom [`TransactionOrdering`] and spawn its maintenance
@@ -264,6 +263,25 @@ where
     Ok(())
 }
 
+/// Spawn the task that records the pool events if enabled.
+fn spawn_pool_recorder_task<Node, Pool>(ctx: &BuilderContext<Node>, pool: Pool) -> eyre::Result<()>
+where
+    Node: FullNodeTypes,
+    Pool: TransactionPool + Clone + 'static,
+{
+    let Some(path) = ctx.config().txpool.record_path.clone() else { return Ok(()) };
+    let file = std::io::BufWriter::new(reth_fs_util::create_file(&path)?);
+    info!(target: "reth::cli", path = %path.display(), "Recording transaction pool events");
+
+    ctx.task_executor().spawn_with_graceful_shutdown_signal(|shutdown| async move {
+        let recorder = reth_transaction_pool::replay::PoolEventRecorder::new(file);
+        if let Err(err) = recorder.record_with_graceful_shutdown(pool, shutdown).await {
+            warn!(target: "reth::cli", %err, "Failed to record transaction pool events");
+        }
+    });
+    Ok(())
+}
+
 /// Spawn the main maintenance task for transaction pool.
 fn spawn_pool_maintenance_task<Node, Pool>(
     ctx: &BuilderContext<Node>,
@@ -296,7 +314,7 @@ where
     Ok(())
 }
 
-/// Spawn all maintenance tasks for a transaction pool (backup + main maintenance).
+/// Spawn all maintenance tasks for a transaction pool (backup, recorder + main maintenance).
 pub fn spawn_maintenance_tasks<Node, Pool>(
     ctx: &BuilderContext<Node>,
     pool: Pool,
@@ -308,6 +326,7 @@ where
     Pool::Transaction: PoolTransaction<Consensus = TxTy<Node::Types>>,
 {
     spawn_local_backup_task(ctx, pool.clone())?;
+    spawn_pool_recorder_task(ctx, pool.clone())?;
     spawn_pool_maintenance_task(ctx, pool, pool_config)?;
     Ok(())
 }
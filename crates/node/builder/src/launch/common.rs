@@ -66,7 +66,9 @@ use reth_node_metrics::{
     version::VersionInfo,
 };
 use reth_provider::{
-    providers::{NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider},
+    providers::{
+        Era1History, NodeTypesForProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider,
+    },
     BlockExecutionWriter, BlockHashReader, BlockNumReader, DBProvider, DatabaseProviderFactory,
     HeaderProvider, ProviderError, ProviderFactory, ProviderResult, RocksDBProviderFactory,
     StageCheckpointReader, StaticFileProviderBuilder, StaticFileProviderFactory, StorageSettings,
@@ -533,14 +535,7 @@ impl<R, ChainSpec: EthChainSpec> LaunchContextWith<Attached<WithConfigs<ChainSpe
     where
         ChainSpec: reth_chainspec::EthereumHardforks,
     {
-        let Some(mut node_prune_config) = self.node_config().prune_config() else {
-            // No CLI config is set, use the toml config.
-            return self.toml_config().prune.clone();
-        };
-
-        // Otherwise, use the CLI configuration and merge with toml config.
-        node_prune_config.merge(self.toml_config().prune.clone());
-        node_prune_config
+        merge_prune_config(self.node_config().prune_config(), self.toml_config().prune.clone())
     }
 
     /// Returns the configured [`PruneModes`], returning the default if no config was available.
@@ -599,12 +594,19 @@ where
         static_files_config.validate()?;
 
         // Apply per-segment blocks_per_file configuration
-        let static_file_provider =
+        let mut static_file_provider =
             StaticFileProviderBuilder::read_write(self.data_dir().static_files())
                 .with_metrics()
                 .with_blocks_per_file_for_segments(&static_files_config.as_blocks_per_file_map())
-                .with_genesis_block_number(self.chain_spec().genesis().number.unwrap_or_default())
-                .build()?;
+                .with_genesis_block_number(self.chain_spec().genesis().number.unwrap_or_default());
+
+        // Serve expired block history from era1 files, if configured
+        if let Some(path) = &self.node_config().era.history_path {
+            let era1_history = Era1History::new(path)?;
+            info!(target: "reth::cli", ?path, blocks = ?era1_history.block_range(), "Serving expired block history from era1 files");
+            static_file_provider = static_file_provider.with_era1_history(era1_history);
+        }
+        let static_file_provider = static_file_provider.build()?;
 
         // Use the provided RocksDB provider or create a new one
         let rocksdb_provider = if let Some(provider) = rocksdb_provider {
@@ -1265,11 +1267,35 @@ where
             }
         }
 
+        if self.balance_changes_index_missing()? {
+            return self.blockchain_db().block_hash(first_stage_checkpoint);
+        }
+
         self.ensure_chain_specific_db_checks()?;
 
         Ok(None)
     }
 
+    /// Returns `true` if the balance changes index is enabled but has never been built.
+    ///
+    /// Persisted blocks are only indexed once the stage has a checkpoint, so the pipeline has to
+    /// backfill the index once, even if all other stages are consistent.
+    fn balance_changes_index_missing(&self) -> ProviderResult<bool> {
+        if !self.toml_config().stages.index_balance_changes.enabled {
+            return Ok(false);
+        }
+
+        let missing =
+            self.blockchain_db().get_stage_checkpoint(StageId::IndexBalanceChanges)?.is_none();
+        if missing {
+            info!(
+                target: "consensus::engine",
+                "Balance changes index is enabled but missing; backfilling it through the pipeline",
+            );
+        }
+        Ok(missing)
+    }
+
     /// Check if the pipeline is consistent under `TrieDB`.
     ///
     /// Precondition: [`Self::align_mdbx_to_triedb_at_startup`] has already run, so
@@ -1321,6 +1347,10 @@ where
             }
         }
 
+        if self.balance_changes_index_missing()? {
+            return self.blockchain_db().block_hash(first_stage_checkpoint);
+        }
+
         info!(
             target: "consensus::engine",
             "Pipeline sync progress is consistent and backends are aligned; starting live sync",
@@ -1616,6 +1646,19 @@ pub fn metrics_hooks<N: NodeTypesWithDB>(provider_factory: &ProviderFactory<N>)
     builder.build()
 }
 
+/// Merges the prune configuration set on the command line with the `[prune]` section of the
+/// configuration file, with the command line taking precedence.
+pub(crate) fn merge_prune_config(cli: Option<PruneConfig>, toml: PruneConfig) -> PruneConfig {
+    let Some(mut cli) = cli else {
+        // No CLI config is set, use the toml config.
+        return toml;
+    };
+
+    // Otherwise, use the CLI configuration and merge with toml config.
+    cli.merge(toml);
+    cli
+}
+
 #[cfg(test)]
 mod tests {
     use super::{LaunchContext, NodeConfig};
@@ -0,0 +1,276 @@
+//! Reloading of the configuration file of a running node.
+
+use crate::launch::common::merge_prune_config;
+use futures::{FutureExt, StreamExt};
+use jsonrpsee::core::RpcResult;
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use reth_config::{Config, ConfigChanges, PruneConfig, TxPoolConfig};
+use reth_network_api::{PeerKind, Peers};
+use reth_node_core::{
+    reload::{ConfigReloadHandle, ConfigReloadRequest},
+    rpc::result::internal_rpc_err,
+};
+use reth_prune::PruneModes;
+use reth_rpc_api::AdminReloadApiServer;
+use reth_tracing::tracing::{debug, info, warn};
+use reth_transaction_pool::{PoolLimits, SubPoolLimit, TransactionPool};
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::sync::{mpsc, watch};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+
+/// How long to wait for further changes of the configuration file before reloading it, so that a
+/// file written in several steps is only reloaded once.
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Applies the reloadable settings of the `[txpool]` section on top of the given limits.
+pub(crate) fn apply_txpool_config(mut limits: PoolLimits, config: &TxPoolConfig) -> PoolLimits {
+    let TxPoolConfig {
+        pending_max_count,
+        pending_max_size,
+        basefee_max_count,
+        basefee_max_size,
+        queued_max_count,
+        queued_max_size,
+        blobpool_max_count,
+        blobpool_max_size,
+        max_account_slots,
+    } = *config;
+
+    let apply = |limit: &mut SubPoolLimit, max_count: Option<usize>, max_size: Option<usize>| {
+        if let Some(max_count) = max_count {
+            limit.max_txs = max_count;
+        }
+        if let Some(max_size) = max_size {
+            limit.max_size = max_size.saturating_mul(1024 * 1024);
+        }
+    };
+    apply(&mut limits.pending_limit, pending_max_count, pending_max_size);
+    apply(&mut limits.basefee_limit, basefee_max_count, basefee_max_size);
+    apply(&mut limits.queued_limit, queued_max_count, queued_max_size);
+    apply(&mut limits.blob_limit, blobpool_max_count, blobpool_max_size);
+    if let Some(max_account_slots) = max_account_slots {
+        limits.max_account_slots = max_account_slots;
+    }
+
+    limits
+}
+
+/// Reloads the configuration file whenever it changes or a reload is requested through a
+/// [`ConfigReloadHandle`], and applies the changed settings to the running node.
+#[derive(Debug)]
+pub(crate) struct ConfigReloader<Network, Pool> {
+    /// Path of the configuration file.
+    path: PathBuf,
+    /// The configuration that was applied last.
+    config: Config,
+    /// The pool limits configured on the command line that the `[txpool]` section overrides.
+    cli_pool_limits: PoolLimits,
+    /// The prune configuration set on the command line, which overrides the `[prune]` section.
+    cli_prune_config: Option<PruneConfig>,
+    /// Sends the prune modes to the pruner.
+    prune_modes: watch::Sender<PruneModes>,
+    network: Network,
+    pool: Pool,
+}
+
+impl<Network, Pool> ConfigReloader<Network, Pool>
+where
+    Network: Peers + 'static,
+    Pool: TransactionPool + 'static,
+{
+    /// Creates a new reloader for the configuration file at the given path.
+    ///
+    /// The configuration file is read again, because the configuration the node was launched with
+    /// includes the command line arguments.
+    pub(crate) fn new(
+        path: PathBuf,
+        cli_pool_limits: PoolLimits,
+        cli_prune_config: Option<PruneConfig>,
+        prune_modes: watch::Sender<PruneModes>,
+        network: Network,
+        pool: Pool,
+    ) -> eyre::Result<Self> {
+        let config = Config::from_path(&path)?;
+        Ok(Self { path, config, cli_pool_limits, cli_prune_config, prune_modes, network, pool })
+    }
+
+    /// Reloads the configuration file on every change and request.
+    pub(crate) async fn run(mut self, requests: mpsc::UnboundedReceiver<ConfigReloadRequest>) {
+        let (file_events_tx, file_events_rx) = mpsc::unbounded_channel();
+        let path = self.path.clone();
+        // Editors usually replace the file instead of writing to it, so the parent directory is
+        // watched instead.
+        let watcher = RecommendedWatcher::new(
+            move |res: notify::Result<notify::Event>| {
+                if res.is_ok_and(|event| {
+                    (event.kind.is_modify() || event.kind.is_create()) &&
+                        event.paths.iter().any(|p| p.file_name() == path.file_name())
+                }) {
+                    let _ = file_events_tx.send(());
+                }
+            },
+            notify::Config::default(),
+        )
+        .and_then(|mut watcher| {
+            let dir = self.path.parent().filter(|dir| !dir.as_os_str().is_empty());
+            let dir = dir.unwrap_or_else(|| Path::new("."));
+            watcher.watch(dir, RecursiveMode::NonRecursive).map(|_| watcher)
+        });
+        let (_watcher, mut file_events) = match watcher {
+            Ok(watcher) => (Some(watcher), UnboundedReceiverStream::new(file_events_rx).boxed()),
+            Err(err) => {
+                warn!(target: "reth::cli", %err, path = ?self.path, "Failed to watch configuration file, reload it with admin_reloadConfig instead");
+                (None, futures::stream::pending().boxed())
+            }
+        };
+
+        // Keep watching the file if all handles are dropped.
+        let mut requests = UnboundedReceiverStream::new(requests).chain(futures::stream::pending());
+        loop {
+            tokio::select! {
+                Some(request) = requests.next() => {
+                    let result = self.reload().await;
+                    if let Err(err) = &result {
+                        warn!(target: "reth::cli", %err, "Failed to reload configuration");
+                    }
+                    let _ = request.send(result);
+                }
+                Some(()) = file_events.next() => {
+                    tokio::time::sleep(DEBOUNCE_INTERVAL).await;
+                    while file_events.next().now_or_never().flatten().is_some() {}
+
+                    if let Err(err) = self.reload().await {
+                        warn!(target: "reth::cli", %err, path = ?self.path, "Failed to reload changed configuration file");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Reads the configuration file and applies all changes, or none if any of them can't be
+    /// applied to the running node.
+    async fn reload(&mut self) -> eyre::Result<ConfigChanges> {
+        let config = Config::from_path(&self.path)?;
+        let changes = self.config.reloadable_changes(&config)?;
+        if changes.is_empty() {
+            debug!(target: "reth::cli", path = ?self.path, "Configuration unchanged");
+            return Ok(changes)
+        }
+
+        // Resolve the added trusted peers first, so that nothing is applied if one can't be
+        // resolved.
+        let mut added_trusted_nodes = Vec::with_capacity(changes.added_trusted_nodes.len());
+        for peer in &changes.added_trusted_nodes {
+            added_trusted_nodes.push(peer.resolve().await?);
+        }
+
+        if changes.prune_modes.is_some() {
+            let prune_config =
+                merge_prune_config(self.cli_prune_config.clone(), config.prune.clone());
+            self.prune_modes.send_replace(prune_config.segments);
+        }
+        if let Some(txpool) = &changes.txpool {
+            self.pool.set_limits(apply_txpool_config(self.cli_pool_limits, txpool));
+        }
+        for record in added_trusted_nodes {
+            self.network.add_trusted_peer_with_udp(record.id, record.tcp_addr(), record.udp_addr());
+        }
+        for peer in &changes.removed_trusted_nodes {
+            self.network.remove_peer(peer.id, PeerKind::Trusted);
+        }
+        for peer_id in &changes.banned_nodes {
+            self.network.ban_peer(*peer_id);
+        }
+        for peer_id in &changes.unbanned_nodes {
+            self.network.unban_peer(*peer_id);
+        }
+
+        for change in &changes.diff {
+            info!(target: "reth::cli", %change, "Configuration reloaded");
+        }
+        self.config = config;
+
+        Ok(changes)
+    }
+}
+
+/// `admin_reloadConfig` implementation that reloads the configuration through a
+/// [`ConfigReloadHandle`].
+#[derive(Debug, Clone)]
+pub(crate) struct AdminReloadApi {
+    handle: ConfigReloadHandle,
+}
+
+impl AdminReloadApi {
+    /// Creates a new instance with the given handle.
+    pub(crate) const fn new(handle: ConfigReloadHandle) -> Self {
+        Self { handle }
+    }
+}
+
+#[async_trait::async_trait]
+impl AdminReloadApiServer for AdminReloadApi {
+    async fn reload_config(&self) -> RpcResult<Vec<String>> {
+        self.handle
+            .reload()
+            .await
+            .map(|changes| changes.diff)
+            .map_err(|err| internal_rpc_err(err.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn txpool_config_overrides_limits() {
+        let limits = PoolLimits {
+            pending_limit: SubPoolLimit::new(10, 1024 * 1024),
+            basefee_limit: SubPoolLimit::new(10, 1024 * 1024),
+            queued_limit: SubPoolLimit::new(10, 1024 * 1024),
+            blob_limit: SubPoolLimit::new(10, 1024 * 1024),
+            max_account_slots: 16,
+        };
+        assert_eq!(apply_txpool_config(limits, &TxPoolConfig::default()), limits);
+
+        let config = TxPoolConfig {
+            pending_max_count: Some(20),
+            queued_max_size: Some(2),
+            max_account_slots: Some(32),
+            ..Default::default()
+        };
+        let expected = PoolLimits {
+            pending_limit: SubPoolLimit::new(20, 1024 * 1024),
+            queued_limit: SubPoolLimit::new(10, 2 * 1024 * 1024),
+            max_account_slots: 32,
+            ..limits
+        };
+        assert_eq!(apply_txpool_config(limits, &config), expected);
+    }
+
+    #[test]
+    fn cli_prune_config_overrides_reloaded_segments() {
+        let distance = |distance| Some(reth_prune::PruneMode::Distance(distance));
+        let cli = PruneConfig {
+            segments: PruneModes { account_history: distance(20_000), ..Default::default() },
+            ..Default::default()
+        };
+        let toml = PruneConfig {
+            segments: PruneModes {
+                account_history: distance(50_000),
+                storage_history: distance(50_000),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let segments = merge_prune_config(Some(cli), toml.clone()).segments;
+        assert_eq!(segments.account_history, distance(20_000));
+        assert_eq!(segments.storage_history, distance(50_000));
+        assert_eq!(merge_prune_config(None, toml.clone()).segments, toml.segments);
+    }
+}
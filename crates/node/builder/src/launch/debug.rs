@@ -8,16 +8,20 @@ use reth_consensus_debug_client::{
     BlockProvider, DebugConsensusClient, EtherscanBlockProvider, RpcBlockProvider,
 };
 use reth_engine_local::{LocalMiner, MiningMode};
+use reth_engine_util::replay::EngineApiReplay;
 use reth_node_api::{
     BlockTy, FullNodeComponents, FullNodeTypes, HeaderTy, PayloadAttrTy, PayloadAttributesBuilder,
     PayloadTypes,
 };
+use reth_node_core::exit::NodeExitFuture;
 use std::{
     future::{Future, IntoFuture},
+    mem,
     pin::Pin,
     sync::Arc,
 };
-use tracing::info;
+use tokio::sync::oneshot;
+use tracing::{error, info};
 
 /// [`Node`] extension with support for debugging utilities.
 ///
@@ -82,7 +86,7 @@ pub trait DebugNode<N: FullNodeComponents>: Node<N> {
 /// Node launcher with support for launching various debugging utilities.
 ///
 /// This launcher wraps an existing launcher and adds debugging capabilities when
-/// certain debug flags are enabled. It provides two main debugging features:
+/// certain debug flags are enabled. It provides the following debugging features:
 ///
 /// ## RPC Consensus Client
 ///
@@ -100,6 +104,14 @@ pub trait DebugNode<N: FullNodeComponents>: Node<N> {
 /// - Submit them to the local engine
 /// - Requires `ETHERSCAN_API_KEY` environment variable
 /// - Falls back to default Etherscan URL for the chain if URL not provided
+///
+/// ## Engine API Replay
+///
+/// When `--debug.engine-api-replay <PATH>` is provided, the launcher will:
+/// - Read the engine API messages stored with `--debug.engine-api-store`
+/// - Submit them to the local engine in the order they were received
+/// - Log the latency of each message and every response that differs from the recorded one
+/// - Exit the node once the last message was answered, with an error if any response differed
 #[derive(Debug, Clone)]
 pub struct DebugNodeLauncher<L = EngineNodeLauncher> {
     inner: L,
@@ -212,7 +224,7 @@ where
             mining_mode,
         } = self;
 
-        let handle = inner.launch_node(target).await?;
+        let mut handle = inner.launch_node(target).await?;
 
         let config = &handle.node.config;
 
@@ -284,6 +296,39 @@ where
                 });
         }
 
+        let mut replay_finished = None;
+        if let Some(path) = config.debug.engine_api_replay.clone() {
+            info!(target: "reth::cli", ?path, "Replaying stored engine API messages");
+
+            let replay =
+                EngineApiReplay::new(path, handle.node.add_ons_handle.beacon_engine_handle.clone());
+            let (tx, rx) = oneshot::channel();
+            handle.node.task_executor.spawn_task(async move {
+                let result = replay.run().await.and_then(|report| {
+                    info!(
+                        target: "reth::cli",
+                        messages = report.messages.len(),
+                        divergences = report.divergences.len(),
+                        total_latency = ?report.total_latency(),
+                        max_latency = ?report.max_latency(),
+                        "Engine API replay finished"
+                    );
+                    eyre::ensure!(
+                        report.is_consistent(),
+                        "{} of {} replayed engine API responses differ from the recorded ones",
+                        report.divergences.len(),
+                        report.messages.len()
+                    );
+                    Ok(())
+                });
+                if let Err(err) = &result {
+                    error!(target: "reth::cli", %err, "Engine API replay failed");
+                }
+                let _ = tx.send(result);
+            });
+            replay_finished = Some(rx);
+        }
+
         if config.dev.dev {
             info!(target: "reth::cli", "Using local payload attributes builder for dev mode");
 
@@ -329,6 +374,18 @@ where
             });
         }
 
+        if let Some(replay_finished) = replay_finished {
+            // the node exits once all stored messages were replayed
+            let node_exit_future =
+                mem::replace(&mut handle.node_exit_future, NodeExitFuture::new(async { Ok(()) }));
+            handle.node_exit_future = NodeExitFuture::new(async move {
+                tokio::select! {
+                    res = node_exit_future => res,
+                    res = replay_finished => res?,
+                }
+            });
+        }
+
         Ok(handle)
     }
 }
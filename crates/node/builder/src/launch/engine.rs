@@ -3,6 +3,7 @@
 use crate::{
     common::{Attached, LaunchContextWith, WithConfigs},
     hooks::NodeHooks,
+    launch::config_reload::ConfigReloader,
     rpc::{EngineShutdown, EngineValidatorAddOn, EngineValidatorBuilder, RethRpcAddOns, RpcHandle},
     setup::build_networked_pipeline,
     AddOns, AddOnsContext, FullNode, LaunchContext, LaunchNode, NodeAdapter,
@@ -23,24 +24,28 @@ use reth_network::{types::BlockRangeUpdate, NetworkSyncUpdater, SyncState};
 use reth_network_api::BlockDownloaderProvider;
 use reth_node_api::{
     BuiltPayload, ConsensusEngineHandle, FullNodeTypes, NodeTypes, NodeTypesWithDBAdapter,
+    PrecompileCacheHandle,
 };
 use reth_node_core::{
+    cli::config::RethTransactionPoolConfig,
     dirs::{ChainPath, DataDirPath},
     exit::NodeExitFuture,
     primitives::Head,
+    reload::ConfigReloadHandle,
 };
 use reth_node_events::node;
 use reth_provider::{
     providers::{BlockchainProvider, NodeTypesForProvider},
     BlockNumReader, HeaderProvider, StorageSettingsCache,
 };
+use reth_static_file::StaticFileRecompressor;
 use reth_tasks::TaskExecutor;
 use reth_tokio_util::EventSender;
 use reth_tracing::tracing::{debug, error, info, warn};
 use reth_trie_db::ChangesetCache;
 use rust_eth_triedb::triedb_manager::is_triedb_active;
 use std::{future::Future, pin::Pin, sync::Arc};
-use tokio::sync::{mpsc::unbounded_channel, oneshot};
+use tokio::sync::{mpsc::unbounded_channel, oneshot, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 /// The engine node launcher.
@@ -126,7 +131,7 @@ impl EngineNodeLauncher {
             })?
             .with_components(components_builder, on_component_initialized).await?;
 
-        let engine_tree_config = if is_triedb_active() &&
+        let mut engine_tree_config = if is_triedb_active() &&
             engine_tree_config.memory_block_buffer_target() < 256
         {
             info!(target: "reth::cli", "TrieDB is active, setting memory block buffer target to 256, old target={}", engine_tree_config.memory_block_buffer_target());
@@ -135,6 +140,20 @@ impl EngineNodeLauncher {
             engine_tree_config.clone()
         };
 
+        // TrieDB computes state roots without the sparse trie, so there is nothing to record or
+        // cross-check.
+        if is_triedb_active() {
+            if engine_tree_config.sparse_trie_mismatch_recorder_blocks() > 0 {
+                warn!(target: "reth::cli", "TrieDB is active, ignoring --engine.sparse-trie-mismatch-recorder-blocks");
+                engine_tree_config =
+                    engine_tree_config.with_sparse_trie_mismatch_recorder_blocks(0);
+            }
+            if engine_tree_config.state_root_cross_check_interval() > 0 {
+                warn!(target: "reth::cli", "TrieDB is active, ignoring --engine.state-root-cross-check-interval");
+                engine_tree_config = engine_tree_config.with_state_root_cross_check_interval(0);
+            }
+        }
+
         // Try to expire pre-merge transaction history if configured
         ctx.expire_pre_merge_transactions()?;
         // spawn exexs if any
@@ -178,7 +197,8 @@ impl EngineNodeLauncher {
 
         let pipeline_events = pipeline.events();
 
-        let mut pruner_builder = ctx.pruner_builder();
+        let (prune_modes_tx, prune_modes_rx) = watch::channel(ctx.prune_modes());
+        let mut pruner_builder = ctx.pruner_builder().prune_modes_updates(prune_modes_rx);
         if let Some(exex_manager_handle) = &maybe_exex_manager_handle {
             pruner_builder =
                 pruner_builder.finished_exex_height(exex_manager_handle.finished_height());
@@ -187,6 +207,46 @@ impl EngineNodeLauncher {
         let pruner_events = pruner.events();
         info!(target: "reth::cli", prune_config=?ctx.prune_config(), "Pruner initialized");
 
+        // Apply changes of the config file to the running node
+        let (config_reload, config_reload_requests) = ConfigReloadHandle::channel();
+        let config_path = node_config.config.clone().unwrap_or_else(|| ctx.data_dir().config());
+        let config_reloader = ConfigReloader::new(
+            config_path,
+            node_config.txpool.pool_config().limits(),
+            node_config.prune_config(),
+            prune_modes_tx,
+            network_handle.clone(),
+            ctx.components().pool().clone(),
+        )?;
+        ctx.task_executor().spawn_task(config_reloader.run(config_reload_requests));
+
+        let recompression = node_config.static_file_recompression;
+        if recompression.recompress {
+            let recompressor = StaticFileRecompressor::new(ctx.provider_factory().clone());
+            let executor = ctx.task_executor().clone();
+            ctx.task_executor().spawn_task(async move {
+                let mut interval = tokio::time::interval(recompression.recompress_interval);
+                loop {
+                    interval.tick().await;
+                    let recompressor = recompressor.clone();
+                    match executor.spawn_blocking(move || recompressor.run()).await {
+                        Ok(Ok(report)) => {
+                            debug!(target: "reth::cli", saved = report.saved(), "Static file recompression finished")
+                        }
+                        Ok(Err(err)) => {
+                            warn!(target: "reth::cli", %err, "Failed to recompress static files")
+                        }
+                        // Recompression resumes with the next static file on the next tick, an
+                        // interrupted one is cleaned up on startup.
+                        Err(err) => {
+                            error!(target: "reth::cli", %err, "Static file recompression panicked")
+                        }
+                    }
+                }
+            });
+            info!(target: "reth::cli", interval = ?recompression.recompress_interval, "Static file recompression enabled");
+        }
+
         let event_sender = EventSender::default();
 
         let beacon_engine_handle = ConsensusEngineHandle::new(consensus_engine_tx.clone());
@@ -204,13 +264,16 @@ impl EngineNodeLauncher {
         // extract the jwt secret from the args if possible
         let jwt_secret = ctx.auth_jwt_secret()?;
 
-        let add_ons_ctx = AddOnsContext {
-            node: ctx.node_adapter().clone(),
-            config: ctx.node_config(),
-            beacon_engine_handle: beacon_engine_handle.clone(),
+        let add_ons_ctx = AddOnsContext::new(
+            ctx.node_adapter().clone(),
+            ctx.node_config(),
+            beacon_engine_handle.clone(),
+            event_sender.clone(),
             jwt_secret,
-            engine_events: event_sender.clone(),
-        };
+        )
+        .with_config_reload(config_reload)
+        // Shared by the engine validators, which register their caches, and the admin API
+        .with_precompile_cache(PrecompileCacheHandle::default());
         let validator_builder = add_ons.engine_validator_builder();
 
         // Build the engine validator with all required components
@@ -1,21 +1,36 @@
 //! Support for launching execution extensions.
 
+use alloy_consensus::BlockHeader;
 use alloy_eips::{eip2124::Head, BlockNumHash};
+use alloy_primitives::{map::B256Set, BlockNumber};
 use futures::future;
 use reth_chain_state::ForkChoiceSubscriptions;
 use reth_chainspec::EthChainSpec;
 use reth_exex::{
-    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotificationSource, Wal,
-    DEFAULT_EXEX_MANAGER_CAPACITY, DEFAULT_WAL_BLOCKS_WARNING,
+    ExExContext, ExExHandle, ExExManager, ExExManagerHandle, ExExNotification,
+    ExExNotificationSource, Wal, WalHandle, DEFAULT_EXEX_MANAGER_CAPACITY,
+    DEFAULT_WAL_BLOCKS_WARNING,
 };
-use reth_node_api::{FullNodeComponents, NodeTypes, PrimitivesTy};
-use reth_provider::CanonStateSubscriptions;
-use reth_tracing::tracing::{debug, info};
-use std::{fmt, fmt::Debug};
+use reth_node_api::{FullNodeComponents, NodePrimitives, NodeTypes, PrimitivesTy};
+use reth_provider::{
+    BlockReader, CanonStateSubscriptions, Chain, ExecutionOutcome, ProviderError, ProviderResult,
+    StateReader, TransactionVariant,
+};
+use reth_tracing::tracing::{debug, error, info, warn};
+use std::{collections::BTreeMap, fmt, fmt::Debug, ops::RangeInclusive, sync::Arc};
+use tokio::sync::broadcast::error::RecvError;
 use tracing::Instrument;
 
 use crate::{common::WithConfigs, exex::BoxedLaunchExEx};
 
+/// The id of the built-in [`SocketExEx`](reth_exex::SocketExEx), installed with `--exex.socket`.
+#[cfg(unix)]
+const SOCKET_EXEX_ID: &str = "socket";
+
+/// The maximum number of new blocks in one notification sent to catch up the `ExEx` manager with
+/// the canonical chain.
+const CATCH_UP_CHUNK_BLOCKS: u64 = 128;
+
 /// Can launch execution extensions.
 pub struct ExExLauncher<Node: FullNodeComponents> {
     head: Head,
@@ -69,10 +84,26 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     pub async fn launch(
         self,
     ) -> eyre::Result<Option<ExExManagerHandle<PrimitivesTy<Node::Types>>>> {
-        let Self { head, extensions, components, config_container, wal_blocks_warning, capacity } =
-            self;
+        let Self {
+            head,
+            mut extensions,
+            components,
+            config_container,
+            wal_blocks_warning,
+            capacity,
+        } = self;
         let head = BlockNumHash::new(head.number, head.hash);
 
+        #[cfg(unix)]
+        if let Some(path) = config_container.config.exex.socket.clone() {
+            extensions.push((
+                SOCKET_EXEX_ID.to_string(),
+                Box::new(move |ctx: ExExContext<Node>| async move {
+                    Ok::<_, eyre::Error>(reth_exex::SocketExEx::new(ctx, path).run())
+                }),
+            ));
+        }
+
         if extensions.is_empty() {
             // nothing to launch
             return Ok(None)
@@ -141,7 +172,8 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
 
         // spawn exex manager
         debug!(target: "reth::cli", "spawning exex manager");
-        let exex_manager = ExExManager::new(
+        let wal_handle = exex_wal.handle();
+        let mut exex_manager = ExExManager::new(
             components.provider().clone(),
             exex_handles,
             capacity,
@@ -149,6 +181,9 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
             components.provider().finalized_block_stream(),
         )
         .with_wal_blocks_warning(wal_blocks_warning);
+        if let Some(max_size) = config_container.config.exex.wal_max_size {
+            exex_manager = exex_manager.with_wal_max_size(max_size as u64);
+        }
         let exex_manager_handle = exex_manager.handle();
         components.task_executor().spawn_critical_task("exex manager", async move {
             exex_manager.await.expect("exex manager crashed");
@@ -157,12 +192,44 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
         // send notifications from the blockchain tree to exex manager
         let mut canon_state_notifications = components.provider().subscribe_to_canonical_state();
         let mut handle = exex_manager_handle.clone();
+        let provider = components.provider().clone();
         components.task_executor().spawn_critical_task(
             "exex manager blockchain tree notifications",
             async move {
-                while let Ok(notification) = canon_state_notifications.recv().await {
+                // The tip of the last notification sent to the manager
+                let mut last_tip = head;
+                // The blocks sent to catch up after falling behind, notifications of them were
+                // covered already
+                let mut caught_up = B256Set::default();
+                loop {
+                    let notification = match canon_state_notifications.recv().await {
+                        Ok(notification) => {
+                            let tip = notification.tip();
+                            if caught_up.contains(&tip.hash()) {
+                                continue
+                            }
+                            caught_up.clear();
+                            last_tip = tip.num_hash();
+                            notification.into()
+                        }
+                        Err(RecvError::Lagged(skipped)) => {
+                            // The manager has no capacity while the WAL is full, so the canonical
+                            // state notifications in the meantime are missed
+                            warn!(target: "reth::cli", skipped, ?last_tip, "ExEx manager fell behind the canonical chain, catching up from the database");
+                            catch_up(
+                                &provider,
+                                &wal_handle,
+                                &mut handle,
+                                &mut last_tip,
+                                &mut caught_up,
+                            )
+                            .await;
+                            continue
+                        }
+                        Err(RecvError::Closed) => break,
+                    };
                     handle
-                        .send_async(ExExNotificationSource::BlockchainTree, notification.into())
+                        .send_async(ExExNotificationSource::BlockchainTree, notification)
                         .await
                         .expect("blockchain tree notification could not be sent to exex manager");
                 }
@@ -175,6 +242,145 @@ impl<Node: FullNodeComponents + Clone> ExExLauncher<Node> {
     }
 }
 
+/// Sends the notifications that bring the `ExEx`es from `last_tip` to the canonical tip in bounded
+/// chunks, and records the blocks they committed in `caught_up`.
+///
+/// Errors are logged and the missed notifications are skipped, so that the `ExEx`es continue with
+/// the next canonical state notification.
+async fn catch_up<P, N>(
+    provider: &P,
+    wal: &WalHandle<N>,
+    handle: &mut ExExManagerHandle<N>,
+    last_tip: &mut BlockNumHash,
+    caught_up: &mut B256Set,
+) where
+    N: NodePrimitives,
+    P: BlockReader<Block = N::Block> + StateReader<Receipt = N::Receipt>,
+{
+    loop {
+        let notification = match catch_up_notification(provider, wal, *last_tip) {
+            Ok(Some(notification)) => notification,
+            Ok(None) => break,
+            Err(err) => {
+                error!(target: "reth::cli", %err, ?last_tip, "Failed to catch up ExEx manager with the canonical chain, skipping the missed notifications");
+                break
+            }
+        };
+        let committed = notification.committed_chain();
+        if let Some(committed) = &committed {
+            caught_up.extend(committed.blocks().values().map(|block| block.hash()));
+        }
+        *last_tip = match (committed, notification.reverted_chain()) {
+            (Some(committed), _) => committed.tip().num_hash(),
+            (None, Some(reverted)) => chain_parent(&reverted),
+            (None, None) => unreachable!("notification has a chain"),
+        };
+        handle
+            .send_async(ExExNotificationSource::BlockchainTree, notification)
+            .await
+            .expect("catch up notification could not be sent to exex manager");
+    }
+}
+
+/// Builds the next notification that brings the `ExEx`es from `last_tip` towards the canonical tip,
+/// after the canonical state notifications in between were missed.
+///
+/// Blocks above the fork with the canonical chain were sent to the `ExEx`es before, so they're read
+/// back from the WAL and reported as reverted. At most [`CATCH_UP_CHUNK_BLOCKS`] new blocks are
+/// committed, so that the catch-up is sent in bounded chunks by calling this again with the new
+/// tip. Returns `None` if `last_tip` is the canonical tip.
+fn catch_up_notification<P, N>(
+    provider: &P,
+    wal: &WalHandle<N>,
+    last_tip: BlockNumHash,
+) -> eyre::Result<Option<ExExNotification<N>>>
+where
+    N: NodePrimitives,
+    P: BlockReader<Block = N::Block> + StateReader<Receipt = N::Receipt>,
+{
+    let is_canonical = |block: BlockNumHash| -> ProviderResult<bool> {
+        Ok(provider.block_hash(block.number)? == Some(block.hash))
+    };
+
+    // Walk back through the WAL to the highest block that's still canonical
+    let mut fork = last_tip;
+    let mut reverted = Vec::new();
+    while !is_canonical(fork)? {
+        let chain = wal
+            .get_committed_notification_by_block_hash(&fork.hash)?
+            .and_then(|notification| notification.committed_chain())
+            .ok_or_else(|| eyre::eyre!("block {fork:?} is not in the ExEx WAL"))?;
+        let mut canonical = None;
+        for block in chain.blocks().range(..=fork.number).values().rev() {
+            if is_canonical(block.num_hash())? {
+                canonical = Some(block.num_hash());
+                break
+            }
+        }
+        let first = canonical.map_or_else(|| chain.first().number(), |block| block.number + 1);
+        reverted.push(chain_range(&chain, first..=fork.number));
+        fork = canonical.unwrap_or_else(|| chain_parent(&chain));
+    }
+    let mut reverted = reverted.into_iter().rev();
+    let old = reverted.next().map(|mut old| {
+        for chain in reverted {
+            old.append_chain(chain)
+                .map_err(|_| eyre::eyre!("reverted chains are not connected"))?;
+        }
+        eyre::Ok(old)
+    });
+
+    let tip = provider.best_block_number()?.min(fork.number + CATCH_UP_CHUNK_BLOCKS);
+    let mut blocks = Vec::new();
+    let mut execution_outcome = ExecutionOutcome::default();
+    for number in fork.number + 1..=tip {
+        let block = provider
+            .recovered_block(number.into(), TransactionVariant::WithHash)?
+            .ok_or(ProviderError::HeaderNotFound(number.into()))?;
+        let state =
+            provider.get_state(number)?.ok_or(ProviderError::StateForNumberNotFound(number))?;
+        if blocks.is_empty() {
+            execution_outcome = state;
+        } else {
+            execution_outcome.extend(state);
+        }
+        blocks.push(block);
+    }
+    let new = (!blocks.is_empty()).then(|| Chain::new(blocks, execution_outcome, BTreeMap::new()));
+
+    Ok(match (old.transpose()?, new) {
+        (Some(old), Some(new)) => {
+            Some(ExExNotification::ChainReorged { old: Arc::new(old), new: Arc::new(new) })
+        }
+        (Some(old), None) => Some(ExExNotification::ChainReverted { old: Arc::new(old) }),
+        (None, Some(new)) => Some(ExExNotification::ChainCommitted { new: Arc::new(new) }),
+        (None, None) => None,
+    })
+}
+
+/// Returns the part of the chain with the blocks in the given range, which must be non-empty and
+/// within the chain.
+fn chain_range<N: NodePrimitives>(
+    chain: &Chain<N>,
+    range: RangeInclusive<BlockNumber>,
+) -> Chain<N> {
+    let blocks = chain.blocks().range(range.clone()).map(|(_, block)| block.clone());
+    let mut execution_outcome = chain.execution_outcome().clone();
+    execution_outcome.revert_to(*range.end());
+    if execution_outcome.block_number_to_index(*range.start()).is_some() {
+        execution_outcome = execution_outcome.split_at(*range.start()).1;
+    }
+    let trie_data =
+        chain.trie_data().range(range).map(|(number, data)| (*number, data.clone())).collect();
+    Chain::new(blocks, execution_outcome, trie_data)
+}
+
+/// Returns the parent of the first block of the chain.
+fn chain_parent<N: NodePrimitives>(chain: &Chain<N>) -> BlockNumHash {
+    let fork = chain.fork_block();
+    BlockNumHash::new(fork.number, fork.hash)
+}
+
 impl<Node: FullNodeComponents> Debug for ExExLauncher<Node> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("ExExLauncher")
@@ -11,6 +11,7 @@ use reth_node_core::{
     args::InvalidBlockHookType,
     dirs::{ChainPath, DataDirPath},
     node_config::NodeConfig,
+    version::version_metadata,
 };
 use reth_primitives_traits::NodePrimitives;
 use reth_provider::ChainSpecProvider;
@@ -54,7 +55,8 @@ where
 ///
 /// This function constructs the appropriate [`InvalidBlockHook`] based on the debug
 /// configuration in the node config. It supports:
-/// - Witness hooks for capturing block witness data
+/// - Witness hooks for capturing block witness data and a reproduction bundle
+/// - Prestate and opcode hooks for capturing transaction traces
 /// - Healthy node verification via RPC
 ///
 /// # Arguments
@@ -74,6 +76,7 @@ where
     N: NodePrimitives,
     P: reth_provider::StateProviderFactory
         + reth_provider::ChainSpecProvider
+        + reth_provider::HeaderProvider<Header = N::BlockHeader>
         + Clone
         + Send
         + Sync
@@ -81,7 +84,9 @@ where
     E: reth_evm::ConfigureEvm<Primitives = N> + Clone + 'static,
 {
     use reth_engine_primitives::{InvalidBlockHooks, NoopInvalidBlockHook};
-    use reth_invalid_block_hooks::InvalidBlockWitnessHook;
+    use reth_invalid_block_hooks::{
+        InvalidBlockOpcodeHook, InvalidBlockPreStateHook, InvalidBlockWitnessHook,
+    };
 
     let Some(ref hook) = config.debug.invalid_block_hook else {
         return Ok(Box::new(NoopInvalidBlockHook::default()))
@@ -89,27 +94,42 @@ where
 
     let healthy_node_rpc_client = get_healthy_node_client(config, chain_id).await?;
 
-    let output_directory = data_dir.invalid_block_hooks();
+    // All hooks write to the witness directory, so that the traces of an invalid block are next
+    // to its witness and reproduction bundle. The file names of each hook are distinct.
+    let output_directory =
+        data_dir.invalid_block_hooks().join(InvalidBlockHookType::Witness.to_string());
+    std::fs::create_dir_all(&output_directory)?;
+
     let hooks = hook
         .iter()
         .copied()
-        .map(|hook| {
-            let output_directory = output_directory.join(hook.to_string());
-            std::fs::create_dir_all(&output_directory)?;
-
-            Ok(match hook {
-                InvalidBlockHookType::Witness => Box::new(InvalidBlockWitnessHook::new(
+        .map(|hook| -> Box<dyn InvalidBlockHook<_>> {
+            let output_directory = output_directory.clone();
+            match hook {
+                InvalidBlockHookType::Witness => Box::new(
+                    InvalidBlockWitnessHook::new(
+                        provider.clone(),
+                        evm_config.clone(),
+                        output_directory,
+                        healthy_node_rpc_client.clone(),
+                    )
+                    .with_node_version(version_metadata().short_version.clone()),
+                ),
+                InvalidBlockHookType::PreState => Box::new(InvalidBlockPreStateHook::new(
+                    provider.clone(),
+                    evm_config.clone(),
+                    output_directory,
+                    healthy_node_rpc_client.clone(),
+                )),
+                InvalidBlockHookType::Opcode => Box::new(InvalidBlockOpcodeHook::new(
                     provider.clone(),
                     evm_config.clone(),
                     output_directory,
                     healthy_node_rpc_client.clone(),
                 )),
-                InvalidBlockHookType::PreState | InvalidBlockHookType::Opcode => {
-                    eyre::bail!("invalid block hook {hook:?} is not implemented yet")
-                }
-            } as Box<dyn InvalidBlockHook<_>>)
+            }
         })
-        .collect::<Result<_, _>>()?;
+        .collect();
 
     Ok(Box::new(InvalidBlockHooks(hooks)))
 }
@@ -5,8 +5,10 @@ mod exex;
 pub mod invalid_block_hook;
 
 pub(crate) mod alignment;
+pub(crate) mod config_reload;
 pub(crate) mod debug;
 pub(crate) mod engine;
+pub(crate) mod precompile_cache;
 
 pub use common::LaunchContext;
 pub use exex::ExExLauncher;
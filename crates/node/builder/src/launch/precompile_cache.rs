@@ -0,0 +1,98 @@
+//! Persistence and admin API of the engine precompile caches.
+
+use jsonrpsee::core::RpcResult;
+use reth_engine_primitives::{PrecompileCacheHandle, PrecompileCacheStats};
+use reth_engine_tree::tree::precompile_cache::PrecompileCacheMap;
+use reth_rpc_api::AdminPrecompileCacheApiServer;
+use reth_tasks::TaskExecutor;
+use reth_tracing::tracing::{info, warn};
+use std::{
+    fmt::Debug,
+    hash::Hash,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// Interval between periodic writes of the persisted precompile cache.
+const PERSIST_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
+/// Loads the persisted entries of the precompile cache from `path`, and writes the most used
+/// entries back to it periodically and on graceful shutdown.
+pub(crate) fn persist_precompile_cache<S>(
+    cache: PrecompileCacheMap<S>,
+    path: PathBuf,
+    max_size: usize,
+    executor: &TaskExecutor,
+) where
+    S: Eq + Hash + Debug + Send + Sync + Clone + 'static,
+{
+    match cache.load(&path) {
+        Ok(entries) => {
+            info!(target: "reth::cli", ?path, entries, "Loaded persisted precompile cache")
+        }
+        Err(err) => warn!(target: "reth::cli", ?path, %err, "Failed to load precompile cache"),
+    }
+
+    executor.spawn_critical_with_graceful_shutdown_signal(
+        "precompile cache persistence",
+        async move |mut shutdown| {
+            let mut interval = tokio::time::interval_at(
+                tokio::time::Instant::now() + PERSIST_INTERVAL,
+                PERSIST_INTERVAL,
+            );
+            loop {
+                tokio::select! {
+                    guard = &mut shutdown => {
+                        persist(&cache, &path, max_size);
+                        drop(guard);
+                        break
+                    }
+                    _ = interval.tick() => {
+                        let (cache, path) = (cache.clone(), path.clone());
+                        // Writing the file can take a while, so it's done off the runtime.
+                        let _ = tokio::task::spawn_blocking(move || {
+                            persist(&cache, &path, max_size)
+                        })
+                        .await;
+                    }
+                }
+            }
+        },
+    );
+}
+
+/// Writes the most used entries of the precompile cache to `path`.
+fn persist<S>(cache: &PrecompileCacheMap<S>, path: &Path, max_size: usize)
+where
+    S: Eq + Hash + Debug + Send + Sync + Clone + 'static,
+{
+    match cache.persist(path, max_size) {
+        Ok(entries) => {
+            info!(target: "reth::cli", ?path, entries, "Persisted precompile cache")
+        }
+        Err(err) => warn!(target: "reth::cli", ?path, %err, "Failed to persist precompile cache"),
+    }
+}
+
+/// `admin_` precompile cache API implementation backed by a [`PrecompileCacheHandle`].
+#[derive(Debug, Clone)]
+pub(crate) struct AdminPrecompileCacheApi {
+    handle: PrecompileCacheHandle,
+}
+
+impl AdminPrecompileCacheApi {
+    /// Creates a new instance with the given handle.
+    pub(crate) const fn new(handle: PrecompileCacheHandle) -> Self {
+        Self { handle }
+    }
+}
+
+impl AdminPrecompileCacheApiServer for AdminPrecompileCacheApi {
+    fn precompile_cache_stats(&self) -> RpcResult<Vec<PrecompileCacheStats>> {
+        Ok(self.handle.stats())
+    }
+
+    fn clear_precompile_cache(&self) -> RpcResult<u64> {
+        Ok(self.handle.clear())
+    }
+}
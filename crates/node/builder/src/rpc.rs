@@ -13,8 +13,12 @@ pub use reth_rpc_builder::{
 pub use reth_trie_db::ChangesetCache;
 
 use crate::{
-    invalid_block_hook::InvalidBlockHookExt, ConfigureEngineEvm, ConsensusEngineEvent,
-    ConsensusEngineHandle,
+    invalid_block_hook::InvalidBlockHookExt,
+    launch::{
+        config_reload::AdminReloadApi,
+        precompile_cache::{persist_precompile_cache, AdminPrecompileCacheApi},
+    },
+    ConfigureEngineEvm, ConsensusEngineEvent, ConsensusEngineHandle,
 };
 use alloy_rpc_types::engine::ClientVersionV1;
 use alloy_rpc_types_engine::ExecutionData;
@@ -39,11 +43,15 @@ use reth_rpc::{
     eth::{core::EthRpcConverterFor, DevSigner, EthApiTypes, FullEthApiServer},
     AdminApi,
 };
-use reth_rpc_api::{eth::helpers::EthTransactions, IntoEngineApiRpcModule};
+use reth_rpc_api::{
+    eth::helpers::EthTransactions, AdminPrecompileCacheApiServer, AdminReloadApiServer,
+    IntoEngineApiRpcModule,
+};
 use reth_rpc_builder::{
     auth::{AuthRpcModule, AuthServerHandle},
     config::RethRpcServerConfig,
-    RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle, TransportRpcModules,
+    RethRpcModule, RpcModuleBuilder, RpcRegistryInner, RpcServerConfig, RpcServerHandle,
+    TransportRpcModules,
 };
 use reth_rpc_engine_api::{capabilities::EngineCapabilities, EngineApi};
 use reth_rpc_eth_types::{cache::cache_new_blocks_task, EthConfig, EthStateCache};
@@ -1121,7 +1129,16 @@ where
         let Self { eth_api_builder, engine_api_builder, hooks, .. } = self;
 
         let engine_api = engine_api_builder.build_engine_api(&ctx).await?;
-        let AddOnsContext { node, config, beacon_engine_handle, jwt_secret, engine_events } = ctx;
+        let AddOnsContext {
+            node,
+            config,
+            beacon_engine_handle,
+            jwt_secret,
+            engine_events,
+            config_reload,
+            precompile_cache,
+            ..
+        } = ctx;
 
         info!(target: "reth::cli", "Engine API handler initialized");
 
@@ -1165,6 +1182,15 @@ where
                 beacon_engine_handle.clone(),
             );
 
+        modules.merge_if_module_configured(
+            RethRpcModule::Admin,
+            AdminReloadApi::new(config_reload).into_rpc(),
+        )?;
+        modules.merge_if_module_configured(
+            RethRpcModule::Admin,
+            AdminPrecompileCacheApi::new(precompile_cache).into_rpc(),
+        )?;
+
         // in dev mode we generate 20 random dev-signer accounts
         if config.dev.dev {
             let signers = DevSigner::from_mnemonic(config.dev.dev_mnemonic.as_str(), 20);
@@ -1487,7 +1513,8 @@ where
         let data_dir = ctx.config.datadir.clone().resolve_datadir(ctx.config.chain.chain());
         let invalid_block_hook = ctx.create_invalid_block_hook(&data_dir).await?;
 
-        Ok(BasicEngineValidator::new(
+        let precompile_cache_disabled = tree_config.precompile_cache_disabled();
+        let engine_validator = BasicEngineValidator::new(
             ctx.node.provider().clone(),
             std::sync::Arc::new(ctx.node.consensus().clone()),
             ctx.node.evm_config().clone(),
@@ -1496,7 +1523,26 @@ where
             invalid_block_hook,
             changeset_cache,
             ctx.node.task_executor().clone(),
-        ))
+        )
+        .with_sparse_trie_mismatch_dir(data_dir.sparse_trie_mismatches())
+        .with_state_root_cross_check_dir(data_dir.state_root_cross_checks());
+
+        if !precompile_cache_disabled {
+            let precompile_cache = engine_validator.precompile_cache_map().clone();
+            // Additional validators, like the one used for debug reorgs, don't persist their cache
+            // to avoid overwriting the file of the main validator.
+            if ctx.config.engine.precompile_cache_persisted && ctx.precompile_cache.is_empty() {
+                persist_precompile_cache(
+                    precompile_cache.clone(),
+                    data_dir.precompile_cache(),
+                    ctx.config.engine.precompile_cache_max_persisted_size * 1024 * 1024,
+                    ctx.node.task_executor(),
+                );
+            }
+            ctx.precompile_cache.register(std::sync::Arc::new(precompile_cache));
+        }
+
+        Ok(engine_validator)
     }
 }
 
@@ -72,6 +72,21 @@ pub struct DebugArgs {
     #[arg(long = "debug.engine-api-store", help_heading = "Debug", value_name = "PATH")]
     pub engine_api_store: Option<PathBuf>,
 
+    /// The path to replay engine API messages from.
+    /// If specified, the engine API messages stored with `--debug.engine-api-store`
+    /// will be sent to the engine in order once the node is started, and the engine
+    /// responses compared with the recorded ones. The node exits once the last message was
+    /// answered.
+    #[arg(
+        long = "debug.engine-api-replay",
+        help_heading = "Debug",
+        value_name = "PATH",
+        conflicts_with = "tip",
+        conflicts_with = "etherscan",
+        conflicts_with = "rpc_consensus_url"
+    )]
+    pub engine_api_replay: Option<PathBuf>,
+
     /// Determines which type of invalid block hook to install
     ///
     /// Example: `witness,prestate`
@@ -123,6 +138,7 @@ impl Default for DebugArgs {
             reorg_frequency: None,
             reorg_depth: None,
             engine_api_store: None,
+            engine_api_replay: None,
             invalid_block_hook: Some(InvalidBlockSelection::default()),
             healthy_node_rpc_url: None,
             ethstats: None,
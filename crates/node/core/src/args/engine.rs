@@ -12,7 +12,8 @@ use std::{sync::OnceLock, time::Duration};
 
 use crate::node_config::{
     DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB, DEFAULT_MEMORY_BLOCK_BUFFER_TARGET,
-    DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
+    DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_PRECOMPILE_CACHE_MAX_PERSISTED_SIZE_MB,
+    DEFAULT_RESERVED_CPU_CORES,
 };
 use reth_engine_primitives::DEFAULT_MIN_BLOCKS_FOR_PIPELINE_RUN;
 
@@ -38,6 +39,8 @@ pub struct DefaultEngineValues {
     multiproof_chunk_size: usize,
     reserved_cpu_cores: usize,
     precompile_cache_disabled: bool,
+    precompile_cache_persisted: bool,
+    precompile_cache_max_persisted_size: usize,
     state_root_fallback: bool,
     always_process_payload_attributes_on_canonical_head: bool,
     allow_unwind_canonical_header: bool,
@@ -49,6 +52,8 @@ pub struct DefaultEngineValues {
     sparse_trie_max_hot_accounts: usize,
     slow_block_threshold: Option<Duration>,
     disable_sparse_trie_cache_pruning: bool,
+    sparse_trie_mismatch_recorder_blocks: usize,
+    state_root_cross_check_interval: u64,
     state_root_task_timeout: Option<String>,
     share_execution_cache_with_payload_builder: bool,
     share_sparse_trie_with_payload_builder: bool,
@@ -154,6 +159,18 @@ impl DefaultEngineValues {
         self
     }
 
+    /// Set whether to persist the precompile cache across restarts by default
+    pub const fn with_precompile_cache_persisted(mut self, v: bool) -> Self {
+        self.precompile_cache_persisted = v;
+        self
+    }
+
+    /// Set the default max size of the persisted precompile cache in MB
+    pub const fn with_precompile_cache_max_persisted_size(mut self, v: usize) -> Self {
+        self.precompile_cache_max_persisted_size = v;
+        self
+    }
+
     /// Set whether to enable state root fallback by default
     pub const fn with_state_root_fallback(mut self, v: bool) -> Self {
         self.state_root_fallback = v;
@@ -223,6 +240,19 @@ impl DefaultEngineValues {
         self
     }
 
+    /// Set the default number of sparse trie state root computations kept by the mismatch
+    /// recorder
+    pub const fn with_sparse_trie_mismatch_recorder_blocks(mut self, v: usize) -> Self {
+        self.sparse_trie_mismatch_recorder_blocks = v;
+        self
+    }
+
+    /// Set the default interval of blocks whose state root is cross-checked
+    pub const fn with_state_root_cross_check_interval(mut self, v: u64) -> Self {
+        self.state_root_cross_check_interval = v;
+        self
+    }
+
     /// Set the default state root task timeout
     pub fn with_state_root_task_timeout(mut self, v: Option<String>) -> Self {
         self.state_root_task_timeout = v;
@@ -283,6 +313,8 @@ impl Default for DefaultEngineValues {
             multiproof_chunk_size: DEFAULT_MULTIPROOF_TASK_CHUNK_SIZE,
             reserved_cpu_cores: DEFAULT_RESERVED_CPU_CORES,
             precompile_cache_disabled: false,
+            precompile_cache_persisted: false,
+            precompile_cache_max_persisted_size: DEFAULT_PRECOMPILE_CACHE_MAX_PERSISTED_SIZE_MB,
             state_root_fallback: false,
             always_process_payload_attributes_on_canonical_head: false,
             allow_unwind_canonical_header: false,
@@ -294,6 +326,8 @@ impl Default for DefaultEngineValues {
             sparse_trie_max_hot_accounts: DEFAULT_SPARSE_TRIE_MAX_HOT_ACCOUNTS,
             slow_block_threshold: None,
             disable_sparse_trie_cache_pruning: false,
+            sparse_trie_mismatch_recorder_blocks: 0,
+            state_root_cross_check_interval: 0,
             state_root_task_timeout: Some("1s".to_string()),
             share_execution_cache_with_payload_builder: false,
             share_sparse_trie_with_payload_builder: false,
@@ -401,6 +435,15 @@ pub struct EngineArgs {
     #[arg(long = "engine.disable-precompile-cache", default_value_t = DefaultEngineValues::get_global().precompile_cache_disabled)]
     pub precompile_cache_disabled: bool,
 
+    /// Persist the most used precompile cache entries to the datadir periodically and on
+    /// shutdown, and load them on startup
+    #[arg(long = "engine.persist-precompile-cache", default_value_t = DefaultEngineValues::get_global().precompile_cache_persisted)]
+    pub precompile_cache_persisted: bool,
+
+    /// Max size of the persisted precompile cache in MB
+    #[arg(long = "engine.precompile-cache-max-persisted-size", default_value_t = DefaultEngineValues::get_global().precompile_cache_max_persisted_size)]
+    pub precompile_cache_max_persisted_size: usize,
+
     /// Enable state root fallback, useful for testing
     #[arg(long = "engine.state-root-fallback", default_value_t = DefaultEngineValues::get_global().state_root_fallback)]
     pub state_root_fallback: bool,
@@ -470,6 +513,28 @@ pub struct EngineArgs {
     #[arg(long = "engine.disable-sparse-trie-cache-pruning", default_value_t = DefaultEngineValues::get_global().disable_sparse_trie_cache_pruning)]
     pub disable_sparse_trie_cache_pruning: bool,
 
+    /// Number of recent sparse trie state root computations whose inputs (hashed state updates
+    /// and revealed multiproofs) are kept in a ring buffer. When the state root task result
+    /// disagrees with the block header or the fallback computation, the buffer is dumped together
+    /// with the block's prefix sets to `<DATADIR>/sparse-trie-mismatches`.
+    ///
+    /// A computation that reuses the sparse trie of the previous block also records the leaves the
+    /// trie retained, so that it can be replayed against the parent state. Ignored with
+    /// `--statedb.triedb`.
+    ///
+    /// Set to 0 to disable the recorder (default).
+    #[arg(long = "engine.sparse-trie-mismatch-recorder-blocks", default_value_t = DefaultEngineValues::get_global().sparse_trie_mismatch_recorder_blocks)]
+    pub sparse_trie_mismatch_recorder_blocks: usize,
+
+    /// Cross-check the state root of every Nth block: the sparse trie task result is compared
+    /// against the parallel and serial state root computations, run in the background off the
+    /// critical path. Disagreeing roots and trie updates are reported as metrics and dumped
+    /// node by node to `<DATADIR>/state-root-cross-checks`. Ignored with `--statedb.triedb`.
+    ///
+    /// Set to 0 to disable the cross-check (default).
+    #[arg(long = "engine.state-root-cross-check-interval", value_name = "BLOCKS", default_value_t = DefaultEngineValues::get_global().state_root_cross_check_interval)]
+    pub state_root_cross_check_interval: u64,
+
     /// Configure the timeout for the state root task before spawning a sequential fallback.
     /// If the state root task takes longer than this, a sequential computation starts in
     /// parallel and whichever finishes first is used.
@@ -583,6 +648,8 @@ impl Default for EngineArgs {
             multiproof_chunk_size,
             reserved_cpu_cores,
             precompile_cache_disabled,
+            precompile_cache_persisted,
+            precompile_cache_max_persisted_size,
             state_root_fallback,
             always_process_payload_attributes_on_canonical_head,
             allow_unwind_canonical_header,
@@ -594,6 +661,8 @@ impl Default for EngineArgs {
             sparse_trie_max_hot_accounts,
             slow_block_threshold,
             disable_sparse_trie_cache_pruning,
+            sparse_trie_mismatch_recorder_blocks,
+            state_root_cross_check_interval,
             state_root_task_timeout,
             share_execution_cache_with_payload_builder,
             share_sparse_trie_with_payload_builder,
@@ -621,6 +690,8 @@ impl Default for EngineArgs {
             reserved_cpu_cores,
             precompile_cache_enabled: true,
             precompile_cache_disabled,
+            precompile_cache_persisted,
+            precompile_cache_max_persisted_size,
             state_root_fallback,
             always_process_payload_attributes_on_canonical_head,
             allow_unwind_canonical_header,
@@ -632,6 +703,8 @@ impl Default for EngineArgs {
             sparse_trie_max_hot_accounts,
             slow_block_threshold,
             disable_sparse_trie_cache_pruning,
+            sparse_trie_mismatch_recorder_blocks,
+            state_root_cross_check_interval,
             state_root_task_timeout: state_root_task_timeout
                 .as_deref()
                 .map(|s| humantime::parse_duration(s).expect("valid default duration")),
@@ -688,6 +761,8 @@ impl EngineArgs {
             .with_sparse_trie_max_hot_accounts(self.sparse_trie_max_hot_accounts)
             .with_slow_block_threshold(self.slow_block_threshold)
             .with_disable_sparse_trie_cache_pruning(self.disable_sparse_trie_cache_pruning)
+            .with_sparse_trie_mismatch_recorder_blocks(self.sparse_trie_mismatch_recorder_blocks)
+            .with_state_root_cross_check_interval(self.state_root_cross_check_interval)
             .with_state_root_task_timeout(self.state_root_task_timeout.filter(|d| !d.is_zero()))
             .with_share_execution_cache_with_payload_builder(
                 self.share_execution_cache_with_payload_builder,
@@ -756,6 +831,8 @@ mod tests {
             reserved_cpu_cores: 4,
             precompile_cache_enabled: true,
             precompile_cache_disabled: true,
+            precompile_cache_persisted: true,
+            precompile_cache_max_persisted_size: 16,
             state_root_fallback: true,
             always_process_payload_attributes_on_canonical_head: true,
             allow_unwind_canonical_header: true,
@@ -767,6 +844,8 @@ mod tests {
             sparse_trie_max_hot_accounts: 500,
             slow_block_threshold: None,
             disable_sparse_trie_cache_pruning: true,
+            sparse_trie_mismatch_recorder_blocks: 8,
+            state_root_cross_check_interval: 100,
             state_root_task_timeout: Some(Duration::from_secs(2)),
             share_execution_cache_with_payload_builder: false,
             share_sparse_trie_with_payload_builder: false,
@@ -804,6 +883,9 @@ mod tests {
             "--engine.reserved-cpu-cores",
             "4",
             "--engine.disable-precompile-cache",
+            "--engine.persist-precompile-cache",
+            "--engine.precompile-cache-max-persisted-size",
+            "16",
             "--engine.state-root-fallback",
             "--engine.always-process-payload-attributes-on-canonical-head",
             "--engine.allow-unwind-canonical-header",
@@ -819,6 +901,10 @@ mod tests {
             "--engine.sparse-trie-max-hot-accounts",
             "500",
             "--engine.disable-sparse-trie-cache-pruning",
+            "--engine.sparse-trie-mismatch-recorder-blocks",
+            "8",
+            "--engine.state-root-cross-check-interval",
+            "100",
             "--engine.state-root-task-timeout",
             "2s",
             "--engine.disable-bal-parallel-execution",
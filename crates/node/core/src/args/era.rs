@@ -1,6 +1,6 @@
 use clap::Args;
 use reth_chainspec::{ChainKind, NamedChain};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use url::Url;
 
 /// Syncs ERA1 encoded blocks from a local or remote source.
@@ -18,6 +18,13 @@ pub struct EraArgs {
     /// Describes where to get the ERA files to import from.
     #[clap(flatten)]
     pub source: EraSourceArgs,
+
+    /// The path to a directory of ERA1 files to serve expired block history from.
+    ///
+    /// Blocks, transactions and receipts below the node's earliest available block, or receipts
+    /// that have been pruned, are read from the ERA1 files in this directory instead.
+    #[arg(long = "era.history-path", value_name = "ERA_HISTORY_PATH", verbatim_doc_comment)]
+    pub history_path: Option<PathBuf>,
 }
 
 /// Arguments for the block history import based on ERA1 encoded files.
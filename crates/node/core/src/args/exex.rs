@@ -0,0 +1,58 @@
+//! clap [Args](clap::Args) for execution extensions
+
+use crate::args::parse_byte_size;
+use clap::Args;
+use std::path::PathBuf;
+
+/// Parameters for execution extensions
+#[derive(Debug, Clone, Default, Args, PartialEq, Eq)]
+#[command(next_help_heading = "ExEx")]
+pub struct ExExArgs {
+    /// The path of a Unix socket to serve `ExEx` notifications on.
+    ///
+    /// If specified, a built-in `ExEx` streams the committed, reverted and reorged chains of the
+    /// node to the consumers connected to the socket, as length-prefixed JSON messages. The last
+    /// block acked by the consumers is kept in a file next to the socket with the `ack` extension,
+    /// and the WAL isn't finalized past it until a consumer acks again.
+    #[arg(long = "exex.socket", value_name = "PATH")]
+    pub socket: Option<PathBuf>,
+
+    /// Maximum size of the `ExEx` Write-Ahead Log (e.g., 512MB, 10GB).
+    ///
+    /// If an `ExEx` stops processing notifications, the WAL can't be finalized and keeps growing.
+    /// Once it reaches this size, the `ExEx` manager stops accepting new notifications until the
+    /// `ExEx`es catch up and the WAL is finalized, instead of filling up the disk. Block
+    /// processing continues, and the blocks committed in the meantime are sent to the `ExEx`es as
+    /// a single notification read from the database once the WAL has room again. Unlimited by
+    /// default.
+    #[arg(long = "exex.wal-max-size", value_name = "SIZE", value_parser = parse_byte_size)]
+    pub wal_max_size: Option<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_exex_args() {
+        let args = CommandParser::<ExExArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, ExExArgs::default());
+
+        let args =
+            CommandParser::<ExExArgs>::parse_from(["reth", "--exex.socket", "/tmp/reth-exex.ipc"])
+                .args;
+        assert_eq!(args.socket, Some(PathBuf::from("/tmp/reth-exex.ipc")));
+
+        let args =
+            CommandParser::<ExExArgs>::parse_from(["reth", "--exex.wal-max-size", "10GB"]).args;
+        assert_eq!(args.wal_max_size, Some(10 * 1024 * 1024 * 1024));
+    }
+}
@@ -72,9 +72,16 @@ pub use engine::{DefaultEngineValues, EngineArgs};
 mod era;
 pub use era::{DefaultEraHost, EraArgs, EraSourceArgs};
 
+/// `ExExArgs` for configuring the built-in execution extensions.
+mod exex;
+pub use exex::ExExArgs;
+
 /// `StaticFilesArgs` for configuring static files.
 mod static_files;
-pub use static_files::{StaticFilesArgs, MINIMAL_BLOCKS_PER_FILE};
+pub use static_files::{
+    StaticFileRecompressionArgs, StaticFilesArgs, DEFAULT_STATIC_FILE_RECOMPRESSION_INTERVAL,
+    MINIMAL_BLOCKS_PER_FILE,
+};
 
 /// `StateDbArgs` for configuring state database.
 mod statedb;
@@ -1,7 +1,9 @@
 //! clap [Args](clap::Args) for static files configuration
 
 use clap::Args;
+use humantime::parse_duration;
 use reth_config::config::{BlocksPerFileConfig, StaticFilesConfig};
+use std::time::Duration;
 
 /// Blocks per static file when running in `--minimal` node.
 ///
@@ -37,6 +39,37 @@ pub struct StaticFilesArgs {
     pub blocks_per_file_storage_change_sets: Option<u64>,
 }
 
+/// Default interval between two runs of the static file recompression.
+pub const DEFAULT_STATIC_FILE_RECOMPRESSION_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// Parameters for recompressing static files in the background while the node is running.
+#[derive(Debug, Args, PartialEq, Eq, Clone, Copy)]
+#[command(next_help_heading = "Static Files")]
+pub struct StaticFileRecompressionArgs {
+    /// Recompress complete static files of finalized blocks in the background.
+    ///
+    /// Transactions and receipts static files are rewritten with zstd dictionaries trained per
+    /// column, which considerably reduces their size.
+    #[arg(long = "static-files.recompress")]
+    pub recompress: bool,
+
+    /// Interval between two background recompression runs.
+    #[arg(
+        long = "static-files.recompress-interval",
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        default_value = "1h",
+        requires = "recompress"
+    )]
+    pub recompress_interval: Duration,
+}
+
+impl Default for StaticFileRecompressionArgs {
+    fn default() -> Self {
+        Self { recompress: false, recompress_interval: DEFAULT_STATIC_FILE_RECOMPRESSION_INTERVAL }
+    }
+}
+
 impl StaticFilesArgs {
     /// Merges the CLI arguments with an existing [`StaticFilesConfig`], giving priority to CLI
     /// args.
@@ -75,3 +108,39 @@ impl StaticFilesArgs {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    /// A helper type to parse Args more easily
+    #[derive(Parser)]
+    struct CommandParser<T: Args> {
+        #[command(flatten)]
+        args: T,
+    }
+
+    #[test]
+    fn test_parse_static_file_recompression_args() {
+        let args = CommandParser::<StaticFileRecompressionArgs>::parse_from(["reth"]).args;
+        assert_eq!(args, StaticFileRecompressionArgs::default());
+
+        let args = CommandParser::<StaticFileRecompressionArgs>::parse_from([
+            "reth",
+            "--static-files.recompress",
+            "--static-files.recompress-interval",
+            "30m",
+        ])
+        .args;
+        assert!(args.recompress);
+        assert_eq!(args.recompress_interval, Duration::from_secs(30 * 60));
+
+        assert!(CommandParser::<StaticFileRecompressionArgs>::try_parse_from([
+            "reth",
+            "--static-files.recompress-interval",
+            "30m",
+        ])
+        .is_err());
+    }
+}
@@ -7,14 +7,19 @@ use clap::{builder::Resettable, Args};
 use reth_cli_util::{parse_duration_from_secs_or_ms, parsers::format_duration_as_secs_or_ms};
 use reth_network::transactions::constants::tx_manager::DEFAULT_REANNOUNCE_TIME;
 use reth_transaction_pool::{
+    admission::{
+        AdmissionConfig, OriginGasPriceFloor, SenderFilter, SenderFilterLists,
+        SourceRateLimitConfig,
+    },
     blobstore::disk::DEFAULT_MAX_CACHED_BLOBS,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     validate::DEFAULT_MAX_TX_INPUT_BYTES,
-    LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit, DEFAULT_PRICE_BUMP,
-    DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
-    REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
-    TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
+    LocalTransactionConfig, OrderingConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+    TransactionOrderingKind, DEFAULT_PRICE_BUMP, DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS,
+    MAX_NEW_PENDING_TXS_NOTIFICATIONS, REPLACE_BLOB_PRICE_BUMP,
+    TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER, TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT,
+    TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
 };
 use std::{path::PathBuf, sync::OnceLock, time::Duration};
 
@@ -57,6 +62,17 @@ pub struct DefaultTxPoolValues {
     transactions_backup_path: Option<PathBuf>,
     disable_transactions_backup: bool,
     max_batch_size: usize,
+    ordering: TransactionOrderingKind,
+    ordering_boost_recipients: Vec<Address>,
+    ordering_boost_bps: u64,
+    admission_deny: Vec<Address>,
+    admission_allow: Vec<Address>,
+    admission_min_gas_price_local: Option<u128>,
+    admission_min_gas_price_external: Option<u128>,
+    admission_min_gas_price_private: Option<u128>,
+    admission_rate_limit: Option<u32>,
+    admission_rate_limit_burst: Option<u32>,
+    record_path: Option<PathBuf>,
 }
 
 impl DefaultTxPoolValues {
@@ -255,6 +271,72 @@ impl DefaultTxPoolValues {
         self.max_batch_size = v;
         self
     }
+
+    /// Set the default transaction ordering
+    pub const fn with_ordering(mut self, v: TransactionOrderingKind) -> Self {
+        self.ordering = v;
+        self
+    }
+
+    /// Set the default recipients boosted by the weighted ordering
+    pub fn with_ordering_boost_recipients(mut self, v: Vec<Address>) -> Self {
+        self.ordering_boost_recipients = v;
+        self
+    }
+
+    /// Set the default boost of the boosted recipients in basis points
+    pub const fn with_ordering_boost_bps(mut self, v: u64) -> Self {
+        self.ordering_boost_bps = v;
+        self
+    }
+
+    /// Set the default senders that are never admitted
+    pub fn with_admission_deny(mut self, v: Vec<Address>) -> Self {
+        self.admission_deny = v;
+        self
+    }
+
+    /// Set the default senders that are exclusively admitted
+    pub fn with_admission_allow(mut self, v: Vec<Address>) -> Self {
+        self.admission_allow = v;
+        self
+    }
+
+    /// Set the default minimum gas price of local transactions
+    pub const fn with_admission_min_gas_price_local(mut self, v: Option<u128>) -> Self {
+        self.admission_min_gas_price_local = v;
+        self
+    }
+
+    /// Set the default minimum gas price of external transactions
+    pub const fn with_admission_min_gas_price_external(mut self, v: Option<u128>) -> Self {
+        self.admission_min_gas_price_external = v;
+        self
+    }
+
+    /// Set the default minimum gas price of private transactions
+    pub const fn with_admission_min_gas_price_private(mut self, v: Option<u128>) -> Self {
+        self.admission_min_gas_price_private = v;
+        self
+    }
+
+    /// Set the default per-source rate limit in transactions per second
+    pub const fn with_admission_rate_limit(mut self, v: Option<u32>) -> Self {
+        self.admission_rate_limit = v;
+        self
+    }
+
+    /// Set the default per-source rate limit burst
+    pub const fn with_admission_rate_limit_burst(mut self, v: Option<u32>) -> Self {
+        self.admission_rate_limit_burst = v;
+        self
+    }
+
+    /// Set the default path the pool events are recorded to
+    pub fn with_record_path(mut self, v: Option<PathBuf>) -> Self {
+        self.record_path = v;
+        self
+    }
 }
 
 impl Default for DefaultTxPoolValues {
@@ -291,6 +373,17 @@ impl Default for DefaultTxPoolValues {
             transactions_backup_path: None,
             disable_transactions_backup: false,
             max_batch_size: 1,
+            ordering: TransactionOrderingKind::default(),
+            ordering_boost_recipients: Vec::new(),
+            ordering_boost_bps: 0,
+            admission_deny: Vec::new(),
+            admission_allow: Vec::new(),
+            admission_min_gas_price_local: None,
+            admission_min_gas_price_external: None,
+            admission_min_gas_price_private: None,
+            admission_rate_limit: None,
+            admission_rate_limit_burst: None,
+            record_path: None,
         }
     }
 }
@@ -426,6 +519,62 @@ pub struct TxPoolArgs {
     /// Max batch size for transaction pool insertions
     #[arg(long = "txpool.max-batch-size", default_value_t = DefaultTxPoolValues::get_global().max_batch_size)]
     pub max_batch_size: usize,
+
+    /// How pending transactions are ordered for block building: `coinbase-tip`,
+    /// `effective-gas-price`, `fifo` or `weighted`.
+    ///
+    /// `fifo` serves transactions in arrival order, regardless of their fee. `weighted` scores
+    /// transactions by their gas price times their gas limit, boosted for the
+    /// `--txpool.ordering-boost-recipients`.
+    #[arg(long = "txpool.ordering", value_name = "ORDERING", default_value_t = DefaultTxPoolValues::get_global().ordering)]
+    pub ordering: TransactionOrderingKind,
+
+    /// Recipients, e.g. paymaster contracts, whose transactions are boosted by the weighted
+    /// ordering.
+    #[arg(long = "txpool.ordering-boost-recipients", default_values = DefaultTxPoolValues::get_global().ordering_boost_recipients.iter().map(ToString::to_string))]
+    pub ordering_boost_recipients: Vec<Address>,
+
+    /// Boost of the `--txpool.ordering-boost-recipients` in basis points, e.g. 5000 for +50%.
+    #[arg(long = "txpool.ordering-boost-bps", default_value_t = DefaultTxPoolValues::get_global().ordering_boost_bps)]
+    pub ordering_boost_bps: u64,
+
+    /// Senders whose transactions are never admitted to the pool.
+    #[arg(long = "txpool.admission.deny", value_name = "ADDRESS", default_values = DefaultTxPoolValues::get_global().admission_deny.iter().map(ToString::to_string))]
+    pub admission_deny: Vec<Address>,
+
+    /// If set, only transactions of these senders are admitted to the pool.
+    #[arg(long = "txpool.admission.allow", value_name = "ADDRESS", default_values = DefaultTxPoolValues::get_global().admission_allow.iter().map(ToString::to_string))]
+    pub admission_allow: Vec<Address>,
+
+    /// Minimum gas price in wei of local transactions.
+    #[arg(long = "txpool.admission.min-gas-price-local", value_name = "WEI", default_value = Resettable::from(DefaultTxPoolValues::get_global().admission_min_gas_price_local.map(|v| v.to_string().into())))]
+    pub admission_min_gas_price_local: Option<u128>,
+
+    /// Minimum gas price in wei of external transactions, e.g. received from peers or over RPC.
+    #[arg(long = "txpool.admission.min-gas-price-external", value_name = "WEI", default_value = Resettable::from(DefaultTxPoolValues::get_global().admission_min_gas_price_external.map(|v| v.to_string().into())))]
+    pub admission_min_gas_price_external: Option<u128>,
+
+    /// Minimum gas price in wei of private transactions.
+    #[arg(long = "txpool.admission.min-gas-price-private", value_name = "WEI", default_value = Resettable::from(DefaultTxPoolValues::get_global().admission_min_gas_price_private.map(|v| v.to_string().into())))]
+    pub admission_min_gas_price_private: Option<u128>,
+
+    /// Maximum number of transactions per second admitted per source, i.e. per peer or RPC
+    /// client address. Disabled if not set.
+    #[arg(long = "txpool.admission.rate-limit", value_name = "TXS_PER_SEC", default_value = Resettable::from(DefaultTxPoolValues::get_global().admission_rate_limit.map(|v| v.to_string().into())))]
+    pub admission_rate_limit: Option<u32>,
+
+    /// Number of transactions a source can submit in a burst, defaults to one second worth of
+    /// `--txpool.admission.rate-limit`.
+    #[arg(long = "txpool.admission.rate-limit-burst", value_name = "TXS", requires = "admission_rate_limit", default_value = Resettable::from(DefaultTxPoolValues::get_global().admission_rate_limit_burst.map(|v| v.to_string().into())))]
+    pub admission_rate_limit_burst: Option<u32>,
+
+    /// Records the events of the pool and the head blocks it observed to the given file, one JSON
+    /// object per line.
+    ///
+    /// The recording can be replayed against a different pool configuration with
+    /// `reth debug txpool-replay`.
+    #[arg(long = "txpool.record", value_name = "PATH", default_value = Resettable::from(DefaultTxPoolValues::get_global().record_path.as_ref().map(|v| v.to_string_lossy().into())))]
+    pub record_path: Option<PathBuf>,
 }
 
 impl TxPoolArgs {
@@ -480,6 +629,17 @@ impl Default for TxPoolArgs {
             transactions_backup_path,
             disable_transactions_backup,
             max_batch_size,
+            ordering,
+            ordering_boost_recipients,
+            ordering_boost_bps,
+            admission_deny,
+            admission_allow,
+            admission_min_gas_price_local,
+            admission_min_gas_price_external,
+            admission_min_gas_price_private,
+            admission_rate_limit,
+            admission_rate_limit_burst,
+            record_path,
         } = DefaultTxPoolValues::get_global().clone();
         Self {
             pending_max_count,
@@ -513,6 +673,17 @@ impl Default for TxPoolArgs {
             transactions_backup_path,
             disable_transactions_backup,
             max_batch_size,
+            ordering,
+            ordering_boost_recipients,
+            ordering_boost_bps,
+            admission_deny,
+            admission_allow,
+            admission_min_gas_price_local,
+            admission_min_gas_price_external,
+            admission_min_gas_price_private,
+            admission_rate_limit,
+            admission_rate_limit_burst,
+            record_path,
         }
     }
 }
@@ -557,6 +728,25 @@ impl RethTransactionPoolConfig for TxPoolArgs {
             max_new_pending_txs_notifications: self.max_new_pending_txs_notifications,
             max_queued_lifetime: self.max_queued_lifetime,
             max_inflight_delegated_slot_limit: default_config.max_inflight_delegated_slot_limit,
+            admission: AdmissionConfig {
+                sender_filter: SenderFilter::new(SenderFilterLists {
+                    allow: (!self.admission_allow.is_empty())
+                        .then(|| self.admission_allow.iter().copied().collect()),
+                    deny: self.admission_deny.iter().copied().collect(),
+                }),
+                min_gas_price: OriginGasPriceFloor {
+                    local: self.admission_min_gas_price_local,
+                    external: self.admission_min_gas_price_external,
+                    private: self.admission_min_gas_price_private,
+                },
+                source_rate_limit: self.admission_rate_limit.map(|rate| {
+                    let config = SourceRateLimitConfig::new(rate);
+                    match self.admission_rate_limit_burst {
+                        Some(burst) => config.with_burst(burst),
+                        None => config,
+                    }
+                }),
+            },
         }
     }
 
@@ -564,6 +754,15 @@ impl RethTransactionPoolConfig for TxPoolArgs {
     fn max_batch_size(&self) -> usize {
         self.max_batch_size
     }
+
+    /// Returns the configuration of the transaction ordering.
+    fn ordering_config(&self) -> OrderingConfig {
+        OrderingConfig {
+            kind: self.ordering,
+            boosted_recipients: self.ordering_boost_recipients.iter().copied().collect(),
+            recipient_boost_bps: self.ordering_boost_bps,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -643,6 +842,17 @@ mod tests {
             transactions_backup_path: Some(PathBuf::from("/tmp/txpool-backup")),
             disable_transactions_backup: false,
             max_batch_size: 10,
+            ordering: TransactionOrderingKind::Weighted,
+            ordering_boost_recipients: vec![address!("0x0000000000000000000000000000000000000003")],
+            ordering_boost_bps: 5000,
+            admission_deny: vec![address!("0x0000000000000000000000000000000000000004")],
+            admission_allow: vec![],
+            admission_min_gas_price_local: None,
+            admission_min_gas_price_external: Some(1000000000),
+            admission_min_gas_price_private: None,
+            admission_rate_limit: Some(50),
+            admission_rate_limit_burst: Some(100),
+            record_path: Some(PathBuf::from("/tmp/txpool-record.jsonl")),
         };
 
         let parsed_args = CommandParser::<TxPoolArgs>::parse_from([
@@ -705,9 +915,70 @@ mod tests {
             "/tmp/txpool-backup",
             "--txpool.max-batch-size",
             "10",
+            "--txpool.ordering",
+            "weighted",
+            "--txpool.ordering-boost-recipients",
+            "0x0000000000000000000000000000000000000003",
+            "--txpool.ordering-boost-bps",
+            "5000",
+            "--txpool.admission.deny",
+            "0x0000000000000000000000000000000000000004",
+            "--txpool.admission.min-gas-price-external",
+            "1000000000",
+            "--txpool.admission.rate-limit",
+            "50",
+            "--txpool.admission.rate-limit-burst",
+            "100",
+            "--txpool.record",
+            "/tmp/txpool-record.jsonl",
         ])
         .args;
 
         assert_eq!(parsed_args, args);
     }
+
+    #[test]
+    fn txpool_parse_ordering() {
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        assert_eq!(args.ordering_config().kind, TransactionOrderingKind::CoinbaseTip);
+
+        let args =
+            CommandParser::<TxPoolArgs>::parse_from(["reth", "--txpool.ordering", "fifo"]).args;
+        assert_eq!(args.ordering_config().kind, TransactionOrderingKind::Fifo);
+
+        let result =
+            CommandParser::<TxPoolArgs>::try_parse_from(["reth", "--txpool.ordering", "random"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn txpool_admission_config() {
+        let args = CommandParser::<TxPoolArgs>::parse_from(["reth"]).args;
+        let admission = args.pool_config().admission;
+        assert_eq!(admission.sender_filter.lists(), SenderFilterLists::default());
+        assert!(admission.source_rate_limit.is_none());
+
+        let args = CommandParser::<TxPoolArgs>::parse_from([
+            "reth",
+            "--txpool.admission.allow",
+            "0x0000000000000000000000000000000000000001",
+            "--txpool.admission.min-gas-price-private",
+            "7",
+            "--txpool.admission.rate-limit",
+            "10",
+        ])
+        .args;
+        let admission = args.pool_config().admission;
+        let allow = admission.sender_filter.lists().allow.unwrap();
+        assert!(allow.contains(&address!("0x0000000000000000000000000000000000000001")));
+        assert_eq!(admission.min_gas_price.private, Some(7));
+        assert_eq!(admission.source_rate_limit, Some(SourceRateLimitConfig::new(10)));
+
+        let result = CommandParser::<TxPoolArgs>::try_parse_from([
+            "reth",
+            "--txpool.admission.rate-limit-burst",
+            "10",
+        ]);
+        assert!(result.is_err());
+    }
 }
@@ -4,7 +4,7 @@ use alloy_eips::eip1559::ETHEREUM_BLOCK_GAS_LIMIT_36M;
 use alloy_primitives::Bytes;
 use reth_chainspec::{Chain, ChainKind, NamedChain};
 use reth_network::{protocol::IntoRlpxSubProtocol, NetworkPrimitives};
-use reth_transaction_pool::PoolConfig;
+use reth_transaction_pool::{OrderingConfig, PoolConfig};
 use std::time::Duration;
 
 /// 60M gas limit
@@ -86,4 +86,9 @@ pub trait RethTransactionPoolConfig {
 
     /// Returns max batch size for transaction batch insertion.
     fn max_batch_size(&self) -> usize;
+
+    /// Returns the configuration of the transaction ordering.
+    fn ordering_config(&self) -> OrderingConfig {
+        OrderingConfig::default()
+    }
 }
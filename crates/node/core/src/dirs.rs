@@ -353,6 +353,13 @@ impl<D> ChainPath<D> {
         self.data_dir().join("txpool-transactions-backup.rlp")
     }
 
+    /// Returns the path to the persisted precompile cache file
+    ///
+    /// `<DIR>/<CHAIN_ID>/precompile-cache.bin`
+    pub fn precompile_cache(&self) -> PathBuf {
+        self.data_dir().join("precompile-cache.bin")
+    }
+
     /// Returns the path to the config file for this chain.
     ///
     /// `<DIR>/<CHAIN_ID>/reth.toml`
@@ -374,6 +381,20 @@ impl<D> ChainPath<D> {
         self.data_dir().join("invalid_block_hooks")
     }
 
+    /// Returns the path to the sparse trie mismatch dumps directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/sparse-trie-mismatches`
+    pub fn sparse_trie_mismatches(&self) -> PathBuf {
+        self.data_dir().join("sparse-trie-mismatches")
+    }
+
+    /// Returns the path to the state root cross-check dumps directory for this chain.
+    ///
+    /// `<DIR>/<CHAIN_ID>/state-root-cross-checks`
+    pub fn state_root_cross_checks(&self) -> PathBuf {
+        self.data_dir().join("state-root-cross-checks")
+    }
+
     /// Returns the path to the ExEx WAL directory for this chain.
     pub fn exex_wal(&self) -> PathBuf {
         self.data_dir().join("exex/wal")
@@ -13,6 +13,7 @@ pub mod cli;
 pub mod dirs;
 pub mod exit;
 pub mod node_config;
+pub mod reload;
 pub mod utils;
 pub mod version;
 
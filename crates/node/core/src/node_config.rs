@@ -33,7 +33,7 @@ use std::{
 };
 use tracing::*;
 
-use crate::args::{EraArgs, MetricArgs};
+use crate::args::{EraArgs, ExExArgs, MetricArgs, StaticFileRecompressionArgs};
 pub use reth_engine_primitives::{
     DEFAULT_MEMORY_BLOCK_BUFFER_TARGET, DEFAULT_PERSISTENCE_THRESHOLD, DEFAULT_RESERVED_CPU_CORES,
 };
@@ -41,6 +41,9 @@ pub use reth_engine_primitives::{
 /// Default size of cross-block cache in megabytes.
 pub const DEFAULT_CROSS_BLOCK_CACHE_SIZE_MB: usize = 4 * 1024;
 
+/// Default max size of the persisted precompile cache in megabytes.
+pub const DEFAULT_PRECOMPILE_CACHE_MAX_PERSISTED_SIZE_MB: usize = 64;
+
 /// This includes all necessary configuration to launch the node.
 /// The individual configuration options can be overwritten before launching the node.
 ///
@@ -149,9 +152,15 @@ pub struct NodeConfig<ChainSpec> {
     /// All ERA import related arguments with --era prefix
     pub era: EraArgs,
 
+    /// All `ExEx` related arguments with --exex prefix
+    pub exex: ExExArgs,
+
     /// All static files related arguments
     pub static_files: StaticFilesArgs,
 
+    /// Background static file recompression arguments
+    pub static_file_recompression: StaticFileRecompressionArgs,
+
     /// All state database related arguments
     pub statedb: StateDbArgs,
 
@@ -187,7 +196,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             datadir: DatadirArgs::default(),
             engine: EngineArgs::default(),
             era: EraArgs::default(),
+            exex: ExExArgs::default(),
             static_files: StaticFilesArgs::default(),
+            static_file_recompression: StaticFileRecompressionArgs::default(),
             statedb: StateDbArgs::default(),
             storage: StorageArgs::default(),
         }
@@ -263,7 +274,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning,
             engine,
             era,
+            exex,
             static_files,
+            static_file_recompression,
             statedb,
             storage,
             ..
@@ -284,7 +297,9 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning,
             engine,
             era,
+            exex,
             static_files,
+            static_file_recompression,
             statedb,
             storage,
         }
@@ -363,6 +378,21 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
         self
     }
 
+    /// Set the background static file recompression args for the node
+    pub const fn with_static_file_recompression(
+        mut self,
+        static_file_recompression: StaticFileRecompressionArgs,
+    ) -> Self {
+        self.static_file_recompression = static_file_recompression;
+        self
+    }
+
+    /// Set the built-in `ExEx` args for the node
+    pub fn with_exex(mut self, exex: ExExArgs) -> Self {
+        self.exex = exex;
+        self
+    }
+
     /// Set the pruning args for the node
     pub fn with_pruning(mut self, pruning: PruningArgs) -> Self {
         self.pruning = pruning;
@@ -583,8 +613,10 @@ impl<ChainSpec> NodeConfig<ChainSpec> {
             pruning: self.pruning,
             engine: self.engine,
             era: self.era,
+            exex: self.exex,
             statedb: self.statedb,
             static_files: self.static_files,
+            static_file_recompression: self.static_file_recompression,
             storage: self.storage,
         }
     }
@@ -626,8 +658,10 @@ impl<ChainSpec> Clone for NodeConfig<ChainSpec> {
             datadir: self.datadir.clone(),
             engine: self.engine.clone(),
             era: self.era.clone(),
+            exex: self.exex.clone(),
             statedb: self.statedb.clone(),
             static_files: self.static_files,
+            static_file_recompression: self.static_file_recompression,
             storage: self.storage,
         }
     }
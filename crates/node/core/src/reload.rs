@@ -0,0 +1,45 @@
+//! Helper types for reloading the configuration of a running node.
+
+use reth_config::ConfigChanges;
+use tokio::sync::{mpsc, oneshot};
+
+/// A request to reload the configuration file, answered with the applied changes.
+pub type ConfigReloadRequest = oneshot::Sender<eyre::Result<ConfigChanges>>;
+
+/// A handle to the task that reloads the configuration file of a running node.
+#[derive(Debug, Clone)]
+pub struct ConfigReloadHandle {
+    to_reloader: mpsc::UnboundedSender<ConfigReloadRequest>,
+}
+
+impl ConfigReloadHandle {
+    /// Creates a new handle that sends reload requests to the given channel.
+    pub const fn new(to_reloader: mpsc::UnboundedSender<ConfigReloadRequest>) -> Self {
+        Self { to_reloader }
+    }
+
+    /// Creates a new handle and the receiver of its reload requests.
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<ConfigReloadRequest>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        (Self::new(tx), rx)
+    }
+
+    /// Reloads the configuration file and returns the changes that were applied.
+    ///
+    /// Fails without applying anything if the file contains changes that can't be applied to a
+    /// running node.
+    pub async fn reload(&self) -> eyre::Result<ConfigChanges> {
+        let (tx, rx) = oneshot::channel();
+        self.to_reloader
+            .send(tx)
+            .map_err(|_| eyre::eyre!("configuration reloading is not running"))?;
+        rx.await.map_err(|_| eyre::eyre!("configuration reloading stopped"))?
+    }
+}
+
+impl Default for ConfigReloadHandle {
+    /// Returns a handle that isn't connected to a reloader, so reloading always fails.
+    fn default() -> Self {
+        Self::channel().0
+    }
+}
@@ -29,6 +29,8 @@ pub struct PrunerBuilder {
     minimum_pruning_distance: Option<u64>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Receiver of prune modes that replace [`Self::segments`] while the pruner is running.
+    prune_modes_updates: Option<watch::Receiver<PruneModes>>,
 }
 
 impl PrunerBuilder {
@@ -83,6 +85,13 @@ impl PrunerBuilder {
         self
     }
 
+    /// Sets the receiver of prune modes that replace the configured segments while the pruner is
+    /// running.
+    pub fn prune_modes_updates(mut self, prune_modes: watch::Receiver<PruneModes>) -> Self {
+        self.prune_modes_updates = Some(prune_modes);
+        self
+    }
+
     /// Builds a [Pruner] from the current configuration with the given provider factory.
     pub fn build_with_provider_factory<PF>(self, provider_factory: PF) -> Pruner<PF::ProviderRW, PF>
     where
@@ -103,8 +112,8 @@ impl PrunerBuilder {
                 Primitives = <PF::ProviderRW as NodePrimitivesProvider>::Primitives,
             >,
     {
-        let segments =
-            SegmentSet::from_components(provider_factory.static_file_provider(), self.segments);
+        let static_file_provider = provider_factory.static_file_provider();
+        let segments = SegmentSet::from_components(static_file_provider.clone(), self.segments);
 
         let mut pruner = Pruner::new_with_factory(
             provider_factory,
@@ -117,6 +126,15 @@ impl PrunerBuilder {
         if let Some(distance) = self.minimum_pruning_distance {
             pruner = pruner.with_minimum_pruning_distance(distance);
         }
+        if let Some(prune_modes) = self.prune_modes_updates {
+            pruner = pruner.with_prune_modes_updates(
+                prune_modes,
+                Box::new(move |prune_modes| {
+                    SegmentSet::from_components(static_file_provider.clone(), prune_modes)
+                        .into_vec()
+                }),
+            );
+        }
         pruner
     }
 
@@ -139,7 +157,8 @@ impl PrunerBuilder {
             + StorageChangeSetReader
             + RocksDBProviderFactory,
     {
-        let segments = SegmentSet::<Provider>::from_components(static_file_provider, self.segments);
+        let segments =
+            SegmentSet::<Provider>::from_components(static_file_provider.clone(), self.segments);
 
         let mut pruner = Pruner::new(
             segments.into_vec(),
@@ -151,6 +170,18 @@ impl PrunerBuilder {
         if let Some(distance) = self.minimum_pruning_distance {
             pruner = pruner.with_minimum_pruning_distance(distance);
         }
+        if let Some(prune_modes) = self.prune_modes_updates {
+            pruner = pruner.with_prune_modes_updates(
+                prune_modes,
+                Box::new(move |prune_modes| {
+                    SegmentSet::<Provider>::from_components(
+                        static_file_provider.clone(),
+                        prune_modes,
+                    )
+                    .into_vec()
+                }),
+            );
+        }
         pruner
     }
 }
@@ -164,6 +195,7 @@ impl Default for PrunerBuilder {
             timeout: None,
             minimum_pruning_distance: None,
             finished_exex_height: watch::channel(FinishedExExHeight::NoExExs).1,
+            prune_modes_updates: None,
         }
     }
 }
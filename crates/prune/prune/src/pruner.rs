@@ -11,12 +11,12 @@ use reth_provider::{
     DBProvider, DatabaseProviderFactory, PruneCheckpointReader, PruneCheckpointWriter,
     StageCheckpointReader,
 };
-use reth_prune_types::{PruneProgress, PrunedSegmentInfo, PrunerOutput};
+use reth_prune_types::{PruneModes, PruneProgress, PrunedSegmentInfo, PrunerOutput};
 use reth_stages_types::StageId;
 use reth_tokio_util::{EventSender, EventStream};
-use std::time::Duration;
+use std::{fmt, time::Duration};
 use tokio::sync::watch;
-use tracing::{debug, instrument};
+use tracing::{debug, info, instrument};
 
 /// Result of [`Pruner::run`] execution.
 pub type PrunerResult = Result<PrunerOutput, PrunerError>;
@@ -27,6 +27,22 @@ pub type PrunerWithResult<S, DB> = (Pruner<S, DB>, PrunerResult);
 /// Pruner with preset provider factory.
 pub type PrunerWithFactory<PF> = Pruner<<PF as DatabaseProviderFactory>::ProviderRW, PF>;
 
+/// Builds the prune segments for the given [`PruneModes`].
+pub type SegmentsBuilder<Provider> =
+    Box<dyn Fn(PruneModes) -> Vec<Box<dyn Segment<Provider>>> + Send + Sync>;
+
+/// Receives new [`PruneModes`] for a running [`Pruner`] and rebuilds its segments from them.
+struct PruneModesUpdates<Provider> {
+    prune_modes: watch::Receiver<PruneModes>,
+    build_segments: SegmentsBuilder<Provider>,
+}
+
+impl<Provider> fmt::Debug for PruneModesUpdates<Provider> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PruneModesUpdates").field("prune_modes", &self.prune_modes).finish()
+    }
+}
+
 /// Pruning routine. Main pruning logic happens in [`Pruner::run`].
 #[derive(Debug)]
 pub struct Pruner<Provider, PF> {
@@ -49,6 +65,8 @@ pub struct Pruner<Provider, PF> {
     minimum_pruning_distance: Option<u64>,
     /// The finished height of all `ExEx`'s.
     finished_exex_height: watch::Receiver<FinishedExExHeight>,
+    /// Updates of the prune modes that the segments are rebuilt from before the next run.
+    prune_modes_updates: Option<PruneModesUpdates<Provider>>,
     #[doc(hidden)]
     metrics: Metrics,
     event_sender: EventSender<PrunerEvent>,
@@ -72,6 +90,7 @@ impl<Provider> Pruner<Provider, ()> {
             timeout,
             minimum_pruning_distance: None,
             finished_exex_height,
+            prune_modes_updates: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -100,6 +119,7 @@ where
             timeout,
             minimum_pruning_distance: None,
             finished_exex_height,
+            prune_modes_updates: None,
             metrics: Metrics::default(),
             event_sender: Default::default(),
         }
@@ -112,6 +132,29 @@ impl<Provider, S> Pruner<Provider, S> {
         self.minimum_pruning_distance = Some(distance);
         self
     }
+
+    /// Rebuilds the segments with `build_segments` whenever new [`PruneModes`] are sent to
+    /// `prune_modes`. The new segments are used starting with the next run.
+    pub fn with_prune_modes_updates(
+        mut self,
+        prune_modes: watch::Receiver<PruneModes>,
+        build_segments: SegmentsBuilder<Provider>,
+    ) -> Self {
+        self.prune_modes_updates = Some(PruneModesUpdates { prune_modes, build_segments });
+        self
+    }
+
+    /// Rebuilds the segments if new [`PruneModes`] were received since the last run.
+    fn apply_prune_modes_updates(&mut self) {
+        let Some(updates) = &mut self.prune_modes_updates else { return };
+        if !updates.prune_modes.has_changed().unwrap_or_default() {
+            return
+        }
+
+        let prune_modes = updates.prune_modes.borrow_and_update().clone();
+        self.segments = (updates.build_segments)(prune_modes);
+        info!(target: "pruner", segments = self.segments.len(), "Prune modes updated");
+    }
 }
 
 impl<Provider, S> Pruner<Provider, S>
@@ -139,6 +182,8 @@ where
         provider: &Provider,
         tip_block_number: BlockNumber,
     ) -> PrunerResult {
+        self.apply_prune_modes_updates();
+
         let Some(tip_block_number) =
             self.adjust_tip_block_number_to_finished_exex_height(tip_block_number)
         else {
@@ -380,9 +425,10 @@ fn is_stage_finished<Provider: StageCheckpointReader>(
 
 #[cfg(test)]
 mod tests {
-    use crate::Pruner;
+    use crate::{Pruner, PrunerBuilder};
     use reth_exex_types::FinishedExExHeight;
     use reth_provider::test_utils::create_test_provider_factory;
+    use reth_prune_types::{PruneMode, PruneModes, PruneSegment};
 
     #[test]
     fn is_pruning_needed() {
@@ -423,4 +469,32 @@ mod tests {
         finished_exex_height_tx.send(FinishedExExHeight::Height(third_block_number)).unwrap();
         assert!(pruner.is_pruning_needed(third_block_number));
     }
+
+    #[test]
+    fn prune_modes_updates() {
+        let provider_factory = create_test_provider_factory();
+        let (prune_modes_tx, prune_modes_rx) = tokio::sync::watch::channel(PruneModes::default());
+
+        let mut pruner = PrunerBuilder::default()
+            .prune_modes_updates(prune_modes_rx)
+            .build_with_provider_factory(provider_factory);
+        assert!(pruner.segments.is_empty());
+
+        // Segments are only rebuilt if the prune modes changed
+        pruner.apply_prune_modes_updates();
+        assert!(pruner.segments.is_empty());
+
+        let prune_modes = PruneModes {
+            account_history: Some(PruneMode::Distance(10_064)),
+            storage_history: Some(PruneMode::Distance(10_064)),
+            ..Default::default()
+        };
+        prune_modes_tx.send(prune_modes).unwrap();
+        pruner.apply_prune_modes_updates();
+
+        assert_eq!(
+            pruner.segments.iter().map(|segment| segment.segment()).collect::<Vec<_>>(),
+            vec![PruneSegment::AccountHistory, PruneSegment::StorageHistory]
+        );
+    }
 }
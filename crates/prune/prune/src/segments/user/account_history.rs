@@ -6,8 +6,10 @@ use crate::{
     },
     PrunerError,
 };
-use alloy_primitives::BlockNumber;
-use reth_db_api::{models::ShardedKey, tables, transaction::DbTxMut};
+use alloy_primitives::{Address, BlockNumber};
+use reth_db_api::{
+    cursor::DbDupCursorRO, models::ShardedKey, tables, transaction::DbTxMut, DatabaseError,
+};
 use reth_provider::{
     changeset_walker::StaticFileAccountChangesetWalker, DBProvider, EitherWriter,
     RocksDBProviderFactory, StaticFileProviderFactory,
@@ -148,6 +150,11 @@ impl AccountHistory {
         }
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets from static files)");
 
+        let last_fully_pruned_block = last_changeset_pruned_block
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+        prune_balance_changes(provider, &highest_deleted_accounts, last_fully_pruned_block)?;
+
         let result = HistoryPruneResult {
             highest_deleted: highest_deleted_accounts,
             last_pruned_block: last_changeset_pruned_block,
@@ -210,6 +217,11 @@ impl AccountHistory {
             )?;
         trace!(target: "pruner", pruned = %pruned_changesets, %done, "Pruned account history (changesets from database)");
 
+        let last_fully_pruned_block = last_changeset_pruned_block
+            .map(|block_number| if done { block_number } else { block_number.saturating_sub(1) })
+            .unwrap_or(range_end);
+        prune_balance_changes(provider, &highest_deleted_accounts, last_fully_pruned_block)?;
+
         let result = HistoryPruneResult {
             highest_deleted: highest_deleted_accounts,
             last_pruned_block: last_changeset_pruned_block,
@@ -239,7 +251,10 @@ impl AccountHistory {
         range_end: BlockNumber,
     ) -> Result<SegmentOutput, PrunerError>
     where
-        Provider: DBProvider + StaticFileProviderFactory + ChangeSetReader + RocksDBProviderFactory,
+        Provider: DBProvider<Tx: DbTxMut>
+            + StaticFileProviderFactory
+            + ChangeSetReader
+            + RocksDBProviderFactory,
     {
         // Unlike MDBX path, we don't divide the limit by 2 because RocksDB path only prunes
         // history shards (no separate changeset table to delete from). The changesets are in
@@ -284,7 +299,8 @@ impl AccountHistory {
         let mut updated_shards = 0usize;
 
         // Sort by address for better RocksDB cache locality
-        let mut sorted_accounts: Vec<_> = highest_deleted_accounts.into_iter().collect();
+        let mut sorted_accounts: Vec<_> =
+            highest_deleted_accounts.iter().map(|(addr, highest)| (*addr, *highest)).collect();
         sorted_accounts.sort_unstable_by_key(|(addr, _)| *addr);
 
         provider.with_rocksdb_batch(|mut batch| {
@@ -301,6 +317,8 @@ impl AccountHistory {
         })?;
         trace!(target: "pruner", deleted = deleted_shards, updated = updated_shards, %done, "Pruned account history (RocksDB indices)");
 
+        prune_balance_changes(provider, &highest_deleted_accounts, last_changeset_pruned_block)?;
+
         // Delete static file jars only when fully processed. During provider.commit(), RocksDB
         // batch is committed before the MDBX checkpoint. If crash occurs after RocksDB commit
         // but before MDBX commit, on restart the pruner checkpoint indicates data needs
@@ -326,6 +344,36 @@ impl AccountHistory {
     }
 }
 
+/// Deletes the entries of [`tables::AccountBalanceChanges`] of the accounts with pruned
+/// changesets, up to their highest pruned block.
+///
+/// The table is only populated if the balance changes index is enabled, so this is a no-op
+/// otherwise.
+fn prune_balance_changes<Provider>(
+    provider: &Provider,
+    highest_deleted_accounts: &FxHashMap<Address, BlockNumber>,
+    last_pruned_block: BlockNumber,
+) -> Result<usize, DatabaseError>
+where
+    Provider: DBProvider<Tx: DbTxMut>,
+{
+    let mut cursor = provider.tx_ref().cursor_dup_write::<tables::AccountBalanceChanges>()?;
+    let mut deleted = 0;
+    for (address, highest) in highest_deleted_accounts {
+        let to_block = (*highest).min(last_pruned_block);
+        let mut walker = cursor.walk_dup(Some(*address), None)?;
+        while let Some((_, change)) = walker.next().transpose()? &&
+            change.block_number <= to_block
+        {
+            walker.delete_current()?;
+            deleted += 1;
+        }
+    }
+    trace!(target: "pruner", %deleted, "Pruned account balance changes");
+
+    Ok(deleted)
+}
+
 #[cfg(test)]
 mod tests {
     use super::ACCOUNT_HISTORY_TABLES_TO_PRUNE;
@@ -1,5 +1,7 @@
+use alloy_primitives::Address;
 use alloy_rpc_types_admin::{NodeInfo, PeerInfo};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use reth_engine_primitives::PrecompileCacheStats;
 use reth_network_peers::{AnyNode, NodeRecord};
 
 /// Admin namespace rpc interface that gives access to several non-standard RPC methods.
@@ -50,4 +52,58 @@ pub trait AdminApi {
     /// Returns the number of transactions that were removed from the pool.
     #[method(name = "clearTxpool")]
     async fn clear_txpool(&self) -> RpcResult<u64>;
+
+    /// Adds the given sender to the deny list of the transaction pool.
+    ///
+    /// Transactions from denied senders are rejected before validation.
+    /// Returns true if the sender was not already denied.
+    #[method(name = "txpoolDenySender")]
+    fn txpool_deny_sender(&self, sender: Address) -> RpcResult<bool>;
+
+    /// Removes the given sender from the deny list of the transaction pool.
+    ///
+    /// Returns true if the sender was denied.
+    #[method(name = "txpoolUndenySender")]
+    fn txpool_undeny_sender(&self, sender: Address) -> RpcResult<bool>;
+
+    /// Replaces the sender allow and deny lists of the transaction pool.
+    ///
+    /// If an allow list is given, only transactions of allowed senders are accepted. Passing
+    /// `null` disables the allow list.
+    #[method(name = "setTxpoolSenderLists")]
+    fn set_txpool_sender_lists(
+        &self,
+        allow: Option<Vec<Address>>,
+        deny: Vec<Address>,
+    ) -> RpcResult<bool>;
+}
+
+/// An extension to the `admin_` namespace for reloading the configuration file of the node.
+///
+/// This is separate from the regular `admin_` api, because reloading is handled by the node
+/// rather than by the RPC components.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminReloadApi {
+    /// Reloads the configuration file and applies the prune, transaction pool and peer settings
+    /// that can be changed while the node is running.
+    ///
+    /// Returns a description of every applied change. Fails without applying anything if the file
+    /// contains changes that require a restart.
+    #[method(name = "reloadConfig")]
+    async fn reload_config(&self) -> RpcResult<Vec<String>>;
+}
+
+/// An extension to the `admin_` namespace for inspecting and clearing the precompile caches of the
+/// engine.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "admin"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "admin"))]
+pub trait AdminPrecompileCacheApi {
+    /// Returns the size and hit statistics of the cache of every precompile.
+    #[method(name = "precompileCacheStats")]
+    fn precompile_cache_stats(&self) -> RpcResult<Vec<PrecompileCacheStats>>;
+
+    /// Clears the precompile caches, returning the number of removed entries.
+    #[method(name = "clearPrecompileCache")]
+    fn clear_precompile_cache(&self) -> RpcResult<u64>;
 }
@@ -40,25 +40,25 @@ pub use servers::*;
 /// Aggregates all server traits.
 pub mod servers {
     pub use crate::{
-        admin::AdminApiServer,
+        admin::{AdminApiServer, AdminPrecompileCacheApiServer, AdminReloadApiServer},
         debug::{DebugApiServer, DebugExecutionWitnessApiServer},
         engine::{EngineApiServer, EngineEthApiServer, IntoEngineApiRpcModule},
         mev::{MevFullApiServer, MevSimApiServer},
         miner::MinerApiServer,
         net::NetApiServer,
         otterscan::OtterscanServer,
-        reth::RethApiServer,
+        reth::{AddressBalanceChange, AddressBalanceChanges, RethApiServer},
         reth_engine::{RethEngineApiServer, RethNewPayloadInput, RethPayloadStatus},
         rpc::RpcApiServer,
         testing::TestingApiServer,
         trace::TraceApiServer,
-        txpool::TxPoolApiServer,
+        txpool::{TxPoolApiServer, TxpoolStatusResponse, TxpoolTransactionStatus},
         validation::BlockSubmissionValidationApiServer,
         web3::Web3ApiServer,
     };
     pub use reth_rpc_eth_api::{
         self as eth, EthApiServer, EthBundleApiServer, EthCallBundleApiServer, EthConfigApiServer,
-        EthFilterApiServer, EthPubSubApiServer, L2EthApiExtServer,
+        EthFilterApiServer, EthPrivateTransactionApiServer, EthPubSubApiServer, L2EthApiExtServer,
     };
 }
 
@@ -70,7 +70,7 @@ pub use clients::*;
 #[cfg(feature = "client")]
 pub mod clients {
     pub use crate::{
-        admin::AdminApiClient,
+        admin::{AdminApiClient, AdminPrecompileCacheApiClient, AdminReloadApiClient},
         anvil::AnvilApiClient,
         debug::{DebugApiClient, DebugExecutionWitnessApiClient},
         engine::{EngineApiClient, EngineEthApiClient},
@@ -90,6 +90,6 @@ pub mod clients {
     };
     pub use reth_rpc_eth_api::{
         EthApiClient, EthBundleApiClient, EthCallBundleApiClient, EthConfigApiClient,
-        EthFilterApiClient, L2EthApiExtClient,
+        EthFilterApiClient, EthPrivateTransactionApiClient, L2EthApiExtClient,
     };
 }
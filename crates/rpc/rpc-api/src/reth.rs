@@ -1,10 +1,35 @@
 use alloy_eips::BlockId;
-use alloy_primitives::{map::AddressMap, U256, U64};
+use alloy_primitives::{map::AddressMap, Address, U256, U64};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
 
 // Required for the subscription attributes below
 use reth_chain_state as _;
 
+/// A balance change of an account, returned by `reth_getBalanceChangesForAddress`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBalanceChange {
+    /// The block in which the balance changed.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// The balance before the block.
+    pub before: U256,
+    /// The balance after the block.
+    pub after: U256,
+}
+
+/// A page of balance changes of an account, returned by `reth_getBalanceChangesForAddress`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AddressBalanceChanges {
+    /// The balance changes, sorted by block number.
+    pub changes: Vec<AddressBalanceChange>,
+    /// The block to request the next page from, if the page is full.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub next_block: Option<u64>,
+}
+
 /// Reth API namespace for reth-specific methods
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "reth"))]
 #[cfg_attr(feature = "client", rpc(server, client, namespace = "reth"))]
@@ -16,6 +41,21 @@ pub trait RethApi {
         block_id: BlockId,
     ) -> RpcResult<AddressMap<U256>>;
 
+    /// Returns the balance changes of an account in a block range, sorted by block number.
+    ///
+    /// Requires the balance changes index to be enabled with `stages.index_balance_changes`.
+    /// Only persisted blocks are indexed, so `to_block` is capped at the tip of the index, which
+    /// is also the default. At most `limit` changes are returned per page, 1000 by default and at
+    /// most.
+    #[method(name = "getBalanceChangesForAddress")]
+    async fn reth_get_balance_changes_for_address(
+        &self,
+        address: Address,
+        from_block: U64,
+        to_block: Option<U64>,
+        limit: Option<U64>,
+    ) -> RpcResult<AddressBalanceChanges>;
+
     /// Re-executes a block (or a range of blocks) and returns the execution outcome including
     /// receipts, state changes, and EIP-7685 requests.
     ///
@@ -1,7 +1,36 @@
 use alloy_json_rpc::RpcObject;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash, U256};
 use alloy_rpc_types_txpool::{TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolStatus};
 use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The state of a single transaction in the txpool, returned by `txpool_status(hash)`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolTransactionStatus {
+    /// The sub-pool that contains the transaction: `pending`, `basefee`, `queued` or `blob`.
+    pub subpool: String,
+    /// Why the transaction is parked, if it is not pending.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The lowest nonce of the sender that is missing before this transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none", with = "alloy_serde::quantity::opt")]
+    pub missing_nonce: Option<u64>,
+    /// How much balance the sender is missing to cover this transaction and its ancestors.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub missing_balance: Option<U256>,
+}
+
+/// The response of `txpool_status`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TxpoolStatusResponse {
+    /// The number of pending and queued transactions, if no transaction hash was given.
+    Pool(TxpoolStatus),
+    /// The state of the given transaction, or `None` if it is not in the pool.
+    Transaction(Option<TxpoolTransactionStatus>),
+}
 
 /// Txpool rpc interface.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "txpool"))]
@@ -10,9 +39,12 @@ pub trait TxPoolApi<T: RpcObject> {
     /// Returns the number of transactions currently pending for inclusion in the next block(s), as
     /// well as the ones that are being scheduled for future execution only.
     ///
+    /// With a transaction hash, this instead returns the sub-pool of the transaction, why it is
+    /// parked, and the nonce or balance the sender is missing, if the transaction is in the pool.
+    ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_status) for more details
     #[method(name = "status")]
-    async fn txpool_status(&self) -> RpcResult<TxpoolStatus>;
+    async fn txpool_status(&self, hash: Option<TxHash>) -> RpcResult<TxpoolStatusResponse>;
 
     /// Returns a summary of all the transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
@@ -28,10 +60,22 @@ pub trait TxPoolApi<T: RpcObject> {
     #[method(name = "contentFrom")]
     async fn txpool_content_from(&self, from: Address) -> RpcResult<TxpoolContentFrom<T>>;
 
+    /// Retrieves the queued transactions of this address, grouped by nonce.
+    #[method(name = "queuedFrom")]
+    async fn txpool_queued_from(&self, from: Address) -> RpcResult<BTreeMap<String, T>>;
+
     /// Returns the details of all transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     ///
+    /// Without arguments this behaves like geth. The optional `offset` and `limit` paginate over
+    /// the senders, in the order the pool first saw them, so that all transactions of a sender are
+    /// on the same page.
+    ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     #[method(name = "content")]
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent<T>>;
+    async fn txpool_content(
+        &self,
+        offset: Option<usize>,
+        limit: Option<usize>,
+    ) -> RpcResult<TxpoolContent<T>>;
 }
@@ -56,8 +56,8 @@ use reth_rpc_eth_types::{receipt::EthReceiptConverter, EthConfig, EthSubscriptio
 use reth_rpc_layer::{AuthLayer, Claims, CompressionLayer, JwtAuthValidator, JwtSecret};
 pub use reth_rpc_server_types::RethRpcModule;
 use reth_storage_api::{
-    AccountReader, BlockReader, ChangeSetReader, FullRpcProvider, NodePrimitivesProvider,
-    StateProviderFactory,
+    AccountReader, BalanceChangeReader, BlockReader, ChangeSetReader, FullRpcProvider,
+    NodePrimitivesProvider, StateProviderFactory,
 };
 use reth_tasks::{pool::BlockingTaskGuard, Runtime};
 use reth_tokio_util::EventSender;
@@ -113,6 +113,11 @@ use reth_rpc::eth::sim_bundle::EthSimBundle;
 // Rpc rate limiter
 pub mod rate_limiter;
 
+// Http and ws server exposing the client address
+mod remote_addr;
+pub use remote_addr::RemoteAddrServer;
+use remote_addr::DEFAULT_MAX_CONNECTIONS;
+
 /// A builder type to configure the RPC module: See [`RpcModule`]
 ///
 /// This is the main entrypoint and the easiest way to configure an RPC server.
@@ -317,7 +322,8 @@ where
         + ForkChoiceSubscriptions<Header = N::BlockHeader>
         + PersistedBlockSubscriptions
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + BalanceChangeReader,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EvmConfig: ConfigureEvm<Primitives = N> + 'static,
@@ -665,6 +671,7 @@ where
             Transaction = N::SignedTx,
         > + AccountReader
         + ChangeSetReader
+        + BalanceChangeReader
         + CanonStateSubscriptions<Primitives = N>
         + ForkChoiceSubscriptions<Header = N::BlockHeader>
         + PersistedBlockSubscriptions,
@@ -782,7 +789,8 @@ where
             Transaction = N::SignedTx,
             Receipt = N::Receipt,
         > + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + BalanceChangeReader,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: EthApiTypes,
     EvmConfig: ConfigureEvm<Primitives = N>,
@@ -863,7 +871,8 @@ where
         + ForkChoiceSubscriptions<Header = N::BlockHeader>
         + PersistedBlockSubscriptions
         + AccountReader
-        + ChangeSetReader,
+        + ChangeSetReader
+        + BalanceChangeReader,
     Pool: TransactionPool + Clone + 'static,
     Network: NetworkInfo + Peers + Clone + 'static,
     EthApi: FullEthApiServer,
@@ -979,14 +988,13 @@ where
                             let mut module = eth_api.clone().into_rpc();
                             module.merge(eth_filter.clone().into_rpc()).expect("No conflicts");
                             module.merge(eth_pubsub.clone().into_rpc()).expect("No conflicts");
+                            let bundle =
+                                EthBundle::new(eth_api.clone(), self.blocking_pool_guard.clone());
                             module
-                                .merge(
-                                    EthBundle::new(
-                                        eth_api.clone(),
-                                        self.blocking_pool_guard.clone(),
-                                    )
-                                    .into_rpc(),
-                                )
+                                .merge(EthCallBundleApiServer::into_rpc(bundle.clone()))
+                                .expect("No conflicts");
+                            module
+                                .merge(EthPrivateTransactionApiServer::into_rpc(bundle))
                                 .expect("No conflicts");
 
                             module.into()
@@ -1092,6 +1100,8 @@ pub struct RpcServerConfig<RpcMiddleware = Identity> {
     http_addr: Option<SocketAddr>,
     /// Control whether http responses should be compressed
     http_disable_compression: bool,
+    /// Maximum number of concurrent connections of each of the http and ws servers
+    max_connections: u32,
     /// Configs for WS server
     ws_server_config: Option<ServerConfigBuilder>,
     /// Allowed CORS Domains for ws.
@@ -1118,6 +1128,7 @@ impl Default for RpcServerConfig<Identity> {
             http_cors_domains: None,
             http_addr: None,
             http_disable_compression: false,
+            max_connections: DEFAULT_MAX_CONNECTIONS,
             ws_server_config: None,
             ws_cors_domains: None,
             ws_addr: None,
@@ -1182,6 +1193,7 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             http_cors_domains: self.http_cors_domains,
             http_addr: self.http_addr,
             http_disable_compression: self.http_disable_compression,
+            max_connections: self.max_connections,
             ws_server_config: self.ws_server_config,
             ws_cors_domains: self.ws_cors_domains,
             ws_addr: self.ws_addr,
@@ -1209,6 +1221,15 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         self
     }
 
+    /// Configure the maximum number of concurrent connections of each of the http and ws servers
+    ///
+    /// This should match the `max_connections` of the configured [`ServerConfigBuilder`]s, which
+    /// the servers can't read back.
+    pub const fn with_max_connections(mut self, max_connections: u32) -> Self {
+        self.max_connections = max_connections;
+        self
+    }
+
     /// Configure the cors domains for HTTP
     pub fn with_http_cors(mut self, cors_domain: Option<String>) -> Self {
         self.http_cors_domains = cors_domain;
@@ -1386,33 +1407,34 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
             modules.config.ensure_ws_http_identical()?;
 
             if let Some(config) = self.http_server_config {
-                let server = ServerBuilder::new()
-                    .set_http_middleware(
-                        tower::ServiceBuilder::new()
-                            .option_layer(Self::maybe_cors_layer(cors)?)
-                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                            .option_layer(Self::maybe_compression_layer(
-                                self.http_disable_compression,
-                            )),
-                    )
-                    .set_rpc_middleware(
-                        RpcServiceBuilder::default()
-                            .layer(
-                                modules
-                                    .http
-                                    .as_ref()
-                                    .or(modules.ws.as_ref())
-                                    .map(RpcRequestMetrics::same_port)
-                                    .unwrap_or_default(),
-                            )
-                            .layer(self.rpc_middleware.clone()),
-                    )
-                    .set_config(config.build())
-                    .build(http_socket_addr)
-                    .await
-                    .map_err(|err| {
-                        RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
-                    })?;
+                let server = RemoteAddrServer::bind(
+                    ServerBuilder::new()
+                        .set_http_middleware(
+                            tower::ServiceBuilder::new()
+                                .option_layer(Self::maybe_cors_layer(cors)?)
+                                .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                                .option_layer(Self::maybe_compression_layer(
+                                    self.http_disable_compression,
+                                )),
+                        )
+                        .set_rpc_middleware(
+                            RpcServiceBuilder::default()
+                                .layer(
+                                    modules
+                                        .http
+                                        .as_ref()
+                                        .or(modules.ws.as_ref())
+                                        .map(RpcRequestMetrics::same_port)
+                                        .unwrap_or_default(),
+                                )
+                                .layer(self.rpc_middleware.clone()),
+                        )
+                        .set_config(config.build()),
+                    http_socket_addr,
+                    self.max_connections,
+                )
+                .await
+                .map_err(|err| RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr)))?;
                 let addr = server.local_addr().map_err(|err| {
                     RpcError::server_error(err, ServerKind::WsHttp(http_socket_addr))
                 })?;
@@ -1439,21 +1461,26 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         let mut http_server = None;
 
         if let Some(config) = self.ws_server_config {
-            let server = ServerBuilder::new()
-                .set_config(config.ws_only().build())
-                .set_http_middleware(
-                    tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
-                )
-                .set_rpc_middleware(
-                    RpcServiceBuilder::default()
-                        .layer(modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default())
-                        .layer(self.rpc_middleware.clone()),
-                )
-                .build(ws_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
+            let server = RemoteAddrServer::bind(
+                ServerBuilder::new()
+                    .set_config(config.ws_only().build())
+                    .set_http_middleware(
+                        tower::ServiceBuilder::new()
+                            .option_layer(Self::maybe_cors_layer(self.ws_cors_domains.clone())?)
+                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret)),
+                    )
+                    .set_rpc_middleware(
+                        RpcServiceBuilder::default()
+                            .layer(
+                                modules.ws.as_ref().map(RpcRequestMetrics::ws).unwrap_or_default(),
+                            )
+                            .layer(self.rpc_middleware.clone()),
+                    ),
+                ws_socket_addr,
+                self.max_connections,
+            )
+            .await
+            .map_err(|err| RpcError::server_error(err, ServerKind::WS(ws_socket_addr)))?;
 
             let addr = server
                 .local_addr()
@@ -1464,24 +1491,33 @@ impl<RpcMiddleware> RpcServerConfig<RpcMiddleware> {
         }
 
         if let Some(config) = self.http_server_config {
-            let server = ServerBuilder::new()
-                .set_config(config.http_only().build())
-                .set_http_middleware(
-                    tower::ServiceBuilder::new()
-                        .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
-                        .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
-                        .option_layer(Self::maybe_compression_layer(self.http_disable_compression)),
-                )
-                .set_rpc_middleware(
-                    RpcServiceBuilder::default()
-                        .layer(
-                            modules.http.as_ref().map(RpcRequestMetrics::http).unwrap_or_default(),
-                        )
-                        .layer(self.rpc_middleware.clone()),
-                )
-                .build(http_socket_addr)
-                .await
-                .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
+            let server = RemoteAddrServer::bind(
+                ServerBuilder::new()
+                    .set_config(config.http_only().build())
+                    .set_http_middleware(
+                        tower::ServiceBuilder::new()
+                            .option_layer(Self::maybe_cors_layer(self.http_cors_domains.clone())?)
+                            .option_layer(Self::maybe_jwt_layer(self.jwt_secret))
+                            .option_layer(Self::maybe_compression_layer(
+                                self.http_disable_compression,
+                            )),
+                    )
+                    .set_rpc_middleware(
+                        RpcServiceBuilder::default()
+                            .layer(
+                                modules
+                                    .http
+                                    .as_ref()
+                                    .map(RpcRequestMetrics::http)
+                                    .unwrap_or_default(),
+                            )
+                            .layer(self.rpc_middleware.clone()),
+                    ),
+                http_socket_addr,
+                self.max_connections,
+            )
+            .await
+            .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
             let local_addr = server
                 .local_addr()
                 .map_err(|err| RpcError::server_error(err, ServerKind::Http(http_socket_addr)))?;
@@ -0,0 +1,188 @@
+//! HTTP and WS server that exposes the address of the client to the RPC handlers.
+
+use alloy_primitives::{hex, keccak256, Address, Signature};
+use http_body_util::{BodyExt, Full, Limited};
+use jsonrpsee::server::{
+    serve_with_graceful_shutdown, stop_channel, HttpBody, HttpRequest, HttpResponse, Methods,
+    ServerBuilder, ServerHandle, TowerService, TowerServiceBuilder,
+};
+use reth_rpc_server_types::{RemoteAddr, RpcRequestSigner, FLASHBOTS_SIGNATURE_HEADER};
+use std::{io, net::SocketAddr, sync::Arc, time::Duration};
+use tokio::{net::TcpListener, sync::Semaphore};
+use tower::{BoxError, Service};
+use tracing::debug;
+
+/// Maximum size of a request body that is buffered to verify its signature.
+const MAX_SIGNED_REQUEST_SIZE: usize = 10 * 1024 * 1024;
+
+/// Default maximum number of concurrent connections, the same as the default of
+/// [`ServerConfigBuilder`](jsonrpsee::server::ServerConfigBuilder).
+pub(crate) const DEFAULT_MAX_CONNECTIONS: u32 = 100;
+
+/// Time to wait before accepting connections again after accepting one failed, e.g. because the
+/// process ran out of file descriptors.
+const ACCEPT_ERROR_BACKOFF: Duration = Duration::from_millis(100);
+
+/// A bound HTTP or WS server that inserts the [`RemoteAddr`] of each connection into the
+/// extensions of its requests.
+///
+/// If a request carries a valid [`FLASHBOTS_SIGNATURE_HEADER`], the [`RpcRequestSigner`] is
+/// inserted as well.
+///
+/// This behaves like [`Server`](jsonrpsee::server::Server), which does not expose the address of
+/// the client to the RPC handlers, e.g. for the per-client admission policies of the pool.
+/// Connections beyond the maximum number of concurrent connections are only accepted once another
+/// one is closed.
+#[derive(Debug)]
+pub struct RemoteAddrServer<HttpMiddleware, RpcMiddleware> {
+    listener: TcpListener,
+    builder: TowerServiceBuilder<RpcMiddleware, HttpMiddleware>,
+    connections: Arc<Semaphore>,
+}
+
+impl<HttpMiddleware, RpcMiddleware> RemoteAddrServer<HttpMiddleware, RpcMiddleware> {
+    /// Binds the server to the given address.
+    ///
+    /// The server accepts at most `max_connections` concurrent connections, which should match
+    /// the `max_connections` of the builder's config.
+    pub async fn bind(
+        builder: ServerBuilder<HttpMiddleware, RpcMiddleware>,
+        addr: SocketAddr,
+        max_connections: u32,
+    ) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr).await?;
+        Ok(Self {
+            listener,
+            builder: builder.to_service_builder(),
+            connections: Arc::new(Semaphore::new(max_connections as usize)),
+        })
+    }
+
+    /// Returns the address the server is bound to.
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Starts serving the given methods in the background.
+    pub fn start(self, methods: impl Into<Methods>) -> ServerHandle
+    where
+        TowerServiceBuilder<RpcMiddleware, HttpMiddleware>: Clone + Send + 'static,
+        TowerService<RpcMiddleware, HttpMiddleware>: Service<
+                HttpRequest<HttpBody>,
+                Response = HttpResponse,
+                Error = BoxError,
+                Future: Send,
+            > + Clone
+            + Send
+            + 'static,
+    {
+        let Self { listener, builder, connections } = self;
+        let methods = methods.into();
+        let (stop_handle, server_handle) = stop_channel();
+
+        tokio::spawn(async move {
+            loop {
+                let permit = tokio::select! {
+                    permit = connections.clone().acquire_owned() => match permit {
+                        Ok(permit) => permit,
+                        Err(_) => break,
+                    },
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+                let (stream, remote_addr) = tokio::select! {
+                    res = listener.accept() => match res {
+                        Ok(conn) => conn,
+                        Err(err) => {
+                            debug!(target: "rpc", %err, "Failed to accept connection");
+                            tokio::time::sleep(ACCEPT_ERROR_BACKOFF).await;
+                            continue
+                        }
+                    },
+                    _ = stop_handle.clone().shutdown() => break,
+                };
+                let _ = stream.set_nodelay(true);
+
+                let service = builder.clone().build(methods.clone(), stop_handle.clone());
+                let service = tower::service_fn(move |req: HttpRequest<hyper::body::Incoming>| {
+                    let mut service = service.clone();
+                    async move {
+                        let mut req = with_request_signer(req).await?;
+                        req.extensions_mut().insert(RemoteAddr(remote_addr));
+                        service.call(req).await
+                    }
+                });
+                let connection =
+                    serve_with_graceful_shutdown(stream, service, stop_handle.clone().shutdown());
+                tokio::spawn(async move {
+                    let _ = connection.await;
+                    drop(permit);
+                });
+            }
+        });
+
+        server_handle
+    }
+}
+
+/// Inserts the [`RpcRequestSigner`] into the extensions of the request if it carries a valid
+/// [`FLASHBOTS_SIGNATURE_HEADER`].
+///
+/// The body of signed requests is buffered to verify the signature.
+async fn with_request_signer(
+    req: HttpRequest<hyper::body::Incoming>,
+) -> Result<HttpRequest<HttpBody>, BoxError> {
+    let Some(signature) = req.headers().get(FLASHBOTS_SIGNATURE_HEADER).cloned() else {
+        return Ok(req.map(HttpBody::new))
+    };
+
+    let (mut parts, body) = req.into_parts();
+    let body = Limited::new(body, MAX_SIGNED_REQUEST_SIZE).collect().await?.to_bytes();
+    match signature.to_str().ok().and_then(|signature| recover_request_signer(signature, &body)) {
+        Some(signer) => {
+            parts.extensions.insert(RpcRequestSigner(signer));
+        }
+        None => debug!(target: "rpc", "Ignoring invalid request signature"),
+    }
+    Ok(HttpRequest::from_parts(parts, HttpBody::new(Full::new(body))))
+}
+
+/// Recovers the signer of a request body from the value of a [`FLASHBOTS_SIGNATURE_HEADER`].
+///
+/// Returns `None` if the signature is malformed or was not created by the given address.
+fn recover_request_signer(header: &str, body: &[u8]) -> Option<Address> {
+    let (address, signature) = header.split_once(':')?;
+    let address = address.parse::<Address>().ok()?;
+    let signature = Signature::from_raw(&hex::decode(signature).ok()?).ok()?;
+    let message = hex::encode_prefixed(keccak256(body));
+    let signer = signature.recover_address_from_msg(message).ok()?;
+    (signer == address).then_some(signer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_signer::SignerSync;
+    use alloy_signer_local::PrivateKeySigner;
+
+    #[test]
+    fn recover_flashbots_signature() {
+        let signer = PrivateKeySigner::random();
+        let body = br#"{"jsonrpc":"2.0","id":1,"method":"eth_cancelPrivateTransaction"}"#;
+        let signature = signer
+            .sign_message_sync(hex::encode_prefixed(keccak256(body)).as_bytes())
+            .unwrap()
+            .as_bytes();
+        let header = format!("{}:{}", signer.address(), hex::encode_prefixed(signature));
+
+        assert_eq!(recover_request_signer(&header, body), Some(signer.address()));
+
+        // tampered body
+        assert_eq!(recover_request_signer(&header, b"{}"), None);
+
+        // claimed address does not match the signature
+        let header = format!("{}:{}", Address::repeat_byte(1), hex::encode_prefixed(signature));
+        assert_eq!(recover_request_signer(&header, body), None);
+
+        assert_eq!(recover_request_signer("invalid", body), None);
+    }
+}
@@ -8,6 +8,7 @@ use alloy_rpc_types_mev::{
     EthCancelPrivateTransaction, EthSendBundle, EthSendPrivateTransaction,
 };
 use jsonrpsee::proc_macros::rpc;
+use reth_transaction_pool::PrivateTransactionStatus;
 
 /// A subset of the [EthBundleApi] API interface that only supports `eth_callBundle`.
 #[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
@@ -22,6 +23,47 @@ pub trait EthCallBundleApi {
     ) -> jsonrpsee::core::RpcResult<EthCallBundleResponse>;
 }
 
+/// A subset of the [EthBundleApi] API interface for private transactions.
+///
+/// Private transactions are kept in the local pool and are never gossiped to peers.
+#[cfg_attr(not(feature = "client"), rpc(server, namespace = "eth"))]
+#[cfg_attr(feature = "client", rpc(server, client, namespace = "eth"))]
+pub trait EthPrivateTransactionApi {
+    /// `eth_sendPrivateTransaction` inserts the transaction into the local pool without announcing
+    /// it to peers. If `maxBlockNumber` is set, the transaction is evicted once that block was
+    /// reached without including it.
+    #[method(name = "sendPrivateTransaction", with_extensions)]
+    async fn send_private_transaction(
+        &self,
+        request: EthSendPrivateTransaction,
+    ) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// `eth_sendPrivateRawTransaction` is the same as `eth_sendPrivateTransaction` without an
+    /// expiry.
+    #[method(name = "sendPrivateRawTransaction", with_extensions)]
+    async fn send_private_raw_transaction(&self, bytes: Bytes) -> jsonrpsee::core::RpcResult<B256>;
+
+    /// `eth_cancelPrivateTransaction` removes a pending private transaction from the pool.
+    ///
+    /// The request must be signed by the sender of the transaction with the
+    /// `X-Flashbots-Signature` header.
+    ///
+    /// Returns `false` if the transaction is not a pending private transaction.
+    #[method(name = "cancelPrivateTransaction", with_extensions)]
+    async fn cancel_private_transaction(
+        &self,
+        request: EthCancelPrivateTransaction,
+    ) -> jsonrpsee::core::RpcResult<bool>;
+
+    /// `eth_getPrivateTransactionStatus` returns the inclusion status of a private transaction,
+    /// or `null` if the transaction is unknown.
+    #[method(name = "getPrivateTransactionStatus")]
+    async fn private_transaction_status(
+        &self,
+        tx_hash: B256,
+    ) -> jsonrpsee::core::RpcResult<Option<PrivateTransactionStatus>>;
+}
+
 /// The __full__ Eth bundle rpc interface.
 ///
 /// See also <https://docs.flashbots.net/flashbots-auction/advanced/rpc-endpoint>
@@ -16,11 +16,12 @@ use alloy_rpc_types_eth::{
     StateContext, SyncStatus, Work,
 };
 use alloy_serde::JsonStorageKey;
-use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use jsonrpsee::{core::RpcResult, proc_macros::rpc, Extensions};
 use reth_primitives_traits::TxTy;
 use reth_rpc_convert::RpcTxReq;
 use reth_rpc_eth_types::{EthApiError, FillTransaction, TransactionDataAndReceipt};
-use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_rpc_server_types::{result::internal_rpc_err, RemoteAddr, ToRpcResult};
+use reth_transaction_pool::admission::AdmissionSource;
 use serde_json::Value;
 use std::collections::HashMap;
 use tracing::trace;
@@ -412,13 +413,13 @@ pub trait EthApi<
     async fn send_transaction(&self, request: TxReq) -> RpcResult<B256>;
 
     /// Sends signed transaction, returning its hash.
-    #[method(name = "sendRawTransaction")]
+    #[method(name = "sendRawTransaction", with_extensions)]
     async fn send_raw_transaction(&self, bytes: Bytes) -> RpcResult<B256>;
 
     /// Sends a signed transaction and awaits the transaction receipt.
     ///
     /// This will return a timeout error if the transaction isn't included within some time period.
-    #[method(name = "sendRawTransactionSync")]
+    #[method(name = "sendRawTransactionSync", with_extensions)]
     async fn send_raw_transaction_sync(&self, bytes: Bytes) -> RpcResult<R>;
 
     /// Returns an Ethereum specific signature with: sign(keccak256("\x19Ethereum Signed Message:\n"
@@ -975,15 +976,19 @@ where
     }
 
     /// Handler for: `eth_sendRawTransaction`
-    async fn send_raw_transaction(&self, tx: Bytes) -> RpcResult<B256> {
+    async fn send_raw_transaction(&self, ext: &Extensions, tx: Bytes) -> RpcResult<B256> {
         trace!(target: "rpc::eth", ?tx, "Serving eth_sendRawTransaction");
-        Ok(EthTransactions::send_raw_transaction(self, tx).await?)
+        Ok(EthTransactions::send_raw_transaction_from(self, admission_source(ext), tx).await?)
     }
 
     /// Handler for: `eth_sendRawTransactionSync`
-    async fn send_raw_transaction_sync(&self, tx: Bytes) -> RpcResult<RpcReceipt<T::NetworkTypes>> {
+    async fn send_raw_transaction_sync(
+        &self,
+        ext: &Extensions,
+        tx: Bytes,
+    ) -> RpcResult<RpcReceipt<T::NetworkTypes>> {
         trace!(target: "rpc::eth", ?tx, "Serving eth_sendRawTransactionSync");
-        Ok(EthTransactions::send_raw_transaction_sync(self, tx).await?)
+        Ok(EthTransactions::send_raw_transaction_sync_from(self, admission_source(ext), tx).await?)
     }
 
     /// Handler for: `eth_sign`
@@ -1056,3 +1061,8 @@ where
         Ok(bal.map(|b: BlockAccessList| alloy_rlp::encode(b).into()))
     }
 }
+
+/// Returns the [`AdmissionSource`] of a request from the [`RemoteAddr`] in its extensions.
+fn admission_source(ext: &Extensions) -> AdmissionSource {
+    ext.get::<RemoteAddr>().map(|addr| AdmissionSource::Ip(addr.0.ip())).unwrap_or_default()
+}
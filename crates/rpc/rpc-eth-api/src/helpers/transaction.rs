@@ -33,7 +33,8 @@ use reth_storage_api::{
     ReceiptProvider, TransactionsProvider,
 };
 use reth_transaction_pool::{
-    AddedTransactionOutcome, PoolPooledTx, PoolTransaction, TransactionOrigin, TransactionPool,
+    admission::AdmissionSource, AddedTransactionOutcome, PoolPooledTx, PoolTransaction,
+    TransactionOrigin, TransactionPool,
 };
 use std::{sync::Arc, time::Duration};
 
@@ -79,11 +80,25 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
     fn send_raw_transaction(
         &self,
         tx: Bytes,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+        self.send_raw_transaction_from(AdmissionSource::Unknown, tx)
+    }
+
+    /// Same as [`EthTransactions::send_raw_transaction`], but with the [`AdmissionSource`] the
+    /// transaction was received from, e.g. the address of the RPC client.
+    fn send_raw_transaction_from(
+        &self,
+        source: AdmissionSource,
+        tx: Bytes,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
         async move {
             let recovered = recover_raw_transaction::<PoolPooledTx<Self::Pool>>(&tx)?;
-            self.send_transaction(TransactionOrigin::External, WithEncoded::new(tx, recovered))
-                .await
+            self.send_transaction_from(
+                source,
+                TransactionOrigin::External,
+                WithEncoded::new(tx, recovered),
+            )
+            .await
         }
     }
 
@@ -94,6 +109,20 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         tx: WithEncoded<Recovered<PoolPooledTx<Self::Pool>>>,
     ) -> impl Future<Output = Result<B256, Self::Error>> + Send;
 
+    /// Same as [`EthTransactions::send_transaction`], but with the [`AdmissionSource`] the
+    /// transaction was received from.
+    ///
+    /// By default the source is ignored.
+    fn send_transaction_from(
+        &self,
+        source: AdmissionSource,
+        origin: TransactionOrigin,
+        tx: WithEncoded<Recovered<PoolPooledTx<Self::Pool>>>,
+    ) -> impl Future<Output = Result<B256, Self::Error>> + Send {
+        let _ = source;
+        self.send_transaction(origin, tx)
+    }
+
     /// Decodes and recovers the transaction and submits it to the pool.
     ///
     /// And awaits the receipt.
@@ -101,6 +130,19 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         &self,
         tx: Bytes,
     ) -> impl Future<Output = Result<RpcReceipt<Self::NetworkTypes>, Self::Error>> + Send
+    where
+        Self: LoadReceipt + 'static,
+    {
+        self.send_raw_transaction_sync_from(AdmissionSource::Unknown, tx)
+    }
+
+    /// Same as [`EthTransactions::send_raw_transaction_sync`], but with the [`AdmissionSource`]
+    /// the transaction was received from.
+    fn send_raw_transaction_sync_from(
+        &self,
+        source: AdmissionSource,
+        tx: Bytes,
+    ) -> impl Future<Output = Result<RpcReceipt<Self::NetworkTypes>, Self::Error>> + Send
     where
         Self: LoadReceipt + 'static,
     {
@@ -108,7 +150,7 @@ pub trait EthTransactions: LoadTransaction<Provider: BlockReaderIdExt> {
         let timeout_duration = self.send_raw_transaction_sync_timeout();
         async move {
             let mut stream = this.provider().canonical_state_stream();
-            let hash = EthTransactions::send_raw_transaction(&this, tx).await?;
+            let hash = EthTransactions::send_raw_transaction_from(&this, source, tx).await?;
             tokio::time::timeout(timeout_duration, async {
                 while let Some(notification) = stream.next().await {
                     let chain = notification.committed();
@@ -21,7 +21,7 @@ pub mod node;
 pub mod pubsub;
 pub mod types;
 
-pub use bundle::{EthBundleApiServer, EthCallBundleApiServer};
+pub use bundle::{EthBundleApiServer, EthCallBundleApiServer, EthPrivateTransactionApiServer};
 pub use core::{EthApiServer, FullEthApiServer};
 pub use ext::L2EthApiExtServer;
 pub use filter::{EngineEthFilter, EthFilterApiServer, QueryLimits};
@@ -35,7 +35,7 @@ pub use reth_rpc_eth_types::error::{
 pub use types::{EthApiTypes, FullEthApiTypes, RpcBlock, RpcHeader, RpcReceipt, RpcTransaction};
 
 #[cfg(feature = "client")]
-pub use bundle::{EthBundleApiClient, EthCallBundleApiClient};
+pub use bundle::{EthBundleApiClient, EthCallBundleApiClient, EthPrivateTransactionApiClient};
 #[cfg(feature = "client")]
 pub use core::EthApiClient;
 #[cfg(feature = "client")]
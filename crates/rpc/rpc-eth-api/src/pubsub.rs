@@ -1,8 +1,11 @@
 //! `eth_` RPC API for pubsub subscription.
 
 use alloy_json_rpc::RpcObject;
+use alloy_primitives::{TxHash, B256};
 use alloy_rpc_types_eth::pubsub::{Params, SubscriptionKind};
 use jsonrpsee::proc_macros::rpc;
+use reth_transaction_pool::{FullTransactionEvent, PoolTransaction};
+use serde::{Deserialize, Serialize};
 
 /// Ethereum pub-sub rpc interface.
 #[rpc(server, namespace = "eth")]
@@ -15,7 +18,133 @@ pub trait EthPubSubApi<T: RpcObject> {
     )]
     async fn subscribe(
         &self,
-        kind: SubscriptionKind,
+        kind: EthSubscriptionKind,
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult;
 }
+
+/// The kind of an `eth_subscribe` subscription.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EthSubscriptionKind {
+    /// A standard subscription.
+    Eth(SubscriptionKind),
+    /// A reth specific subscription.
+    Reth(RethSubscriptionKind),
+}
+
+impl From<SubscriptionKind> for EthSubscriptionKind {
+    fn from(kind: SubscriptionKind) -> Self {
+        Self::Eth(kind)
+    }
+}
+
+/// Subscriptions that are only supported by reth.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum RethSubscriptionKind {
+    /// Events of all transactions in the transaction pool, see [`TxpoolEvent`].
+    TxpoolEvents,
+}
+
+/// An item of the `txpoolEvents` subscription.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxpoolEvent {
+    /// Hash of the transaction.
+    pub hash: TxHash,
+    /// What happened to the transaction.
+    #[serde(flatten)]
+    pub kind: TxpoolEventKind,
+}
+
+impl TxpoolEvent {
+    /// Converts a pool event, returns `None` for events that don't concern a single transaction.
+    pub fn from_pool_event<T: PoolTransaction>(event: &FullTransactionEvent<T>) -> Option<Self> {
+        let (hash, kind) = match event {
+            FullTransactionEvent::Pending(hash) => (*hash, TxpoolEventKind::Pending),
+            FullTransactionEvent::Queued(hash, reason) => (
+                *hash,
+                TxpoolEventKind::Queued {
+                    reason: reason.as_ref().map(|reason| reason.as_str().to_string()),
+                },
+            ),
+            FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                (*tx_hash, TxpoolEventKind::Mined { block_hash: *block_hash })
+            }
+            FullTransactionEvent::Replaced { transaction, replaced_by } => {
+                (*transaction.hash(), TxpoolEventKind::Replaced { replaced_by: *replaced_by })
+            }
+            FullTransactionEvent::Discarded { tx_hash, reason, .. } => {
+                (*tx_hash, TxpoolEventKind::Discarded { reason: reason.as_str().to_string() })
+            }
+            FullTransactionEvent::Invalid(hash) => (*hash, TxpoolEventKind::Invalid),
+            FullTransactionEvent::Propagated(_) => return None,
+        };
+        Some(Self { hash, kind })
+    }
+}
+
+/// The kind of a [`TxpoolEvent`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "camelCase")]
+pub enum TxpoolEventKind {
+    /// The transaction was added to or promoted to the pending sub-pool.
+    Pending,
+    /// The transaction was added to or demoted to a parked sub-pool.
+    Queued {
+        /// Why the transaction is parked.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        reason: Option<String>,
+    },
+    /// The transaction was mined.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// Hash of the block that included the transaction.
+        block_hash: B256,
+    },
+    /// The transaction was replaced by another transaction with the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// Hash of the replacement.
+        replaced_by: TxHash,
+    },
+    /// The transaction was dropped from the pool.
+    Discarded {
+        /// Why the transaction was dropped.
+        reason: String,
+    },
+    /// The transaction became invalid.
+    Invalid,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscription_kind_serde() {
+        let kind: EthSubscriptionKind = serde_json::from_str(r#""newHeads""#).unwrap();
+        assert_eq!(kind, EthSubscriptionKind::Eth(SubscriptionKind::NewHeads));
+
+        let kind: EthSubscriptionKind = serde_json::from_str(r#""txpoolEvents""#).unwrap();
+        assert_eq!(kind, EthSubscriptionKind::Reth(RethSubscriptionKind::TxpoolEvents));
+    }
+
+    #[test]
+    fn txpool_event_serde() {
+        let event = TxpoolEvent {
+            hash: TxHash::ZERO,
+            kind: TxpoolEventKind::Discarded { reason: "poolLimits".to_string() },
+        };
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "hash": TxHash::ZERO,
+                "event": "discarded",
+                "reason": "poolLimits",
+            })
+        );
+    }
+}
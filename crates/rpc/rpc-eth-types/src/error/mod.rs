@@ -16,9 +16,12 @@ use reth_rpc_convert::{CallFeesError, EthTxEnvError, TransactionConversionError}
 use reth_rpc_server_types::result::{
     block_id_to_str, internal_rpc_err, invalid_params_rpc_err, rpc_err, rpc_error_with_code,
 };
-use reth_transaction_pool::error::{
-    Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
-    PoolError, PoolErrorKind, PoolTransactionError,
+use reth_transaction_pool::{
+    admission::AdmissionError,
+    error::{
+        Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
+        PoolError, PoolErrorKind, PoolTransactionError,
+    },
 };
 use revm::{
     context_interface::result::{
@@ -1025,6 +1028,9 @@ pub enum RpcPoolError {
     /// constraint (blob vs normal tx)
     #[error("address already reserved")]
     AddressAlreadyReserved,
+    /// Thrown if the transaction was rejected by an admission policy of the pool
+    #[error(transparent)]
+    Rejected(AdmissionError),
     /// Other unspecified error
     #[error(transparent)]
     Other(Box<dyn core::error::Error + Send + Sync>),
@@ -1053,6 +1059,9 @@ impl From<RpcPoolError> for jsonrpsee_types::error::ErrorObject<'static> {
             RpcPoolError::AddressAlreadyReserved => {
                 rpc_error_with_code(EthRpcErrorCode::InvalidInput.code(), error.to_string())
             }
+            RpcPoolError::Rejected(_) => {
+                rpc_error_with_code(EthRpcErrorCode::TransactionRejected.code(), error.to_string())
+            }
             RpcPoolError::Other(other) => internal_rpc_err(other.to_string()),
         }
     }
@@ -1070,6 +1079,10 @@ impl From<PoolError> for RpcPoolError {
             PoolErrorKind::Other(err) => Self::Other(err),
             PoolErrorKind::AlreadyImported => Self::AlreadyKnown,
             PoolErrorKind::ExistingConflictingTransactionType(_, _) => Self::AddressAlreadyReserved,
+            PoolErrorKind::AdmissionRejected(AdmissionError::Underpriced { .. }) => {
+                Self::Underpriced
+            }
+            PoolErrorKind::AdmissionRejected(err) => Self::Rejected(err),
         }
     }
 }
@@ -19,3 +19,22 @@ pub use module::{
 };
 
 pub use result::ToRpcResult;
+
+/// The address of the client an RPC request was received from.
+///
+/// Inserted into the extensions of every request served over HTTP or WS, handlers can read it
+/// with `#[method(.., with_extensions)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RemoteAddr(pub std::net::SocketAddr);
+
+/// The HTTP header that carries the signature of a request, as used by Flashbots.
+///
+/// The value is `<address>:<signature>`, where the signature is an EIP-191 signature of the
+/// hex encoded keccak256 hash of the request body.
+pub const FLASHBOTS_SIGNATURE_HEADER: &str = "x-flashbots-signature";
+
+/// The address that signed an RPC request with the [`FLASHBOTS_SIGNATURE_HEADER`].
+///
+/// Only inserted into the extensions of HTTP requests with a valid signature.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RpcRequestSigner(pub alloy_primitives::Address);
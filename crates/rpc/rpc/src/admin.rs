@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use alloy_genesis::ChainConfig;
+use alloy_primitives::Address;
 use alloy_rpc_types_admin::{
     EthInfo, EthPeerInfo, EthProtocolInfo, NodeInfo, PeerInfo, PeerNetworkInfo, PeerProtocolInfo,
     Ports, ProtocolInfo,
@@ -12,8 +13,11 @@ use reth_network_api::{NetworkInfo, Peers};
 use reth_network_peers::{AnyNode, NodeRecord};
 use reth_network_types::PeerKind;
 use reth_rpc_api::AdminApiServer;
-use reth_rpc_server_types::ToRpcResult;
-use reth_transaction_pool::TransactionPool;
+use reth_rpc_server_types::{result::internal_rpc_err, ToRpcResult};
+use reth_transaction_pool::{
+    admission::{SenderFilter, SenderFilterLists},
+    TransactionPool,
+};
 use revm_primitives::keccak256;
 
 /// `admin` API implementation.
@@ -35,6 +39,15 @@ impl<N, ChainSpec, Pool> AdminApi<N, ChainSpec, Pool> {
     }
 }
 
+impl<N, ChainSpec, Pool: TransactionPool> AdminApi<N, ChainSpec, Pool> {
+    /// Returns the sender filter of the pool or an error if the pool doesn't support one.
+    fn sender_filter(&self) -> RpcResult<SenderFilter> {
+        self.pool
+            .sender_filter()
+            .ok_or_else(|| internal_rpc_err("transaction pool does not support sender filters"))
+    }
+}
+
 #[async_trait]
 impl<N, ChainSpec, Pool> AdminApiServer for AdminApi<N, ChainSpec, Pool>
 where
@@ -191,6 +204,29 @@ where
         let _ = self.pool.remove_transactions(all_hashes);
         Ok(count)
     }
+
+    /// Handler for `admin_txpoolDenySender`
+    fn txpool_deny_sender(&self, sender: Address) -> RpcResult<bool> {
+        Ok(self.sender_filter()?.deny(sender))
+    }
+
+    /// Handler for `admin_txpoolUndenySender`
+    fn txpool_undeny_sender(&self, sender: Address) -> RpcResult<bool> {
+        Ok(self.sender_filter()?.undeny(&sender))
+    }
+
+    /// Handler for `admin_setTxpoolSenderLists`
+    fn set_txpool_sender_lists(
+        &self,
+        allow: Option<Vec<Address>>,
+        deny: Vec<Address>,
+    ) -> RpcResult<bool> {
+        self.sender_filter()?.set_lists(SenderFilterLists {
+            allow: allow.map(|allow| allow.into_iter().collect()),
+            deny: deny.into_iter().collect(),
+        });
+        Ok(true)
+    }
 }
 
 impl<N, ChainSpec, Pool> std::fmt::Debug for AdminApi<N, ChainSpec, Pool> {
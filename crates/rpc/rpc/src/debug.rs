@@ -19,7 +19,9 @@ use parking_lot::RwLock;
 use reth_chainspec::{ChainSpecProvider, EthChainSpec, EthereumHardforks};
 use reth_engine_primitives::ConsensusEngineEvent;
 use reth_errors::RethError;
-use reth_evm::{execute::Executor, ConfigureEvm, EvmEnvFor, TxEnvFor};
+use reth_evm::{
+    execute::Executor, handle_bsc_system_transaction, ConfigureEvm, EvmEnvFor, TxEnvFor,
+};
 use reth_primitives_traits::{
     Block as BlockTrait, BlockBody, BlockTy, ReceiptWithBloom, RecoveredBlock,
 };
@@ -111,21 +113,6 @@ impl<Eth> DebugApi<Eth>
 where
     Eth: TraceExt,
 {
-    /// Handles BSC system transactions by disabling block gas limit validation.
-    ///
-    /// BSC system transactions are identified by:
-    /// 1. `gas_limit` == `u64::MAX / 2`
-    /// 2. caller == block beneficiary (coinbase)
-    fn handle_bsc_system_transaction(
-        evm_env: &mut EvmEnvFor<Eth::Evm>,
-        tx_env: &TxEnvFor<Eth::Evm>,
-    ) {
-        if tx_env.gas_limit() == u64::MAX / 2 && tx_env.caller() == evm_env.block_env.beneficiary()
-        {
-            evm_env.cfg_env.disable_block_gas_limit = true;
-        }
-    }
-
     /// Acquires a permit to execute a tracing call.
     async fn acquire_trace_permit(&self) -> Result<OwnedSemaphorePermit, AcquireError> {
         self.inner.blocking_task_guard.clone().acquire_owned().await
@@ -331,7 +318,10 @@ where
                                 at,
                                 overrides,
                                 move |db, mut evm_env, tx_env| {
-                                    Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                                    handle_bsc_system_transaction::<Eth::Evm>(
+                                        &mut evm_env,
+                                        &tx_env,
+                                    );
                                     this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                                     Ok(inspector)
                                 },
@@ -355,7 +345,10 @@ where
                                 at,
                                 overrides,
                                 move |db, mut evm_env, tx_env| {
-                                    Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                                    handle_bsc_system_transaction::<Eth::Evm>(
+                                        &mut evm_env,
+                                        &tx_env,
+                                    );
 
                                     let gas_limit = tx_env.gas_limit();
                                     let res = this.eth_api().inspect(
@@ -389,7 +382,10 @@ where
                                 at,
                                 overrides,
                                 move |db, mut evm_env, tx_env| {
-                                    Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                                    handle_bsc_system_transaction::<Eth::Evm>(
+                                        &mut evm_env,
+                                        &tx_env,
+                                    );
 
                                     let gas_limit = tx_env.gas_limit();
                                     let res = this.eth_api().inspect(
@@ -439,7 +435,10 @@ where
                                         index: None,
                                     };
 
-                                    Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                                    handle_bsc_system_transaction::<Eth::Evm>(
+                                        &mut evm_env,
+                                        &tx_env,
+                                    );
 
                                     let res = this.eth_api().inspect(
                                         &mut *db,
@@ -473,7 +472,10 @@ where
                                 at,
                                 overrides,
                                 move |db, mut evm_env, tx_env| {
-                                    Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                                    handle_bsc_system_transaction::<Eth::Evm>(
+                                        &mut evm_env,
+                                        &tx_env,
+                                    );
 
                                     let gas_limit = tx_env.gas_limit();
                                     this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
@@ -541,7 +543,7 @@ where
         let (res, tx_gas_limit, inspector) = self
             .eth_api()
             .spawn_with_call_at(call, at, overrides, move |db, mut evm_env, tx_env| {
-                Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                handle_bsc_system_transaction::<Eth::Evm>(&mut evm_env, &tx_env);
                 let gas_limit = tx_env.gas_limit();
                 let res = this.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                 Ok((res, gas_limit, inspector))
@@ -607,7 +609,7 @@ where
                 let (mut evm_env, tx_env) =
                     eth_api.prepare_call_env(evm_env, call, &mut db, overrides)?;
 
-                Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                handle_bsc_system_transaction::<Eth::Evm>(&mut evm_env, &tx_env);
 
                 let mut inspector =
                     DebugInspector::new(tracing_options).map_err(Eth::Error::from_eth_err)?;
@@ -873,7 +875,7 @@ where
                 GethDebugTracerType::BuiltInTracer(tracer) => match tracer {
                     GethDebugBuiltInTracerType::FourByteTracer => {
                         let mut inspector = FourByteInspector::default();
-                        Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                        handle_bsc_system_transaction::<Eth::Evm>(&mut evm_env, &tx_env);
                         let res = self.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
                         return Ok((FourByteFrame::from(&inspector).into(), res.state))
                     }
@@ -889,7 +891,7 @@ where
                             ))
                         });
 
-                        Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                        handle_bsc_system_transaction::<Eth::Evm>(&mut evm_env, &tx_env);
 
                         let gas_limit = tx_env.gas_limit();
                         let res = self.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
@@ -914,7 +916,7 @@ where
                             )
                         });
 
-                        Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                        handle_bsc_system_transaction::<Eth::Evm>(&mut evm_env, &tx_env);
 
                         let gas_limit = tx_env.gas_limit();
                         let res =
@@ -940,7 +942,7 @@ where
                         let mut inspector = MuxInspector::try_from_config(mux_config)
                             .map_err(Eth::Error::from_eth_err)?;
 
-                        Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                        handle_bsc_system_transaction::<Eth::Evm>(&mut evm_env, &tx_env);
 
                         let res =
                             self.eth_api().inspect(&mut *db, evm_env, tx_env, &mut inspector)?;
@@ -959,7 +961,7 @@ where
                             TracingInspectorConfig::from_flat_call_config(&flat_call_config),
                         );
 
-                        Self::handle_bsc_system_transaction(&mut evm_env, &tx_env);
+                        handle_bsc_system_transaction::<Eth::Evm>(&mut evm_env, &tx_env);
 
                         let gas_limit = tx_env.gas_limit();
                         let res = self.eth_api().inspect(db, evm_env, tx_env, &mut inspector)?;
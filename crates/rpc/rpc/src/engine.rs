@@ -4,7 +4,7 @@ use alloy_rpc_types_eth::{
     state::StateOverride, BlockOverrides, EIP1186AccountProofResponse, Filter, Log, SyncStatus,
 };
 use alloy_serde::JsonStorageKey;
-use jsonrpsee::core::RpcResult as Result;
+use jsonrpsee::{core::RpcResult as Result, Extensions};
 use reth_primitives_traits::TxTy;
 use reth_rpc_api::{EngineEthApiServer, EthApiServer};
 use reth_rpc_convert::RpcTxReq;
@@ -122,7 +122,7 @@ where
 
     /// Handler for: `eth_sendRawTransaction`
     async fn send_raw_transaction(&self, bytes: Bytes) -> Result<B256> {
-        self.eth.send_raw_transaction(bytes).instrument(engine_span!()).await
+        self.eth.send_raw_transaction(&Extensions::new(), bytes).instrument(engine_span!()).await
     }
 
     async fn transaction_receipt(
@@ -3,19 +3,24 @@
 use alloy_consensus::{transaction::TxHashRef, EnvKzgSettings, Transaction as _};
 use alloy_eips::eip7840::BlobParams;
 use alloy_evm::env::BlockEnvironment;
-use alloy_primitives::{uint, Keccak256, U256};
-use alloy_rpc_types_mev::{EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult};
-use jsonrpsee::core::RpcResult;
+use alloy_primitives::{uint, Address, Bytes, Keccak256, B256, U256};
+use alloy_rpc_types_mev::{
+    EthCallBundle, EthCallBundleResponse, EthCallBundleTransactionResult,
+    EthCancelPrivateTransaction, EthSendPrivateTransaction,
+};
+use jsonrpsee::{core::RpcResult, Extensions};
 use reth_chainspec::{ChainSpecProvider, EthChainSpec};
 use reth_evm::{ConfigureEvm, Evm};
 use reth_rpc_eth_api::{
     helpers::{Call, EthTransactions, LoadPendingBlock},
-    EthCallBundleApiServer, FromEthApiError, FromEvmError,
+    EthCallBundleApiServer, EthPrivateTransactionApiServer, FromEthApiError, FromEvmError,
 };
 use reth_rpc_eth_types::{utils::recover_raw_transaction, EthApiError, RpcInvalidTransactionError};
+use reth_rpc_server_types::{RemoteAddr, RpcRequestSigner};
 use reth_tasks::pool::BlockingTaskGuard;
 use reth_transaction_pool::{
-    EthBlobTransactionSidecar, EthPoolTransaction, PoolPooledTx, PoolTransaction, TransactionPool,
+    admission::AdmissionSource, EthBlobTransactionSidecar, EthPoolTransaction, PoolPooledTx,
+    PoolTransaction, PrivateTransactionStatus, TransactionPool,
 };
 use revm::{
     context::Block, context_interface::result::ResultAndState, DatabaseCommit, DatabaseRef,
@@ -259,6 +264,114 @@ where
     }
 }
 
+impl<Eth> EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    /// Decodes and recovers the transaction and adds it to the pool as a private transaction that
+    /// is never announced to peers.
+    ///
+    /// If `max_block_number` is set, the pool evicts the transaction once that block was reached
+    /// without including it.
+    pub async fn send_private_transaction(
+        &self,
+        tx: Bytes,
+        max_block_number: Option<u64>,
+    ) -> Result<B256, Eth::Error> {
+        self.send_private_transaction_from(AdmissionSource::Unknown, tx, max_block_number).await
+    }
+
+    /// Same as [`EthBundle::send_private_transaction`], but with the [`AdmissionSource`] the
+    /// transaction was received from, which is evaluated by per-source admission policies.
+    pub async fn send_private_transaction_from(
+        &self,
+        source: AdmissionSource,
+        tx: Bytes,
+        max_block_number: Option<u64>,
+    ) -> Result<B256, Eth::Error> {
+        let recovered = recover_raw_transaction::<PoolPooledTx<Eth::Pool>>(&tx)?;
+        let pool_transaction = <Eth::Pool as TransactionPool>::Transaction::from_pooled(recovered);
+        let outcome = self
+            .eth_api()
+            .pool()
+            .add_private_transaction_from_source(source, pool_transaction, max_block_number)
+            .await
+            .map_err(|err| Eth::Error::from_eth_err(EthApiError::from(err)))?;
+        Ok(outcome.hash)
+    }
+
+    /// Cancels a pending private transaction on behalf of the signer of the request.
+    ///
+    /// Only the sender of the transaction may cancel it, returns `false` if the transaction is not
+    /// a pending private transaction.
+    pub fn cancel_private_transaction(
+        &self,
+        tx_hash: B256,
+        signer: Option<Address>,
+    ) -> Result<bool, EthApiError> {
+        let Some(signer) = signer else {
+            return Err(EthApiError::InvalidParams(
+                "missing or invalid X-Flashbots-Signature header".to_string(),
+            ))
+        };
+        let pool = self.eth_api().pool();
+        let Some(tx) = pool.get(&tx_hash) else { return Ok(false) };
+        if tx.sender() != signer {
+            return Err(EthApiError::InvalidParams(
+                "request must be signed by the sender of the transaction".to_string(),
+            ))
+        }
+        Ok(pool.cancel_private_transaction(tx_hash))
+    }
+}
+
+#[async_trait::async_trait]
+impl<Eth> EthPrivateTransactionApiServer for EthBundle<Eth>
+where
+    Eth: EthTransactions + 'static,
+{
+    async fn send_private_transaction(
+        &self,
+        ext: &Extensions,
+        request: EthSendPrivateTransaction,
+    ) -> RpcResult<B256> {
+        Self::send_private_transaction_from(
+            self,
+            admission_source(ext),
+            request.tx,
+            request.max_block_number,
+        )
+        .await
+        .map_err(Into::into)
+    }
+
+    async fn send_private_raw_transaction(
+        &self,
+        ext: &Extensions,
+        bytes: Bytes,
+    ) -> RpcResult<B256> {
+        Self::send_private_transaction_from(self, admission_source(ext), bytes, None)
+            .await
+            .map_err(Into::into)
+    }
+
+    async fn cancel_private_transaction(
+        &self,
+        ext: &Extensions,
+        request: EthCancelPrivateTransaction,
+    ) -> RpcResult<bool> {
+        let signer = ext.get::<RpcRequestSigner>().map(|signer| signer.0);
+        Ok(Self::cancel_private_transaction(self, request.tx_hash, signer)?)
+    }
+
+    async fn private_transaction_status(
+        &self,
+        tx_hash: B256,
+    ) -> RpcResult<Option<PrivateTransactionStatus>> {
+        Ok(self.eth_api().pool().private_transaction_status(tx_hash))
+    }
+}
+
 #[async_trait::async_trait]
 impl<Eth> EthCallBundleApiServer for EthBundle<Eth>
 where
@@ -269,6 +382,11 @@ where
     }
 }
 
+/// Returns the [`AdmissionSource`] of a request from the [`RemoteAddr`] in its extensions.
+fn admission_source(ext: &Extensions) -> AdmissionSource {
+    ext.get::<RemoteAddr>().map(|addr| AdmissionSource::Ip(addr.0.ip())).unwrap_or_default()
+}
+
 /// Container type for `EthBundle` internals
 #[derive(Debug)]
 struct EthBundleInner<Eth> {
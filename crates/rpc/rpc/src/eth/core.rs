@@ -519,9 +519,27 @@ where
         &self,
         origin: reth_transaction_pool::TransactionOrigin,
         transaction: <N::Pool as TransactionPool>::Transaction,
+    ) -> Result<AddedTransactionOutcome, EthApiError> {
+        self.add_pool_transaction_from(
+            reth_transaction_pool::admission::AdmissionSource::Unknown,
+            origin,
+            transaction,
+        )
+        .await
+    }
+
+    /// Adds an _unvalidated_ transaction received from the given source into the pool via the
+    /// transaction batch sender.
+    #[inline]
+    pub async fn add_pool_transaction_from(
+        &self,
+        source: reth_transaction_pool::admission::AdmissionSource,
+        origin: reth_transaction_pool::TransactionOrigin,
+        transaction: <N::Pool as TransactionPool>::Transaction,
     ) -> Result<AddedTransactionOutcome, EthApiError> {
         let (response_tx, response_rx) = tokio::sync::oneshot::channel();
-        let request = reth_transaction_pool::BatchTxRequest::new(origin, transaction, response_tx);
+        let request = reth_transaction_pool::BatchTxRequest::new(origin, transaction, response_tx)
+            .with_source(source);
 
         self.tx_batch_sender()
             .send(request)
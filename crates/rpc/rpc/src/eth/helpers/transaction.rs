@@ -16,8 +16,8 @@ use reth_rpc_eth_api::{
 use reth_rpc_eth_types::{error::RpcPoolError, EthApiError};
 use reth_storage_api::BlockReaderIdExt;
 use reth_transaction_pool::{
-    error::Eip4844PoolTransactionError, AddedTransactionOutcome, EthBlobTransactionSidecar,
-    EthPoolTransaction, PoolPooledTx, PoolTransaction, TransactionPool,
+    admission::AdmissionSource, error::Eip4844PoolTransactionError, AddedTransactionOutcome,
+    EthBlobTransactionSidecar, EthPoolTransaction, PoolPooledTx, PoolTransaction, TransactionPool,
 };
 
 impl<N, Rpc> EthTransactions for EthApi<N, Rpc>
@@ -40,6 +40,15 @@ where
         &self,
         origin: reth_transaction_pool::TransactionOrigin,
         tx: WithEncoded<Recovered<PoolPooledTx<Self::Pool>>>,
+    ) -> Result<B256, Self::Error> {
+        self.send_transaction_from(AdmissionSource::Unknown, origin, tx).await
+    }
+
+    async fn send_transaction_from(
+        &self,
+        source: AdmissionSource,
+        origin: reth_transaction_pool::TransactionOrigin,
+        tx: WithEncoded<Recovered<PoolPooledTx<Self::Pool>>>,
     ) -> Result<B256, Self::Error> {
         let (tx, recovered) = tx.split();
         let mut pool_transaction =
@@ -107,7 +116,7 @@ where
                 }).map_err(EthApiError::other)?;
 
             // Retain tx in local tx pool after forwarding, for local RPC usage.
-            let _ = self.inner.add_pool_transaction(origin, pool_transaction).await;
+            let _ = self.inner.add_pool_transaction_from(source, origin, pool_transaction).await;
 
             return Ok(hash);
         }
@@ -116,7 +125,7 @@ where
         self.broadcast_raw_transaction(tx);
 
         let AddedTransactionOutcome { hash, .. } =
-            self.inner.add_pool_transaction(origin, pool_transaction).await?;
+            self.inner.add_pool_transaction_from(source, origin, pool_transaction).await?;
 
         Ok(hash)
     }
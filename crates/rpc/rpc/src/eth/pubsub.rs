@@ -19,7 +19,8 @@ use reth_network_api::NetworkInfo;
 use reth_primitives_traits::TransactionMeta;
 use reth_rpc_convert::{transaction::ConvertReceiptInput, RpcHeader};
 use reth_rpc_eth_api::{
-    pubsub::EthPubSubApiServer, EthApiTypes, RpcConvert, RpcNodeCore, RpcTransaction,
+    pubsub::{EthPubSubApiServer, EthSubscriptionKind, RethSubscriptionKind, TxpoolEvent},
+    EthApiTypes, RpcConvert, RpcNodeCore, RpcTransaction,
 };
 use reth_rpc_eth_types::logs_utils;
 use reth_rpc_server_types::result::{internal_rpc_err, invalid_params_rpc_err};
@@ -73,6 +74,11 @@ where
         self.inner.full_pending_transaction_stream()
     }
 
+    /// Returns a stream that yields the events of all transactions in the txpool.
+    pub fn txpool_event_stream(&self) -> impl Stream<Item = TxpoolEvent> + Unpin {
+        self.inner.txpool_event_stream()
+    }
+
     /// Returns a stream that yields all new RPC blocks.
     pub fn new_headers_stream(&self) -> impl Stream<Item = RpcHeader<Eth::NetworkTypes>> {
         self.inner.new_headers_stream()
@@ -293,13 +299,18 @@ where
     async fn subscribe(
         &self,
         pending: PendingSubscriptionSink,
-        kind: SubscriptionKind,
+        kind: EthSubscriptionKind,
         params: Option<Params>,
     ) -> jsonrpsee::core::SubscriptionResult {
         let sink = pending.accept().await?;
         let pubsub = self.clone();
         self.inner.subscription_task_spawner.spawn_task(async move {
-            let _ = pubsub.handle_accepted(sink, kind, params).await;
+            let _ = match kind {
+                EthSubscriptionKind::Eth(kind) => pubsub.handle_accepted(sink, kind, params).await,
+                EthSubscriptionKind::Reth(RethSubscriptionKind::TxpoolEvents) => {
+                    pipe_from_stream(sink, pubsub.txpool_event_stream()).await
+                }
+            };
         });
 
         Ok(())
@@ -417,6 +428,14 @@ where
     ) -> impl Stream<Item = NewTransactionEvent<<Eth::Pool as TransactionPool>::Transaction>> {
         self.eth_api.pool().new_pending_pool_transactions_listener()
     }
+
+    /// Returns a stream that yields the events of all transactions in the txpool.
+    fn txpool_event_stream(&self) -> impl Stream<Item = TxpoolEvent> + Unpin {
+        self.eth_api
+            .pool()
+            .all_transactions_event_listener()
+            .filter_map(|event| std::future::ready(TxpoolEvent::from_pool_event(&event)))
+    }
 }
 
 impl<Eth> EthPubSubInner<Eth>
@@ -2,7 +2,7 @@ use std::{future::Future, sync::Arc};
 
 use alloy_consensus::BlockHeader;
 use alloy_eips::BlockId;
-use alloy_primitives::{map::AddressMap, U256, U64};
+use alloy_primitives::{map::AddressMap, Address, U256, U64};
 use async_trait::async_trait;
 use futures::{Stream, StreamExt};
 use jsonrpsee::{core::RpcResult, PendingSubscriptionSink, SubscriptionMessage, SubscriptionSink};
@@ -14,15 +14,19 @@ use reth_errors::RethResult;
 use reth_evm::{execute::Executor, ConfigureEvm};
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{NodePrimitives, SealedHeader};
-use reth_rpc_api::RethApiServer;
+use reth_rpc_api::{AddressBalanceChange, AddressBalanceChanges, RethApiServer};
 use reth_rpc_eth_types::{EthApiError, EthResult};
 use reth_storage_api::{
-    BlockReader, BlockReaderIdExt, ChangeSetReader, StateProviderFactory, TransactionVariant,
+    BalanceChangeReader, BlockReader, BlockReaderIdExt, ChangeSetReader, StateProviderFactory,
+    TransactionVariant,
 };
 use reth_tasks::{pool::BlockingTaskGuard, Runtime};
 use serde::Serialize;
 use tokio::sync::oneshot;
 
+/// The maximum number of balance changes returned by `reth_getBalanceChangesForAddress`.
+const MAX_BALANCE_CHANGES_PER_PAGE: usize = 1000;
+
 /// `reth` API implementation.
 ///
 /// This type provides the functionality for handling `reth` prototype RPC requests.
@@ -58,7 +62,8 @@ impl<Provider, EvmConfig> RethApi<Provider, EvmConfig> {
 
 impl<Provider, EvmConfig> RethApi<Provider, EvmConfig>
 where
-    Provider: BlockReaderIdExt + ChangeSetReader + StateProviderFactory + 'static,
+    Provider:
+        BlockReaderIdExt + ChangeSetReader + BalanceChangeReader + StateProviderFactory + 'static,
     EvmConfig: Send + Sync + 'static,
 {
     /// Executes the future on a new blocking task.
@@ -103,6 +108,66 @@ where
         )?;
         Ok(hash_map)
     }
+
+    /// Returns a page of balance changes of an account from the balance changes index.
+    pub async fn balance_changes_for_address(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: Option<u64>,
+        limit: Option<usize>,
+    ) -> EthResult<AddressBalanceChanges> {
+        self.on_blocking_task(async move |this| {
+            this.try_balance_changes_for_address(address, from_block, to_block, limit)
+        })
+        .await
+    }
+
+    fn try_balance_changes_for_address(
+        &self,
+        address: Address,
+        from_block: u64,
+        to_block: Option<u64>,
+        limit: Option<usize>,
+    ) -> EthResult<AddressBalanceChanges> {
+        let Some(index_tip) = self.provider().balance_changes_index_tip()? else {
+            return Err(EthApiError::InvalidParams(
+                "balance changes index is not enabled".to_string(),
+            ))
+        };
+
+        let limit = limit.unwrap_or(MAX_BALANCE_CHANGES_PER_PAGE);
+        if limit == 0 || limit > MAX_BALANCE_CHANGES_PER_PAGE {
+            return Err(EthApiError::InvalidParams(format!(
+                "limit must be between 1 and {MAX_BALANCE_CHANGES_PER_PAGE}, got {limit}"
+            )))
+        }
+
+        let to_block = to_block.map_or(index_tip, |to_block| to_block.min(index_tip));
+        if from_block > to_block {
+            return Ok(AddressBalanceChanges::default())
+        }
+
+        let changes =
+            self.provider().account_balance_changes(address, from_block..=to_block, limit)?;
+        let next_block = changes
+            .last()
+            .filter(|_| changes.len() == limit)
+            .map(|change| change.block_number + 1)
+            .filter(|next_block| *next_block <= to_block);
+
+        Ok(AddressBalanceChanges {
+            changes: changes
+                .into_iter()
+                .map(|change| AddressBalanceChange {
+                    block_number: change.block_number,
+                    before: change.before,
+                    after: change.after,
+                })
+                .collect(),
+            next_block,
+        })
+    }
 }
 
 impl<N, Provider, EvmConfig> RethApi<Provider, EvmConfig>
@@ -110,6 +175,7 @@ where
     N: NodePrimitives,
     Provider: BlockReaderIdExt
         + ChangeSetReader
+        + BalanceChangeReader
         + StateProviderFactory
         + BlockReader<Block = N::Block>
         + CanonStateSubscriptions<Primitives = N>
@@ -190,6 +256,7 @@ impl<Provider, EvmConfig> RethApiServer for RethApi<Provider, EvmConfig>
 where
     Provider: BlockReaderIdExt
         + ChangeSetReader
+        + BalanceChangeReader
         + StateProviderFactory
         + BlockReader<Block = <Provider::Primitives as NodePrimitives>::Block>
         + CanonStateSubscriptions
@@ -206,6 +273,24 @@ where
         Ok(Self::balance_changes_in_block(self, block_id).await?)
     }
 
+    /// Handler for `reth_getBalanceChangesForAddress`
+    async fn reth_get_balance_changes_for_address(
+        &self,
+        address: Address,
+        from_block: U64,
+        to_block: Option<U64>,
+        limit: Option<U64>,
+    ) -> RpcResult<AddressBalanceChanges> {
+        Ok(Self::balance_changes_for_address(
+            self,
+            address,
+            from_block.to(),
+            to_block.map(|block| block.to()),
+            limit.map(|limit| limit.saturating_to()),
+        )
+        .await?)
+    }
+
     /// Handler for `reth_getBlockExecutionOutcome`
     async fn reth_get_block_execution_outcome(
         &self,
@@ -2,14 +2,14 @@ use core::fmt;
 use std::collections::BTreeMap;
 
 use alloy_consensus::Transaction;
-use alloy_primitives::Address;
+use alloy_primitives::{Address, TxHash};
 use alloy_rpc_types_txpool::{
     TxpoolContent, TxpoolContentFrom, TxpoolInspect, TxpoolInspectSummary, TxpoolStatus,
 };
 use async_trait::async_trait;
 use jsonrpsee::core::RpcResult;
 use reth_primitives_traits::NodePrimitives;
-use reth_rpc_api::TxPoolApiServer;
+use reth_rpc_api::{TxPoolApiServer, TxpoolStatusResponse, TxpoolTransactionStatus};
 use reth_rpc_convert::{RpcConvert, RpcTypes};
 use reth_rpc_eth_api::RpcTransaction;
 use reth_transaction_pool::{
@@ -39,7 +39,15 @@ where
     Pool: TransactionPool<Transaction: PoolTransaction<Consensus: Transaction>> + 'static,
     Eth: RpcConvert<Primitives: NodePrimitives<SignedTx = PoolConsensusTx<Pool>>>,
 {
-    fn content(&self) -> Result<TxpoolContent<RpcTransaction<Eth::Network>>, Eth::Error> {
+    /// Returns the content of the pool.
+    ///
+    /// If `offset` or `limit` are set, only the transactions of the senders on that page are
+    /// included, see [`TransactionPool::transactions_page`].
+    fn content(
+        &self,
+        offset: usize,
+        limit: Option<usize>,
+    ) -> Result<TxpoolContent<RpcTransaction<Eth::Network>>, Eth::Error> {
         #[inline]
         fn insert<Tx, RpcTxB>(
             tx: &Tx,
@@ -61,7 +69,11 @@ where
             Ok(())
         }
 
-        let AllPoolTransactions { pending, queued } = self.pool.all_transactions();
+        let AllPoolTransactions { pending, queued } = if offset > 0 || limit.is_some() {
+            self.pool.transactions_page(offset, limit.unwrap_or(usize::MAX))
+        } else {
+            self.pool.all_transactions()
+        };
 
         let mut content = TxpoolContent::default();
         for pending in pending {
@@ -85,11 +97,26 @@ where
     /// well as the ones that are being scheduled for future execution only.
     /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_status)
     ///
+    /// With a transaction hash, returns the state of that transaction in the pool instead.
+    ///
     /// Handler for `txpool_status`
-    async fn txpool_status(&self) -> RpcResult<TxpoolStatus> {
-        trace!(target: "rpc::eth", "Serving txpool_status");
-        let (pending, queued) = self.pool.pending_and_queued_txn_count();
-        Ok(TxpoolStatus { pending: pending as u64, queued: queued as u64 })
+    async fn txpool_status(&self, hash: Option<TxHash>) -> RpcResult<TxpoolStatusResponse> {
+        trace!(target: "rpc::eth", ?hash, "Serving txpool_status");
+        let Some(hash) = hash else {
+            let (pending, queued) = self.pool.pending_and_queued_txn_count();
+            return Ok(TxpoolStatusResponse::Pool(TxpoolStatus {
+                pending: pending as u64,
+                queued: queued as u64,
+            }))
+        };
+        Ok(TxpoolStatusResponse::Transaction(self.pool.transaction_state(hash).map(|state| {
+            TxpoolTransactionStatus {
+                subpool: state.subpool.as_str().to_string(),
+                reason: state.queued_reason.map(|reason| reason.as_str().to_string()),
+                missing_nonce: state.missing_nonce,
+                missing_balance: state.missing_balance,
+            }
+        })))
     }
 
     /// Returns a summary of all the transactions currently pending for inclusion in the next
@@ -135,7 +162,27 @@ where
         from: Address,
     ) -> RpcResult<TxpoolContentFrom<RpcTransaction<Eth::Network>>> {
         trace!(target: "rpc::eth", ?from, "Serving txpool_contentFrom");
-        Ok(self.content().map_err(Into::into)?.remove_from(&from))
+        Ok(self.content(0, None).map_err(Into::into)?.remove_from(&from))
+    }
+
+    /// Retrieves the queued transactions of this address, grouped by nonce.
+    ///
+    /// Handler for `txpool_queuedFrom`
+    async fn txpool_queued_from(
+        &self,
+        from: Address,
+    ) -> RpcResult<BTreeMap<String, RpcTransaction<Eth::Network>>> {
+        trace!(target: "rpc::eth", ?from, "Serving txpool_queuedFrom");
+        let mut queued = BTreeMap::new();
+        for tx in self.pool.get_queued_transactions_by_sender(from) {
+            queued.insert(
+                tx.nonce().to_string(),
+                self.converter
+                    .fill_pending(tx.transaction.clone_into_consensus())
+                    .map_err(Into::into)?,
+            );
+        }
+        Ok(queued)
     }
 
     /// Returns the details of all transactions currently pending for inclusion in the next
@@ -143,9 +190,13 @@ where
     ///
     /// See [here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content) for more details
     /// Handler for `txpool_content`
-    async fn txpool_content(&self) -> RpcResult<TxpoolContent<RpcTransaction<Eth::Network>>> {
-        trace!(target: "rpc::eth", "Serving txpool_content");
-        Ok(self.content().map_err(Into::into)?)
+    async fn txpool_content(
+        &self,
+        offset: Option<usize>,
+        limit: Option<usize>,
+    ) -> RpcResult<TxpoolContent<RpcTransaction<Eth::Network>>> {
+        trace!(target: "rpc::eth", ?offset, ?limit, "Serving txpool_content");
+        Ok(self.content(offset.unwrap_or_default(), limit).map_err(Into::into)?)
     }
 }
 
@@ -39,9 +39,9 @@
 use crate::{
     stages::{
         AccountHashingStage, BodyStage, EraImportSource, EraStage, ExecutionStage, FinishStage,
-        HeaderStage, IndexAccountHistoryStage, IndexStorageHistoryStage, MerkleStage,
-        PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage, StorageHashingStage,
-        TransactionLookupStage,
+        HeaderStage, IndexAccountHistoryStage, IndexBalanceChangesStage, IndexStorageHistoryStage,
+        MerkleStage, PruneSenderRecoveryStage, PruneStage, SenderRecoveryStage,
+        StorageHashingStage, TransactionLookupStage,
     },
     StageSet, StageSetBuilder,
 };
@@ -79,6 +79,7 @@ use tokio::sync::watch;
 /// - [`TransactionLookupStage`]
 /// - [`IndexStorageHistoryStage`]
 /// - [`IndexAccountHistoryStage`]
+/// - [`IndexBalanceChangesStage`] (if enabled)
 /// - [`PruneStage`] (execute)
 /// - [`FinishStage`]
 #[derive(Debug)]
@@ -463,6 +464,7 @@ where
     TransactionLookupStage: Stage<Provider>,
     IndexStorageHistoryStage: Stage<Provider>,
     IndexAccountHistoryStage: Stage<Provider>,
+    IndexBalanceChangesStage: Stage<Provider>,
 {
     fn builder(self) -> StageSetBuilder<Provider> {
         StageSetBuilder::default()
@@ -481,5 +483,12 @@ where
                 self.stages_config.etl.clone(),
                 self.prune_modes.account_history,
             ))
+            .add_stage_opt(self.stages_config.index_balance_changes.enabled.then(|| {
+                IndexBalanceChangesStage::new(
+                    self.stages_config.index_balance_changes,
+                    self.stages_config.etl.clone(),
+                    self.prune_modes.account_history,
+                )
+            }))
     }
 }
@@ -0,0 +1,271 @@
+use alloy_primitives::{Address, BlockNumber, U256};
+use reth_config::config::{EtlConfig, IndexBalanceChangesConfig};
+use reth_db_api::{
+    cursor::{DbCursorRO, DbCursorRW, DbDupCursorRW},
+    models::{AccountBeforeTx, BalanceChange, CompactU256, ShardedKey},
+    table::{Decode, Decompress},
+    tables,
+    transaction::{DbTx, DbTxMut},
+};
+use reth_etl::Collector;
+use reth_provider::{
+    AccountReader, DBProvider, HistoryWriter, StaticFileProviderFactory, StorageSettingsCache,
+};
+use reth_prune_types::{PruneMode, PrunePurpose, PruneSegment};
+use reth_stages_api::{
+    ExecInput, ExecOutput, Stage, StageCheckpoint, StageError, StageId, UnwindInput, UnwindOutput,
+};
+use std::ops::RangeInclusive;
+use tracing::info;
+
+/// Stage is indexing the balance changes of accounts from the account changesets generated in
+/// [`ExecutionStage`][crate::stages::ExecutionStage]. For each account and block in which its
+/// balance changed, the balances before and after the block are written to
+/// [`tables::AccountBalanceChanges`].
+///
+/// The balance after the last change of an account is read from the plain state, so the stage
+/// must run after the execution stage reached the same target.
+#[derive(Debug)]
+pub struct IndexBalanceChangesStage {
+    /// Number of blocks after which the control
+    /// flow will be returned to the pipeline for commit.
+    pub commit_threshold: u64,
+    /// Pruning configuration of the account history. Changesets below its target are not
+    /// available anymore.
+    pub prune_mode: Option<PruneMode>,
+    /// ETL configuration
+    pub etl_config: EtlConfig,
+}
+
+impl IndexBalanceChangesStage {
+    /// Create new instance of [`IndexBalanceChangesStage`].
+    pub const fn new(
+        config: IndexBalanceChangesConfig,
+        etl_config: EtlConfig,
+        prune_mode: Option<PruneMode>,
+    ) -> Self {
+        Self { commit_threshold: config.commit_threshold, etl_config, prune_mode }
+    }
+}
+
+impl Default for IndexBalanceChangesStage {
+    fn default() -> Self {
+        Self { commit_threshold: 10_000, prune_mode: None, etl_config: EtlConfig::default() }
+    }
+}
+
+impl<Provider> Stage<Provider> for IndexBalanceChangesStage
+where
+    Provider: DBProvider<Tx: DbTxMut>
+        + HistoryWriter
+        + AccountReader
+        + StaticFileProviderFactory
+        + StorageSettingsCache,
+{
+    /// Return the id of the stage
+    fn id(&self) -> StageId {
+        StageId::IndexBalanceChanges
+    }
+
+    /// Execute the stage.
+    fn execute(
+        &mut self,
+        provider: &Provider,
+        mut input: ExecInput,
+    ) -> Result<ExecOutput, StageError> {
+        // The account history pruner also prunes this table, so there's nothing to index below
+        // its target.
+        if let Some((target_prunable_block, _)) = self
+            .prune_mode
+            .map(|mode| {
+                mode.prune_target_block(
+                    input.target(),
+                    PruneSegment::AccountHistory,
+                    PrunePurpose::User,
+                )
+            })
+            .transpose()?
+            .flatten() &&
+            target_prunable_block > input.checkpoint().block_number
+        {
+            input.checkpoint = Some(StageCheckpoint::new(target_prunable_block));
+        }
+
+        if input.target_reached() {
+            return Ok(ExecOutput::done(input.checkpoint()))
+        }
+
+        let mut range = input.next_block_range();
+        let first_sync = input.checkpoint().block_number == 0;
+
+        // On first sync we clear the table since it's faster to rebuild from scratch.
+        if first_sync {
+            provider.tx_ref().clear::<tables::AccountBalanceChanges>()?;
+            range = 0..=*input.next_block_range().end();
+        }
+
+        info!(target: "sync::stages::index_balance_changes::exec", ?first_sync, "Collecting balances");
+        let collector = collect_balances_before(provider, range.clone(), &self.etl_config)?;
+
+        info!(target: "sync::stages::index_balance_changes::exec", "Loading balance changes into database");
+        load_balance_changes(provider, collector, first_sync)?;
+
+        Ok(ExecOutput { checkpoint: StageCheckpoint::new(*range.end()), done: true })
+    }
+
+    /// Unwind the stage.
+    fn unwind(
+        &mut self,
+        provider: &Provider,
+        input: UnwindInput,
+    ) -> Result<UnwindOutput, StageError> {
+        let (range, unwind_progress, _) =
+            input.unwind_block_range_with_threshold(self.commit_threshold);
+
+        provider.unwind_balance_changes_index_range(range)?;
+
+        Ok(UnwindOutput { checkpoint: StageCheckpoint::new(unwind_progress) })
+    }
+}
+
+/// Collects the balance of each account before each of its changes in the range, keyed by account
+/// and block number.
+fn collect_balances_before<Provider>(
+    provider: &Provider,
+    range: RangeInclusive<BlockNumber>,
+    etl_config: &EtlConfig,
+) -> Result<Collector<ShardedKey<Address>, CompactU256>, StageError>
+where
+    Provider: DBProvider + StaticFileProviderFactory + StorageSettingsCache,
+{
+    let mut collector = Collector::new(etl_config.file_size, etl_config.dir.clone());
+    let mut insert = |block_number, account: AccountBeforeTx| {
+        let balance = account.info.map(|info| info.balance).unwrap_or_default();
+        collector.insert(ShardedKey::new(account.address, block_number), balance.into())
+    };
+
+    if provider.cached_storage_settings().storage_v2 {
+        for entry in provider.static_file_provider().walk_account_changeset_range(range) {
+            let (block_number, account) = entry?;
+            insert(block_number, account)?;
+        }
+    } else {
+        let mut cursor = provider.tx_ref().cursor_read::<tables::AccountChangeSets>()?;
+        for entry in cursor.walk_range(range)? {
+            let (block_number, account) = entry?;
+            insert(block_number, account)?;
+        }
+    }
+
+    Ok(collector)
+}
+
+/// Writes the collected balance changes to [`tables::AccountBalanceChanges`].
+///
+/// The balance after a change is the balance before the next change of the account, or its
+/// current balance for the last change. Changes that leave the balance untouched are skipped.
+fn load_balance_changes<Provider>(
+    provider: &Provider,
+    mut collector: Collector<ShardedKey<Address>, CompactU256>,
+    append_only: bool,
+) -> Result<(), StageError>
+where
+    Provider: DBProvider<Tx: DbTxMut> + AccountReader,
+{
+    let mut cursor = provider.tx_ref().cursor_dup_write::<tables::AccountBalanceChanges>()?;
+    let mut write = |address: Address, change: BalanceChange| -> Result<(), StageError> {
+        if change.before == change.after {
+            return Ok(())
+        }
+        if append_only {
+            cursor.append_dup(address, change)?;
+        } else {
+            cursor.upsert(address, &change)?;
+        }
+        Ok(())
+    };
+    let current_balance = |address: &Address| -> Result<U256, StageError> {
+        Ok(provider.basic_account(address)?.map(|account| account.balance).unwrap_or_default())
+    };
+
+    // The pending change of the previous entry, waiting for its balance after the block.
+    let mut pending: Option<(Address, BlockNumber, U256)> = None;
+    for element in collector.iter()? {
+        let (k, v) = element?;
+        let key = ShardedKey::<Address>::decode_owned(k)?;
+        let before = U256::from(CompactU256::decompress_owned(v)?);
+
+        if let Some((address, block_number, pending_before)) =
+            pending.replace((key.key, key.highest_block_number, before))
+        {
+            let after = if address == key.key { before } else { current_balance(&address)? };
+            write(address, BalanceChange { block_number, before: pending_before, after })?;
+        }
+    }
+    if let Some((address, block_number, before)) = pending {
+        write(address, BalanceChange { block_number, before, after: current_balance(&address)? })?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TestStageDB;
+    use alloy_primitives::address;
+    use reth_db_api::models::StoredBlockBodyIndices;
+    use reth_primitives_traits::Account;
+    use reth_provider::DatabaseProviderFactory;
+
+    const ADDRESS: Address = address!("0x0000000000000000000000000000000000000001");
+
+    fn acc(balance: u64) -> AccountBeforeTx {
+        AccountBeforeTx {
+            address: ADDRESS,
+            info: Some(Account { balance: U256::from(balance), ..Default::default() }),
+        }
+    }
+
+    fn change(block_number: BlockNumber, before: u64, after: u64) -> BalanceChange {
+        BalanceChange { block_number, before: U256::from(before), after: U256::from(after) }
+    }
+
+    #[tokio::test]
+    async fn index_balance_changes() {
+        let db = TestStageDB::default();
+
+        // The balance changes in blocks 1 and 3, and the account is only touched in block 2
+        db.commit(|tx| {
+            for block in 0..=3 {
+                tx.put::<tables::BlockBodyIndices>(block, StoredBlockBodyIndices::default())?;
+            }
+            tx.put::<tables::AccountChangeSets>(1, acc(10))?;
+            tx.put::<tables::AccountChangeSets>(2, acc(20))?;
+            tx.put::<tables::AccountChangeSets>(3, acc(20))?;
+            tx.put::<tables::PlainAccountState>(ADDRESS, acc(30).info.unwrap())?;
+            Ok(())
+        })
+        .unwrap();
+
+        let mut stage = IndexBalanceChangesStage::default();
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input = ExecInput { target: Some(3), checkpoint: None };
+        let out = stage.execute(&provider, input).unwrap();
+        assert_eq!(out, ExecOutput { checkpoint: StageCheckpoint::new(3), done: true });
+        provider.commit().unwrap();
+
+        let table = db.table::<tables::AccountBalanceChanges>().unwrap();
+        assert_eq!(table, vec![(ADDRESS, change(1, 10, 20)), (ADDRESS, change(3, 20, 30))]);
+
+        let provider = db.factory.database_provider_rw().unwrap();
+        let input =
+            UnwindInput { checkpoint: StageCheckpoint::new(3), unwind_to: 1, ..Default::default() };
+        let out = stage.unwind(&provider, input).unwrap();
+        assert_eq!(out, UnwindOutput { checkpoint: StageCheckpoint::new(1) });
+        provider.commit().unwrap();
+
+        let table = db.table::<tables::AccountBalanceChanges>().unwrap();
+        assert_eq!(table, vec![(ADDRESS, change(1, 10, 20))]);
+    }
+}
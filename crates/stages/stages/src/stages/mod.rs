@@ -13,6 +13,8 @@ mod hashing_storage;
 mod headers;
 /// Index history of account changes
 mod index_account_history;
+/// Index balance changes of accounts
+mod index_balance_changes;
 /// Index history of storage changes
 mod index_storage_history;
 /// Stage for computing state root.
@@ -31,6 +33,7 @@ pub use hashing_account::*;
 pub use hashing_storage::*;
 pub use headers::*;
 pub use index_account_history::*;
+pub use index_balance_changes::*;
 pub use index_storage_history::*;
 pub use merkle::*;
 pub use prune::*;
@@ -29,6 +29,10 @@ pub enum StageId {
     TransactionLookup,
     IndexStorageHistory,
     IndexAccountHistory,
+    /// Opt-in stage indexing the balance changes of accounts. Not part of [`StageId::ALL`], since
+    /// the index is only maintained once the stage has run. The node backfills it on startup if
+    /// it's enabled but has no checkpoint yet.
+    IndexBalanceChanges,
     Prune,
     Finish,
     /// Other custom stage with a provided string identifier.
@@ -94,6 +98,7 @@ impl StageId {
             Self::TransactionLookup => "TransactionLookup",
             Self::IndexAccountHistory => "IndexAccountHistory",
             Self::IndexStorageHistory => "IndexStorageHistory",
+            Self::IndexBalanceChanges => "IndexBalanceChanges",
             Self::Prune => "Prune",
             Self::Finish => "Finish",
             Self::Other(s) => s,
@@ -158,6 +163,7 @@ mod tests {
         assert_eq!(StageId::MerkleExecute.to_string(), "MerkleExecute");
         assert_eq!(StageId::IndexAccountHistory.to_string(), "IndexAccountHistory");
         assert_eq!(StageId::IndexStorageHistory.to_string(), "IndexStorageHistory");
+        assert_eq!(StageId::IndexBalanceChanges.to_string(), "IndexBalanceChanges");
         assert_eq!(StageId::TransactionLookup.to_string(), "TransactionLookup");
         assert_eq!(StageId::Finish.to_string(), "Finish");
 
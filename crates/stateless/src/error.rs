@@ -0,0 +1,34 @@
+use alloy_primitives::B256;
+use reth_consensus::ConsensusError;
+use reth_evm::execute::BlockExecutionError;
+use reth_storage_errors::provider::ProviderError;
+use reth_trie_sparse::errors::SparseStateTrieError;
+
+/// Error returned by [`stateless_validation`](crate::stateless_validation).
+#[derive(Debug, thiserror::Error)]
+pub enum StatelessValidationError {
+    /// The witness doesn't contain the header of the parent block.
+    #[error("parent header {0} is missing from the witness")]
+    MissingParentHeader(B256),
+    /// The ancestor headers of the witness don't form a chain.
+    #[error("ancestor header {number} is not the child of the previous ancestor header")]
+    InvalidAncestorChain {
+        /// Number of the first header that doesn't link to its predecessor.
+        number: u64,
+    },
+    /// An ancestor header couldn't be decoded.
+    #[error(transparent)]
+    Rlp(#[from] alloy_rlp::Error),
+    /// Reading the state from the witness failed.
+    #[error(transparent)]
+    Witness(#[from] ProviderError),
+    /// Revealing or updating the state trie failed.
+    #[error(transparent)]
+    StateTrie(#[from] SparseStateTrieError),
+    /// The block couldn't be executed.
+    #[error(transparent)]
+    Execution(#[from] BlockExecutionError),
+    /// The block is invalid.
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+}
@@ -0,0 +1,31 @@
+//! Stateless execution of blocks.
+//!
+//! An [`ExecutionWitness`](alloy_rpc_types_debug::ExecutionWitness) contains the trie nodes,
+//! bytecodes and ancestor headers that are needed to execute a block. [`WitnessDatabase`] serves
+//! the state of the parent block from them, so that a block can be executed without a database.
+//!
+//! [`stateless_validation`] fully validates a block from its witness: it executes the block on a
+//! [`WitnessDatabase`], checks the receipts, and computes the post-state root on a sparse trie
+//! that is revealed from the trie nodes of the witness.
+
+#![doc(
+    html_logo_url = "https://raw.githubusercontent.com/paradigmxyz/reth/main/assets/reth-docs.png",
+    html_favicon_url = "https://avatars0.githubusercontent.com/u/97369466?s=256",
+    issue_tracker_base_url = "https://github.com/paradigmxyz/reth/issues/"
+)]
+#![cfg_attr(not(test), warn(unused_crate_dependencies))]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+mod error;
+pub use error::StatelessValidationError;
+
+mod root;
+
+mod trie;
+pub use trie::WitnessTrie;
+
+mod witness_db;
+pub use witness_db::WitnessDatabase;
+
+mod validation;
+pub use validation::stateless_validation;
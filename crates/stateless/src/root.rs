@@ -0,0 +1,156 @@
+//! State root calculation on top of the trie nodes of a witness.
+
+use crate::WitnessTrie;
+use alloy_primitives::{map::B256Map, B256};
+use alloy_rlp::Decodable;
+use reth_storage_errors::provider::ProviderResult;
+use reth_trie_common::{
+    DecodedMultiProof, DecodedStorageMultiProof, HashedPostState, Nibbles, TrieAccount,
+    EMPTY_ROOT_HASH,
+};
+use reth_trie_sparse::{
+    errors::{SparseStateTrieErrorKind, SparseStateTrieResult, SparseTrieErrorKind},
+    RevealableSparseTrie, SparseStateTrie,
+};
+
+/// Returns the nodes of the state trie with the given root that are part of the witness, together
+/// with the storage tries of all accounts whose storage root is part of the witness, as a
+/// multiproof that can be revealed in a [`SparseStateTrie`].
+pub(crate) fn witness_multiproof(
+    witness: &WitnessTrie,
+    state_root: B256,
+) -> ProviderResult<DecodedMultiProof> {
+    let mut storage_roots = Vec::new();
+    let account_subtree = witness.decoded_nodes(state_root, |path, value| {
+        let account = TrieAccount::decode(&mut &value[..])?;
+        storage_roots.push((B256::from_slice(&path.pack()), account.storage_root));
+        Ok(())
+    })?;
+
+    let mut storages = B256Map::default();
+    for (hashed_address, storage_root) in storage_roots {
+        // The storage of the account wasn't accessed, so it stays blinded.
+        if storage_root != EMPTY_ROOT_HASH && !witness.contains(&storage_root) {
+            continue
+        }
+
+        let subtree = witness.decoded_nodes(storage_root, |_, _| Ok(()))?;
+        storages.insert(
+            hashed_address,
+            DecodedStorageMultiProof {
+                root: storage_root,
+                subtree,
+                branch_node_masks: Default::default(),
+            },
+        );
+    }
+
+    Ok(DecodedMultiProof { account_subtree, branch_node_masks: Default::default(), storages })
+}
+
+/// Applies the changes of the block to the revealed state trie and returns the new state root.
+pub(crate) fn calculate_state_root(
+    trie: &mut SparseStateTrie,
+    state: HashedPostState,
+) -> SparseStateTrieResult<B256> {
+    let mut storages = state.storages.into_iter().collect::<Vec<_>>();
+    storages.sort_unstable_by_key(|(hashed_address, _)| *hashed_address);
+    for (hashed_address, storage) in storages {
+        let revealed = trie.take_storage_trie(&hashed_address);
+        let mut storage_trie = match revealed {
+            _ if storage.wiped => RevealableSparseTrie::revealed_empty(),
+            Some(storage_trie) => storage_trie,
+            // Accounts that don't exist in the parent state start with empty storage.
+            None if trie.get_account_value(&hashed_address).is_none() => {
+                RevealableSparseTrie::revealed_empty()
+            }
+            None => {
+                return Err(SparseStateTrieErrorKind::SparseStorageTrie(
+                    hashed_address,
+                    SparseTrieErrorKind::Blind,
+                )
+                .into())
+            }
+        };
+
+        let mut slots = storage.storage.into_iter().collect::<Vec<_>>();
+        slots.sort_unstable_by_key(|(hashed_slot, _)| *hashed_slot);
+        for (hashed_slot, value) in slots {
+            let path = Nibbles::unpack(hashed_slot);
+            if value.is_zero() {
+                storage_trie.remove_leaf(&path)?;
+            } else {
+                storage_trie.update_leaf(path, alloy_rlp::encode_fixed_size(&value).to_vec())?;
+            }
+        }
+        trie.insert_storage_trie(hashed_address, storage_trie);
+    }
+
+    let mut accounts = state.accounts.into_iter().collect::<Vec<_>>();
+    accounts.sort_unstable_by_key(|(hashed_address, _)| *hashed_address);
+    for (hashed_address, account) in accounts {
+        trie.update_account_stateless(hashed_address, account)?;
+    }
+
+    trie.root()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use alloy_primitives::{keccak256, Address, U256};
+    use alloy_rpc_types_debug::ExecutionWitness;
+    use reth_primitives_traits::Account;
+    use reth_trie_common::{proof::ProofRetainer, HashBuilder, HashedStorage};
+    use std::collections::BTreeMap;
+
+    /// Returns the root of the state trie with the given accounts and a witness with all its nodes.
+    pub(crate) fn state_trie(accounts: &BTreeMap<B256, TrieAccount>) -> (B256, ExecutionWitness) {
+        let targets = accounts.keys().map(Nibbles::unpack);
+        let mut hash_builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::from_iter(targets));
+        for (hashed_address, account) in accounts {
+            hash_builder.add_leaf(Nibbles::unpack(hashed_address), &alloy_rlp::encode(account));
+        }
+        let root = hash_builder.root();
+        let witness = ExecutionWitness {
+            state: hash_builder.take_proof_nodes().into_inner().into_values().collect(),
+            ..Default::default()
+        };
+        (root, witness)
+    }
+
+    #[test]
+    fn state_root_from_witness() {
+        let account = |i: u64| Account { nonce: i, balance: U256::from(i), bytecode_hash: None };
+        let hashed_address = |i: u8| keccak256(Address::with_last_byte(i));
+        let mut accounts = (0..20u8)
+            .map(|i| (hashed_address(i), account(i as u64).into_trie_account(EMPTY_ROOT_HASH)))
+            .collect::<BTreeMap<_, _>>();
+        let (state_root, witness) = state_trie(&accounts);
+
+        let mut trie = SparseStateTrie::new();
+        trie.reveal_decoded_multiproof(
+            witness_multiproof(&WitnessTrie::new(&witness), state_root).unwrap(),
+        )
+        .unwrap();
+
+        // Update an account and its storage, remove an account and create a new one.
+        let slot = keccak256(B256::with_last_byte(1));
+        let mut state = HashedPostState::default();
+        state.accounts.insert(hashed_address(3), Some(account(30)));
+        state.accounts.insert(hashed_address(5), None);
+        state.accounts.insert(hashed_address(200), Some(account(200)));
+        state
+            .storages
+            .insert(hashed_address(3), HashedStorage::from_iter(false, [(slot, U256::from(1))]));
+
+        let mut storage = HashBuilder::default();
+        storage.add_leaf(Nibbles::unpack(slot), &alloy_rlp::encode_fixed_size(&U256::from(1)));
+        accounts.insert(hashed_address(3), account(30).into_trie_account(storage.root()));
+        accounts.remove(&hashed_address(5));
+        accounts.insert(hashed_address(200), account(200).into_trie_account(EMPTY_ROOT_HASH));
+
+        assert_eq!(calculate_state_root(&mut trie, state).unwrap(), state_trie(&accounts).0);
+    }
+}
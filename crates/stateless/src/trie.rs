@@ -0,0 +1,208 @@
+use alloy_primitives::{keccak256, map::B256Map, Address, Bytes, B256, U256};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use reth_trie_common::{
+    proof::DecodedProofNodes, Nibbles, RlpNode, TrieAccount, TrieNode, EMPTY_ROOT_HASH,
+};
+
+/// The trie nodes of an [`ExecutionWitness`], keyed by their hash.
+///
+/// Accounts and storage slots are looked up by walking the nodes from the root of the trie. A
+/// lookup fails if it reaches a node that is not part of the witness.
+#[derive(Debug, Clone, Default)]
+pub struct WitnessTrie {
+    nodes: B256Map<Bytes>,
+}
+
+impl WitnessTrie {
+    /// Creates a new trie from the state nodes of the witness.
+    pub fn new(witness: &ExecutionWitness) -> Self {
+        Self { nodes: witness.state.iter().map(|node| (keccak256(node), node.clone())).collect() }
+    }
+
+    /// Returns `true` if the node with the given hash is part of the witness.
+    pub fn contains(&self, hash: &B256) -> bool {
+        self.nodes.contains_key(hash)
+    }
+
+    /// Returns the account with the given address in the state trie with the given root.
+    pub fn account(
+        &self,
+        state_root: B256,
+        address: Address,
+    ) -> ProviderResult<Option<TrieAccount>> {
+        self.get(state_root, keccak256(address))?
+            .map(|value| TrieAccount::decode(&mut value.as_slice()))
+            .transpose()
+            .map_err(Into::into)
+    }
+
+    /// Returns the value of the storage slot in the storage trie with the given root.
+    pub fn storage(&self, storage_root: B256, slot: U256) -> ProviderResult<U256> {
+        let Some(value) = self.get(storage_root, keccak256(B256::from(slot)))? else {
+            return Ok(U256::ZERO)
+        };
+        Ok(U256::decode(&mut value.as_slice())?)
+    }
+
+    /// Returns the value of the leaf with the given key in the trie with the given root.
+    fn get(&self, root: B256, key: B256) -> ProviderResult<Option<Vec<u8>>> {
+        if root == EMPTY_ROOT_HASH {
+            return Ok(None)
+        }
+
+        let path = Nibbles::unpack(key);
+        let mut depth = 0;
+        let mut node = self.node(root)?;
+        loop {
+            match TrieNode::decode(&mut &node[..])? {
+                TrieNode::EmptyRoot => return Ok(None),
+                TrieNode::Branch(branch) => {
+                    if depth == path.len() {
+                        return Ok(None)
+                    }
+                    let nibble = path.get_unchecked(depth);
+                    if !branch.state_mask.is_bit_set(nibble) {
+                        return Ok(None)
+                    }
+
+                    // The stack only contains the children that are set in the state mask.
+                    let index = (branch.state_mask.get() & ((1 << nibble) - 1)).count_ones();
+                    node = self.child(&branch.stack[index as usize])?;
+                    depth += 1;
+                }
+                TrieNode::Extension(extension) => {
+                    let end = depth + extension.key.len();
+                    if end > path.len() || path.slice(depth..end) != extension.key {
+                        return Ok(None)
+                    }
+
+                    node = self.child(&extension.child)?;
+                    depth = end;
+                }
+                TrieNode::Leaf(leaf) => {
+                    return Ok((path.slice(depth..) == leaf.key).then_some(leaf.value))
+                }
+            }
+        }
+    }
+
+    /// Returns the nodes of the trie with the given root that are part of the witness, keyed by
+    /// their path.
+    ///
+    /// Nodes that are embedded in their parent are not returned separately, and children that are
+    /// not part of the witness are left blinded. `on_leaf` is called with the full path and value
+    /// of every leaf that is not embedded, which includes all leaves of the account trie.
+    pub(crate) fn decoded_nodes(
+        &self,
+        root: B256,
+        mut on_leaf: impl FnMut(Nibbles, &[u8]) -> ProviderResult<()>,
+    ) -> ProviderResult<DecodedProofNodes> {
+        let mut nodes = DecodedProofNodes::default();
+        if root == EMPTY_ROOT_HASH {
+            nodes.insert(Nibbles::default(), TrieNode::EmptyRoot);
+            return Ok(nodes)
+        }
+
+        let mut stack = vec![(Nibbles::default(), self.node(root)?)];
+        while let Some((path, rlp)) = stack.pop() {
+            let node = TrieNode::decode(&mut &rlp[..])?;
+            match &node {
+                TrieNode::EmptyRoot => {}
+                TrieNode::Branch(branch) => {
+                    let mut children = branch.stack.iter();
+                    for nibble in 0..16u8 {
+                        if !branch.state_mask.is_bit_set(nibble) {
+                            continue
+                        }
+                        let Some(child) = children.next() else { break };
+                        if let Some(hash) = child.as_hash() &&
+                            let Some(child) = self.nodes.get(&hash)
+                        {
+                            let mut child_path = path;
+                            child_path.push_unchecked(nibble);
+                            stack.push((child_path, child.clone()));
+                        }
+                    }
+                }
+                TrieNode::Extension(extension) => {
+                    if let Some(hash) = extension.child.as_hash() &&
+                        let Some(child) = self.nodes.get(&hash)
+                    {
+                        let mut child_path = path;
+                        child_path.extend(&extension.key);
+                        stack.push((child_path, child.clone()));
+                    }
+                }
+                TrieNode::Leaf(leaf) => {
+                    let mut full_path = path;
+                    full_path.extend(&leaf.key);
+                    on_leaf(full_path, &leaf.value)?;
+                }
+            }
+            nodes.insert(path, node);
+        }
+
+        Ok(nodes)
+    }
+
+    /// Returns the RLP of the node with the given hash.
+    fn node(&self, hash: B256) -> ProviderResult<Bytes> {
+        self.nodes.get(&hash).cloned().ok_or_else(|| {
+            ProviderError::TrieWitnessError(format!("trie node {hash} is missing from the witness"))
+        })
+    }
+
+    /// Returns the RLP of a child node, which is either embedded in its parent or referenced by
+    /// its hash.
+    fn child(&self, child: &RlpNode) -> ProviderResult<Bytes> {
+        match child.as_hash() {
+            Some(hash) => self.node(hash),
+            None => Ok(Bytes::copy_from_slice(child)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_consensus::constants::KECCAK_EMPTY;
+    use reth_trie_common::{proof::ProofRetainer, HashBuilder};
+
+    #[test]
+    fn walk_witness_trie() {
+        let accounts = (0..100u64)
+            .map(|i| {
+                let address = Address::with_last_byte(i as u8);
+                let account = TrieAccount {
+                    nonce: i,
+                    balance: U256::from(i * 1000),
+                    storage_root: EMPTY_ROOT_HASH,
+                    code_hash: KECCAK_EMPTY,
+                };
+                (keccak256(address), (address, account))
+            })
+            .collect::<std::collections::BTreeMap<_, _>>();
+
+        // Only the first ten accounts are part of the witness.
+        let targets = accounts.keys().take(10).map(Nibbles::unpack);
+        let mut hash_builder =
+            HashBuilder::default().with_proof_retainer(ProofRetainer::from_iter(targets));
+        for (hashed_address, (_, account)) in &accounts {
+            hash_builder.add_leaf(Nibbles::unpack(hashed_address), &alloy_rlp::encode(account));
+        }
+        let state_root = hash_builder.root();
+        let witness = ExecutionWitness {
+            state: hash_builder.take_proof_nodes().into_inner().into_values().collect(),
+            ..Default::default()
+        };
+
+        let trie = WitnessTrie::new(&witness);
+        for (address, account) in accounts.values().take(10) {
+            assert_eq!(trie.account(state_root, *address).unwrap(), Some(*account));
+        }
+        assert!(trie.account(EMPTY_ROOT_HASH, Address::ZERO).unwrap().is_none());
+        assert!(trie.account(B256::repeat_byte(0x11), Address::ZERO).is_err());
+    }
+}
@@ -0,0 +1,251 @@
+use crate::{
+    root::{calculate_state_root, witness_multiproof},
+    StatelessValidationError, WitnessDatabase,
+};
+use alloy_consensus::BlockHeader;
+use alloy_primitives::B256;
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use reth_consensus::{ConsensusError, FullConsensus};
+use reth_evm::{
+    execute::{BlockExecutionOutput, Executor},
+    ConfigureEvm,
+};
+use reth_primitives_traits::{
+    BlockTy, GotExpected, HeaderTy, ReceiptTy, RecoveredBlock, SealedHeader,
+};
+use reth_trie_common::{HashedPostState, KeccakKeyHasher};
+use reth_trie_sparse::SparseStateTrie;
+
+/// Validates a block using only its [`ExecutionWitness`].
+///
+/// The parent header is taken from the ancestor headers of the witness, which must form a chain
+/// that ends at the parent of the block. The block is validated against its parent, executed on
+/// top of the state of the witness, and its receipts and post-state root are checked against the
+/// header.
+///
+/// Returns the output of the execution if the block is valid.
+pub fn stateless_validation<E, C>(
+    block: &RecoveredBlock<BlockTy<E::Primitives>>,
+    witness: &ExecutionWitness,
+    evm_config: &E,
+    consensus: &C,
+) -> Result<BlockExecutionOutput<ReceiptTy<E::Primitives>>, StatelessValidationError>
+where
+    E: ConfigureEvm,
+    C: FullConsensus<E::Primitives>,
+{
+    let parent = parent_header::<HeaderTy<E::Primitives>>(block.parent_hash(), witness)?;
+
+    consensus.validate_header(block.sealed_header())?;
+    consensus.validate_header_against_parent(block.sealed_header(), &parent)?;
+    consensus.validate_block_pre_execution(block.sealed_block())?;
+
+    let db = WitnessDatabase::new::<HeaderTy<E::Primitives>>(parent.state_root(), witness)?;
+    let mut trie = SparseStateTrie::new();
+    trie.reveal_decoded_multiproof(witness_multiproof(db.trie(), parent.state_root())?)?;
+
+    let output = evm_config.executor(db).execute(block)?;
+    consensus.validate_block_post_execution(block, &output.result, None)?;
+
+    let hashed_state = HashedPostState::from_bundle_state::<KeccakKeyHasher>(output.state.state());
+    let state_root = calculate_state_root(&mut trie, hashed_state)?;
+    if state_root != block.state_root() {
+        return Err(ConsensusError::BodyStateRootDiff(
+            GotExpected { got: state_root, expected: block.state_root() }.into(),
+        )
+        .into())
+    }
+
+    Ok(output)
+}
+
+/// Returns the header of the parent block from the ancestor headers of the witness, after checking
+/// that the ancestor headers form a chain.
+fn parent_header<H: reth_primitives_traits::BlockHeader>(
+    parent_hash: B256,
+    witness: &ExecutionWitness,
+) -> Result<SealedHeader<H>, StatelessValidationError> {
+    let mut headers = witness
+        .headers
+        .iter()
+        .map(|header| Ok(SealedHeader::seal_slow(H::decode(&mut header.as_ref())?)))
+        .collect::<Result<Vec<_>, alloy_rlp::Error>>()?;
+    headers.sort_unstable_by_key(|header| header.number());
+
+    for window in headers.windows(2) {
+        if window[1].parent_hash() != window[0].hash() {
+            return Err(StatelessValidationError::InvalidAncestorChain {
+                number: window[1].number(),
+            })
+        }
+    }
+
+    headers
+        .pop()
+        .filter(|header| header.hash() == parent_hash)
+        .ok_or(StatelessValidationError::MissingParentHeader(parent_hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::root::tests::state_trie;
+    use alloy_consensus::{
+        constants::EMPTY_OMMER_ROOT_HASH,
+        proofs::{calculate_receipt_root, calculate_transaction_root},
+        Header, TxLegacy, TxReceipt,
+    };
+    use alloy_primitives::{keccak256, Address, Bytes, TxKind, U256};
+    use reth_chainspec::{ChainSpec, ChainSpecBuilder, MAINNET};
+    use reth_ethereum_consensus::EthBeaconConsensus;
+    use reth_ethereum_primitives::{Block, BlockBody, Receipt, Transaction, TxType};
+    use reth_evm_ethereum::EthEvmConfig;
+    use reth_primitives_traits::{Account, Block as _, SignedTransaction};
+    use reth_testing_utils::generators::{self, sign_tx_with_random_key_pair};
+    use reth_trie_common::EMPTY_ROOT_HASH;
+    use std::{collections::BTreeMap, sync::Arc};
+
+    const BASE_FEE: u64 = 7;
+    const GAS_PRICE: u128 = 10;
+    const TRANSFER_GAS: u64 = 21_000;
+    const INITIAL_BALANCE: u64 = 1_000_000_000_000_000_000;
+
+    /// Returns the root of the state trie with the given accounts and a witness with its nodes.
+    fn state(accounts: &[(Address, u64, u64)]) -> (B256, ExecutionWitness) {
+        let accounts = accounts
+            .iter()
+            .map(|(address, nonce, balance)| {
+                let account =
+                    Account { nonce: *nonce, balance: U256::from(*balance), bytecode_hash: None };
+                (keccak256(address), account.into_trie_account(EMPTY_ROOT_HASH))
+            })
+            .collect::<BTreeMap<_, _>>();
+        state_trie(&accounts)
+    }
+
+    /// Creates a Shanghai block with a single transfer on top of a parent with a funded sender,
+    /// and the witness of the parent state. The block commits to the given post-state root, or to
+    /// the correct one if `None`.
+    fn transfer_block(
+        post_state_root: Option<B256>,
+    ) -> (Arc<ChainSpec>, RecoveredBlock<Block>, ExecutionWitness) {
+        let chain_spec = Arc::new(
+            ChainSpecBuilder::default()
+                .chain(MAINNET.chain)
+                .genesis(MAINNET.genesis.clone())
+                .shanghai_activated()
+                .build(),
+        );
+
+        let recipient = Address::with_last_byte(0xaa);
+        let beneficiary = Address::with_last_byte(0xbb);
+        let tx = sign_tx_with_random_key_pair(
+            &mut generators::rng(),
+            Transaction::Legacy(TxLegacy {
+                chain_id: Some(1),
+                nonce: 0,
+                gas_price: GAS_PRICE,
+                gas_limit: TRANSFER_GAS,
+                to: TxKind::Call(recipient),
+                value: U256::from(1),
+                input: Bytes::default(),
+            }),
+        );
+        let sender = tx.recover_signer().unwrap();
+
+        // Unrelated accounts, so that the trie has branch nodes
+        let mut parent_accounts =
+            (1..=8).map(|i| (Address::with_last_byte(i), 0, 1)).collect::<Vec<_>>();
+        let mut post_accounts = parent_accounts.clone();
+        parent_accounts.push((sender, 0, INITIAL_BALANCE));
+        post_accounts.extend([
+            (sender, 1, INITIAL_BALANCE - TRANSFER_GAS * GAS_PRICE as u64 - 1),
+            (recipient, 0, 1),
+            (beneficiary, 0, TRANSFER_GAS * (GAS_PRICE as u64 - BASE_FEE)),
+        ]);
+        let (parent_state_root, mut witness) = state(&parent_accounts);
+
+        let parent = Header {
+            number: 1,
+            timestamp: 12,
+            gas_limit: 30_000_000,
+            // At the gas target, so that the base fee doesn't change
+            gas_used: 15_000_000,
+            base_fee_per_gas: Some(BASE_FEE),
+            state_root: parent_state_root,
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            ..Default::default()
+        };
+        witness.headers = vec![alloy_rlp::encode(&parent).into()];
+
+        let receipt = Receipt {
+            tx_type: TxType::Legacy,
+            success: true,
+            cumulative_gas_used: TRANSFER_GAS,
+            logs: vec![],
+        };
+        let transactions = vec![tx];
+        let header = Header {
+            parent_hash: parent.hash_slow(),
+            number: 2,
+            timestamp: 24,
+            gas_limit: 30_000_000,
+            gas_used: TRANSFER_GAS,
+            base_fee_per_gas: Some(BASE_FEE),
+            beneficiary,
+            state_root: post_state_root.unwrap_or_else(|| state(&post_accounts).0),
+            transactions_root: calculate_transaction_root(&transactions),
+            receipts_root: calculate_receipt_root(&[receipt.with_bloom_ref()]),
+            withdrawals_root: Some(EMPTY_ROOT_HASH),
+            ommers_hash: EMPTY_OMMER_ROOT_HASH,
+            ..Default::default()
+        };
+        let block = Block::new(
+            header,
+            BlockBody { transactions, ommers: vec![], withdrawals: Some(Default::default()) },
+        )
+        .seal_slow()
+        .try_recover()
+        .unwrap();
+
+        (chain_spec, block, witness)
+    }
+
+    #[test]
+    fn validates_block_from_witness() {
+        let (chain_spec, block, witness) = transfer_block(None);
+
+        let output = stateless_validation(
+            &block,
+            &witness,
+            &EthEvmConfig::new(chain_spec.clone()),
+            &EthBeaconConsensus::new(chain_spec),
+        )
+        .unwrap();
+        assert_eq!(output.result.gas_used, TRANSFER_GAS);
+        assert_eq!(output.result.receipts.len(), 1);
+    }
+
+    #[test]
+    fn rejects_block_with_wrong_state_root() {
+        let (chain_spec, block, witness) = transfer_block(Some(B256::with_last_byte(1)));
+
+        let err = stateless_validation(
+            &block,
+            &witness,
+            &EthEvmConfig::new(chain_spec.clone()),
+            &EthBeaconConsensus::new(chain_spec),
+        )
+        .unwrap_err();
+        assert!(
+            matches!(
+                err,
+                StatelessValidationError::Consensus(ConsensusError::BodyStateRootDiff(ref diff))
+                    if diff.expected == B256::with_last_byte(1)
+            ),
+            "unexpected error: {err}"
+        );
+    }
+}
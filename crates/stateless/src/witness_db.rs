@@ -0,0 +1,113 @@
+use crate::WitnessTrie;
+use alloy_consensus::BlockHeader;
+use alloy_primitives::{keccak256, map::B256Map, Address, B256, U256};
+use alloy_rlp::Decodable;
+use alloy_rpc_types_debug::ExecutionWitness;
+use reth_primitives_traits::Account;
+use reth_revm::{bytecode::Bytecode, state::AccountInfo, Database};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::collections::BTreeMap;
+
+/// A [`Database`] that serves the state of a block from an [`ExecutionWitness`].
+///
+/// Accounts and storage slots are read from the trie nodes of the witness, bytecodes from its codes
+/// and block hashes from its ancestor headers. Reading anything that is not part of the witness
+/// fails with [`ProviderError::TrieWitnessError`].
+#[derive(Debug, Clone)]
+pub struct WitnessDatabase {
+    /// The trie nodes of the witness.
+    trie: WitnessTrie,
+    /// The state root the state is read at.
+    state_root: B256,
+    /// Bytecodes of the witness by their hash.
+    bytecodes: B256Map<Bytecode>,
+    /// Hashes of the ancestor headers of the witness by their number.
+    block_hashes: BTreeMap<u64, B256>,
+    /// Storage roots of the accounts that were read, because storage is always read after the
+    /// account.
+    storage_roots: BTreeMap<Address, B256>,
+}
+
+impl WitnessDatabase {
+    /// Creates a database that serves the state at the given state root, usually the state root of
+    /// the parent of the block that is executed.
+    ///
+    /// The ancestor headers of the witness are decoded as `H`.
+    pub fn new<H: BlockHeader + Decodable>(
+        state_root: B256,
+        witness: &ExecutionWitness,
+    ) -> ProviderResult<Self> {
+        let mut block_hashes = BTreeMap::new();
+        for header in &witness.headers {
+            let number = H::decode(&mut header.as_ref())?.number();
+            block_hashes.insert(number, keccak256(header));
+        }
+
+        Ok(Self {
+            trie: WitnessTrie::new(witness),
+            state_root,
+            bytecodes: witness
+                .codes
+                .iter()
+                .map(|code| (keccak256(code), Bytecode::new_raw(code.clone())))
+                .collect(),
+            block_hashes,
+            storage_roots: BTreeMap::new(),
+        })
+    }
+
+    /// Returns the trie nodes of the witness.
+    pub const fn trie(&self) -> &WitnessTrie {
+        &self.trie
+    }
+
+    /// Returns the hashes of the ancestor headers of the witness by their number.
+    pub const fn block_hashes(&self) -> &BTreeMap<u64, B256> {
+        &self.block_hashes
+    }
+}
+
+impl Database for WitnessDatabase {
+    type Error = ProviderError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let Some(account) = self.trie.account(self.state_root, address)? else { return Ok(None) };
+        self.storage_roots.insert(address, account.storage_root);
+
+        Ok(Some(
+            Account {
+                nonce: account.nonce,
+                balance: account.balance,
+                bytecode_hash: Some(account.code_hash),
+            }
+            .into(),
+        ))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        self.bytecodes.get(&code_hash).cloned().ok_or_else(|| {
+            ProviderError::TrieWitnessError(format!(
+                "bytecode {code_hash} is missing from the witness"
+            ))
+        })
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let storage_root = match self.storage_roots.get(&address) {
+            Some(storage_root) => *storage_root,
+            None => match self.trie.account(self.state_root, address)? {
+                Some(account) => account.storage_root,
+                None => return Ok(U256::ZERO),
+            },
+        };
+        self.trie.storage(storage_root, index)
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        self.block_hashes.get(&number).copied().ok_or_else(|| {
+            ProviderError::TrieWitnessError(format!(
+                "header of block {number} is missing from the witness"
+            ))
+        })
+    }
+}
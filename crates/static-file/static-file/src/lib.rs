@@ -8,9 +8,15 @@
 #![cfg_attr(docsrs, feature(doc_cfg))]
 #![cfg_attr(not(test), warn(unused_crate_dependencies))]
 
+mod recompressor;
 pub mod segments;
 mod static_file_producer;
 
+pub use recompressor::{
+    SegmentRecompressionReport, StaticFileRecompressionReport, StaticFileRecompressor,
+    DEFAULT_RECOMPRESSION_SEGMENTS,
+};
+
 pub use static_file_producer::{
     StaticFileProducer, StaticFileProducerInner, StaticFileProducerResult,
     StaticFileProducerWithResult,
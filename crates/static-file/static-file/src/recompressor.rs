@@ -0,0 +1,141 @@
+//! Support for recompressing cold static files.
+
+use reth_primitives_traits::FastInstant as Instant;
+use reth_provider::{
+    providers::RecompressedStaticFile, ChainStateBlockReader, DatabaseProviderFactory,
+    StaticFileProviderFactory,
+};
+use reth_static_file_types::{Compression, StaticFileSegment};
+use reth_storage_errors::provider::ProviderResult;
+use tracing::{debug, info};
+
+/// Segments recompressed by default, the ones that take up most of the space.
+pub const DEFAULT_RECOMPRESSION_SEGMENTS: [StaticFileSegment; 2] =
+    [StaticFileSegment::Transactions, StaticFileSegment::Receipts];
+
+/// Recompresses static files that only contain finalized blocks.
+///
+/// Static files are written uncompressed, since they're appended to block by block. Once a static
+/// file is complete and its blocks are finalized, it's never modified again and can be rewritten
+/// with a compression that needs a pass over all of its data first, like zstd with dictionaries
+/// trained per column. See
+/// [`StaticFileProvider::recompress_jar`](reth_provider::providers::StaticFileProvider::recompress_jar).
+#[derive(Debug, Clone)]
+pub struct StaticFileRecompressor<Provider> {
+    /// Provider factory
+    provider: Provider,
+    /// Segments to recompress.
+    segments: Vec<StaticFileSegment>,
+    /// Compression to rewrite the static files with.
+    compression: Compression,
+}
+
+impl<Provider> StaticFileRecompressor<Provider> {
+    /// Creates a new [`StaticFileRecompressor`] that recompresses the
+    /// [`DEFAULT_RECOMPRESSION_SEGMENTS`] with zstd dictionaries.
+    pub fn new(provider: Provider) -> Self {
+        Self {
+            provider,
+            segments: DEFAULT_RECOMPRESSION_SEGMENTS.to_vec(),
+            compression: Compression::ZstdWithDictionary,
+        }
+    }
+
+    /// Sets the segments to recompress.
+    pub fn with_segments(mut self, segments: Vec<StaticFileSegment>) -> Self {
+        self.segments = segments;
+        self
+    }
+
+    /// Sets the compression to rewrite the static files with.
+    pub const fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+}
+
+impl<Provider> StaticFileRecompressor<Provider>
+where
+    Provider: StaticFileProviderFactory + DatabaseProviderFactory<Provider: ChainStateBlockReader>,
+{
+    /// Recompresses all static files of the configured segments that only contain finalized
+    /// blocks and are not compressed yet.
+    pub fn run(&self) -> ProviderResult<StaticFileRecompressionReport> {
+        let mut report = StaticFileRecompressionReport::default();
+        let Some(finalized) =
+            self.provider.database_provider_ro()?.last_finalized_block_number()?
+        else {
+            debug!(target: "static_file", "No finalized block, skipping recompression");
+            return Ok(report)
+        };
+
+        let static_file_provider = self.provider.static_file_provider();
+        for &segment in &self.segments {
+            let start = Instant::now();
+            let files =
+                static_file_provider.recompress_segment(segment, self.compression, finalized)?;
+            if files.is_empty() {
+                continue
+            }
+
+            let segment_report = SegmentRecompressionReport::new(segment, &files);
+            info!(
+                target: "static_file",
+                %segment,
+                files = segment_report.files,
+                size_before = segment_report.size_before,
+                size_after = segment_report.size_after,
+                saved = segment_report.saved(),
+                elapsed = ?start.elapsed(),
+                "Recompressed static files"
+            );
+            report.segments.push(segment_report);
+        }
+
+        Ok(report)
+    }
+}
+
+/// Space saved by a [`StaticFileRecompressor::run`], per segment.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StaticFileRecompressionReport {
+    /// Segments that had static files recompressed.
+    pub segments: Vec<SegmentRecompressionReport>,
+}
+
+impl StaticFileRecompressionReport {
+    /// Returns the number of bytes saved across all segments.
+    pub fn saved(&self) -> u64 {
+        self.segments.iter().map(SegmentRecompressionReport::saved).sum()
+    }
+}
+
+/// Space saved by recompressing the static files of a single segment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentRecompressionReport {
+    /// The segment.
+    pub segment: StaticFileSegment,
+    /// Number of recompressed static files.
+    pub files: usize,
+    /// Total size of the recompressed static files before recompression, in bytes.
+    pub size_before: u64,
+    /// Total size of the recompressed static files after recompression, in bytes.
+    pub size_after: u64,
+}
+
+impl SegmentRecompressionReport {
+    /// Sums up the recompressed static files of a segment.
+    pub fn new(segment: StaticFileSegment, files: &[RecompressedStaticFile]) -> Self {
+        Self {
+            segment,
+            files: files.len(),
+            size_before: files.iter().map(|file| file.size_before).sum(),
+            size_after: files.iter().map(|file| file.size_after).sum(),
+        }
+    }
+
+    /// Returns the number of bytes saved.
+    pub const fn saved(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+}
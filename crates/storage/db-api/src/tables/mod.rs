@@ -21,7 +21,7 @@ use crate::{
         accounts::BlockNumberAddress,
         blocks::{HeaderHash, StoredBlockOmmers},
         storage_sharded_key::StorageShardedKey,
-        AccountBeforeTx, ClientVersion, CompactU256, IntegerList, ShardedKey,
+        AccountBeforeTx, BalanceChange, ClientVersion, CompactU256, IntegerList, ShardedKey,
         StoredBlockBodyIndices, StoredBlockWithdrawals,
     },
     table::{Decode, DupSort, Encode, Table, TableInfo},
@@ -443,6 +443,16 @@ tables! {
         type Value = BlockNumberList;
     }
 
+    /// Stores the balance changes of an account, sorted by block number.
+    ///
+    /// Only populated if the `IndexBalanceChanges` stage is enabled. Blocks in which an account
+    /// changed without its balance changing are not stored.
+    table AccountBalanceChanges {
+        type Key = Address;
+        type Value = BalanceChange;
+        type SubKey = BlockNumber;
+    }
+
     /// Stores the state of an account before a certain transaction changed it.
     /// Change on state can be: account is created, selfdestructed, touched while empty
     /// or changed balance,nonce.
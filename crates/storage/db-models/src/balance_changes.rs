@@ -0,0 +1,57 @@
+use alloy_primitives::{BlockNumber, U256};
+use reth_primitives_traits::ValueWithSubKey;
+
+/// Balance change of an account in a block, as it is saved in the database.
+///
+/// [`BlockNumber`] is the subkey.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+#[cfg_attr(any(test, feature = "arbitrary"), derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(any(test, feature = "reth-codec"), reth_codecs::add_arbitrary_tests(compact))]
+pub struct BalanceChange {
+    /// Block in which the balance changed. Acts as `DupSort::SubKey`.
+    pub block_number: BlockNumber,
+    /// Balance before the block.
+    pub before: U256,
+    /// Balance after the block.
+    pub after: U256,
+}
+
+impl ValueWithSubKey for BalanceChange {
+    type SubKey = BlockNumber;
+
+    fn get_subkey(&self) -> Self::SubKey {
+        self.block_number
+    }
+}
+
+// NOTE: The block number is encoded as big-endian bytes, so the entries of an account are sorted
+// by block and can be fetched with seek_by_key_subkey. The length of the first balance is stored
+// in a single byte, since both balances are compacted.
+#[cfg(any(test, feature = "reth-codec"))]
+impl reth_codecs::Compact for BalanceChange {
+    fn to_compact<B>(&self, buf: &mut B) -> usize
+    where
+        B: bytes::BufMut + AsMut<[u8]>,
+    {
+        buf.put_slice(&self.block_number.to_be_bytes());
+
+        let mut before = alloc::vec::Vec::with_capacity(32);
+        let before_len = self.before.to_compact(&mut before);
+        buf.put_u8(before_len as u8);
+        buf.put_slice(&before);
+
+        9 + before_len + self.after.to_compact(buf)
+    }
+
+    fn from_compact(buf: &[u8], len: usize) -> (Self, &[u8]) {
+        let block_number = BlockNumber::from_be_bytes(buf[..8].try_into().unwrap());
+        let before_len = buf[8] as usize;
+        let (before, buf) = U256::from_compact(&buf[9..], before_len);
+        let (after, buf) = U256::from_compact(buf, len - 9 - before_len);
+        (Self { block_number, before, after }, buf)
+    }
+}
+
+#[cfg(any(test, feature = "reth-codec"))]
+reth_codecs::impl_compression_for_compact!(BalanceChange);
@@ -15,6 +15,10 @@ extern crate alloc;
 pub mod accounts;
 pub use accounts::AccountBeforeTx;
 
+/// Balance changes
+pub mod balance_changes;
+pub use balance_changes::BalanceChange;
+
 /// Blocks
 pub mod blocks;
 pub use blocks::{StaticFileBlockWithdrawals, StoredBlockBodyIndices, StoredBlockWithdrawals};
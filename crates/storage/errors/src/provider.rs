@@ -123,6 +123,16 @@ pub enum ProviderError {
         /// The earliest available block number.
         earliest_available: BlockNumber,
     },
+    /// A block read from an era1 file doesn't match the canonical chain.
+    #[error("{field} of era1 block #{number} does not match the canonical chain: {mismatch}")]
+    Era1BlockMismatch {
+        /// The block number.
+        number: BlockNumber,
+        /// The field that doesn't match, e.g. the block hash.
+        field: &'static str,
+        /// The value read from the era1 file and the canonical value.
+        mismatch: Box<GotExpected<B256>>,
+    },
     /// Historical state at the requested block may be inconsistent because pipeline sync is in
     /// progress. The Execution stage has advanced `PlainState` beyond the history index coverage,
     /// so the `InPlainState` fallback would return data from a future block.
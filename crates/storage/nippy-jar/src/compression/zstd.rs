@@ -71,7 +71,7 @@ impl Zstd {
     }
 
     /// If using dictionaries, creates a list of [`Compressor`].
-    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'_>>>, NippyJarError> {
+    pub fn compressors(&self) -> Result<Option<Vec<Compressor<'static>>>, NippyJarError> {
         match self.state {
             ZstdState::PendingDictionary => Err(NippyJarError::CompressorNotReady),
             ZstdState::Ready => {
@@ -189,7 +189,6 @@ impl Compression for Zstd {
         matches!(self.state, ZstdState::Ready)
     }
 
-    #[cfg(test)]
     /// If using it with dictionaries, prepares a dictionary for each column.
     fn prepare_compression(
         &mut self,
@@ -277,7 +276,6 @@ impl std::fmt::Debug for ZstdDictionaries<'_> {
 }
 
 impl ZstdDictionaries<'_> {
-    #[cfg(test)]
     /// Creates [`ZstdDictionaries`].
     pub(crate) fn new(raw: Vec<RawDictionary>) -> Self {
         Self(raw.into_iter().map(ZstdDictionary::Raw).collect())
@@ -287,7 +285,7 @@ impl ZstdDictionaries<'_> {
     pub(crate) fn load(raw: Vec<RawDictionary>) -> Self {
         Self(
             raw.into_iter()
-                .map(|dict| ZstdDictionary::Loaded(DecoderDictionary::copy(&dict)))
+                .map(|dict| ZstdDictionary::Loaded(DecoderDictionary::copy(&dict), dict))
                 .collect(),
         )
     }
@@ -304,8 +302,8 @@ impl ZstdDictionaries<'_> {
             .collect::<Result<Vec<_>, _>>()?)
     }
 
-    /// Creates a list of compressors from a list of [`ZstdDictionary::Raw`].
-    pub(crate) fn compressors(&self) -> Result<Vec<Compressor<'_>>, NippyJarError> {
+    /// Creates a list of compressors from the raw dictionaries.
+    pub(crate) fn compressors(&self) -> Result<Vec<Compressor<'static>>, NippyJarError> {
         Ok(self
             .iter()
             .flat_map(|dict| {
@@ -318,19 +316,18 @@ impl ZstdDictionaries<'_> {
 }
 
 /// A Zstd dictionary. It's created and serialized with [`ZstdDictionary::Raw`], and deserialized as
-/// [`ZstdDictionary::Loaded`].
+/// [`ZstdDictionary::Loaded`], which keeps the raw dictionary so that a reloaded jar can still be
+/// serialized and compress new values.
 pub(crate) enum ZstdDictionary<'a> {
-    #[cfg_attr(not(test), expect(dead_code))]
     Raw(RawDictionary),
-    Loaded(DecoderDictionary<'a>),
+    Loaded(DecoderDictionary<'a>, RawDictionary),
 }
 
 impl ZstdDictionary<'_> {
     /// Returns a reference to the expected `RawDictionary`
     pub(crate) const fn raw(&self) -> Option<&RawDictionary> {
         match self {
-            ZstdDictionary::Raw(dict) => Some(dict),
-            ZstdDictionary::Loaded(_) => None,
+            ZstdDictionary::Raw(dict) | ZstdDictionary::Loaded(_, dict) => Some(dict),
         }
     }
 
@@ -338,7 +335,7 @@ impl ZstdDictionary<'_> {
     pub(crate) const fn loaded(&self) -> Option<&DecoderDictionary<'_>> {
         match self {
             ZstdDictionary::Raw(_) => None,
-            ZstdDictionary::Loaded(dict) => Some(dict),
+            ZstdDictionary::Loaded(dict, _) => Some(dict),
         }
     }
 }
@@ -349,7 +346,7 @@ impl<'de> Deserialize<'de> for ZstdDictionary<'_> {
         D: Deserializer<'de>,
     {
         let dict = RawDictionary::deserialize(deserializer)?;
-        Ok(Self::Loaded(DecoderDictionary::copy(&dict)))
+        Ok(Self::Loaded(DecoderDictionary::copy(&dict), dict))
     }
 }
 
@@ -359,8 +356,7 @@ impl Serialize for ZstdDictionary<'_> {
         S: Serializer,
     {
         match self {
-            ZstdDictionary::Raw(r) => r.serialize(serializer),
-            ZstdDictionary::Loaded(_) => unreachable!(),
+            ZstdDictionary::Raw(r) | ZstdDictionary::Loaded(_, r) => r.serialize(serializer),
         }
     }
 }
@@ -368,11 +364,6 @@ impl Serialize for ZstdDictionary<'_> {
 #[cfg(test)]
 impl PartialEq for ZstdDictionary<'_> {
     fn eq(&self, other: &Self) -> bool {
-        if let (Self::Raw(a), Self::Raw(b)) = (self, &other) {
-            return a == b
-        }
-        unimplemented!(
-            "`DecoderDictionary` can't be compared. So comparison should be done after decompressing a value."
-        );
+        self.raw() == other.raw()
     }
 }
@@ -25,9 +25,7 @@ use tracing::*;
 
 /// Compression algorithms supported by `NippyJar`.
 pub mod compression;
-#[cfg(test)]
-use compression::Compression;
-use compression::Compressors;
+use compression::{Compression, Compressors};
 
 /// empty enum for backwards compatibility
 #[derive(Debug, Serialize, Deserialize)]
@@ -276,10 +274,7 @@ impl<H: NippyJarHeader> NippyJar<H> {
     fn freeze_config(&self) -> Result<(), NippyJarError> {
         Ok(reth_fs_util::atomic_write_file(&self.config_path(), |file| self.save_to_writer(file))?)
     }
-}
 
-#[cfg(test)]
-impl<H: NippyJarHeader> NippyJar<H> {
     /// If required, prepares any compression algorithm to an early pass of the data.
     pub fn prepare_compression(
         &mut self,
@@ -293,6 +288,51 @@ impl<H: NippyJarHeader> NippyJar<H> {
         Ok(())
     }
 
+    /// Copies all rows of this jar into `target` and returns it once committed.
+    ///
+    /// `target` is usually configured with a different compression. If its compressor requires
+    /// dictionaries, they are trained first on an evenly spread sample of at most `max_samples`
+    /// rows of this jar.
+    pub fn copy_rows_to<T: NippyJarHeader>(
+        &self,
+        mut target: NippyJar<T>,
+        max_samples: usize,
+    ) -> Result<NippyJar<T>, NippyJarError> {
+        if target.columns != self.columns {
+            return Err(NippyJarError::ColumnLenMismatch(self.columns, target.columns))
+        }
+
+        let mut cursor = NippyJarCursor::new(self)?;
+
+        if target.compressor.as_ref().is_some_and(|compressor| !compressor.is_ready()) {
+            let step = self.rows.div_ceil(max_samples.max(1)).max(1);
+            let mut samples = vec![Vec::new(); self.columns];
+            for row in (0..self.rows).step_by(step) {
+                let Some(values) = cursor.row_by_number(row)? else { break };
+                for (column, value) in values.into_iter().enumerate() {
+                    samples[column].push(value.to_vec());
+                }
+            }
+            target.prepare_compression(samples)?;
+            cursor.reset();
+        }
+
+        debug!(target: "nippy-jar", from=?self.data_path(), to=?target.data_path(), rows=self.rows, "Copying rows.");
+
+        let mut writer = NippyJarWriter::new(target)?;
+        while let Some(values) = cursor.next_row()? {
+            for value in values {
+                writer.append_column(Some(Ok(value)))?;
+            }
+        }
+        writer.commit()?;
+
+        Ok(writer.into_jar())
+    }
+}
+
+#[cfg(test)]
+impl<H: NippyJarHeader> NippyJar<H> {
     /// Writes all data and configuration to a file and the offset index to another.
     pub fn freeze(
         self,
@@ -611,6 +651,81 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_copy_rows_to_zstd_with_dictionaries() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let source_path = tempfile::NamedTempFile::new().unwrap();
+        let target_path = tempfile::NamedTempFile::new().unwrap();
+
+        let source = NippyJar::new_without_header(num_columns, source_path.path())
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows)
+            .unwrap();
+
+        let target =
+            NippyJar::new_without_header(num_columns, target_path.path()).with_zstd(true, 5000);
+        let target = source.copy_rows_to(target, 50).unwrap();
+        assert_eq!(target.rows(), source.rows());
+
+        let loaded_nippy = NippyJar::load_without_header(target_path.path()).unwrap();
+        if let Some(Compressors::Zstd(zstd)) = loaded_nippy.compressor() {
+            assert!(zstd.use_dict);
+        } else {
+            panic!("Expected Zstd compressor")
+        }
+
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len());
+    }
+
+    #[test]
+    fn test_append_and_prune_reloaded_zstd_with_dictionaries() {
+        let (col1, col2) = test_data(None);
+        let num_rows = col1.len() as u64;
+        let num_columns = 2;
+        let source_path = tempfile::NamedTempFile::new().unwrap();
+        let target_path = tempfile::NamedTempFile::new().unwrap();
+
+        let source = NippyJar::new_without_header(num_columns, source_path.path())
+            .freeze(vec![clone_with_result(&col1), clone_with_result(&col2)], num_rows - 10)
+            .unwrap();
+        let target =
+            NippyJar::new_without_header(num_columns, target_path.path()).with_zstd(true, 5000);
+        source.copy_rows_to(target, 50).unwrap();
+
+        // Appends the remaining rows and prunes the last 5 rows of the reloaded jar
+        let loaded_nippy = NippyJar::load_without_header(target_path.path()).unwrap();
+        let mut writer = NippyJarWriter::new(loaded_nippy).unwrap();
+        writer
+            .append_rows(
+                vec![
+                    clone_with_result(&col1[num_rows as usize - 10..].to_vec()),
+                    clone_with_result(&col2[num_rows as usize - 10..].to_vec()),
+                ],
+                10,
+            )
+            .unwrap();
+        writer.commit().unwrap();
+        writer.prune_rows(5).unwrap();
+        drop(writer);
+
+        let loaded_nippy = NippyJar::load_without_header(target_path.path()).unwrap();
+        assert_eq!(loaded_nippy.rows(), num_rows as usize - 5);
+        let mut cursor = NippyJarCursor::new(&loaded_nippy).unwrap();
+        let mut row_index = 0usize;
+        while let Some(row) = cursor.next_row().unwrap() {
+            assert_eq!((row[0], row[1]), (col1[row_index].as_slice(), col2[row_index].as_slice()));
+            row_index += 1;
+        }
+        assert_eq!(row_index, col1.len() - 5);
+    }
+
     #[test]
     fn test_zstd_no_dictionaries() {
         let (col1, col2) = test_data(None);
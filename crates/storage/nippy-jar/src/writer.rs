@@ -1,12 +1,13 @@
 use crate::{
-    compression::Compression, ColumnResult, NippyJar, NippyJarChecker, NippyJarError,
-    NippyJarHeader,
+    compression::{Compression, Compressors},
+    ColumnResult, NippyJar, NippyJarChecker, NippyJarError, NippyJarHeader,
 };
 use std::{
     fs::{File, OpenOptions},
     io::{BufWriter, Read, Seek, SeekFrom, Write},
     path::Path,
 };
+use zstd::bulk::Compressor;
 
 /// Size of one offset in bytes.
 pub(crate) const OFFSET_SIZE_BYTES: u8 = 8;
@@ -43,6 +44,8 @@ pub struct NippyJarWriter<H: NippyJarHeader = ()> {
     column: usize,
     /// Whether the writer has changed data that needs to be committed.
     dirty: bool,
+    /// Per column compressors, if the jar is compressed with trained zstd dictionaries.
+    column_compressors: Option<ColumnCompressors>,
 }
 
 impl<H: NippyJarHeader> NippyJarWriter<H> {
@@ -70,6 +73,15 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             (jar, data_file.expect("qed"), offsets_file.expect("qed"))
         };
 
+        // Reloaded jars keep the raw dictionaries, so appended values are compressed with them as
+        // well.
+        let column_compressors = match jar.compressor() {
+            Some(Compressors::Zstd(zstd)) if zstd.use_dict => {
+                zstd.compressors().ok().flatten().map(ColumnCompressors)
+            }
+            _ => None,
+        };
+
         let mut writer = Self {
             jar,
             data_file,
@@ -79,6 +91,7 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
             offsets: Vec::with_capacity(1_000_000),
             column: 0,
             dirty: false,
+            column_compressors,
         };
 
         if !is_created {
@@ -227,7 +240,11 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
     /// Writes column to data file. If it's the last column of the row, call `finalize_row()`
     fn write_column(&mut self, value: &[u8]) -> Result<usize, NippyJarError> {
         self.uncompressed_row_size += value.len();
-        let len = if let Some(compression) = &self.jar.compressor {
+        let len = if let Some(compressors) = &mut self.column_compressors {
+            let compressed = compressors.0[self.column].compress(value)?;
+            self.data_file.write_all(&compressed)?;
+            compressed.len()
+        } else if let Some(compression) = &self.jar.compressor {
             let before = self.tmp_buf.len();
             let len = compression.compress_to(value, &mut self.tmp_buf)?;
             self.data_file.write_all(&self.tmp_buf[before..before + len])?;
@@ -485,3 +502,12 @@ impl<H: NippyJarHeader> NippyJarWriter<H> {
         &self.jar
     }
 }
+
+/// Zstd [`Compressor`]s with the trained dictionary of each column loaded.
+struct ColumnCompressors(Vec<Compressor<'static>>);
+
+impl std::fmt::Debug for ColumnCompressors {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnCompressors").field("num", &self.0.len()).finish()
+    }
+}
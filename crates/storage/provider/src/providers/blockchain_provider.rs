@@ -3,13 +3,14 @@ use crate::{
         ConsistentProvider, ProviderNodeTypes, RocksDBProvider, StaticFileProvider,
         StaticFileProviderRWRefMut,
     },
-    AccountReader, BalProvider, BalStoreHandle, BlockHashReader, BlockIdReader, BlockNumReader,
-    BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker, CanonStateNotifications,
-    CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader, ChangeSetReader,
-    DatabaseProviderFactory, HashedPostStateProvider, HeaderProvider, InMemoryBalStore,
-    ProviderError, ProviderFactory, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
-    RocksDBProviderFactory, StageCheckpointReader, StateProviderBox, StateProviderFactory,
-    StateReader, StaticFileProviderFactory, TransactionVariant, TransactionsProvider,
+    AccountReader, BalProvider, BalStoreHandle, BalanceChangeReader, BlockHashReader,
+    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, CanonChainTracker,
+    CanonStateNotifications, CanonStateSubscriptions, ChainSpecProvider, ChainStateBlockReader,
+    ChangeSetReader, DatabaseProviderFactory, HashedPostStateProvider, HeaderProvider,
+    InMemoryBalStore, ProviderError, ProviderFactory, PruneCheckpointReader, ReceiptProvider,
+    ReceiptProviderIdExt, RocksDBProviderFactory, StageCheckpointReader, StateProviderBox,
+    StateProviderFactory, StateReader, StaticFileProviderFactory, TransactionVariant,
+    TransactionsProvider,
 };
 use alloy_consensus::transaction::TransactionMeta;
 use alloy_eips::{BlockHashOrNumber, BlockId, BlockNumHash, BlockNumberOrTag};
@@ -20,7 +21,9 @@ use reth_chain_state::{
     MemoryOverlayStateProvider, PersistedBlockNotifications, PersistedBlockSubscriptions,
 };
 use reth_chainspec::ChainInfo;
-use reth_db_api::models::{AccountBeforeTx, BlockNumberAddress, StoredBlockBodyIndices};
+use reth_db_api::models::{
+    AccountBeforeTx, BalanceChange, BlockNumberAddress, StoredBlockBodyIndices,
+};
 use reth_execution_types::ExecutionOutcome;
 use reth_node_types::{BlockTy, HeaderTy, NodeTypesWithDB, ReceiptTy, TxTy};
 use reth_primitives_traits::{Account, RecoveredBlock, SealedHeader, StorageEntry};
@@ -772,6 +775,21 @@ impl<N: ProviderNodeTypes> ChangeSetReader for BlockchainProvider<N> {
     }
 }
 
+impl<N: ProviderNodeTypes> BalanceChangeReader for BlockchainProvider<N> {
+    fn balance_changes_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        self.database.provider()?.balance_changes_index_tip()
+    }
+
+    fn account_balance_changes(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BalanceChange>> {
+        self.database.provider()?.account_balance_changes(address, range, limit)
+    }
+}
+
 impl<N: ProviderNodeTypes> AccountReader for BlockchainProvider<N> {
     /// Get basic account information.
     fn basic_account(&self, address: &Address) -> ProviderResult<Option<Account>> {
@@ -4,11 +4,12 @@ use crate::{
         database::{chain::ChainStorage, metrics},
         rocksdb::{PendingRocksDBBatches, RocksDBProvider, RocksDBWriteCtx},
         static_file::{StaticFileWriteCtx, StaticFileWriter},
-        NodeTypesForProvider, StaticFileProvider,
+        Era1History, NodeTypesForProvider, StaticFileProvider,
     },
     to_range,
     traits::{
-        AccountExtReader, BlockSource, ChangeSetReader, ReceiptProvider, StageCheckpointWriter,
+        AccountExtReader, BalanceChangeReader, BlockSource, ChangeSetReader, ReceiptProvider,
+        StageCheckpointWriter,
     },
     AccountReader, BlockBodyWriter, BlockExecutionWriter, BlockHashReader, BlockNumReader,
     BlockReader, BlockWriter, BundleStateInit, ChainStateBlockReader, ChainStateBlockWriter,
@@ -39,8 +40,8 @@ use reth_db_api::{
     cursor::{DbCursorRO, DbCursorRW, DbDupCursorRO, DbDupCursorRW},
     database::{Database, ReaderTxnTracker},
     models::{
-        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BlockNumberAddress,
-        BlockNumberAddressRange, ShardedKey, StorageBeforeTx, StorageSettings,
+        sharded_key, storage_sharded_key::StorageShardedKey, AccountBeforeTx, BalanceChange,
+        BlockNumberAddress, BlockNumberAddressRange, ShardedKey, StorageBeforeTx, StorageSettings,
         StoredBlockBodyIndices,
     },
     table::Table,
@@ -903,6 +904,9 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
             if save_mode.with_state() {
                 let start = Instant::now();
                 self.update_history_indices(first_number..=last_block_number)?;
+                if self.balance_changes_index_tip()?.is_some() {
+                    self.insert_balance_changes(&blocks)?;
+                }
                 timings.update_history_indices = start.elapsed();
             }
 
@@ -1013,6 +1017,11 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         // Unwind account history indices.
         self.unwind_account_history_indices(changed_accounts.iter())?;
 
+        // Unwind the balance changes index, if it's maintained.
+        if self.balance_changes_index_tip()?.is_some() {
+            self.unwind_balance_changes_index(changed_accounts.iter())?;
+        }
+
         let changed_storages = self.storage_changesets_range(from..)?;
 
         // Unwind storage hashes.
@@ -1067,6 +1076,41 @@ impl<TX: DbTx + DbTxMut + 'static, N: NodeTypesForProvider> DatabaseProvider<TX,
         Ok(())
     }
 
+    /// Inserts the balance changes of the given blocks into the balance changes index.
+    fn insert_balance_changes(
+        &self,
+        blocks: &[ExecutedBlock<N::Primitives>],
+    ) -> ProviderResult<()> {
+        let mut cursor = self.tx.cursor_dup_write::<tables::AccountBalanceChanges>()?;
+        for block in blocks {
+            let block_number = block.recovered_block().number();
+            let changes = block
+                .execution_outcome()
+                .state
+                .state
+                .iter()
+                .filter_map(|(address, account)| {
+                    let before = account.original_info.as_ref().map(|info| info.balance);
+                    let after = account.info.as_ref().map(|info| info.balance);
+                    (before != after).then(|| {
+                        let change = BalanceChange {
+                            block_number,
+                            before: before.unwrap_or_default(),
+                            after: after.unwrap_or_default(),
+                        };
+                        (*address, change)
+                    })
+                })
+                .collect::<BTreeMap<_, _>>();
+
+            for (address, change) in changes {
+                cursor.upsert(address, &change)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Writes bytecodes to MDBX.
     fn write_bytecodes(
         &self,
@@ -1256,6 +1300,39 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
 }
 
 impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
+    /// Returns the [`Era1History`] if the given block has been expired and can be served from it.
+    fn expired_block_era1_history(&self, number: BlockNumber) -> Option<&Era1History> {
+        if number >= self.static_file_provider.earliest_history_height() {
+            return None
+        }
+        self.static_file_provider.era1_history().filter(|history| history.contains(number))
+    }
+
+    /// Returns the canonical hash of the block, which blocks read from the [`Era1History`] are
+    /// checked against.
+    fn era1_canonical_hash(&self, number: BlockNumber) -> ProviderResult<B256> {
+        self.block_hash(number)?.ok_or_else(|| ProviderError::HeaderNotFound(number.into()))
+    }
+
+    /// Returns the [`Era1History`] if the receipts of the given block have been expired or pruned
+    /// and can be served from it.
+    fn pruned_receipts_era1_history(
+        &self,
+        number: BlockNumber,
+    ) -> ProviderResult<Option<&Era1History>> {
+        let Some(history) =
+            self.static_file_provider.era1_history().filter(|history| history.contains(number))
+        else {
+            return Ok(None)
+        };
+
+        let pruned = number < self.static_file_provider.earliest_history_height() ||
+            self.get_prune_checkpoint(PruneSegment::Receipts)?
+                .and_then(|checkpoint| checkpoint.block_number)
+                .is_some_and(|pruned| number <= pruned);
+        Ok(pruned.then_some(history))
+    }
+
     fn recovered_block<H, HF, B, BF>(
         &self,
         id: BlockHashOrNumber,
@@ -1271,6 +1348,20 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> DatabaseProvider<TX, N> {
         let Some(block_number) = self.convert_hash_or_number(id)? else { return Ok(None) };
         let Some(header) = header_by_number(block_number)? else { return Ok(None) };
 
+        if let Some(history) = self.expired_block_era1_history(block_number) {
+            let hash = self.era1_canonical_hash(block_number)?;
+            let Some(block) = history.block::<BlockTy<N>>(block_number, hash)? else {
+                return Ok(None)
+            };
+            let body = block.into_body();
+            let senders = body
+                .transactions()
+                .iter()
+                .map(|tx| tx.recover_signer_unchecked())
+                .collect::<Result<Vec<_>, _>>()?;
+            return construct_block(header, body, senders)
+        }
+
         // Get the block body
         //
         // If the body indices are not found, this means that the transactions either do not exist
@@ -1821,6 +1912,33 @@ impl<TX: DbTx, N: NodeTypes> ChangeSetReader for DatabaseProvider<TX, N> {
     }
 }
 
+impl<TX: DbTx, N: NodeTypes> BalanceChangeReader for DatabaseProvider<TX, N> {
+    fn balance_changes_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(self
+            .get_stage_checkpoint(StageId::IndexBalanceChanges)?
+            .map(|checkpoint| checkpoint.block_number))
+    }
+
+    fn account_balance_changes(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BalanceChange>> {
+        let mut cursor = self.tx.cursor_dup_read::<tables::AccountBalanceChanges>()?;
+        let mut changes = Vec::new();
+        let mut entry = cursor.seek_by_key_subkey(address, *range.start())?;
+        while let Some(change) = entry &&
+            change.block_number <= *range.end() &&
+            changes.len() < limit
+        {
+            changes.push(change);
+            entry = cursor.next_dup_val()?;
+        }
+        Ok(changes)
+    }
+}
+
 impl<Tx: DbTx + 'static, N: NodeTypesForProvider> StateReader for DatabaseProvider<Tx, N> {
     type Receipt = ReceiptTy<N>;
 
@@ -2045,11 +2163,15 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> BlockReader for DatabaseProvid
     /// If the header is found, but the transactions either do not exist, or are not indexed, this
     /// will return None.
     ///
-    /// Returns an error if the requested block is below the earliest available history.
+    /// Returns an error if the requested block is below the earliest available history, unless it
+    /// can be served from the configured [`Era1History`].
     fn block(&self, id: BlockHashOrNumber) -> ProviderResult<Option<Self::Block>> {
         if let Some(number) = self.convert_hash_or_number(id)? {
             let earliest_available = self.static_file_provider.earliest_history_height();
             if number < earliest_available {
+                if let Some(history) = self.expired_block_era1_history(number) {
+                    return history.block(number, self.era1_canonical_hash(number)?)
+                }
                 return Err(ProviderError::BlockExpired { requested: number, earliest_available })
             }
 
@@ -2231,7 +2353,6 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
         tx_hash: TxHash,
     ) -> ProviderResult<Option<(Self::Transaction, TransactionMeta)>> {
         if let Some(transaction_id) = self.transaction_id(tx_hash)? &&
-            let Some(transaction) = self.transaction_by_id_unhashed(transaction_id)? &&
             let Some(block_number) = self.block_by_transaction_id(transaction_id)? &&
             let Some(sealed_header) = self.sealed_header(block_number)?
         {
@@ -2243,6 +2364,16 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> TransactionsProvider for Datab
                 // index
                 let index = transaction_id - block_body.first_tx_num();
 
+                let transaction =
+                    if let Some(history) = self.expired_block_era1_history(block_number) {
+                        history.block::<BlockTy<N>>(block_number, block_hash)?.and_then(|block| {
+                            block.body().transactions().get(index as usize).cloned()
+                        })
+                    } else {
+                        self.transaction_by_id_unhashed(transaction_id)?
+                    };
+                let Some(transaction) = transaction else { return Ok(None) };
+
                 let meta = TransactionMeta {
                     tx_hash,
                     index,
@@ -2327,6 +2458,18 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
     type Receipt = ReceiptTy<N>;
 
     fn receipt(&self, id: TxNumber) -> ProviderResult<Option<Self::Receipt>> {
+        // Only look up the block of the transaction if there is era1 history to fall back to
+        if self.static_file_provider.era1_history().is_some() &&
+            let Some(number) = self.block_by_transaction_id(id)? &&
+            let Some(history) = self.pruned_receipts_era1_history(number)? &&
+            let Some(body) = self.block_body_indices(number)?
+        {
+            let receipts = history
+                .receipts::<HeaderTy<N>, Self::Receipt>(number, self.era1_canonical_hash(number)?)?
+                .unwrap_or_default();
+            return Ok(receipts.into_iter().nth((id - body.first_tx_num()) as usize))
+        }
+
         self.static_file_provider.get_with_static_file_or_database(
             StaticFileSegment::Receipts,
             id,
@@ -2350,6 +2493,10 @@ impl<TX: DbTx + 'static, N: NodeTypesForProvider> ReceiptProvider for DatabasePr
         if let Some(number) = self.convert_hash_or_number(block)? &&
             let Some(body) = self.block_body_indices(number)?
         {
+            if let Some(history) = self.pruned_receipts_era1_history(number)? {
+                return history.receipts::<HeaderTy<N>, _>(number, self.era1_canonical_hash(number)?)
+            }
+
             let tx_range = body.tx_num_range();
             return if tx_range.is_empty() {
                 Ok(Some(Vec::new()))
@@ -2506,6 +2653,18 @@ impl<TX: DbTxMut, N: NodeTypes> StageCheckpointWriter for DatabaseProvider<TX, N
             )?;
         }
 
+        // The balance changes index is opt-in, so its checkpoint is only updated if it exists.
+        let stage_id = StageId::IndexBalanceChanges;
+        if let Some((_, checkpoint)) = cursor.seek_exact(stage_id.to_string())? {
+            cursor.upsert(
+                stage_id.to_string(),
+                &StageCheckpoint {
+                    block_number,
+                    ..if drop_stage_checkpoint { Default::default() } else { checkpoint }
+                },
+            )?;
+        }
+
         Ok(())
     }
 }
@@ -3669,6 +3828,33 @@ impl<TX: DbTxMut + DbTx + 'static, N: NodeTypes> HistoryWriter for DatabaseProvi
 
         Ok(())
     }
+
+    fn unwind_balance_changes_index<'a>(
+        &self,
+        changesets: impl Iterator<Item = &'a (BlockNumber, AccountBeforeTx)>,
+    ) -> ProviderResult<usize> {
+        let mut cursor = self.tx.cursor_dup_write::<tables::AccountBalanceChanges>()?;
+        let mut walked = 0;
+        for (block_number, account) in changesets {
+            if cursor
+                .seek_by_key_subkey(account.address, *block_number)?
+                .is_some_and(|change| change.block_number == *block_number)
+            {
+                cursor.delete_current()?;
+            }
+            walked += 1;
+        }
+
+        Ok(walked)
+    }
+
+    fn unwind_balance_changes_index_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<usize> {
+        let changesets = self.account_changesets_range(range)?;
+        self.unwind_balance_changes_index(changesets.iter())
+    }
 }
 
 impl<TX: DbTxMut + DbTx + 'static, N: NodeTypesForProvider> BlockExecutionWriter
@@ -4162,8 +4348,11 @@ impl<TX: Send, N: NodeTypes> StoragePath for DatabaseProvider<TX, N> {
 mod tests {
     use super::*;
     use crate::{
-        test_utils::{blocks::BlockchainTestData, create_test_provider_factory},
-        BlockWriter,
+        providers::{RocksDBBuilder, StaticFileProviderBuilder},
+        test_utils::{
+            blocks::BlockchainTestData, create_test_provider_factory, MockNodeTypesWithDB,
+        },
+        BlockWriter, ProviderFactory,
     };
     use alloy_consensus::Header;
     use alloy_primitives::{
@@ -4171,6 +4360,7 @@ mod tests {
         U256,
     };
     use reth_chain_state::ExecutedBlock;
+    use reth_chainspec::MAINNET;
     use reth_db_api::models::StorageSettings;
     use reth_ethereum_primitives::Receipt;
     use reth_execution_types::{AccountRevertInit, BlockExecutionOutput, BlockExecutionResult};
@@ -4182,7 +4372,7 @@ mod tests {
     };
     use revm_database::BundleState;
     use revm_state::AccountInfo;
-    use std::{sync::mpsc, time::Duration};
+    use std::{path::Path, sync::mpsc, time::Duration};
 
     #[test]
     fn test_receipts_by_block_range_empty_range() {
@@ -4409,6 +4599,91 @@ mod tests {
         }
     }
 
+    /// Creates a test provider factory that serves history from the era1 files in `era1_dir`.
+    fn create_test_provider_factory_with_era1(
+        era1_dir: &Path,
+    ) -> ProviderFactory<MockNodeTypesWithDB> {
+        let datadir = reth_db::test_utils::tempdir_path();
+        let static_files_path = datadir.join("static_files");
+        std::fs::create_dir_all(&static_files_path).unwrap();
+
+        ProviderFactory::new(
+            reth_db::test_utils::create_test_rw_db_with_datadir(&datadir),
+            MAINNET.clone(),
+            StaticFileProviderBuilder::read_write(static_files_path)
+                .with_era1_history(Era1History::new(era1_dir).unwrap())
+                .build()
+                .unwrap(),
+            RocksDBBuilder::new(datadir.join("rocksdb")).with_default_tables().build().unwrap(),
+            reth_tasks::Runtime::test(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_receipts_fall_back_to_era1_when_pruned() {
+        let mut rng = generators::rng();
+        let blocks = (0..=4)
+            .map(|number| {
+                random_block(
+                    &mut rng,
+                    number,
+                    BlockParams { tx_count: Some(2), ..Default::default() },
+                )
+            })
+            .collect::<Vec<_>>();
+
+        let era1_dir = tempfile::tempdir().unwrap();
+        let headers = blocks[..4].iter().map(|block| block.header().clone()).collect::<Vec<_>>();
+        crate::test_utils::write_era1_file_with_headers(era1_dir.path(), &headers, 2);
+
+        let factory = create_test_provider_factory_with_era1(era1_dir.path());
+        factory.set_storage_settings_cache(StorageSettings::v1());
+
+        let provider_rw = factory.provider_rw().unwrap();
+        for block in &blocks {
+            provider_rw.insert_block(&block.clone().try_recover().unwrap()).unwrap();
+        }
+        provider_rw.commit().unwrap();
+
+        // Receipts that haven't been pruned are not read from the era1 files
+        assert_eq!(factory.provider().unwrap().receipt(2).unwrap(), None);
+
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_prune_checkpoint(
+                PruneSegment::Receipts,
+                PruneCheckpoint {
+                    block_number: Some(3),
+                    tx_number: Some(7),
+                    prune_mode: PruneMode::Full,
+                },
+            )
+            .unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider = factory.provider().unwrap();
+
+        // Second transaction of block 1
+        assert_eq!(provider.receipt(3).unwrap().unwrap().cumulative_gas_used, 1001);
+
+        let hash = *blocks[2].body().transactions[0].tx_hash();
+        assert_eq!(provider.receipt_by_hash(hash).unwrap().unwrap().cumulative_gas_used, 2000);
+
+        let receipts = provider.receipts_by_block(3.into()).unwrap().unwrap();
+        assert_eq!(
+            receipts.iter().map(|receipt| receipt.cumulative_gas_used).collect::<Vec<_>>(),
+            [3000, 3001]
+        );
+
+        // Block 4 is above the pruned range and not in the era1 files
+        assert_eq!(provider.receipt(8).unwrap(), None);
+        assert_eq!(
+            provider.receipt_by_hash(*blocks[4].body().transactions[0].tx_hash()).unwrap(),
+            None
+        );
+    }
+
     #[test]
     fn test_receipts_by_block_range_consistency_with_individual_calls() {
         let factory = create_test_provider_factory();
@@ -5490,6 +5765,88 @@ mod tests {
         run_save_blocks_and_verify(StorageMode::V2);
     }
 
+    #[test]
+    fn test_save_blocks_indexes_balance_changes() {
+        let changed = Address::with_last_byte(1);
+        let unchanged = Address::with_last_byte(2);
+        let executed_block = |number: u64, parent_hash: B256, balance: u64| {
+            let account = |balance: u64| AccountInfo {
+                nonce: number,
+                balance: U256::from(balance),
+                ..Default::default()
+            };
+            let bundle = BundleState::builder(number..=number)
+                .state_original_account_info(changed, account(balance + 100))
+                .state_present_account_info(changed, account(balance))
+                .revert_account_info(number, changed, Some(Some(account(balance + 100))))
+                .state_original_account_info(unchanged, account(0))
+                .state_present_account_info(unchanged, AccountInfo { nonce: 7, ..account(0) })
+                .revert_account_info(number, unchanged, Some(Some(account(0))))
+                .build();
+            let hashed_state =
+                HashedPostState::from_bundle_state::<KeccakKeyHasher>(bundle.state()).into_sorted();
+            let block = SealedBlock::<reth_ethereum_primitives::Block>::seal_parts(
+                Header { number, parent_hash, difficulty: U256::from(1), ..Default::default() },
+                Default::default(),
+            );
+            ExecutedBlock::new(
+                Arc::new(block.try_recover().unwrap()),
+                Arc::new(BlockExecutionOutput {
+                    result: BlockExecutionResult {
+                        receipts: vec![],
+                        requests: Default::default(),
+                        gas_used: 0,
+                        blob_gas_used: 0,
+                    },
+                    state: bundle,
+                }),
+                ComputedTrieData { hashed_state: Arc::new(hashed_state), ..Default::default() },
+            )
+        };
+
+        // Without a checkpoint, the index isn't maintained.
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_blocks(vec![executed_block(0, B256::ZERO, 300)], SaveBlocksMode::Full)
+            .unwrap();
+        assert_eq!(provider_rw.balance_changes_index_tip().unwrap(), None);
+        assert!(provider_rw.account_balance_changes(changed, 0..=0, 10).unwrap().is_empty());
+
+        // Once the stage has a checkpoint, persisted blocks are indexed.
+        let factory = create_test_provider_factory();
+        let provider_rw = factory.provider_rw().unwrap();
+        provider_rw
+            .save_stage_checkpoint(StageId::IndexBalanceChanges, StageCheckpoint::new(0))
+            .unwrap();
+        let genesis = executed_block(0, B256::ZERO, 300);
+        let first = executed_block(1, genesis.recovered_block().hash(), 200);
+        let second = executed_block(2, first.recovered_block().hash(), 100);
+        provider_rw.save_blocks(vec![genesis], SaveBlocksMode::Full).unwrap();
+        provider_rw.save_blocks(vec![first, second], SaveBlocksMode::Full).unwrap();
+        provider_rw.commit().unwrap();
+
+        let provider_rw = factory.provider_rw().unwrap();
+        let change = |block_number: u64, before: u64, after: u64| BalanceChange {
+            block_number,
+            before: U256::from(before),
+            after: U256::from(after),
+        };
+        assert_eq!(
+            provider_rw.account_balance_changes(changed, 1..=2, 10).unwrap(),
+            vec![change(1, 300, 200), change(2, 200, 100)]
+        );
+        assert_eq!(provider_rw.account_balance_changes(changed, 2..=2, 10).unwrap().len(), 1);
+        assert!(provider_rw.account_balance_changes(unchanged, 0..=2, 10).unwrap().is_empty());
+
+        // Unwinding the last block removes its entry.
+        assert_eq!(provider_rw.unwind_balance_changes_index_range(2..).unwrap(), 2);
+        assert_eq!(
+            provider_rw.account_balance_changes(changed, 0..=2, 10).unwrap(),
+            vec![change(0, 400, 300), change(1, 300, 200)]
+        );
+    }
+
     #[test]
     fn test_write_and_remove_state_roundtrip_v2() {
         let factory = create_test_provider_factory();
@@ -0,0 +1,316 @@
+//! Read-through access to block history stored in era1 files.
+
+use alloy_consensus::{BlockHeader as _, ReceiptWithBloom};
+use alloy_primitives::{BlockNumber, B256};
+use parking_lot::Mutex;
+use reth_era::{
+    common::decode::DecodeCompressedRlp,
+    era1::{file::Era1BlockReader, types::execution::BlockTuple},
+};
+use reth_primitives_traits::{Block, BlockBody, BlockHeader, GotExpected, Receipt};
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs::File,
+    ops::RangeInclusive,
+    path::{Path, PathBuf},
+};
+use tracing::{debug, warn};
+
+/// The maximum number of idle era1 file readers kept open across all files.
+const MAX_IDLE_READERS: usize = 16;
+
+/// A directory of era1 files that serves headers, bodies and receipts of blocks which are no
+/// longer available in the database or static files.
+///
+/// The files are indexed by the block range of their
+/// [`BlockIndex`](reth_era::era1::types::group::BlockIndex) when the history is opened, and every
+/// block is read on demand with a single seek.
+///
+/// Every block is checked against the canonical hash of its number, so that files of another
+/// network or corrupted files are never served. Reads of mismatching blocks fail with
+/// [`ProviderError::Era1BlockMismatch`].
+#[derive(Debug)]
+pub struct Era1History {
+    /// Directory containing the era1 files.
+    dir: PathBuf,
+    /// Era1 files by their first block number, with their last block number.
+    files: BTreeMap<BlockNumber, (BlockNumber, PathBuf)>,
+    /// Idle readers of recently read files, so that concurrent lookups don't contend on a single
+    /// reader and consecutive lookups of the same file don't reopen it.
+    readers: Mutex<ReaderPool>,
+}
+
+impl Era1History {
+    /// Opens the era1 files in the given directory.
+    ///
+    /// Files that can't be read are skipped with a warning. Fails if the directory can't be read.
+    pub fn new(dir: impl AsRef<Path>) -> ProviderResult<Self> {
+        let dir = dir.as_ref().to_path_buf();
+        let mut files = BTreeMap::new();
+
+        for entry in reth_fs_util::read_dir(&dir).map_err(ProviderError::other)? {
+            let path = entry.map_err(ProviderError::other)?.path();
+            if path.extension().is_none_or(|extension| extension != "era1") {
+                continue
+            }
+
+            match Era1BlockReader::open(&path) {
+                Ok(reader) => {
+                    let range = reader.block_range();
+                    files.insert(*range.start(), (*range.end(), path));
+                }
+                Err(err) => {
+                    warn!(target: "providers::era1", ?path, %err, "Skipping unreadable era1 file")
+                }
+            }
+        }
+
+        debug!(target: "providers::era1", ?dir, files = files.len(), "Opened era1 history");
+        Ok(Self { dir, files, readers: Mutex::default() })
+    }
+
+    /// Returns the directory of the era1 files.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Returns the range of blocks from the first to the last era1 file, if there are any.
+    ///
+    /// Blocks in gaps between the files are not available.
+    pub fn block_range(&self) -> Option<RangeInclusive<BlockNumber>> {
+        let (first, _) = self.files.first_key_value()?;
+        let (_, (last, _)) = self.files.last_key_value()?;
+        Some(*first..=*last)
+    }
+
+    /// Returns `true` if the block is contained in one of the era1 files.
+    pub fn contains(&self, number: BlockNumber) -> bool {
+        self.file_for_block(number).is_some()
+    }
+
+    /// Returns the first block number and path of the file containing the given block.
+    fn file_for_block(&self, number: BlockNumber) -> Option<(BlockNumber, &Path)> {
+        let (start, (end, path)) = self.files.range(..=number).next_back()?;
+        (number <= *end).then_some((*start, path.as_path()))
+    }
+
+    /// Reads the compressed block with the given number.
+    fn block_tuple(&self, number: BlockNumber) -> ProviderResult<Option<BlockTuple>> {
+        let Some((start, path)) = self.file_for_block(number) else { return Ok(None) };
+
+        // Take a reader out of the pool, so the lock isn't held while reading
+        let pooled = self.readers.lock().take(start);
+        let mut reader = match pooled {
+            Some(reader) => reader,
+            None => Era1BlockReader::open(path).map_err(ProviderError::other)?,
+        };
+
+        let block = reader.read_block(number).map_err(ProviderError::other)?;
+        self.readers.lock().put(start, reader);
+        Ok(block)
+    }
+
+    /// Reads the block with the given number and checks that its header hashes to the canonical
+    /// `hash`.
+    fn verified_block_tuple<H: BlockHeader>(
+        &self,
+        number: BlockNumber,
+        hash: B256,
+    ) -> ProviderResult<Option<(H, BlockTuple)>> {
+        let Some(block) = self.block_tuple(number)? else { return Ok(None) };
+        let header: H = block.header.decode().map_err(ProviderError::other)?;
+        ensure_matches(number, "hash", header.hash_slow(), hash)?;
+        Ok(Some((header, block)))
+    }
+
+    /// Returns the header of the block with the given number and canonical hash.
+    pub fn header<H: BlockHeader>(
+        &self,
+        number: BlockNumber,
+        hash: B256,
+    ) -> ProviderResult<Option<H>> {
+        Ok(self.verified_block_tuple(number, hash)?.map(|(header, _)| header))
+    }
+
+    /// Returns the block with the given number and canonical hash.
+    ///
+    /// The transactions of the body are checked against the transactions root of the header.
+    pub fn block<B: Block>(&self, number: BlockNumber, hash: B256) -> ProviderResult<Option<B>> {
+        let Some((header, block)) = self.verified_block_tuple::<B::Header>(number, hash)? else {
+            return Ok(None)
+        };
+        let body: B::Body = block.body.decode().map_err(ProviderError::other)?;
+        ensure_matches(
+            number,
+            "transactions root",
+            body.calculate_tx_root(),
+            header.transactions_root(),
+        )?;
+        Ok(Some(B::new(header, body)))
+    }
+
+    /// Returns the receipts of the block with the given number and canonical hash.
+    pub fn receipts<H: BlockHeader, R: Receipt>(
+        &self,
+        number: BlockNumber,
+        hash: B256,
+    ) -> ProviderResult<Option<Vec<R>>> {
+        let Some((_, block)) = self.verified_block_tuple::<H>(number, hash)? else {
+            return Ok(None)
+        };
+        let receipts: Vec<ReceiptWithBloom<R>> =
+            block.receipts.decode().map_err(ProviderError::other)?;
+        Ok(Some(receipts.into_iter().map(|receipt| receipt.receipt).collect()))
+    }
+}
+
+/// Returns [`ProviderError::Era1BlockMismatch`] if the value of the field read from an era1 file
+/// isn't the canonical one.
+fn ensure_matches(
+    number: BlockNumber,
+    field: &'static str,
+    got: B256,
+    expected: B256,
+) -> ProviderResult<()> {
+    if got != expected {
+        return Err(ProviderError::Era1BlockMismatch {
+            number,
+            field,
+            mismatch: GotExpected { got, expected }.into(),
+        })
+    }
+    Ok(())
+}
+
+/// Idle era1 file readers by the first block number of their file.
+#[derive(Debug, Default)]
+struct ReaderPool {
+    /// Idle readers by file. Files without idle readers are removed.
+    idle: HashMap<BlockNumber, Vec<Era1BlockReader<File>>>,
+    /// The total number of idle readers.
+    len: usize,
+}
+
+impl ReaderPool {
+    /// Takes an idle reader of the file starting at the given block out of the pool.
+    fn take(&mut self, start: BlockNumber) -> Option<Era1BlockReader<File>> {
+        let readers = self.idle.get_mut(&start)?;
+        let reader = readers.pop()?;
+        if readers.is_empty() {
+            self.idle.remove(&start);
+        }
+        self.len -= 1;
+        Some(reader)
+    }
+
+    /// Returns a reader of the file starting at the given block to the pool.
+    ///
+    /// If the pool is full, a reader of another file is closed to make room, because the returned
+    /// reader was just used. If all idle readers belong to the same file, the returned reader is
+    /// closed instead.
+    fn put(&mut self, start: BlockNumber, reader: Era1BlockReader<File>) {
+        if self.len >= MAX_IDLE_READERS {
+            let Some(other) = self.idle.keys().copied().find(|other| *other != start) else {
+                return
+            };
+            self.take(other);
+        }
+
+        self.idle.entry(start).or_default().push(reader);
+        self.len += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{era1_test_header, write_era1_file};
+    use alloy_consensus::Header;
+    use assert_matches::assert_matches;
+    use reth_ethereum_primitives::{Block, Receipt};
+
+    #[test]
+    fn read_through_era1_files() {
+        let dir = tempfile::tempdir().unwrap();
+        write_era1_file(dir.path(), 0, 4, 1);
+        write_era1_file(dir.path(), 8, 4, 1);
+        std::fs::write(dir.path().join("README"), "not an era1 file").unwrap();
+
+        let history = Era1History::new(dir.path()).unwrap();
+        assert_eq!(history.block_range(), Some(0..=11));
+        assert!(history.contains(3));
+        assert!(!history.contains(5));
+        assert!(history.contains(9));
+        assert!(!history.contains(12));
+
+        // Alternate between the files
+        for number in [9, 2, 11, 0] {
+            let hash = era1_test_header(number).hash_slow();
+            let block = history.block::<Block>(number, hash).unwrap().unwrap();
+            assert_eq!(block.header.number, number);
+            assert!(block.body.transactions.is_empty());
+
+            let receipts = history.receipts::<Header, Receipt>(number, hash).unwrap().unwrap();
+            assert_eq!(receipts.len(), 1);
+            assert_eq!(receipts[0].cumulative_gas_used, number * 1000);
+        }
+
+        let hash = era1_test_header(10).hash_slow();
+        assert_eq!(history.header::<Header>(10, hash).unwrap().unwrap().number, 10);
+        assert!(history.block::<Block>(5, B256::ZERO).unwrap().is_none());
+        assert!(history.receipts::<Header, Receipt>(12, B256::ZERO).unwrap().is_none());
+    }
+
+    #[test]
+    fn rejects_non_canonical_era1_blocks() {
+        let dir = tempfile::tempdir().unwrap();
+        write_era1_file(dir.path(), 0, 4, 1);
+
+        // The canonical block 2 differs from the one in the era1 file
+        let history = Era1History::new(dir.path()).unwrap();
+        let canonical = Header { gas_used: 21_000, ..era1_test_header(2) }.hash_slow();
+        let got = era1_test_header(2).hash_slow();
+
+        for err in [
+            history.header::<Header>(2, canonical).unwrap_err(),
+            history.block::<Block>(2, canonical).unwrap_err(),
+            history.receipts::<Header, Receipt>(2, canonical).unwrap_err(),
+        ] {
+            assert_matches!(
+                err,
+                ProviderError::Era1BlockMismatch { number: 2, field: "hash", mismatch }
+                    if *mismatch == GotExpected { got, expected: canonical }
+            );
+        }
+    }
+
+    #[test]
+    fn concurrent_reads_share_bounded_reader_pool() {
+        let files = MAX_IDLE_READERS as u64 + 2;
+        let dir = tempfile::tempdir().unwrap();
+        for file in 0..files {
+            write_era1_file(dir.path(), file * 2, 2, 1);
+        }
+
+        let history = Era1History::new(dir.path()).unwrap();
+        std::thread::scope(|scope| {
+            for thread in 0..4 {
+                let history = &history;
+                scope.spawn(move || {
+                    for offset in 0..files * 2 {
+                        let number = (offset + thread * 7) % (files * 2);
+                        let hash = era1_test_header(number).hash_slow();
+                        let header = history.header::<Header>(number, hash).unwrap().unwrap();
+                        assert_eq!(header.number, number);
+                    }
+                });
+            }
+        });
+
+        let pool = history.readers.lock();
+        assert!(pool.len > 0);
+        assert!(pool.len <= MAX_IDLE_READERS);
+        assert_eq!(pool.len, pool.idle.values().map(Vec::len).sum::<usize>());
+    }
+}
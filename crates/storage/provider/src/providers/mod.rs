@@ -9,8 +9,10 @@ pub use database::*;
 
 mod static_file;
 pub use static_file::{
-    StaticFileAccess, StaticFileJarProvider, StaticFileProvider, StaticFileProviderBuilder,
-    StaticFileProviderRW, StaticFileProviderRWRefMut, StaticFileWriteCtx, StaticFileWriter,
+    RecompressedStaticFile, StaticFileAccess, StaticFileJarProvider, StaticFileProvider,
+    StaticFileProviderBuilder, StaticFileProviderRW, StaticFileProviderRWRefMut,
+    StaticFileWriteCtx, StaticFileWriter, RECOMPRESSION_MAX_DICTIONARY_SAMPLES,
+    RECOMPRESSION_MAX_DICTIONARY_SIZE,
 };
 
 mod state;
@@ -23,6 +25,9 @@ pub use state::{
     overlay::{OverlayBuilder, OverlayStateProvider, OverlayStateProviderFactory},
 };
 
+mod era1;
+pub use era1::Era1History;
+
 mod consistent_view;
 pub use consistent_view::{ConsistentDbView, ConsistentViewError};
 
@@ -32,6 +37,9 @@ pub use blockchain_provider::BlockchainProvider;
 mod consistent;
 pub use consistent::ConsistentProvider;
 
+mod replica;
+pub use replica::{ReplicaFollower, DEFAULT_REPLICA_MAX_BLOCKS_PER_POLL};
+
 pub(crate) mod rocksdb;
 
 pub use rocksdb::{
@@ -0,0 +1,308 @@
+use crate::{
+    providers::{BlockchainProvider, ProviderNodeTypes},
+    BlockHashReader, BlockNumReader, BlockReader, ChainStateBlockReader, DatabaseProviderFactory,
+    DatabaseProviderRO, HeaderProvider, StateReader,
+};
+use alloy_eips::BlockNumHash;
+use alloy_primitives::BlockNumber;
+use reth_chain_state::CanonStateNotification;
+use reth_execution_types::{Chain, ExecutionOutcome};
+use reth_node_types::{BlockTy, ReceiptTy};
+use reth_primitives_traits::RecoveredBlock;
+use reth_storage_errors::provider::{ProviderError, ProviderResult};
+use std::{collections::VecDeque, sync::Arc};
+use tracing::{debug, warn};
+
+/// The default maximum number of blocks that are committed by a single
+/// [`ReplicaFollower::poll`].
+pub const DEFAULT_REPLICA_MAX_BLOCKS_PER_POLL: u64 = 256;
+
+/// The number of recent canonical blocks the [`ReplicaFollower`] remembers to find the fork point
+/// if the primary unwinds persisted blocks.
+const RECENT_BLOCKS: usize = 256;
+
+/// Follows the canonical chain that another reth process persists to a shared datadir.
+///
+/// A replica opens the storage of a syncing primary read-only, see
+/// [`ProviderFactoryBuilder::open_read_only`](crate::providers::ProviderFactoryBuilder::open_read_only).
+/// Without an engine that advances the canonical head, the head of its [`BlockchainProvider`]
+/// stays at the block the provider was created with. The follower derives the head from what the
+/// primary persisted instead: [`ReplicaFollower::poll`] checks the best block of the database,
+/// loads the new blocks and their execution outcome, updates the canonical, safe and finalized
+/// headers and emits a [`CanonStateNotification`], so that caches and filters keep working.
+///
+/// If the primary unwound blocks the follower already committed, e.g. after a reorg below its
+/// persisted tip, the next notification is a [`CanonStateNotification::Reorg`] whose old chain
+/// contains the unwound blocks and their receipts. The state changes of the unwound blocks are no
+/// longer available, so its bundle state is empty.
+///
+/// The follower never writes to the database.
+#[derive(Debug)]
+pub struct ReplicaFollower<N: ProviderNodeTypes> {
+    /// The provider that is kept in sync with the primary.
+    provider: BlockchainProvider<N>,
+    /// The most recent committed canonical blocks, oldest first. The last one is the head.
+    recent: VecDeque<BlockNumHash>,
+    /// The most recent blocks and receipts this follower committed, oldest first.
+    committed: VecDeque<(RecoveredBlock<BlockTy<N>>, Vec<ReceiptTy<N>>)>,
+    /// Committed blocks that the primary unwound and that were not reported yet, newest first.
+    reverted: Vec<(RecoveredBlock<BlockTy<N>>, Vec<ReceiptTy<N>>)>,
+    /// The maximum number of blocks committed by a single poll.
+    max_blocks_per_poll: u64,
+}
+
+impl<N: ProviderNodeTypes> ReplicaFollower<N> {
+    /// Creates a new follower for the given provider, starting at its current canonical head.
+    pub fn new(provider: BlockchainProvider<N>) -> Self {
+        let head = provider.canonical_in_memory_state().get_canonical_head().num_hash();
+        Self {
+            provider,
+            recent: VecDeque::from([head]),
+            committed: VecDeque::new(),
+            reverted: Vec::new(),
+            max_blocks_per_poll: DEFAULT_REPLICA_MAX_BLOCKS_PER_POLL,
+        }
+    }
+
+    /// Sets the maximum number of blocks committed by a single poll.
+    ///
+    /// If the primary is further ahead, the remaining blocks are committed by the next polls.
+    pub const fn with_max_blocks_per_poll(mut self, max_blocks_per_poll: u64) -> Self {
+        self.max_blocks_per_poll = max_blocks_per_poll;
+        self
+    }
+
+    /// Returns the canonical head the follower has committed.
+    pub fn head(&self) -> BlockNumHash {
+        *self.recent.back().expect("contains at least the head")
+    }
+
+    /// Checks whether the primary persisted new blocks and commits them.
+    ///
+    /// Returns the notification that was sent, if new blocks were committed.
+    pub fn poll(&mut self) -> ProviderResult<Option<CanonStateNotification<N::Primitives>>> {
+        let provider = self.provider.database_provider_ro()?;
+        let best = provider.best_block_number()?;
+        let previous = self.head();
+
+        // The primary can unwind persisted blocks, e.g. when healing after an unclean shutdown,
+        // walk back to the last block that is still canonical.
+        while let Some(&block) = self.recent.back() {
+            if block.number <= best && provider.block_hash(block.number)? == Some(block.hash) {
+                break
+            }
+            self.recent.pop_back();
+            if self.committed.back().is_some_and(|(committed, _)| committed.num_hash() == block) {
+                self.reverted.extend(self.committed.pop_back());
+            }
+        }
+        let Some(&fork) = self.recent.back() else {
+            let hash =
+                provider.block_hash(best)?.ok_or(ProviderError::HeaderNotFound(best.into()))?;
+            warn!(target: "providers::replica", previous = previous.number, best, "Primary unwound past all known blocks");
+            self.recent.push_back(BlockNumHash::new(best, hash));
+            self.update_head(&provider, best)?;
+            return Ok(None)
+        };
+        if fork != previous {
+            warn!(target: "providers::replica", previous = previous.number, fork = fork.number, "Primary unwound persisted blocks");
+        }
+
+        if best == fork.number {
+            if fork != previous {
+                self.update_head(&provider, fork.number)?;
+            }
+            return Ok(None)
+        }
+
+        let end = best.min(fork.number.saturating_add(self.max_blocks_per_poll));
+        let range = fork.number + 1..=end;
+        debug!(target: "providers::replica", ?range, "Following primary");
+
+        let blocks = provider.recovered_block_range(range.clone())?;
+        let mut outcome: Option<ExecutionOutcome<_>> = None;
+        for number in range {
+            let state =
+                provider.get_state(number)?.ok_or(ProviderError::StateForNumberNotFound(number))?;
+            match &mut outcome {
+                Some(outcome) => outcome.extend(state),
+                None => outcome = Some(state),
+            }
+        }
+
+        let outcome = outcome.unwrap_or_default();
+        for (block, receipts) in blocks.iter().zip(&outcome.receipts) {
+            self.recent.push_back(block.num_hash());
+            self.committed.push_back((block.clone(), receipts.clone()));
+        }
+        while self.recent.len() > RECENT_BLOCKS {
+            self.recent.pop_front();
+        }
+        while self.committed.len() > RECENT_BLOCKS {
+            self.committed.pop_front();
+        }
+        self.update_head(&provider, end)?;
+
+        let new = Arc::new(Chain::new(blocks, outcome, Default::default()));
+        let notification = match self.take_reverted() {
+            Some(old) => CanonStateNotification::Reorg { old, new },
+            None => CanonStateNotification::Commit { new },
+        };
+        self.provider.canonical_in_memory_state().notify_canon_state(notification.clone());
+        Ok(Some(notification))
+    }
+
+    /// Returns the chain of committed blocks that the primary unwound since the last notification.
+    fn take_reverted(&mut self) -> Option<Arc<Chain<N::Primitives>>> {
+        let first_block = self.reverted.last()?.0.num_hash().number;
+        let (blocks, receipts): (Vec<_>, Vec<_>) = self.reverted.drain(..).rev().unzip();
+        let outcome = ExecutionOutcome { receipts, first_block, ..Default::default() };
+        Some(Arc::new(Chain::new(blocks, outcome, Default::default())))
+    }
+
+    /// Sets the canonical head and the safe and finalized headers the primary persisted.
+    fn update_head(
+        &self,
+        provider: &DatabaseProviderRO<N::DB, N>,
+        head: BlockNumber,
+    ) -> ProviderResult<()> {
+        let sealed_header = |number: BlockNumber| {
+            provider.sealed_header(number)?.ok_or(ProviderError::HeaderNotFound(number.into()))
+        };
+
+        let state = self.provider.canonical_in_memory_state();
+        state.set_canonical_head(sealed_header(head)?);
+        if let Some(finalized) = provider.last_finalized_block_number()? {
+            state.set_finalized(sealed_header(finalized.min(head))?);
+        }
+        if let Some(safe) = provider.last_safe_block_number()? {
+            state.set_safe(sealed_header(safe.min(head))?);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        test_utils::{create_test_provider_factory, MockNodeTypesWithDB},
+        BlockWriter, ProviderFactory, StateWriteConfig,
+    };
+    use alloy_primitives::B256;
+    use assert_matches::assert_matches;
+    use reth_ethereum_primitives::Block;
+    use reth_primitives_traits::SealedBlock;
+    use reth_stages_types::{StageCheckpoint, StageId};
+    use reth_storage_api::{DBProvider, StageCheckpointWriter, StateWriter};
+    use reth_testing_utils::generators::{self, random_block_range, BlockRangeParams};
+    use revm_database::OriginalValuesKnown;
+
+    /// Persists the blocks like the primary does, unwinding any blocks above the first one.
+    fn persist(factory: &ProviderFactory<MockNodeTypesWithDB>, blocks: &[SealedBlock<Block>]) {
+        let first = blocks[0].number;
+        let last = first + blocks.len() as u64 - 1;
+        let provider_rw = factory.database_provider_rw().unwrap();
+        if provider_rw.best_block_number().unwrap() >= first {
+            provider_rw.remove_block_and_execution_above(first - 1).unwrap();
+        }
+        for block in blocks {
+            provider_rw.insert_block(&block.clone().try_recover().unwrap()).unwrap();
+        }
+        provider_rw
+            .write_state(
+                &ExecutionOutcome {
+                    first_block: first,
+                    receipts: vec![vec![]; blocks.len()],
+                    ..Default::default()
+                },
+                OriginalValuesKnown::No,
+                StateWriteConfig::default(),
+            )
+            .unwrap();
+        provider_rw.save_stage_checkpoint(StageId::Finish, StageCheckpoint::new(last)).unwrap();
+        provider_rw.commit().unwrap();
+    }
+
+    #[test]
+    fn follows_persisted_blocks() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=4,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..2, ..Default::default() },
+        );
+        let factory = create_test_provider_factory();
+
+        persist(&factory, &blocks[0..2]);
+        let provider = BlockchainProvider::new(factory.clone()).unwrap();
+        let mut notifications = provider.canonical_in_memory_state().subscribe_canon_state();
+        let mut follower = ReplicaFollower::new(provider.clone()).with_max_blocks_per_poll(2);
+        assert_eq!(follower.head().number, 1);
+        assert!(follower.poll().unwrap().is_none());
+
+        persist(&factory, &blocks[2..5]);
+        let notification = follower.poll().unwrap().unwrap();
+        assert_eq!(notification.tip().number(), 3);
+        assert_eq!(notifications.try_recv().unwrap().tip().hash(), blocks[3].hash());
+        assert_eq!(provider.best_block_number().unwrap(), 3);
+
+        let notification = follower.poll().unwrap().unwrap();
+        assert_eq!(notification.committed().len(), 1);
+        assert_eq!(follower.head(), blocks[4].num_hash());
+        assert_eq!(provider.best_block_number().unwrap(), 4);
+        assert!(follower.poll().unwrap().is_none());
+    }
+
+    #[test]
+    fn reports_unwound_blocks_as_reorg() {
+        let mut rng = generators::rng();
+        let blocks = random_block_range(
+            &mut rng,
+            0..=4,
+            BlockRangeParams { parent: Some(B256::ZERO), tx_count: 0..2, ..Default::default() },
+        );
+        let fork = random_block_range(
+            &mut rng,
+            3..=5,
+            BlockRangeParams {
+                parent: Some(blocks[2].hash()),
+                tx_count: 0..2,
+                ..Default::default()
+            },
+        );
+        let factory = create_test_provider_factory();
+
+        persist(&factory, &blocks[0..2]);
+        let provider = BlockchainProvider::new(factory.clone()).unwrap();
+        let mut follower = ReplicaFollower::new(provider.clone());
+        persist(&factory, &blocks[2..5]);
+        assert_matches!(follower.poll().unwrap(), Some(CanonStateNotification::Commit { .. }));
+
+        // the primary unwinds blocks 3 and 4 and persists a longer fork on top of block 2
+        persist(&factory, &fork);
+        let notification = follower.poll().unwrap().unwrap();
+        let CanonStateNotification::Reorg { old, new } = &notification else {
+            panic!("expected reorg, got {notification:?}")
+        };
+        assert_eq!(
+            old.blocks_iter().map(|block| block.hash()).collect::<Vec<_>>(),
+            vec![blocks[3].hash(), blocks[4].hash()]
+        );
+        assert_eq!(old.blocks_and_receipts().count(), 2);
+        assert_eq!(new.tip().hash(), fork[2].hash());
+        assert_eq!(follower.head(), fork[2].num_hash());
+        assert_eq!(provider.best_block_number().unwrap(), 5);
+
+        // the reverted blocks are only reported once
+        persist(
+            &factory,
+            &random_block_range(
+                &mut rng,
+                6..=6,
+                BlockRangeParams { parent: Some(fork[2].hash()), ..Default::default() },
+            ),
+        );
+        assert_matches!(follower.poll().unwrap(), Some(CanonStateNotification::Commit { .. }));
+    }
+}
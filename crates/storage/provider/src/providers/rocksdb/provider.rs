@@ -145,6 +145,10 @@ const DEFAULT_COMPRESS_BUF_CAPACITY: usize = 4096;
 /// that occurs between auto-commits.
 const DEFAULT_AUTO_COMMIT_THRESHOLD: usize = 4 * 1024 * 1024 * 1024;
 
+/// Size of the batches written when copying a secondary instance in
+/// [`RocksDBProvider::checkpoint`] (64 MiB).
+const CHECKPOINT_COPY_BATCH_SIZE: usize = 64 * 1024 * 1024;
+
 /// Builder for [`RocksDBProvider`].
 pub struct RocksDBBuilder {
     path: PathBuf,
@@ -1084,6 +1088,52 @@ impl RocksDBProvider {
         Ok(())
     }
 
+    /// Creates a consistent copy of the database in the `dest` directory, which must not exist.
+    ///
+    /// For read-write providers this creates a `RocksDB` checkpoint, which hard-links the SST
+    /// files if `dest` is on the same filesystem. Secondary providers catch up with the primary
+    /// and then copy all entries of every table into a new database, since checkpoints require
+    /// the primary instance.
+    #[instrument(level = "debug", target = "providers::rocksdb", skip_all, fields(dest = ?dest.as_ref()))]
+    pub fn checkpoint(&self, dest: impl AsRef<Path>) -> ProviderResult<()> {
+        let to_provider_error = |e: rocksdb::Error| {
+            ProviderError::Database(DatabaseError::Other(format!(
+                "failed to checkpoint RocksDB: {e}"
+            )))
+        };
+
+        match self.0.as_ref() {
+            RocksDBProviderInner::ReadWrite { db, .. } => rocksdb::checkpoint::Checkpoint::new(db)
+                .and_then(|checkpoint| checkpoint.create_checkpoint(dest))
+                .map_err(to_provider_error),
+            RocksDBProviderInner::Secondary { db, .. } => {
+                self.try_catch_up_with_primary()?;
+
+                let target = RocksDBBuilder::new(dest).with_default_tables().build()?;
+                let target_db = target.0.db_rw();
+                for cf_name in ROCKSDB_TABLES {
+                    let (Some(source_cf), Some(target_cf)) =
+                        (db.cf_handle(cf_name), target_db.cf_handle(cf_name))
+                    else {
+                        continue
+                    };
+
+                    let mut batch = WriteBatchWithTransaction::<true>::default();
+                    for entry in db.iterator_cf(&source_cf, IteratorMode::Start) {
+                        let (key, value) = entry.map_err(to_provider_error)?;
+                        batch.put_cf(&target_cf, key, value);
+                        if batch.size_in_bytes() >= CHECKPOINT_COPY_BATCH_SIZE {
+                            target.commit_batch(std::mem::take(&mut batch))?;
+                        }
+                    }
+                    target.commit_batch(batch)?;
+                }
+
+                target.flush(ROCKSDB_TABLES)
+            }
+        }
+    }
+
     /// Creates a raw iterator over all entries in the specified table.
     ///
     /// Returns raw `(key_bytes, value_bytes)` pairs without decoding.
@@ -3164,6 +3214,36 @@ mod tests {
         assert_eq!(result, HistoryInfo::InChangeset(500));
     }
 
+    /// Verifies that both read-write and secondary providers create complete checkpoints.
+    #[test]
+    fn test_checkpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let rw_provider = RocksDBBuilder::new(temp_dir.path().join("primary"))
+            .with_default_tables()
+            .build()
+            .unwrap();
+        let ro_provider = RocksDBBuilder::new(temp_dir.path().join("primary"))
+            .with_default_tables()
+            .with_read_only(true)
+            .build()
+            .unwrap();
+
+        let tx_hash = TxHash::from(B256::from([1u8; 32]));
+        rw_provider.put::<tables::TransactionHashNumbers>(tx_hash, &100).unwrap();
+
+        for (provider, name) in [(&rw_provider, "rw"), (&ro_provider, "secondary")] {
+            let dest = temp_dir.path().join(name);
+            provider.checkpoint(&dest).unwrap();
+
+            let checkpoint = RocksDBBuilder::new(&dest).with_default_tables().build().unwrap();
+            assert_eq!(
+                checkpoint.get::<tables::TransactionHashNumbers>(tx_hash).unwrap(),
+                Some(100),
+                "{name}"
+            );
+        }
+    }
+
     #[test]
     fn test_account_history_info_ignores_blocks_above_visible_tip() {
         let temp_dir = TempDir::new().unwrap();
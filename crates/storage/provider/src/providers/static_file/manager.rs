@@ -4,6 +4,7 @@ use super::{
 };
 use crate::{
     changeset_walker::{StaticFileAccountChangesetWalker, StaticFileStorageChangesetWalker},
+    providers::Era1History,
     to_range, BlockHashReader, BlockNumReader, BlockReader, BlockSource, EitherWriter,
     EitherWriterDestination, HeaderProvider, ReceiptProvider, StageCheckpointReader, StatsReader,
     TransactionVariant, TransactionsProvider, TransactionsProviderExt,
@@ -14,7 +15,7 @@ use alloy_primitives::{
     b256, keccak256, Address, BlockHash, BlockNumber, TxHash, TxNumber, B256, U256,
 };
 
-use parking_lot::RwLock;
+use parking_lot::{Mutex, MutexGuard, RwLock};
 use reth_chain_state::ExecutedBlock;
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, NamedChain};
 use reth_db::{
@@ -33,7 +34,7 @@ use reth_db_api::{
     transaction::DbTx,
 };
 use reth_ethereum_primitives::{Receipt, TransactionSigned};
-use reth_nippy_jar::{NippyJar, NippyJarChecker};
+use reth_nippy_jar::{NippyJar, NippyJarChecker, CONFIG_FILE_EXTENSION};
 use reth_node_types::NodePrimitives;
 use reth_primitives_traits::{
     dashmap::DashMap, AlloyBlockHeader as _, BlockBody as _, RecoveredBlock, SealedHeader,
@@ -42,8 +43,8 @@ use reth_primitives_traits::{
 use reth_prune_types::PruneSegment;
 use reth_stages_types::PipelineTarget;
 use reth_static_file_types::{
-    find_fixed_range, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive, StaticFileMap,
-    StaticFileSegment, DEFAULT_BLOCKS_PER_STATIC_FILE,
+    find_fixed_range, Compression, HighestStaticFiles, SegmentHeader, SegmentRangeInclusive,
+    StaticFileMap, StaticFileSegment, DEFAULT_BLOCKS_PER_STATIC_FILE,
 };
 use reth_storage_api::{
     BlockBodyIndicesProvider, ChangeSetReader, DBProvider, PruneCheckpointReader,
@@ -56,6 +57,7 @@ use std::{
     ops::{Bound, Deref, Range, RangeBounds, RangeInclusive},
     path::{Path, PathBuf},
     sync::{atomic::AtomicU64, mpsc, Arc},
+    time::Duration,
 };
 use tracing::{debug, info, info_span, instrument, trace, warn};
 
@@ -63,6 +65,20 @@ use tracing::{debug, info, info_span, instrument, trace, warn};
 /// represent either a block or a transaction number end of a static file range.
 type SegmentRanges = BTreeMap<u64, SegmentRangeInclusive>;
 
+/// Directory inside the static files directory where recompressed static files are written before
+/// they replace the original ones.
+const RECOMPRESSION_DIR: &str = ".recompress";
+/// Extension of the marker file that is written once a recompressed static file is complete.
+const RECOMPRESSION_DONE_EXTENSION: &str = "done";
+/// Maximum size of a zstd dictionary trained for a column of a recompressed static file.
+pub const RECOMPRESSION_MAX_DICTIONARY_SIZE: usize = 128 * 1024;
+/// Maximum number of rows of a static file that are sampled to train the zstd dictionaries.
+pub const RECOMPRESSION_MAX_DICTIONARY_SAMPLES: usize = 100_000;
+/// Number of times a static file is loaded while it is replaced by its recompressed copy.
+const RECOMPRESSION_LOAD_ATTEMPTS: usize = 100;
+/// Time to wait before loading a static file again that is replaced by its recompressed copy.
+const RECOMPRESSION_LOAD_BACKOFF: Duration = Duration::from_millis(10);
+
 /// Access mode on a static file provider. RO/RW.
 #[derive(Debug, Default, PartialEq, Eq)]
 pub enum StaticFileAccess {
@@ -106,6 +122,26 @@ pub struct StaticFileWriteCtx {
     pub receipts_prunable: bool,
 }
 
+/// Outcome of recompressing a single static file, see [`StaticFileProvider::recompress_jar`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecompressedStaticFile {
+    /// Segment of the static file.
+    pub segment: StaticFileSegment,
+    /// Fixed block range of the static file.
+    pub block_range: SegmentRangeInclusive,
+    /// Size of the data, offsets and configuration files before recompression, in bytes.
+    pub size_before: u64,
+    /// Size of the data, offsets and configuration files after recompression, in bytes.
+    pub size_after: u64,
+}
+
+impl RecompressedStaticFile {
+    /// Returns the number of bytes saved by recompressing the static file.
+    pub const fn saved(&self) -> u64 {
+        self.size_before.saturating_sub(self.size_after)
+    }
+}
+
 /// [`StaticFileProvider`] manages all existing [`StaticFileJarProvider`].
 ///
 /// "Static files" contain immutable chain history data, such as:
@@ -131,6 +167,7 @@ pub struct StaticFileProviderBuilder<P> {
     blocks_per_file: StaticFileMap<u64>,
     path: P,
     genesis_block_number: u64,
+    era1_history: Option<Era1History>,
 }
 
 impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
@@ -142,6 +179,7 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            era1_history: None,
         }
     }
 
@@ -153,6 +191,7 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             blocks_per_file: Default::default(),
             use_metrics: false,
             genesis_block_number: 0,
+            era1_history: None,
         }
     }
 
@@ -218,6 +257,13 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
         self
     }
 
+    /// Sets the [`Era1History`] that serves blocks below the earliest history height of the
+    /// [`StaticFileProvider`].
+    pub fn with_era1_history(mut self, era1_history: Era1History) -> Self {
+        self.era1_history = Some(era1_history);
+        self
+    }
+
     /// Builds the final [`StaticFileProvider`] and initializes the index.
     pub fn build<N: NodePrimitives>(self) -> ProviderResult<StaticFileProvider<N>> {
         let mut provider = StaticFileProviderInner::new(self.path, self.access)?;
@@ -229,8 +275,12 @@ impl<P: AsRef<Path>> StaticFileProviderBuilder<P> {
             provider.blocks_per_file.insert(segment, blocks_per_file);
         }
         provider.genesis_block_number = self.genesis_block_number;
+        provider.era1_history = self.era1_history;
 
         let provider = StaticFileProvider(Arc::new(provider));
+        if provider.access.is_read_write() {
+            provider.finish_interrupted_recompressions()?;
+        }
         provider.initialize_index()?;
         Ok(provider)
     }
@@ -291,6 +341,9 @@ pub struct StaticFileProviderInner<N> {
     path: PathBuf,
     /// Maintains a writer set of [`StaticFileSegment`].
     writers: StaticFileWriters<N>,
+    /// Serializes deleting and recompressing the static files of a segment, so that a jar is not
+    /// replaced by its recompressed copy after it was deleted.
+    file_locks: StaticFileMap<Mutex<()>>,
     /// Metrics for the static files.
     metrics: Option<Arc<StaticFileProviderMetrics>>,
     /// Access rights of the provider.
@@ -301,6 +354,8 @@ pub struct StaticFileProviderInner<N> {
     _lock_file: Option<StorageLock>,
     /// Genesis block number, default is 0;
     genesis_block_number: u64,
+    /// Era1 files that serve the blocks which have been expired, if configured.
+    era1_history: Option<Era1History>,
 }
 
 impl<N: NodePrimitives> StaticFileProviderInner<N> {
@@ -313,14 +368,17 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
         };
 
         let mut blocks_per_file = StaticFileMap::default();
+        let mut file_locks = StaticFileMap::default();
         for segment in StaticFileSegment::iter() {
             blocks_per_file.insert(segment, DEFAULT_BLOCKS_PER_STATIC_FILE);
+            file_locks.insert(segment, Mutex::new(()));
         }
 
         let provider = Self {
             map: Default::default(),
             indexes: Default::default(),
             writers: Default::default(),
+            file_locks,
             earliest_history_height: Default::default(),
             path: path.as_ref().to_path_buf(),
             metrics: None,
@@ -328,6 +386,7 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
             blocks_per_file,
             _lock_file,
             genesis_block_number: 0,
+            era1_history: None,
         };
 
         Ok(provider)
@@ -337,6 +396,11 @@ impl<N: NodePrimitives> StaticFileProviderInner<N> {
         self.access.is_read_only()
     }
 
+    /// Locks the static files of `segment` against concurrent deletion and recompression.
+    fn lock_files(&self, segment: StaticFileSegment) -> MutexGuard<'_, ()> {
+        self.file_locks.get(segment).expect("initialized for all segments").lock()
+    }
+
     /// Each static file has a fixed number of blocks. This gives out the range where the requested
     /// block is positioned.
     ///
@@ -907,6 +971,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         segment: StaticFileSegment,
         block: BlockNumber,
     ) -> ProviderResult<SegmentHeader> {
+        let _lock = self.lock_files(segment);
+
         let fixed_block_range = self.find_fixed_range(segment, block);
         let key = (fixed_block_range.end(), segment);
         let file = self.path.join(segment.filename(&fixed_block_range));
@@ -970,6 +1036,168 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(deleted_headers)
     }
 
+    /// Recompresses all static files of `segment` whose block range ends at or below `up_to_block`
+    /// with the given compression, see [`Self::recompress_jar`].
+    ///
+    /// Returns the static files that were recompressed.
+    pub fn recompress_segment(
+        &self,
+        segment: StaticFileSegment,
+        compression: Compression,
+        up_to_block: BlockNumber,
+    ) -> ProviderResult<Vec<RecompressedStaticFile>> {
+        let ranges = self
+            .indexes
+            .read()
+            .get(segment)
+            .map(|index| index.expected_block_ranges_by_max_block.clone())
+            .unwrap_or_default();
+
+        let mut recompressed = Vec::new();
+        for range in ranges.into_values().filter(|range| range.end() <= up_to_block) {
+            if let Some(file) = self.recompress_jar(segment, range.start(), compression)? {
+                recompressed.push(file);
+            }
+        }
+        Ok(recompressed)
+    }
+
+    /// Rewrites the static file of `segment` that contains `block` with the given compression, and
+    /// replaces the original one with it.
+    ///
+    /// Only static files that are followed by a newer static file of the same segment are
+    /// recompressed, since the highest one is still appended to. The recompressed jar is written
+    /// next to the static files first and moved into place once complete, which is rolled forward
+    /// on startup if the node stops in between. Readers are not blocked: until the cached jar is
+    /// swapped, they keep reading the memory mapped original files, and readers that load the
+    /// static file while its files are replaced load it again once they are.
+    ///
+    /// The swap holds the same lock as [`Self::delete_jar`], and is skipped if the static file was
+    /// deleted, e.g. by the pruner, while it was recompressed.
+    ///
+    /// Returns `None` if the static file is the highest one of the segment, is empty or is already
+    /// compressed, if it was deleted in the meantime, or if recompressing it does not reduce its
+    /// size.
+    pub fn recompress_jar(
+        &self,
+        segment: StaticFileSegment,
+        block: BlockNumber,
+        compression: Compression,
+    ) -> ProviderResult<Option<RecompressedStaticFile>> {
+        if self.access.is_read_only() {
+            return Err(ProviderError::ReadOnlyStaticFileAccess)
+        }
+
+        let fixed_block_range = self.find_fixed_range(segment, block);
+        if self
+            .get_highest_static_file_block(segment)
+            .is_none_or(|highest| highest <= fixed_block_range.end())
+        {
+            return Ok(None)
+        }
+
+        // Makes sure readers use the cached jar, and its mmap handles, while the files on disk are
+        // replaced.
+        drop(self.get_or_create_jar_provider(segment, &fixed_block_range)?);
+
+        let file_name = segment.filename(&fixed_block_range);
+        let path = self.path.join(&file_name);
+        let jar = NippyJar::<SegmentHeader>::load(&path).map_err(ProviderError::other)?;
+        if jar.compressor().is_some() || jar.rows() == 0 {
+            return Ok(None)
+        }
+
+        let recompression_dir = self.path.join(RECOMPRESSION_DIR);
+        reth_fs_util::create_dir_all(&recompression_dir).map_err(ProviderError::other)?;
+        let recompressed_path = recompression_dir.join(&file_name);
+        for file in static_file_paths(&recompressed_path) {
+            reth_fs_util::remove_file_if_exists(file).map_err(ProviderError::other)?;
+        }
+
+        let recompressed =
+            NippyJar::new(jar.columns(), &recompressed_path, jar.user_header().clone());
+        let recompressed = match compression {
+            Compression::Lz4 => recompressed.with_lz4(),
+            Compression::Zstd => recompressed.with_zstd(false, 0),
+            Compression::ZstdWithDictionary => {
+                recompressed.with_zstd(true, RECOMPRESSION_MAX_DICTIONARY_SIZE)
+            }
+            Compression::Uncompressed => return Ok(None),
+        };
+
+        debug!(target: "providers::static_file", ?segment, ?fixed_block_range, ?compression, "Recompressing static file");
+        jar.copy_rows_to(recompressed, RECOMPRESSION_MAX_DICTIONARY_SAMPLES)
+            .map_err(ProviderError::other)?;
+        drop(jar);
+
+        let _lock = self.lock_files(segment);
+        let remove_recompressed = || {
+            for file in static_file_paths(&recompressed_path) {
+                reth_fs_util::remove_file_if_exists(file).map_err(ProviderError::other)?;
+            }
+            Ok::<_, ProviderError>(())
+        };
+
+        if !path.exists() {
+            debug!(target: "providers::static_file", ?segment, ?fixed_block_range, "Static file was deleted while it was recompressed");
+            remove_recompressed()?;
+            return Ok(None)
+        }
+
+        let size_before = static_file_size(&path)?;
+        let size_after = static_file_size(&recompressed_path)?;
+        if size_after >= size_before {
+            debug!(target: "providers::static_file", ?segment, ?fixed_block_range, size_before, size_after, "Recompression does not reduce static file size");
+            remove_recompressed()?;
+            return Ok(None)
+        }
+
+        let marker = recompressed_path.with_extension(RECOMPRESSION_DONE_EXTENSION);
+        reth_fs_util::atomic_write_file(&marker, |_| Ok::<_, std::io::Error>(()))
+            .map_err(ProviderError::other)?;
+        move_static_file(&recompressed_path, &path)?;
+        reth_fs_util::remove_file(&marker).map_err(ProviderError::other)?;
+
+        // Swaps the cached jar. Readers that still hold the previous one keep reading the replaced
+        // files until they're done.
+        let jar = NippyJar::load(&path).map_err(ProviderError::other)?;
+        self.map.insert((fixed_block_range.end(), segment), LoadedJar::new(jar)?);
+
+        info!(target: "providers::static_file", ?segment, ?fixed_block_range, size_before, size_after, "Recompressed static file");
+
+        Ok(Some(RecompressedStaticFile {
+            segment,
+            block_range: fixed_block_range,
+            size_before,
+            size_after,
+        }))
+    }
+
+    /// Moves the recompressed static files that were complete when the node stopped into place,
+    /// and removes any incomplete ones.
+    fn finish_interrupted_recompressions(&self) -> ProviderResult<()> {
+        let recompression_dir = self.path.join(RECOMPRESSION_DIR);
+        if !recompression_dir.exists() {
+            return Ok(())
+        }
+
+        for entry in reth_fs_util::read_dir(&recompression_dir)
+            .map_err(ProviderError::other)?
+            .filter_map(Result::ok)
+        {
+            let marker = entry.path();
+            if marker.extension().is_some_and(|extension| extension == RECOMPRESSION_DONE_EXTENSION) &&
+                let Some(file_name) = marker.file_stem()
+            {
+                warn!(target: "providers::static_file", ?file_name, "Finishing interrupted static file recompression");
+                move_static_file(&recompression_dir.join(file_name), &self.path.join(file_name))?;
+                reth_fs_util::remove_file(&marker).map_err(ProviderError::other)?;
+            }
+        }
+
+        reth_fs_util::remove_dir_all(&recompression_dir).map_err(ProviderError::other)
+    }
+
     /// Given a segment and block range it returns a cached
     /// [`StaticFileJarProvider`]. TODO(joshie): we should check the size and pop N if there's too
     /// many.
@@ -987,9 +1215,8 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
             jar.into()
         } else {
             trace!(target: "providers::static_file", ?segment, ?fixed_block_range, "Creating jar from scratch");
-            let path = self.path.join(segment.filename(fixed_block_range));
-            let jar = NippyJar::load(&path).map_err(ProviderError::other)?;
-            self.map.entry(key).insert(LoadedJar::new(jar)?).downgrade().into()
+            let jar = self.load_jar(&segment.filename(fixed_block_range))?;
+            self.map.entry(key).insert(jar).downgrade().into()
         };
 
         if let Some(metrics) = &self.metrics {
@@ -998,6 +1225,45 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         Ok(provider)
     }
 
+    /// Loads the static file with the given file name.
+    ///
+    /// [`Self::recompress_jar`] replaces the files of a static file one at a time, so a reader,
+    /// possibly in another process, could otherwise load a mix of the original and the recompressed
+    /// files. The recompression marker exists for as long as the files are replaced, and the
+    /// configuration file changes once they are, so the static file is loaded again if either is
+    /// observed while loading it.
+    fn load_jar(&self, file_name: &str) -> ProviderResult<LoadedJar> {
+        let path = self.path.join(file_name);
+        let marker = self
+            .path
+            .join(RECOMPRESSION_DIR)
+            .join(file_name)
+            .with_extension(RECOMPRESSION_DONE_EXTENSION);
+        let config_version = || {
+            let metadata = reth_fs_util::metadata(path.with_extension(CONFIG_FILE_EXTENSION))
+                .map_err(ProviderError::other)?;
+            Ok::<_, ProviderError>((metadata.len(), metadata.modified().ok()))
+        };
+
+        for _ in 0..RECOMPRESSION_LOAD_ATTEMPTS {
+            if !marker.exists() {
+                let version = config_version()?;
+                let jar =
+                    NippyJar::load(&path).map_err(ProviderError::other).and_then(LoadedJar::new);
+                if !marker.exists() && config_version()? == version {
+                    return jar
+                }
+            }
+            trace!(target: "providers::static_file", ?path, "Static file is being replaced by its recompressed copy");
+            std::thread::sleep(RECOMPRESSION_LOAD_BACKOFF);
+        }
+
+        Err(ProviderError::other(std::io::Error::other(format!(
+            "static file {} is still being replaced by its recompressed copy",
+            path.display()
+        ))))
+    }
+
     /// Gets a static file segment's block range from the provider inner block
     /// index.
     fn get_segment_ranges_from_block(
@@ -1888,6 +2154,12 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
         self.earliest_history_height.load(std::sync::atomic::Ordering::Relaxed)
     }
 
+    /// Returns the [`Era1History`] that serves blocks below the
+    /// [earliest history height](Self::earliest_history_height), if configured.
+    pub const fn era1_history(&self) -> Option<&Era1History> {
+        self.era1_history.as_ref()
+    }
+
     /// Gets the lowest static file's block range if it exists for a static file segment.
     ///
     /// If there is nothing on disk for the given segment, this will return [`None`].
@@ -2217,6 +2489,34 @@ impl<N: NodePrimitives> StaticFileProvider<N> {
     }
 }
 
+/// Returns the data, offsets and configuration files of the static file at `path`.
+fn static_file_paths(path: &Path) -> [PathBuf; 3] {
+    [path.to_path_buf(), path.with_extension("off"), path.with_extension(CONFIG_FILE_EXTENSION)]
+}
+
+/// Returns the total size of the data, offsets and configuration files of the static file at
+/// `path`.
+fn static_file_size(path: &Path) -> ProviderResult<u64> {
+    static_file_paths(path).into_iter().try_fold(0, |size, file| {
+        Ok(size + reth_fs_util::metadata(file).map_err(ProviderError::other)?.len())
+    })
+}
+
+/// Moves the files of the static file at `from` that are still there to `to`.
+///
+/// The configuration file is moved last, so a static file is only ever loaded with the
+/// configuration of the data it was written with once the move is complete. Callers keep the
+/// recompression marker of the static file for the duration of the move, which
+/// [`StaticFileProvider::load_jar`] waits for.
+fn move_static_file(from: &Path, to: &Path) -> ProviderResult<()> {
+    for (from, to) in static_file_paths(from).into_iter().zip(static_file_paths(to)) {
+        if from.exists() {
+            reth_fs_util::rename(from, to).map_err(ProviderError::other)?;
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 struct StaticFileSegmentIndex {
     /// Min static file block range.
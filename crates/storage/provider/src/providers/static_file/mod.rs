@@ -1,7 +1,8 @@
 mod manager;
 pub use manager::{
-    StaticFileAccess, StaticFileProvider, StaticFileProviderBuilder, StaticFileWriteCtx,
-    StaticFileWriter,
+    RecompressedStaticFile, StaticFileAccess, StaticFileProvider, StaticFileProviderBuilder,
+    StaticFileWriteCtx, StaticFileWriter, RECOMPRESSION_MAX_DICTIONARY_SAMPLES,
+    RECOMPRESSION_MAX_DICTIONARY_SIZE,
 };
 
 mod jar;
@@ -91,7 +92,7 @@ mod tests {
         test_utils::create_test_provider_factory, HeaderProvider, StaticFileProviderFactory,
     };
     use alloy_consensus::{Header, SignableTransaction, Transaction, TxLegacy};
-    use alloy_primitives::{Address, BlockHash, Signature, TxNumber, B256, U160, U256};
+    use alloy_primitives::{Address, BlockHash, Bytes, Signature, TxNumber, B256, U160, U256};
     use rand::seq::SliceRandom;
     use reth_db::{
         models::{AccountBeforeTx, StorageBeforeTx},
@@ -101,7 +102,7 @@ mod tests {
     use reth_ethereum_primitives::{EthPrimitives, Receipt, TransactionSigned};
     use reth_primitives_traits::Account;
     use reth_static_file_types::{
-        find_fixed_range, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE,
+        find_fixed_range, Compression, SegmentRangeInclusive, DEFAULT_BLOCKS_PER_STATIC_FILE,
     };
     use reth_storage_api::{
         ChangeSetReader, ReceiptProvider, StorageChangeSetReader, TransactionsProvider,
@@ -184,6 +185,57 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_recompress_jar() {
+        let (static_dir, _) = create_test_static_files_dir();
+        let blocks_per_file = 10;
+        let tip = 24;
+        let build = || -> StaticFileProvider<EthPrimitives> {
+            StaticFileProviderBuilder::read_write(&static_dir)
+                .with_blocks_per_file(blocks_per_file)
+                .build()
+                .expect("Failed to build static file provider")
+        };
+        let assert_headers = |sf_rw: &StaticFileProvider<EthPrimitives>| {
+            for num in 0..=tip {
+                assert_eq!(sf_rw.header_by_number(num).unwrap().map(|h| h.number), Some(num));
+            }
+        };
+
+        let sf_rw = build();
+        {
+            let mut header_writer = sf_rw.latest_writer(StaticFileSegment::Headers).unwrap();
+            let mut header =
+                Header { extra_data: Bytes::from(vec![0; 1024]), ..Default::default() };
+            for num in 0..=tip {
+                header.number = num;
+                header_writer.append_header(&header, &BlockHash::default()).unwrap();
+            }
+            header_writer.commit().unwrap();
+        }
+
+        let recompressed = sf_rw
+            .recompress_segment(StaticFileSegment::Headers, Compression::Zstd, u64::MAX)
+            .unwrap();
+
+        // The highest static file is still appended to, so it's left as is.
+        assert_eq!(
+            recompressed.iter().map(|file| file.block_range).collect::<Vec<_>>(),
+            vec![SegmentRangeInclusive::new(0, 9), SegmentRangeInclusive::new(10, 19)]
+        );
+        assert!(recompressed.iter().all(|file| file.saved() > 0));
+        assert_eq!(
+            sf_rw.recompress_jar(StaticFileSegment::Headers, 0, Compression::Zstd).unwrap(),
+            None
+        );
+        assert_eq!(fs::read_dir(static_dir.path().join(".recompress")).unwrap().count(), 0);
+        assert_headers(&sf_rw);
+
+        // Recompressed static files are loaded from disk after a restart.
+        drop(sf_rw);
+        assert_headers(&build());
+    }
+
     #[test]
     fn test_header_truncation() {
         let (static_dir, _) = create_test_static_files_dir();
@@ -0,0 +1,78 @@
+//! Helpers to write era1 files for testing.
+
+use alloy_consensus::{BlockBody, Header, TxReceipt};
+use alloy_primitives::{BlockNumber, B256, U256};
+use reth_era::{
+    common::file_ops::{EraFileFormat, FileWriter},
+    e2s::types::IndexEntry,
+    era1::{
+        file::{Era1File, Era1Writer},
+        types::{
+            execution::{
+                Accumulator, CompressedBody, CompressedHeader, CompressedReceipts, TotalDifficulty,
+            },
+            group::{BlockIndex, Era1Group, Era1Id},
+        },
+    },
+};
+use reth_ethereum_primitives::{Receipt, TransactionSigned, TxType};
+use std::path::Path;
+
+/// Returns the header of the empty block with the given number written by [`write_era1_file`].
+pub fn era1_test_header(number: BlockNumber) -> Header {
+    Header { number, gas_limit: 30_000_000, ..Default::default() }
+}
+
+/// Writes an era1 file with the empty blocks of [`era1_test_header`], each with the given number of
+/// receipts.
+///
+/// The cumulative gas used of each receipt is `number * 1000 + index`.
+pub fn write_era1_file(dir: &Path, start: BlockNumber, count: u64, receipts: u64) {
+    let headers = (start..start + count).map(era1_test_header).collect::<Vec<_>>();
+    write_era1_file_with_headers(dir, &headers, receipts)
+}
+
+/// Writes an era1 file with the given consecutive headers and empty bodies, each with the given
+/// number of receipts.
+///
+/// The cumulative gas used of each receipt is `number * 1000 + index`.
+pub fn write_era1_file_with_headers(dir: &Path, headers: &[Header], receipts: u64) {
+    let start = headers[0].number;
+    let count = headers.len() as u64;
+    let mut blocks = Vec::new();
+    let mut positions = Vec::new();
+    let mut position = 8;
+    for header in headers {
+        let number = header.number;
+        let receipts = (0..receipts)
+            .map(|index| {
+                Receipt {
+                    tx_type: TxType::Legacy,
+                    success: true,
+                    cumulative_gas_used: number * 1000 + index,
+                    logs: vec![],
+                }
+                .with_bloom()
+            })
+            .collect::<Vec<_>>();
+        let block = BlockTuple::new(
+            CompressedHeader::from_header(header).unwrap(),
+            CompressedBody::from_body(&BlockBody::<TransactionSigned>::default()).unwrap(),
+            CompressedReceipts::from_encodable_list(&receipts).unwrap(),
+            TotalDifficulty::new(U256::ZERO),
+        );
+
+        positions.push(position);
+        for len in [block.header.data.len(), block.body.data.len(), block.receipts.data.len(), 32] {
+            position += 8 + len as i64;
+        }
+        blocks.push(block);
+    }
+
+    let block_index_position = position + 8 + 32;
+    let offsets = positions.iter().map(|position| position - block_index_position).collect();
+    let group =
+        Era1Group::new(blocks, Accumulator::new(B256::ZERO), BlockIndex::new(start, offsets));
+    let file = Era1File::new(group, Era1Id::new("testnet", start, count as u32));
+    Era1Writer::create(dir.join(format!("testnet-{start}.era1")), &file).unwrap();
+}
@@ -22,7 +22,7 @@ use reth_chainspec::{ChainInfo, EthChainSpec};
 use reth_db::transaction::DbTx;
 use reth_db_api::{
     mock::{DatabaseMock, TxMock},
-    models::{AccountBeforeTx, StorageSettings, StoredBlockBodyIndices},
+    models::{AccountBeforeTx, BalanceChange, StorageSettings, StoredBlockBodyIndices},
 };
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::ExecutionOutcome;
@@ -33,9 +33,10 @@ use reth_primitives_traits::{
 use reth_prune_types::{PruneCheckpoint, PruneModes, PruneSegment};
 use reth_stages_types::{StageCheckpoint, StageId};
 use reth_storage_api::{
-    BlockBodyIndicesProvider, BytecodeReader, DBProvider, DatabaseProviderFactory,
-    HashedPostStateProvider, NodePrimitivesProvider, StageCheckpointReader, StateProofProvider,
-    StorageChangeSetReader, StorageRootProvider, StorageSettingsCache,
+    BalanceChangeReader, BlockBodyIndicesProvider, BytecodeReader, DBProvider,
+    DatabaseProviderFactory, HashedPostStateProvider, NodePrimitivesProvider,
+    StageCheckpointReader, StateProofProvider, StorageChangeSetReader, StorageRootProvider,
+    StorageSettingsCache,
 };
 use reth_storage_errors::provider::{ConsistentViewError, ProviderError, ProviderResult};
 use reth_trie::{
@@ -1027,6 +1028,23 @@ impl<T: NodePrimitives, ChainSpec: Send + Sync> ChangeSetReader for MockEthProvi
     }
 }
 
+impl<T: NodePrimitives, ChainSpec: Send + Sync> BalanceChangeReader
+    for MockEthProvider<T, ChainSpec>
+{
+    fn balance_changes_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn account_balance_changes(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BalanceChange>> {
+        Ok(Vec::default())
+    }
+}
+
 impl<T: NodePrimitives, ChainSpec: Send + Sync> StorageChangeSetReader
     for MockEthProvider<T, ChainSpec>
 {
@@ -20,9 +20,11 @@ type DbStateRoot<'a, TX, A> = StateRoot<
 >;
 
 pub mod blocks;
+mod era1;
 mod mock;
 mod noop;
 
+pub use era1::{era1_test_header, write_era1_file, write_era1_file_with_headers};
 pub use mock::{ExtendedAccount, MockEthProvider};
 pub use noop::NoopProvider;
 pub use reth_chain_state::test_utils::TestCanonStateSubscriptions;
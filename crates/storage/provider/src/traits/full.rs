@@ -1,10 +1,10 @@
 //! Helper provider traits to encapsulate all provider traits for simplicity.
 
 use crate::{
-    AccountReader, BalProvider, BlockReader, BlockReaderIdExt, ChainSpecProvider, ChangeSetReader,
-    DatabaseProviderFactory, HashedPostStateProvider, PruneCheckpointReader,
-    RocksDBProviderFactory, StageCheckpointReader, StateProviderFactory, StateReader,
-    StaticFileProviderFactory,
+    AccountReader, BalProvider, BalanceChangeReader, BlockReader, BlockReaderIdExt,
+    ChainSpecProvider, ChangeSetReader, DatabaseProviderFactory, HashedPostStateProvider,
+    PruneCheckpointReader, RocksDBProviderFactory, StageCheckpointReader, StateProviderFactory,
+    StateReader, StaticFileProviderFactory,
 };
 use reth_chain_state::{
     CanonStateSubscriptions, ForkChoiceSubscriptions, PersistedBlockSubscriptions,
@@ -34,11 +34,12 @@ pub trait FullProvider<N: NodeTypesWithDB>:
     > + AccountReader
     + BalProvider
     + StateProviderFactory
-    + StateReader
+    + StateReader<Receipt = ReceiptTy<N>>
     + HashedPostStateProvider
     + ChainSpecProvider<ChainSpec = N::ChainSpec>
     + ChangeSetReader
     + StorageChangeSetReader
+    + BalanceChangeReader
     + CanonStateSubscriptions
     + ForkChoiceSubscriptions<Header = HeaderTy<N>>
     + PersistedBlockSubscriptions
@@ -70,11 +71,12 @@ impl<T, N: NodeTypesWithDB> FullProvider<N> for T where
         > + AccountReader
         + BalProvider
         + StateProviderFactory
-        + StateReader
+        + StateReader<Receipt = ReceiptTy<N>>
         + HashedPostStateProvider
         + ChainSpecProvider<ChainSpec = N::ChainSpec>
         + ChangeSetReader
         + StorageChangeSetReader
+        + BalanceChangeReader
         + CanonStateSubscriptions
         + ForkChoiceSubscriptions<Header = HeaderTy<N>>
         + PersistedBlockSubscriptions
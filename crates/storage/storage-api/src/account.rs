@@ -5,7 +5,7 @@ use alloc::{
 use alloy_primitives::{Address, BlockNumber};
 use auto_impl::auto_impl;
 use core::ops::{RangeBounds, RangeInclusive};
-use reth_db_models::AccountBeforeTx;
+use reth_db_models::{AccountBeforeTx, BalanceChange};
 use reth_primitives_traits::Account;
 use reth_storage_errors::provider::ProviderResult;
 
@@ -79,3 +79,21 @@ pub trait ChangeSetReader {
         range: impl RangeBounds<BlockNumber>,
     ) -> ProviderResult<Vec<(BlockNumber, AccountBeforeTx)>>;
 }
+
+/// Reader for the index of account balance changes.
+#[auto_impl(&, Arc, Box)]
+pub trait BalanceChangeReader {
+    /// Returns the highest block covered by the balance changes index.
+    ///
+    /// Returns `None` if the index is not maintained.
+    fn balance_changes_index_tip(&self) -> ProviderResult<Option<BlockNumber>>;
+
+    /// Returns up to `limit` balance changes of the account in the given block range, sorted by
+    /// block number.
+    fn account_balance_changes(
+        &self,
+        address: Address,
+        range: RangeInclusive<BlockNumber>,
+        limit: usize,
+    ) -> ProviderResult<Vec<BalanceChange>>;
+}
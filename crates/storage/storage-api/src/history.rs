@@ -53,6 +53,22 @@ pub trait HistoryWriter: Send {
         storage_transitions: impl IntoIterator<Item = ((Address, B256), impl IntoIterator<Item = u64>)>,
     ) -> ProviderResult<()>;
 
+    /// Unwind and clear the account balance changes index.
+    ///
+    /// Returns number of changesets walked.
+    fn unwind_balance_changes_index<'a>(
+        &self,
+        changesets: impl Iterator<Item = &'a (BlockNumber, AccountBeforeTx)>,
+    ) -> ProviderResult<usize>;
+
+    /// Unwind and clear the account balance changes index in a given block range.
+    ///
+    /// Returns number of changesets walked.
+    fn unwind_balance_changes_index_range(
+        &self,
+        range: impl RangeBounds<BlockNumber>,
+    ) -> ProviderResult<usize>;
+
     /// Read account/storage changesets and update account/storage history indices.
     fn update_history_indices(&self, range: RangeInclusive<BlockNumber>) -> ProviderResult<()>;
 }
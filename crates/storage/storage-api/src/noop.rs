@@ -3,12 +3,13 @@
 pub use crate::bal::NoopBalStore;
 
 use crate::{
-    AccountReader, BalProvider, BalStoreHandle, BlockBodyIndicesProvider, BlockHashReader,
-    BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource, BytecodeReader,
-    ChangeSetReader, HashedPostStateProvider, HeaderProvider, NodePrimitivesProvider,
-    PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt, StageCheckpointReader,
-    StateProofProvider, StateProvider, StateProviderBox, StateProviderFactory, StateReader,
-    StateRootProvider, StorageRootProvider, TransactionVariant, TransactionsProvider,
+    AccountReader, BalProvider, BalStoreHandle, BalanceChangeReader, BlockBodyIndicesProvider,
+    BlockHashReader, BlockIdReader, BlockNumReader, BlockReader, BlockReaderIdExt, BlockSource,
+    BytecodeReader, ChangeSetReader, HashedPostStateProvider, HeaderProvider,
+    NodePrimitivesProvider, PruneCheckpointReader, ReceiptProvider, ReceiptProviderIdExt,
+    StageCheckpointReader, StateProofProvider, StateProvider, StateProviderBox,
+    StateProviderFactory, StateReader, StateRootProvider, StorageRootProvider, TransactionVariant,
+    TransactionsProvider,
 };
 
 #[cfg(feature = "db-api")]
@@ -27,7 +28,7 @@ use core::{
 use reth_chainspec::{ChainInfo, ChainSpecProvider, EthChainSpec, MAINNET};
 #[cfg(feature = "db-api")]
 use reth_db_api::mock::{DatabaseMock, TxMock};
-use reth_db_models::{AccountBeforeTx, StoredBlockBodyIndices};
+use reth_db_models::{AccountBeforeTx, BalanceChange, StoredBlockBodyIndices};
 use reth_ethereum_primitives::EthPrimitives;
 use reth_execution_types::ExecutionOutcome;
 use reth_primitives_traits::{Account, Bytecode, NodePrimitives, RecoveredBlock, SealedHeader};
@@ -416,6 +417,21 @@ impl<C: Send + Sync, N: NodePrimitives> ChangeSetReader for NoopProvider<C, N> {
     }
 }
 
+impl<C: Send + Sync, N: NodePrimitives> BalanceChangeReader for NoopProvider<C, N> {
+    fn balance_changes_index_tip(&self) -> ProviderResult<Option<BlockNumber>> {
+        Ok(None)
+    }
+
+    fn account_balance_changes(
+        &self,
+        _address: Address,
+        _range: RangeInclusive<BlockNumber>,
+        _limit: usize,
+    ) -> ProviderResult<Vec<BalanceChange>> {
+        Ok(Vec::default())
+    }
+}
+
 #[cfg(feature = "db-api")]
 impl<C: Send + Sync, N: NodePrimitives> StorageChangeSetReader for NoopProvider<C, N> {
     fn storage_changeset(
@@ -0,0 +1,501 @@
+//! Admission policies for the transaction pool.
+//!
+//! Admission policies are evaluated for every transaction that enters the pool through
+//! [`Pool`](crate::Pool), _before_ the transaction is validated. This makes them a cheap first line
+//! of defense against spam: a rejected transaction never reaches the validator and never acquires
+//! the pool lock.
+//!
+//! The pool always evaluates the built-in policies configured via [`AdmissionConfig`], followed by
+//! any custom [`AdmissionPolicy`] installed with
+//! [`PoolInner::add_admission_policy`](crate::pool::PoolInner::add_admission_policy).
+
+use crate::{
+    traits::{PeerId, PoolTransaction},
+    TransactionOrigin,
+};
+use alloy_primitives::{map::AddressSet, Address};
+use parking_lot::{Mutex, RwLock};
+use schnellru::{ByLength, LruMap};
+use std::{
+    fmt,
+    net::IpAddr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// The default number of sources tracked by the [`SourceRateLimit`] policy.
+pub const DEFAULT_MAX_TRACKED_SOURCES: u32 = 4096;
+
+/// Identifies where a transaction was received from.
+///
+/// This is more fine-grained than [`TransactionOrigin`], which only distinguishes between trust
+/// levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AdmissionSource {
+    /// The source of the transaction is unknown.
+    #[default]
+    Unknown,
+    /// The transaction was received from the network peer with the given id.
+    Peer(PeerId),
+    /// The transaction was received from a client with the given IP address, e.g. over RPC.
+    Ip(IpAddr),
+}
+
+impl AdmissionSource {
+    /// Returns `true` if the source is [`AdmissionSource::Unknown`].
+    pub const fn is_unknown(&self) -> bool {
+        matches!(self, Self::Unknown)
+    }
+}
+
+impl fmt::Display for AdmissionSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unknown => f.write_str("unknown"),
+            Self::Peer(peer_id) => write!(f, "peer {peer_id}"),
+            Self::Ip(ip) => write!(f, "ip {ip}"),
+        }
+    }
+}
+
+/// Everything an [`AdmissionPolicy`] can inspect about a transaction that is about to be admitted.
+#[derive(Debug)]
+pub struct AdmissionContext<'a, T> {
+    /// The origin the transaction is added with.
+    pub origin: TransactionOrigin,
+    /// Where the transaction was received from.
+    pub source: AdmissionSource,
+    /// The unvalidated transaction.
+    pub transaction: &'a T,
+}
+
+/// Reasons why an [`AdmissionPolicy`] rejected a transaction.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum AdmissionError {
+    /// The source exceeded its configured transaction rate.
+    #[error("rate limit exceeded for {0}")]
+    RateLimited(AdmissionSource),
+    /// The sender is on the deny list.
+    #[error("sender {0} is denied")]
+    SenderDenied(Address),
+    /// An allow list is configured and the sender is not on it.
+    #[error("sender {0} is not allowed")]
+    SenderNotAllowed(Address),
+    /// The transaction's gas price is below the minimum configured for its origin.
+    #[error("gas price {gas_price} below minimum {minimum} for {origin:?} transactions")]
+    Underpriced {
+        /// The origin of the transaction.
+        origin: TransactionOrigin,
+        /// The gas price (or priority fee) of the transaction.
+        gas_price: u128,
+        /// The configured minimum.
+        minimum: u128,
+    },
+    /// Any other policy specific reason.
+    #[error("{0}")]
+    Other(String),
+}
+
+/// A policy that decides whether a transaction may enter the pool.
+///
+/// Implementations must be cheap: they are invoked for every incoming transaction before
+/// validation.
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait AdmissionPolicy<T: PoolTransaction>: fmt::Debug + Send + Sync {
+    /// Returns `Ok(())` if the transaction may enter the pool.
+    fn admit(&self, ctx: &AdmissionContext<'_, T>) -> Result<(), AdmissionError>;
+}
+
+/// Configuration of the built-in admission policies.
+#[derive(Debug, Clone, Default)]
+pub struct AdmissionConfig {
+    /// Runtime-reloadable sender allow and deny lists.
+    pub sender_filter: SenderFilter,
+    /// Minimum gas price per [`TransactionOrigin`].
+    pub min_gas_price: OriginGasPriceFloor,
+    /// Per-source rate limit, disabled if `None`.
+    pub source_rate_limit: Option<SourceRateLimitConfig>,
+}
+
+/// An ordered set of [`AdmissionPolicy`]s.
+///
+/// A transaction is admitted if every policy admits it, policies are evaluated in insertion order
+/// and evaluation stops at the first rejection.
+pub struct AdmissionPolicies<T> {
+    policies: RwLock<Vec<Arc<dyn AdmissionPolicy<T>>>>,
+}
+
+impl<T: PoolTransaction> AdmissionPolicies<T> {
+    /// Creates the built-in policies from the given config.
+    pub fn from_config(config: &AdmissionConfig) -> Self {
+        let mut policies: Vec<Arc<dyn AdmissionPolicy<T>>> =
+            vec![Arc::new(config.sender_filter.clone()), Arc::new(config.min_gas_price)];
+        if let Some(rate_limit) = config.source_rate_limit {
+            policies.push(Arc::new(SourceRateLimit::new(rate_limit)));
+        }
+        Self { policies: RwLock::new(policies) }
+    }
+
+    /// Appends a policy that is evaluated after all existing ones.
+    pub fn push(&self, policy: Arc<dyn AdmissionPolicy<T>>) {
+        self.policies.write().push(policy);
+    }
+
+    /// Returns the number of installed policies.
+    pub fn len(&self) -> usize {
+        self.policies.read().len()
+    }
+
+    /// Returns `true` if no policies are installed.
+    pub fn is_empty(&self) -> bool {
+        self.policies.read().is_empty()
+    }
+}
+
+impl<T: PoolTransaction> Default for AdmissionPolicies<T> {
+    fn default() -> Self {
+        Self { policies: Default::default() }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for AdmissionPolicies<T> {
+    fn admit(&self, ctx: &AdmissionContext<'_, T>) -> Result<(), AdmissionError> {
+        self.policies.read().iter().try_for_each(|policy| policy.admit(ctx))
+    }
+}
+
+impl<T> fmt::Debug for AdmissionPolicies<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AdmissionPolicies").field("policies", &*self.policies.read()).finish()
+    }
+}
+
+/// Sender allow and deny lists that can be updated at runtime.
+///
+/// Cloning this type is cheap and all clones share the same lists, so a handle can be kept around
+/// (e.g. by an admin RPC) to reload the lists while the pool is running.
+///
+/// Transactions from denied senders are always rejected. If an allow list is set, only
+/// transactions from allowed senders are admitted.
+#[derive(Debug, Clone, Default)]
+pub struct SenderFilter {
+    lists: Arc<RwLock<SenderFilterLists>>,
+}
+
+/// The allow and deny lists of a [`SenderFilter`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SenderFilterLists {
+    /// If set, only these senders are admitted.
+    pub allow: Option<AddressSet>,
+    /// Senders that are never admitted.
+    pub deny: AddressSet,
+}
+
+impl SenderFilter {
+    /// Creates a new filter with the given lists.
+    pub fn new(lists: SenderFilterLists) -> Self {
+        Self { lists: Arc::new(RwLock::new(lists)) }
+    }
+
+    /// Returns a snapshot of the current lists.
+    pub fn lists(&self) -> SenderFilterLists {
+        self.lists.read().clone()
+    }
+
+    /// Replaces both lists.
+    pub fn set_lists(&self, lists: SenderFilterLists) {
+        *self.lists.write() = lists;
+    }
+
+    /// Replaces the allow list, `None` admits every sender that is not denied.
+    pub fn set_allow_list(&self, allow: Option<AddressSet>) {
+        self.lists.write().allow = allow;
+    }
+
+    /// Replaces the deny list.
+    pub fn set_deny_list(&self, deny: AddressSet) {
+        self.lists.write().deny = deny;
+    }
+
+    /// Adds the sender to the deny list.
+    ///
+    /// Returns `true` if the sender was not already denied.
+    pub fn deny(&self, sender: Address) -> bool {
+        self.lists.write().deny.insert(sender)
+    }
+
+    /// Removes the sender from the deny list.
+    ///
+    /// Returns `true` if the sender was denied.
+    pub fn undeny(&self, sender: &Address) -> bool {
+        self.lists.write().deny.remove(sender)
+    }
+
+    /// Returns whether transactions of the given sender are admitted.
+    pub fn check(&self, sender: &Address) -> Result<(), AdmissionError> {
+        let lists = self.lists.read();
+        if lists.deny.contains(sender) {
+            return Err(AdmissionError::SenderDenied(*sender))
+        }
+        if let Some(allow) = &lists.allow &&
+            !allow.contains(sender)
+        {
+            return Err(AdmissionError::SenderNotAllowed(*sender))
+        }
+        Ok(())
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for SenderFilter {
+    fn admit(&self, ctx: &AdmissionContext<'_, T>) -> Result<(), AdmissionError> {
+        self.check(ctx.transaction.sender_ref())
+    }
+}
+
+/// Minimum gas price per [`TransactionOrigin`].
+///
+/// The gas price of a transaction is its priority fee for dynamic fee transactions and its gas
+/// price for legacy transactions, see `priority_fee_or_price`. This is useful on chains with a
+/// (near) zero base fee, where the global
+/// [`minimum_priority_fee`](crate::PoolConfig::minimum_priority_fee) is too coarse.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OriginGasPriceFloor {
+    /// Minimum for [`TransactionOrigin::Local`] transactions.
+    pub local: Option<u128>,
+    /// Minimum for [`TransactionOrigin::External`] transactions.
+    pub external: Option<u128>,
+    /// Minimum for [`TransactionOrigin::Private`] transactions.
+    pub private: Option<u128>,
+}
+
+impl OriginGasPriceFloor {
+    /// Sets the minimum for external transactions.
+    pub const fn with_external(mut self, minimum: u128) -> Self {
+        self.external = Some(minimum);
+        self
+    }
+
+    /// Sets the minimum for local transactions.
+    pub const fn with_local(mut self, minimum: u128) -> Self {
+        self.local = Some(minimum);
+        self
+    }
+
+    /// Sets the minimum for private transactions.
+    pub const fn with_private(mut self, minimum: u128) -> Self {
+        self.private = Some(minimum);
+        self
+    }
+
+    /// Returns the minimum for the given origin, if any.
+    pub const fn minimum(&self, origin: TransactionOrigin) -> Option<u128> {
+        match origin {
+            TransactionOrigin::Local => self.local,
+            TransactionOrigin::External => self.external,
+            TransactionOrigin::Private => self.private,
+        }
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for OriginGasPriceFloor {
+    fn admit(&self, ctx: &AdmissionContext<'_, T>) -> Result<(), AdmissionError> {
+        let Some(minimum) = self.minimum(ctx.origin) else { return Ok(()) };
+        let gas_price = ctx.transaction.priority_fee_or_price();
+        if gas_price < minimum {
+            return Err(AdmissionError::Underpriced { origin: ctx.origin, gas_price, minimum })
+        }
+        Ok(())
+    }
+}
+
+/// Configuration of the [`SourceRateLimit`] policy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SourceRateLimitConfig {
+    /// Sustained number of transactions per second admitted per source.
+    pub txs_per_second: u32,
+    /// Number of transactions a source can submit in a burst.
+    pub burst: u32,
+    /// Maximum number of sources to track, least recently seen sources are forgotten first.
+    pub max_tracked_sources: u32,
+}
+
+impl SourceRateLimitConfig {
+    /// Creates a new config with the given rate and a burst of one second worth of transactions.
+    pub const fn new(txs_per_second: u32) -> Self {
+        Self {
+            txs_per_second,
+            burst: txs_per_second,
+            max_tracked_sources: DEFAULT_MAX_TRACKED_SOURCES,
+        }
+    }
+
+    /// Sets the burst size.
+    pub const fn with_burst(mut self, burst: u32) -> Self {
+        self.burst = burst;
+        self
+    }
+}
+
+/// Rate limits transactions per [`AdmissionSource`] with a token bucket.
+///
+/// Transactions of an [`AdmissionSource::Unknown`] source are not rate limited.
+pub struct SourceRateLimit {
+    config: SourceRateLimitConfig,
+    buckets: Mutex<LruMap<AdmissionSource, TokenBucket, ByLength>>,
+}
+
+impl SourceRateLimit {
+    /// Creates a new rate limiter.
+    pub fn new(config: SourceRateLimitConfig) -> Self {
+        Self { config, buckets: Mutex::new(LruMap::new(ByLength::new(config.max_tracked_sources))) }
+    }
+
+    /// Consumes a token of the given source at the given time.
+    fn try_acquire(&self, source: AdmissionSource, now: Instant) -> Result<(), AdmissionError> {
+        let SourceRateLimitConfig { txs_per_second, burst, .. } = self.config;
+        let mut buckets = self.buckets.lock();
+        let Some(bucket) = buckets
+            .get_or_insert(source, || TokenBucket { tokens: burst as f64, refilled_at: now })
+        else {
+            // the limiter can't track any source
+            return Ok(())
+        };
+
+        let elapsed = now.saturating_duration_since(bucket.refilled_at);
+        bucket.refill(elapsed, txs_per_second as f64, burst as f64);
+        bucket.refilled_at = now;
+
+        if bucket.tokens < 1.0 {
+            return Err(AdmissionError::RateLimited(source))
+        }
+        bucket.tokens -= 1.0;
+        Ok(())
+    }
+}
+
+impl<T: PoolTransaction> AdmissionPolicy<T> for SourceRateLimit {
+    fn admit(&self, ctx: &AdmissionContext<'_, T>) -> Result<(), AdmissionError> {
+        if ctx.source.is_unknown() {
+            return Ok(())
+        }
+        self.try_acquire(ctx.source, Instant::now())
+    }
+}
+
+impl fmt::Debug for SourceRateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SourceRateLimit")
+            .field("config", &self.config)
+            .field("tracked_sources", &self.buckets.lock().len())
+            .finish()
+    }
+}
+
+/// Token bucket state of a single source.
+#[derive(Debug)]
+struct TokenBucket {
+    tokens: f64,
+    refilled_at: Instant,
+}
+
+impl TokenBucket {
+    fn refill(&mut self, elapsed: Duration, rate: f64, capacity: f64) {
+        self.tokens = elapsed.as_secs_f64().mul_add(rate, self.tokens).min(capacity);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::MockTransaction;
+
+    fn ctx(
+        tx: &MockTransaction,
+        origin: TransactionOrigin,
+    ) -> AdmissionContext<'_, MockTransaction> {
+        AdmissionContext { origin, source: AdmissionSource::Unknown, transaction: tx }
+    }
+
+    #[test]
+    fn sender_filter_deny_and_allow() {
+        let tx = MockTransaction::eip1559();
+        let filter = SenderFilter::default();
+        assert!(filter.admit(&ctx(&tx, TransactionOrigin::External)).is_ok());
+
+        assert!(filter.deny(tx.sender()));
+        assert_eq!(
+            filter.admit(&ctx(&tx, TransactionOrigin::External)),
+            Err(AdmissionError::SenderDenied(tx.sender()))
+        );
+
+        // updates are visible through all clones
+        let handle = filter.clone();
+        handle.undeny(&tx.sender());
+        assert!(filter.admit(&ctx(&tx, TransactionOrigin::External)).is_ok());
+
+        handle.set_allow_list(Some(AddressSet::from_iter([Address::with_last_byte(1)])));
+        assert_eq!(
+            filter.admit(&ctx(&tx, TransactionOrigin::External)),
+            Err(AdmissionError::SenderNotAllowed(tx.sender()))
+        );
+    }
+
+    #[test]
+    fn origin_gas_price_floor() {
+        let tx = MockTransaction::legacy().with_gas_price(10);
+        let floor = OriginGasPriceFloor::default().with_external(11);
+
+        assert!(floor.admit(&ctx(&tx, TransactionOrigin::Local)).is_ok());
+        assert_eq!(
+            floor.admit(&ctx(&tx, TransactionOrigin::External)),
+            Err(AdmissionError::Underpriced {
+                origin: TransactionOrigin::External,
+                gas_price: 10,
+                minimum: 11
+            })
+        );
+    }
+
+    #[test]
+    fn source_rate_limit_refills() {
+        let limit = SourceRateLimit::new(SourceRateLimitConfig::new(2));
+        let peer = AdmissionSource::Peer(PeerId::random());
+        let other = AdmissionSource::Ip(IpAddr::from([127, 0, 0, 1]));
+        let now = Instant::now();
+
+        assert!(limit.try_acquire(peer, now).is_ok());
+        assert!(limit.try_acquire(peer, now).is_ok());
+        assert_eq!(limit.try_acquire(peer, now), Err(AdmissionError::RateLimited(peer)));
+
+        // other sources have their own budget
+        assert!(limit.try_acquire(other, now).is_ok());
+
+        // half a second refills one token
+        let later = now + Duration::from_millis(500);
+        assert!(limit.try_acquire(peer, later).is_ok());
+        assert!(limit.try_acquire(peer, later).is_err());
+    }
+
+    #[test]
+    fn unknown_source_is_not_rate_limited() {
+        let tx = MockTransaction::eip1559();
+        let limit = SourceRateLimit::new(SourceRateLimitConfig::new(0));
+        assert!(limit.admit(&ctx(&tx, TransactionOrigin::External)).is_ok());
+    }
+
+    #[test]
+    fn policies_stop_at_first_rejection() {
+        let tx = MockTransaction::legacy().with_gas_price(1);
+        let config = AdmissionConfig {
+            min_gas_price: OriginGasPriceFloor::default().with_external(2),
+            ..Default::default()
+        };
+        config.sender_filter.deny(tx.sender());
+
+        let policies = AdmissionPolicies::<MockTransaction>::from_config(&config);
+        assert_eq!(
+            policies.admit(&ctx(&tx, TransactionOrigin::External)),
+            Err(AdmissionError::SenderDenied(tx.sender()))
+        );
+    }
+}
@@ -4,7 +4,8 @@
 //! many concurrent transaction pool insertions.
 
 use crate::{
-    error::PoolError, AddedTransactionOutcome, PoolTransaction, TransactionOrigin, TransactionPool,
+    error::PoolError, AddedTransactionOutcome, AdmissionSource, PoolTransaction, TransactionOrigin,
+    TransactionPool,
 };
 use pin_project::pin_project;
 use std::{
@@ -17,6 +18,8 @@ use tokio::sync::{mpsc, oneshot};
 /// A single batch transaction request
 #[derive(Debug)]
 pub struct BatchTxRequest<T: PoolTransaction> {
+    /// Where the transaction was received from
+    source: AdmissionSource,
     /// Origin of the transaction (e.g. Local, External)
     origin: TransactionOrigin,
     /// Tx to be inserted in to the pool
@@ -35,7 +38,13 @@ where
         pool_tx: T,
         response_tx: oneshot::Sender<Result<AddedTransactionOutcome, PoolError>>,
     ) -> Self {
-        Self { origin, pool_tx, response_tx }
+        Self { source: AdmissionSource::Unknown, origin, pool_tx, response_tx }
+    }
+
+    /// Sets the [`AdmissionSource`] the transaction was received from
+    pub const fn with_source(mut self, source: AdmissionSource) -> Self {
+        self.source = source;
+        self
     }
 }
 
@@ -67,8 +76,8 @@ where
     }
 
     async fn process_request(pool: &Pool, req: BatchTxRequest<Pool::Transaction>) {
-        let BatchTxRequest { origin, pool_tx, response_tx } = req;
-        let pool_result = pool.add_transaction(origin, pool_tx).await;
+        let BatchTxRequest { source, origin, pool_tx, response_tx } = req;
+        let pool_result = pool.add_transaction_from_source(source, origin, pool_tx).await;
         let _ = response_tx.send(pool_result);
     }
 
@@ -80,10 +89,12 @@ where
             return
         }
 
-        let (transactions, response_txs): (Vec<_>, Vec<_>) =
-            batch.into_iter().map(|req| ((req.origin, req.pool_tx), req.response_tx)).unzip();
+        let (transactions, response_txs): (Vec<_>, Vec<_>) = batch
+            .into_iter()
+            .map(|req| ((req.source, req.origin, req.pool_tx), req.response_tx))
+            .unzip();
 
-        let pool_results = pool.add_transactions_with_origins(transactions).await;
+        let pool_results = pool.add_transactions_from_sources(transactions).await;
         for (response_tx, pool_result) in response_txs.into_iter().zip(pool_results) {
             let _ = response_tx.send(pool_result);
         }
@@ -123,9 +134,14 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::test_utils::{testing_pool, MockTransaction};
+    use crate::{
+        admission::{AdmissionConfig, AdmissionError, SourceRateLimitConfig},
+        error::PoolErrorKind,
+        test_utils::{testing_pool, MockTransaction, TestPool, TestPoolBuilder},
+        PoolConfig,
+    };
     use futures::stream::{FuturesUnordered, StreamExt};
-    use std::time::Duration;
+    use std::{net::IpAddr, time::Duration};
     use tokio::time::timeout;
 
     #[tokio::test]
@@ -247,4 +263,42 @@ mod tests {
 
         handle.abort();
     }
+
+    #[tokio::test]
+    async fn test_process_batch_rate_limits_source() {
+        let config = PoolConfig {
+            admission: AdmissionConfig {
+                source_rate_limit: Some(SourceRateLimitConfig::new(2)),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let pool: TestPool = TestPoolBuilder::default().with_config(config).into();
+        let client = AdmissionSource::Ip(IpAddr::from([10, 0, 0, 1]));
+
+        let mut batch_requests = Vec::new();
+        let mut responses = Vec::new();
+        for i in 0..3 {
+            let tx = MockTransaction::legacy().with_nonce(i).with_gas_price(100);
+            let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+            batch_requests.push(
+                BatchTxRequest::new(TransactionOrigin::External, tx, response_tx)
+                    .with_source(client),
+            );
+            responses.push(response_rx);
+        }
+
+        BatchTxProcessor::process_batch(&pool, batch_requests).await;
+
+        let mut results = Vec::new();
+        for response_rx in responses {
+            results.push(response_rx.await.expect("Response channel was closed unexpectedly"));
+        }
+        assert!(results[0].is_ok());
+        assert!(results[1].is_ok());
+        assert!(matches!(
+            results[2].as_ref().unwrap_err().kind,
+            PoolErrorKind::AdmissionRejected(AdmissionError::RateLimited(source)) if source == client
+        ));
+    }
 }
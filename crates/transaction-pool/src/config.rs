@@ -1,4 +1,5 @@
 use crate::{
+    admission::AdmissionConfig,
     maintain::MAX_QUEUED_TRANSACTION_LIFETIME,
     pool::{NEW_TX_LISTENER_BUFFER_SIZE, PENDING_TX_LISTENER_BUFFER_SIZE},
     PoolSize, TransactionOrigin,
@@ -72,6 +73,8 @@ pub struct PoolConfig {
     ///
     /// This restricts how many executable transaction a delegated sender can stack.
     pub max_inflight_delegated_slot_limit: usize,
+    /// Built-in admission policies that are evaluated before transactions are validated.
+    pub admission: AdmissionConfig,
 }
 
 impl PoolConfig {
@@ -100,6 +103,40 @@ impl PoolConfig {
         self
     }
 
+    /// Configures the built-in admission policies.
+    pub fn with_admission(mut self, admission: AdmissionConfig) -> Self {
+        self.admission = admission;
+        self
+    }
+
+    /// Returns the size limits of the pool.
+    pub const fn limits(&self) -> PoolLimits {
+        PoolLimits {
+            pending_limit: self.pending_limit,
+            basefee_limit: self.basefee_limit,
+            queued_limit: self.queued_limit,
+            blob_limit: self.blob_limit,
+            max_account_slots: self.max_account_slots,
+        }
+    }
+
+    /// Configures the size limits of the pool.
+    pub const fn with_limits(mut self, limits: PoolLimits) -> Self {
+        let PoolLimits {
+            pending_limit,
+            basefee_limit,
+            queued_limit,
+            blob_limit,
+            max_account_slots,
+        } = limits;
+        self.pending_limit = pending_limit;
+        self.basefee_limit = basefee_limit;
+        self.queued_limit = queued_limit;
+        self.blob_limit = blob_limit;
+        self.max_account_slots = max_account_slots;
+        self
+    }
+
     /// Returns whether the size and amount constraints in any sub-pools are exceeded.
     #[inline]
     pub const fn is_exceeded(&self, pool_size: PoolSize) -> bool {
@@ -129,10 +166,26 @@ impl Default for PoolConfig {
             max_new_pending_txs_notifications: MAX_NEW_PENDING_TXS_NOTIFICATIONS,
             max_queued_lifetime: MAX_QUEUED_TRANSACTION_LIFETIME,
             max_inflight_delegated_slot_limit: DEFAULT_MAX_INFLIGHT_DELEGATED_SLOTS,
+            admission: Default::default(),
         }
     }
 }
 
+/// The size limits of the pool, which can be changed while the pool is running.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolLimits {
+    /// Max number of transaction in the pending sub-pool
+    pub pending_limit: SubPoolLimit,
+    /// Max number of transaction in the basefee sub-pool
+    pub basefee_limit: SubPoolLimit,
+    /// Max number of transaction in the queued sub-pool
+    pub queued_limit: SubPoolLimit,
+    /// Max number of transactions in the blob sub-pool
+    pub blob_limit: SubPoolLimit,
+    /// Max number of executable transaction slots guaranteed per account
+    pub max_account_slots: usize,
+}
+
 /// Size limits for a sub-pool.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct SubPoolLimit {
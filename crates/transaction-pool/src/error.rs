@@ -2,6 +2,7 @@
 
 use std::any::Any;
 
+use crate::admission::AdmissionError;
 use alloy_eips::eip4844::BlobTransactionValidationError;
 use alloy_primitives::{Address, TxHash, U256};
 use reth_primitives_traits::transaction::error::InvalidTransactionError;
@@ -66,6 +67,10 @@ pub enum PoolErrorKind {
     /// Thrown if the mutual exclusivity constraint (blob vs normal transaction) is violated.
     #[error("transaction type {1} conflicts with existing transaction for {0}")]
     ExistingConflictingTransactionType(Address, u8),
+    /// Thrown if an [`AdmissionPolicy`](crate::admission::AdmissionPolicy) rejected the
+    /// transaction before validation.
+    #[error("transaction rejected: {0}")]
+    AdmissionRejected(#[from] AdmissionError),
     /// Any other error that occurred while inserting/validating a transaction. e.g. IO database
     /// error
     #[error(transparent)]
@@ -143,6 +148,10 @@ impl PoolError {
                 // exclusivity (blob vs normal tx) for all senders
                 false
             }
+            PoolErrorKind::AdmissionRejected(_) => {
+                // local policy decision, the transaction itself may be perfectly valid
+                false
+            }
         }
     }
 
@@ -278,22 +278,29 @@
 
 pub use imbl::OrdMap;
 
+use crate::{admission::SenderFilter, identifier::TransactionId, pool::PoolInner};
 pub use crate::{
+    admission::{AdmissionPolicy, AdmissionSource},
     batcher::{BatchTxProcessor, BatchTxRequest},
     blobstore::{BlobStore, BlobStoreError},
     config::{
-        LocalTransactionConfig, PoolConfig, PriceBumpConfig, SubPoolLimit,
+        LocalTransactionConfig, PoolConfig, PoolLimits, PriceBumpConfig, SubPoolLimit,
         DEFAULT_MAX_INFLIGHT_DELEGATED_SLOTS, DEFAULT_PRICE_BUMP,
         DEFAULT_TXPOOL_ADDITIONAL_VALIDATION_TASKS, MAX_NEW_PENDING_TXS_NOTIFICATIONS,
         REPLACE_BLOB_PRICE_BUMP, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER,
         TXPOOL_SUBPOOL_MAX_SIZE_MB_DEFAULT, TXPOOL_SUBPOOL_MAX_TXS_DEFAULT,
     },
     error::PoolResult,
-    ordering::{CoinbaseTipOrdering, Priority, TransactionOrdering},
+    ordering::{
+        CoinbaseTipOrdering, ConfiguredOrdering, EffectiveGasPriceOrdering, FifoOrdering,
+        OrderingConfig, Priority, PriorityBoost, RecipientBoost, TransactionOrdering,
+        TransactionOrderingKind, WeightedOrdering,
+    },
     pool::{
         blob_tx_priority, fee_delta, state::SubPool, AddedTransactionOutcome,
-        AllTransactionsEvents, FullTransactionEvent, NewTransactionEvent, TransactionEvent,
-        TransactionEvents, TransactionListenerKind,
+        AllTransactionsEvents, DiscardReason, FullTransactionEvent, NewTransactionEvent,
+        PoolTransactionState, PrivateTransactionStatus, TransactionEvent, TransactionEvents,
+        TransactionListenerKind,
     },
     traits::*,
     validate::{
@@ -301,7 +308,6 @@ pub use crate::{
         TransactionValidator, ValidPoolTransaction,
     },
 };
-use crate::{identifier::TransactionId, pool::PoolInner};
 use alloy_eips::{
     eip4844::{BlobAndProofV1, BlobAndProofV2, BlobCellsAndProofsV1},
     eip7594::BlobTransactionSidecarVariant,
@@ -319,11 +325,14 @@ use std::sync::Arc;
 use tokio::sync::mpsc::Receiver;
 use tracing::{instrument, trace};
 
+pub mod admission;
 pub mod error;
 pub mod maintain;
 pub mod metrics;
 pub mod noop;
 pub mod pool;
+#[cfg(feature = "serde")]
+pub mod replay;
 pub mod validate;
 
 pub mod batcher;
@@ -344,6 +353,15 @@ pub type EthTransactionPool<Client, S, Evm = EthEvmConfig, T = EthPooledTransact
     S,
 >;
 
+/// Type alias for an ethereum transaction pool that uses the [`ConfiguredOrdering`] selected at
+/// runtime.
+pub type ConfiguredEthTransactionPool<Client, S, Evm = EthEvmConfig, T = EthPooledTransaction> =
+    Pool<
+        TransactionValidationTaskExecutor<EthTransactionValidator<Client, T, Evm>>,
+        ConfiguredOrdering<T>,
+        S,
+    >;
+
 /// A shareable, generic, customizable `TransactionPool` implementation.
 #[derive(Debug)]
 pub struct Pool<V, T: TransactionOrdering, S> {
@@ -379,6 +397,12 @@ where
         self.inner().validator()
     }
 
+    /// Installs a custom [`AdmissionPolicy`] that is evaluated for every incoming transaction
+    /// after the built-in policies configured in [`PoolConfig::admission`].
+    pub fn add_admission_policy(&self, policy: impl AdmissionPolicy<T::Transaction> + 'static) {
+        self.inner().add_admission_policy(policy)
+    }
+
     /// Validates the given transaction
     async fn validate(
         &self,
@@ -388,6 +412,47 @@ where
         self.pool.validator().validate_transaction(origin, transaction).await
     }
 
+    /// Evaluates the admission policies for all transactions, validates the admitted ones and adds
+    /// them to the pool.
+    ///
+    /// Returns one result per transaction, in the same order.
+    async fn add_admitted_transactions(
+        &self,
+        transactions: Vec<(AdmissionSource, TransactionOrigin, V::Transaction)>,
+    ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        if transactions.is_empty() {
+            return Vec::new()
+        }
+
+        // `None` marks the slots of admitted transactions
+        let mut results = Vec::with_capacity(transactions.len());
+        let mut admitted = Vec::with_capacity(transactions.len());
+        for (source, origin, tx) in transactions {
+            match self.pool.check_admission(origin, source, &tx) {
+                Ok(()) => {
+                    results.push(None);
+                    admitted.push((origin, tx));
+                }
+                Err(err) => results.push(Some(Err(err))),
+            }
+        }
+
+        let mut added = if admitted.is_empty() {
+            Vec::new().into_iter()
+        } else {
+            let origins: Vec<_> = admitted.iter().map(|(origin, _)| *origin).collect();
+            let validated = self.pool.validator().validate_transactions(admitted).await;
+            self.pool.add_transactions_with_origins(origins.into_iter().zip(validated)).into_iter()
+        };
+
+        results
+            .into_iter()
+            .map(|res| {
+                res.unwrap_or_else(|| added.next().expect("one result per admitted transaction"))
+            })
+            .collect()
+    }
+
     /// Number of transactions in the entire pool
     pub fn len(&self) -> usize {
         self.pool.len()
@@ -487,6 +552,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<TransactionEvents> {
+        self.pool.check_admission(origin, AdmissionSource::Unknown, &transaction)?;
         let tx = self.validate(origin, transaction).await;
         self.pool.add_transaction_and_subscribe(origin, tx)
     }
@@ -496,9 +562,7 @@ where
         origin: TransactionOrigin,
         transaction: Self::Transaction,
     ) -> PoolResult<AddedTransactionOutcome> {
-        let tx = self.validate(origin, transaction).await;
-        let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
-        results.pop().expect("result length is the same as the input")
+        self.add_transaction_from_source(AdmissionSource::Unknown, origin, transaction).await
     }
 
     async fn add_transactions(
@@ -506,27 +570,102 @@ where
         origin: TransactionOrigin,
         transactions: Vec<Self::Transaction>,
     ) -> Vec<PoolResult<AddedTransactionOutcome>> {
-        if transactions.is_empty() {
-            return Vec::new()
-        }
-        let validated = self
-            .pool
-            .validator()
-            .validate_transactions(transactions.into_iter().map(|tx| (origin, tx)))
-            .await;
-        self.pool.add_transactions(origin, validated)
+        self.add_admitted_transactions(
+            transactions.into_iter().map(|tx| (AdmissionSource::Unknown, origin, tx)).collect(),
+        )
+        .await
     }
 
     async fn add_transactions_with_origins(
         &self,
         transactions: Vec<(TransactionOrigin, Self::Transaction)>,
     ) -> Vec<PoolResult<AddedTransactionOutcome>> {
-        if transactions.is_empty() {
-            return Vec::new()
-        }
-        let origins: Vec<_> = transactions.iter().map(|(origin, _)| *origin).collect();
-        let validated = self.pool.validator().validate_transactions(transactions).await;
-        self.pool.add_transactions_with_origins(origins.into_iter().zip(validated))
+        self.add_admitted_transactions(
+            transactions
+                .into_iter()
+                .map(|(origin, tx)| (AdmissionSource::Unknown, origin, tx))
+                .collect(),
+        )
+        .await
+    }
+
+    async fn add_transaction_from_source(
+        &self,
+        source: AdmissionSource,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> PoolResult<AddedTransactionOutcome> {
+        self.pool.check_admission(origin, source, &transaction)?;
+        let tx = self.validate(origin, transaction).await;
+        let mut results = self.pool.add_transactions(origin, std::iter::once(tx));
+        results.pop().expect("result length is the same as the input")
+    }
+
+    async fn add_transactions_from_sources(
+        &self,
+        transactions: Vec<(AdmissionSource, TransactionOrigin, Self::Transaction)>,
+    ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        self.add_admitted_transactions(transactions).await
+    }
+
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<u64>,
+    ) -> PoolResult<AddedTransactionOutcome> {
+        self.add_private_transaction_from_source(
+            AdmissionSource::Unknown,
+            transaction,
+            max_block_number,
+        )
+        .await
+    }
+
+    async fn add_private_transaction_from_source(
+        &self,
+        source: AdmissionSource,
+        transaction: Self::Transaction,
+        max_block_number: Option<u64>,
+    ) -> PoolResult<AddedTransactionOutcome> {
+        let origin = TransactionOrigin::Private;
+        self.pool.check_admission(origin, source, &transaction)?;
+        let tx = self.validate(origin, transaction).await;
+        self.pool.add_private_transaction(tx, max_block_number)
+    }
+
+    fn cancel_private_transaction(&self, tx_hash: TxHash) -> bool {
+        self.pool.cancel_private_transaction(tx_hash)
+    }
+
+    fn private_transaction_status(&self, tx_hash: TxHash) -> Option<PrivateTransactionStatus> {
+        self.pool.private_transaction_status(&tx_hash)
+    }
+
+    fn transaction_state(&self, tx_hash: TxHash) -> Option<PoolTransactionState> {
+        self.pool.transaction_state(&tx_hash)
+    }
+
+    async fn add_external_transactions_from_source(
+        &self,
+        source: AdmissionSource,
+        transactions: Vec<Self::Transaction>,
+    ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        self.add_admitted_transactions(
+            transactions.into_iter().map(|tx| (source, TransactionOrigin::External, tx)).collect(),
+        )
+        .await
+    }
+
+    fn sender_filter(&self) -> Option<SenderFilter> {
+        Some(self.config().admission.sender_filter.clone())
+    }
+
+    fn limits(&self) -> Option<PoolLimits> {
+        Some(self.pool.limits())
+    }
+
+    fn set_limits(&self, limits: PoolLimits) {
+        self.pool.set_limits(limits)
     }
 
     fn transaction_event_listener(&self, tx_hash: TxHash) -> Option<TransactionEvents> {
@@ -643,6 +782,14 @@ where
         self.pool.all_transactions()
     }
 
+    fn transactions_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> AllPoolTransactions<Self::Transaction> {
+        self.pool.transactions_page(offset, limit)
+    }
+
     fn all_transaction_hashes(&self) -> Vec<TxHash> {
         self.pool.all_transaction_hashes()
     }
@@ -11,8 +11,9 @@ use crate::{
     validate::ValidTransaction,
     AddedTransactionOutcome, AllPoolTransactions, AllTransactionsEvents, BestTransactions,
     BlockInfo, EthPoolTransaction, EthPooledTransaction, NewTransactionEvent, PoolResult, PoolSize,
-    PoolTransaction, PropagatedTransactions, TransactionEvents, TransactionOrigin, TransactionPool,
-    TransactionValidationOutcome, TransactionValidator, ValidPoolTransaction,
+    PoolTransaction, PoolTransactionState, PropagatedTransactions, TransactionEvents,
+    TransactionOrigin, TransactionPool, TransactionValidationOutcome, TransactionValidator,
+    ValidPoolTransaction,
 };
 use alloy_eips::{
     eip1559::ETHEREUM_BLOCK_GAS_LIMIT_30M,
@@ -111,6 +112,15 @@ impl<T: EthPoolTransaction> TransactionPool for NoopTransactionPool<T> {
             .collect()
     }
 
+    async fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        _max_block_number: Option<u64>,
+    ) -> PoolResult<AddedTransactionOutcome> {
+        let hash = *transaction.hash();
+        Err(PoolError::other(hash, Box::new(NoopInsertError::new(transaction))))
+    }
+
     fn transaction_event_listener(&self, _tx_hash: TxHash) -> Option<TransactionEvents> {
         None
     }
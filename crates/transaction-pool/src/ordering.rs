@@ -1,5 +1,12 @@
 use crate::traits::PoolTransaction;
-use std::{cmp::Ordering, fmt::Debug, marker::PhantomData};
+use alloy_primitives::map::AddressSet;
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    marker::PhantomData,
+    str::FromStr,
+    sync::Arc,
+};
 
 /// Priority of the transaction that can be missing.
 ///
@@ -97,6 +104,310 @@ impl<T> Clone for CoinbaseTipOrdering<T> {
     }
 }
 
+/// Orders transactions by the gas price they pay at the given base fee.
+///
+/// Unlike [`CoinbaseTipOrdering`] this includes the base fee, which makes no difference on chains
+/// where the base fee is zero, but ranks legacy and dynamic fee transactions consistently on chains
+/// with a small non-zero base fee. Transactions that can't pay the base fee have no priority.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct EffectiveGasPriceOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for EffectiveGasPriceOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = u128;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        effective_gas_price(transaction, base_fee).into()
+    }
+}
+
+impl<T> Default for EffectiveGasPriceOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for EffectiveGasPriceOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// Orders transactions first come, first served.
+///
+/// All transactions have the same priority, so the pool orders them by the order in which they
+/// were inserted, regardless of the fee they pay.
+#[derive(Debug)]
+#[non_exhaustive]
+pub struct FifoOrdering<T>(PhantomData<T>);
+
+impl<T> TransactionOrdering for FifoOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = u128;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        _transaction: &Self::Transaction,
+        _base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        Priority::Value(0)
+    }
+}
+
+impl<T> Default for FifoOrdering<T> {
+    fn default() -> Self {
+        Self(Default::default())
+    }
+}
+
+impl<T> Clone for FifoOrdering<T> {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+/// A boost of the priority of a transaction used by [`WeightedOrdering`].
+#[auto_impl::auto_impl(&, Arc, Box)]
+pub trait PriorityBoost<T>: Debug + Send + Sync {
+    /// Returns the boost of the transaction in basis points, e.g. `5_000` for +50%.
+    fn boost_bps(&self, transaction: &T) -> u64;
+}
+
+/// Boosts transactions that call one of the configured contracts, e.g. whitelisted paymasters.
+#[derive(Debug, Clone, Default)]
+pub struct RecipientBoost {
+    /// The boosted recipients.
+    recipients: AddressSet,
+    /// The boost in basis points.
+    boost_bps: u64,
+}
+
+impl RecipientBoost {
+    /// Creates a new boost of `boost_bps` basis points for the given recipients.
+    pub const fn new(recipients: AddressSet, boost_bps: u64) -> Self {
+        Self { recipients, boost_bps }
+    }
+}
+
+impl<T: PoolTransaction> PriorityBoost<T> for RecipientBoost {
+    fn boost_bps(&self, transaction: &T) -> u64 {
+        match transaction.to() {
+            Some(to) if self.recipients.contains(&to) => self.boost_bps,
+            _ => 0,
+        }
+    }
+}
+
+/// Orders transactions by the fees they pay, weighted by the configured [`PriorityBoost`]s.
+///
+/// The score is the gas price the transaction pays at the given base fee times its gas limit, which
+/// bounds the gas it uses, scaled up by the sum of all boosts. Transactions that can't pay the base
+/// fee have no priority.
+#[derive(Debug)]
+pub struct WeightedOrdering<T> {
+    boosts: Vec<Arc<dyn PriorityBoost<T>>>,
+}
+
+impl<T> WeightedOrdering<T> {
+    /// Creates a new ordering without boosts.
+    pub const fn new() -> Self {
+        Self { boosts: Vec::new() }
+    }
+
+    /// Adds a boost to the ordering.
+    pub fn with_boost(mut self, boost: impl PriorityBoost<T> + 'static) -> Self {
+        self.boosts.push(Arc::new(boost));
+        self
+    }
+}
+
+impl<T> TransactionOrdering for WeightedOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = u128;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        let Some(gas_price) = effective_gas_price(transaction, base_fee) else {
+            return Priority::None
+        };
+        let fees = gas_price.saturating_mul(transaction.gas_limit() as u128);
+        let boost =
+            self.boosts.iter().map(|boost| boost.boost_bps(transaction) as u128).sum::<u128>();
+        Priority::Value(fees.saturating_mul(BPS + boost) / BPS)
+    }
+}
+
+impl<T> Default for WeightedOrdering<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for WeightedOrdering<T> {
+    fn clone(&self) -> Self {
+        Self { boosts: self.boosts.clone() }
+    }
+}
+
+/// Basis points of a 100% weight.
+const BPS: u128 = 10_000;
+
+/// Returns the gas price the transaction pays at the given base fee, or `None` if it can't pay the
+/// base fee.
+fn effective_gas_price<T: PoolTransaction>(transaction: &T, base_fee: u64) -> Option<u128> {
+    (transaction.max_fee_per_gas() >= base_fee as u128)
+        .then(|| transaction.effective_gas_price(Some(base_fee)))
+}
+
+/// The built-in [`TransactionOrdering`]s that can be selected at runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TransactionOrderingKind {
+    /// [`CoinbaseTipOrdering`].
+    #[default]
+    CoinbaseTip,
+    /// [`EffectiveGasPriceOrdering`].
+    EffectiveGasPrice,
+    /// [`FifoOrdering`].
+    Fifo,
+    /// [`WeightedOrdering`].
+    Weighted,
+}
+
+impl TransactionOrderingKind {
+    /// All kinds.
+    pub const ALL: [Self; 4] =
+        [Self::CoinbaseTip, Self::EffectiveGasPrice, Self::Fifo, Self::Weighted];
+
+    /// Returns the name of the kind.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::CoinbaseTip => "coinbase-tip",
+            Self::EffectiveGasPrice => "effective-gas-price",
+            Self::Fifo => "fifo",
+            Self::Weighted => "weighted",
+        }
+    }
+}
+
+impl fmt::Display for TransactionOrderingKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for TransactionOrderingKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL.into_iter().find(|kind| kind.as_str() == s).ok_or_else(|| {
+            let valid = Self::ALL.map(|kind| kind.as_str()).join(", ");
+            format!("invalid transaction ordering `{s}`, expected one of: {valid}")
+        })
+    }
+}
+
+/// Configuration of a [`ConfiguredOrdering`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct OrderingConfig {
+    /// The ordering to use.
+    pub kind: TransactionOrderingKind,
+    /// Recipients that are boosted by the [`WeightedOrdering`].
+    pub boosted_recipients: AddressSet,
+    /// The boost of the boosted recipients in basis points.
+    pub recipient_boost_bps: u64,
+}
+
+/// A [`TransactionOrdering`] that is one of the built-in orderings, selected at runtime.
+#[derive(Debug)]
+pub enum ConfiguredOrdering<T> {
+    /// See [`CoinbaseTipOrdering`].
+    CoinbaseTip(CoinbaseTipOrdering<T>),
+    /// See [`EffectiveGasPriceOrdering`].
+    EffectiveGasPrice(EffectiveGasPriceOrdering<T>),
+    /// See [`FifoOrdering`].
+    Fifo(FifoOrdering<T>),
+    /// See [`WeightedOrdering`].
+    Weighted(WeightedOrdering<T>),
+}
+
+impl<T: PoolTransaction + 'static> ConfiguredOrdering<T> {
+    /// Creates the ordering for the given config.
+    pub fn new(config: &OrderingConfig) -> Self {
+        match config.kind {
+            TransactionOrderingKind::CoinbaseTip => Self::CoinbaseTip(Default::default()),
+            TransactionOrderingKind::EffectiveGasPrice => {
+                Self::EffectiveGasPrice(Default::default())
+            }
+            TransactionOrderingKind::Fifo => Self::Fifo(Default::default()),
+            TransactionOrderingKind::Weighted => {
+                let mut ordering = WeightedOrdering::new();
+                if !config.boosted_recipients.is_empty() {
+                    ordering = ordering.with_boost(RecipientBoost::new(
+                        config.boosted_recipients.clone(),
+                        config.recipient_boost_bps,
+                    ));
+                }
+                Self::Weighted(ordering)
+            }
+        }
+    }
+}
+
+impl<T> TransactionOrdering for ConfiguredOrdering<T>
+where
+    T: PoolTransaction + 'static,
+{
+    type PriorityValue = u128;
+    type Transaction = T;
+
+    fn priority(
+        &self,
+        transaction: &Self::Transaction,
+        base_fee: u64,
+    ) -> Priority<Self::PriorityValue> {
+        match self {
+            Self::CoinbaseTip(ordering) => ordering.priority(transaction, base_fee),
+            Self::EffectiveGasPrice(ordering) => ordering.priority(transaction, base_fee),
+            Self::Fifo(ordering) => ordering.priority(transaction, base_fee),
+            Self::Weighted(ordering) => ordering.priority(transaction, base_fee),
+        }
+    }
+}
+
+impl<T> Clone for ConfiguredOrdering<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::CoinbaseTip(ordering) => Self::CoinbaseTip(ordering.clone()),
+            Self::EffectiveGasPrice(ordering) => Self::EffectiveGasPrice(ordering.clone()),
+            Self::Fifo(ordering) => Self::Fifo(ordering.clone()),
+            Self::Weighted(ordering) => Self::Weighted(ordering.clone()),
+        }
+    }
+}
+
+impl<T> Default for ConfiguredOrdering<T> {
+    fn default() -> Self {
+        Self::CoinbaseTip(Default::default())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -112,4 +423,68 @@ mod tests {
         assert!(p2 > p3); // Value(1) > None
         assert_eq!(p3, Priority::None);
     }
+
+    #[test]
+    fn ordering_kind_from_str() {
+        for kind in TransactionOrderingKind::ALL {
+            assert_eq!(kind.to_string().parse::<TransactionOrderingKind>().unwrap(), kind);
+        }
+        assert!("tip".parse::<TransactionOrderingKind>().is_err());
+    }
+
+    #[test]
+    fn weighted_ordering_boosts_recipients() {
+        use crate::test_utils::MockTransaction;
+        use alloy_primitives::{Address, TxKind};
+
+        let paymaster = Address::random();
+        let ordering = ConfiguredOrdering::new(&OrderingConfig {
+            kind: TransactionOrderingKind::Weighted,
+            boosted_recipients: [paymaster].into_iter().collect(),
+            recipient_boost_bps: 5_000,
+        });
+
+        let tx =
+            MockTransaction::eip1559().with_max_fee(20).with_priority_fee(10).with_gas_limit(100);
+        let mut boosted = tx.clone();
+        if let MockTransaction::Eip1559 { to, .. } = &mut boosted {
+            *to = TxKind::Call(paymaster);
+        }
+
+        // weighted by the gas price times the gas limit
+        assert_eq!(ordering.priority(&tx, 0), Priority::Value(1_000));
+        assert_eq!(ordering.priority(&boosted, 0), Priority::Value(1_500));
+        assert_eq!(
+            ordering.priority(&tx.clone().with_gas_limit(1_000_000), 0),
+            Priority::Value(10_000_000)
+        );
+        assert_eq!(ordering.priority(&tx, 15), Priority::Value(2_000));
+        assert_eq!(ordering.priority(&tx, 21), Priority::None);
+    }
+
+    #[test]
+    fn fifo_ordering_serves_transactions_by_arrival() {
+        use crate::{
+            pool::PendingPool,
+            test_utils::{MockTransaction, MockTransactionFactory},
+        };
+        use alloy_primitives::Address;
+
+        let ordering = FifoOrdering::default();
+        let cheap = MockTransaction::eip1559().with_max_fee(10).with_priority_fee(1);
+        let expensive = MockTransaction::eip1559().with_max_fee(100).with_priority_fee(100);
+        assert_eq!(ordering.priority(&expensive, 5), ordering.priority(&cheap, 5));
+
+        let mut f = MockTransactionFactory::default();
+        let mut pool = PendingPool::new(FifoOrdering::default());
+        let first = f.validated_arc(cheap.clone().with_sender(Address::random()));
+        let second = f.validated_arc(cheap.with_sender(Address::random()));
+        let third = f.validated_arc(expensive);
+        pool.add_transaction(first.clone(), 0);
+        pool.add_transaction(second.clone(), 0);
+        pool.add_transaction(third.clone(), 0);
+
+        let best = pool.best().map(|tx| *tx.hash()).collect::<Vec<_>>();
+        assert_eq!(best, vec![*first.hash(), *second.hash(), *third.hash()]);
+    }
 }
@@ -31,8 +31,14 @@ pub enum FullTransactionEvent<T: PoolTransaction> {
         /// The transaction that replaced the event subject.
         replaced_by: TxHash,
     },
-    /// Transaction was dropped due to configured limits.
-    Discarded(TxHash),
+    /// Transaction was dropped from the pool, e.g. due to configured limits.
+    #[non_exhaustive]
+    Discarded {
+        /// The hash of the discarded transaction.
+        tx_hash: TxHash,
+        /// Why the transaction was discarded.
+        reason: DiscardReason,
+    },
     /// Transaction became invalid indefinitely.
     Invalid(TxHash),
     /// Transaction was propagated to peers.
@@ -50,13 +56,42 @@ impl<T: PoolTransaction> Clone for FullTransactionEvent<T> {
             Self::Replaced { transaction, replaced_by } => {
                 Self::Replaced { transaction: Arc::clone(transaction), replaced_by: *replaced_by }
             }
-            Self::Discarded(hash) => Self::Discarded(*hash),
+            Self::Discarded { tx_hash, reason } => {
+                Self::Discarded { tx_hash: *tx_hash, reason: *reason }
+            }
             Self::Invalid(hash) => Self::Invalid(*hash),
             Self::Propagated(propagated) => Self::Propagated(Arc::clone(propagated)),
         }
     }
 }
 
+/// The reason a transaction was discarded from the pool.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+pub enum DiscardReason {
+    /// The pool exceeded its configured limits.
+    PoolLimits,
+    /// The transaction can no longer be executed after a state change, e.g. its nonce is too low.
+    Outdated,
+    /// The transaction was explicitly removed from the pool.
+    Removed,
+    /// The transaction could not be validated.
+    ValidationError,
+}
+
+impl DiscardReason {
+    /// Returns the name of the reason.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::PoolLimits => "poolLimits",
+            Self::Outdated => "outdated",
+            Self::Removed => "removed",
+            Self::ValidationError => "validationError",
+        }
+    }
+}
+
 /// Various events that describe status changes of a transaction.
 #[derive(Debug, Clone, Eq, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -66,7 +66,9 @@
 //!    category (2.) and become pending.
 
 use crate::{
+    admission::{AdmissionContext, AdmissionPolicies, AdmissionPolicy, AdmissionSource},
     blobstore::BlobStore,
+    config::PoolLimits,
     error::{PoolError, PoolErrorKind, PoolResult},
     identifier::{SenderId, SenderIdentifiers, TransactionId},
     metrics::BlobStoreMetrics,
@@ -75,6 +77,7 @@ use crate::{
             BlobTransactionSidecarListener, PendingTransactionHashListener, PoolEventBroadcast,
             TransactionListener,
         },
+        private::PrivateTransactions,
         state::SubPool,
         txpool::{SenderInfo, TxPool},
         update::UpdateOutcome,
@@ -90,7 +93,7 @@ use crate::{
 
 use alloy_primitives::{
     map::{AddressSet, HashSet},
-    Address, TxHash, B256,
+    Address, TxHash, B256, U256,
 };
 use parking_lot::{Mutex, RwLock, RwLockReadGuard, RwLockWriteGuard};
 use reth_eth_wire_types::HandleMempoolData;
@@ -112,10 +115,11 @@ use tracing::{debug, trace, warn};
 mod events;
 pub use best::{BestTransactionFilter, BestTransactionsWithPrioritizedSenders};
 pub use blob::{blob_tx_priority, fee_delta, BlobOrd, BlobTransactions};
-pub use events::{FullTransactionEvent, NewTransactionEvent, TransactionEvent};
+pub use events::{DiscardReason, FullTransactionEvent, NewTransactionEvent, TransactionEvent};
 pub use listener::{AllTransactionsEvents, TransactionEvents, TransactionListenerKind};
 pub use parked::{BasefeeOrd, ParkedOrd, ParkedPool, QueuedOrd};
 pub use pending::PendingPool;
+pub use private::PrivateTransactionStatus;
 
 mod best;
 pub use best::BestTransactions;
@@ -124,6 +128,7 @@ mod blob;
 pub mod listener;
 mod parked;
 pub mod pending;
+mod private;
 pub mod size;
 pub(crate) mod state;
 pub mod txpool;
@@ -151,6 +156,10 @@ where
     pool: RwLock<TxPool<T>>,
     /// Pool settings.
     config: PoolConfig,
+    /// Policies that decide whether a transaction may enter the pool.
+    admission: AdmissionPolicies<T::Transaction>,
+    /// Tracks the expiry and status of private transactions.
+    private_transactions: RwLock<PrivateTransactions>,
     /// Manages listeners for transaction state change events.
     event_listener: RwLock<PoolEventBroadcast<T::Transaction>>,
     /// Tracks whether any event listeners have ever been installed.
@@ -184,6 +193,8 @@ where
             pending_transaction_listener: Default::default(),
             transaction_listener: Default::default(),
             blob_transaction_sidecar_listener: Default::default(),
+            admission: AdmissionPolicies::from_config(&config.admission),
+            private_transactions: Default::default(),
             config,
             blob_store,
             blob_store_metrics: Default::default(),
@@ -260,15 +271,60 @@ where
     }
 
     /// Get the config the pool was configured with.
+    ///
+    /// This doesn't reflect limits changed with [`Self::set_limits`], see [`Self::limits`].
     pub const fn config(&self) -> &PoolConfig {
         &self.config
     }
 
+    /// Returns the size limits the pool currently enforces.
+    pub fn limits(&self) -> PoolLimits {
+        self.pool.read().limits()
+    }
+
+    /// Replaces the size limits of the pool and discards the worst transactions if the pool
+    /// exceeds the new limits.
+    pub fn set_limits(&self, limits: PoolLimits) {
+        let discarded = {
+            let mut pool = self.pool.write();
+            pool.set_limits(limits);
+            pool.discard_worst()
+        };
+
+        if !discarded.is_empty() {
+            self.delete_discarded_blobs(discarded.iter());
+            self.with_event_listener(|listener| {
+                listener.discarded_many(&discarded, DiscardReason::PoolLimits)
+            });
+        }
+    }
+
     /// Get the validator reference.
     pub const fn validator(&self) -> &V {
         &self.validator
     }
 
+    /// Installs a custom [`AdmissionPolicy`] that is evaluated after all existing policies.
+    pub fn add_admission_policy(&self, policy: impl AdmissionPolicy<T::Transaction> + 'static) {
+        self.admission.push(Arc::new(policy));
+    }
+
+    /// Evaluates the admission policies of the pool for the given, not yet validated, transaction.
+    ///
+    /// Returns [`PoolErrorKind::AdmissionRejected`] if any policy rejected the transaction.
+    pub fn check_admission(
+        &self,
+        origin: TransactionOrigin,
+        source: AdmissionSource,
+        transaction: &T::Transaction,
+    ) -> PoolResult<()> {
+        let ctx = AdmissionContext { origin, source, transaction };
+        self.admission.admit(&ctx).map_err(|err| {
+            trace!(target: "txpool", hash=%transaction.hash(), %source, %err, "transaction rejected by admission policy");
+            PoolError::new(*transaction.hash(), err)
+        })
+    }
+
     /// Adds a new transaction listener to the pool that gets notified about every new _pending_
     /// transaction inserted into the pool
     pub fn add_pending_listener(&self, kind: TransactionListenerKind) -> mpsc::Receiver<TxHash> {
@@ -535,6 +591,12 @@ where
         trace!(target: "txpool", "changed_accounts: {:?} mined_transactions: {:?}", changed_accounts, mined_transactions);
         let changed_senders = self.changed_senders(changed_accounts.into_iter());
 
+        self.private_transactions.write().on_mined(
+            &mined_transactions,
+            block_info.last_seen_block_number,
+            block_info.last_seen_block_hash,
+        );
+
         // update the pool
         let outcome = self.pool.write().on_canonical_state_change(
             block_info,
@@ -548,6 +610,64 @@ where
 
         // notify listeners about updates
         self.notify_on_new_state(outcome);
+
+        // evict private transactions that can no longer be included in the next block
+        let expired = {
+            let mut private = self.private_transactions.write();
+            let pool = self.get_pool_data();
+            private.retain_in_pool(|hash| pool.contains(hash));
+            private.expire(block_info.last_seen_block_number)
+        };
+        if !expired.is_empty() {
+            debug!(target: "txpool", ?expired, "evicting expired private transactions");
+            self.remove_transactions(expired);
+        }
+    }
+
+    /// Adds a private transaction that is evicted once the given max block number is reached.
+    ///
+    /// Private transactions are never propagated to peers, regardless of what the validator
+    /// decided.
+    pub fn add_private_transaction(
+        &self,
+        tx: TransactionValidationOutcome<T::Transaction>,
+        max_block_number: Option<u64>,
+    ) -> PoolResult<AddedTransactionOutcome> {
+        if let Some(max_block_number) = max_block_number &&
+            max_block_number <= self.block_info().last_seen_block_number
+        {
+            return Err(PoolError::other(tx.tx_hash(), "private transaction already expired"))
+        }
+
+        let mut results = self.add_transactions(TransactionOrigin::Private, std::iter::once(tx));
+        let outcome = results.pop().expect("result length is the same as the input")?;
+        self.private_transactions.write().insert(outcome.hash, max_block_number);
+        Ok(outcome)
+    }
+
+    /// Cancels a pending private transaction and removes it from the pool.
+    ///
+    /// Returns `false` if the transaction is not a pending private transaction.
+    pub fn cancel_private_transaction(&self, hash: TxHash) -> bool {
+        if !self.private_transactions.write().cancel(hash) {
+            return false
+        }
+        self.remove_transactions(vec![hash]);
+        true
+    }
+
+    /// Returns the status of a transaction that was added with
+    /// [`Self::add_private_transaction`].
+    ///
+    /// The status of private transactions that left the pool is remembered for a limited number of
+    /// transactions.
+    pub fn private_transaction_status(&self, hash: &TxHash) -> Option<PrivateTransactionStatus> {
+        let mut private = self.private_transactions.write();
+        if private.is_pending(hash) && !self.get_pool_data().contains(hash) {
+            // removed from the pool since the last canonical update, e.g. replaced
+            private.drop_pending(*hash);
+        }
+        private.status(hash)
     }
 
     /// Performs account updates on the pool.
@@ -603,7 +723,8 @@ where
                 let tx = ValidPoolTransaction {
                     transaction,
                     transaction_id,
-                    propagate,
+                    // private transactions must never be propagated
+                    propagate: propagate && !origin.is_private(),
                     timestamp: Instant::now(),
                     origin,
                     authority_ids: authorities.map(|auths| self.get_sender_ids(auths)),
@@ -625,7 +746,9 @@ where
                 (Err(PoolError::new(*tx.hash(), err)), None)
             }
             TransactionValidationOutcome::Error(tx_hash, err) => {
-                self.with_event_listener(|listener| listener.discarded(&tx_hash));
+                self.with_event_listener(|listener| {
+                    listener.discarded(&tx_hash, DiscardReason::ValidationError)
+                });
                 (Err(PoolError::other(tx_hash, err)), None)
             }
         }
@@ -668,6 +791,25 @@ where
             Item = (TransactionOrigin, TransactionValidationOutcome<T::Transaction>),
         >,
     ) -> Vec<PoolResult<AddedTransactionOutcome>> {
+        // Private transactions that were reorged out are re-injected as external transactions,
+        // they must stay private.
+        let mut reorged_private = Vec::new();
+        let transactions = {
+            let mut private = self.private_transactions.write();
+            transactions
+                .into_iter()
+                .map(|(origin, tx)| {
+                    if origin.is_external() &&
+                        let Some(max_block_number) = private.take_reorged(&tx.tx_hash())
+                    {
+                        reorged_private.push((tx.tx_hash(), max_block_number));
+                        return (TransactionOrigin::Private, tx)
+                    }
+                    (origin, tx)
+                })
+                .collect::<Vec<_>>()
+        };
+
         // Collect results and metadata while holding the pool write lock
         let (mut results, added_metas, discarded) = {
             let mut pool = self.pool.write();
@@ -706,7 +848,9 @@ where
         if !discarded.is_empty() {
             // Delete any blobs associated with discarded blob transactions
             self.delete_discarded_blobs(discarded.iter());
-            self.with_event_listener(|listener| listener.discarded_many(&discarded));
+            self.with_event_listener(|listener| {
+                listener.discarded_many(&discarded, DiscardReason::PoolLimits)
+            });
 
             let discarded_hashes =
                 discarded.into_iter().map(|tx| *tx.hash()).collect::<HashSet<_>>();
@@ -722,6 +866,18 @@ where
             }
         };
 
+        if !reorged_private.is_empty() {
+            let mut private = self.private_transactions.write();
+            for (hash, max_block_number) in reorged_private {
+                if results.iter().any(|res| res.as_ref().is_ok_and(|outcome| outcome.hash == hash))
+                {
+                    private.insert(hash, max_block_number);
+                } else {
+                    private.drop_reorged(hash, max_block_number);
+                }
+            }
+        }
+
         results
     }
 
@@ -920,7 +1076,7 @@ where
                 listener.pending(tx.hash(), None);
             }
             for tx in &discarded {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Outdated);
             }
         })
     }
@@ -987,7 +1143,7 @@ where
                 listener.pending(tx.hash(), None);
             }
             for tx in &discarded {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Outdated);
             }
         });
 
@@ -1016,7 +1172,7 @@ where
                     listener.pending(tx.hash(), None);
                 }
                 for tx in discarded {
-                    listener.discarded(tx.hash());
+                    listener.discarded(tx.hash(), DiscardReason::Outdated);
                 }
             }
             AddedTransaction::Parked { transaction, replaced, queued_reason, .. } => {
@@ -1070,6 +1226,16 @@ where
         }
     }
 
+    /// Returns the pending and queued transactions of at most `limit` senders, skipping the first
+    /// `offset` senders, in the order the pool first saw them.
+    pub fn transactions_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> AllPoolTransactions<T::Transaction> {
+        self.get_pool_data().transactions_page(offset, limit)
+    }
+
     /// Returns _all_ transactions in the pool
     pub fn all_transaction_hashes(&self) -> Vec<TxHash> {
         self.get_pool_data().all().transactions_iter().map(|tx| *tx.hash()).collect()
@@ -1088,7 +1254,9 @@ where
         }
         let removed = self.pool.write().remove_transactions(hashes);
 
-        self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.with_event_listener(|listener| {
+            listener.discarded_many(&removed, DiscardReason::Removed)
+        });
 
         removed
     }
@@ -1106,7 +1274,7 @@ where
 
         self.with_event_listener(|listener| {
             for tx in &removed {
-                listener.discarded(tx.hash());
+                listener.discarded(tx.hash(), DiscardReason::Removed);
             }
         });
 
@@ -1121,7 +1289,9 @@ where
         let Some(sender_id) = self.sender_id(&sender) else { return Vec::new() };
         let removed = self.pool.write().remove_transactions_by_sender(sender_id);
 
-        self.with_event_listener(|listener| listener.discarded_many(&removed));
+        self.with_event_listener(|listener| {
+            listener.discarded_many(&removed, DiscardReason::Removed)
+        });
 
         removed
     }
@@ -1158,6 +1328,11 @@ where
         self.get_pool_data().get(tx_hash)
     }
 
+    /// Returns the current state of the transaction, if it is in the pool.
+    pub fn transaction_state(&self, tx_hash: &TxHash) -> Option<PoolTransactionState> {
+        self.get_pool_data().transaction_state(tx_hash)
+    }
+
     /// Returns all transactions of the address
     pub fn get_transactions_by_sender(
         &self,
@@ -1579,6 +1754,35 @@ pub enum QueuedReason {
     InsufficientBlobFee,
 }
 
+impl QueuedReason {
+    /// Returns the name of the reason.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::NonceGap => "nonceGap",
+            Self::ParkedAncestors => "parkedAncestors",
+            Self::InsufficientBalance => "insufficientBalance",
+            Self::TooMuchGas => "tooMuchGas",
+            Self::InsufficientBaseFee => "insufficientBaseFee",
+            Self::InsufficientBlobFee => "insufficientBlobFee",
+        }
+    }
+}
+
+/// The current state of a transaction in the pool.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PoolTransactionState {
+    /// The sub-pool that contains the transaction.
+    pub subpool: SubPool,
+    /// Why the transaction is not pending, if it isn't.
+    pub queued_reason: Option<QueuedReason>,
+    /// The lowest nonce of the sender that is missing before the transaction, if there is a
+    /// nonce gap.
+    pub missing_nonce: Option<u64>,
+    /// The amount the sender's balance is short of covering this transaction and all of its
+    /// ancestors.
+    pub missing_balance: Option<U256>,
+}
+
 /// The state of a transaction when is was added to the pool
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum AddedTransactionState {
@@ -1675,12 +1879,13 @@ mod tests {
     use crate::{
         blobstore::{BlobStore, InMemoryBlobStore},
         identifier::SenderId,
+        pool::{state::SubPool, PrivateTransactionStatus, QueuedReason},
         test_utils::{MockTransaction, TestPoolBuilder},
         validate::ValidTransaction,
         BlockInfo, PoolConfig, SubPoolLimit, TransactionOrigin, TransactionValidationOutcome, U256,
     };
     use alloy_eips::{eip4844::BlobTransactionSidecar, eip7594::BlobTransactionSidecarVariant};
-    use alloy_primitives::Address;
+    use alloy_primitives::{Address, TxHash, B256};
     use std::{fs, path::PathBuf};
 
     #[test]
@@ -1807,4 +2012,135 @@ mod tests {
         assert!(test_pool.remove_transactions_by_sender(sender).is_empty());
         assert_eq!(test_pool.sender_id(&sender), None);
     }
+
+    #[test]
+    fn transaction_state_reports_nonce_gap() {
+        let test_pool = &TestPoolBuilder::default().with_config(Default::default()).pool;
+        let tx = MockTransaction::eip1559().with_nonce(2);
+        let hash = *tx.get_hash();
+
+        test_pool.add_transactions(
+            TransactionOrigin::External,
+            [TransactionValidationOutcome::Valid {
+                balance: U256::MAX,
+                state_nonce: 0,
+                bytecode_hash: None,
+                transaction: ValidTransaction::Valid(tx),
+                propagate: true,
+                authorities: None,
+            }],
+        );
+
+        let state = test_pool.transaction_state(&hash).unwrap();
+        assert_eq!(state.subpool, SubPool::Queued);
+        assert_eq!(state.queued_reason, Some(QueuedReason::NonceGap));
+        assert_eq!(state.missing_nonce, Some(0));
+        assert_eq!(state.missing_balance, None);
+        assert!(test_pool.transaction_state(&TxHash::random()).is_none());
+    }
+
+    #[test]
+    fn transactions_page_groups_senders() {
+        let test_pool = &TestPoolBuilder::default().with_config(Default::default()).pool;
+        let senders = [Address::random(), Address::random(), Address::random()];
+        let txs = senders.iter().flat_map(|sender| {
+            (0..2).map(|nonce| MockTransaction::eip1559().with_sender(*sender).with_nonce(nonce))
+        });
+
+        test_pool.add_transactions(
+            TransactionOrigin::External,
+            txs.map(|tx| TransactionValidationOutcome::Valid {
+                balance: U256::MAX,
+                state_nonce: 0,
+                bytecode_hash: None,
+                transaction: ValidTransaction::Valid(tx),
+                propagate: true,
+                authorities: None,
+            }),
+        );
+
+        let page = test_pool.transactions_page(1, 1);
+        assert_eq!(page.pending.len(), 2);
+        assert!(page.pending.iter().all(|tx| tx.sender() == senders[1]));
+        assert!(page.queued.is_empty());
+
+        assert_eq!(test_pool.transactions_page(0, 2).pending.len(), 4);
+        assert_eq!(test_pool.transactions_page(2, usize::MAX).pending.len(), 2);
+        assert!(test_pool.transactions_page(3, 1).pending.is_empty());
+    }
+
+    #[test]
+    fn private_transactions_are_not_propagated() {
+        let test_pool = &TestPoolBuilder::default().with_config(Default::default()).pool;
+        let tx = MockTransaction::eip1559();
+        let hash = *tx.get_hash();
+
+        test_pool
+            .add_private_transaction(
+                TransactionValidationOutcome::Valid {
+                    balance: U256::from(1_000),
+                    state_nonce: 0,
+                    bytecode_hash: None,
+                    transaction: ValidTransaction::Valid(tx),
+                    // overridden by the pool
+                    propagate: true,
+                    authorities: None,
+                },
+                Some(10),
+            )
+            .unwrap();
+
+        assert!(!test_pool.get(&hash).unwrap().propagate);
+        assert!(test_pool.pooled_transactions_hashes().is_empty());
+        assert_eq!(
+            test_pool.private_transaction_status(&hash),
+            Some(PrivateTransactionStatus::Pending { max_block_number: Some(10) })
+        );
+
+        assert!(test_pool.cancel_private_transaction(hash));
+        assert!(test_pool.get(&hash).is_none());
+        assert_eq!(
+            test_pool.private_transaction_status(&hash),
+            Some(PrivateTransactionStatus::Cancelled)
+        );
+    }
+
+    #[test]
+    fn reorged_private_transactions_stay_private() {
+        let test_pool = &TestPoolBuilder::default().with_config(Default::default()).pool;
+        let tx = MockTransaction::eip1559();
+        let hash = *tx.get_hash();
+        let outcome = || TransactionValidationOutcome::Valid {
+            balance: U256::from(1_000),
+            state_nonce: 0,
+            bytecode_hash: None,
+            transaction: ValidTransaction::Valid(tx.clone()),
+            propagate: true,
+            authorities: None,
+        };
+
+        test_pool.add_private_transaction(outcome(), None).unwrap();
+
+        // mined
+        test_pool.remove_transactions(vec![hash]);
+        test_pool.private_transactions.write().on_mined(&[hash], 1, B256::random());
+        assert!(matches!(
+            test_pool.private_transaction_status(&hash),
+            Some(PrivateTransactionStatus::Included { .. })
+        ));
+
+        // reorged out and re-injected as external transaction
+        test_pool
+            .add_transactions(TransactionOrigin::External, [outcome()])
+            .pop()
+            .unwrap()
+            .unwrap();
+        let pooled = test_pool.get(&hash).unwrap();
+        assert!(pooled.origin.is_private());
+        assert!(!pooled.propagate);
+        assert_eq!(
+            test_pool.private_transaction_status(&hash),
+            Some(PrivateTransactionStatus::Pending { max_block_number: None })
+        );
+    }
 }
@@ -0,0 +1,263 @@
+//! Tracking of private transactions.
+//!
+//! Private transactions are inserted with [`TransactionOrigin::Private`](crate::TransactionOrigin)
+//! and are never propagated to peers. They can optionally carry a max block number after which they
+//! are evicted from the pool, and their inclusion status can be queried until some time after they
+//! left the pool.
+
+use alloy_primitives::{map::HashMap, TxHash, B256};
+use schnellru::{ByLength, LruMap};
+
+/// How many finished private transactions to remember the status of.
+pub const DEFAULT_MAX_FINISHED_PRIVATE_TRANSACTIONS: u32 = 10_000;
+
+/// The status of a transaction that was submitted as private.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "status", rename_all = "camelCase"))]
+pub enum PrivateTransactionStatus {
+    /// The transaction is in the pool, waiting to be included.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Pending {
+        /// The last block the transaction may be included in.
+        max_block_number: Option<u64>,
+    },
+    /// The transaction was included in a canonical block.
+    ///
+    /// If the block is reorged out, the transaction is re-injected into the pool and becomes
+    /// [`PrivateTransactionStatus::Pending`] again.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Included {
+        /// Number of the block that included the transaction.
+        block_number: u64,
+        /// Hash of the block that included the transaction.
+        block_hash: B256,
+    },
+    /// The transaction was not included before its max block number and was evicted.
+    #[cfg_attr(feature = "serde", serde(rename_all = "camelCase"))]
+    Expired {
+        /// The block at which the transaction was evicted.
+        block_number: u64,
+    },
+    /// The transaction was cancelled by the submitter.
+    Cancelled,
+    /// The transaction left the pool for other reasons, e.g. it was replaced or became invalid.
+    Dropped,
+}
+
+impl PrivateTransactionStatus {
+    /// Returns `true` if the transaction is still in the pool.
+    pub const fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending { .. })
+    }
+}
+
+/// Keeps track of private transactions and their expiry.
+#[derive(Debug)]
+pub(crate) struct PrivateTransactions {
+    /// Private transactions currently in the pool and their max block number.
+    pending: HashMap<TxHash, Option<u64>>,
+    /// Final status of private transactions that left the pool and their max block number.
+    finished: LruMap<TxHash, (PrivateTransactionStatus, Option<u64>)>,
+}
+
+impl PrivateTransactions {
+    /// Creates a new tracker that remembers the given number of finished transactions.
+    pub(crate) fn new(max_finished: u32) -> Self {
+        Self { pending: Default::default(), finished: LruMap::new(ByLength::new(max_finished)) }
+    }
+
+    /// Starts tracking a private transaction that was added to the pool.
+    pub(crate) fn insert(&mut self, hash: TxHash, max_block_number: Option<u64>) {
+        self.finished.remove(&hash);
+        self.pending.insert(hash, max_block_number);
+    }
+
+    /// Returns whether the transaction is a tracked, pending private transaction.
+    pub(crate) fn is_pending(&self, hash: &TxHash) -> bool {
+        self.pending.contains_key(hash)
+    }
+
+    /// Marks the pending private transaction as dropped.
+    ///
+    /// Returns `false` if the transaction is not pending.
+    pub(crate) fn drop_pending(&mut self, hash: TxHash) -> bool {
+        self.finish(hash, PrivateTransactionStatus::Dropped)
+    }
+
+    /// Marks the pending private transaction as cancelled.
+    ///
+    /// Returns `false` if the transaction is not pending.
+    pub(crate) fn cancel(&mut self, hash: TxHash) -> bool {
+        self.finish(hash, PrivateTransactionStatus::Cancelled)
+    }
+
+    /// Marks all private transactions of the given hashes as included in the given block.
+    ///
+    /// This also updates the block of transactions that were included before, e.g. when they are
+    /// included again after a reorg.
+    pub(crate) fn on_mined<'a>(
+        &mut self,
+        mined: impl IntoIterator<Item = &'a TxHash>,
+        block_number: u64,
+        block_hash: B256,
+    ) {
+        if self.pending.is_empty() && self.finished.is_empty() {
+            return
+        }
+        let included = PrivateTransactionStatus::Included { block_number, block_hash };
+        for hash in mined {
+            if self.finish(*hash, included) {
+                continue
+            }
+            if let Some((status, _)) = self.finished.get(hash) &&
+                matches!(status, PrivateTransactionStatus::Included { .. })
+            {
+                *status = included;
+            }
+        }
+    }
+
+    /// Takes an included private transaction whose block was reorged out and returns its max
+    /// block number.
+    ///
+    /// Returns `None` if the transaction is not an included private transaction.
+    pub(crate) fn take_reorged(&mut self, hash: &TxHash) -> Option<Option<u64>> {
+        match self.finished.peek(hash) {
+            Some((PrivateTransactionStatus::Included { .. }, _)) => {
+                self.finished.remove(hash).map(|(_, max_block_number)| max_block_number)
+            }
+            _ => None,
+        }
+    }
+
+    /// Marks a private transaction that was taken with [`Self::take_reorged`] but could not be
+    /// re-injected into the pool as dropped.
+    pub(crate) fn drop_reorged(&mut self, hash: TxHash, max_block_number: Option<u64>) {
+        self.finished.insert(hash, (PrivateTransactionStatus::Dropped, max_block_number));
+    }
+
+    /// Marks all pending private transactions that can no longer be included in blocks after
+    /// `block_number` as expired and returns their hashes.
+    pub(crate) fn expire(&mut self, block_number: u64) -> Vec<TxHash> {
+        let expired = self
+            .pending
+            .iter()
+            .filter(|(_, max)| max.is_some_and(|max| max <= block_number))
+            .map(|(hash, _)| *hash)
+            .collect::<Vec<_>>();
+        for hash in &expired {
+            self.finish(*hash, PrivateTransactionStatus::Expired { block_number });
+        }
+        expired
+    }
+
+    /// Marks all pending private transactions for which `in_pool` returns `false` as dropped.
+    pub(crate) fn retain_in_pool(&mut self, mut in_pool: impl FnMut(&TxHash) -> bool) {
+        let dropped =
+            self.pending.keys().filter(|hash| !in_pool(hash)).copied().collect::<Vec<_>>();
+        for hash in dropped {
+            self.finish(hash, PrivateTransactionStatus::Dropped);
+        }
+    }
+
+    /// Returns the status of the private transaction, if it is known.
+    pub(crate) fn status(&self, hash: &TxHash) -> Option<PrivateTransactionStatus> {
+        if let Some(max_block_number) = self.pending.get(hash) {
+            return Some(PrivateTransactionStatus::Pending { max_block_number: *max_block_number })
+        }
+        self.finished.peek(hash).map(|(status, _)| *status)
+    }
+
+    /// Moves a pending transaction to the finished set with the given status.
+    fn finish(&mut self, hash: TxHash, status: PrivateTransactionStatus) -> bool {
+        let Some(max_block_number) = self.pending.remove(&hash) else { return false };
+        self.finished.insert(hash, (status, max_block_number));
+        true
+    }
+}
+
+impl Default for PrivateTransactions {
+    fn default() -> Self {
+        Self::new(DEFAULT_MAX_FINISHED_PRIVATE_TRANSACTIONS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn private_transaction_lifecycle() {
+        let mut private = PrivateTransactions::default();
+        let (a, b, c, d) = (B256::random(), B256::random(), B256::random(), B256::random());
+
+        private.insert(a, Some(10));
+        private.insert(b, None);
+        private.insert(c, Some(12));
+        private.insert(d, None);
+        assert_eq!(
+            private.status(&a),
+            Some(PrivateTransactionStatus::Pending { max_block_number: Some(10) })
+        );
+
+        let block_hash = B256::random();
+        private.on_mined(&[b], 9, block_hash);
+        assert_eq!(
+            private.status(&b),
+            Some(PrivateTransactionStatus::Included { block_number: 9, block_hash })
+        );
+
+        assert_eq!(private.expire(10), vec![a]);
+        assert_eq!(
+            private.status(&a),
+            Some(PrivateTransactionStatus::Expired { block_number: 10 })
+        );
+
+        assert!(private.cancel(c));
+        assert!(!private.cancel(c));
+        assert_eq!(private.status(&c), Some(PrivateTransactionStatus::Cancelled));
+
+        private.retain_in_pool(|_| false);
+        assert_eq!(private.status(&d), Some(PrivateTransactionStatus::Dropped));
+        assert_eq!(private.status(&B256::random()), None);
+    }
+
+    #[test]
+    fn private_transaction_reorg() {
+        let mut private = PrivateTransactions::default();
+        let (a, b) = (B256::random(), B256::random());
+        private.insert(a, Some(20));
+        private.insert(b, None);
+
+        private.on_mined(&[a, b], 9, B256::random());
+
+        // `b` was included again in the new chain
+        let block_hash = B256::random();
+        private.on_mined(&[b], 9, block_hash);
+        assert_eq!(
+            private.status(&b),
+            Some(PrivateTransactionStatus::Included { block_number: 9, block_hash })
+        );
+
+        // `a` was reorged out and is re-injected
+        assert_eq!(private.take_reorged(&a), Some(Some(20)));
+        assert_eq!(private.take_reorged(&a), None);
+        private.insert(a, Some(20));
+        assert_eq!(
+            private.status(&a),
+            Some(PrivateTransactionStatus::Pending { max_block_number: Some(20) })
+        );
+        assert!(private.cancel(a));
+        assert_eq!(private.take_reorged(&a), None);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn status_serde() {
+        let status = PrivateTransactionStatus::Expired { block_number: 1 };
+        let json = serde_json::to_string(&status).unwrap();
+        assert_eq!(json, r#"{"status":"expired","blockNumber":1}"#);
+        assert_eq!(serde_json::from_str::<PrivateTransactionStatus>(&json).unwrap(), status);
+    }
+}
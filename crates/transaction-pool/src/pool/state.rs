@@ -187,6 +187,16 @@ impl SubPool {
     pub fn is_promoted(&self, other: Self) -> bool {
         self > &other
     }
+
+    /// Returns the name of the sub-pool.
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Self::Queued => "queued",
+            Self::BaseFee => "basefee",
+            Self::Blob => "blob",
+            Self::Pending => "pending",
+        }
+    }
 }
 
 impl From<TxState> for SubPool {
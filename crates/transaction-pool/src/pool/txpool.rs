@@ -1,7 +1,7 @@
 //! The internal transaction pool implementation.
 
 use crate::{
-    config::{LocalTransactionConfig, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
+    config::{LocalTransactionConfig, PoolLimits, TXPOOL_MAX_ACCOUNT_SLOTS_PER_SENDER},
     error::{
         Eip4844PoolTransactionError, Eip7702PoolTransactionError, InvalidPoolTransactionError,
         PoolError, PoolErrorKind,
@@ -16,8 +16,9 @@ use crate::{
         state::{SubPool, TxState},
         update::{Destination, PoolUpdate, UpdateOutcome},
         AddedPendingTransaction, AddedTransaction, OnNewCanonicalStateOutcome,
+        PoolTransactionState,
     },
-    traits::{BestTransactionsAttributes, BlockInfo, PoolSize},
+    traits::{AllPoolTransactions, BestTransactionsAttributes, BlockInfo, PoolSize},
     PoolConfig, PoolResult, PoolTransaction, PoolUpdateKind, PriceBumpConfig, TransactionOrdering,
     ValidPoolTransaction, U256,
 };
@@ -143,6 +144,19 @@ impl<T: TransactionOrdering> TxPool<T> {
         }
     }
 
+    /// Returns the size limits the pool currently enforces.
+    pub const fn limits(&self) -> PoolLimits {
+        self.config.limits()
+    }
+
+    /// Replaces the size limits of the pool.
+    ///
+    /// Lowered sub-pool limits are enforced by the next [`Self::discard_worst`] call.
+    pub fn set_limits(&mut self, limits: PoolLimits) {
+        self.config = self.config.clone().with_limits(limits);
+        self.all_transactions.max_account_slots = limits.max_account_slots;
+    }
+
     /// Retrieves the highest nonce for a specific sender from the transaction pool.
     pub fn get_highest_nonce_by_sender(&self, sender: SenderId) -> Option<u64> {
         self.all().txs_iter(sender).last().map(|(_, tx)| tx.transaction.nonce())
@@ -504,6 +518,46 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.basefee_pool.all().chain(self.queued_pool.all()).collect()
     }
 
+    /// Returns the pending and queued transactions of at most `limit` senders, skipping the first
+    /// `offset` senders.
+    ///
+    /// Senders are ordered by their [`SenderId`], i.e. in the order the pool first saw them, so
+    /// pages stay stable while new senders are added. Only the senders on the page are visited.
+    pub(crate) fn transactions_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> AllPoolTransactions<T::Transaction> {
+        let txs = &self.all_transactions.txs;
+        let mut page = AllPoolTransactions::default();
+        let mut next_sender = txs.keys().next().map(|id| id.sender);
+        let mut index = 0usize;
+
+        while let Some(sender) = next_sender {
+            if index >= offset.saturating_add(limit) {
+                break
+            }
+            if index >= offset {
+                for (_, tx) in txs.range(sender.range()) {
+                    match tx.subpool {
+                        SubPool::Pending => page.pending.push(Arc::clone(&tx.transaction)),
+                        SubPool::Queued | SubPool::BaseFee => {
+                            page.queued.push(Arc::clone(&tx.transaction))
+                        }
+                        SubPool::Blob => {}
+                    }
+                }
+            }
+            index += 1;
+            next_sender = txs
+                .range((Excluded(sender.into_transaction_id(u64::MAX)), Unbounded))
+                .next()
+                .map(|(id, _)| id.sender);
+        }
+
+        page
+    }
+
     /// Returns the number of transactions in parked pools
     pub(crate) fn queued_transactions_count(&self) -> usize {
         self.basefee_pool.len() + self.queued_pool.len()
@@ -557,6 +611,30 @@ impl<T: TransactionOrdering> TxPool<T> {
         self.all_transactions.by_hash.get(tx_hash).cloned()
     }
 
+    /// Returns the current state of the transaction with the given hash, if it is in the pool.
+    pub(crate) fn transaction_state(&self, tx_hash: &TxHash) -> Option<PoolTransactionState> {
+        let id = self.all_transactions.by_hash.get(tx_hash)?.transaction_id;
+        let tx = self.all_transactions.txs.get(&id)?;
+        let info = self.all_transactions.sender_info.get(&id.sender).cloned().unwrap_or_default();
+
+        let mut next_nonce = info.state_nonce;
+        for (ancestor, _) in self.all_transactions.txs_iter(id.sender) {
+            if ancestor.nonce >= id.nonce || ancestor.nonce > next_nonce {
+                break
+            }
+            next_nonce = ancestor.nonce + 1;
+        }
+
+        let missing_balance = tx.next_cumulative_cost().saturating_sub(info.balance);
+
+        Some(PoolTransactionState {
+            subpool: tx.subpool,
+            queued_reason: tx.state.determine_queued_reason(tx.subpool),
+            missing_nonce: (next_nonce < id.nonce).then_some(next_nonce),
+            missing_balance: (!missing_balance.is_zero()).then_some(missing_balance),
+        })
+    }
+
     /// Returns transactions for the multiple given hashes, if they exist.
     pub(crate) fn get_all(
         &self,
@@ -0,0 +1,810 @@
+//! Recording and replaying of transaction pool traffic.
+//!
+//! [`PoolEventRecorder`] writes the ordered stream of [`FullTransactionEvent`]s of a running pool,
+//! together with the chain head updates the pool observed, to a file with one JSON encoded
+//! [`PoolEventRecord`] per line.
+//!
+//! [`PoolReplay`] drives a fresh pool with such a recording and collects a [`ReplayReport`] with
+//! insertion latencies, sub-pool sizes at every head update and the eviction decisions of the
+//! replayed pool compared to the recorded ones. This makes it possible to tune a
+//! [`PoolConfig`](crate::PoolConfig) offline against real traffic.
+//!
+//! The replay runs as fast as possible, it does not reproduce the timing of the recording.
+
+use crate::{
+    traits::{BlockInfo, PoolSize},
+    validate::ValidTransaction,
+    DiscardReason, FullTransactionEvent, PoolTransaction, TransactionOrigin, TransactionPool,
+    TransactionPoolExt, TransactionValidationOutcome, TransactionValidator,
+};
+use alloy_eips::eip2718::{Decodable2718, Encodable2718};
+use alloy_primitives::{
+    map::{HashMap, HashSet},
+    Address, Bytes, TxHash, B256, U256,
+};
+use futures_util::StreamExt;
+use parking_lot::Mutex;
+use reth_ethereum_primitives::Block;
+use reth_execution_types::ChangedAccount;
+use reth_primitives_traits::Recovered;
+use reth_tasks::shutdown::GracefulShutdown;
+use serde::{Deserialize, Serialize};
+use std::{
+    fmt,
+    io::{self, BufRead, Write},
+    marker::PhantomData,
+    pin::pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+/// A single entry of a pool recording.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PoolEventRecord {
+    /// Microseconds since the recording started.
+    pub elapsed_micros: u64,
+    /// The recorded event.
+    pub event: RecordedPoolEvent,
+}
+
+/// An event that was recorded by [`PoolEventRecorder`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum RecordedPoolEvent {
+    /// A transaction was added to the pool.
+    #[serde(rename_all = "camelCase")]
+    Added {
+        /// Hash of the transaction.
+        hash: TxHash,
+        /// Origin the transaction was submitted with.
+        origin: TransactionOrigin,
+        /// Recovered sender of the transaction.
+        sender: Address,
+        /// Nonce of the transaction.
+        nonce: u64,
+        /// Whether the transaction was added to the pending sub-pool.
+        pending: bool,
+        /// Whether this is a blob transaction, which is recorded without its sidecar and can't be
+        /// replayed.
+        #[serde(default)]
+        blob: bool,
+        /// EIP-2718 encoded consensus transaction.
+        transaction: Bytes,
+    },
+    /// A transaction was replaced by another transaction of the same sender and nonce.
+    #[serde(rename_all = "camelCase")]
+    Replaced {
+        /// Hash of the replaced transaction.
+        hash: TxHash,
+        /// Hash of the replacement.
+        replaced_by: TxHash,
+    },
+    /// A transaction was discarded, e.g. evicted because of pool limits.
+    Discarded {
+        /// Hash of the discarded transaction.
+        hash: TxHash,
+        /// Why the transaction was discarded, `None` in recordings that predate it.
+        #[serde(default)]
+        reason: Option<DiscardReason>,
+    },
+    /// A transaction became invalid.
+    Invalid {
+        /// Hash of the invalid transaction.
+        hash: TxHash,
+    },
+    /// A transaction was mined.
+    #[serde(rename_all = "camelCase")]
+    Mined {
+        /// Hash of the mined transaction.
+        hash: TxHash,
+        /// Hash of the block that included the transaction.
+        block_hash: B256,
+    },
+    /// The pool started tracking a new head block.
+    #[serde(rename_all = "camelCase")]
+    Head {
+        /// Number of the new head block.
+        block_number: u64,
+        /// Hash of the new head block.
+        block_hash: B256,
+        /// Gas limit of the new head block.
+        block_gas_limit: u64,
+        /// Base fee of the next block.
+        pending_basefee: u64,
+        /// Blob fee of the next block.
+        pending_blob_fee: Option<u128>,
+    },
+}
+
+impl RecordedPoolEvent {
+    /// Returns the head event for the given block info.
+    pub const fn head(info: &BlockInfo) -> Self {
+        Self::Head {
+            block_number: info.last_seen_block_number,
+            block_hash: info.last_seen_block_hash,
+            block_gas_limit: info.block_gas_limit,
+            pending_basefee: info.pending_basefee,
+            pending_blob_fee: info.pending_blob_fee,
+        }
+    }
+}
+
+/// Records the events of a pool as JSON lines.
+///
+/// Transactions are recorded in full the first time they are announced as pending or queued,
+/// subsequent events only reference the transaction hash.
+#[derive(Debug)]
+pub struct PoolEventRecorder<W> {
+    /// Where records are written to.
+    writer: W,
+    /// When the recording started.
+    started: Instant,
+    /// Transactions that are recorded and still in the pool.
+    recorded: HashSet<TxHash>,
+    /// The last recorded head block.
+    last_head: Option<B256>,
+}
+
+impl<W: Write> PoolEventRecorder<W> {
+    /// Creates a new recorder that writes to the given writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer, started: Instant::now(), recorded: Default::default(), last_head: None }
+    }
+
+    /// Records a head update if the head differs from the last recorded one.
+    pub fn record_head(&mut self, info: &BlockInfo) -> io::Result<()> {
+        if self.last_head == Some(info.last_seen_block_hash) {
+            return Ok(())
+        }
+        self.last_head = Some(info.last_seen_block_hash);
+        self.write(RecordedPoolEvent::head(info))
+    }
+
+    /// Records the given event that was emitted by the pool.
+    ///
+    /// The pool is used to look up the full transaction of new transactions and to detect head
+    /// updates. Transactions that already left the pool when their event is processed are not
+    /// recorded.
+    pub fn record_event<P: TransactionPool>(
+        &mut self,
+        pool: &P,
+        event: &FullTransactionEvent<P::Transaction>,
+    ) -> io::Result<()> {
+        self.record_head(&pool.block_info())?;
+
+        let event = match event {
+            FullTransactionEvent::Pending(hash) | FullTransactionEvent::Queued(hash, _) => {
+                // promotions and demotions are not recorded, the replayed pool is expected to
+                // make these decisions itself
+                if self.recorded.contains(hash) {
+                    return Ok(())
+                }
+                let Some(tx) = pool.get(hash) else { return Ok(()) };
+                self.recorded.insert(*hash);
+                RecordedPoolEvent::Added {
+                    hash: *hash,
+                    origin: tx.origin,
+                    sender: tx.sender(),
+                    nonce: tx.nonce(),
+                    pending: matches!(event, FullTransactionEvent::Pending(_)),
+                    blob: tx.is_eip4844(),
+                    transaction: tx
+                        .transaction
+                        .clone_into_consensus()
+                        .into_inner()
+                        .encoded_2718()
+                        .into(),
+                }
+            }
+            FullTransactionEvent::Replaced { transaction, replaced_by } => {
+                let hash = *transaction.hash();
+                if !self.recorded.remove(&hash) {
+                    return Ok(())
+                }
+                RecordedPoolEvent::Replaced { hash, replaced_by: *replaced_by }
+            }
+            FullTransactionEvent::Discarded { tx_hash: hash, reason } => {
+                if !self.recorded.remove(hash) {
+                    return Ok(())
+                }
+                RecordedPoolEvent::Discarded { hash: *hash, reason: Some(*reason) }
+            }
+            FullTransactionEvent::Invalid(hash) => {
+                if !self.recorded.remove(hash) {
+                    return Ok(())
+                }
+                RecordedPoolEvent::Invalid { hash: *hash }
+            }
+            FullTransactionEvent::Mined { tx_hash, block_hash } => {
+                if !self.recorded.remove(tx_hash) {
+                    return Ok(())
+                }
+                RecordedPoolEvent::Mined { hash: *tx_hash, block_hash: *block_hash }
+            }
+            FullTransactionEvent::Propagated(_) => return Ok(()),
+        };
+        self.write(event)
+    }
+
+    /// Records all events of the pool until the pool is dropped.
+    ///
+    /// Returns the writer once the event stream ended.
+    pub async fn record<P: TransactionPool>(mut self, pool: P) -> io::Result<W> {
+        let mut events = pool.all_transactions_event_listener();
+        self.record_head(&pool.block_info())?;
+        while let Some(event) = events.next().await {
+            self.record_event(&pool, &event)?;
+        }
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Records all events of the pool until the pool is dropped or the node shuts down.
+    ///
+    /// The writer is flushed before the graceful shutdown completes.
+    pub async fn record_with_graceful_shutdown<P: TransactionPool>(
+        mut self,
+        pool: P,
+        shutdown: GracefulShutdown,
+    ) -> io::Result<W> {
+        let mut events = pool.all_transactions_event_listener();
+        let mut shutdown = pin!(shutdown);
+        self.record_head(&pool.block_info())?;
+        let guard = loop {
+            tokio::select! {
+                event = events.next() => match event {
+                    Some(event) => self.record_event(&pool, &event)?,
+                    None => break None,
+                },
+                guard = &mut shutdown => break Some(guard),
+            }
+        };
+        self.writer.flush()?;
+        drop(guard);
+        Ok(self.writer)
+    }
+
+    /// Flushes the writer.
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Consumes the recorder and returns the writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn write(&mut self, event: RecordedPoolEvent) -> io::Result<()> {
+        let record =
+            PoolEventRecord { elapsed_micros: self.started.elapsed().as_micros() as u64, event };
+        serde_json::to_writer(&mut self.writer, &record)?;
+        self.writer.write_all(b"\n")
+    }
+}
+
+/// Reads the records written by a [`PoolEventRecorder`].
+pub fn read_records<R: BufRead>(reader: R) -> impl Iterator<Item = io::Result<PoolEventRecord>> {
+    reader.lines().filter_map(|line| match line {
+        Ok(line) if line.trim().is_empty() => None,
+        Ok(line) => Some(serde_json::from_str(&line).map_err(io::Error::from)),
+        Err(err) => Some(Err(err)),
+    })
+}
+
+/// Drives a pool with a recording of another pool.
+///
+/// Recorded transactions are inserted with their recorded origin. Mined transactions are removed
+/// from the pool and the nonce of their sender is advanced, the balance of senders is assumed to
+/// be sufficient. Transactions that were explicitly removed from the recorded pool, e.g. via RPC,
+/// are removed as well. Recorded evictions and invalidations are not replayed, the replayed pool
+/// is expected to make these decisions itself.
+///
+/// Blob transactions are recorded without their sidecar, so they are counted but not replayed.
+///
+/// For realistic results the pool should use a [`ReplayValidator`] that shares its
+/// [`ReplayNonces`] with the replay, see [`PoolReplay::with_nonces`].
+#[derive(Debug)]
+pub struct PoolReplay<P> {
+    pool: P,
+    nonces: ReplayNonces,
+}
+
+impl<P: TransactionPoolExt> PoolReplay<P> {
+    /// Creates a new replay that drives the given pool.
+    pub fn new(pool: P) -> Self {
+        Self { pool, nonces: Default::default() }
+    }
+
+    /// Sets the nonces of the [`ReplayValidator`] of the pool, which are advanced when recorded
+    /// transactions are mined.
+    pub fn with_nonces(mut self, nonces: ReplayNonces) -> Self {
+        self.nonces = nonces;
+        self
+    }
+
+    /// Returns the replayed pool.
+    pub const fn pool(&self) -> &P {
+        &self.pool
+    }
+
+    /// Replays the records in order and returns the report.
+    pub async fn run(&self, records: impl IntoIterator<Item = PoolEventRecord>) -> ReplayReport {
+        let mut events = self.pool.all_transactions_event_listener();
+        let mut report = ReplayReport::default();
+        let mut state = ReplayState::default();
+        let mut mined = Vec::new();
+
+        for record in records {
+            if !matches!(record.event, RecordedPoolEvent::Mined { .. }) && !mined.is_empty() {
+                self.apply_mined(&mut state, std::mem::take(&mut mined));
+            }
+
+            match record.event {
+                RecordedPoolEvent::Added {
+                    hash,
+                    origin,
+                    sender,
+                    nonce,
+                    pending,
+                    blob,
+                    transaction,
+                } => {
+                    if blob {
+                        report.blob += 1;
+                        continue
+                    }
+                    let Some(tx) = decode_transaction::<P::Transaction>(&transaction, sender)
+                    else {
+                        report.skipped += 1;
+                        continue
+                    };
+                    let start = Instant::now();
+                    let res = self.pool.add_transaction(origin, tx).await;
+                    report.insert_latency.record(start.elapsed());
+                    match res {
+                        Ok(outcome) => {
+                            report.added += 1;
+                            report.placement.record(pending, !outcome.state.is_queued());
+                            state.added(hash, outcome.hash, sender, nonce);
+                        }
+                        Err(_) => report.rejected += 1,
+                    }
+                }
+                RecordedPoolEvent::Head {
+                    block_number,
+                    block_hash,
+                    block_gas_limit,
+                    pending_basefee,
+                    pending_blob_fee,
+                } => {
+                    let start = Instant::now();
+                    self.pool.set_block_info(BlockInfo {
+                        last_seen_block_hash: block_hash,
+                        last_seen_block_number: block_number,
+                        block_gas_limit,
+                        pending_basefee,
+                        pending_blob_fee,
+                    });
+                    report.head_update_latency.record(start.elapsed());
+                    report.sizes.push(SubPoolSizes::new(block_number, self.pool.pool_size()));
+                }
+                RecordedPoolEvent::Mined { hash, .. } => mined.push(hash),
+                RecordedPoolEvent::Discarded { hash, reason: Some(DiscardReason::Removed) } => {
+                    if let Some((replayed, ..)) = state.transactions.get(&hash) {
+                        self.pool.remove_transactions(vec![*replayed]);
+                        report.removed += 1;
+                    }
+                }
+                RecordedPoolEvent::Discarded { hash, .. } => {
+                    if state.transactions.contains_key(&hash) {
+                        report.evictions.recorded.insert(hash);
+                    }
+                }
+                RecordedPoolEvent::Replaced { .. } | RecordedPoolEvent::Invalid { .. } => {}
+            }
+
+            state.drain_discarded(&mut events);
+        }
+
+        if !mined.is_empty() {
+            self.apply_mined(&mut state, mined);
+        }
+        state.drain_discarded(&mut events);
+
+        report.evictions.replayed = state.discarded;
+        report.final_size = self.pool.pool_size();
+        report
+    }
+
+    /// Removes the mined transactions from the pool and advances the nonces of their senders.
+    fn apply_mined(&self, state: &mut ReplayState, mined: Vec<TxHash>) {
+        let mut accounts = HashMap::<Address, u64>::default();
+        let mut hashes = Vec::with_capacity(mined.len());
+        for hash in mined {
+            let Some((replayed, sender, nonce)) = state.transactions.get(&hash).copied() else {
+                continue
+            };
+            let next = accounts.entry(sender).or_default();
+            *next = (*next).max(nonce + 1);
+            hashes.push(replayed);
+        }
+
+        self.nonces.advance(accounts.iter().map(|(sender, nonce)| (*sender, *nonce)));
+        self.pool.remove_transactions(hashes);
+        self.pool.update_accounts(
+            accounts
+                .into_iter()
+                .map(|(address, nonce)| ChangedAccount { address, nonce, balance: U256::MAX })
+                .collect(),
+        );
+    }
+}
+
+/// Bookkeeping of a running replay.
+#[derive(Debug, Default)]
+struct ReplayState {
+    /// Recorded hash to replayed hash, sender and nonce of all added transactions.
+    transactions: HashMap<TxHash, (TxHash, Address, u64)>,
+    /// Replayed hash to recorded hash.
+    recorded_hashes: HashMap<TxHash, TxHash>,
+    /// Recorded hashes of transactions the replayed pool discarded.
+    discarded: HashSet<TxHash>,
+}
+
+impl ReplayState {
+    fn added(&mut self, recorded: TxHash, replayed: TxHash, sender: Address, nonce: u64) {
+        self.transactions.insert(recorded, (replayed, sender, nonce));
+        self.recorded_hashes.insert(replayed, recorded);
+    }
+
+    /// Collects all discard events the replayed pool emitted so far.
+    ///
+    /// Transactions the replay removed itself, because they were mined or removed in the
+    /// recording, are not evictions.
+    fn drain_discarded<T: PoolTransaction>(
+        &mut self,
+        events: &mut crate::AllTransactionsEvents<T>,
+    ) {
+        while let Ok(event) = events.events.try_recv() {
+            if let FullTransactionEvent::Discarded { tx_hash: hash, reason } = event &&
+                reason != DiscardReason::Removed &&
+                let Some(recorded) = self.recorded_hashes.get(&hash)
+            {
+                self.discarded.insert(*recorded);
+            }
+        }
+    }
+}
+
+/// Decodes a recorded transaction into a pool transaction.
+///
+/// Returns `None` if the transaction can't be decoded or converted.
+fn decode_transaction<T: PoolTransaction>(encoded: &[u8], sender: Address) -> Option<T> {
+    let tx = T::Consensus::decode_2718(&mut &encoded[..]).ok()?;
+    T::try_from_consensus(Recovered::new_unchecked(tx, sender)).ok()
+}
+
+/// The on-chain nonces of senders during a replay, shared by a [`ReplayValidator`] and
+/// [`PoolReplay`].
+#[derive(Debug, Clone, Default)]
+pub struct ReplayNonces(Arc<Mutex<HashMap<Address, u64>>>);
+
+impl ReplayNonces {
+    /// Returns the nonce of the sender, or initializes it with the given nonce if the sender is
+    /// not known yet.
+    fn get_or_init(&self, sender: Address, nonce: u64) -> u64 {
+        *self.0.lock().entry(sender).or_insert(nonce)
+    }
+
+    /// Sets the on-chain nonce of the sender, e.g. from the state at the start of the recording.
+    pub fn set_nonce(&self, sender: Address, nonce: u64) {
+        self.0.lock().insert(sender, nonce);
+    }
+
+    /// Advances the nonces of the given senders.
+    fn advance(&self, accounts: impl IntoIterator<Item = (Address, u64)>) {
+        let mut nonces = self.0.lock();
+        for (sender, nonce) in accounts {
+            let current = nonces.entry(sender).or_default();
+            *current = (*current).max(nonce);
+        }
+    }
+}
+
+/// A validator for replays that accepts all transactions.
+///
+/// The on-chain nonce of a sender is the nonce of its first replayed transaction and is advanced
+/// when recorded transactions are mined, so that nonce gaps of the recording are preserved. The
+/// balance of senders is assumed to be sufficient.
+#[derive(Debug)]
+pub struct ReplayValidator<T> {
+    nonces: ReplayNonces,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> ReplayValidator<T> {
+    /// Creates a new validator with the given nonces.
+    pub const fn new(nonces: ReplayNonces) -> Self {
+        Self { nonces, _phantom: PhantomData }
+    }
+}
+
+impl<T: PoolTransaction> TransactionValidator for ReplayValidator<T> {
+    type Transaction = T;
+    type Block = Block;
+
+    async fn validate_transaction(
+        &self,
+        _origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> TransactionValidationOutcome<Self::Transaction> {
+        let state_nonce = self.nonces.get_or_init(transaction.sender(), transaction.nonce());
+        let authorities = transaction.authorization_list().map(|auths| {
+            auths.iter().flat_map(|auth| auth.recover_authority()).collect::<Vec<_>>()
+        });
+        TransactionValidationOutcome::Valid {
+            balance: U256::MAX,
+            state_nonce,
+            bytecode_hash: None,
+            transaction: ValidTransaction::Valid(transaction),
+            propagate: false,
+            authorities,
+        }
+    }
+}
+
+/// The outcome of a [`PoolReplay`].
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    /// Number of transactions that were added to the pool.
+    pub added: usize,
+    /// Number of transactions the pool rejected.
+    pub rejected: usize,
+    /// Number of recorded transactions that could not be decoded.
+    pub skipped: usize,
+    /// Number of recorded blob transactions, which are not replayed.
+    pub blob: usize,
+    /// Number of transactions that were removed because they were explicitly removed from the
+    /// recorded pool.
+    pub removed: usize,
+    /// Latency of transaction insertions.
+    pub insert_latency: LatencyHistogram,
+    /// Latency of head updates.
+    pub head_update_latency: LatencyHistogram,
+    /// Sub-pool sizes after every head update.
+    pub sizes: Vec<SubPoolSizes>,
+    /// Size of the pool after the replay.
+    pub final_size: PoolSize,
+    /// Recorded and replayed evictions.
+    pub evictions: Evictions,
+    /// Whether the recorded and the replayed pool agreed on which transactions were pending
+    /// when they were added.
+    pub placement: Placement,
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "added: {}, rejected: {}, skipped: {}, blob (not replayed): {}, removed: {}",
+            self.added, self.rejected, self.skipped, self.blob, self.removed
+        )?;
+        writeln!(f, "insert latency: {}", self.insert_latency)?;
+        writeln!(f, "head update latency: {}", self.head_update_latency)?;
+        writeln!(
+            f,
+            "final size: pending {}, basefee {}, queued {}, blob {}",
+            self.final_size.pending,
+            self.final_size.basefee,
+            self.final_size.queued,
+            self.final_size.blob
+        )?;
+        writeln!(
+            f,
+            "placement: matching {}, pending only in recording {}, pending only in replay {}",
+            self.placement.matching,
+            self.placement.recorded_pending_only,
+            self.placement.replayed_pending_only
+        )?;
+        write!(
+            f,
+            "evictions: recorded {}, replayed {}, in both {}",
+            self.evictions.recorded.len(),
+            self.evictions.replayed.len(),
+            self.evictions.matching().count()
+        )
+    }
+}
+
+/// Compares whether added transactions were pending in the recorded and the replayed pool.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Placement {
+    /// Transactions that were pending in both or queued in both pools.
+    pub matching: usize,
+    /// Transactions that were only pending in the recorded pool.
+    pub recorded_pending_only: usize,
+    /// Transactions that were only pending in the replayed pool.
+    pub replayed_pending_only: usize,
+}
+
+impl Placement {
+    /// Records whether a transaction was pending in the recorded and the replayed pool.
+    pub const fn record(&mut self, recorded_pending: bool, replayed_pending: bool) {
+        match (recorded_pending, replayed_pending) {
+            (true, false) => self.recorded_pending_only += 1,
+            (false, true) => self.replayed_pending_only += 1,
+            _ => self.matching += 1,
+        }
+    }
+
+    /// Returns the number of transactions the pools disagreed on.
+    pub const fn mismatches(&self) -> usize {
+        self.recorded_pending_only + self.replayed_pending_only
+    }
+}
+
+/// Number of transactions per sub-pool at a given block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SubPoolSizes {
+    /// The head block.
+    pub block_number: u64,
+    /// Number of pending transactions.
+    pub pending: usize,
+    /// Number of transactions in the basefee sub-pool.
+    pub basefee: usize,
+    /// Number of queued transactions.
+    pub queued: usize,
+    /// Number of blob transactions.
+    pub blob: usize,
+}
+
+impl SubPoolSizes {
+    /// Creates the sizes from the given pool size.
+    pub const fn new(block_number: u64, size: PoolSize) -> Self {
+        Self {
+            block_number,
+            pending: size.pending,
+            basefee: size.basefee,
+            queued: size.queued,
+            blob: size.blob,
+        }
+    }
+}
+
+/// Transactions that were discarded by the recorded and the replayed pool.
+///
+/// Both sets contain the hashes of the recording, and only transactions that were added to the
+/// replayed pool. Explicit removals are not evictions.
+#[derive(Debug, Clone, Default)]
+pub struct Evictions {
+    /// Transactions discarded by the recorded pool.
+    pub recorded: HashSet<TxHash>,
+    /// Transactions discarded by the replayed pool.
+    pub replayed: HashSet<TxHash>,
+}
+
+impl Evictions {
+    /// Transactions that were discarded by both pools.
+    pub fn matching(&self) -> impl Iterator<Item = &TxHash> {
+        self.recorded.intersection(&self.replayed)
+    }
+
+    /// Transactions that were only discarded by the replayed pool.
+    pub fn replayed_only(&self) -> impl Iterator<Item = &TxHash> {
+        self.replayed.difference(&self.recorded)
+    }
+
+    /// Transactions that were only discarded by the recorded pool.
+    pub fn recorded_only(&self) -> impl Iterator<Item = &TxHash> {
+        self.recorded.difference(&self.replayed)
+    }
+}
+
+/// A histogram of latencies with power of two microsecond buckets.
+#[derive(Debug, Clone, Default)]
+pub struct LatencyHistogram {
+    /// Bucket `i` counts latencies below `2^i` microseconds.
+    buckets: [u64; Self::BUCKETS],
+    count: u64,
+    total: Duration,
+    max: Duration,
+}
+
+impl LatencyHistogram {
+    const BUCKETS: usize = 32;
+
+    /// Records a latency.
+    pub fn record(&mut self, latency: Duration) {
+        let micros = latency.as_micros().min(u64::MAX as u128) as u64;
+        let bucket = (u64::BITS - micros.leading_zeros()) as usize;
+        self.buckets[bucket.min(Self::BUCKETS - 1)] += 1;
+        self.count += 1;
+        self.total += latency;
+        self.max = self.max.max(latency);
+    }
+
+    /// Returns the number of recorded latencies.
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Returns the highest recorded latency.
+    pub const fn max(&self) -> Duration {
+        self.max
+    }
+
+    /// Returns the mean of all recorded latencies.
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO
+        }
+        Duration::from_nanos((self.total.as_nanos() / self.count as u128) as u64)
+    }
+
+    /// Returns an upper bound of the given quantile, e.g. `0.99` for the 99th percentile.
+    pub fn quantile(&self, quantile: f64) -> Duration {
+        let target = (self.count as f64 * quantile.clamp(0.0, 1.0)).ceil() as u64;
+        let mut seen = 0;
+        for (bucket, count) in self.buckets.iter().enumerate() {
+            seen += count;
+            if seen >= target.max(1) {
+                return Duration::from_micros(1 << bucket).min(self.max)
+            }
+        }
+        self.max
+    }
+
+    /// Returns the number of latencies per bucket as `(upper bound, count)` pairs.
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(bucket, count)| (Duration::from_micros(1 << bucket), *count))
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "count {}, mean {:?}, p50 {:?}, p90 {:?}, p99 {:?}, max {:?}",
+            self.count,
+            self.mean(),
+            self.quantile(0.5),
+            self.quantile(0.9),
+            self.quantile(0.99),
+            self.max
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn histogram_quantiles() {
+        let mut histogram = LatencyHistogram::default();
+        assert_eq!(histogram.quantile(0.5), Duration::ZERO);
+
+        for micros in [1, 2, 3, 100, 1_000] {
+            histogram.record(Duration::from_micros(micros));
+        }
+        assert_eq!(histogram.count(), 5);
+        assert_eq!(histogram.max(), Duration::from_micros(1_000));
+        assert_eq!(histogram.quantile(0.5), Duration::from_micros(4));
+        assert_eq!(histogram.quantile(1.0), Duration::from_micros(1_000));
+    }
+
+    #[test]
+    fn record_serde() {
+        let record = PoolEventRecord {
+            elapsed_micros: 7,
+            event: RecordedPoolEvent::Mined { hash: TxHash::ZERO, block_hash: B256::ZERO },
+        };
+        let json = serde_json::to_string(&record).unwrap();
+        let decoded = read_records(json.as_bytes()).collect::<io::Result<Vec<_>>>().unwrap();
+        assert_eq!(decoded, vec![record]);
+    }
+}
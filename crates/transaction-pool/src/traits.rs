@@ -51,11 +51,13 @@
 //! - Conversion from consensus to pooled always fails
 
 use crate::{
+    admission::{AdmissionSource, SenderFilter},
     blobstore::BlobStoreError,
+    config::PoolLimits,
     error::{InvalidPoolTransactionError, PoolError, PoolResult},
     pool::{
-        state::SubPool, BestTransactionFilter, NewTransactionEvent, TransactionEvents,
-        TransactionListenerKind,
+        state::SubPool, BestTransactionFilter, NewTransactionEvent, PoolTransactionState,
+        PrivateTransactionStatus, TransactionEvents, TransactionListenerKind,
     },
     validate::ValidPoolTransaction,
     AddedTransactionOutcome, AllTransactionsEvents,
@@ -79,7 +81,7 @@ use reth_execution_types::ChangedAccount;
 use reth_primitives_traits::{Block, InMemorySize, Recovered, SealedBlock, SignedTransaction};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt,
     fmt::Debug,
     future::Future,
@@ -144,6 +146,21 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         self.add_transactions(TransactionOrigin::External, transactions)
     }
 
+    /// Imports all _external_ transactions received from the given [`AdmissionSource`].
+    ///
+    /// The source is evaluated by per-source admission policies, for example to rate limit
+    /// individual peers. By default the source is ignored.
+    ///
+    /// Consumer: P2P
+    fn add_external_transactions_from_source(
+        &self,
+        source: AdmissionSource,
+        transactions: Vec<Self::Transaction>,
+    ) -> impl Future<Output = Vec<PoolResult<AddedTransactionOutcome>>> + Send {
+        let _ = source;
+        self.add_external_transactions(transactions)
+    }
+
     /// Adds an _unvalidated_ transaction into the pool and subscribe to state changes.
     ///
     /// This is the same as [`TransactionPool::add_transaction`] but returns an event stream for the
@@ -190,6 +207,106 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
         transactions: Vec<(TransactionOrigin, Self::Transaction)>,
     ) -> impl Future<Output = Vec<PoolResult<AddedTransactionOutcome>>> + Send;
 
+    /// Adds an _unvalidated_ transaction received from the given [`AdmissionSource`] into the
+    /// pool.
+    ///
+    /// The source is evaluated by per-source admission policies, for example to rate limit
+    /// individual RPC clients. By default the source is ignored.
+    ///
+    /// Consumer: RPC
+    fn add_transaction_from_source(
+        &self,
+        source: AdmissionSource,
+        origin: TransactionOrigin,
+        transaction: Self::Transaction,
+    ) -> impl Future<Output = PoolResult<AddedTransactionOutcome>> + Send {
+        let _ = source;
+        self.add_transaction(origin, transaction)
+    }
+
+    /// Adds the given _unvalidated_ transactions into the pool, each paired with the
+    /// [`AdmissionSource`] it was received from and its own [`TransactionOrigin`].
+    ///
+    /// By default the sources are ignored.
+    ///
+    /// Returns a list of results.
+    ///
+    /// Consumer: RPC
+    fn add_transactions_from_sources(
+        &self,
+        transactions: Vec<(AdmissionSource, TransactionOrigin, Self::Transaction)>,
+    ) -> impl Future<Output = Vec<PoolResult<AddedTransactionOutcome>>> + Send {
+        self.add_transactions_with_origins(
+            transactions.into_iter().map(|(_, origin, tx)| (origin, tx)).collect(),
+        )
+    }
+
+    /// Adds an _unvalidated_ transaction into the pool as [`TransactionOrigin::Private`].
+    ///
+    /// Private transactions are never propagated to peers. If a `max_block_number` is given, the
+    /// transaction is evicted once a block with that number was added to the canonical chain
+    /// without including it.
+    ///
+    /// By default private transactions are not supported and rejected.
+    ///
+    /// Consumer: RPC
+    fn add_private_transaction(
+        &self,
+        transaction: Self::Transaction,
+        max_block_number: Option<u64>,
+    ) -> impl Future<Output = PoolResult<AddedTransactionOutcome>> + Send {
+        let _ = max_block_number;
+        let hash = *transaction.hash();
+        futures_util::future::ready(Err(PoolError::other(
+            hash,
+            "private transactions are not supported",
+        )))
+    }
+
+    /// Same as [`TransactionPool::add_private_transaction`], but with the [`AdmissionSource`] the
+    /// transaction was received from, which is evaluated by per-source admission policies.
+    ///
+    /// By default the source is ignored.
+    ///
+    /// Consumer: RPC
+    fn add_private_transaction_from_source(
+        &self,
+        source: AdmissionSource,
+        transaction: Self::Transaction,
+        max_block_number: Option<u64>,
+    ) -> impl Future<Output = PoolResult<AddedTransactionOutcome>> + Send {
+        let _ = source;
+        self.add_private_transaction(transaction, max_block_number)
+    }
+
+    /// Cancels a pending private transaction and removes it from the pool.
+    ///
+    /// Returns `false` if the transaction is not a pending private transaction.
+    ///
+    /// Consumer: RPC
+    fn cancel_private_transaction(&self, tx_hash: TxHash) -> bool {
+        let _ = tx_hash;
+        false
+    }
+
+    /// Returns the status of a transaction that was added with
+    /// [`TransactionPool::add_private_transaction`], if it is known.
+    ///
+    /// Consumer: RPC
+    fn private_transaction_status(&self, tx_hash: TxHash) -> Option<PrivateTransactionStatus> {
+        let _ = tx_hash;
+        None
+    }
+
+    /// Returns the current state of the transaction, e.g. the sub-pool it is in and why it is not
+    /// pending, if the transaction is in the pool.
+    ///
+    /// Consumer: RPC
+    fn transaction_state(&self, tx_hash: TxHash) -> Option<PoolTransactionState> {
+        let _ = tx_hash;
+        None
+    }
+
     /// Submit a consensus transaction directly to the pool
     fn add_consensus_transaction(
         &self,
@@ -456,6 +573,35 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Consumer: RPC
     fn all_transactions(&self) -> AllPoolTransactions<Self::Transaction>;
 
+    /// Returns the transactions of at most `limit` senders, skipping the first `offset` senders,
+    /// grouped like [`Self::all_transactions`].
+    ///
+    /// All transactions of a sender are on the same page. The order of the senders is
+    /// implementation specific but stable while senders are added. By default this pages over
+    /// [`Self::all_transactions`] with senders ordered by address.
+    ///
+    /// Consumer: RPC
+    fn transactions_page(
+        &self,
+        offset: usize,
+        limit: usize,
+    ) -> AllPoolTransactions<Self::Transaction> {
+        let AllPoolTransactions { pending, queued } = self.all_transactions();
+        let page = pending
+            .iter()
+            .chain(&queued)
+            .map(|tx| tx.sender())
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect::<BTreeSet<_>>();
+        AllPoolTransactions {
+            pending: pending.into_iter().filter(|tx| page.contains(&tx.sender())).collect(),
+            queued: queued.into_iter().filter(|tx| page.contains(&tx.sender())).collect(),
+        }
+    }
+
     /// Returns the _hashes_ of all transactions regardless of whether they can be propagated or
     /// not.
     ///
@@ -467,6 +613,29 @@ pub trait TransactionPool: Clone + Debug + Send + Sync {
     /// Consumer: Utility
     fn all_transaction_hashes(&self) -> Vec<TxHash>;
 
+    /// Returns a handle to the sender allow and deny lists of the pool, if the pool supports them.
+    ///
+    /// Updates through the returned handle take effect for all subsequently added transactions.
+    ///
+    /// Consumer: RPC
+    fn sender_filter(&self) -> Option<SenderFilter> {
+        None
+    }
+
+    /// Returns the size limits the pool currently enforces, if the pool supports changing them.
+    ///
+    /// Consumer: Utility
+    fn limits(&self) -> Option<PoolLimits> {
+        None
+    }
+
+    /// Replaces the size limits of the pool. Transactions exceeding the new limits are discarded.
+    ///
+    /// This has no effect if the pool doesn't support changing its limits, see [`Self::limits`].
+    ///
+    /// Consumer: Utility
+    fn set_limits(&self, _limits: PoolLimits) {}
+
     /// Removes a single transaction corresponding to the given hash.
     ///
     /// Note: This removes the transaction as if it got discarded (_not_ mined).
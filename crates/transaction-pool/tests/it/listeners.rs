@@ -1,6 +1,7 @@
 use assert_matches::assert_matches;
 use reth_transaction_pool::{
     noop::MockTransactionValidator,
+    pool::DiscardReason,
     test_utils::{MockTransactionFactory, TestPoolBuilder},
     FullTransactionEvent, PoolTransaction, TransactionEvent, TransactionListenerKind,
     TransactionOrigin, TransactionPool,
@@ -170,7 +171,11 @@ async fn txpool_listener_all() {
 
     assert_eq!(transaction.transaction.hash(), removed_txs[0].transaction.hash());
 
-    assert_matches!(all_tx_events.next().await, Some(FullTransactionEvent::Discarded(hash)) if hash == *transaction.transaction.get_hash());
+    assert_matches!(
+        all_tx_events.next().await,
+        Some(FullTransactionEvent::Discarded { tx_hash, reason: DiscardReason::Removed, .. })
+            if tx_hash == *transaction.transaction.get_hash()
+    );
 }
 
 #[tokio::test(flavor = "multi_thread")]
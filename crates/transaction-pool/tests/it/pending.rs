@@ -1,8 +1,11 @@
 use assert_matches::assert_matches;
 use reth_transaction_pool::{
-    test_utils::{MockTransactionFactory, TestPoolBuilder},
-    TransactionOrigin, TransactionPool,
+    admission::{AdmissionConfig, AdmissionError, AdmissionSource, SourceRateLimitConfig},
+    error::PoolErrorKind,
+    test_utils::{MockTransactionFactory, TestPool, TestPoolBuilder},
+    PoolConfig, TransactionOrigin, TransactionPool,
 };
+use std::net::IpAddr;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn txpool_new_pending_txs() {
@@ -23,3 +26,29 @@ async fn txpool_new_pending_txs() {
     assert_matches!(added_result, Ok(outcome) if outcome.hash == *transaction.transaction.get_hash());
     assert_matches!(best_txns.next(), Some(tx) if tx.transaction.get_hash() == transaction.transaction.get_hash());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_rate_limits_private_txs_by_source() {
+    let config = PoolConfig {
+        admission: AdmissionConfig {
+            source_rate_limit: Some(SourceRateLimitConfig::new(1)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let txpool: TestPool = TestPoolBuilder::default().with_config(config).into();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+    let client = AdmissionSource::Ip(IpAddr::from([10, 0, 0, 1]));
+
+    let transaction = mock_tx_factory.create_eip1559().transaction;
+    let added_result =
+        txpool.add_private_transaction_from_source(client, transaction.clone(), None).await;
+    assert_matches!(added_result, Ok(outcome) if outcome.hash == *transaction.get_hash());
+
+    let transaction = mock_tx_factory.create_eip1559().transaction;
+    let added_result = txpool.add_private_transaction_from_source(client, transaction, None).await;
+    assert_matches!(
+        added_result.unwrap_err().kind,
+        PoolErrorKind::AdmissionRejected(AdmissionError::RateLimited(source)) if source == client
+    );
+}
@@ -0,0 +1,113 @@
+use futures_util::{FutureExt, StreamExt};
+use reth_transaction_pool::{
+    blobstore::InMemoryBlobStore,
+    replay::{
+        read_records, Placement, PoolEventRecorder, PoolReplay, RecordedPoolEvent, ReplayNonces,
+        ReplayValidator,
+    },
+    test_utils::{testing_pool, MockOrdering, MockTransaction, MockTransactionFactory},
+    DiscardReason, Pool, TransactionOrigin, TransactionPool,
+};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_record_and_replay() {
+    let txpool = testing_pool();
+    let mut events = txpool.all_transactions_event_listener();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+
+    let first = mock_tx_factory.create_eip1559().transaction;
+    let second = first.next();
+    txpool.add_transaction(TransactionOrigin::External, first.clone()).await.unwrap();
+    txpool.add_transaction(TransactionOrigin::Local, second).await.unwrap();
+    txpool.remove_transactions(vec![*first.get_hash()]);
+
+    let mut recorder = PoolEventRecorder::new(Vec::new());
+    while let Some(Some(event)) = events.next().now_or_never() {
+        recorder.record_event(&txpool, &event).unwrap();
+    }
+    let recording = recorder.into_inner();
+
+    let records = read_records(recording.as_slice()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(matches!(records[0].event, RecordedPoolEvent::Head { .. }));
+    assert!(matches!(
+        records.last().unwrap().event,
+        RecordedPoolEvent::Discarded { reason: Some(DiscardReason::Removed), .. }
+    ));
+
+    let replay = PoolReplay::new(testing_pool());
+    let report = replay.run(records).await;
+    assert_eq!(report.added, 2);
+    assert_eq!(report.insert_latency.count(), 2);
+    assert_eq!(report.sizes.len(), 1);
+    // the removal is replayed and isn't an eviction
+    assert_eq!(report.removed, 1);
+    assert_eq!(report.final_size.total, 1);
+    assert!(report.evictions.recorded.is_empty());
+    assert!(report.evictions.replayed.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_replay_counts_blob_transactions() {
+    let txpool = testing_pool();
+    let mut events = txpool.all_transactions_event_listener();
+    let blob_tx = MockTransactionFactory::default().create_eip4844().transaction;
+    txpool.add_transaction(TransactionOrigin::External, blob_tx).await.unwrap();
+
+    let mut recorder = PoolEventRecorder::new(Vec::new());
+    while let Some(Some(event)) = events.next().now_or_never() {
+        recorder.record_event(&txpool, &event).unwrap();
+    }
+    let recording = recorder.into_inner();
+
+    let records = read_records(recording.as_slice()).collect::<Result<Vec<_>, _>>().unwrap();
+    assert!(matches!(records.last().unwrap().event, RecordedPoolEvent::Added { blob: true, .. }));
+
+    // recorded without the sidecar, so not replayed
+    let report = PoolReplay::new(testing_pool()).run(records).await;
+    assert_eq!(report.blob, 1);
+    assert_eq!(report.added, 0);
+    assert_eq!(report.final_size.total, 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn txpool_replay_reports_placement() {
+    let txpool = testing_pool();
+    let mut events = txpool.all_transactions_event_listener();
+    let mut mock_tx_factory = MockTransactionFactory::default();
+
+    let first = mock_tx_factory.create_eip1559().transaction;
+    let second = first.next();
+    txpool.add_transaction(TransactionOrigin::External, first.clone()).await.unwrap();
+    txpool.add_transaction(TransactionOrigin::External, second.clone()).await.unwrap();
+
+    let mut recorder = PoolEventRecorder::new(Vec::new());
+    while let Some(Some(event)) = events.next().now_or_never() {
+        recorder.record_event(&txpool, &event).unwrap();
+    }
+    let recording = recorder.into_inner();
+
+    // only replay the second transaction, which has a nonce gap in the replayed pool
+    let first_hash = *first.get_hash();
+    let records = read_records(recording.as_slice())
+        .map(Result::unwrap)
+        .filter(|record| {
+            !matches!(record.event, RecordedPoolEvent::Added { hash, .. } if hash == first_hash)
+        })
+        .collect::<Vec<_>>();
+
+    let nonces = ReplayNonces::default();
+    nonces.set_nonce(*first.get_sender(), *first.get_nonce());
+    let pool = Pool::new(
+        ReplayValidator::<MockTransaction>::new(nonces.clone()),
+        MockOrdering::default(),
+        InMemoryBlobStore::default(),
+        Default::default(),
+    );
+    let report = PoolReplay::new(pool).with_nonces(nonces).run(records).await;
+    assert_eq!(report.added, 1);
+    assert_eq!(report.final_size.queued, 1);
+    assert_eq!(
+        report.placement,
+        Placement { matching: 0, recorded_pending_only: 1, replayed_pending_only: 0 }
+    );
+}
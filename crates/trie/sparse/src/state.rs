@@ -143,6 +143,20 @@ impl<A, S> SparseStateTrie<A, S> {
     pub fn take_deferred_drops(&mut self) -> DeferredDrops {
         core::mem::take(&mut self.deferred_drops)
     }
+
+    /// Returns the hashed addresses of the hot accounts, whose leaves are retained by
+    /// [`Self::prune`].
+    #[cfg(feature = "std")]
+    pub fn hot_accounts(&self) -> impl Iterator<Item = B256> + '_ {
+        self.hot_accounts_lfu.keys().copied()
+    }
+
+    /// Returns the hashed addresses and slots of the hot storage slots, whose leaves are retained
+    /// by [`Self::prune`].
+    #[cfg(feature = "std")]
+    pub fn hot_slots(&self) -> impl Iterator<Item = (B256, B256)> + '_ {
+        self.hot_slots_lfu.keys().map(|key| (key.address, key.slot))
+    }
 }
 
 impl SparseStateTrie {
@@ -69,6 +69,10 @@ export const rethCliSidebar: SidebarItem = {
                     text: "reth db copy",
                     link: "/cli/reth/db/copy"
                 },
+                {
+                    text: "reth db backup",
+                    link: "/cli/reth/db/backup"
+                },
                 {
                     text: "reth db diff",
                     link: "/cli/reth/db/diff"
@@ -119,6 +123,10 @@ export const rethCliSidebar: SidebarItem = {
                     text: "reth db repair-trie",
                     link: "/cli/reth/db/repair-trie"
                 },
+                {
+                    text: "reth db verify",
+                    link: "/cli/reth/db/verify"
+                },
                 {
                     text: "reth db static-file-header",
                     link: "/cli/reth/db/static-file-header",
@@ -134,6 +142,17 @@ export const rethCliSidebar: SidebarItem = {
                         }
                     ]
                 },
+                {
+                    text: "reth db static-file",
+                    link: "/cli/reth/db/static-file",
+                    collapsed: true,
+                    items: [
+                        {
+                            text: "reth db static-file recompress",
+                            link: "/cli/reth/db/static-file/recompress"
+                        }
+                    ]
+                },
                 {
                     text: "reth db version",
                     link: "/cli/reth/db/version"
@@ -314,6 +333,59 @@ export const rethCliSidebar: SidebarItem = {
         {
             text: "reth re-execute",
             link: "/cli/reth/re-execute"
+        },
+        {
+            text: "reth debug",
+            link: "/cli/reth/debug",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth debug replay-engine",
+                    link: "/cli/reth/debug/replay-engine"
+                },
+                {
+                    text: "reth debug reproduce",
+                    link: "/cli/reth/debug/reproduce"
+                },
+                {
+                    text: "reth debug stateless",
+                    link: "/cli/reth/debug/stateless"
+                },
+                {
+                    text: "reth debug txpool-replay",
+                    link: "/cli/reth/debug/txpool-replay"
+                }
+            ]
+        },
+        {
+            text: "reth exex",
+            link: "/cli/reth/exex",
+            collapsed: true,
+            items: [
+                {
+                    text: "reth exex wal",
+                    link: "/cli/reth/exex/wal",
+                    collapsed: true,
+                    items: [
+                        {
+                            text: "reth exex wal list",
+                            link: "/cli/reth/exex/wal/list"
+                        },
+                        {
+                            text: "reth exex wal show",
+                            link: "/cli/reth/exex/wal/show"
+                        },
+                        {
+                            text: "reth exex wal export",
+                            link: "/cli/reth/exex/wal/export"
+                        },
+                        {
+                            text: "reth exex wal compact",
+                            link: "/cli/reth/exex/wal/compact"
+                        }
+                    ]
+                }
+            ]
         }
     ]
 };